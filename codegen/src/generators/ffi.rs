@@ -0,0 +1,51 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// Emit a minimal host-language binding for the plugin ABI
+/// (`on_event(source_id, seq_no) -> bool`) in `lang`.
+pub fn generate(lang: &str, output: &Path) -> Result<()> {
+    let (filename, contents) = match lang {
+        "go" => ("zenith_plugin.go", GO_TEMPLATE),
+        "python" => ("zenith_plugin.py", PYTHON_TEMPLATE),
+        "node" => ("zenith_plugin.js", NODE_TEMPLATE),
+        other => bail!("unsupported FFI target language '{other}'"),
+    };
+
+    fs::create_dir_all(output).with_context(|| format!("creating {output:?}"))?;
+    let path = output.join(filename);
+    fs::write(&path, contents).with_context(|| format!("writing {path:?}"))?;
+
+    Ok(())
+}
+
+const GO_TEMPLATE: &str = r#"package zenith
+
+// #cgo LDFLAGS: -lzenith_dataplane
+// extern int on_event(unsigned int source_id, unsigned long long seq_no);
+import "C"
+
+// OnEvent calls into the loaded Zenith plugin's on_event export.
+func OnEvent(sourceID uint32, seqNo uint64) bool {
+	return C.on_event(C.uint(sourceID), C.ulonglong(seqNo)) != 0
+}
+"#;
+
+const PYTHON_TEMPLATE: &str = r#"import ctypes
+
+_lib = ctypes.CDLL("libzenith_dataplane.so")
+_lib.on_event.argtypes = [ctypes.c_uint32, ctypes.c_uint64]
+_lib.on_event.restype = ctypes.c_int32
+
+
+def on_event(source_id: int, seq_no: int) -> bool:
+    return _lib.on_event(source_id, seq_no) != 0
+"#;
+
+const NODE_TEMPLATE: &str = r#"const koffi = require("koffi");
+
+const lib = koffi.load("libzenith_dataplane.so");
+const onEvent = lib.func("int on_event(uint32, uint64)");
+
+module.exports.onEvent = (sourceId, seqNo) => onEvent(sourceId, seqNo) !== 0;
+"#;
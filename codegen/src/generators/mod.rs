@@ -0,0 +1,4 @@
+pub mod ffi;
+pub mod plugin;
+pub mod schema;
+pub mod wit;
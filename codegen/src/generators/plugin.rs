@@ -0,0 +1,22 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::templates;
+
+/// Scaffold a new bare-module WASM plugin crate at `<output>/<name>`.
+pub fn generate(name: &str, output: &Path, ptype: &str) -> Result<()> {
+    let template = match ptype {
+        "filter" => templates::PLUGIN_FILTER_TEMPLATE,
+        other => bail!("unsupported plugin type '{other}': only 'filter' is implemented"),
+    };
+
+    let dir = output.join(name);
+    let src_dir = dir.join("src");
+    fs::create_dir_all(&src_dir).with_context(|| format!("creating {src_dir:?}"))?;
+
+    let lib_path = src_dir.join("lib.rs");
+    fs::write(&lib_path, template.trim_start()).with_context(|| format!("writing {lib_path:?}"))?;
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+#[derive(Deserialize)]
+struct SchemaSpec {
+    name: String,
+    fields: Vec<FieldSpec>,
+}
+
+#[derive(Deserialize)]
+struct FieldSpec {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+/// Generate a typed record definition for `input`'s JSON schema spec in `lang`.
+pub fn generate(input: &Path, lang: &str, output: &Path) -> Result<()> {
+    let raw = fs::read_to_string(input).with_context(|| format!("reading {input:?}"))?;
+    let spec: SchemaSpec = serde_json::from_str(&raw).with_context(|| format!("parsing {input:?}"))?;
+
+    let code = match lang {
+        "rust" => render_rust(&spec)?,
+        "python" => render_python(&spec)?,
+        other => bail!("unsupported schema target language '{other}'"),
+    };
+
+    fs::write(output, code).with_context(|| format!("writing {output:?}"))?;
+    Ok(())
+}
+
+fn render_rust(spec: &SchemaSpec) -> Result<String> {
+    let mut out = format!("#[derive(Debug, Clone)]\npub struct {} {{\n", spec.name);
+    for field in &spec.fields {
+        let rust_ty = match field.ty.as_str() {
+            "int32" => "i32",
+            "int64" => "i64",
+            "float32" => "f32",
+            "float64" => "f64",
+            "string" => "String",
+            "bool" => "bool",
+            other => bail!("unsupported field type '{other}' for rust output"),
+        };
+        out.push_str(&format!("    pub {}: {},\n", field.name, rust_ty));
+    }
+    out.push_str("}\n");
+    Ok(out)
+}
+
+fn render_python(spec: &SchemaSpec) -> Result<String> {
+    let mut out = "from dataclasses import dataclass\n\n\n".to_string();
+    out.push_str(&format!("@dataclass\nclass {}:\n", spec.name));
+    if spec.fields.is_empty() {
+        out.push_str("    pass\n");
+        return Ok(out);
+    }
+    for field in &spec.fields {
+        let py_ty = match field.ty.as_str() {
+            "int32" | "int64" => "int",
+            "float32" | "float64" => "float",
+            "string" => "str",
+            "bool" => "bool",
+            other => bail!("unsupported field type '{other}' for python output"),
+        };
+        out.push_str(&format!("    {}: {}\n", field.name, py_ty));
+    }
+    Ok(out)
+}
@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// Emits a `.wit` world describing the Zenith plugin ABI (KV host imports,
+/// `process`/`plugin-info`/`plugin-version` exports) plus a guest Rust
+/// skeleton built on `wit-bindgen`, so plugin authors get a contract the
+/// host and guest both compile against instead of hand-matching raw
+/// `extern "C"` pointer signatures.
+pub fn generate(name: &str, output: &Path) -> Result<()> {
+    let world = to_kebab_case(name);
+
+    let wit_dir = output.join("wit");
+    fs::create_dir_all(&wit_dir).with_context(|| format!("creating {wit_dir:?}"))?;
+    let wit_path = wit_dir.join(format!("{world}.wit"));
+    fs::write(&wit_path, render_wit(&world)).with_context(|| format!("writing {wit_path:?}"))?;
+
+    let src_dir = output.join("src");
+    fs::create_dir_all(&src_dir).with_context(|| format!("creating {src_dir:?}"))?;
+    let lib_path = src_dir.join("lib.rs");
+    fs::write(&lib_path, render_guest_skeleton(&world, name))
+        .with_context(|| format!("writing {lib_path:?}"))?;
+
+    Ok(())
+}
+
+fn to_kebab_case(name: &str) -> String {
+    name.chars()
+        .map(|c| if c == '_' || c == ' ' { '-' } else { c.to_ascii_lowercase() })
+        .collect()
+}
+
+fn render_wit(world: &str) -> String {
+    format!(
+        r#"package zenith:plugins;
+
+/// The world implemented by Zenith WASM plugins that want a typed,
+/// versioned contract instead of hand-matching raw `extern "C"` pointer
+/// signatures against the host's `PluginManager`.
+world {world} {{
+    /// Per-plugin namespaced key/value store backed by the host's `KvAPI`.
+    import zenith-kv-set: func(key: string, value: list<u8>);
+    import zenith-kv-get: func(key: string) -> option<list<u8>>;
+    import zenith-kv-delete: func(key: string) -> bool;
+
+    /// Process one input buffer and return the transformed output.
+    export process: func(input: list<u8>) -> list<u8>;
+
+    /// Human-readable plugin name/version string.
+    export plugin-info: func() -> string;
+
+    /// Monotonically increasing plugin ABI version.
+    export plugin-version: func() -> u32;
+}}
+"#
+    )
+}
+
+fn render_guest_skeleton(world: &str, name: &str) -> String {
+    format!(
+        r#"wit_bindgen::generate!({{
+    world: "{world}",
+    path: "wit/{world}.wit",
+}});
+
+struct Plugin;
+
+impl Guest for Plugin {{
+    // #[export] process: func(input: list<u8>) -> list<u8>
+    fn process(input: Vec<u8>) -> Vec<u8> {{
+        // TODO: implement plugin logic.
+        input
+    }}
+
+    // #[export] plugin-info: func() -> string
+    fn plugin_info() -> String {{
+        "{name} v0.1.0".to_string()
+    }}
+
+    // #[export] plugin-version: func() -> u32
+    fn plugin_version() -> u32 {{
+        1
+    }}
+}}
+
+export!(Plugin);
+
+// Host imports expand to plain functions, equivalent to:
+//   #[import] fn zenith_kv_set(key: string, value: list<u8>);
+//   #[import] fn zenith_kv_get(key: string) -> option<list<u8>>;
+//   #[import] fn zenith_kv_delete(key: string) -> bool;
+"#
+    )
+}
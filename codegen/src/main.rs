@@ -5,7 +5,7 @@ use std::path::PathBuf;
 mod generators;
 mod templates;
 
-use generators::{plugin, ffi, schema};
+use generators::{plugin, ffi, schema, wit};
 
 #[derive(Parser)]
 #[command(name = "zenith-codegen")]
@@ -48,15 +48,28 @@ enum Commands {
         /// Schema definition file (JSON)
         #[arg(short, long)]
         input: PathBuf,
-        
+
         /// Output language (rust, python)
         #[arg(short, long, default_value = "rust")]
         lang: String,
-        
+
         /// Output file
         #[arg(short, long)]
         output: PathBuf,
     },
+
+    /// Generate a WIT world for the plugin ABI, plus a matching wit-bindgen
+    /// guest skeleton, instead of hand-matching raw `extern "C"` signatures
+    Wit {
+        /// Plugin name (kebab-cased as the WIT world name, and used in the
+        /// guest's plugin-info string)
+        #[arg(short, long)]
+        name: String,
+
+        /// Output directory
+        #[arg(short, long, default_value = ".")]
+        output: PathBuf,
+    },
 }
 
 fn main() -> Result<()> {
@@ -75,6 +88,10 @@ fn main() -> Result<()> {
             schema::generate(&input, &lang, &output)?;
             println!("[OK] Schema code generated at {:?}", output);
         }
+        Commands::Wit { name, output } => {
+            wit::generate(&name, &output)?;
+            println!("[OK] WIT world '{}' generated at {:?}", name, output);
+        }
     }
 
     Ok(())
@@ -10,7 +10,9 @@ use tracing::info;
 use uuid::Uuid;
 
 mod models;
+mod one_or_vec;
 use models::*;
+use one_or_vec::OneOrVec;
 
 /// Application state
 #[derive(Clone)]
@@ -44,7 +46,8 @@ async fn main() {
         // Health & Info
         .route("/health", get(health_check))
         .route("/api/v1/info", get(get_info))
-        
+        .route("/metrics", get(metrics))
+
         // Node Management
         .route("/api/v1/nodes", get(list_nodes))
         .route("/api/v1/nodes", post(register_node))
@@ -102,21 +105,27 @@ async fn list_nodes(State(state): State<AppState>) -> Json<Vec<DataNode>> {
 
 async fn register_node(
     State(state): State<AppState>,
-    Json(req): Json<RegisterNodeRequest>,
-) -> Result<Json<DataNode>, StatusCode> {
-    let node = DataNode {
-        id: Uuid::new_v4().to_string(),
-        address: req.address,
-        capacity: req.capacity,
-        status: NodeStatus::Active,
-        registered_at: chrono::Utc::now(),
-    };
-
+    Json(req): Json<OneOrVec<RegisterNodeRequest>>,
+) -> Json<Vec<DataNode>> {
     let mut nodes = state.nodes.lock().unwrap();
-    nodes.insert(node.id.clone(), node.clone());
+    let created: Vec<DataNode> = req
+        .into_vec()
+        .into_iter()
+        .map(|req| {
+            let node = DataNode {
+                id: Uuid::new_v4().to_string(),
+                address: req.address,
+                capacity: req.capacity,
+                status: NodeStatus::Active,
+                registered_at: chrono::Utc::now(),
+            };
+            nodes.insert(node.id.clone(), node.clone());
+            info!("Registered node: {}", node.id);
+            node
+        })
+        .collect();
 
-    info!("Registered node: {}", node.id);
-    Ok(Json(node))
+    Json(created)
 }
 
 async fn get_node(
@@ -150,21 +159,27 @@ async fn list_plugins(State(state): State<AppState>) -> Json<Vec<Plugin>> {
 
 async fn register_plugin(
     State(state): State<AppState>,
-    Json(req): Json<RegisterPluginRequest>,
-) -> Result<Json<Plugin>, StatusCode> {
-    let plugin = Plugin {
-        id: Uuid::new_v4().to_string(),
-        name: req.name,
-        version: req.version,
-        wasm_url: req.wasm_url,
-        created_at: chrono::Utc::now(),
-    };
-
+    Json(req): Json<OneOrVec<RegisterPluginRequest>>,
+) -> Json<Vec<Plugin>> {
     let mut plugins = state.plugins.lock().unwrap();
-    plugins.insert(plugin.id.clone(), plugin.clone());
+    let created: Vec<Plugin> = req
+        .into_vec()
+        .into_iter()
+        .map(|req| {
+            let plugin = Plugin {
+                id: Uuid::new_v4().to_string(),
+                name: req.name,
+                version: req.version,
+                wasm_url: req.wasm_url,
+                created_at: chrono::Utc::now(),
+            };
+            plugins.insert(plugin.id.clone(), plugin.clone());
+            info!("Registered plugin: {}", plugin.id);
+            plugin
+        })
+        .collect();
 
-    info!("Registered plugin: {}", plugin.id);
-    Ok(Json(plugin))
+    Json(created)
 }
 
 async fn delete_plugin(
@@ -187,21 +202,27 @@ async fn list_deployments(State(state): State<AppState>) -> Json<Vec<Deployment>
 
 async fn create_deployment(
     State(state): State<AppState>,
-    Json(req): Json<CreateDeploymentRequest>,
-) -> Result<Json<Deployment>, StatusCode> {
-    let deployment = Deployment {
-        id: Uuid::new_v4().to_string(),
-        plugin_id: req.plugin_id,
-        node_ids: req.node_ids,
-        status: DeploymentStatus::Pending,
-        created_at: chrono::Utc::now(),
-    };
-
+    Json(req): Json<OneOrVec<CreateDeploymentRequest>>,
+) -> Json<Vec<Deployment>> {
     let mut deployments = state.deployments.lock().unwrap();
-    deployments.insert(deployment.id.clone(), deployment.clone());
+    let created: Vec<Deployment> = req
+        .into_vec()
+        .into_iter()
+        .map(|req| {
+            let deployment = Deployment {
+                id: Uuid::new_v4().to_string(),
+                plugin_id: req.plugin_id,
+                node_ids: req.node_ids,
+                status: DeploymentStatus::Pending,
+                created_at: chrono::Utc::now(),
+            };
+            deployments.insert(deployment.id.clone(), deployment.clone());
+            info!("Created deployment: {}", deployment.id);
+            deployment
+        })
+        .collect();
 
-    info!("Created deployment: {}", deployment.id);
-    Ok(Json(deployment))
+    Json(created)
 }
 
 async fn delete_deployment(
@@ -211,7 +232,69 @@ async fn delete_deployment(
     let mut deployments = state.deployments.lock().unwrap();
     deployments.remove(&id)
         .ok_or(StatusCode::NOT_FOUND)?;
-    
+
     info!("Deleted deployment: {}", id);
     Ok(StatusCode::NO_CONTENT)
 }
+
+// Metrics
+//
+// A small in-process registry so new gauges/counters (per-route request
+// counts, deregistration events, ...) can be added by pushing onto `metrics`
+// rather than hand-writing another render function.
+struct MetricRegistry {
+    metrics: Vec<(String, String)>,
+}
+
+impl MetricRegistry {
+    fn new() -> Self {
+        Self { metrics: Vec::new() }
+    }
+
+    fn gauge(&mut self, name: &str, value: impl std::fmt::Display) {
+        self.metrics.push((name.to_string(), value.to_string()));
+    }
+
+    fn gauge_with_labels(&mut self, name: &str, labels: &[(&str, &str)], value: impl std::fmt::Display) {
+        let label_str = labels
+            .iter()
+            .map(|(k, v)| format!("{k}=\"{v}\""))
+            .collect::<Vec<_>>()
+            .join(",");
+        self.metrics.push((format!("{name}{{{label_str}}}"), value.to_string()));
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for (name, value) in &self.metrics {
+            out.push_str(name);
+            out.push(' ');
+            out.push_str(value);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Prometheus/OpenMetrics text-format scrape endpoint.
+async fn metrics(State(state): State<AppState>) -> String {
+    let nodes = state.nodes.lock().unwrap();
+    let plugins = state.plugins.lock().unwrap();
+    let deployments = state.deployments.lock().unwrap();
+
+    let mut status_counts: HashMap<String, usize> = HashMap::new();
+    for d in deployments.values() {
+        *status_counts.entry(format!("{:?}", d.status).to_lowercase()).or_insert(0) += 1;
+    }
+
+    let mut registry = MetricRegistry::new();
+    registry.gauge("zenith_nodes_total", nodes.len());
+    registry.gauge("zenith_plugins_total", plugins.len());
+    registry.gauge("zenith_deployments_total", deployments.len());
+    registry.gauge("zenith_uptime_seconds", state.start_time.elapsed().as_secs());
+    for (status, count) in &status_counts {
+        registry.gauge_with_labels("zenith_deployments", &[("status", status)], count);
+    }
+
+    registry.render()
+}
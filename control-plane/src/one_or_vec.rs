@@ -0,0 +1,39 @@
+//! Accepts either a bare JSON object or a JSON array in the same request
+//! body shape, so a single POST route can onboard one item or a batch.
+
+use serde::{Deserialize, Deserializer};
+
+/// Deserializes from either a single `T` or a `Vec<T>`, always yielding a
+/// `Vec<T>`. Used on registration routes so a client can register one
+/// node/plugin/deployment with the existing single-object body, or many in
+/// one call with a JSON array, without two separate endpoints.
+#[derive(Debug, Clone)]
+pub struct OneOrVec<T>(pub Vec<T>);
+
+impl<'de, T> Deserialize<'de> for OneOrVec<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr<T> {
+            One(T),
+            Many(Vec<T>),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::One(item) => OneOrVec(vec![item]),
+            Repr::Many(items) => OneOrVec(items),
+        })
+    }
+}
+
+impl<T> OneOrVec<T> {
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+}
@@ -2,49 +2,313 @@ use crate::ring_buffer::ZenithRingBuffer;
 // use crate::event::ZenithEvent;
 use crate::wasm_host::{WasmHost, WasmPlugin};
 use crate::error::Result;
+use arrow::array::Array;
+use host_api::EventContext;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+/// Raw bytes backing each column of `event`'s `RecordBatch`, in column
+/// order - the same buffer access `wasm_host::PluginState::column_bytes`
+/// uses, reused here to feed [`host_api::event_context`] so
+/// `HostAPI::read_event_field`/`read_event_column` see the same data a
+/// plugin's `zenith_read` host call would.
+fn event_columns(event: &crate::event::ZenithEvent) -> Vec<Arc<[u8]>> {
+    let batch = event.batch();
+    (0..batch.num_columns())
+        .map(|i| {
+            let data = batch.column(i).to_data();
+            data.buffers()
+                .first()
+                .map(|b| Arc::from(b.as_slice()))
+                .unwrap_or_else(|| Arc::from(&[][..]))
+        })
+        .collect()
+}
+
+/// Dispatch a single event to every loaded plugin and forward/drop it based
+/// on their combined verdict.
+///
+/// Each plugin is invoked on its own scoped thread - safe because
+/// `WasmPlugin::on_event_with_batch` takes `&self` and synchronizes through
+/// its own `Mutex<Store>`, so distinct plugins never contend with each
+/// other. This is the fan-out/fan-in half of the worker pool: concurrent
+/// across plugins for one event, with verdicts reduced by logical AND once
+/// every thread has reported back. Bookkeeping that needs exclusive access
+/// (committing or rolling back a just-reloaded plugin) happens afterward,
+/// once all threads have joined.
+fn dispatch_event(event: crate::event::ZenithEvent, plugins: &Arc<Mutex<Vec<PluginEntry>>>) {
+    let mut plugin_list = plugins.lock().unwrap();
+    let (source_id, seq_no) = (event.header.source_id, event.header.seq_no);
+    let columns = event_columns(&event);
+    let event = Arc::new(event);
+
+    let results: Vec<Result<bool>> = thread::scope(|scope| {
+        let handles: Vec<_> = plugin_list
+            .iter()
+            .map(|entry| {
+                let columns = columns.clone();
+                let event = event.clone();
+                scope.spawn(move || {
+                    // Install the event so `HostAPI::read_event_field`/`read_event_column`
+                    // have something real to read for the duration of this call, then
+                    // clear it immediately after so a later, context-free host call can't
+                    // observe stale data. Thread-local, so each plugin's thread installs
+                    // its own copy without racing the others.
+                    host_api::event_context::install(EventContext::new(source_id, seq_no, columns));
+                    // Gives `HostAPI::get_random_u64`/`fill_random` a
+                    // per-plugin CSPRNG stream instead of one shared across
+                    // every loaded plugin - see `host_api::random_context`.
+                    host_api::random_context::install(entry.id.clone());
+
+                    // Pass metadata plus the batch itself so plugins can transform,
+                    // not just pass/block, the event.
+                    let result = entry.plugin.on_event_with_batch(source_id, seq_no, Some(event));
+
+                    host_api::random_context::clear();
+                    host_api::event_context::clear();
+                    result
+                })
+            })
+            .collect();
+
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let mut allowed = true;
+    for (entry, result) in plugin_list.iter_mut().zip(results) {
+        match result {
+            Ok(res) => {
+                if !res {
+                    allowed = false;
+                }
+                // The just-reloaded version survived its first
+                // dispatch: commit it and drop the fallback.
+                if entry.needs_validation {
+                    entry.needs_validation = false;
+                    entry.previous = None;
+                }
+            }
+            Err(e) => {
+                eprintln!("Plugin Execution Error: {}", e);
+                if entry.needs_validation {
+                    if let Some(previous) = entry.previous.take() {
+                        eprintln!(
+                            "[zenith] plugin '{}' trapped on its first event after reload; rolling back to the previous version",
+                            entry.id
+                        );
+                        entry.plugin = previous;
+                    }
+                    entry.needs_validation = false;
+                }
+            }
+        }
+    }
+
+    if allowed {
+        // println!("Event Processed: {}", event.header.seq_no);
+        // Logic to forward to storage/network would be here
+    } else {
+        // println!("Event Dropped: {}", event.header.seq_no);
+    }
+}
+
+/// Pin the calling worker thread to a NUMA node, chosen round-robin by
+/// `worker_id` over the nodes `zenith_runtime_cpu` discovers on this host.
+/// Best-effort: a discovery or affinity-syscall failure is surfaced to the
+/// caller to log, not treated as fatal, since an unpinned worker still runs
+/// correctly, just without the node-locality benefit.
+fn pin_worker_to_numa_node(worker_id: usize) -> std::result::Result<(), String> {
+    let topology = zenith_runtime_cpu::NumaTopology::discover().map_err(|e| e.to_string())?;
+    let num_nodes = topology.num_nodes().max(1);
+    let node_id = worker_id as u32 % num_nodes;
+    let cpus = topology
+        .cpus_for_node(node_id)
+        .ok_or_else(|| format!("no CPUs found for NUMA node {}", node_id))?;
+    let cpus: Vec<i32> = cpus.iter().map(|&c| c as i32).collect();
+    zenith_runtime_cpu::numa_ffi::bind_thread_to_cpus(&cpus).map_err(|e| format!("{:?}", e))
+}
+
+/// One slot in [`ZenithEngine`]'s plugin list. Wraps a [`WasmPlugin`] with
+/// the bookkeeping needed for a versioned hot-reload: `id` identifies the
+/// slot across reloads (the loading plugin's path, for plugins loaded from
+/// a directory the runtime watches; a generated id for anonymously-loaded
+/// ones), and `previous`/`needs_validation` implement atomic swap with
+/// rollback - see [`ZenithEngine::reload_plugin_with_capabilities`].
+pub struct PluginEntry {
+    id: String,
+    plugin: WasmPlugin,
+    /// The plugin this slot held before its most recent reload, kept only
+    /// until the new one proves itself (or doesn't) on its first dispatched
+    /// event. `None` once the new version is committed, or for a slot that
+    /// has never been reloaded.
+    previous: Option<WasmPlugin>,
+    /// Set by a reload that replaced an existing slot's plugin; cleared by
+    /// the consumer loop the next time this slot is dispatched, which is
+    /// also where a trap on that first call triggers rollback.
+    needs_validation: bool,
+}
+
+impl PluginEntry {
+    pub(crate) fn fresh(id: String, plugin: WasmPlugin) -> Self {
+        Self { id, plugin, previous: None, needs_validation: false }
+    }
+}
+
+/// Tunables for [`ZenithEngine`]'s consumer side: how many worker threads
+/// pull events off the ring buffer, and whether each one pins itself to a
+/// NUMA node.
+///
+/// `runtime::scheduler::{Scheduler, Priority}` cannot be reused here - the
+/// `runtime` crate already depends on `zenith_core`, so `core` depending
+/// back on `runtime` would be a cycle. This config, and the worker pool in
+/// [`ZenithEngine::start`], are a `core`-local equivalent built for the same
+/// purpose: fanning event dispatch out across threads instead of one
+/// sequential consumer. NUMA pinning reuses `zenith_runtime_cpu::NumaTopology`
+/// directly, which has no dependency on `zenith_core` and is safe to pull in.
+#[derive(Debug, Clone, Copy)]
+pub struct WorkerPoolConfig {
+    /// Number of consumer threads draining the ring buffer concurrently.
+    pub worker_count: usize,
+    /// Pin each worker thread to a NUMA node (round-robin by worker index),
+    /// keeping its wasmtime stores and the memory they touch node-local.
+    pub numa_pinning: bool,
+}
+
+impl Default for WorkerPoolConfig {
+    /// A single, unpinned worker - the pre-worker-pool behavior.
+    fn default() -> Self {
+        Self { worker_count: 1, numa_pinning: false }
+    }
+}
+
 pub struct ZenithEngine {
     buffer: ZenithRingBuffer,
     wasm_host: Arc<WasmHost>,
-    plugins: Arc<Mutex<Vec<WasmPlugin>>>,
+    plugins: Arc<Mutex<Vec<PluginEntry>>>,
     running: Arc<std::sync::atomic::AtomicBool>,
+    /// Source of generated ids for plugins loaded without one (e.g. via the
+    /// `zenith_load_plugin` FFI entry point), so every slot still has a
+    /// stable identity to reload against.
+    next_anonymous_id: std::sync::atomic::AtomicU64,
+    worker_pool: WorkerPoolConfig,
 }
 
 impl ZenithEngine {
     pub fn new(buffer_size: usize) -> Result<Self> {
+        Self::with_worker_pool(buffer_size, WorkerPoolConfig::default())
+    }
+
+    /// Like [`Self::new`], dispatching events to `worker_pool.worker_count`
+    /// concurrent consumer threads instead of one. Each event is still
+    /// fanned out to every loaded plugin and the event is allowed only if
+    /// every plugin's verdict is `true` - see [`Self::start`].
+    pub fn with_worker_pool(buffer_size: usize, worker_pool: WorkerPoolConfig) -> Result<Self> {
+        Self::from_wasm_host(buffer_size, WasmHost::new()?, worker_pool)
+    }
+
+    /// Like [`Self::new`], caching compiled plugins under `cache_dir`
+    /// instead of recompiling them on every load - see
+    /// [`WasmHost::with_cache`]. Pair with [`Self::precompile_dir`] to warm
+    /// the cache ahead of time.
+    pub fn with_cache_dir(buffer_size: usize, cache_dir: impl Into<std::path::PathBuf>) -> Result<Self> {
+        Self::from_wasm_host(buffer_size, WasmHost::with_cache(cache_dir)?, WorkerPoolConfig::default())
+    }
+
+    fn from_wasm_host(buffer_size: usize, wasm_host: WasmHost, worker_pool: WorkerPoolConfig) -> Result<Self> {
         Ok(Self {
             buffer: ZenithRingBuffer::new(buffer_size),
-            wasm_host: Arc::new(WasmHost::new()?),
+            wasm_host: Arc::new(wasm_host),
             plugins: Arc::new(Mutex::new(Vec::new())),
             running: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+            next_anonymous_id: std::sync::atomic::AtomicU64::new(0),
+            worker_pool,
         })
     }
 
+    /// Compiles and caches every `.wasm` file in `dir` ahead of time; see
+    /// [`WasmHost::precompile_dir`]. Requires this engine to have been
+    /// built with [`Self::with_cache_dir`].
+    pub fn precompile_dir(&self, dir: impl AsRef<std::path::Path>) -> Result<usize> {
+        self.wasm_host.precompile_dir(dir)
+    }
+
     pub fn get_ring_buffer(&self) -> ZenithRingBuffer {
         self.buffer.clone()
     }
 
+    fn next_anonymous_id(&self) -> String {
+        let n = self.next_anonymous_id.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        format!("anon-{n}")
+    }
+
     pub fn load_plugin(&self, wasm_bytes: &[u8]) -> Result<()> {
         let plugin = self.wasm_host.load_plugin(wasm_bytes)?;
         let mut plugins = self.plugins.lock().unwrap();
-        plugins.push(plugin);
+        plugins.push(PluginEntry::fresh(self.next_anonymous_id(), plugin));
         Ok(())
     }
 
-    pub fn start(&self) {
-        let buffer = self.buffer.clone();
-        let running = self.running.clone();
-        let plugins = self.plugins.clone(); 
+    /// Like [`Self::load_plugin`], but gates the plugin's host calls to
+    /// `capabilities` instead of the unrestricted default. See
+    /// [`WasmHost::load_plugin_with_capabilities`].
+    pub fn load_plugin_with_capabilities(
+        &self,
+        wasm_bytes: &[u8],
+        capabilities: Arc<host_api::Capabilities>,
+    ) -> Result<()> {
+        let plugin = self.wasm_host.load_plugin_with_capabilities(wasm_bytes, capabilities)?;
+        let mut plugins = self.plugins.lock().unwrap();
+        plugins.push(PluginEntry::fresh(self.next_anonymous_id(), plugin));
+        Ok(())
+    }
 
+    /// Loads or hot-reloads the plugin identified by `id` (the runtime's
+    /// hot-reload watcher uses the plugin's source path).
+    ///
+    /// The new plugin is compiled before anything about the existing slot
+    /// is touched, so a plugin that fails to compile never disturbs a
+    /// running one. If `id` names an existing slot, its current plugin
+    /// becomes `previous` and is kept live until the new version either
+    /// completes its first dispatched event successfully (committed,
+    /// `previous` dropped) or traps on it (rolled back to `previous`, with
+    /// the failure logged) - see the consumer loop in [`Self::start`]. If
+    /// `id` is new, the plugin is simply loaded with nothing to roll back
+    /// to.
+    pub fn reload_plugin_with_capabilities(
+        &self,
+        id: impl Into<String>,
+        wasm_bytes: &[u8],
+        capabilities: Arc<host_api::Capabilities>,
+    ) -> Result<()> {
+        let id = id.into();
+        let new_plugin = self.wasm_host.load_plugin_with_capabilities(wasm_bytes, capabilities)?;
+
+        let mut plugins = self.plugins.lock().unwrap();
+        match plugins.iter_mut().find(|entry| entry.id == id) {
+            Some(entry) => {
+                // Opt-in: only runs if the plugin exports the relevant
+                // `zenith_state_schema_version`/`zenith_serialize_state`/
+                // `zenith_deserialize_state` functions. See
+                // `WasmPlugin::migrate_state_from`.
+                new_plugin.migrate_state_from(&entry.plugin, &id);
+                let old_plugin = std::mem::replace(&mut entry.plugin, new_plugin);
+                entry.previous = Some(old_plugin);
+                entry.needs_validation = true;
+            }
+            None => plugins.push(PluginEntry::fresh(id, new_plugin)),
+        }
+        Ok(())
+    }
+
+    pub fn start(&self) {
         // Start Admin API
         let admin_state = crate::admin_api::AdminState {
             buffer: self.buffer.clone(),
             plugins: self.plugins.clone(),
+            metrics: Arc::new(crate::metrics::MetricsRegistry::new()),
         };
-        
+
         thread::spawn(move || {
             let rt = tokio::runtime::Builder::new_current_thread()
                 .enable_all()
@@ -53,35 +317,32 @@ impl ZenithEngine {
             rt.block_on(crate::admin_api::start_admin_server(admin_state, 8080));
         });
 
-        thread::spawn(move || {
-            println!("Zenith Core Engine: Consumer thread started.");
-            while running.load(std::sync::atomic::Ordering::Relaxed) {
-                if let Some(event) = buffer.pop() {
-                    // Process event
-                    let plugin_list = plugins.lock().unwrap();
-                    let mut allowed = true;
-                    
-                    for plugin in plugin_list.iter() {
-                        // Pass metadata to WASM
-                        match plugin.on_event(event.header.source_id, event.header.seq_no) {
-                            Ok(res) => {
-                                if !res { allowed = false; }
-                            },
-                            Err(e) => eprintln!("Plugin Execution Error: {}", e),
-                        }
+        let worker_pool = self.worker_pool;
+        for worker_id in 0..worker_pool.worker_count.max(1) {
+            let buffer = self.buffer.clone();
+            let running = self.running.clone();
+            let plugins = self.plugins.clone();
+
+            thread::spawn(move || {
+                if worker_pool.numa_pinning {
+                    if let Err(e) = pin_worker_to_numa_node(worker_id) {
+                        eprintln!(
+                            "[zenith] worker {} failed to pin to a NUMA node: {}",
+                            worker_id, e
+                        );
                     }
+                }
 
-                    if allowed {
-                         // println!("Event Processed: {}", event.header.seq_no);
-                         // Logic to forward to storage/network would be here
+                println!("Zenith Core Engine: Consumer worker {} started.", worker_id);
+                while running.load(std::sync::atomic::Ordering::Relaxed) {
+                    if let Some(event) = buffer.pop() {
+                        dispatch_event(event, &plugins);
                     } else {
-                         // println!("Event Dropped: {}", event.header.seq_no);
+                        thread::park_timeout(Duration::from_micros(10));
                     }
-                } else {
-                    thread::park_timeout(Duration::from_micros(10));
                 }
-            }
-        });
+            });
+        }
     }
 
     pub fn shutdown(&self) {
@@ -142,7 +403,56 @@ mod tests {
         // This should return an error, not Ok(())
         assert!(result.is_err(), "Invalid WASM should fail to load");
     }
-    
+
+    #[test]
+    fn test_reload_plugin_with_capabilities_replaces_existing_slot() {
+        let engine = ZenithEngine::new(1024).unwrap();
+        let minimal_wasm: &[u8] = &[
+            0x00, 0x61, 0x73, 0x6D, // WASM magic number
+            0x01, 0x00, 0x00, 0x00, // Version 1
+        ];
+        let caps = || Arc::new(host_api::Capabilities::all());
+
+        engine.reload_plugin_with_capabilities("plugin-a", minimal_wasm, caps()).unwrap();
+        assert_eq!(engine.plugins.lock().unwrap().len(), 1);
+
+        // Reloading the same id replaces the slot in place rather than
+        // appending, and marks it pending first-event validation.
+        engine.reload_plugin_with_capabilities("plugin-a", minimal_wasm, caps()).unwrap();
+        {
+            let plugins = engine.plugins.lock().unwrap();
+            assert_eq!(plugins.len(), 1, "reloading a known id should replace, not append");
+            assert!(plugins[0].needs_validation, "a freshly-reloaded slot awaits first-event validation");
+            assert!(plugins[0].previous.is_some(), "the previous version should be kept until validated");
+        }
+
+        // A distinct id is a new slot, independent of the first.
+        engine.reload_plugin_with_capabilities("plugin-b", minimal_wasm, caps()).unwrap();
+        assert_eq!(engine.plugins.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_worker_pool_config_default_is_single_unpinned_worker() {
+        let config = WorkerPoolConfig::default();
+        assert_eq!(config.worker_count, 1);
+        assert!(!config.numa_pinning);
+    }
+
+    #[test]
+    fn test_with_worker_pool_spawns_multiple_consumer_threads() {
+        let config = WorkerPoolConfig { worker_count: 4, numa_pinning: false };
+        let engine = ZenithEngine::with_worker_pool(1024, config).unwrap();
+
+        engine.start();
+        thread::sleep(Duration::from_millis(50));
+        assert!(engine.running.load(std::sync::atomic::Ordering::Relaxed),
+            "engine should still be running with a multi-worker pool");
+
+        engine.shutdown();
+        thread::sleep(Duration::from_millis(20));
+        assert!(!engine.running.load(std::sync::atomic::Ordering::Relaxed));
+    }
+
     #[test]
     fn test_engine_multiple_operations() {
         let engine = ZenithEngine::new(1024).unwrap();
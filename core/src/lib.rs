@@ -4,6 +4,7 @@ pub mod engine;
 pub mod wasm_host;
 pub mod error;
 pub mod admin_api;
+pub mod metrics;
 pub mod validation;
 
 use std::ffi::c_void;
@@ -0,0 +1,182 @@
+//! Prometheus Metrics Registry
+//!
+//! Hand-rolled text-format metrics for the Admin API, exposed on `/metrics`
+//! so operators can scrape Zenith the same way they would an S3 front-end:
+//! per-route request counters, an error counter, and a request-duration
+//! histogram, plus runtime gauges for ring-buffer occupancy and loaded
+//! plugin count.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Upper bounds (seconds) of the request-duration histogram buckets.
+const DURATION_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0];
+
+/// Per-route request-duration histogram with fixed Prometheus-style buckets.
+#[derive(Default)]
+struct Histogram {
+    bucket_counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            bucket_counts: vec![0; DURATION_BUCKETS.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, seconds: f64) {
+        for (bound, bucket) in DURATION_BUCKETS.iter().zip(self.bucket_counts.iter_mut()) {
+            if seconds <= *bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+/// Thread-safe registry of counters and histograms for the Admin API.
+///
+/// Shared behind an `Arc` across every request handled by `start_admin_server`
+/// and rendered to Prometheus text format on demand by `/metrics`.
+pub struct MetricsRegistry {
+    requests_total: Mutex<HashMap<(String, String, u16), u64>>,
+    errors_total: Mutex<HashMap<(String, String), u64>>,
+    request_duration: Mutex<HashMap<(String, String), Histogram>>,
+}
+
+impl MetricsRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self {
+            requests_total: Mutex::new(HashMap::new()),
+            errors_total: Mutex::new(HashMap::new()),
+            request_duration: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record one completed request: increments the request counter and
+    /// the duration histogram for `(method, route)`.
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, seconds: f64) {
+        let key = (method.to_string(), route.to_string(), status);
+        *self.requests_total.lock().unwrap().entry(key).or_insert(0) += 1;
+
+        let mut histograms = self.request_duration.lock().unwrap();
+        histograms
+            .entry((method.to_string(), route.to_string()))
+            .or_insert_with(Histogram::new)
+            .observe(seconds);
+    }
+
+    /// Increment the error counter for `(method, route)`.
+    pub fn inc_error(&self, method: &str, route: &str) {
+        let key = (method.to_string(), route.to_string());
+        *self.errors_total.lock().unwrap().entry(key).or_insert(0) += 1;
+    }
+
+    /// Render the registry plus the given runtime gauges as Prometheus
+    /// exposition text format.
+    pub fn render(&self, buffer_len: usize, plugin_count: usize) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP zenith_admin_requests_total Total Admin API requests by method, route and status\n");
+        out.push_str("# TYPE zenith_admin_requests_total counter\n");
+        for ((method, route, status), count) in self.requests_total.lock().unwrap().iter() {
+            out.push_str(&format!(
+                "zenith_admin_requests_total{{method=\"{method}\",route=\"{route}\",status=\"{status}\"}} {count}\n"
+            ));
+        }
+
+        out.push_str("# HELP zenith_admin_errors_total Total Admin API requests that returned a 4xx/5xx status\n");
+        out.push_str("# TYPE zenith_admin_errors_total counter\n");
+        for ((method, route), count) in self.errors_total.lock().unwrap().iter() {
+            out.push_str(&format!(
+                "zenith_admin_errors_total{{method=\"{method}\",route=\"{route}\"}} {count}\n"
+            ));
+        }
+
+        out.push_str("# HELP zenith_admin_request_duration_seconds Admin API request duration in seconds\n");
+        out.push_str("# TYPE zenith_admin_request_duration_seconds histogram\n");
+        for ((method, route), hist) in self.request_duration.lock().unwrap().iter() {
+            for (bound, bucket_count) in DURATION_BUCKETS.iter().zip(hist.bucket_counts.iter()) {
+                out.push_str(&format!(
+                    "zenith_admin_request_duration_seconds_bucket{{method=\"{method}\",route=\"{route}\",le=\"{bound}\"}} {bucket_count}\n"
+                ));
+            }
+            out.push_str(&format!(
+                "zenith_admin_request_duration_seconds_bucket{{method=\"{method}\",route=\"{route}\",le=\"+Inf\"}} {}\n",
+                hist.count
+            ));
+            out.push_str(&format!(
+                "zenith_admin_request_duration_seconds_sum{{method=\"{method}\",route=\"{route}\"}} {}\n",
+                hist.sum
+            ));
+            out.push_str(&format!(
+                "zenith_admin_request_duration_seconds_count{{method=\"{method}\",route=\"{route}\"}} {}\n",
+                hist.count
+            ));
+        }
+
+        out.push_str("# HELP zenith_ring_buffer_len Current number of events queued in the ring buffer\n");
+        out.push_str("# TYPE zenith_ring_buffer_len gauge\n");
+        out.push_str(&format!("zenith_ring_buffer_len {buffer_len}\n"));
+
+        out.push_str("# HELP zenith_loaded_plugins Current number of loaded WASM plugins\n");
+        out.push_str("# TYPE zenith_loaded_plugins gauge\n");
+        out.push_str(&format!("zenith_loaded_plugins {plugin_count}\n"));
+
+        out
+    }
+}
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_registry_renders_gauges() {
+        let registry = MetricsRegistry::new();
+        let rendered = registry.render(3, 1);
+        assert!(rendered.contains("zenith_ring_buffer_len 3"));
+        assert!(rendered.contains("zenith_loaded_plugins 1"));
+    }
+
+    #[test]
+    fn test_observe_request_increments_counter_and_histogram() {
+        let registry = MetricsRegistry::new();
+        registry.observe_request("GET", "/status", 200, 0.002);
+        registry.observe_request("GET", "/status", 200, 0.002);
+
+        let rendered = registry.render(0, 0);
+        assert!(rendered.contains(
+            "zenith_admin_requests_total{method=\"GET\",route=\"/status\",status=\"200\"} 2"
+        ));
+        assert!(rendered.contains(
+            "zenith_admin_request_duration_seconds_count{method=\"GET\",route=\"/status\"} 2"
+        ));
+    }
+
+    #[test]
+    fn test_inc_error_increments_error_counter() {
+        let registry = MetricsRegistry::new();
+        registry.inc_error("GET", "/plugins");
+        registry.inc_error("GET", "/plugins");
+        registry.inc_error("GET", "/plugins");
+
+        let rendered = registry.render(0, 0);
+        assert!(rendered.contains(
+            "zenith_admin_errors_total{method=\"GET\",route=\"/plugins\"} 3"
+        ));
+    }
+}
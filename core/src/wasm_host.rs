@@ -1,65 +1,712 @@
 // WasmHost implementation
-use wasmtime::{Engine, Linker, Module, Store, Config};
+use wasmtime::component::{bindgen, Component, Linker as ComponentLinker};
+use wasmtime::{Engine, Linker, Memory, Module, Store, Config, Trap};
 use wasmtime_wasi::{WasiCtx, WasiCtxBuilder};
 use crate::error::Result;
+use crate::event::ZenithEvent;
+use arrow::array::Array;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 
-pub struct WasmPlugin {
-    store: Arc<Mutex<Store<WasiCtx>>>,
-    instance: wasmtime::Instance,
+bindgen!({
+    path: "wit/dataplane.wit",
+    world: "dataplane-plugin",
+});
+
+/// The layer value (bytes 6-7 of the preamble, after `\0asm` and the
+/// two-byte version) that distinguishes a Component-Model binary from a
+/// plain core module.
+const COMPONENT_MODEL_LAYER: u16 = 1;
+
+fn is_component_binary(bytes: &[u8]) -> bool {
+    // Both core modules and components start with the `\0asm` magic and a
+    // two-byte version; components additionally set the high half of the
+    // following two bytes (the "layer") to 1. See the binary component spec.
+    bytes.len() >= 8
+        && &bytes[0..4] == b"\0asm"
+        && u16::from_le_bytes([bytes[6], bytes[7]]) == COMPONENT_MODEL_LAYER
+}
+
+/// Per-store state threaded through the `Linker`. Bundles the WASI context
+/// required by `wasmtime_wasi` with the in-flight event so host functions can
+/// resolve guest calls like `zenith_read` against the batch currently being
+/// processed by `on_event`.
+struct PluginState {
+    wasi: WasiCtx,
+    /// The event passed to the current `on_event` call, if any. Cleared
+    /// between calls so host functions can't see stale data.
+    event: Option<Arc<ZenithEvent>>,
+    /// Column selected by the most recent `zenith_get_column_ptr` call.
+    selected_column: Option<usize>,
+    /// Buffer captured from the guest's most recent `zenith_emit` call.
+    emitted: Option<Vec<u8>>,
+}
+
+impl PluginState {
+    fn column_bytes(&self, col: usize) -> Option<&[u8]> {
+        let event = self.event.as_ref()?;
+        let array = event.batch().column(col);
+        let data = array.to_data();
+        data.buffers().first().map(|b| b.as_slice())
+    }
+}
+
+/// Registers the `zenith_*` host imports that give guest plugins read access
+/// to the `RecordBatch` behind the current event, and a way to hand
+/// transformed bytes back to the host.
+fn add_host_functions(linker: &mut Linker<PluginState>) -> Result<()> {
+    linker.func_wrap(
+        "env",
+        "zenith_get_column_ptr",
+        |mut caller: wasmtime::Caller<'_, PluginState>, col: i32| -> i32 {
+            let col = col as usize;
+            let valid = caller.data().column_bytes(col).is_some();
+            if valid {
+                caller.data_mut().selected_column = Some(col);
+                col as i32
+            } else {
+                -1
+            }
+        },
+    )?;
+
+    linker.func_wrap(
+        "env",
+        "zenith_column_len",
+        |caller: wasmtime::Caller<'_, PluginState>, col: i32| -> i32 {
+            caller
+                .data()
+                .column_bytes(col as usize)
+                .map(|b| b.len() as i32)
+                .unwrap_or(-1)
+        },
+    )?;
+
+    linker.func_wrap(
+        "env",
+        "zenith_read",
+        |mut caller: wasmtime::Caller<'_, PluginState>, offset: i32, len: i32, dest: i32| -> i32 {
+            let Some(col) = caller.data().selected_column else {
+                return -1;
+            };
+            let Some(bytes) = caller.data().column_bytes(col) else {
+                return -1;
+            };
+            let (offset, len) = (offset as usize, len as usize);
+            if offset.checked_add(len).map_or(true, |end| end > bytes.len()) {
+                return -1;
+            }
+            let chunk = bytes[offset..offset + len].to_vec();
+
+            let Some(memory) = caller.get_export("memory").and_then(|e| e.into_memory()) else {
+                return -1;
+            };
+            if memory.write(&mut caller, dest as usize, &chunk).is_err() {
+                return -1;
+            }
+            len as i32
+        },
+    )?;
+
+    linker.func_wrap(
+        "env",
+        "zenith_emit",
+        |mut caller: wasmtime::Caller<'_, PluginState>, ptr: i32, len: i32| -> i32 {
+            let memory: Memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                Some(m) => m,
+                None => return -1,
+            };
+            let mut buf = vec![0u8; len as usize];
+            if memory.read(&caller, ptr as usize, &mut buf).is_err() {
+                return -1;
+            }
+            caller.data_mut().emitted = Some(buf);
+            0
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Default fuel budget granted to a plugin instance before it is considered
+/// a runaway and interrupted. Roughly bounds the number of WASM instructions
+/// a single `on_event` call may execute.
+pub const DEFAULT_FUEL_BUDGET: u64 = 10_000_000;
+
+/// How often the epoch ticker increments the engine epoch. Combined with
+/// `EPOCH_DEADLINE_TICKS`, this bounds the wall-clock time a plugin call may
+/// run before being interrupted, independent of fuel consumption.
+const EPOCH_TICK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Number of epoch ticks a plugin call is allowed before it is interrupted.
+const EPOCH_DEADLINE_TICKS: u64 = 20; // ~1s wall-clock at the tick interval above
+
+/// A loaded plugin, backed by either the legacy core-module ABI (bare
+/// `on_event(i32,i64)->i32` export) or the typed Component Model world
+/// defined in `wit/dataplane.wit`.
+pub enum WasmPlugin {
+    Module {
+        store: Arc<Mutex<Store<PluginState>>>,
+        instance: wasmtime::Instance,
+        fuel_budget: u64,
+        capabilities: Arc<host_api::Capabilities>,
+    },
+    Component {
+        store: Arc<Mutex<Store<PluginState>>>,
+        bindings: DataplanePlugin,
+        fuel_budget: u64,
+        capabilities: Arc<host_api::Capabilities>,
+    },
 }
 
 pub struct WasmHost {
     engine: Engine,
-    linker: Linker<WasiCtx>,
+    linker: Linker<PluginState>,
+    component_linker: ComponentLinker<PluginState>,
+    fuel_budget: u64,
+    /// When set, every store built by this host is configured for
+    /// bit-reproducible execution (see [`WasmHost::deterministic`]).
+    deterministic: bool,
+    /// When set, compiled modules/components are cached on disk keyed by a
+    /// blake3 hash of the plugin bytes plus a fingerprint of this host's
+    /// `Config`, so reloading the same plugin (across a process restart or a
+    /// `zenith_load_plugin` hot-reload) skips recompilation.
+    cache_dir: Option<PathBuf>,
+    /// Fingerprint of the `Config` used to build `engine`. Serialized
+    /// artifacts are only valid for the exact config that produced them, so
+    /// this is folded into the cache key to avoid loading incompatible
+    /// precompiled code after a config change.
+    config_fingerprint: u64,
 }
 
 impl WasmHost {
+    /// Create a host with the default fuel budget (see [`DEFAULT_FUEL_BUDGET`]).
     pub fn new() -> Result<Self> {
-        let config = Config::new();
-        // config.wasm_component_model(true); // Disable for basic module
-        
+        Self::with_fuel_budget(DEFAULT_FUEL_BUDGET)
+    }
+
+    /// Create a host whose loaded plugins are metered with `fuel_budget` units
+    /// of fuel per `on_event` call, and interrupted if they run past the
+    /// epoch deadline regardless of remaining fuel.
+    pub fn with_fuel_budget(fuel_budget: u64) -> Result<Self> {
+        Self::build(fuel_budget, false, None)
+    }
+
+    /// Create a host that caches compiled plugins under `dir`, keyed by a
+    /// blake3 hash of the plugin bytes plus this host's config fingerprint.
+    /// Loading a plugin already present in the cache skips compilation
+    /// entirely via `Module::deserialize`/`Component::deserialize`.
+    pub fn with_cache(dir: impl Into<PathBuf>) -> Result<Self> {
+        Self::build(DEFAULT_FUEL_BUDGET, false, Some(dir.into()))
+    }
+
+    /// Create a host whose plugin executions are reproducible bit-for-bit
+    /// across hosts: NaN payloads are canonicalized, WASI clocks/random are
+    /// not wired up (guests have no non-deterministic WASI source to read),
+    /// and the optimization pipeline is pinned so codegen doesn't vary by
+    /// machine. Given identical plugin bytes and input batches, `on_event`
+    /// results and emitted buffers are byte-for-byte equal on any node.
+    pub fn deterministic() -> Result<Self> {
+        Self::deterministic_with_fuel_budget(DEFAULT_FUEL_BUDGET)
+    }
+
+    /// Like [`WasmHost::deterministic`], with a caller-chosen fuel budget.
+    pub fn deterministic_with_fuel_budget(fuel_budget: u64) -> Result<Self> {
+        Self::build(fuel_budget, true, None)
+    }
+
+    fn build(fuel_budget: u64, deterministic: bool, cache_dir: Option<PathBuf>) -> Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        config.epoch_interruption(true);
+        config.wasm_component_model(true);
+
+        if deterministic {
+            // Force a single canonical NaN bit pattern instead of whatever the
+            // host FPU happens to produce, and pin codegen so two nodes
+            // compiling the same bytes emit identical semantics.
+            config.cranelift_nan_canonicalization(true);
+            config.cranelift_opt_level(wasmtime::OptLevel::Speed);
+        }
+
+        // A fingerprint of the config knobs that affect compiled-artifact
+        // compatibility; folded into the AOT cache key below so a config
+        // change (e.g. toggling `deterministic`) can't load stale/incompatible
+        // precompiled code.
+        let config_fingerprint = {
+            use std::hash::{Hash, Hasher};
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            deterministic.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        if let Some(dir) = &cache_dir {
+            std::fs::create_dir_all(dir)?;
+        }
+
         let engine = Engine::new(&config)?;
         let mut linker = Linker::new(&engine);
-        wasmtime_wasi::add_to_linker(&mut linker, |s| s)?;
+        wasmtime_wasi::add_to_linker(&mut linker, |s: &mut PluginState| &mut s.wasi)?;
+        add_host_functions(&mut linker)?;
+        let component_linker = ComponentLinker::new(&engine);
+
+        // Background ticker drives the wall-clock deadline for every store
+        // created from this engine; it is independent of fuel so a plugin
+        // that never yields back to the host (e.g. a tight host-call-free
+        // loop) is still interrupted.
+        let ticker_engine = engine.clone();
+        thread::spawn(move || loop {
+            thread::sleep(EPOCH_TICK_INTERVAL);
+            ticker_engine.increment_epoch();
+        });
 
         Ok(Self {
             engine,
             linker,
+            component_linker,
+            fuel_budget,
+            deterministic,
+            cache_dir,
+            config_fingerprint,
         })
     }
 
+    /// Path the given plugin bytes would be cached at, if this host has a
+    /// cache directory configured.
+    fn cache_path(&self, wasm_bytes: &[u8]) -> Option<PathBuf> {
+        let dir = self.cache_dir.as_ref()?;
+        let content_hash = blake3::hash(wasm_bytes);
+        Some(dir.join(format!("{content_hash}-{:016x}.cwasm", self.config_fingerprint)))
+    }
+
+    fn new_store(&self) -> Result<Store<PluginState>> {
+        let mut builder = WasiCtxBuilder::new();
+        if self.deterministic {
+            // No stdio, clocks, or random sources are wired up: guests running
+            // under the deterministic host have nothing non-deterministic to
+            // read from WASI, so two nodes executing the same plugin bytes
+            // against the same batch can't diverge through it.
+        } else {
+            builder.inherit_stdio();
+        }
+        let wasi = builder.build();
+        let state = PluginState {
+            wasi,
+            event: None,
+            selected_column: None,
+            emitted: None,
+        };
+
+        let mut store = Store::new(&self.engine, state);
+        store.set_fuel(self.fuel_budget)?;
+        store.set_epoch_deadline(EPOCH_DEADLINE_TICKS);
+        Ok(store)
+    }
+
+    /// Loads `wasm_bytes` as either a core module or a Component-Model
+    /// component, auto-detected from the binary preamble. The plugin is
+    /// granted every [`host_api::Capability`], preserving the pre-existing,
+    /// unrestricted default; use [`WasmHost::load_plugin_with_capabilities`]
+    /// to sandbox a plugin's host calls.
     pub fn load_plugin(&self, wasm_bytes: &[u8]) -> Result<WasmPlugin> {
-        let wasi = WasiCtxBuilder::new()
-            .inherit_stdio()
-            .build();
-        
-        let mut store = Store::new(&self.engine, wasi);
-        let module = Module::new(&self.engine, wasm_bytes)?;
+        self.load_plugin_with_capabilities(wasm_bytes, Arc::new(host_api::Capabilities::all()))
+    }
+
+    /// Like [`WasmHost::load_plugin`], but gates the loaded plugin's host
+    /// calls to `capabilities`: for the duration of every `on_event`/
+    /// `on_event_with_batch` call, `capabilities` is installed as the active
+    /// grant consulted by [`host_api::capability_context`], so `HostAPI`
+    /// exports the plugin reaches through the FFI boundary are denied
+    /// according to this grant rather than the thread's default.
+    pub fn load_plugin_with_capabilities(
+        &self,
+        wasm_bytes: &[u8],
+        capabilities: Arc<host_api::Capabilities>,
+    ) -> Result<WasmPlugin> {
+        if is_component_binary(wasm_bytes) {
+            self.load_component_plugin(wasm_bytes, capabilities)
+        } else {
+            self.load_module_plugin(wasm_bytes, capabilities)
+        }
+    }
+
+    fn load_module_plugin(&self, wasm_bytes: &[u8], capabilities: Arc<host_api::Capabilities>) -> Result<WasmPlugin> {
+        let mut store = self.new_store()?;
+        let module = self.compile_module(wasm_bytes)?;
         let instance = self.linker.instantiate(&mut store, &module)?;
 
-        Ok(WasmPlugin {
+        Ok(WasmPlugin::Module {
             store: Arc::new(Mutex::new(store)),
             instance,
+            fuel_budget: self.fuel_budget,
+            capabilities,
+        })
+    }
+
+    fn load_component_plugin(&self, wasm_bytes: &[u8], capabilities: Arc<host_api::Capabilities>) -> Result<WasmPlugin> {
+        let mut store = self.new_store()?;
+        let component = self.compile_component(wasm_bytes)?;
+        let bindings = DataplanePlugin::instantiate(&mut store, &component, &self.component_linker)?;
+
+        Ok(WasmPlugin::Component {
+            store: Arc::new(Mutex::new(store)),
+            bindings,
+            fuel_budget: self.fuel_budget,
+            capabilities,
         })
     }
+
+    /// Compiles `wasm_bytes` into a `Module`, transparently using the AOT
+    /// cache when one is configured: a cache hit deserializes the previously
+    /// compiled artifact instead of recompiling, a miss compiles and then
+    /// persists the artifact for next time.
+    fn compile_module(&self, wasm_bytes: &[u8]) -> Result<Module> {
+        let Some(path) = self.cache_path(wasm_bytes) else {
+            return Ok(Module::new(&self.engine, wasm_bytes)?);
+        };
+
+        if path.exists() {
+            // SAFETY: the cache key folds in a fingerprint of the engine's
+            // `Config`, so an artifact found at `path` was compiled by a
+            // config-compatible engine. `Module::deserialize` itself revalidates
+            // the wasmtime-version header and target triple.
+            if let Ok(module) = unsafe { Module::deserialize_file(&self.engine, &path) } {
+                return Ok(module);
+            }
+            // Fall through to recompile on any mismatch (e.g. artifact from an
+            // older wasmtime build) rather than failing the load.
+        }
+
+        let module = Module::new(&self.engine, wasm_bytes)?;
+        self.persist_artifact(&path, module.serialize()?);
+        Ok(module)
+    }
+
+    /// Like [`WasmHost::compile_module`], for Component-Model binaries.
+    fn compile_component(&self, wasm_bytes: &[u8]) -> Result<Component> {
+        let Some(path) = self.cache_path(wasm_bytes) else {
+            return Ok(Component::new(&self.engine, wasm_bytes)?);
+        };
+
+        if path.exists() {
+            if let Ok(component) = unsafe { Component::deserialize_file(&self.engine, &path) } {
+                return Ok(component);
+            }
+        }
+
+        let component = Component::new(&self.engine, wasm_bytes)?;
+        self.persist_artifact(&path, component.serialize()?);
+        Ok(component)
+    }
+
+    fn persist_artifact(&self, path: &Path, bytes: Vec<u8>) {
+        if let Err(e) = std::fs::write(path, bytes) {
+            eprintln!("[zenith] failed to persist AOT plugin cache at {path:?}: {e}");
+        }
+    }
+
+    /// Compiles and caches every `.wasm` file in `dir` ahead of time,
+    /// without instantiating any of them as a running plugin. Requires this
+    /// host to have been built with [`Self::with_cache`]; a host built any
+    /// other way has nowhere to persist the artifacts, so this is a no-op.
+    /// Returns the number of files precompiled.
+    ///
+    /// Intended for production startup: call this against the plugin
+    /// directory before [`Self::load_plugin`]/[`Self::load_plugin_with_capabilities`]
+    /// ever runs against it, so every later load is a cache hit -
+    /// `Module::deserialize`/`Component::deserialize` only, no compilation.
+    pub fn precompile_dir(&self, dir: impl AsRef<Path>) -> Result<usize> {
+        if self.cache_dir.is_none() {
+            eprintln!("[zenith] precompile_dir called on a host with no cache directory configured; skipping");
+            return Ok(0);
+        }
+
+        let mut count = 0;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "wasm") {
+                let bytes = std::fs::read(&path)?;
+                if is_component_binary(&bytes) {
+                    self.compile_component(&bytes)?;
+                } else {
+                    self.compile_module(&bytes)?;
+                }
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
 }
 
 impl WasmPlugin {
+    /// Invoke the plugin's `on_event` export with no batch attached, preserving
+    /// the original pass/block-filter calling convention.
     pub fn on_event(&self, source_id: u32, seq_no: u64) -> Result<bool> {
-        let mut store = self.store.lock().expect("Lock poisoned");
-        // Look for a function named "on_event" that takes (i32, i64) -> i32
-        // Rust u32 -> wasm i32, u64 -> i64 usually
-        let func = self.instance.get_typed_func::<(i32, i64), i32>(&mut *store, "on_event");
-        
-        match func {
-            Ok(f) => {
-                let res = f.call(&mut *store, (source_id as i32, seq_no as i64))?;
-                Ok(res != 0)
+        self.on_event_with_batch(source_id, seq_no, None)
+    }
+
+    /// Invoke the plugin's `on_event` export with `event`'s `RecordBatch` made
+    /// available to the guest through the `zenith_get_column_ptr` /
+    /// `zenith_column_len` / `zenith_read` host imports for the duration of
+    /// the call.
+    pub fn on_event_with_batch(
+        &self,
+        source_id: u32,
+        seq_no: u64,
+        event: Option<Arc<ZenithEvent>>,
+    ) -> Result<bool> {
+        match self {
+            WasmPlugin::Module {
+                store,
+                instance,
+                fuel_budget,
+                capabilities,
+            } => {
+                let mut store = store.lock().expect("Lock poisoned");
+                // Look for a function named "on_event" that takes (i32, i64) -> i32
+                // Rust u32 -> wasm i32, u64 -> i64 usually
+                let func = instance.get_typed_func::<(i32, i64), i32>(&mut *store, "on_event");
+
+                match func {
+                    Ok(f) => {
+                        // Refill fuel and reset the wall-clock deadline for this call so a
+                        // long-lived plugin instance can't exhaust its budget across
+                        // many legitimate calls, only within a single runaway one.
+                        store.set_fuel(*fuel_budget)?;
+                        store.set_epoch_deadline(EPOCH_DEADLINE_TICKS);
+
+                        store.data_mut().event = event;
+                        store.data_mut().selected_column = None;
+                        store.data_mut().emitted = None;
+
+                        host_api::capability_context::install(capabilities.clone());
+                        let result = f.call(&mut *store, (source_id as i32, seq_no as i64));
+                        host_api::capability_context::clear();
+
+                        // Never hold onto the batch past this call.
+                        store.data_mut().event = None;
+
+                        match result {
+                            Ok(res) => Ok(res != 0),
+                            Err(err) => {
+                                if err.downcast_ref::<Trap>() == Some(&Trap::OutOfFuel)
+                                    || err.downcast_ref::<Trap>() == Some(&Trap::Interrupt)
+                                {
+                                    Ok(false)
+                                } else {
+                                    Err(err.into())
+                                }
+                            }
+                        }
+                    }
+                    Err(_) => {
+                        // If not found, allow by default
+                        Ok(true)
+                    }
+                }
+            }
+            WasmPlugin::Component {
+                store,
+                bindings,
+                fuel_budget,
+                capabilities,
+            } => {
+                let mut store = store.lock().expect("Lock poisoned");
+                store.set_fuel(*fuel_budget)?;
+                store.set_epoch_deadline(EPOCH_DEADLINE_TICKS);
+                store.data_mut().event = event;
+
+                host_api::capability_context::install(capabilities.clone());
+                let result = bindings.call_process_event(&mut *store, source_id, seq_no);
+                host_api::capability_context::clear();
+
+                store.data_mut().event = None;
+
+                match result {
+                    Ok(allowed) => Ok(allowed),
+                    Err(err) => {
+                        if err.downcast_ref::<Trap>() == Some(&Trap::OutOfFuel)
+                            || err.downcast_ref::<Trap>() == Some(&Trap::Interrupt)
+                        {
+                            Ok(false)
+                        } else {
+                            Err(err.into())
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the buffer the guest handed back via `zenith_emit` during the
+    /// most recent `on_event_with_batch` call, if any. Only module plugins
+    /// can set it: the host does not call a component's `transform-batch`
+    /// export, so for component plugins this is always `None`.
+    pub fn take_emitted(&self) -> Option<Vec<u8>> {
+        let store = match self {
+            WasmPlugin::Module { store, .. } => store,
+            WasmPlugin::Component { store, .. } => store,
+        };
+        let mut store = store.lock().expect("Lock poisoned");
+        store.data_mut().emitted.take()
+    }
+
+    /// The plugin's declared state-schema version, via an optional
+    /// `zenith_state_schema_version() -> i32` export. `None` if the plugin
+    /// doesn't export it - state migration across hot reloads is opt-in, and
+    /// a plugin with no export simply starts every reload with blank state,
+    /// as before.
+    fn state_schema_version(&self) -> Option<i32> {
+        match self {
+            WasmPlugin::Module { store, instance, .. } => {
+                let mut store = store.lock().expect("Lock poisoned");
+                let func = instance
+                    .get_typed_func::<(), i32>(&mut *store, "zenith_state_schema_version")
+                    .ok()?;
+                func.call(&mut *store, ()).ok()
+            }
+            // Component plugins have no state-migration exports.
+            WasmPlugin::Component { .. } => None,
+        }
+    }
+
+    /// The instance's own store and its exported `memory`, if it has one -
+    /// the same "memory" export every `zenith_*` host call above assumes.
+    fn exported_memory(&self) -> Option<(Arc<Mutex<Store<PluginState>>>, Memory)> {
+        match self {
+            WasmPlugin::Module { store, instance, .. } => {
+                let mut guard = store.lock().expect("Lock poisoned");
+                let memory = instance.get_export(&mut *guard, "memory")?.into_memory()?;
+                drop(guard);
+                Some((store.clone(), memory))
             }
-            Err(_) => {
-                // If not found, allow by default
-                Ok(true)
+            WasmPlugin::Component { .. } => None,
+        }
+    }
+
+    /// Calls the plugin's `zenith_serialize_state() -> (ptr, len)` export,
+    /// if present, and copies that span out of its linear memory.
+    fn serialize_state(&self) -> Option<Vec<u8>> {
+        match self {
+            WasmPlugin::Module { store, instance, .. } => {
+                let mut store = store.lock().expect("Lock poisoned");
+                let func = instance
+                    .get_typed_func::<(), (i32, i32)>(&mut *store, "zenith_serialize_state")
+                    .ok()?;
+                let (ptr, len) = func.call(&mut *store, ()).ok()?;
+                if ptr < 0 || len < 0 {
+                    return None;
+                }
+                let memory = instance.get_export(&mut *store, "memory")?.into_memory()?;
+                let mut buf = vec![0u8; len as usize];
+                memory.read(&*store, ptr as usize, &mut buf).ok()?;
+                Some(buf)
+            }
+            WasmPlugin::Component { .. } => None,
+        }
+    }
+
+    /// Hands `blob` to a freshly instantiated plugin via its
+    /// `zenith_alloc(i32) -> i32` and `zenith_deserialize_state(i32, i32)`
+    /// exports, if both are present. Returns whether the restore completed.
+    fn deserialize_state(&self, blob: &[u8]) -> bool {
+        match self {
+            WasmPlugin::Module { store, instance, .. } => {
+                let mut store = store.lock().expect("Lock poisoned");
+                let Ok(alloc) = instance.get_typed_func::<i32, i32>(&mut *store, "zenith_alloc") else {
+                    return false;
+                };
+                let Ok(deserialize) =
+                    instance.get_typed_func::<(i32, i32), ()>(&mut *store, "zenith_deserialize_state")
+                else {
+                    return false;
+                };
+                let Some(memory) = instance.get_export(&mut *store, "memory").and_then(|e| e.into_memory()) else {
+                    return false;
+                };
+                let Ok(ptr) = alloc.call(&mut *store, blob.len() as i32) else {
+                    return false;
+                };
+                if ptr < 0 || memory.write(&mut *store, ptr as usize, blob).is_err() {
+                    return false;
+                }
+                deserialize.call(&mut *store, (ptr, blob.len() as i32)).is_ok()
+            }
+            WasmPlugin::Component { .. } => false,
+        }
+    }
+
+    /// Copies `previous`'s exported linear memory directly into this
+    /// plugin's, growing it to fit if needed. This is the wasmer
+    /// `copy_to_store`-style shortcut: when the reloaded module's memory
+    /// layout hasn't changed, it restores state without running any guest
+    /// code at all, skipping the serialize/deserialize round-trip.
+    fn copy_memory_from(&self, previous: &WasmPlugin) -> bool {
+        let (Some((prev_store, prev_memory)), Some((new_store, new_memory))) =
+            (previous.exported_memory(), self.exported_memory())
+        else {
+            return false;
+        };
+
+        let prev_guard = prev_store.lock().expect("Lock poisoned");
+        let bytes = prev_memory.data(&*prev_guard).to_vec();
+        drop(prev_guard);
+
+        let mut new_guard = new_store.lock().expect("Lock poisoned");
+        const PAGE_SIZE: u64 = 64 * 1024;
+        let needed_pages = (bytes.len() as u64 + PAGE_SIZE - 1) / PAGE_SIZE;
+        let current_pages = new_memory.size(&*new_guard);
+        if current_pages < needed_pages
+            && new_memory.grow(&mut *new_guard, needed_pages - current_pages).is_err()
+        {
+            return false;
+        }
+        new_memory.write(&mut *new_guard, 0, &bytes).is_ok()
+    }
+
+    /// Best-effort state migration from `previous` into this
+    /// freshly-instantiated plugin, called before a hot reload's old
+    /// instance is torn down (see
+    /// [`crate::engine::ZenithEngine::reload_plugin_with_capabilities`]).
+    /// Tries, in order:
+    ///
+    /// 1. If both plugins declare the same `zenith_state_schema_version`, a
+    ///    direct memory copy ([`Self::copy_memory_from`]) - no guest code
+    ///    runs, so this only works when the new module's memory layout is
+    ///    unchanged from the old one.
+    /// 2. Otherwise, the `zenith_serialize_state`/`zenith_deserialize_state`
+    ///    export pair, if the old plugin exports the former and the new one
+    ///    the latter (plus `zenith_alloc`).
+    ///
+    /// A schema version mismatch, or neither path being available, leaves
+    /// the new instance with its blank starting state, logging why so a
+    /// silently-lost dedup table or rate-limit bucket doesn't go unnoticed.
+    pub(crate) fn migrate_state_from(&self, previous: &WasmPlugin, id: &str) {
+        match (previous.state_schema_version(), self.state_schema_version()) {
+            (Some(old), Some(new)) if old == new => {
+                if self.copy_memory_from(previous) {
+                    return;
+                }
+                eprintln!(
+                    "[zenith] plugin '{id}' declares state schema v{new} on both sides but its memory could not be copied; falling back to serialize/deserialize"
+                );
+            }
+            (Some(old), Some(new)) if old != new => {
+                eprintln!(
+                    "[zenith] plugin '{id}' state schema changed (v{old} -> v{new}); starting fresh instead of migrating"
+                );
+                return;
+            }
+            _ => {}
+        }
+
+        if let Some(blob) = previous.serialize_state() {
+            if !self.deserialize_state(&blob) {
+                eprintln!(
+                    "[zenith] plugin '{id}' exports zenith_serialize_state but its reloaded instance could not restore the serialized state; starting fresh"
+                );
             }
         }
     }
@@ -74,7 +721,85 @@ mod tests {
         let result = WasmHost::new();
         assert!(result.is_ok(), "WasmHost creation should succeed");
     }
-    
+
+    #[test]
+    fn test_wasm_host_custom_fuel_budget() {
+        let result = WasmHost::with_fuel_budget(1_000);
+        assert!(result.is_ok(), "WasmHost creation with a custom fuel budget should succeed");
+    }
+
+    #[test]
+    fn test_wasm_host_deterministic_creation() {
+        let result = WasmHost::deterministic();
+        assert!(result.is_ok(), "Deterministic WasmHost creation should succeed");
+    }
+
+    #[test]
+    fn test_wasm_host_with_cache_compiles_and_reuses_artifact() {
+        let dir = std::env::temp_dir().join(format!("zenith-wasm-cache-test-{}", std::process::id()));
+        let host = WasmHost::with_cache(&dir).unwrap();
+
+        let minimal_wasm = &[
+            0x00, 0x61, 0x73, 0x6D,  // WASM magic number
+            0x01, 0x00, 0x00, 0x00,  // Version 1
+        ];
+
+        assert!(host.load_plugin(minimal_wasm).is_ok());
+        assert!(
+            std::fs::read_dir(&dir).unwrap().count() >= 1,
+            "compiling with a cache dir configured should persist an artifact"
+        );
+
+        // Loading again should hit the cache instead of failing to recompile.
+        assert!(host.load_plugin(minimal_wasm).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_precompile_dir_warms_cache_for_every_wasm_file() {
+        let cache_dir = std::env::temp_dir().join(format!("zenith-precompile-cache-{}", std::process::id()));
+        let plugin_dir = std::env::temp_dir().join(format!("zenith-precompile-plugins-{}", std::process::id()));
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+
+        let minimal_wasm: &[u8] = &[
+            0x00, 0x61, 0x73, 0x6D, // WASM magic number
+            0x01, 0x00, 0x00, 0x00, // Version 1
+        ];
+        std::fs::write(plugin_dir.join("a.wasm"), minimal_wasm).unwrap();
+        std::fs::write(plugin_dir.join("b.wasm"), minimal_wasm).unwrap();
+        std::fs::write(plugin_dir.join("not-wasm.txt"), b"ignore me").unwrap();
+
+        let host = WasmHost::with_cache(&cache_dir).unwrap();
+        let count = host.precompile_dir(&plugin_dir).unwrap();
+        assert_eq!(count, 2, "should precompile every .wasm file and skip non-.wasm ones");
+        assert!(
+            std::fs::read_dir(&cache_dir).unwrap().count() >= 1,
+            "precompiling should persist an artifact into the cache dir"
+        );
+
+        let _ = std::fs::remove_dir_all(&cache_dir);
+        let _ = std::fs::remove_dir_all(&plugin_dir);
+    }
+
+    /// A host built without [`WasmHost::with_cache`] has nowhere to persist
+    /// artifacts, so `precompile_dir` should be a no-op rather than error.
+    #[test]
+    fn test_precompile_dir_without_cache_dir_is_a_noop() {
+        let plugin_dir = std::env::temp_dir().join(format!("zenith-precompile-nocache-{}", std::process::id()));
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("a.wasm"),
+            &[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00][..],
+        )
+        .unwrap();
+
+        let host = WasmHost::new().unwrap();
+        assert_eq!(host.precompile_dir(&plugin_dir).unwrap(), 0);
+
+        let _ = std::fs::remove_dir_all(&plugin_dir);
+    }
+
     #[test]
     fn test_wasm_host_load_invalid_plugin() {
         let host = WasmHost::new().unwrap();
@@ -157,4 +882,23 @@ mod tests {
         let expected = res != 0;
         assert!(expected, "Negative result should still mean 'allow event'");
     }
+
+    /// A plugin with no `zenith_state_schema_version`/`zenith_serialize_state`
+    /// exports has no migration path - `migrate_state_from` should be a
+    /// no-op rather than panicking or erroring.
+    #[test]
+    fn test_migrate_state_from_is_a_noop_without_exports() {
+        let host = WasmHost::new().unwrap();
+        let minimal_wasm = &[
+            0x00, 0x61, 0x73, 0x6D, // WASM magic number
+            0x01, 0x00, 0x00, 0x00, // Version 1
+        ];
+
+        let old = host.load_plugin(minimal_wasm).unwrap();
+        let new = host.load_plugin(minimal_wasm).unwrap();
+
+        assert_eq!(old.state_schema_version(), None);
+        assert_eq!(old.serialize_state(), None);
+        new.migrate_state_from(&old, "test-plugin");
+    }
 }
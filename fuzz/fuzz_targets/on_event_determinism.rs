@@ -0,0 +1,44 @@
+//! Focused differential target: instantiate the same generated module twice
+//! on two independently-built `WasmHost`s and assert `on_event` agrees. Catches
+//! host-config-dependent nondeterminism (e.g. NaN payload, scheduling) that a
+//! single-host run of `publish_wasm_pipeline` wouldn't exercise.
+
+#![no_main]
+
+use arbitrary::{Arbitrary, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use zenith_core::wasm_host::WasmHost;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    module_seed: Vec<u8>,
+    source_id: u32,
+    seq_no: u64,
+}
+
+fuzz_target!(|input: Input| {
+    let mut u = Unstructured::new(&input.module_seed);
+    let Ok(module) = wasm_smith::Module::new(wasm_smith::Config::default(), &mut u) else {
+        return;
+    };
+    let wasm_bytes = module.to_bytes();
+
+    let (Ok(host_a), Ok(host_b)) = (WasmHost::deterministic(), WasmHost::deterministic()) else {
+        return;
+    };
+
+    let (Ok(plugin_a), Ok(plugin_b)) =
+        (host_a.load_plugin(&wasm_bytes), host_b.load_plugin(&wasm_bytes))
+    else {
+        return;
+    };
+
+    let a = plugin_a.on_event(input.source_id, input.seq_no);
+    let b = plugin_b.on_event(input.source_id, input.seq_no);
+
+    match (a, b) {
+        (Ok(a), Ok(b)) => assert_eq!(a, b, "deterministic hosts disagreed on on_event"),
+        (Err(_), Err(_)) => {}
+        (a, b) => panic!("deterministic hosts diverged: {a:?} vs {b:?}"),
+    }
+});
@@ -0,0 +1,55 @@
+//! Differential/stress fuzz target for the WASM plugin side of the pipeline:
+//! generates an arbitrary-but-valid guest module with `wasm-smith`, loads it
+//! through `WasmHost`, then calls `on_event` with a random
+//! `source_id`/`seq_no`. No `RecordBatch` is attached to the call, and
+//! `zenith_publish` and `arrow::ffi` are not exercised.
+//!
+//! Invariants checked here, not by the ad-hoc unit tests in `wasm_host.rs`:
+//! - loading a generated module never panics (a failed load is just skipped);
+//! - a guest trap (including fuel exhaustion) never panics across the host
+//!   boundary, it surfaces as `Ok(false)`/`Err` per the documented contract;
+//! - `on_event` is deterministic: running the same module twice against the
+//!   same inputs produces the same result.
+
+#![no_main]
+
+use arbitrary::{Arbitrary, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use zenith_core::wasm_host::WasmHost;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    module_seed: Vec<u8>,
+    source_id: u32,
+    seq_no: u64,
+}
+
+fuzz_target!(|input: Input| {
+    let mut u = Unstructured::new(&input.module_seed);
+    let Ok(module) = wasm_smith::Module::new(wasm_smith::Config::default(), &mut u) else {
+        return;
+    };
+    let wasm_bytes = module.to_bytes();
+
+    // A host constructed per-iteration keeps fuel/epoch state isolated
+    // between fuzz cases.
+    let Ok(host) = WasmHost::new() else { return };
+
+    let Ok(plugin) = host.load_plugin(&wasm_bytes) else {
+        // wasm-smith only emits valid modules, but instantiation can still
+        // legitimately fail (e.g. unsupported imports); that's fine as long
+        // as it's an `Err`, not a panic.
+        return;
+    };
+
+    let first = plugin.on_event(input.source_id, input.seq_no);
+
+    // Differential check: the identical module run twice against identical
+    // inputs must not diverge.
+    let second = plugin.on_event(input.source_id, input.seq_no);
+    match (first, second) {
+        (Ok(a), Ok(b)) => assert_eq!(a, b, "on_event is not deterministic across repeat calls"),
+        (Err(_), Err(_)) => {}
+        (a, b) => panic!("on_event diverged between repeat calls: {a:?} vs {b:?}"),
+    }
+});
@@ -0,0 +1,341 @@
+/// Capability-based access control for the host API.
+///
+/// Every `HostAPI` export (and the `KvAPI`/`HttpAPI`/`FsAPI` calls it fans
+/// out to) used to be globally callable by any loaded plugin - the module
+/// header's "capability-based interface" claim was aspirational. This
+/// module makes it real: a plugin is loaded with a [`Capabilities`] set
+/// declaring which [`Capability`] it holds and, per capability, an
+/// optional rate/size limit. [`crate::capability_context`] installs the
+/// currently-executing plugin's `Capabilities` on the calling thread so
+/// host exports can check against it without threading a parameter through
+/// every call.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// A distinct host-side privilege a plugin may be granted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    Log = 0,
+    Clock = 1,
+    Random = 2,
+    Kv = 3,
+    Http = 4,
+    Fs = 5,
+}
+
+impl Capability {
+    const COUNT: usize = 6;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// All capabilities, in a fixed order matching their internal index -
+    /// used to size and iterate the per-capability accounting tables below.
+    pub const ALL: [Capability; Self::COUNT] = [
+        Capability::Log,
+        Capability::Clock,
+        Capability::Random,
+        Capability::Kv,
+        Capability::Http,
+        Capability::Fs,
+    ];
+
+    /// Parses a capability's manifest name (case-insensitive), e.g.
+    /// `"kv"` -> [`Capability::Kv`]. Returns `None` for anything else, so
+    /// [`Capabilities::from_manifest`] can skip unrecognized lines instead
+    /// of rejecting the whole manifest.
+    pub fn parse(name: &str) -> Option<Capability> {
+        match name.to_ascii_lowercase().as_str() {
+            "log" => Some(Capability::Log),
+            "clock" => Some(Capability::Clock),
+            "random" => Some(Capability::Random),
+            "kv" => Some(Capability::Kv),
+            "http" => Some(Capability::Http),
+            "fs" => Some(Capability::Fs),
+            _ => None,
+        }
+    }
+}
+
+/// Per-capability limits enforced on top of the grant itself. All fields
+/// default to "unrestricted"; set only the ones a given deployment wants to
+/// bound.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CapabilityLimit {
+    /// Steady-state call rate, enforced with a token bucket (capacity equal
+    /// to one second's worth of tokens). `None` is unlimited.
+    pub max_calls_per_sec: Option<f64>,
+    /// Upper bound on the size of a single call's payload (e.g. log message
+    /// bytes, KV value bytes). Calls over this limit are denied outright
+    /// rather than truncated. `None` is unlimited.
+    pub max_bytes_per_call: Option<usize>,
+    /// Grants read access only; write-shaped calls under this capability
+    /// (e.g. `FsAPI::write_file`, `KvAPI::set`) must be denied even though
+    /// the capability itself is granted. Meaningless for capabilities with
+    /// no write operations (`Log`, `Clock`, `Random`).
+    pub read_only: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RateState {
+    tokens: f64,
+    last_refill: Instant,
+    /// `RateState::new()` can't seed `tokens` with a full bucket because it
+    /// runs before the capability's `max_calls_per_sec` (the bucket's
+    /// capacity) is known. Instead `tokens` starts at `0.0` and this flag
+    /// marks that as "not yet primed"; `Capabilities::check` fills the
+    /// bucket to capacity the first time it actually rate-limits a call
+    /// under this state, rather than treating the elapsed-since-construction
+    /// time as real refill time.
+    initialized: bool,
+}
+
+impl RateState {
+    fn new() -> Self {
+        Self { tokens: 0.0, last_refill: Instant::now(), initialized: false }
+    }
+}
+
+/// Negative error code every gated host export returns when a call is
+/// denied, whether because the capability isn't granted or because it's
+/// granted but over its rate/size limit.
+pub const DENIED: i32 = -403;
+
+/// The set of capabilities granted to a single plugin, plus the live
+/// accounting state (call counters, rate-limit token buckets) needed to
+/// enforce the limits attached to each grant.
+#[derive(Debug)]
+pub struct Capabilities {
+    granted: HashMap<Capability, CapabilityLimit>,
+    calls: Vec<AtomicU64>,
+    rate: Mutex<Vec<RateState>>,
+}
+
+impl Capabilities {
+    /// No capabilities granted; every [`Self::check`] call is denied. The
+    /// safe default for a plugin whose manifest declares nothing.
+    pub fn none() -> Self {
+        Self {
+            granted: HashMap::new(),
+            calls: (0..Capability::COUNT).map(|_| AtomicU64::new(0)).collect(),
+            rate: Mutex::new(vec![RateState::new(); Capability::COUNT]),
+        }
+    }
+
+    /// Every capability granted with no limits - the implicit grant for a
+    /// plugin loaded without a manifest, preserving pre-capability-gating
+    /// behavior.
+    pub fn all() -> Self {
+        let mut caps = Self::none();
+        for cap in Capability::ALL {
+            caps.granted.insert(cap, CapabilityLimit::default());
+        }
+        caps
+    }
+
+    /// Builder-style grant of `cap` with `limit`.
+    pub fn allow(mut self, cap: Capability, limit: CapabilityLimit) -> Self {
+        self.granted.insert(cap, limit);
+        self
+    }
+
+    /// Parses a plugin manifest's capability grants from a line-based text
+    /// format, one grant per line:
+    ///
+    /// ```text
+    /// log=allow
+    /// kv=allow,readonly
+    /// http=allow,rate=10,max_bytes=65536
+    /// fs=deny
+    /// ```
+    ///
+    /// Blank lines and `#`-prefixed comments are ignored. A capability not
+    /// mentioned, or explicitly `deny`d, is not granted - same as omitting
+    /// it from [`Self::none`]. Unrecognized capability names and malformed
+    /// lines are skipped rather than rejecting the whole manifest, so one
+    /// typo doesn't lock a plugin out of every capability it needs.
+    pub fn from_manifest(text: &str) -> Self {
+        let mut caps = Self::none();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, rest)) = line.split_once('=') else {
+                continue;
+            };
+            let Some(cap) = Capability::parse(name.trim()) else {
+                continue;
+            };
+
+            let mut parts = rest.split(',');
+            if parts.next().map(str::trim) != Some("allow") {
+                continue;
+            }
+
+            let mut limit = CapabilityLimit::default();
+            for opt in parts {
+                let opt = opt.trim();
+                if opt == "readonly" {
+                    limit.read_only = true;
+                } else if let Some(rate) = opt.strip_prefix("rate=") {
+                    limit.max_calls_per_sec = rate.parse().ok();
+                } else if let Some(bytes) = opt.strip_prefix("max_bytes=") {
+                    limit.max_bytes_per_call = bytes.parse().ok();
+                }
+            }
+            caps = caps.allow(cap, limit);
+        }
+        caps
+    }
+
+    pub fn is_granted(&self, cap: Capability) -> bool {
+        self.granted.contains_key(&cap)
+    }
+
+    /// Whether `cap` is granted read-only, per [`CapabilityLimit::read_only`].
+    pub fn is_read_only(&self, cap: Capability) -> bool {
+        self.granted.get(&cap).is_some_and(|limit| limit.read_only)
+    }
+
+    /// Lifetime calls admitted under `cap` so far.
+    pub fn call_count(&self, cap: Capability) -> u64 {
+        self.calls[cap.index()].load(Ordering::Relaxed)
+    }
+
+    /// Check (and, if admitted, record) a call of `bytes` payload size
+    /// under `cap`. Denies with [`DENIED`] if `cap` isn't granted, the
+    /// payload exceeds [`CapabilityLimit::max_bytes_per_call`], or the
+    /// call would exceed [`CapabilityLimit::max_calls_per_sec`].
+    pub fn check(&self, cap: Capability, bytes: usize) -> Result<(), i32> {
+        let Some(limit) = self.granted.get(&cap) else {
+            return Err(DENIED);
+        };
+
+        if let Some(max_bytes) = limit.max_bytes_per_call {
+            if bytes > max_bytes {
+                return Err(DENIED);
+            }
+        }
+
+        if let Some(rate) = limit.max_calls_per_sec {
+            let mut states = self.rate.lock().unwrap();
+            let state = &mut states[cap.index()];
+            let now = Instant::now();
+            let capacity = rate.max(1.0);
+            if !state.initialized {
+                // First call under this grant: start with a full bucket
+                // rather than crediting it for time elapsed since
+                // construction, per standard token-bucket semantics.
+                state.tokens = capacity;
+                state.last_refill = now;
+                state.initialized = true;
+            } else {
+                let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+                state.last_refill = now;
+                state.tokens = (state.tokens + elapsed * rate).min(capacity);
+            }
+            if state.tokens < 1.0 {
+                return Err(DENIED);
+            }
+            state.tokens -= 1.0;
+        }
+
+        self.calls[cap.index()].fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Convenience for write-shaped calls: denies with [`DENIED`] if `cap`
+    /// is granted read-only, then delegates to [`Self::check`].
+    pub fn check_write(&self, cap: Capability, bytes: usize) -> Result<(), i32> {
+        if self.is_read_only(cap) {
+            return Err(DENIED);
+        }
+        self.check(cap, bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_none_denies_everything() {
+        let caps = Capabilities::none();
+        assert_eq!(caps.check(Capability::Log, 0), Err(DENIED));
+    }
+
+    #[test]
+    fn test_all_grants_everything_unlimited() {
+        let caps = Capabilities::all();
+        for cap in Capability::ALL {
+            assert!(caps.check(cap, 1_000_000).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_max_bytes_per_call_denies_oversized_payload() {
+        let caps = Capabilities::none()
+            .allow(Capability::Log, CapabilityLimit { max_bytes_per_call: Some(8), ..Default::default() });
+        assert!(caps.check(Capability::Log, 8).is_ok());
+        assert_eq!(caps.check(Capability::Log, 9), Err(DENIED));
+    }
+
+    #[test]
+    fn test_rate_limit_denies_burst_past_capacity() {
+        let caps = Capabilities::none()
+            .allow(Capability::Http, CapabilityLimit { max_calls_per_sec: Some(1.0), ..Default::default() });
+        assert!(caps.check(Capability::Http, 0).is_ok());
+        assert_eq!(caps.check(Capability::Http, 0), Err(DENIED));
+    }
+
+    #[test]
+    fn test_read_only_denies_write_but_allows_read() {
+        let caps = Capabilities::none()
+            .allow(Capability::Kv, CapabilityLimit { read_only: true, ..Default::default() });
+        assert!(caps.check(Capability::Kv, 0).is_ok());
+        assert_eq!(caps.check_write(Capability::Kv, 0), Err(DENIED));
+    }
+
+    #[test]
+    fn test_call_count_tracks_admitted_calls() {
+        let caps = Capabilities::all();
+        assert_eq!(caps.call_count(Capability::Random), 0);
+        caps.check(Capability::Random, 0).unwrap();
+        caps.check(Capability::Random, 0).unwrap();
+        assert_eq!(caps.call_count(Capability::Random), 2);
+    }
+
+    #[test]
+    fn test_capability_parse_is_case_insensitive() {
+        assert_eq!(Capability::parse("Kv"), Some(Capability::Kv));
+        assert_eq!(Capability::parse("HTTP"), Some(Capability::Http));
+        assert_eq!(Capability::parse("nonsense"), None);
+    }
+
+    #[test]
+    fn test_from_manifest_parses_grants_and_options() {
+        let caps = Capabilities::from_manifest(
+            "# comment\n\nlog=allow\nkv=allow,readonly\nhttp=allow,rate=10,max_bytes=65536\nfs=deny\n",
+        );
+
+        assert!(caps.is_granted(Capability::Log));
+        assert!(caps.is_granted(Capability::Kv));
+        assert!(caps.is_read_only(Capability::Kv));
+        assert!(caps.is_granted(Capability::Http));
+        assert!(!caps.is_granted(Capability::Fs));
+        assert!(!caps.is_granted(Capability::Clock));
+
+        assert_eq!(caps.check(Capability::Http, 1_000_000), Err(DENIED));
+    }
+
+    #[test]
+    fn test_from_manifest_skips_malformed_and_unknown_lines() {
+        let caps = Capabilities::from_manifest("not_a_capability=allow\nlog\nrandom=allow\n");
+        assert!(!caps.is_granted(Capability::Log));
+        assert!(caps.is_granted(Capability::Random));
+    }
+}
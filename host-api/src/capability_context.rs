@@ -0,0 +1,82 @@
+/// Thread-local capability grant installed by the host around a plugin
+/// invocation, mirroring [`crate::event_context`]. `HostAPI` exports call
+/// [`require`] instead of reaching into [`crate::Capabilities`] directly, so
+/// a call made outside any plugin invocation (e.g. a native Rust caller
+/// embedding `host_api` directly, or a unit test) is treated as trusted and
+/// always admitted - gating only applies to calls attributed to a specific
+/// plugin's installed grant.
+use crate::capabilities::{Capabilities, Capability, DENIED};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<Capabilities>>> = const { RefCell::new(None) };
+}
+
+/// Install `caps` as the active grant for the calling thread. Callers must
+/// pair this with a matching [`clear`] once the plugin invocation the grant
+/// was installed for has returned.
+pub fn install(caps: Arc<Capabilities>) {
+    CURRENT.with(|cell| *cell.borrow_mut() = Some(caps));
+}
+
+/// Clear the active capability grant for the calling thread.
+pub fn clear() {
+    CURRENT.with(|cell| *cell.borrow_mut() = None);
+}
+
+/// Run `f` with read access to the calling thread's active capability
+/// grant, if any.
+pub fn with_current<R>(f: impl FnOnce(Option<&Capabilities>) -> R) -> R {
+    CURRENT.with(|cell| f(cell.borrow().as_deref()))
+}
+
+/// Require `cap` (admitting a call of `bytes` payload size) under the
+/// active grant. With no grant installed, the call is trusted and always
+/// admitted; see the module docs for why.
+pub fn require(cap: Capability, bytes: usize) -> Result<(), i32> {
+    with_current(|caps| match caps {
+        Some(caps) => caps.check(cap, bytes),
+        None => Ok(()),
+    })
+}
+
+/// Like [`require`], for write-shaped calls: also denies when the active
+/// grant marks `cap` read-only (see
+/// [`crate::capabilities::CapabilityLimit::read_only`]). With no grant
+/// installed, behaves the same as [`require`] - the call is trusted.
+pub fn require_write(cap: Capability, bytes: usize) -> Result<(), i32> {
+    with_current(|caps| match caps {
+        Some(caps) => caps.check_write(cap, bytes),
+        None => Ok(()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capabilities::CapabilityLimit;
+
+    #[test]
+    fn test_require_with_no_context_is_trusted() {
+        clear();
+        assert_eq!(require(Capability::Http, 0), Ok(()));
+    }
+
+    #[test]
+    fn test_require_consults_installed_grant() {
+        install(Arc::new(Capabilities::none()));
+        assert_eq!(require(Capability::Http, 0), Err(DENIED));
+        clear();
+    }
+
+    #[test]
+    fn test_require_write_denies_read_only_grant() {
+        install(Arc::new(
+            Capabilities::none().allow(Capability::Fs, CapabilityLimit { read_only: true, ..Default::default() }),
+        ));
+        assert_eq!(require(Capability::Fs, 0), Ok(()));
+        assert_eq!(require_write(Capability::Fs, 0), Err(DENIED));
+        clear();
+    }
+}
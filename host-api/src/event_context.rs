@@ -0,0 +1,81 @@
+/// Thread-local event context installed by the host around a plugin
+/// invocation, giving [`crate::HostAPI::read_event_field`] and
+/// [`crate::HostAPI::read_event_column`] real data to read instead of the
+/// placeholder string the former used to return.
+///
+/// The host (the consumer thread in `ZenithEngine::start`) calls [`install`]
+/// with the event it's about to dispatch, invokes the plugin, then calls
+/// [`clear`]. Scoping this per-thread rather than behind a single global
+/// means concurrent event-processing workers never observe each other's
+/// event.
+use std::cell::RefCell;
+use std::sync::Arc;
+
+thread_local! {
+    static CURRENT: RefCell<Option<EventContext>> = const { RefCell::new(None) };
+}
+
+/// The event currently being delivered to a plugin on this thread: its
+/// header fields plus the bytes of each of its Arrow columns' first
+/// buffer. The host copies those bytes once per event into `Arc<[u8]>`s
+/// shared by every plugin the event goes to; they are not a view into the
+/// batch's own buffers.
+pub struct EventContext {
+    pub source_id: u32,
+    pub seq_no: u64,
+    columns: Vec<Arc<[u8]>>,
+}
+
+impl EventContext {
+    pub fn new(source_id: u32, seq_no: u64, columns: Vec<Arc<[u8]>>) -> Self {
+        Self { source_id, seq_no, columns }
+    }
+
+    /// Raw bytes backing column `index`, if the active event has that many
+    /// columns.
+    pub fn column(&self, index: usize) -> Option<&[u8]> {
+        self.columns.get(index).map(|c| c.as_ref())
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+}
+
+/// Install `ctx` as the active event for the calling thread. Callers must
+/// pair this with a matching [`clear`] once the plugin invocation the
+/// context was installed for has returned.
+pub fn install(ctx: EventContext) {
+    CURRENT.with(|cell| *cell.borrow_mut() = Some(ctx));
+}
+
+/// Clear the active event context for the calling thread.
+pub fn clear() {
+    CURRENT.with(|cell| *cell.borrow_mut() = None);
+}
+
+/// Run `f` with read access to the calling thread's active event context,
+/// if any.
+pub fn with_current<R>(f: impl FnOnce(Option<&EventContext>) -> R) -> R {
+    CURRENT.with(|cell| f(cell.borrow().as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_and_read_back() {
+        clear();
+        install(EventContext::new(7, 42, vec![Arc::from(&b"hello"[..])]));
+        with_current(|ctx| {
+            let ctx = ctx.expect("context should be installed");
+            assert_eq!(ctx.source_id, 7);
+            assert_eq!(ctx.seq_no, 42);
+            assert_eq!(ctx.column(0), Some(&b"hello"[..]));
+            assert_eq!(ctx.column(1), None);
+        });
+        clear();
+        with_current(|ctx| assert!(ctx.is_none(), "context should be cleared"));
+    }
+}
@@ -1,19 +1,116 @@
 /// Sandboxed Filesystem Module for WASM Plugins
 /// Provides restricted filesystem access with safety guarantees
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::io::{Read, Write};
-use std::sync::RwLock;
+use std::sync::{Arc, RwLock};
+
+/// Root of the virtual, read-only introspection namespace `FsAPI`
+/// overlays on top of the sandbox. Paths under this prefix never touch
+/// disk; they're resolved against [`VIRTUAL_HANDLERS`] instead.
+pub const VIRTUAL_ROOT: &str = "/.zenith";
+
+/// A registered virtual-path handler: produces the bytes for exactly one
+/// `/.zenith/...` path on demand, in place of a disk read.
+type VirtualHandler = Arc<dyn Fn() -> Vec<u8> + Send + Sync>;
 
 lazy_static::lazy_static! {
     static ref SANDBOX_ROOT: RwLock<PathBuf> = RwLock::new(PathBuf::from("/tmp/zenith_sandbox"));
+    static ref VIRTUAL_HANDLERS: RwLock<HashMap<String, VirtualHandler>> = {
+        let mut handlers: HashMap<String, VirtualHandler> = HashMap::new();
+        handlers.insert(
+            "/.zenith/numa/topology".to_string(),
+            Arc::new(numa_topology_json) as VirtualHandler,
+        );
+        RwLock::new(handlers)
+    };
+}
+
+/// `/.zenith/numa/topology`'s content: the discovered `NumaTopology` as
+/// JSON, hand-rolled rather than pulling `serde_json` into a crate that
+/// otherwise has no serialization dependency (see `logging::LogEntry::to_json`
+/// for the same tradeoff).
+fn numa_topology_json() -> Vec<u8> {
+    let topology = match zenith_runtime_cpu::NumaTopology::discover() {
+        Ok(topology) => topology,
+        Err(e) => return format!("{{\"error\":{}}}", json_string(&e.to_string())).into_bytes(),
+    };
+
+    let mut out = String::from("{");
+    out.push_str(&format!("\"num_nodes\":{},", topology.num_nodes()));
+    out.push_str(&format!("\"num_cpus\":{},", topology.num_cpus()));
+    out.push_str(&format!("\"num_physical_cpus\":{},", topology.num_physical_cpus()));
+    out.push_str(&format!("\"effective_cpus\":{},", topology.effective_cpus()));
+    out.push_str(&format!("\"numa_available\":{},", topology.is_numa_available()));
+    out.push_str(&format!("\"total_memory\":{},", topology.total_memory()));
+    out.push_str(&format!("\"memory_limit\":{},", topology.memory_limit()));
+    out.push_str("\"nodes\":[");
+    let mut nodes: Vec<_> = topology.nodes().collect();
+    nodes.sort_by_key(|n| n.node_id);
+    for (i, node) in nodes.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&format!(
+            "{{\"node_id\":{},\"cpu_cores\":{:?},\"physical_cores\":{:?},\"total_memory\":{},\"free_memory\":{}}}",
+            node.node_id, node.cpu_cores, node.physical_cores, node.total_memory, node.free_memory
+        ));
+    }
+    out.push_str("]}");
+    out.into_bytes()
+}
+
+/// Escape `s` as a JSON string literal, including the surrounding quotes.
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
 }
 
 /// Filesystem API with sandboxing
 pub struct FsAPI;
 
 impl FsAPI {
+    /// Register a handler that produces the bytes for `path` on demand,
+    /// instead of reading it from disk. `path` must live under
+    /// [`VIRTUAL_ROOT`]; overwrites any existing handler for the same
+    /// path. Used by the runtime to publish live data (e.g. loaded
+    /// plugins, a plugin's own `SandboxLimits`) that only it has access
+    /// to - `FsAPI` itself only knows how to serve `/.zenith/numa/topology`.
+    pub fn register_virtual_path(
+        path: impl Into<String>,
+        handler: impl Fn() -> Vec<u8> + Send + Sync + 'static,
+    ) {
+        let path = path.into();
+        assert!(
+            path.starts_with(VIRTUAL_ROOT),
+            "virtual path {} must live under {}",
+            path, VIRTUAL_ROOT
+        );
+        VIRTUAL_HANDLERS.write().unwrap().insert(path, Arc::new(handler));
+    }
+
+    /// Remove a previously registered virtual-path handler, e.g. when a
+    /// plugin unloads and `/.zenith/self/limits` should stop resolving
+    /// for it.
+    pub fn unregister_virtual_path(path: &str) {
+        VIRTUAL_HANDLERS.write().unwrap().remove(path);
+    }
+
     /// Set sandbox root directory
     pub fn set_sandbox_root(path: PathBuf) {
         let mut root = SANDBOX_ROOT.write().unwrap();
@@ -50,55 +147,121 @@ impl FsAPI {
         Ok(canonical)
     }
     
-    /// Read file contents
+    /// Read file contents. Paths under [`VIRTUAL_ROOT`] resolve against
+    /// the registered virtual-path handlers instead of disk.
+    ///
+    /// Denied if the calling plugin isn't granted [`crate::Capability::Fs`].
     pub fn read_file(path: &str) -> Result<Vec<u8>, String> {
+        crate::capability_context::require(crate::Capability::Fs, 0)
+            .map_err(|_| "capability denied: Fs".to_string())?;
+
+        if path.starts_with(VIRTUAL_ROOT) {
+            return VIRTUAL_HANDLERS
+                .read()
+                .unwrap()
+                .get(path)
+                .map(|handler| handler())
+                .ok_or_else(|| format!("No such virtual path: {}", path));
+        }
+
         let full_path = Self::resolve_path(path)?;
-        
+
         fs::read(&full_path)
             .map_err(|e| format!("Failed to read file: {}", e))
     }
-    
-    /// Write file contents
+
+    /// Write file contents. Paths under [`VIRTUAL_ROOT`] are read-only
+    /// and always rejected.
+    ///
+    /// Denied if the calling plugin isn't granted [`crate::Capability::Fs`]
+    /// or is granted it read-only.
     pub fn write_file(path: &str, data: &[u8]) -> Result<(), String> {
+        crate::capability_context::require_write(crate::Capability::Fs, data.len())
+            .map_err(|_| "capability denied: Fs (write)".to_string())?;
+
+        if path.starts_with(VIRTUAL_ROOT) {
+            return Err(format!("{} is read-only", path));
+        }
+
         let full_path = Self::resolve_path(path)?;
-        
+
         // Ensure parent directory exists
         if let Some(parent) = full_path.parent() {
             fs::create_dir_all(parent)
                 .map_err(|e| format!("Failed to create directory: {}", e))?;
         }
-        
+
         fs::write(&full_path, data)
             .map_err(|e| format!("Failed to write file: {}", e))
     }
-    
-    /// Check if file exists
+
+    /// Check if file (real or virtual) exists
     pub fn exists(path: &str) -> bool {
+        if path.starts_with(VIRTUAL_ROOT) {
+            return path == VIRTUAL_ROOT
+                || VIRTUAL_HANDLERS.read().unwrap().contains_key(path)
+                || Self::virtual_children(path).is_some();
+        }
+
         Self::resolve_path(path)
             .ok()
             .map(|p| p.exists())
             .unwrap_or(false)
     }
-    
-    /// Delete file
+
+    /// Delete file. Paths under [`VIRTUAL_ROOT`] are read-only and always
+    /// rejected.
     pub fn delete_file(path: &str) -> Result<(), String> {
+        if path.starts_with(VIRTUAL_ROOT) {
+            return Err(format!("{} is read-only", path));
+        }
+
         let full_path = Self::resolve_path(path)?;
-        
+
         if !full_path.exists() {
             return Err("File not found".to_string());
         }
-        
+
         fs::remove_file(&full_path)
             .map_err(|e| format!("Failed to delete file: {}", e))
     }
-    
-    /// List directory contents
+
+    /// List the immediate child names registered under virtual directory
+    /// `path`, e.g. `list_dir("/.zenith")` sees `/.zenith/numa/topology`
+    /// and returns `["numa"]`. `None` if nothing is registered under it.
+    fn virtual_children(path: &str) -> Option<Vec<String>> {
+        let prefix = format!("{}/", path.trim_end_matches('/'));
+        let mut names: Vec<String> = VIRTUAL_HANDLERS
+            .read()
+            .unwrap()
+            .keys()
+            .filter_map(|registered| registered.strip_prefix(&prefix))
+            .map(|rest| rest.split('/').next().unwrap_or(rest).to_string())
+            .collect();
+
+        if names.is_empty() {
+            return None;
+        }
+
+        names.sort();
+        names.dedup();
+        Some(names)
+    }
+
+    /// List directory contents. [`VIRTUAL_ROOT`] and its subdirectories
+    /// list the path segments of registered virtual-path handlers rather
+    /// than reading a real directory.
     pub fn list_dir(path: &str) -> Result<Vec<String>, String> {
+        if path.starts_with(VIRTUAL_ROOT) {
+            return Self::virtual_children(path)
+                .ok_or_else(|| format!("No such virtual directory: {}", path));
+        }
+
         let full_path = Self::resolve_path(path)?;
-        
+
         let entries = fs::read_dir(&full_path)
             .map_err(|e| format!("Failed to read directory: {}", e))?;
-        
+
         let mut names = Vec::new();
         for entry in entries {
             if let Ok(entry) = entry {
@@ -107,7 +270,7 @@ impl FsAPI {
                 }
             }
         }
-        
+
         Ok(names)
     }
 }
@@ -203,4 +366,65 @@ mod tests {
         // Cleanup
         fs::remove_dir_all(&sandbox).ok();
     }
+
+    #[test]
+    fn test_virtual_numa_topology_is_readable_json() {
+        let data = FsAPI::read_file("/.zenith/numa/topology").unwrap();
+        let text = String::from_utf8(data).unwrap();
+        assert!(text.starts_with('{'));
+        assert!(text.contains("\"num_cpus\""));
+    }
+
+    #[test]
+    fn test_virtual_paths_are_read_only() {
+        assert!(FsAPI::write_file("/.zenith/numa/topology", b"nope").is_err());
+        assert!(FsAPI::delete_file("/.zenith/numa/topology").is_err());
+    }
+
+    #[test]
+    fn test_virtual_path_registry_and_listing() {
+        FsAPI::register_virtual_path("/.zenith/self/limits", || b"{\"max_memory_bytes\":1}".to_vec());
+
+        assert!(FsAPI::list_dir(VIRTUAL_ROOT).unwrap().contains(&"self".to_string()));
+        assert_eq!(
+            FsAPI::list_dir("/.zenith/self").unwrap(),
+            vec!["limits".to_string()]
+        );
+        assert_eq!(
+            FsAPI::read_file("/.zenith/self/limits").unwrap(),
+            b"{\"max_memory_bytes\":1}"
+        );
+        assert!(FsAPI::exists("/.zenith/self/limits"));
+
+        FsAPI::unregister_virtual_path("/.zenith/self/limits");
+        assert!(!FsAPI::exists("/.zenith/self/limits"));
+    }
+
+    #[test]
+    fn test_unknown_virtual_path_errors() {
+        assert!(FsAPI::read_file("/.zenith/does/not/exist").is_err());
+        assert!(!FsAPI::exists("/.zenith/does/not/exist"));
+    }
+
+    #[test]
+    fn test_read_file_denied_without_fs_capability() {
+        crate::capability_context::install(std::sync::Arc::new(crate::Capabilities::none()));
+        assert!(FsAPI::read_file("/.zenith/self/limits").is_err());
+        crate::capability_context::clear();
+    }
+
+    #[test]
+    fn test_write_file_denied_with_read_only_fs_capability() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("zenith_fs_api_capability_test.txt");
+
+        crate::capability_context::install(std::sync::Arc::new(
+            crate::Capabilities::none().allow(crate::Capability::Fs, crate::CapabilityLimit { read_only: true, ..Default::default() }),
+        ));
+        let result = FsAPI::write_file(path.to_str().unwrap(), b"data");
+        crate::capability_context::clear();
+
+        assert!(result.is_err());
+        assert!(!path.exists());
+    }
 }
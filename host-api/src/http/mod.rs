@@ -1,7 +1,10 @@
 /// HTTP Client Module for WASM Plugins
 /// Provides HTTP request capabilities with sandboxing
 
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 static HTTP_CALL_COUNT: AtomicU64 = AtomicU64::new(0);
 
@@ -34,37 +37,417 @@ pub struct HttpResponse {
     pub headers: Vec<(String, String)>,
 }
 
+/// Performs the actual request/response cycle for [`HttpAPI`]. Abstracting
+/// the transport behind a trait lets plugins talk to real endpoints in
+/// production while the test suite stays hermetic with [`MockRequester`].
+pub trait HttpRequester: Send + Sync {
+    fn execute(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        body: Option<&[u8]>,
+        headers: &[(String, String)],
+        timeout_ms: u64,
+    ) -> Result<HttpResponse, String>;
+}
+
+/// Returns a fixed, successful response without touching the network.
+/// Used as the requester in tests.
+pub struct MockRequester;
+
+impl HttpRequester for MockRequester {
+    fn execute(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        _body: Option<&[u8]>,
+        _headers: &[(String, String)],
+        _timeout_ms: u64,
+    ) -> Result<HttpResponse, String> {
+        tracing::debug!("mock HTTP {:?} request to {}", method, url);
+        Ok(HttpResponse {
+            status_code: 200,
+            body: b"{\"success\": true}".to_vec(),
+            headers: vec![
+                ("content-type".to_string(), "application/json".to_string()),
+            ],
+        })
+    }
+}
+
+/// Performs requests with `reqwest`, blocking the calling (plugin host)
+/// thread on a private single-threaded tokio runtime.
+pub struct ReqwestRequester {
+    runtime: tokio::runtime::Runtime,
+    client: reqwest::Client,
+}
+
+impl ReqwestRequester {
+    pub fn new() -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start tokio runtime for HTTP requester");
+        Self {
+            runtime,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+impl Default for ReqwestRequester {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpRequester for ReqwestRequester {
+    fn execute(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        body: Option<&[u8]>,
+        headers: &[(String, String)],
+        timeout_ms: u64,
+    ) -> Result<HttpResponse, String> {
+        self.runtime.block_on(async {
+            let method = match method {
+                HttpMethod::Get => reqwest::Method::GET,
+                HttpMethod::Post => reqwest::Method::POST,
+                HttpMethod::Put => reqwest::Method::PUT,
+                HttpMethod::Delete => reqwest::Method::DELETE,
+            };
+
+            let mut req = self
+                .client
+                .request(method, url)
+                .timeout(std::time::Duration::from_millis(timeout_ms));
+            for (name, value) in headers {
+                req = req.header(name, value);
+            }
+            if let Some(body) = body {
+                req = req.body(body.to_vec());
+            }
+
+            let response = req.send().await.map_err(|e| e.to_string())?;
+            let status_code = response.status().as_u16();
+            let headers = response
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                    (name.to_string(), value.to_str().unwrap_or_default().to_string())
+                })
+                .collect();
+            let body = response.bytes().await.map_err(|e| e.to_string())?.to_vec();
+
+            Ok(HttpResponse { status_code, body, headers })
+        })
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref REQUESTER: RwLock<Arc<dyn HttpRequester>> = RwLock::new(Arc::new(ReqwestRequester::new()));
+    static ref POLICY: RwLock<HttpPolicy> = RwLock::new(HttpPolicy::default());
+    static ref PLUGIN_STATE: Mutex<HashMap<String, PluginHttpState>> = Mutex::new(HashMap::new());
+}
+
+/// Sandbox policy applied to every `HttpAPI` call.
+#[derive(Debug, Clone)]
+pub struct HttpPolicy {
+    /// Hosts (not URL prefixes) that plugins may reach. A request is
+    /// rejected unless its URL's host matches one of these exactly. The
+    /// sentinel `"insecure:allow-all"` disables the check entirely, for
+    /// dev/testing only. An empty list rejects every request.
+    pub allowed_hosts: Vec<String>,
+    /// Advertise `Accept-Encoding: gzip, deflate` and transparently inflate
+    /// a compressed response before handing it back. Disable for plugins
+    /// that need the raw wire bytes.
+    pub decompress_responses: bool,
+    /// Upper bound on a decompressed body, to cap the work a malicious or
+    /// misbehaving server can force onto the host via a decompression bomb.
+    pub max_decompressed_bytes: usize,
+    /// Per-plugin call/concurrency/rate limits.
+    pub quota: HttpQuota,
+}
+
+impl Default for HttpPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: vec![
+                "localhost".to_string(),
+                "api.example.com".to_string(),
+                "httpbin.org".to_string(),
+            ],
+            decompress_responses: true,
+            max_decompressed_bytes: 16 * 1024 * 1024,
+            quota: HttpQuota::default(),
+        }
+    }
+}
+
+/// Per-plugin HTTP sandbox limits, enforced before a request is dispatched.
+#[derive(Debug, Clone, Copy)]
+pub struct HttpQuota {
+    /// Lifetime call budget for a single plugin.
+    pub max_calls: u64,
+    /// Requests the same plugin may have in flight at once.
+    pub max_concurrent: u32,
+    /// Steady-state request rate, enforced with a token bucket (capacity
+    /// equal to one second's worth of tokens).
+    pub requests_per_second: f64,
+}
+
+impl Default for HttpQuota {
+    fn default() -> Self {
+        Self {
+            max_calls: u64::MAX,
+            max_concurrent: u32::MAX,
+            requests_per_second: f64::INFINITY,
+        }
+    }
+}
+
+/// Token-bucket and in-flight bookkeeping for a single plugin.
+struct PluginHttpState {
+    call_count: u64,
+    in_flight: u32,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl PluginHttpState {
+    fn new() -> Self {
+        Self { call_count: 0, in_flight: 0, tokens: 0.0, last_refill: Instant::now() }
+    }
+}
+
 /// HTTP API
 pub struct HttpAPI;
 
 impl HttpAPI {
-    /// Make an HTTP request (synchronous for MVP)
+    /// Replaces the requester backing every `HttpAPI` call. Used in tests
+    /// to swap in a [`MockRequester`]; production hosts may swap in a
+    /// differently-configured [`ReqwestRequester`] (custom proxy, etc.).
+    pub fn set_requester(requester: Arc<dyn HttpRequester>) {
+        *REQUESTER.write().unwrap() = requester;
+    }
+
+    /// Replaces the host allow-list enforced by [`Self::is_url_allowed`].
+    pub fn set_policy(policy: HttpPolicy) {
+        *POLICY.write().unwrap() = policy;
+    }
+
+    /// Make an HTTP request, without any caller-supplied headers, on behalf
+    /// of an unidentified caller.
     pub fn request(
         method: HttpMethod,
         url: &str,
         body: Option<&[u8]>,
         timeout_ms: u64,
+    ) -> Result<HttpResponse, String> {
+        Self::request_with_headers(method, url, body, &[], timeout_ms, None)
+    }
+
+    /// Make an HTTP request, forwarding `extra_headers` (e.g. `Authorization`,
+    /// `Content-Type`) to the transport. Response headers are filtered
+    /// through [`Self::is_header_allowed`] before being handed back, so a
+    /// plugin can't read or re-forward cookies or CORS state it shouldn't.
+    ///
+    /// `plugin_id` identifies the caller for quota accounting (see
+    /// [`HttpQuota`]); `None` is billed to a shared "unknown" bucket.
+    ///
+    /// Denied before any of the above if the calling plugin isn't granted
+    /// [`crate::Capability::Http`] (see [`crate::capability_context`]).
+    pub fn request_with_headers(
+        method: HttpMethod,
+        url: &str,
+        body: Option<&[u8]>,
+        extra_headers: &[(String, String)],
+        timeout_ms: u64,
+        plugin_id: Option<&str>,
     ) -> Result<HttpResponse, String> {
         HTTP_CALL_COUNT.fetch_add(1, Ordering::Relaxed);
-        
+
+        crate::capability_context::require(crate::Capability::Http, body.map_or(0, <[u8]>::len))
+            .map_err(|_| "capability denied: Http".to_string())?;
+
         // Security: Validate URL (allow-list in production)
         if !Self::is_url_allowed(url) {
             return Err("URL not in allow-list".to_string());
         }
-        
-        // For MVP, return mock response
-        // In production, use reqwest or similar
+
+        let policy = POLICY.read().unwrap().clone();
+        let plugin_key = plugin_id.unwrap_or("unknown");
+        Self::acquire_quota(plugin_key, &policy.quota)?;
+
         tracing::info!("HTTP {:?} request to {}", method, url);
-        
-        Ok(HttpResponse {
-            status_code: 200,
-            body: b"{\"success\": true}".to_vec(),
-            headers: vec![
-                ("content-type".to_string(), "application/json".to_string()),
-            ],
-        })
+
+        let mut headers = extra_headers.to_vec();
+        if policy.decompress_responses {
+            headers.push(("accept-encoding".to_string(), "gzip, deflate".to_string()));
+        }
+
+        let requester = REQUESTER.read().unwrap().clone();
+        let result = Self::execute_with_timeout(requester, method, url, body, headers, timeout_ms);
+        Self::release_quota(plugin_key);
+
+        let mut response = result?;
+
+        if policy.decompress_responses {
+            Self::decompress(&mut response, policy.max_decompressed_bytes)?;
+        }
+
+        response.headers.retain(|(name, _)| Self::is_header_allowed(name));
+
+        Ok(response)
     }
-    
+
+    /// Runs `requester.execute` on its own thread and enforces `timeout_ms`
+    /// regardless of whether the requester itself honors it, so a hanging
+    /// or misbehaving transport can't block the host indefinitely.
+    fn execute_with_timeout(
+        requester: Arc<dyn HttpRequester>,
+        method: HttpMethod,
+        url: &str,
+        body: Option<&[u8]>,
+        headers: Vec<(String, String)>,
+        timeout_ms: u64,
+    ) -> Result<HttpResponse, String> {
+        let url = url.to_string();
+        let body = body.map(|b| b.to_vec());
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            let result = requester.execute(method, &url, body.as_deref(), &headers, timeout_ms);
+            let _ = tx.send(result);
+        });
+
+        rx.recv_timeout(Duration::from_millis(timeout_ms))
+            .unwrap_or_else(|_| Err(format!("HTTP request timed out after {timeout_ms}ms")))
+    }
+
+    /// Checks and books a plugin's call/concurrency/rate quota before a
+    /// request is dispatched. Every `Ok` acquisition must be paired with a
+    /// [`Self::release_quota`] once the request completes.
+    fn acquire_quota(plugin_id: &str, quota: &HttpQuota) -> Result<(), String> {
+        let mut states = PLUGIN_STATE.lock().unwrap();
+        let state = states.entry(plugin_id.to_string()).or_insert_with(PluginHttpState::new);
+
+        if state.call_count >= quota.max_calls {
+            return Err(format!(
+                "plugin '{plugin_id}' exceeded its HTTP call quota of {} calls",
+                quota.max_calls
+            ));
+        }
+        if state.in_flight >= quota.max_concurrent {
+            return Err(format!(
+                "plugin '{plugin_id}' exceeded its HTTP concurrency limit of {} in-flight requests",
+                quota.max_concurrent
+            ));
+        }
+        if quota.requests_per_second.is_finite() {
+            let now = Instant::now();
+            let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+            state.last_refill = now;
+            let capacity = quota.requests_per_second.max(1.0);
+            state.tokens = (state.tokens + elapsed * quota.requests_per_second).min(capacity);
+            if state.tokens < 1.0 {
+                return Err(format!(
+                    "plugin '{plugin_id}' exceeded its HTTP rate limit of {} requests/sec",
+                    quota.requests_per_second
+                ));
+            }
+            state.tokens -= 1.0;
+        }
+
+        state.call_count += 1;
+        state.in_flight += 1;
+        Ok(())
+    }
+
+    /// Releases the in-flight slot acquired by [`Self::acquire_quota`].
+    fn release_quota(plugin_id: &str) {
+        if let Some(state) = PLUGIN_STATE.lock().unwrap().get_mut(plugin_id) {
+            state.in_flight = state.in_flight.saturating_sub(1);
+        }
+    }
+
+    /// Lifetime call count for a single plugin, as tracked by [`HttpQuota`].
+    pub fn plugin_call_count(plugin_id: &str) -> u64 {
+        PLUGIN_STATE.lock().unwrap().get(plugin_id).map(|s| s.call_count).unwrap_or(0)
+    }
+
+    /// Clears a plugin's quota bookkeeping (call count, in-flight count,
+    /// rate-limit tokens). Used in tests between cases that share the
+    /// global plugin-state map.
+    pub fn reset_plugin_quota(plugin_id: &str) {
+        PLUGIN_STATE.lock().unwrap().remove(plugin_id);
+    }
+
+    /// Response headers a guest may see. Drops hop-by-hop headers (RFC 7230
+    /// §7.6.1) and headers that would leak cookie/CORS/framing state the
+    /// sandbox has no business reading or replaying.
+    fn is_header_allowed(name: &str) -> bool {
+        let name = name.to_ascii_lowercase();
+        if name.starts_with("access-control") {
+            return false;
+        }
+        !matches!(
+            name.as_str(),
+            "set-cookie"
+                | "host"
+                | "content-length"
+                | "connection"
+                | "keep-alive"
+                | "proxy-authenticate"
+                | "proxy-authorization"
+                | "te"
+                | "trailer"
+                | "transfer-encoding"
+                | "upgrade"
+        )
+    }
+
+    /// Inflates `response.body` in place if its `Content-Encoding` header
+    /// names a supported codec, then strips that header. No-op if the
+    /// header is absent or names something we don't handle. Bails out if
+    /// the inflated size would exceed `max_decompressed_bytes`, to bound
+    /// the work a compression-bomb response can force on the host.
+    fn decompress(response: &mut HttpResponse, max_decompressed_bytes: usize) -> Result<(), String> {
+        use std::io::Read;
+
+        let Some((_, encoding)) = response
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-encoding"))
+            .map(|(name, value)| (name.clone(), value.to_ascii_lowercase()))
+        else {
+            return Ok(());
+        };
+
+        let mut decoded = Vec::new();
+        let read_result = match encoding.as_str() {
+            "gzip" => flate2::read::GzDecoder::new(&response.body[..])
+                .take(max_decompressed_bytes as u64 + 1)
+                .read_to_end(&mut decoded),
+            "deflate" => flate2::read::DeflateDecoder::new(&response.body[..])
+                .take(max_decompressed_bytes as u64 + 1)
+                .read_to_end(&mut decoded),
+            _ => return Ok(()),
+        };
+        read_result.map_err(|e| format!("failed to decompress {encoding} response: {e}"))?;
+
+        if decoded.len() > max_decompressed_bytes {
+            return Err(format!(
+                "decompressed response exceeded {max_decompressed_bytes} byte limit"
+            ));
+        }
+
+        response.body = decoded;
+        response.headers.retain(|(name, _)| !name.eq_ignore_ascii_case("content-encoding"));
+        Ok(())
+    }
+
     /// Simple GET request
     pub fn get(url: &str) -> Result<HttpResponse, String> {
         Self::request(HttpMethod::Get, url, None, 5000)
@@ -75,13 +458,25 @@ impl HttpAPI {
         Self::request(HttpMethod::Post, url, Some(body), 5000)
     }
     
-    /// Check if URL is allowed (security sandbox)
+    /// Check if URL is allowed by the configured [`HttpPolicy`]. Matches on
+    /// the parsed host, not a string prefix, so a lookalike host like
+    /// `api.example.com.evil.com` can't impersonate an allowed one.
     fn is_url_allowed(url: &str) -> bool {
-        // In production, maintain an allow-list
-        // For MVP, allow localhost and example domains
-        url.starts_with("http://localhost") ||
-        url.starts_with("https://api.example.com") ||
-        url.starts_with("https://httpbin.org")
+        let policy = POLICY.read().unwrap();
+
+        if policy.allowed_hosts.iter().any(|h| h == "insecure:allow-all") {
+            return true;
+        }
+        if policy.allowed_hosts.is_empty() {
+            return false;
+        }
+
+        let Some(host) = url::Url::parse(url).ok().and_then(|u| u.host_str().map(str::to_string))
+        else {
+            return false;
+        };
+
+        policy.allowed_hosts.iter().any(|allowed| *allowed == host)
     }
     
     /// Get HTTP call count
@@ -108,7 +503,7 @@ pub unsafe extern "C" fn zenith_http_get(
         Err(_) => return -2,
     };
     
-    match HttpAPI::get(url) {
+    match HttpAPI::request_with_headers(HttpMethod::Get, url, None, &[], 5000, Some("plugin")) {
         Ok(response) => {
             let copy_len = response.body.len().min(out_len);
             let out_slice = std::slice::from_raw_parts_mut(out_ptr, copy_len);
@@ -140,7 +535,7 @@ pub unsafe extern "C" fn zenith_http_post(
     
     let body = std::slice::from_raw_parts(body_ptr, body_len);
     
-    match HttpAPI::post(url, body) {
+    match HttpAPI::request_with_headers(HttpMethod::Post, url, Some(body), &[], 5000, Some("plugin")) {
         Ok(response) => {
             let copy_len = response.body.len().min(out_len);
             let out_slice = std::slice::from_raw_parts_mut(out_ptr, copy_len);
@@ -151,25 +546,253 @@ pub unsafe extern "C" fn zenith_http_post(
     }
 }
 
+/// A response retained host-side behind a handle, so a guest can page
+/// through a body larger than any single output buffer.
+struct StoredResponse {
+    body: Vec<u8>,
+    /// Read cursor into `body`, advanced by `zenith_http_body_read`.
+    pos: usize,
+    headers: Vec<(String, String)>,
+}
+
+/// Parses a `name\0value\0name\0value\0...` header block as passed by a
+/// guest across the ABI boundary. A trailing unpaired segment is ignored.
+unsafe fn parse_header_block(ptr: *const u8, len: usize) -> Vec<(String, String)> {
+    if ptr.is_null() || len == 0 {
+        return Vec::new();
+    }
+    let raw = std::slice::from_raw_parts(ptr, len);
+    // `split` on a trailing NUL yields a final empty slice; drop it so a
+    // well-formed "name\0value\0" block doesn't leave a bogus empty pair.
+    let mut segments: Vec<&[u8]> = raw.split(|&b| b == 0).collect();
+    if segments.last().is_some_and(|s| s.is_empty()) {
+        segments.pop();
+    }
+
+    segments
+        .chunks_exact(2)
+        .filter_map(|pair| {
+            let name = std::str::from_utf8(pair[0]).ok()?;
+            let value = std::str::from_utf8(pair[1]).ok()?;
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Serializes headers into the same `name\0value\0...` wire format expected
+/// by a guest reading them back.
+fn serialize_header_block(headers: &[(String, String)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for (name, value) in headers {
+        out.extend_from_slice(name.as_bytes());
+        out.push(0);
+        out.extend_from_slice(value.as_bytes());
+        out.push(0);
+    }
+    out
+}
+
+/// Host-side table of in-flight response handles.
+struct HandleState {
+    responses: std::collections::HashMap<u32, StoredResponse>,
+    current_handle: u32,
+}
+
+impl HandleState {
+    fn new() -> Self {
+        Self { responses: std::collections::HashMap::new(), current_handle: 0 }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref HANDLES: RwLock<HandleState> = RwLock::new(HandleState::new());
+}
+
+/// Issues a request and retains the full response host-side under a new
+/// handle, instead of truncating it into a guest buffer. `headers_ptr`/
+/// `headers_len` is an optional `name\0value\0...` block of request headers
+/// (pass a null pointer or zero length for none). Writes the status code
+/// and total body length to `out_status`/`out_body_len` and returns the
+/// handle (read with `zenith_http_body_read`, released with
+/// `zenith_http_close`).
+#[no_mangle]
+pub unsafe extern "C" fn zenith_http_request(
+    method: u32,
+    url_ptr: *const u8,
+    url_len: usize,
+    body_ptr: *const u8,
+    body_len: usize,
+    headers_ptr: *const u8,
+    headers_len: usize,
+    timeout_ms: u64,
+    out_status: *mut u16,
+    out_body_len: *mut usize,
+) -> i64 {
+    if url_ptr.is_null() || out_status.is_null() || out_body_len.is_null() {
+        return -1;
+    }
+
+    let url_slice = std::slice::from_raw_parts(url_ptr, url_len);
+    let url = match std::str::from_utf8(url_slice) {
+        Ok(s) => s,
+        Err(_) => return -2,
+    };
+
+    let body = if body_ptr.is_null() || body_len == 0 {
+        None
+    } else {
+        Some(std::slice::from_raw_parts(body_ptr, body_len))
+    };
+
+    let headers = parse_header_block(headers_ptr, headers_len);
+
+    match HttpAPI::request_with_headers(
+        HttpMethod::from(method),
+        url,
+        body,
+        &headers,
+        timeout_ms,
+        Some("plugin"),
+    ) {
+        Ok(response) => {
+            *out_status = response.status_code;
+            *out_body_len = response.body.len();
+
+            let mut state = HANDLES.write().unwrap();
+            state.current_handle += 1;
+            let handle = state.current_handle;
+            state.responses.insert(
+                handle,
+                StoredResponse { body: response.body, pos: 0, headers: response.headers },
+            );
+            handle as i64
+        }
+        Err(_) => -3,
+    }
+}
+
+/// Total length of `handle`'s response headers serialized as
+/// `name\0value\0...` (already filtered through `HttpAPI::is_header_allowed`).
+/// Returns `-1` if `handle` is unknown.
+#[no_mangle]
+pub extern "C" fn zenith_http_response_header_len(handle: u32) -> isize {
+    match HANDLES.read().unwrap().responses.get(&handle) {
+        Some(stored) => serialize_header_block(&stored.headers).len() as isize,
+        None => -1,
+    }
+}
+
+/// Copies the serialized response header block (see
+/// `zenith_http_response_header_len`) into `out_ptr`, truncated to
+/// `out_len`. Returns the number of bytes copied, or `-1` if `handle` is
+/// unknown.
+#[no_mangle]
+pub unsafe extern "C" fn zenith_http_response_header_read(
+    handle: u32,
+    out_ptr: *mut u8,
+    out_len: usize,
+) -> isize {
+    if out_ptr.is_null() {
+        return -1;
+    }
+
+    let state = HANDLES.read().unwrap();
+    let Some(stored) = state.responses.get(&handle) else {
+        return -1;
+    };
+
+    let serialized = serialize_header_block(&stored.headers);
+    let copy_len = serialized.len().min(out_len);
+    let out_slice = std::slice::from_raw_parts_mut(out_ptr, copy_len);
+    out_slice.copy_from_slice(&serialized[..copy_len]);
+    copy_len as isize
+}
+
+/// Copies the next chunk of a retained response body starting at its read
+/// cursor, advancing the cursor by the amount copied. Returns `0` at EOF,
+/// or a negative value if `handle` is unknown.
+#[no_mangle]
+pub unsafe extern "C" fn zenith_http_body_read(
+    handle: u32,
+    out_ptr: *mut u8,
+    out_len: usize,
+) -> isize {
+    if out_ptr.is_null() {
+        return -1;
+    }
+
+    let mut state = HANDLES.write().unwrap();
+    let Some(stored) = state.responses.get_mut(&handle) else {
+        return -1;
+    };
+
+    let remaining = stored.body.len() - stored.pos;
+    if remaining == 0 {
+        return 0;
+    }
+
+    let copy_len = remaining.min(out_len);
+    let out_slice = std::slice::from_raw_parts_mut(out_ptr, copy_len);
+    out_slice.copy_from_slice(&stored.body[stored.pos..stored.pos + copy_len]);
+    stored.pos += copy_len;
+    copy_len as isize
+}
+
+/// Releases a response handle. Returns `0` on success, `-1` if unknown.
+#[no_mangle]
+pub extern "C" fn zenith_http_close(handle: u32) -> i32 {
+    if HANDLES.write().unwrap().responses.remove(&handle).is_some() {
+        0
+    } else {
+        -1
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_url_validation() {
+        HttpAPI::set_policy(HttpPolicy::default());
         assert!(HttpAPI::is_url_allowed("http://localhost:8080/api"));
         assert!(HttpAPI::is_url_allowed("https://api.example.com/data"));
         assert!(!HttpAPI::is_url_allowed("https://malicious.com"));
     }
 
+    #[test]
+    fn test_url_validation_rejects_lookalike_host() {
+        HttpAPI::set_policy(HttpPolicy::default());
+        assert!(!HttpAPI::is_url_allowed("https://api.example.com.evil.com/data"));
+    }
+
+    #[test]
+    fn test_url_validation_allow_all_sentinel() {
+        HttpAPI::set_policy(HttpPolicy {
+            allowed_hosts: vec!["insecure:allow-all".to_string()],
+            ..HttpPolicy::default()
+        });
+        assert!(HttpAPI::is_url_allowed("https://anything.example"));
+        HttpAPI::set_policy(HttpPolicy::default());
+    }
+
+    #[test]
+    fn test_url_validation_rejects_when_empty() {
+        HttpAPI::set_policy(HttpPolicy { allowed_hosts: vec![], ..HttpPolicy::default() });
+        assert!(!HttpAPI::is_url_allowed("http://localhost/test"));
+        HttpAPI::set_policy(HttpPolicy::default());
+    }
+
     #[test]
     fn test_http_get() {
+        HttpAPI::set_requester(Arc::new(MockRequester));
         let response = HttpAPI::get("http://localhost/test").unwrap();
         assert_eq!(response.status_code, 200);
     }
 
     #[test]
     fn test_http_post() {
+        HttpAPI::set_requester(Arc::new(MockRequester));
         let response = HttpAPI::post("http://localhost/api", b"{\"test\": 1}").unwrap();
         assert_eq!(response.status_code, 200);
     }
@@ -179,4 +802,288 @@ mod tests {
         let result = HttpAPI::get("https://evil.com");
         assert!(result.is_err());
     }
+
+    fn gzip_compress(data: &[u8]) -> Vec<u8> {
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    struct GzipBodyRequester(Vec<u8>);
+
+    impl HttpRequester for GzipBodyRequester {
+        fn execute(
+            &self,
+            _method: HttpMethod,
+            _url: &str,
+            _body: Option<&[u8]>,
+            _headers: &[(String, String)],
+            _timeout_ms: u64,
+        ) -> Result<HttpResponse, String> {
+            Ok(HttpResponse {
+                status_code: 200,
+                body: self.0.clone(),
+                headers: vec![("content-encoding".to_string(), "gzip".to_string())],
+            })
+        }
+    }
+
+    #[test]
+    fn test_gzip_response_is_transparently_decompressed() {
+        HttpAPI::set_policy(HttpPolicy::default());
+        HttpAPI::set_requester(Arc::new(GzipBodyRequester(gzip_compress(b"hello decompressed"))));
+
+        let response = HttpAPI::get("http://localhost/gz").unwrap();
+        assert_eq!(response.body, b"hello decompressed");
+        assert!(!response.headers.iter().any(|(k, _)| k.eq_ignore_ascii_case("content-encoding")));
+    }
+
+    #[test]
+    fn test_gzip_decompression_bomb_is_rejected() {
+        HttpAPI::set_policy(HttpPolicy {
+            max_decompressed_bytes: 4,
+            ..HttpPolicy::default()
+        });
+        HttpAPI::set_requester(Arc::new(GzipBodyRequester(gzip_compress(b"this is way more than 4 bytes"))));
+
+        let result = HttpAPI::get("http://localhost/bomb");
+        assert!(result.is_err());
+        HttpAPI::set_policy(HttpPolicy::default());
+    }
+
+    struct LargeBodyRequester;
+
+    impl HttpRequester for LargeBodyRequester {
+        fn execute(
+            &self,
+            _method: HttpMethod,
+            _url: &str,
+            _body: Option<&[u8]>,
+            _headers: &[(String, String)],
+            _timeout_ms: u64,
+        ) -> Result<HttpResponse, String> {
+            Ok(HttpResponse { status_code: 200, body: vec![7u8; 10], headers: vec![] })
+        }
+    }
+
+    #[test]
+    fn test_handle_based_body_read_pages_through_full_body() {
+        HttpAPI::set_requester(Arc::new(LargeBodyRequester));
+        HttpAPI::set_policy(HttpPolicy::default());
+
+        let mut status = 0u16;
+        let mut total_len = 0usize;
+        let url = b"http://localhost/large";
+        let handle = unsafe {
+            zenith_http_request(
+                0,
+                url.as_ptr(),
+                url.len(),
+                std::ptr::null(),
+                0,
+                std::ptr::null(),
+                0,
+                5000,
+                &mut status,
+                &mut total_len,
+            )
+        };
+        assert!(handle >= 0);
+        assert_eq!(status, 200);
+        assert_eq!(total_len, 10);
+
+        let mut out = [0u8; 4];
+        let n1 =
+            unsafe { zenith_http_body_read(handle as u32, out.as_mut_ptr(), out.len()) };
+        assert_eq!(n1, 4);
+        let n2 =
+            unsafe { zenith_http_body_read(handle as u32, out.as_mut_ptr(), out.len()) };
+        assert_eq!(n2, 4);
+        let n3 =
+            unsafe { zenith_http_body_read(handle as u32, out.as_mut_ptr(), out.len()) };
+        assert_eq!(n3, 2);
+        let eof =
+            unsafe { zenith_http_body_read(handle as u32, out.as_mut_ptr(), out.len()) };
+        assert_eq!(eof, 0);
+
+        assert_eq!(zenith_http_close(handle as u32), 0);
+        assert_eq!(zenith_http_close(handle as u32), -1);
+    }
+
+    #[test]
+    fn test_parse_header_block_roundtrips_serialize() {
+        let headers = vec![
+            ("authorization".to_string(), "Bearer token".to_string()),
+            ("content-type".to_string(), "application/json".to_string()),
+        ];
+        let serialized = serialize_header_block(&headers);
+        let parsed = unsafe { parse_header_block(serialized.as_ptr(), serialized.len()) };
+        assert_eq!(parsed, headers);
+    }
+
+    #[test]
+    fn test_is_header_allowed_drops_sensitive_and_hop_by_hop_headers() {
+        assert!(!HttpAPI::is_header_allowed("Set-Cookie"));
+        assert!(!HttpAPI::is_header_allowed("Host"));
+        assert!(!HttpAPI::is_header_allowed("Content-Length"));
+        assert!(!HttpAPI::is_header_allowed("Access-Control-Allow-Origin"));
+        assert!(!HttpAPI::is_header_allowed("Transfer-Encoding"));
+        assert!(HttpAPI::is_header_allowed("Content-Type"));
+        assert!(HttpAPI::is_header_allowed("X-Request-Id"));
+    }
+
+    struct EchoRequester;
+
+    impl HttpRequester for EchoRequester {
+        fn execute(
+            &self,
+            _method: HttpMethod,
+            _url: &str,
+            _body: Option<&[u8]>,
+            headers: &[(String, String)],
+            _timeout_ms: u64,
+        ) -> Result<HttpResponse, String> {
+            Ok(HttpResponse {
+                status_code: 200,
+                body: vec![],
+                headers: vec![
+                    ("set-cookie".to_string(), "session=secret".to_string()),
+                    ("content-type".to_string(), "application/json".to_string()),
+                    ("x-echo-auth".to_string(), headers.iter()
+                        .find(|(name, _)| name == "authorization")
+                        .map(|(_, v)| v.clone())
+                        .unwrap_or_default()),
+                ],
+            })
+        }
+    }
+
+    #[test]
+    fn test_request_headers_are_forwarded_and_response_headers_filtered() {
+        HttpAPI::set_policy(HttpPolicy::default());
+        HttpAPI::set_requester(Arc::new(EchoRequester));
+
+        let response = HttpAPI::request_with_headers(
+            HttpMethod::Get,
+            "http://localhost/echo",
+            None,
+            &[("authorization".to_string(), "Bearer abc".to_string())],
+            5000,
+            None,
+        )
+        .unwrap();
+
+        assert!(!response.headers.iter().any(|(k, _)| k.eq_ignore_ascii_case("set-cookie")));
+        assert_eq!(
+            response.headers.iter().find(|(k, _)| k == "x-echo-auth").map(|(_, v)| v.as_str()),
+            Some("Bearer abc")
+        );
+    }
+
+    #[test]
+    fn test_response_header_read_abi_returns_filtered_serialized_block() {
+        HttpAPI::set_policy(HttpPolicy::default());
+        HttpAPI::set_requester(Arc::new(EchoRequester));
+
+        let url = b"http://localhost/echo";
+        let mut status = 0u16;
+        let mut total_len = 0usize;
+        let handle = unsafe {
+            zenith_http_request(
+                0,
+                url.as_ptr(),
+                url.len(),
+                std::ptr::null(),
+                0,
+                std::ptr::null(),
+                0,
+                5000,
+                &mut status,
+                &mut total_len,
+            )
+        };
+        assert!(handle >= 0);
+
+        let header_len = zenith_http_response_header_len(handle as u32);
+        assert!(header_len > 0);
+
+        let mut buf = vec![0u8; header_len as usize];
+        let copied =
+            unsafe { zenith_http_response_header_read(handle as u32, buf.as_mut_ptr(), buf.len()) };
+        assert_eq!(copied, header_len);
+
+        let parsed = unsafe { parse_header_block(buf.as_ptr(), buf.len()) };
+        assert!(!parsed.iter().any(|(name, _)| name.eq_ignore_ascii_case("set-cookie")));
+
+        zenith_http_close(handle as u32);
+    }
+
+    #[test]
+    fn test_quota_rejects_once_call_budget_exhausted() {
+        HttpAPI::reset_plugin_quota("quota-calls");
+        HttpAPI::set_policy(HttpPolicy { quota: HttpQuota { max_calls: 2, ..HttpQuota::default() }, ..HttpPolicy::default() });
+        HttpAPI::set_requester(Arc::new(MockRequester));
+
+        for _ in 0..2 {
+            HttpAPI::request_with_headers(
+                HttpMethod::Get, "http://localhost/a", None, &[], 5000, Some("quota-calls"),
+            )
+            .unwrap();
+        }
+        let result = HttpAPI::request_with_headers(
+            HttpMethod::Get, "http://localhost/a", None, &[], 5000, Some("quota-calls"),
+        );
+        assert!(result.is_err());
+
+        HttpAPI::reset_plugin_quota("quota-calls");
+        HttpAPI::set_policy(HttpPolicy::default());
+    }
+
+    struct BlockingRequester;
+
+    impl HttpRequester for BlockingRequester {
+        fn execute(
+            &self,
+            _method: HttpMethod,
+            _url: &str,
+            _body: Option<&[u8]>,
+            _headers: &[(String, String)],
+            _timeout_ms: u64,
+        ) -> Result<HttpResponse, String> {
+            std::thread::sleep(Duration::from_secs(60));
+            Ok(HttpResponse { status_code: 200, body: vec![], headers: vec![] })
+        }
+    }
+
+    #[test]
+    fn test_timeout_is_enforced_even_when_requester_ignores_it() {
+        HttpAPI::reset_plugin_quota("quota-timeout");
+        HttpAPI::set_policy(HttpPolicy::default());
+        HttpAPI::set_requester(Arc::new(BlockingRequester));
+
+        let result = HttpAPI::request_with_headers(
+            HttpMethod::Get, "http://localhost/slow", None, &[], 50, Some("quota-timeout"),
+        );
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("timed out"));
+
+        HttpAPI::reset_plugin_quota("quota-timeout");
+        HttpAPI::set_requester(Arc::new(MockRequester));
+    }
+
+    #[test]
+    fn test_request_denied_without_http_capability() {
+        HttpAPI::set_policy(HttpPolicy::default());
+        HttpAPI::set_requester(Arc::new(MockRequester));
+
+        crate::capability_context::install(std::sync::Arc::new(crate::Capabilities::none()));
+        let result = HttpAPI::request_with_headers(
+            HttpMethod::Get, "http://localhost/ok", None, &[], 1000, Some("quota-capability"),
+        );
+        crate::capability_context::clear();
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("capability denied"));
+    }
 }
@@ -0,0 +1,284 @@
+/// Pluggable storage backends for `KvAPI`.
+///
+/// `MemoryBackend` mirrors the historical in-process-only behavior.
+/// `FileBackend` appends every mutation to an on-disk log and replays it
+/// on open, so plugin-local state survives a host restart.
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use parking_lot::Mutex;
+
+/// Storage behind `KvAPI`. Implementations own their own synchronization;
+/// `KvAPI` only ever needs a `&dyn KvBackend` behind its outer lock.
+pub trait KvBackend: Send + Sync {
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), String>;
+    fn get(&self, key: &str) -> Option<Vec<u8>>;
+    fn delete(&self, key: &str) -> bool;
+    fn exists(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+    fn keys(&self) -> Vec<String>;
+    fn clear(&self);
+    fn count(&self) -> usize;
+}
+
+/// Selects which `KvBackend` a `KvAPI`/`PluginManager` should use.
+#[derive(Debug, Clone)]
+pub enum KvBackendKind {
+    /// Entries live only for the lifetime of the process.
+    Memory,
+    /// Entries are persisted to an append-only log at `path` and survive
+    /// host restarts.
+    File(PathBuf),
+}
+
+impl KvBackendKind {
+    pub fn build(&self) -> Box<dyn KvBackend> {
+        match self {
+            KvBackendKind::Memory => Box::new(MemoryBackend::new()),
+            KvBackendKind::File(path) => Box::new(
+                FileBackend::open(path).unwrap_or_else(|e| {
+                    panic!("failed to open KV log at {path:?}: {e}")
+                }),
+            ),
+        }
+    }
+}
+
+/// In-memory map, no persistence across restarts.
+pub struct MemoryBackend {
+    map: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self {
+            map: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl KvBackend for MemoryBackend {
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), String> {
+        self.map.lock().insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Option<Vec<u8>> {
+        self.map.lock().get(key).cloned()
+    }
+
+    fn delete(&self, key: &str) -> bool {
+        self.map.lock().remove(key).is_some()
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.map.lock().keys().cloned().collect()
+    }
+
+    fn clear(&self) {
+        self.map.lock().clear();
+    }
+
+    fn count(&self) -> usize {
+        self.map.lock().len()
+    }
+}
+
+const OP_SET: u8 = 0;
+const OP_DELETE: u8 = 1;
+
+/// Append-only log backed store: every `set`/`delete` is appended as a
+/// record, and `open` replays the whole log into an in-memory mirror so
+/// reads stay as fast as `MemoryBackend`. The log is never compacted; a
+/// long-running plugin should keep its key count bounded.
+pub struct FileBackend {
+    map: Mutex<HashMap<String, Vec<u8>>>,
+    log: Mutex<File>,
+}
+
+impl FileBackend {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let map = Self::replay(path)?;
+
+        let log = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(Self {
+            map: Mutex::new(map),
+            log: Mutex::new(log),
+        })
+    }
+
+    fn replay(path: &Path) -> io::Result<HashMap<String, Vec<u8>>> {
+        let mut map = HashMap::new();
+
+        let file = match File::open(path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(map),
+            Err(e) => return Err(e),
+        };
+        let mut reader = BufReader::new(file);
+
+        let mut op_byte = [0u8; 1];
+        loop {
+            match reader.read_exact(&mut op_byte) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e),
+            }
+
+            let key = read_frame(&mut reader)?;
+            let key = String::from_utf8(key)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            match op_byte[0] {
+                OP_SET => {
+                    let value = read_frame(&mut reader)?;
+                    map.insert(key, value);
+                }
+                OP_DELETE => {
+                    map.remove(&key);
+                }
+                other => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("unknown KV log opcode {other}"),
+                    ))
+                }
+            }
+        }
+
+        Ok(map)
+    }
+
+    fn append_set(&self, key: &str, value: &[u8]) -> io::Result<()> {
+        let mut log = self.log.lock();
+        log.write_all(&[OP_SET])?;
+        write_frame(&mut *log, key.as_bytes())?;
+        write_frame(&mut *log, value)?;
+        log.flush()
+    }
+
+    fn append_delete(&self, key: &str) -> io::Result<()> {
+        let mut log = self.log.lock();
+        log.write_all(&[OP_DELETE])?;
+        write_frame(&mut *log, key.as_bytes())?;
+        log.flush()
+    }
+}
+
+impl KvBackend for FileBackend {
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), String> {
+        self.append_set(key, value).map_err(|e| e.to_string())?;
+        self.map.lock().insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Option<Vec<u8>> {
+        self.map.lock().get(key).cloned()
+    }
+
+    fn delete(&self, key: &str) -> bool {
+        let existed = self.map.lock().remove(key).is_some();
+        if existed {
+            // Best-effort: the in-memory mirror is already updated, so a
+            // failed append only risks replaying a stale entry on restart.
+            let _ = self.append_delete(key);
+        }
+        existed
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.map.lock().keys().cloned().collect()
+    }
+
+    fn clear(&self) {
+        let keys: Vec<String> = self.keys();
+        for key in keys {
+            self.delete(&key);
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.map.lock().len()
+    }
+}
+
+fn read_frame<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut len_bytes = [0u8; 4];
+    reader.read_exact(&mut len_bytes)?;
+    let len = u32::from_le_bytes(len_bytes) as usize;
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn write_frame<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    writer.write_all(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_backend_basic_operations() {
+        let backend = MemoryBackend::new();
+        backend.set("k", b"v").unwrap();
+        assert_eq!(backend.get("k"), Some(b"v".to_vec()));
+        assert!(backend.delete("k"));
+        assert_eq!(backend.get("k"), None);
+    }
+
+    #[test]
+    fn file_backend_persists_across_reopen() {
+        let path = std::env::temp_dir().join(format!(
+            "zenith_kv_test_{:?}.log",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let backend = FileBackend::open(&path).unwrap();
+            backend.set("a", b"1").unwrap();
+            backend.set("b", b"2").unwrap();
+            backend.delete("a");
+        }
+
+        let reopened = FileBackend::open(&path).unwrap();
+        assert_eq!(reopened.get("a"), None);
+        assert_eq!(reopened.get("b"), Some(b"2".to_vec()));
+        assert_eq!(reopened.count(), 1);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn file_backend_replays_overwrites_in_order() {
+        let path = std::env::temp_dir().join(format!(
+            "zenith_kv_test_overwrite_{:?}.log",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let backend = FileBackend::open(&path).unwrap();
+            backend.set("k", b"first").unwrap();
+            backend.set("k", b"second").unwrap();
+        }
+
+        let reopened = FileBackend::open(&path).unwrap();
+        assert_eq!(reopened.get("k"), Some(b"second".to_vec()));
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -1,58 +1,68 @@
 /// Key-Value Store Module for WASM Plugins
 /// Provides persistent state storage for plugins
+pub mod backend;
 
-use std::sync::RwLock;
-use std::collections::HashMap;
+use parking_lot::RwLock;
+
+use backend::{KvBackend, KvBackendKind, MemoryBackend};
 
 lazy_static::lazy_static! {
-    static ref KV_STORE: RwLock<HashMap<String, Vec<u8>>> = RwLock::new(HashMap::new());
+    static ref KV_STORE: RwLock<Box<dyn KvBackend>> = RwLock::new(Box::new(MemoryBackend::new()));
 }
 
 /// Key-Value store API
 pub struct KvAPI;
 
 impl KvAPI {
-    /// Set a key-value pair
+    /// Swap the active storage backend. Existing entries in the
+    /// previously active backend are not migrated.
+    pub fn configure(kind: &KvBackendKind) {
+        *KV_STORE.write() = kind.build();
+    }
+
+    /// Set a key-value pair.
+    ///
+    /// Denied (with a descriptive error, not a backend error) if the
+    /// calling plugin's capabilities deny [`crate::Capability::Kv`]
+    /// entirely or grant it read-only.
     pub fn set(key: &str, value: &[u8]) -> Result<(), String> {
-        let mut store = KV_STORE.write().unwrap();
-        store.insert(key.to_string(), value.to_vec());
-        Ok(())
+        crate::capability_context::require_write(crate::Capability::Kv, value.len())
+            .map_err(|_| "capability denied: Kv (write)".to_string())?;
+        KV_STORE.read().set(key, value)
     }
-    
-    /// Get a value by key
+
+    /// Get a value by key.
+    ///
+    /// Returns `None` (indistinguishable from a missing key) if the calling
+    /// plugin isn't granted [`crate::Capability::Kv`].
     pub fn get(key: &str) -> Option<Vec<u8>> {
-        let store = KV_STORE.read().unwrap();
-        store.get(key).cloned()
+        crate::capability_context::require(crate::Capability::Kv, 0).ok()?;
+        KV_STORE.read().get(key)
     }
-    
+
     /// Delete a key
     pub fn delete(key: &str) -> bool {
-        let mut store = KV_STORE.write().unwrap();
-        store.remove(key).is_some()
+        KV_STORE.read().delete(key)
     }
-    
+
     /// Check if key exists
     pub fn exists(key: &str) -> bool {
-        let store = KV_STORE.read().unwrap();
-        store.contains_key(key)
+        KV_STORE.read().exists(key)
     }
-    
+
     /// Get all keys
     pub fn keys() -> Vec<String> {
-        let store = KV_STORE.read().unwrap();
-        store.keys().cloned().collect()
+        KV_STORE.read().keys()
     }
-    
+
     /// Clear all entries
     pub fn clear() {
-        let mut store = KV_STORE.write().unwrap();
-        store.clear();
+        KV_STORE.read().clear();
     }
-    
+
     /// Get number of entries
     pub fn count() -> usize {
-        let store = KV_STORE.read().unwrap();
-        store.len()
+        KV_STORE.read().count()
     }
 }
 
@@ -67,15 +77,15 @@ pub unsafe extern "C" fn zenith_kv_set(
     if key_ptr.is_null() || value_ptr.is_null() {
         return -1;
     }
-    
+
     let key_slice = std::slice::from_raw_parts(key_ptr, key_len);
     let key = match std::str::from_utf8(key_slice) {
         Ok(s) => s,
         Err(_) => return -2,
     };
-    
+
     let value = std::slice::from_raw_parts(value_ptr, value_len);
-    
+
     match KvAPI::set(key, value) {
         Ok(_) => 0,
         Err(_) => -3,
@@ -92,13 +102,13 @@ pub unsafe extern "C" fn zenith_kv_get(
     if key_ptr.is_null() || out_ptr.is_null() {
         return -1;
     }
-    
+
     let key_slice = std::slice::from_raw_parts(key_ptr, key_len);
     let key = match std::str::from_utf8(key_slice) {
         Ok(s) => s,
         Err(_) => return -2,
     };
-    
+
     match KvAPI::get(key) {
         Some(value) => {
             let copy_len = value.len().min(out_len);
@@ -118,13 +128,13 @@ pub unsafe extern "C" fn zenith_kv_delete(
     if key_ptr.is_null() {
         return -1;
     }
-    
+
     let key_slice = std::slice::from_raw_parts(key_ptr, key_len);
     let key = match std::str::from_utf8(key_slice) {
         Ok(s) => s,
         Err(_) => return -2,
     };
-    
+
     if KvAPI::delete(key) {
         0
     } else {
@@ -144,23 +154,23 @@ mod tests {
     #[test]
     fn test_kv_operations() {
         KvAPI::clear();
-        
+
         // Set
         KvAPI::set("test_key", b"test_value").unwrap();
         assert_eq!(KvAPI::count(), 1);
-        
+
         // Get
         let value = KvAPI::get("test_key").unwrap();
         assert_eq!(value, b"test_value");
-        
+
         // Exists
         assert!(KvAPI::exists("test_key"));
         assert!(!KvAPI::exists("nonexistent"));
-        
+
         // Delete
         assert!(KvAPI::delete("test_key"));
         assert_eq!(KvAPI::count(), 0);
-        
+
         // Delete non-existent
         assert!(!KvAPI::delete("test_key"));
     }
@@ -168,14 +178,63 @@ mod tests {
     #[test]
     fn test_kv_keys() {
         KvAPI::clear();
-        
+
         KvAPI::set("key1", b"val1").unwrap();
         KvAPI::set("key2", b"val2").unwrap();
         KvAPI::set("key3", b"val3").unwrap();
-        
+
         let keys = KvAPI::keys();
         assert_eq!(keys.len(), 3);
         assert!(keys.contains(&"key1".to_string()));
         assert!(keys.contains(&"key2".to_string()));
     }
+
+    #[test]
+    fn test_kv_configure_switches_backend() {
+        KvAPI::configure(&backend::KvBackendKind::Memory);
+        KvAPI::clear();
+        KvAPI::set("only_in_memory", b"v").unwrap();
+        assert!(KvAPI::exists("only_in_memory"));
+
+        let path = std::env::temp_dir().join("zenith_kv_api_configure_test.log");
+        let _ = std::fs::remove_file(&path);
+        KvAPI::configure(&backend::KvBackendKind::File(path.clone()));
+        assert!(!KvAPI::exists("only_in_memory"));
+
+        KvAPI::set("durable", b"v").unwrap();
+        assert!(KvAPI::exists("durable"));
+
+        // Restore the default backend so later tests in this binary don't
+        // observe a file-backed KV store.
+        KvAPI::configure(&backend::KvBackendKind::Memory);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_set_denied_without_kv_capability() {
+        crate::capability_context::install(std::sync::Arc::new(crate::Capabilities::none()));
+        assert!(KvAPI::set("blocked", b"v").is_err());
+        crate::capability_context::clear();
+    }
+
+    #[test]
+    fn test_set_denied_with_read_only_kv_capability() {
+        crate::capability_context::install(std::sync::Arc::new(
+            crate::Capabilities::none().allow(crate::Capability::Kv, crate::CapabilityLimit { read_only: true, ..Default::default() }),
+        ));
+        assert!(KvAPI::set("blocked", b"v").is_err());
+        crate::capability_context::clear();
+    }
+
+    #[test]
+    fn test_get_denied_without_kv_capability_returns_none() {
+        KvAPI::clear();
+        KvAPI::set("present", b"v").unwrap();
+
+        crate::capability_context::install(std::sync::Arc::new(crate::Capabilities::none()));
+        assert_eq!(KvAPI::get("present"), None);
+        crate::capability_context::clear();
+
+        assert_eq!(KvAPI::get("present"), Some(b"v".to_vec()));
+    }
 }
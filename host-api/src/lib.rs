@@ -12,13 +12,20 @@ pub mod logging;
 pub mod kv;
 pub mod http;
 pub mod fs;
+pub mod event_context;
+pub mod capabilities;
+pub mod capability_context;
+pub mod random_context;
 
 // Re-exports
 pub use random::RandomAPI;
+pub use random::distributions::{Normal, Exponential, Uniform};
 pub use logging::{LoggingAPI, LogLevel, LogEntry};
 pub use kv::KvAPI;
 pub use http::{HttpAPI, HttpMethod, HttpResponse};
 pub use fs::FsAPI;
+pub use event_context::EventContext;
+pub use capabilities::{Capabilities, Capability, CapabilityLimit};
 
 /// Global counters for host API usage tracking
 static HOST_CALL_COUNT: AtomicU64 = AtomicU64::new(0);
@@ -29,13 +36,22 @@ pub struct HostAPI;
 
 impl HostAPI {
     /// Log a message from the plugin
-    /// 
+    ///
+    /// Returns [`capabilities::DENIED`] if the calling plugin's installed
+    /// [`Capabilities`] (see [`capability_context`]) doesn't grant
+    /// [`Capability::Log`], or grants it under a `max_bytes_per_call` the
+    /// message exceeds.
+    ///
     /// # Safety
     /// message_ptr must point to valid UTF-8 data of length message_len
     pub unsafe fn log(level: u32, message_ptr: *const u8, message_len: usize) -> i32 {
         HOST_CALL_COUNT.fetch_add(1, Ordering::Relaxed);
         LOG_COUNT.fetch_add(1, Ordering::Relaxed);
 
+        if let Err(denied) = capability_context::require(Capability::Log, message_len) {
+            return denied;
+        }
+
         if message_ptr.is_null() {
             return -1;
         }
@@ -58,29 +74,78 @@ impl HostAPI {
         0
     }
 
-    /// Get current timestamp in nanoseconds since UNIX epoch
+    /// Get current timestamp in nanoseconds since UNIX epoch.
+    ///
+    /// Returns `0` if the calling plugin isn't granted [`Capability::Clock`]
+    /// - callers that need to distinguish denial from a genuine (if
+    ///   vanishingly unlikely) epoch-zero timestamp should check
+    ///   capabilities themselves before calling.
     pub fn get_timestamp_ns() -> u64 {
         HOST_CALL_COUNT.fetch_add(1, Ordering::Relaxed);
-        
+
+        if capability_context::require(Capability::Clock, 0).is_err() {
+            return 0;
+        }
+
         std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_nanos() as u64
     }
 
-    /// Get a random u64 value
+    /// Get a random u64 value from the calling plugin's CSPRNG stream (see
+    /// [`RandomAPI`]).
+    ///
+    /// Returns `0` if the calling plugin isn't granted [`Capability::Random`]
+    /// (see [`Self::get_timestamp_ns`] for why `0` rather than a negative
+    /// code signals denial here).
     pub fn get_random_u64() -> u64 {
         HOST_CALL_COUNT.fetch_add(1, Ordering::Relaxed);
-        
-        // In production, use proper RNG
-        // For now, use timestamp + counter
-        let ts = Self::get_timestamp_ns();
-        let count = HOST_CALL_COUNT.load(Ordering::Relaxed);
-        ts.wrapping_add(count)
+
+        if capability_context::require(Capability::Random, 0).is_err() {
+            return 0;
+        }
+
+        RandomAPI::random_u64()
+    }
+
+    /// Fill a plugin-owned buffer with `len` bytes drawn from the calling
+    /// plugin's CSPRNG stream - the arbitrary-length counterpart to
+    /// [`Self::get_random_u64`].
+    ///
+    /// Returns the number of bytes written, or a negative error code:
+    /// [`capabilities::DENIED`] if the calling plugin isn't granted
+    /// [`Capability::Random`], `-1` if `out_ptr` is null.
+    ///
+    /// # Safety
+    /// out_ptr must be valid for len bytes
+    pub unsafe fn fill_random(out_ptr: *mut u8, len: usize) -> i32 {
+        HOST_CALL_COUNT.fetch_add(1, Ordering::Relaxed);
+
+        if let Err(denied) = capability_context::require(Capability::Random, len) {
+            return denied;
+        }
+
+        if out_ptr.is_null() {
+            return -1;
+        }
+
+        let out = std::slice::from_raw_parts_mut(out_ptr, len);
+        RandomAPI::random_bytes(out);
+        len as i32
     }
 
-    /// Read event metadata field by index
-    /// 
+    /// Read a field of the event currently installed in this thread's
+    /// [`event_context`] into `out_buffer`, copying at most `out_buffer_len`
+    /// bytes. Field indices `0` and `1` are the event header's `source_id`
+    /// (4 little-endian bytes) and `seq_no` (8 little-endian bytes); indices
+    /// `2..` select column `field_index - 2`'s raw Arrow buffer bytes.
+    ///
+    /// Returns the number of bytes copied, or a negative error code:
+    /// `-1` if `out_buffer` is null, `-2` if no event context is installed
+    /// (the plugin isn't currently inside an `on_event` call), `-3` if
+    /// `field_index` names a column beyond the event's column count.
+    ///
     /// # Safety
     /// out_buffer must be valid for out_buffer_len bytes
     pub unsafe fn read_event_field(
@@ -94,14 +159,61 @@ impl HostAPI {
             return -1;
         }
 
-        // In real implementation, this would access thread-local event context
-        // For now, return placeholder data
-        let placeholder = format!("field_{}", field_index);
-        let bytes = placeholder.as_bytes();
-        let copy_len = bytes.len().min(out_buffer_len);
+        event_context::with_current(|ctx| {
+            let Some(ctx) = ctx else { return -2 };
 
-        std::ptr::copy_nonoverlapping(bytes.as_ptr(), out_buffer, copy_len);
-        copy_len as i32
+            let bytes: &[u8] = match field_index {
+                0 => &ctx.source_id.to_le_bytes(),
+                1 => &ctx.seq_no.to_le_bytes(),
+                _ => match ctx.column((field_index - 2) as usize) {
+                    Some(bytes) => bytes,
+                    None => return -3,
+                },
+            };
+
+            let copy_len = bytes.len().min(out_buffer_len);
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), out_buffer, copy_len);
+            copy_len as i32
+        })
+    }
+
+    /// Hand the plugin a direct, read-only view into the `column_index`'th
+    /// column of the currently-installed event, instead of copying it
+    /// through a plugin-owned buffer the way [`Self::read_event_field`]
+    /// does. Borrows the same technique wasmer-style host APIs use for
+    /// memory views: `*out_ptr`/`*out_len` point straight at the event
+    /// context's bytes for that column (the host's per-event copy of the
+    /// column's first buffer, see [`event_context::EventContext`]), valid
+    /// only until the host clears the event context at the end of the
+    /// current `on_event` call.
+    ///
+    /// Returns `0` on success, `-1` if either out pointer is null, `-2` if
+    /// no event context is installed, `-3` if `column_index` is out of
+    /// range.
+    ///
+    /// # Safety
+    /// out_ptr and out_len must each be valid for a single write, and the
+    /// caller must not read through `*out_ptr` past the end of the current
+    /// `on_event` call.
+    pub unsafe fn read_event_column(
+        column_index: u32,
+        out_ptr: *mut *const u8,
+        out_len: *mut usize,
+    ) -> i32 {
+        HOST_CALL_COUNT.fetch_add(1, Ordering::Relaxed);
+
+        if out_ptr.is_null() || out_len.is_null() {
+            return -1;
+        }
+
+        event_context::with_current(|ctx| {
+            let Some(ctx) = ctx else { return -2 };
+            let Some(bytes) = ctx.column(column_index as usize) else { return -3 };
+
+            *out_ptr = bytes.as_ptr();
+            *out_len = bytes.len();
+            0
+        })
     }
 
     /// Get total number of host calls made
@@ -141,6 +253,11 @@ pub extern "C" fn zenith_host_get_random_u64() -> u64 {
     HostAPI::get_random_u64()
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn zenith_host_fill_random(out_ptr: *mut u8, len: usize) -> i32 {
+    HostAPI::fill_random(out_ptr, len)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn zenith_host_read_event_field(
     field_index: u32,
@@ -150,6 +267,15 @@ pub unsafe extern "C" fn zenith_host_read_event_field(
     HostAPI::read_event_field(field_index, out_buffer, out_buffer_len)
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn zenith_host_read_event_column(
+    column_index: u32,
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) -> i32 {
+    HostAPI::read_event_column(column_index, out_ptr, out_len)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,9 +306,106 @@ mod tests {
         
         let _ = HostAPI::get_timestamp_ns();
         assert_eq!(HostAPI::get_host_call_count(), 1);
-        
+
         let _ = HostAPI::get_random_u64();
-        // get_random_u64 internally calls get_timestamp_ns, so count is 3 (1 + 2)
-        assert_eq!(HostAPI::get_host_call_count(), 3);
+        assert_eq!(HostAPI::get_host_call_count(), 2);
+    }
+
+    #[test]
+    fn test_fill_random_writes_requested_length() {
+        let mut buf = [0u8; 32];
+        unsafe {
+            let n = HostAPI::fill_random(buf.as_mut_ptr(), buf.len());
+            assert_eq!(n, 32);
+        }
+        assert!(buf.iter().any(|&b| b != 0));
+    }
+
+    #[test]
+    fn test_fill_random_denied_without_capability() {
+        capability_context::install(std::sync::Arc::new(Capabilities::none()));
+        let mut buf = [0u8; 8];
+        unsafe {
+            assert_eq!(HostAPI::fill_random(buf.as_mut_ptr(), buf.len()), capabilities::DENIED);
+        }
+        capability_context::clear();
+    }
+
+    #[test]
+    fn test_read_event_field_without_context() {
+        event_context::clear();
+        let mut buf = [0u8; 8];
+        unsafe {
+            assert_eq!(HostAPI::read_event_field(0, buf.as_mut_ptr(), buf.len()), -2);
+        }
+    }
+
+    #[test]
+    fn test_read_event_field_header_and_column() {
+        event_context::install(EventContext::new(9, 77, vec![std::sync::Arc::from(&b"abc"[..])]));
+
+        let mut buf = [0u8; 8];
+        unsafe {
+            let n = HostAPI::read_event_field(0, buf.as_mut_ptr(), buf.len());
+            assert_eq!(n, 4);
+            assert_eq!(u32::from_le_bytes(buf[..4].try_into().unwrap()), 9);
+
+            let n = HostAPI::read_event_field(1, buf.as_mut_ptr(), buf.len());
+            assert_eq!(n, 8);
+            assert_eq!(u64::from_le_bytes(buf), 77);
+
+            let n = HostAPI::read_event_field(2, buf.as_mut_ptr(), buf.len());
+            assert_eq!(n, 3);
+            assert_eq!(&buf[..3], b"abc");
+
+            assert_eq!(HostAPI::read_event_field(3, buf.as_mut_ptr(), buf.len()), -3);
+        }
+
+        event_context::clear();
+    }
+
+    #[test]
+    fn test_read_event_column_is_zero_copy() {
+        event_context::install(EventContext::new(1, 1, vec![std::sync::Arc::from(&b"xyz"[..])]));
+
+        let mut ptr: *const u8 = std::ptr::null();
+        let mut len: usize = 0;
+        unsafe {
+            assert_eq!(HostAPI::read_event_column(0, &mut ptr, &mut len), 0);
+            assert_eq!(len, 3);
+            assert_eq!(std::slice::from_raw_parts(ptr, len), b"xyz");
+
+            assert_eq!(HostAPI::read_event_column(1, &mut ptr, &mut len), -3);
+        }
+
+        event_context::clear();
+    }
+
+    #[test]
+    fn test_log_denied_without_log_capability() {
+        capability_context::install(std::sync::Arc::new(Capabilities::none()));
+        let msg = "blocked";
+        unsafe {
+            assert_eq!(HostAPI::log(1, msg.as_ptr(), msg.len()), capabilities::DENIED);
+        }
+        capability_context::clear();
+    }
+
+    #[test]
+    fn test_log_allowed_with_log_capability() {
+        capability_context::install(std::sync::Arc::new(Capabilities::all()));
+        let msg = "allowed";
+        unsafe {
+            assert_eq!(HostAPI::log(1, msg.as_ptr(), msg.len()), 0);
+        }
+        capability_context::clear();
+    }
+
+    #[test]
+    fn test_clock_and_random_denied_without_capability() {
+        capability_context::install(std::sync::Arc::new(Capabilities::none()));
+        assert_eq!(HostAPI::get_timestamp_ns(), 0);
+        assert_eq!(HostAPI::get_random_u64(), 0);
+        capability_context::clear();
     }
 }
@@ -1,6 +1,7 @@
 /// Structured Logging Module for WASM Plugins
 /// Provides leveled, structured logging with context
 
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Mutex;
 use std::collections::VecDeque;
 
@@ -9,7 +10,7 @@ const MAX_LOG_ENTRIES: usize = 1000;
 
 /// Log level
 #[repr(u32)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LogLevel {
     Trace = 0,
     Debug = 1,
@@ -37,18 +38,83 @@ pub struct LogEntry {
     pub level: LogLevel,
     pub message: String,
     pub plugin_id: Option<String>,
+    /// Structured context attached to the entry, e.g. `("request_id", "abc")`.
+    /// Empty for entries logged through the plain-string [`LoggingAPI::log`].
+    pub fields: Vec<(String, String)>,
+}
+
+impl LogEntry {
+    /// Serialize as a single JSON object line (no trailing newline), for
+    /// [`LoggingAPI::drain_logs_json`]. Hand-rolled rather than pulling in
+    /// `serde_json` for one call site in a crate that otherwise has no
+    /// serialization dependency.
+    fn to_json(&self) -> String {
+        let mut out = String::from("{");
+        out.push_str(&format!("\"timestamp\":{},", self.timestamp));
+        out.push_str(&format!("\"level\":\"{:?}\",", self.level));
+        out.push_str(&format!("\"message\":{},", json_string(&self.message)));
+        match &self.plugin_id {
+            Some(id) => out.push_str(&format!("\"plugin_id\":{},", json_string(id))),
+            None => out.push_str("\"plugin_id\":null,"),
+        }
+        out.push_str("\"fields\":{");
+        for (i, (key, value)) in self.fields.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str(&format!("{}:{}", json_string(key), json_string(value)));
+        }
+        out.push_str("}}");
+        out
+    }
+}
+
+/// Escape and quote `s` as a JSON string literal.
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
 }
 
 lazy_static::lazy_static! {
     static ref LOG_BUFFER: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
 }
 
+/// Floor below which [`LoggingAPI::log`]/[`LoggingAPI::log_kv`] neither
+/// buffer nor forward to `tracing`. Stored as the `u32` discriminant of a
+/// [`LogLevel`] since atomics need a primitive; defaults to `Trace` (0), i.e.
+/// no filtering.
+static MIN_LEVEL: AtomicU32 = AtomicU32::new(LogLevel::Trace as u32);
+
 /// Logging API
 pub struct LoggingAPI;
 
 impl LoggingAPI {
     /// Log a message
     pub fn log(level: LogLevel, message: &str, plugin_id: Option<&str>) {
+        Self::log_kv(level, message, plugin_id, Vec::new());
+    }
+
+    /// Log a message with structured context. Records below the
+    /// [`Self::set_min_level`] threshold are dropped entirely: neither
+    /// buffered nor forwarded to `tracing`.
+    pub fn log_kv(level: LogLevel, message: &str, plugin_id: Option<&str>, fields: Vec<(String, String)>) {
+        if level < Self::min_level() {
+            return;
+        }
+
         let entry = LogEntry {
             timestamp: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
@@ -57,8 +123,9 @@ impl LoggingAPI {
             level,
             message: message.to_string(),
             plugin_id: plugin_id.map(String::from),
+            fields,
         };
-        
+
         // Print to tracing
         match level {
             LogLevel::Trace => tracing::trace!("[{}] {}", plugin_id.unwrap_or("unknown"), message),
@@ -67,17 +134,17 @@ impl LoggingAPI {
             LogLevel::Warn => tracing::warn!("[{}] {}", plugin_id.unwrap_or("unknown"), message),
             LogLevel::Error => tracing::error!("[{}] {}", plugin_id.unwrap_or("unknown"), message),
         }
-        
+
         // Store in buffer
         let mut buffer = LOG_BUFFER.lock().unwrap();
         buffer.push_back(entry);
-        
+
         // Trim if too large
         while buffer.len() > MAX_LOG_ENTRIES {
             buffer.pop_front();
         }
     }
-    
+
     /// Get recent log entries
     pub fn get_recent_logs(count: usize) -> Vec<LogEntry> {
         let buffer = LOG_BUFFER.lock().unwrap();
@@ -87,12 +154,48 @@ impl LoggingAPI {
             .cloned()
             .collect()
     }
-    
+
+    /// Get the `count` most recent log entries at or above `min_level`,
+    /// optionally restricted to a single `plugin_id`.
+    pub fn get_logs_filtered(min_level: LogLevel, plugin_id: Option<&str>, count: usize) -> Vec<LogEntry> {
+        let buffer = LOG_BUFFER.lock().unwrap();
+        buffer.iter()
+            .rev()
+            .filter(|entry| entry.level >= min_level)
+            .filter(|entry| match plugin_id {
+                Some(id) => entry.plugin_id.as_deref() == Some(id),
+                None => true,
+            })
+            .take(count)
+            .cloned()
+            .collect()
+    }
+
+    /// Set the floor below which `log`/`log_kv` drop records entirely.
+    pub fn set_min_level(level: LogLevel) {
+        MIN_LEVEL.store(level as u32, Ordering::Relaxed);
+    }
+
+    /// Current `set_min_level` threshold.
+    pub fn min_level() -> LogLevel {
+        LogLevel::from(MIN_LEVEL.load(Ordering::Relaxed))
+    }
+
+    /// Remove every buffered entry, serializing each as one line of a
+    /// newline-delimited JSON document (one `LogEntry` object per line) for
+    /// export. Entries removed this way are gone from `get_recent_logs`
+    /// afterward, unlike the read-only query methods.
+    pub fn drain_logs_json() -> String {
+        let mut buffer = LOG_BUFFER.lock().unwrap();
+        let lines: Vec<String> = buffer.drain(..).map(|entry| entry.to_json()).collect();
+        lines.join("\n")
+    }
+
     /// Clear log buffer
     pub fn clear_logs() {
         LOG_BUFFER.lock().unwrap().clear();
     }
-    
+
     /// Get log count
     pub fn get_log_count() -> usize {
         LOG_BUFFER.lock().unwrap().len()
@@ -125,6 +228,57 @@ pub extern "C" fn zenith_log_count() -> usize {
     LoggingAPI::get_log_count()
 }
 
+/// Log a message with structured key/value fields from a WASM plugin.
+///
+/// `fields_ptr`/`fields_len` point to UTF-8 text encoding the field list as
+/// one `key\tvalue` pair per line (`\n`-separated), e.g. `"request_id\tabc\nretry\t2"`.
+/// Pass `fields_len` 0 to log with no fields (equivalent to [`zenith_log`]).
+///
+/// # Safety
+/// `message_ptr` must point to valid UTF-8 of length `message_len`, and
+/// `fields_ptr` (when `fields_len` is nonzero) must point to valid UTF-8 of
+/// length `fields_len`.
+#[no_mangle]
+pub unsafe extern "C" fn zenith_log_kv(
+    level: u32,
+    message_ptr: *const u8,
+    message_len: usize,
+    fields_ptr: *const u8,
+    fields_len: usize,
+) -> i32 {
+    if message_ptr.is_null() {
+        return -1;
+    }
+
+    let message_slice = std::slice::from_raw_parts(message_ptr, message_len);
+    let message = match std::str::from_utf8(message_slice) {
+        Ok(s) => s,
+        Err(_) => return -2,
+    };
+
+    let fields = if fields_len == 0 {
+        Vec::new()
+    } else {
+        if fields_ptr.is_null() {
+            return -1;
+        }
+        let fields_slice = std::slice::from_raw_parts(fields_ptr, fields_len);
+        let fields_text = match std::str::from_utf8(fields_slice) {
+            Ok(s) => s,
+            Err(_) => return -2,
+        };
+        fields_text
+            .lines()
+            .filter(|line| !line.is_empty())
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    };
+
+    LoggingAPI::log_kv(level.into(), message, Some("plugin"), fields);
+    0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,11 +303,122 @@ mod tests {
     #[test]
     fn test_log_buffer_limit() {
         LoggingAPI::clear_logs();
-        
+
         for i in 0..1500 {
             LoggingAPI::log(LogLevel::Debug, &format!("Log {}", i), None);
         }
-        
+
         assert_eq!(LoggingAPI::get_log_count(), MAX_LOG_ENTRIES);
     }
+
+    /// log_kv must attach the supplied fields to the buffered entry.
+    #[test]
+    fn test_log_kv_attaches_fields() {
+        static TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        let _guard = TEST_LOCK.lock().unwrap();
+
+        LoggingAPI::clear_logs();
+        LoggingAPI::log_kv(
+            LogLevel::Info,
+            "request handled",
+            Some("router"),
+            vec![("request_id".to_string(), "abc-123".to_string())],
+        );
+
+        let logs = LoggingAPI::get_recent_logs(1);
+        assert_eq!(logs[0].fields, vec![("request_id".to_string(), "abc-123".to_string())]);
+    }
+
+    /// Records below the min_level threshold must be neither buffered nor
+    /// counted, and the threshold must not affect records at or above it.
+    /// Kills mutation: level < min_level check dropped or inverted
+    #[test]
+    fn test_set_min_level_drops_below_threshold() {
+        static TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        let _guard = TEST_LOCK.lock().unwrap();
+
+        LoggingAPI::clear_logs();
+        LoggingAPI::set_min_level(LogLevel::Warn);
+
+        LoggingAPI::log(LogLevel::Info, "dropped", None);
+        assert_eq!(LoggingAPI::get_log_count(), 0,
+            "a record below min_level must not be buffered");
+
+        LoggingAPI::log(LogLevel::Error, "kept", None);
+        assert_eq!(LoggingAPI::get_log_count(), 1,
+            "a record at or above min_level must still be buffered");
+
+        LoggingAPI::set_min_level(LogLevel::Trace);
+    }
+
+    /// get_logs_filtered must exclude entries below min_level and entries
+    /// from a different plugin_id than requested.
+    #[test]
+    fn test_get_logs_filtered_by_level_and_plugin() {
+        static TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        let _guard = TEST_LOCK.lock().unwrap();
+
+        LoggingAPI::clear_logs();
+        LoggingAPI::log(LogLevel::Info, "from a, info", Some("plugin-a"));
+        LoggingAPI::log(LogLevel::Error, "from a, error", Some("plugin-a"));
+        LoggingAPI::log(LogLevel::Error, "from b, error", Some("plugin-b"));
+
+        let filtered = LoggingAPI::get_logs_filtered(LogLevel::Error, Some("plugin-a"), 10);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].message, "from a, error");
+    }
+
+    /// drain_logs_json must serialize every buffered entry and leave the
+    /// buffer empty afterward.
+    #[test]
+    fn test_drain_logs_json_empties_buffer() {
+        static TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        let _guard = TEST_LOCK.lock().unwrap();
+
+        LoggingAPI::clear_logs();
+        LoggingAPI::log_kv(
+            LogLevel::Warn,
+            "disk at 90%",
+            Some("monitor"),
+            vec![("disk".to_string(), "/dev/sda1".to_string())],
+        );
+
+        let json = LoggingAPI::drain_logs_json();
+        assert!(json.contains("\"message\":\"disk at 90%\""));
+        assert!(json.contains("\"disk\":\"/dev/sda1\""));
+        assert!(json.contains("\"plugin_id\":\"monitor\""));
+        assert_eq!(LoggingAPI::get_log_count(), 0,
+            "drain_logs_json must remove the entries it exported");
+    }
+
+    /// zenith_log_kv must parse the tab/newline-delimited fields blob and
+    /// attach it to the logged entry.
+    #[test]
+    fn test_zenith_log_kv_parses_fields() {
+        static TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        let _guard = TEST_LOCK.lock().unwrap();
+
+        LoggingAPI::clear_logs();
+        let message = b"hello from wasm";
+        let fields = b"request_id\tabc\nretry\t2";
+        let rc = unsafe {
+            zenith_log_kv(
+                LogLevel::Info as u32,
+                message.as_ptr(),
+                message.len(),
+                fields.as_ptr(),
+                fields.len(),
+            )
+        };
+        assert_eq!(rc, 0);
+
+        let logs = LoggingAPI::get_recent_logs(1);
+        assert_eq!(
+            logs[0].fields,
+            vec![
+                ("request_id".to_string(), "abc".to_string()),
+                ("retry".to_string(), "2".to_string()),
+            ]
+        );
+    }
 }
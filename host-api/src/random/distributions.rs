@@ -0,0 +1,292 @@
+/// Shaped-randomness samplers for WASM Plugins
+///
+/// Built directly on `RandomAPI`'s ChaCha20 stream so plugins that need
+/// normal, exponential, or ranged-uniform draws don't have to reimplement
+/// sampling on top of the raw CSPRNG themselves.
+use super::RandomAPI;
+
+/// Number of ziggurat layers used to cover the positive half of the
+/// standard normal curve.
+const LAYERS: usize = 256;
+/// Boundaries below the bottom layer's `r`, computed once during table
+/// construction (one fewer than `LAYERS`, since the bottom layer is handled
+/// separately via `sample_tail`).
+const CHAIN_LEN: usize = LAYERS - 1;
+
+#[inline]
+fn half_gaussian_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp()
+}
+
+/// Numerically integrate `half_gaussian_pdf` from `r` to effectively
+/// infinity (the density is negligible past `r + 12` for any `r` the
+/// ziggurat construction below will try).
+fn tail_area(r: f64) -> f64 {
+    let upper = r + 12.0;
+    let steps = 2048usize;
+    let h = (upper - r) / steps as f64;
+
+    let mut sum = half_gaussian_pdf(r) + half_gaussian_pdf(upper);
+    for i in 1..steps {
+        let x = r + i as f64 * h;
+        let weight = if i % 2 == 0 { 2.0 } else { 4.0 };
+        sum += weight * half_gaussian_pdf(x);
+    }
+    sum * h / 3.0
+}
+
+/// Precomputed ziggurat tables for sampling the positive half of a standard
+/// normal distribution.
+///
+/// Layer 0 is the bottom layer: its core `[0, r)` is a rectangle fully
+/// under the curve, and beyond `r` lies the tail. Layers `1..LAYERS-1` are
+/// ordinary rectangle-plus-wedge layers of equal area `v`, each one
+/// narrower than the last; layer `LAYERS-1` is the apex layer, capped at
+/// density 1.0 rather than a neighboring layer.
+struct ZigguratTables {
+    r: f64,
+    v: f64,
+    /// `chain_x[i]` / `chain_y[i]` are the boundary and density at the top
+    /// of layer `i`, i.e. the width and floor of layer `i + 1`
+    /// (`chain_x[0] == r`).
+    chain_x: [f64; CHAIN_LEN],
+    chain_y: [f64; CHAIN_LEN],
+}
+
+impl ZigguratTables {
+    /// Build the chain of layer boundaries for a candidate `r`, plus the
+    /// residual of the apex closure condition (zero once `r` is correct).
+    ///
+    /// Returns `None` if `r` is so small/large that the recursion runs into
+    /// an invalid density (an out-of-range candidate, not a construction
+    /// bug — the caller scans `r` until it finds the sign change).
+    fn try_build(r: f64) -> Option<(f64, [f64; CHAIN_LEN], [f64; CHAIN_LEN], f64)> {
+        let v = r * half_gaussian_pdf(r) + tail_area(r);
+
+        let mut chain_x = [0.0f64; CHAIN_LEN];
+        let mut chain_y = [0.0f64; CHAIN_LEN];
+        chain_x[0] = r;
+        chain_y[0] = half_gaussian_pdf(r);
+
+        for i in 1..CHAIN_LEN {
+            let y = chain_y[i - 1] + v / chain_x[i - 1];
+            if !(0.0..1.0).contains(&y) {
+                return None;
+            }
+            chain_x[i] = (-2.0 * y.ln()).sqrt();
+            chain_y[i] = y;
+        }
+
+        let apex_width = chain_x[CHAIN_LEN - 1];
+        let apex_floor = chain_y[CHAIN_LEN - 1];
+        let residual = apex_width * (1.0 - apex_floor) - v;
+        Some((v, chain_x, chain_y, residual))
+    }
+
+    /// Find `r` via bisection on the apex closure residual and build the
+    /// final tables.
+    fn build() -> Self {
+        let scan_lo = 0.5_f64;
+        let scan_hi = 6.0_f64;
+        let steps = 4000;
+
+        let mut bracket = None;
+        let mut prev_r = scan_lo;
+        let mut prev_residual = Self::try_build(scan_lo).map(|(_, _, _, res)| res);
+
+        for step in 1..=steps {
+            let r = scan_lo + (scan_hi - scan_lo) * step as f64 / steps as f64;
+            let residual = Self::try_build(r).map(|(_, _, _, res)| res);
+            if let (Some(p), Some(c)) = (prev_residual, residual) {
+                if p.signum() != c.signum() {
+                    bracket = Some((prev_r, r));
+                    break;
+                }
+            }
+            prev_r = r;
+            prev_residual = residual;
+        }
+
+        let (mut lo, mut hi) = bracket.unwrap_or((scan_lo, scan_hi));
+        for _ in 0..100 {
+            let mid = 0.5 * (lo + hi);
+            let mid_residual = Self::try_build(mid).map(|(_, _, _, res)| res).unwrap_or(0.0);
+            let lo_residual = Self::try_build(lo).map(|(_, _, _, res)| res).unwrap_or(0.0);
+            if lo_residual.signum() == mid_residual.signum() {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+
+        let r = 0.5 * (lo + hi);
+        let (v, chain_x, chain_y, _) = Self::try_build(r).expect("bisection converged to a valid r");
+        ZigguratTables { r, v, chain_x, chain_y }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref NORMAL_TABLES: ZigguratTables = ZigguratTables::build();
+}
+
+/// Standard normal (mean 0, variance 1) sampler using the ziggurat method.
+pub struct Normal;
+
+impl Normal {
+    /// Sample a value from the standard normal distribution.
+    pub fn sample() -> f64 {
+        let tables = &*NORMAL_TABLES;
+        loop {
+            let bits = RandomAPI::random_u64();
+            let layer = (bits & 0xFF) as usize;
+            let positive = (bits >> 8) & 1 == 1;
+
+            let magnitude = if layer == 0 {
+                match Self::sample_bottom_layer(tables) {
+                    Some(x) => x,
+                    None => continue,
+                }
+            } else {
+                match Self::sample_middle_or_apex_layer(tables, layer) {
+                    Some(x) => x,
+                    None => continue,
+                }
+            };
+
+            return if positive { magnitude } else { -magnitude };
+        }
+    }
+
+    /// Sample from an arbitrary mean/standard-deviation normal distribution.
+    pub fn sample_with(mean: f64, std_dev: f64) -> f64 {
+        mean + std_dev * Self::sample()
+    }
+
+    fn sample_bottom_layer(tables: &ZigguratTables) -> Option<f64> {
+        let core_area = tables.r * half_gaussian_pdf(tables.r);
+        if RandomAPI::random_f64() * tables.v < core_area {
+            // Rectangle [0, r) x [0, f(r)) is entirely under the curve.
+            Some(RandomAPI::random_f64() * tables.r)
+        } else {
+            Some(Self::sample_tail(tables.r))
+        }
+    }
+
+    fn sample_middle_or_apex_layer(tables: &ZigguratTables, layer: usize) -> Option<f64> {
+        let idx = layer - 1;
+        let width = tables.chain_x[idx];
+        let y_lo = tables.chain_y[idx];
+        let is_apex = layer == LAYERS - 1;
+        let (inner_bound, y_hi) = if is_apex {
+            (0.0, 1.0)
+        } else {
+            (tables.chain_x[idx + 1], tables.chain_y[idx + 1])
+        };
+
+        let x = RandomAPI::random_f64() * width;
+        if x < inner_bound {
+            return Some(x);
+        }
+
+        let y_sample = y_lo + RandomAPI::random_f64() * (y_hi - y_lo);
+        if y_sample < half_gaussian_pdf(x) {
+            Some(x)
+        } else {
+            None
+        }
+    }
+
+    /// Sample the tail of the half-normal beyond `r` (Marsaglia & Bray,
+    /// 1964): draw an exponential candidate and a second exponential to
+    /// decide acceptance under the Gaussian tail.
+    fn sample_tail(r: f64) -> f64 {
+        loop {
+            let u1 = RandomAPI::random_f64().max(f64::MIN_POSITIVE);
+            let u2 = RandomAPI::random_f64().max(f64::MIN_POSITIVE);
+            let x = -u1.ln() / r;
+            let y = -u2.ln();
+            if 2.0 * y >= x * x {
+                return r + x;
+            }
+        }
+    }
+}
+
+/// Exponential distribution with rate `lambda`.
+pub struct Exponential {
+    lambda: f64,
+}
+
+impl Exponential {
+    pub fn new(lambda: f64) -> Self {
+        Exponential { lambda }
+    }
+
+    pub fn sample(&self) -> f64 {
+        let u = RandomAPI::random_f64();
+        -(1.0 - u).ln() / self.lambda
+    }
+}
+
+/// Uniform distribution over `[low, high)`.
+pub struct Uniform<T> {
+    low: T,
+    high: T,
+}
+
+impl Uniform<f64> {
+    pub fn new(low: f64, high: f64) -> Self {
+        Uniform { low, high }
+    }
+
+    pub fn sample(&self) -> f64 {
+        self.low + RandomAPI::random_f64() * (self.high - self.low)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn zenith_random_normal() -> f64 {
+    Normal::sample()
+}
+
+#[no_mangle]
+pub extern "C" fn zenith_random_exp(lambda: f64) -> f64 {
+    Exponential::new(lambda).sample()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normal_sample_is_finite_and_bounded() {
+        for _ in 0..500 {
+            let x = Normal::sample();
+            assert!(x.is_finite());
+            assert!(x.abs() < 15.0);
+        }
+    }
+
+    #[test]
+    fn test_normal_sample_with_shifts_and_scales() {
+        let x = Normal::sample_with(10.0, 0.0);
+        assert_eq!(x, 10.0);
+    }
+
+    #[test]
+    fn test_exponential_sample_is_non_negative() {
+        let dist = Exponential::new(2.0);
+        for _ in 0..200 {
+            assert!(dist.sample() >= 0.0);
+        }
+    }
+
+    #[test]
+    fn test_uniform_sample_stays_in_range() {
+        let dist = Uniform::new(5.0, 9.0);
+        for _ in 0..200 {
+            let x = dist.sample();
+            assert!(x >= 5.0 && x < 9.0);
+        }
+    }
+}
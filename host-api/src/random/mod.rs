@@ -1,61 +1,360 @@
 /// Random Number Generation Module for WASM Plugins
 /// Provides cryptographically secure and fast random number generation
 
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::random_context;
+
+pub mod distributions;
 
 static RNG_CALL_COUNT: AtomicU64 = AtomicU64::new(0);
 
+/// Number of keystream bytes a `ReseedingRng` hands out before it pulls a
+/// fresh key from OS entropy. Keeps long-lived plugin instances from
+/// overstretching a single ChaCha20 key.
+const DEFAULT_RESEED_INTERVAL: u64 = 1 << 20;
+
+const CHACHA20_CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+/// A ChaCha20 stream cipher used purely as a counter-based CSPRNG.
+///
+/// State is the usual sixteen `u32` words: four fixed constants, eight key
+/// words, one 32-bit block counter and three nonce words. Each call to
+/// `refill` runs 10 double-rounds over the state, adds the original words
+/// back in, and serializes the result to a 64-byte keystream block.
+struct ChaCha20Rng {
+    key: [u32; 8],
+    nonce: [u32; 3],
+    counter: u32,
+    block: [u8; 64],
+    block_pos: usize,
+}
+
+impl ChaCha20Rng {
+    /// Seed from a 256-bit key and zeroed nonce/counter.
+    fn from_seed(seed: [u8; 32]) -> Self {
+        let mut key = [0u32; 8];
+        for (word, chunk) in key.iter_mut().zip(seed.chunks_exact(4)) {
+            *word = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+        ChaCha20Rng {
+            key,
+            nonce: [0; 3],
+            counter: 0,
+            block: [0; 64],
+            block_pos: 64,
+        }
+    }
+
+    #[inline]
+    fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+        state[a] = state[a].wrapping_add(state[b]);
+        state[d] ^= state[a];
+        state[d] = state[d].rotate_left(16);
+
+        state[c] = state[c].wrapping_add(state[d]);
+        state[b] ^= state[c];
+        state[b] = state[b].rotate_left(12);
+
+        state[a] = state[a].wrapping_add(state[b]);
+        state[d] ^= state[a];
+        state[d] = state[d].rotate_left(8);
+
+        state[c] = state[c].wrapping_add(state[d]);
+        state[b] ^= state[c];
+        state[b] = state[b].rotate_left(7);
+    }
+
+    /// Generate the next 64-byte keystream block and reset the read cursor.
+    fn refill(&mut self) {
+        let mut state = [0u32; 16];
+        state[0..4].copy_from_slice(&CHACHA20_CONSTANTS);
+        state[4..12].copy_from_slice(&self.key);
+        state[12] = self.counter;
+        state[13..16].copy_from_slice(&self.nonce);
+
+        let initial = state;
+        for _ in 0..10 {
+            // Column rounds
+            Self::quarter_round(&mut state, 0, 4, 8, 12);
+            Self::quarter_round(&mut state, 1, 5, 9, 13);
+            Self::quarter_round(&mut state, 2, 6, 10, 14);
+            Self::quarter_round(&mut state, 3, 7, 11, 15);
+            // Diagonal rounds
+            Self::quarter_round(&mut state, 0, 5, 10, 15);
+            Self::quarter_round(&mut state, 1, 6, 11, 12);
+            Self::quarter_round(&mut state, 2, 7, 8, 13);
+            Self::quarter_round(&mut state, 3, 4, 9, 14);
+        }
+
+        for (word, init) in state.iter_mut().zip(initial.iter()) {
+            *word = word.wrapping_add(*init);
+        }
+
+        for (chunk, word) in self.block.chunks_exact_mut(4).zip(state.iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+
+        self.counter = self.counter.wrapping_add(1);
+        self.block_pos = 0;
+    }
+
+    /// Fill `out` with keystream bytes, refilling the internal block as needed.
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        let mut written = 0;
+        while written < out.len() {
+            if self.block_pos == self.block.len() {
+                self.refill();
+            }
+            let available = self.block.len() - self.block_pos;
+            let take = available.min(out.len() - written);
+            out[written..written + take]
+                .copy_from_slice(&self.block[self.block_pos..self.block_pos + take]);
+            self.block_pos += take;
+            written += take;
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+}
+
+/// Pull 32 bytes of OS entropy to seed a `ChaCha20Rng`.
+///
+/// Reads `/dev/urandom` directly rather than pulling in an external crate;
+/// if that's unavailable (e.g. a sandboxed host with no filesystem access)
+/// falls back to mixing several independent time and address sources so the
+/// plugin host still starts up rather than panicking.
+fn os_entropy_seed() -> [u8; 32] {
+    use std::io::Read;
+
+    let mut seed = [0u8; 32];
+    let from_urandom = std::fs::File::open("/dev/urandom")
+        .and_then(|mut f| f.read_exact(&mut seed))
+        .is_ok();
+
+    if !from_urandom {
+        let stack_var = 0u64;
+        let mut mix = (&stack_var as *const u64 as u64)
+            ^ std::process::id() as u64;
+        for chunk in seed.chunks_exact_mut(8) {
+            let ts = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as u64;
+            // A cheap splitmix64-style mix so the fallback seed isn't just
+            // a handful of correlated timestamps.
+            mix = mix.wrapping_add(ts).wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = mix;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^= z >> 31;
+            chunk.copy_from_slice(&z.to_le_bytes());
+        }
+    }
+
+    seed
+}
+
+/// Mix a plugin's identity into a 32-byte seed, so two plugins started from
+/// the same OS entropy (e.g. the same instant at process startup) still end
+/// up on distinct keystreams. Uses a simple FNV-1a-style hash rather than a
+/// real hash function, since this only needs to disperse the identity
+/// across the seed, not resist deliberate collision - the OS entropy half
+/// of the mix is what actually makes the stream unpredictable.
+fn mix_seed_with_identity(mut seed: [u8; 32], identity: &str) -> [u8; 32] {
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    for (lane, chunk) in seed.chunks_exact_mut(8).enumerate() {
+        let mut hash = FNV_OFFSET ^ (lane as u64);
+        for &b in identity.as_bytes() {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+        for (byte, mixed) in chunk.iter_mut().zip(hash.to_le_bytes()) {
+            *byte ^= mixed;
+        }
+    }
+
+    seed
+}
+
+/// A `ChaCha20Rng` that reseeds itself from OS entropy after a configurable
+/// number of keystream bytes, so a long-lived plugin instance never
+/// overstretches a single key.
+struct ReseedingRng {
+    rng: ChaCha20Rng,
+    bytes_since_reseed: u64,
+    reseed_interval: u64,
+    explicitly_seeded: bool,
+}
+
+impl ReseedingRng {
+    fn new() -> Self {
+        Self::from_seed(os_entropy_seed())
+    }
+
+    fn from_seed(seed: [u8; 32]) -> Self {
+        ReseedingRng {
+            rng: ChaCha20Rng::from_seed(seed),
+            bytes_since_reseed: 0,
+            reseed_interval: DEFAULT_RESEED_INTERVAL,
+            explicitly_seeded: false,
+        }
+    }
+
+    /// Reseed deterministically; disables automatic reseeding so a replayed
+    /// plugin run stays fully reproducible.
+    fn seed(&mut self, seed: [u8; 32]) {
+        self.rng = ChaCha20Rng::from_seed(seed);
+        self.bytes_since_reseed = 0;
+        self.explicitly_seeded = true;
+    }
+
+    fn maybe_reseed(&mut self, bytes_generated: u64) {
+        self.bytes_since_reseed += bytes_generated;
+        if !self.explicitly_seeded && self.bytes_since_reseed >= self.reseed_interval {
+            self.rng = ChaCha20Rng::from_seed(os_entropy_seed());
+            self.bytes_since_reseed = 0;
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let val = self.rng.next_u64();
+        self.maybe_reseed(8);
+        val
+    }
+
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        self.rng.fill_bytes(out);
+        self.maybe_reseed(out.len() as u64);
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Default stream used when no plugin identity is installed (native
+    /// Rust callers, unit tests) - the single process-wide stream that
+    /// predates per-plugin streams.
+    static ref PLUGIN_RNG: Mutex<ReseedingRng> = Mutex::new(ReseedingRng::new());
+    /// Per-plugin streams, keyed by the identity [`random_context`] installs
+    /// around a plugin invocation. Each is seeded once, on first use, from
+    /// OS entropy mixed with the plugin's identity (see
+    /// [`mix_seed_with_identity`]) and from then on reseeds itself from
+    /// fresh OS entropy like any other [`ReseedingRng`] - unless
+    /// [`RandomAPI::seed_plugin`] pins it to a fixed seed for reproducible
+    /// replay.
+    static ref PLUGIN_STREAMS: Mutex<HashMap<String, Arc<Mutex<ReseedingRng>>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Look up (creating on first use) the stream for `plugin_id`, seeded from
+/// OS entropy mixed with its identity (see [`mix_seed_with_identity`]).
+fn plugin_stream(plugin_id: &str) -> Arc<Mutex<ReseedingRng>> {
+    let mut streams = PLUGIN_STREAMS.lock().unwrap();
+    streams
+        .entry(plugin_id.to_string())
+        .or_insert_with(|| {
+            let seed = mix_seed_with_identity(os_entropy_seed(), plugin_id);
+            Arc::new(Mutex::new(ReseedingRng::from_seed(seed)))
+        })
+        .clone()
+}
+
+/// Run `f` against the stream for the currently-installed plugin (see
+/// [`random_context`]), or the shared default stream when no plugin
+/// identity is installed - the behavior every caller had before per-plugin
+/// streams existed.
+fn with_current_stream<R>(f: impl FnOnce(&mut ReseedingRng) -> R) -> R {
+    match random_context::current() {
+        Some(plugin_id) => f(&mut plugin_stream(&plugin_id).lock().unwrap()),
+        None => f(&mut PLUGIN_RNG.lock().unwrap()),
+    }
+}
+
 /// Random number generator for plugins
 pub struct RandomAPI;
 
 impl RandomAPI {
-    /// Generate a random u64
+    /// Generate a random u64, drawn from the calling plugin's own stream if
+    /// [`random_context`] has one installed, else the shared default
+    /// stream.
     pub fn random_u64() -> u64 {
         RNG_CALL_COUNT.fetch_add(1, Ordering::Relaxed);
-        
-        // Use system time + counter for deterministic randomness
-        // In production, use proper RNG like ChaCha20
-        let ts = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos() as u64;
-        
-        let count = RNG_CALL_COUNT.load(Ordering::Relaxed);
-        ts.wrapping_mul(6364136223846793005).wrapping_add(count)
-    }
-    
+        with_current_stream(|rng| rng.next_u64())
+    }
+
     /// Generate a random u32
     pub fn random_u32() -> u32 {
         (Self::random_u64() >> 32) as u32
     }
-    
+
     /// Generate random float in [0.0, 1.0)
     pub fn random_f64() -> f64 {
         let val = Self::random_u64();
         // Scale to [0, 1)
         (val >> 11) as f64 * (1.0 / ((1u64 << 53) as f64))
     }
-    
-    /// Generate random bytes
+
+    /// Generate random bytes, drawn from the same stream [`Self::random_u64`]
+    /// would use.
     pub fn random_bytes(out: &mut [u8]) {
-        for chunk in out.chunks_mut(8) {
-            let rand_u64 = Self::random_u64();
-            let bytes = rand_u64.to_le_bytes();
-            let len = chunk.len().min(8);
-            chunk[..len].copy_from_slice(&bytes[..len]);
-        }
+        RNG_CALL_COUNT.fetch_add(1, Ordering::Relaxed);
+        with_current_stream(|rng| rng.fill_bytes(out));
     }
-    
+
     /// Generate random integer in range [min, max)
+    ///
+    /// Uses Lemire's method rather than `rand % range`: a plain modulo is
+    /// biased toward smaller values whenever `range` doesn't divide 2^64
+    /// evenly, and that bias gets worse the larger `range` is relative to
+    /// 2^64.
     pub fn random_range(min: i64, max: i64) -> i64 {
         if min >= max {
             return min;
         }
         let range = (max - min) as u64;
-        let rand = Self::random_u64() % range;
-        min + rand as i64
+        min + Self::lemire_bounded(range) as i64
+    }
+
+    /// Draw a uniform integer in `[0, range)` with no modulo bias, rejecting
+    /// and redrawing the rare values that would otherwise skew the result.
+    fn lemire_bounded(range: u64) -> u64 {
+        loop {
+            let rand = Self::random_u64();
+            let m = (rand as u128) * (range as u128);
+            let low = m as u64;
+            if low < range.wrapping_neg() % range {
+                continue;
+            }
+            return (m >> 64) as u64;
+        }
     }
-    
+
+    /// Seed the default RNG stream deterministically, for replayable runs
+    /// made with no plugin identity installed.
+    ///
+    /// Once called explicitly, the generator stops auto-reseeding from OS
+    /// entropy so a given seed always produces the same stream.
+    pub fn seed(seed: &[u8; 32]) {
+        PLUGIN_RNG.lock().unwrap().seed(*seed);
+    }
+
+    /// Seed `plugin_id`'s stream deterministically, for replayable plugin
+    /// runs. Without this call, a plugin's stream is derived once from OS
+    /// entropy mixed with its identity the first time it draws a random
+    /// value, so it is *not* reproducible across a reload or process
+    /// restart - only an explicit `seed_plugin` call makes it so, and only
+    /// until the next explicit reseed.
+    pub fn seed_plugin(plugin_id: &str, seed: [u8; 32]) {
+        plugin_stream(plugin_id).lock().unwrap().seed(seed);
+    }
+
     /// Get number of RNG calls made
     pub fn get_call_count() -> u64 {
         RNG_CALL_COUNT.load(Ordering::Relaxed)
@@ -83,7 +382,7 @@ pub unsafe extern "C" fn zenith_random_bytes(out_ptr: *mut u8, len: usize) -> i3
     if out_ptr.is_null() {
         return -1;
     }
-    
+
     let slice = std::slice::from_raw_parts_mut(out_ptr, len);
     RandomAPI::random_bytes(slice);
     0
@@ -94,6 +393,23 @@ pub extern "C" fn zenith_random_range(min: i64, max: i64) -> i64 {
     RandomAPI::random_range(min, max)
 }
 
+/// Seed the default RNG stream deterministically from a 32-byte buffer (see
+/// [`RandomAPI::seed`]); per-plugin streams are not affected.
+///
+/// # Safety
+/// `seed_ptr` must point to at least 32 readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn zenith_random_seed(seed_ptr: *const u8) -> i32 {
+    if seed_ptr.is_null() {
+        return -1;
+    }
+    let slice = std::slice::from_raw_parts(seed_ptr, 32);
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(slice);
+    RandomAPI::seed(&seed);
+    0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,4 +442,76 @@ mod tests {
         // Check not all zeros
         assert!(buf.iter().any(|&x| x != 0));
     }
+
+    #[test]
+    fn test_seed_is_deterministic() {
+        let seed = [7u8; 32];
+
+        RandomAPI::seed(&seed);
+        let mut first = [0u8; 32];
+        RandomAPI::random_bytes(&mut first);
+
+        RandomAPI::seed(&seed);
+        let mut second = [0u8; 32];
+        RandomAPI::random_bytes(&mut second);
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_plugin_stream_is_independent_of_default_stream() {
+        random_context::clear();
+        let default_val = RandomAPI::random_u64();
+
+        random_context::install("plugin-a");
+        let plugin_val = RandomAPI::random_u64();
+        random_context::clear();
+
+        // Astronomically unlikely to collide if the streams are actually
+        // distinct rather than aliasing the same generator.
+        assert_ne!(default_val, plugin_val);
+    }
+
+    #[test]
+    fn test_seed_plugin_is_deterministic_and_does_not_affect_others() {
+        random_context::install("plugin-b");
+        RandomAPI::seed_plugin("plugin-b", [3u8; 32]);
+        let mut first = [0u8; 32];
+        RandomAPI::random_bytes(&mut first);
+
+        RandomAPI::seed_plugin("plugin-b", [3u8; 32]);
+        let mut second = [0u8; 32];
+        RandomAPI::random_bytes(&mut second);
+        random_context::clear();
+
+        assert_eq!(first, second);
+
+        random_context::install("plugin-c");
+        RandomAPI::seed_plugin("plugin-c", [9u8; 32]);
+        let mut other = [0u8; 32];
+        RandomAPI::random_bytes(&mut other);
+        random_context::clear();
+
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_chacha20_known_test_vector() {
+        // RFC 8439 section 2.3.2: key = 0x00..=0x1f,
+        // nonce = 00:00:00:09:00:00:00:4a:00:00:00:00, block counter = 1.
+        let mut key = [0u8; 32];
+        for (i, b) in key.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        let mut rng = ChaCha20Rng::from_seed(key);
+        rng.nonce = [0x0900_0000, 0x4a00_0000, 0x0000_0000];
+        rng.counter = 1;
+        rng.refill();
+
+        let expected: [u8; 16] = [
+            0x10, 0xf1, 0xe7, 0xe4, 0xd1, 0x3b, 0x59, 0x15, 0x50, 0x0f, 0xdd, 0x1f, 0xa3, 0x20,
+            0x71, 0xc4,
+        ];
+        assert_eq!(&rng.block[0..16], &expected);
+    }
 }
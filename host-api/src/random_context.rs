@@ -0,0 +1,47 @@
+/// Thread-local plugin identity installed by the host around a plugin
+/// invocation, mirroring [`crate::event_context`] and
+/// [`crate::capability_context`]. Lets [`crate::RandomAPI`] key a distinct
+/// ChaCha20 stream per plugin instead of sharing one process-wide stream,
+/// so one plugin's random draws can never be correlated with (or exhaust
+/// the reseed budget of) another's.
+///
+/// A call made with no context installed - a native Rust caller embedding
+/// `host_api` directly, or a unit test - falls back to the single
+/// process-wide stream that predates per-plugin streams.
+use std::cell::RefCell;
+
+thread_local! {
+    static CURRENT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Install `plugin_id` as the active identity for the calling thread.
+/// Callers must pair this with a matching [`clear`] once the plugin
+/// invocation the identity was installed for has returned.
+pub fn install(plugin_id: impl Into<String>) {
+    CURRENT.with(|cell| *cell.borrow_mut() = Some(plugin_id.into()));
+}
+
+/// Clear the active plugin identity for the calling thread.
+pub fn clear() {
+    CURRENT.with(|cell| *cell.borrow_mut() = None);
+}
+
+/// The plugin identity installed for the calling thread, if any.
+pub fn current() -> Option<String> {
+    CURRENT.with(|cell| cell.borrow().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_and_clear() {
+        clear();
+        assert_eq!(current(), None);
+        install("plugin-a");
+        assert_eq!(current(), Some("plugin-a".to_string()));
+        clear();
+        assert_eq!(current(), None);
+    }
+}
@@ -14,8 +14,80 @@
 
 extern crate alloc;
 
+use alloc::vec;
 use alloc::vec::Vec;
 
+/// Deterministic PRNG used to make pipeline augmentations reproducible from a
+/// single `seed`: a SplitMix64 step mixes the raw seed into a well-distributed
+/// state, then each draw advances that state with xorshift64*.
+struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        let mut z = seed.wrapping_add(0x9E3779B97F4A7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        Self {
+            state: if z == 0 { 1 } else { z },
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+
+    /// Uniform float in `[0, 1)`.
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Uniform float in `[-1, 1)`, used for symmetric jitter deltas.
+    fn next_signed_unit(&mut self) -> f32 {
+        self.next_f32() * 2.0 - 1.0
+    }
+
+    /// `true` with 50% probability.
+    fn next_bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Uniform integer in `[0, bound]` inclusive.
+    fn next_below_inclusive(&mut self, bound: u32) -> u32 {
+        if bound == 0 {
+            0
+        } else {
+            (self.next_u64() % (bound as u64 + 1)) as u32
+        }
+    }
+}
+
+/// `core` has no floating-point transcendentals without `std`/`libm`, so the
+/// handful this plugin needs are implemented directly on top of integer casts.
+fn floorf(x: f32) -> f32 {
+    let truncated = x as i64 as f32;
+    if x < truncated {
+        truncated - 1.0
+    } else {
+        truncated
+    }
+}
+
+fn roundf(x: f32) -> f32 {
+    if x >= 0.0 {
+        floorf(x + 0.5)
+    } else {
+        -floorf(-x + 0.5)
+    }
+}
+
 /// Image data structure passed from host
 #[repr(C)]
 pub struct ImageData {
@@ -59,7 +131,73 @@ impl Default for NormalizeConfig {
     }
 }
 
+/// Random crop configuration. The crop origin is drawn uniformly from the
+/// valid range by the pipeline's seeded PRNG, not supplied by the caller.
+#[repr(C)]
+pub struct CropConfig {
+    pub crop_width: u32,
+    pub crop_height: u32,
+}
+
+/// Color jitter configuration: brightness/contrast factors are drawn as
+/// `1.0 +/- range` by the pipeline's seeded PRNG.
+#[repr(C)]
+pub struct ColorJitterConfig {
+    pub brightness_range: f32,
+    pub contrast_range: f32,
+}
+
+impl Default for ColorJitterConfig {
+    fn default() -> Self {
+        Self {
+            brightness_range: 0.2,
+            contrast_range: 0.2,
+        }
+    }
+}
+
+/// Bitmask selecting which stages of [`process_image`]'s fixed
+/// resize -> crop -> flip -> color-jitter -> normalize pipeline to run.
+pub const OP_RESIZE: u32 = 1 << 0;
+pub const OP_CROP: u32 = 1 << 1;
+pub const OP_FLIP: u32 = 1 << 2;
+pub const OP_COLOR_JITTER: u32 = 1 << 3;
+pub const OP_NORMALIZE: u32 = 1 << 4;
+
+/// Configuration for the full [`process_image`] augmentation pipeline.
+///
+/// `seed` drives a single PRNG stream threaded through the crop, flip and
+/// color-jitter stages in that order, so the same `seed` and `ops` always
+/// produce byte-identical output for a given input image.
+#[repr(C)]
+pub struct PipelineConfig {
+    pub seed: u64,
+    pub ops: u32,
+    pub input_width: u32,
+    pub input_height: u32,
+    pub channels: u32,
+    pub resize: ResizeConfig,
+    pub crop: CropConfig,
+    pub jitter: ColorJitterConfig,
+    pub normalize: NormalizeConfig,
+}
+
 /// Plugin entry point - called by Zenith runtime
+///
+/// Reads a [`PipelineConfig`] from `config_ptr` and applies the stages
+/// selected by `PipelineConfig::ops`, in the fixed order
+/// resize -> crop -> flip -> color-jitter -> normalize, threading a single
+/// seeded PRNG through the random stages so results are reproducible.
+///
+/// If [`OP_NORMALIZE`] is set, `output_ptr` receives `width * height * 3`
+/// little-endian `f32` values (`channels` must be 3). Otherwise it receives
+/// the raw `u8` pixel buffer after the preceding stages.
+///
+/// # Safety
+/// `input_ptr` must point to `config.input_width * config.input_height *
+/// config.channels` readable bytes, `config_ptr` to a valid `PipelineConfig`,
+/// and `output_ptr`/`output_len` to buffers large enough for the final stage's
+/// output (see above).
 #[no_mangle]
 pub extern "C" fn process_image(
     input_ptr: *const u8,
@@ -68,22 +206,107 @@ pub extern "C" fn process_image(
     output_ptr: *mut u8,
     output_len: *mut usize,
 ) -> i32 {
-    // Safety: These pointers are provided by the trusted host
-    // In production, add proper validation
-    
-    // Placeholder implementation
-    // Full implementation would:
-    // 1. Deserialize input image from input_ptr
-    // 2. Parse config from config_ptr
-    // 3. Apply transformations
-    // 4. Write result to output_ptr
-    // 5. Set output_len
-    
-    0 // Success
+    if input_ptr.is_null() || config_ptr.is_null() || output_ptr.is_null() || output_len.is_null() {
+        return -1;
+    }
+
+    // Safety: caller guarantees `config_ptr` points to a valid `PipelineConfig`.
+    let config = unsafe { &*(config_ptr as *const PipelineConfig) };
+
+    let channels = config.channels;
+    if config.input_width == 0 || config.input_height == 0 || channels == 0 {
+        return -1;
+    }
+    let expected_len = config.input_width as usize * config.input_height as usize * channels as usize;
+    if input_len != expected_len {
+        return -2;
+    }
+
+    // Safety: caller guarantees `input_ptr` covers `input_len` bytes.
+    let input = unsafe { core::slice::from_raw_parts(input_ptr, input_len) };
+
+    let mut rng = Rng::new(config.seed);
+    let mut buffer: Vec<u8> = input.to_vec();
+    let mut width = config.input_width;
+    let mut height = config.input_height;
+    let channels = channels as usize;
+
+    if config.ops & OP_RESIZE != 0 {
+        let target_width = config.resize.target_width;
+        let target_height = config.resize.target_height;
+        if target_width == 0 || target_height == 0 {
+            return -1;
+        }
+        let mut resized = vec![0u8; target_width as usize * target_height as usize * channels];
+        if config.resize.interpolation == 0 {
+            resize_nearest_slice(&buffer, width, height, channels, target_width, target_height, &mut resized);
+        } else {
+            resize_bilinear_slice(&buffer, width, height, channels, target_width, target_height, &mut resized);
+        }
+        buffer = resized;
+        width = target_width;
+        height = target_height;
+    }
+
+    if config.ops & OP_CROP != 0 {
+        let crop_width = config.crop.crop_width.min(width);
+        let crop_height = config.crop.crop_height.min(height);
+        if crop_width == 0 || crop_height == 0 {
+            return -1;
+        }
+        let x0 = rng.next_below_inclusive(width - crop_width);
+        let y0 = rng.next_below_inclusive(height - crop_height);
+        let mut cropped = vec![0u8; crop_width as usize * crop_height as usize * channels];
+        crop_slice(&buffer, width, x0, y0, crop_width, crop_height, channels, &mut cropped);
+        buffer = cropped;
+        width = crop_width;
+        height = crop_height;
+    }
+
+    if config.ops & OP_FLIP != 0 && rng.next_bool() {
+        flip_rows_in_place(&mut buffer, width as usize, height as usize, channels);
+    }
+
+    if config.ops & OP_COLOR_JITTER != 0 {
+        let brightness_factor = 1.0 + rng.next_signed_unit() * config.jitter.brightness_range;
+        let contrast_factor = 1.0 + rng.next_signed_unit() * config.jitter.contrast_range;
+        jitter_in_place(&mut buffer, brightness_factor, contrast_factor);
+    }
+
+    if config.ops & OP_NORMALIZE != 0 {
+        if channels != 3 {
+            return -3;
+        }
+        let pixel_count = width as usize * height as usize;
+        let means = [config.normalize.mean_r, config.normalize.mean_g, config.normalize.mean_b];
+        let stds = [config.normalize.std_r, config.normalize.std_g, config.normalize.std_b];
+        // Safety: caller guarantees `output_ptr` covers `pixel_count * 3 * 4` bytes.
+        let out_bytes = unsafe { core::slice::from_raw_parts_mut(output_ptr, pixel_count * 3 * 4) };
+        for i in 0..pixel_count * 3 {
+            let c = i % 3;
+            let value = (buffer[i] as f32 / 255.0 - means[c]) / stds[c];
+            out_bytes[i * 4..i * 4 + 4].copy_from_slice(&value.to_le_bytes());
+        }
+        // Safety: caller guarantees `output_len` is writable.
+        unsafe { *output_len = pixel_count * 3 * 4 };
+    } else {
+        // Safety: caller guarantees `output_ptr` covers `buffer.len()` bytes.
+        let out = unsafe { core::slice::from_raw_parts_mut(output_ptr, buffer.len()) };
+        out.copy_from_slice(&buffer);
+        // Safety: caller guarantees `output_len` is writable.
+        unsafe { *output_len = buffer.len() };
+    }
+
+    0
 }
 
 /// Resize image using nearest-neighbor interpolation
 /// Fast but lower quality - good for training
+///
+/// # Safety
+/// `input_ptr` must point to `input_width * input_height * channels` readable
+/// bytes and `output_ptr` to `target_width * target_height * channels`
+/// writable bytes.
 #[no_mangle]
 pub extern "C" fn resize_nearest(
     input_ptr: *const u8,
@@ -94,12 +317,50 @@ pub extern "C" fn resize_nearest(
     target_height: u32,
     output_ptr: *mut u8,
 ) -> i32 {
-    // Placeholder for actual resize implementation
+    if input_width == 0 || input_height == 0 || channels == 0 || target_width == 0 || target_height == 0 {
+        return -1;
+    }
+
+    let channels_usize = channels as usize;
+    let input_len = input_width as usize * input_height as usize * channels_usize;
+    let output_len = target_width as usize * target_height as usize * channels_usize;
+
+    // Safety: caller guarantees `input_ptr`/`output_ptr` cover these lengths.
+    let input = unsafe { core::slice::from_raw_parts(input_ptr, input_len) };
+    let output = unsafe { core::slice::from_raw_parts_mut(output_ptr, output_len) };
+
+    resize_nearest_slice(input, input_width, input_height, channels_usize, target_width, target_height, output);
+
     0
 }
 
+fn resize_nearest_slice(
+    input: &[u8],
+    input_width: u32,
+    input_height: u32,
+    channels: usize,
+    target_width: u32,
+    target_height: u32,
+    output: &mut [u8],
+) {
+    for oy in 0..target_height {
+        let sy = (oy * input_height / target_height) as usize;
+        for ox in 0..target_width {
+            let sx = (ox * input_width / target_width) as usize;
+            let src_base = (sy * input_width as usize + sx) * channels;
+            let dst_base = (oy as usize * target_width as usize + ox as usize) * channels;
+            output[dst_base..dst_base + channels].copy_from_slice(&input[src_base..src_base + channels]);
+        }
+    }
+}
+
 /// Resize image using bilinear interpolation
 /// Higher quality but slower
+///
+/// # Safety
+/// `input_ptr` must point to `input_width * input_height * channels` readable
+/// bytes and `output_ptr` to `target_width * target_height * channels`
+/// writable bytes.
 #[no_mangle]
 pub extern "C" fn resize_bilinear(
     input_ptr: *const u8,
@@ -110,12 +371,76 @@ pub extern "C" fn resize_bilinear(
     target_height: u32,
     output_ptr: *mut u8,
 ) -> i32 {
-    // Placeholder for actual resize implementation
+    if input_width == 0 || input_height == 0 || channels == 0 || target_width == 0 || target_height == 0 {
+        return -1;
+    }
+
+    let channels_usize = channels as usize;
+    let input_len = input_width as usize * input_height as usize * channels_usize;
+    let output_len = target_width as usize * target_height as usize * channels_usize;
+
+    // Safety: caller guarantees `input_ptr`/`output_ptr` cover these lengths.
+    let input = unsafe { core::slice::from_raw_parts(input_ptr, input_len) };
+    let output = unsafe { core::slice::from_raw_parts_mut(output_ptr, output_len) };
+
+    resize_bilinear_slice(input, input_width, input_height, channels_usize, target_width, target_height, output);
+
     0
 }
 
+fn resize_bilinear_slice(
+    input: &[u8],
+    input_width: u32,
+    input_height: u32,
+    channels: usize,
+    target_width: u32,
+    target_height: u32,
+    output: &mut [u8],
+) {
+    let in_w = input_width as i64;
+    let in_h = input_height as i64;
+
+    for oy in 0..target_height as i64 {
+        let fy = (oy as f32 + 0.5) * input_height as f32 / target_height as f32 - 0.5;
+        let y0 = floorf(fy) as i64;
+        let y1 = y0 + 1;
+        let wy = fy - y0 as f32;
+        let y0c = y0.clamp(0, in_h - 1) as usize;
+        let y1c = y1.clamp(0, in_h - 1) as usize;
+
+        for ox in 0..target_width as i64 {
+            let fx = (ox as f32 + 0.5) * input_width as f32 / target_width as f32 - 0.5;
+            let x0 = floorf(fx) as i64;
+            let x1 = x0 + 1;
+            let wx = fx - x0 as f32;
+            let x0c = x0.clamp(0, in_w - 1) as usize;
+            let x1c = x1.clamp(0, in_w - 1) as usize;
+
+            let p00 = (y0c * input_width as usize + x0c) * channels;
+            let p10 = (y0c * input_width as usize + x1c) * channels;
+            let p01 = (y1c * input_width as usize + x0c) * channels;
+            let p11 = (y1c * input_width as usize + x1c) * channels;
+
+            let dst_base = (oy as usize * target_width as usize + ox as usize) * channels;
+
+            for c in 0..channels {
+                let blended = (1.0 - wx) * (1.0 - wy) * input[p00 + c] as f32
+                    + wx * (1.0 - wy) * input[p10 + c] as f32
+                    + (1.0 - wx) * wy * input[p01 + c] as f32
+                    + wx * wy * input[p11 + c] as f32;
+                output[dst_base + c] = roundf(blended).clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+}
+
 /// Normalize pixel values using mean and std
-/// Converts uint8 [0,255] to float32 normalized values
+/// Converts uint8 [0,255] to float32 normalized values: `(v / 255 - mean) / std`
+/// per channel, assuming 3 interleaved channels (RGB).
+///
+/// # Safety
+/// `input_ptr` must point to `pixel_count * 3` readable bytes and
+/// `output_ptr` to `pixel_count * 3` writable `f32`s.
 #[no_mangle]
 pub extern "C" fn normalize(
     input_ptr: *const u8,
@@ -128,11 +453,30 @@ pub extern "C" fn normalize(
     std_b: f32,
     output_ptr: *mut f32,
 ) -> i32 {
-    // Placeholder for actual normalization implementation
+    if std_r == 0.0 || std_g == 0.0 || std_b == 0.0 {
+        return -1;
+    }
+
+    let len = pixel_count * 3;
+    // Safety: caller guarantees `input_ptr`/`output_ptr` cover `pixel_count * 3`.
+    let input = unsafe { core::slice::from_raw_parts(input_ptr, len) };
+    let output = unsafe { core::slice::from_raw_parts_mut(output_ptr, len) };
+
+    let means = [mean_r, mean_g, mean_b];
+    let stds = [std_r, std_g, std_b];
+    for (i, &v) in input.iter().enumerate() {
+        let c = i % 3;
+        output[i] = (v as f32 / 255.0 - means[c]) / stds[c];
+    }
+
     0
 }
 
-/// Random horizontal flip (50% probability)
+/// Random horizontal flip (50% probability, deterministic given `seed`).
+///
+/// # Safety
+/// `data_ptr` must point to `width * height * channels` readable and
+/// writable bytes.
 #[no_mangle]
 pub extern "C" fn random_horizontal_flip(
     data_ptr: *mut u8,
@@ -141,10 +485,136 @@ pub extern "C" fn random_horizontal_flip(
     channels: u32,
     seed: u64,
 ) -> i32 {
-    // Placeholder for actual flip implementation
+    if width == 0 || height == 0 || channels == 0 {
+        return -1;
+    }
+
+    let mut rng = Rng::new(seed);
+    if !rng.next_bool() {
+        return 0;
+    }
+
+    let channels = channels as usize;
+    let len = width as usize * height as usize * channels;
+    // Safety: caller guarantees `data_ptr` covers `len` bytes.
+    let data = unsafe { core::slice::from_raw_parts_mut(data_ptr, len) };
+    flip_rows_in_place(data, width as usize, height as usize, channels);
+
     0
 }
 
+fn flip_rows_in_place(data: &mut [u8], width: usize, height: usize, channels: usize) {
+    for y in 0..height {
+        let row = &mut data[y * width * channels..(y + 1) * width * channels];
+        for x in 0..width / 2 {
+            let (l, r) = (x * channels, (width - 1 - x) * channels);
+            for c in 0..channels {
+                row.swap(l + c, r + c);
+            }
+        }
+    }
+}
+
+/// Color jitter: scales pixel values by randomly drawn brightness/contrast
+/// factors in `1.0 +/- range`, deterministic given `seed`.
+///
+/// # Safety
+/// `data_ptr` must point to `pixel_count * channels` readable and writable
+/// bytes.
+#[no_mangle]
+pub extern "C" fn color_jitter(
+    data_ptr: *mut u8,
+    pixel_count: usize,
+    channels: u32,
+    brightness_range: f32,
+    contrast_range: f32,
+    seed: u64,
+) -> i32 {
+    if channels == 0 {
+        return -1;
+    }
+
+    let mut rng = Rng::new(seed);
+    let brightness_factor = 1.0 + rng.next_signed_unit() * brightness_range;
+    let contrast_factor = 1.0 + rng.next_signed_unit() * contrast_range;
+
+    let len = pixel_count * channels as usize;
+    // Safety: caller guarantees `data_ptr` covers `len` bytes.
+    let data = unsafe { core::slice::from_raw_parts_mut(data_ptr, len) };
+    jitter_in_place(data, brightness_factor, contrast_factor);
+
+    0
+}
+
+fn jitter_in_place(data: &mut [u8], brightness_factor: f32, contrast_factor: f32) {
+    for v in data.iter_mut() {
+        let centered = (*v as f32 - 127.5) * contrast_factor + 127.5;
+        let adjusted = centered * brightness_factor;
+        *v = roundf(adjusted).clamp(0.0, 255.0) as u8;
+    }
+}
+
+/// Random crop: copies a `crop_width x crop_height` window at a uniformly
+/// random, deterministic-given-`seed` origin into `output_ptr`.
+///
+/// # Safety
+/// `input_ptr` must point to `input_width * input_height * channels` readable
+/// bytes and `output_ptr` to `crop_width * crop_height * channels` writable
+/// bytes.
+#[no_mangle]
+pub extern "C" fn random_crop(
+    input_ptr: *const u8,
+    input_width: u32,
+    input_height: u32,
+    channels: u32,
+    crop_width: u32,
+    crop_height: u32,
+    seed: u64,
+    output_ptr: *mut u8,
+) -> i32 {
+    if input_width == 0 || input_height == 0 || channels == 0 || crop_width == 0 || crop_height == 0 {
+        return -1;
+    }
+    if crop_width > input_width || crop_height > input_height {
+        return -2;
+    }
+
+    let mut rng = Rng::new(seed);
+    let x0 = rng.next_below_inclusive(input_width - crop_width);
+    let y0 = rng.next_below_inclusive(input_height - crop_height);
+
+    let channels = channels as usize;
+    let input_len = input_width as usize * input_height as usize * channels;
+    let output_len = crop_width as usize * crop_height as usize * channels;
+
+    // Safety: caller guarantees `input_ptr`/`output_ptr` cover these lengths.
+    let input = unsafe { core::slice::from_raw_parts(input_ptr, input_len) };
+    let output = unsafe { core::slice::from_raw_parts_mut(output_ptr, output_len) };
+
+    crop_slice(input, input_width, x0, y0, crop_width, crop_height, channels, output);
+
+    0
+}
+
+fn crop_slice(
+    input: &[u8],
+    input_width: u32,
+    x0: u32,
+    y0: u32,
+    crop_width: u32,
+    crop_height: u32,
+    channels: usize,
+    output: &mut [u8],
+) {
+    let row_bytes = crop_width as usize * channels;
+    for row in 0..crop_height as usize {
+        let src_y = y0 as usize + row;
+        let src_start = (src_y * input_width as usize + x0 as usize) * channels;
+        let dst_start = row * row_bytes;
+        output[dst_start..dst_start + row_bytes].copy_from_slice(&input[src_start..src_start + row_bytes]);
+    }
+}
+
 /// Plugin metadata - called by Zenith to discover capabilities
 #[no_mangle]
 pub extern "C" fn plugin_info() -> *const u8 {
@@ -157,3 +627,249 @@ pub extern "C" fn plugin_info() -> *const u8 {
 pub extern "C" fn plugin_version() -> u32 {
     1 // Version 0.1.0
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::vec;
+
+    /// 2x2, 1 channel, values chosen so each quadrant is distinct.
+    const SMALL: [u8; 4] = [10, 20, 30, 40];
+
+    #[test]
+    fn resize_nearest_upsamples_2x2_to_4x4() {
+        let mut output = vec![0u8; 16];
+        let rc = resize_nearest(SMALL.as_ptr(), 2, 2, 1, 4, 4, output.as_mut_ptr());
+        assert_eq!(rc, 0);
+        assert_eq!(
+            output,
+            vec![
+                10, 10, 20, 20,
+                10, 10, 20, 20,
+                30, 30, 40, 40,
+                30, 30, 40, 40,
+            ]
+        );
+    }
+
+    #[test]
+    fn resize_nearest_downsamples_4x4_to_2x2() {
+        #[rustfmt::skip]
+        let input: [u8; 16] = [
+            1, 1, 2, 2,
+            1, 1, 2, 2,
+            3, 3, 4, 4,
+            3, 3, 4, 4,
+        ];
+        let mut output = vec![0u8; 4];
+        let rc = resize_nearest(input.as_ptr(), 4, 4, 1, 2, 2, output.as_mut_ptr());
+        assert_eq!(rc, 0);
+        assert_eq!(output, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn resize_nearest_rejects_zero_dimension() {
+        let mut output = vec![0u8; 4];
+        let rc = resize_nearest(SMALL.as_ptr(), 2, 0, 1, 2, 2, output.as_mut_ptr());
+        assert_eq!(rc, -1);
+    }
+
+    #[test]
+    fn resize_bilinear_identity_resize_is_exact() {
+        let mut output = vec![0u8; 4];
+        let rc = resize_bilinear(SMALL.as_ptr(), 2, 2, 1, 2, 2, output.as_mut_ptr());
+        assert_eq!(rc, 0);
+        assert_eq!(output, vec![10, 20, 30, 40]);
+    }
+
+    #[test]
+    fn resize_bilinear_upsamples_2x2_to_4x4_approximately() {
+        let mut output = vec![0u8; 16];
+        let rc = resize_bilinear(SMALL.as_ptr(), 2, 2, 1, 4, 4, output.as_mut_ptr());
+        assert_eq!(rc, 0);
+
+        // Corners match the source corners exactly (clamped edge samples).
+        assert_eq!(output[0], 10);
+        assert_eq!(output[3], 20);
+        assert_eq!(output[12], 30);
+        assert_eq!(output[15], 40);
+
+        // Interior pixels are weighted blends of all four source corners,
+        // hand-computed from the half-pixel-center sampling formula.
+        assert_eq!(output[5], 18);
+        assert_eq!(output[6], 23);
+        assert_eq!(output[9], 28);
+        assert_eq!(output[10], 33);
+    }
+
+    #[test]
+    fn resize_bilinear_rejects_zero_dimension() {
+        let mut output = vec![0u8; 4];
+        let rc = resize_bilinear(SMALL.as_ptr(), 0, 2, 1, 2, 2, output.as_mut_ptr());
+        assert_eq!(rc, -1);
+    }
+
+    #[test]
+    fn normalize_matches_hand_computed_values() {
+        // A single RGB pixel: pure mid-gray.
+        let input: [u8; 3] = [128, 128, 128];
+        let mut output = [0.0f32; 3];
+        let rc = normalize(input.as_ptr(), 1, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5, output.as_mut_ptr());
+        assert_eq!(rc, 0);
+        let expected = (128.0 / 255.0 - 0.5) / 0.5;
+        for v in output {
+            assert!((v - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn normalize_rejects_zero_std() {
+        let input: [u8; 3] = [0, 0, 0];
+        let mut output = [0.0f32; 3];
+        let rc = normalize(input.as_ptr(), 1, 0.0, 0.0, 0.0, 0.0, 0.5, 0.5, output.as_mut_ptr());
+        assert_eq!(rc, -1);
+    }
+
+    #[test]
+    fn random_horizontal_flip_is_deterministic_per_seed() {
+        #[rustfmt::skip]
+        let original: [u8; 4] = [
+            1, 2,
+            3, 4,
+        ];
+
+        // Find a seed that flips and one that doesn't; both must be stable.
+        let mut flipped_seed = None;
+        let mut kept_seed = None;
+        for seed in 0..64u64 {
+            let mut data = original;
+            random_horizontal_flip(data.as_mut_ptr(), 2, 2, 1, seed);
+            if data == original && kept_seed.is_none() {
+                kept_seed = Some(seed);
+            } else if data != original && flipped_seed.is_none() {
+                flipped_seed = Some(seed);
+            }
+            if flipped_seed.is_some() && kept_seed.is_some() {
+                break;
+            }
+        }
+
+        let flipped_seed = flipped_seed.expect("expected at least one seed to flip");
+        let mut data = original;
+        random_horizontal_flip(data.as_mut_ptr(), 2, 2, 1, flipped_seed);
+        assert_eq!(data, [2, 1, 4, 3]);
+
+        // Same seed always produces the same result.
+        let mut data_again = original;
+        random_horizontal_flip(data_again.as_mut_ptr(), 2, 2, 1, flipped_seed);
+        assert_eq!(data, data_again);
+    }
+
+    #[test]
+    fn color_jitter_is_deterministic_per_seed() {
+        let original: [u8; 4] = [50, 100, 150, 200];
+        let mut a = original;
+        let mut b = original;
+        color_jitter(a.as_mut_ptr(), 4, 1, 0.2, 0.2, 42);
+        color_jitter(b.as_mut_ptr(), 4, 1, 0.2, 0.2, 42);
+        assert_eq!(a, b);
+        assert_ne!(a, original);
+    }
+
+    #[test]
+    fn random_crop_stays_within_bounds_and_is_deterministic() {
+        #[rustfmt::skip]
+        let input: [u8; 16] = [
+            1, 2, 3, 4,
+            5, 6, 7, 8,
+            9, 10, 11, 12,
+            13, 14, 15, 16,
+        ];
+        let mut a = vec![0u8; 4];
+        let mut b = vec![0u8; 4];
+        let rc_a = random_crop(input.as_ptr(), 4, 4, 1, 2, 2, 7, a.as_mut_ptr());
+        let rc_b = random_crop(input.as_ptr(), 4, 4, 1, 2, 2, 7, b.as_mut_ptr());
+        assert_eq!(rc_a, 0);
+        assert_eq!(rc_b, 0);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn random_crop_rejects_crop_larger_than_input() {
+        let input: [u8; 4] = [1, 2, 3, 4];
+        let mut output = vec![0u8; 16];
+        let rc = random_crop(input.as_ptr(), 2, 2, 1, 4, 4, 1, output.as_mut_ptr());
+        assert_eq!(rc, -2);
+    }
+
+    #[test]
+    fn process_image_same_seed_is_byte_identical() {
+        #[rustfmt::skip]
+        let input: [u8; 48] = [
+            10, 20, 30,  40, 50, 60,  70, 80, 90,  100, 110, 120,
+            10, 20, 30,  40, 50, 60,  70, 80, 90,  100, 110, 120,
+            10, 20, 30,  40, 50, 60,  70, 80, 90,  100, 110, 120,
+            10, 20, 30,  40, 50, 60,  70, 80, 90,  100, 110, 120,
+        ];
+
+        let config = PipelineConfig {
+            seed: 1234,
+            ops: OP_FLIP | OP_COLOR_JITTER,
+            input_width: 4,
+            input_height: 4,
+            channels: 3,
+            resize: ResizeConfig { target_width: 0, target_height: 0, interpolation: 0 },
+            crop: CropConfig { crop_width: 0, crop_height: 0 },
+            jitter: ColorJitterConfig::default(),
+            normalize: NormalizeConfig::default(),
+        };
+
+        let mut output_a = vec![0u8; 48];
+        let mut output_b = vec![0u8; 48];
+        let mut len_a: usize = 0;
+        let mut len_b: usize = 0;
+
+        let config_ptr = &config as *const PipelineConfig as *const u8;
+        let rc_a = process_image(input.as_ptr(), 48, config_ptr, output_a.as_mut_ptr(), &mut len_a);
+        let rc_b = process_image(input.as_ptr(), 48, config_ptr, output_b.as_mut_ptr(), &mut len_b);
+
+        assert_eq!(rc_a, 0);
+        assert_eq!(rc_b, 0);
+        assert_eq!(len_a, 48);
+        assert_eq!(len_a, len_b);
+        assert_eq!(output_a, output_b);
+    }
+
+    #[test]
+    fn process_image_full_pipeline_produces_normalized_f32_output() {
+        #[rustfmt::skip]
+        let input: [u8; 48] = [
+            10, 20, 30,  40, 50, 60,  70, 80, 90,  100, 110, 120,
+            10, 20, 30,  40, 50, 60,  70, 80, 90,  100, 110, 120,
+            10, 20, 30,  40, 50, 60,  70, 80, 90,  100, 110, 120,
+            10, 20, 30,  40, 50, 60,  70, 80, 90,  100, 110, 120,
+        ];
+
+        let config = PipelineConfig {
+            seed: 99,
+            ops: OP_RESIZE | OP_CROP | OP_FLIP | OP_COLOR_JITTER | OP_NORMALIZE,
+            input_width: 4,
+            input_height: 4,
+            channels: 3,
+            resize: ResizeConfig { target_width: 4, target_height: 4, interpolation: 1 },
+            crop: CropConfig { crop_width: 2, crop_height: 2 },
+            jitter: ColorJitterConfig::default(),
+            normalize: NormalizeConfig::default(),
+        };
+
+        let mut output = vec![0u8; 2 * 2 * 3 * 4];
+        let mut len: usize = 0;
+        let config_ptr = &config as *const PipelineConfig as *const u8;
+        let rc = process_image(input.as_ptr(), 48, config_ptr, output.as_mut_ptr(), &mut len);
+
+        assert_eq!(rc, 0);
+        assert_eq!(len, 2 * 2 * 3 * 4);
+    }
+}
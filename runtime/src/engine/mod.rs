@@ -5,9 +5,19 @@ use crate::scheduler::{Scheduler, Priority};
 use crate::vm::VM;
 use crate::host_calls::HostCallInterface;
 use anyhow::Result;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::collections::HashMap;
 use tokio::sync::RwLock;
+use zenith_runtime_cpu::NumaTopology;
+
+mod cgroup;
+use cgroup::PluginCgroup;
+pub use cgroup::DEFAULT_CGROUP_PARENT;
+
+mod placement;
+use placement::Placer;
+pub use placement::PlacementPolicy;
 
 /// Plugin registry entry
 #[allow(dead_code)]
@@ -15,6 +25,14 @@ struct PluginEntry {
     id: String,
     vm: VM,
     metadata: PluginMetadata,
+    /// OS-level cgroup confining this plugin's CPU/memory use. `None`
+    /// when cgroup-v2 enforcement isn't available or wasn't requested;
+    /// the plugin still runs, just without this extra guard on top of
+    /// the in-process `Sandbox` timeout.
+    cgroup: Option<PluginCgroup>,
+    /// NUMA node this plugin was placed on at load time; `execute_plugin`
+    /// pins the worker and its memory policy to this node before running.
+    numa_node: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -22,6 +40,63 @@ pub struct PluginMetadata {
     pub name: String,
     pub version: String,
     pub loaded_at: std::time::SystemTime,
+    /// Resource bounds this plugin's execution worker should run under.
+    /// When set, [`RuntimeEngine`] derives a [`CgroupLimits`] from it to
+    /// enforce them at the OS level via cgroup-v2. The in-process timeout
+    /// check in `execute_plugin` always uses the engine's shared `Sandbox`
+    /// defaults, not these bounds. `None` means no OS-level confinement.
+    pub sandbox_limits: Option<SandboxLimits>,
+    /// Host-call capability grant this plugin's worker should install for
+    /// the duration of every dispatch, consulted by
+    /// `host_api::capability_context` to gate `HostAPI` calls the plugin
+    /// makes. `None` means "grant every capability", preserving the
+    /// pre-existing unrestricted default.
+    pub capabilities: Option<Arc<host_api::Capabilities>>,
+}
+
+/// Per-plugin cgroup-v2 resource limits, modeled on the subset of the OCI
+/// runtime-spec `linux.resources` block that cgroup-v2 exposes: CPU
+/// quota/period/shares and a memory limit with optional swap.
+#[derive(Debug, Clone, Copy)]
+pub struct CgroupLimits {
+    /// Allowed CPU time per period, in microseconds (`cpu.max`'s first
+    /// field). `None` leaves the CPU controller unconstrained ("max").
+    pub cpu_quota_us: Option<u64>,
+    /// Length of one CPU accounting period, in microseconds (`cpu.max`'s
+    /// second field). Ignored when `cpu_quota_us` is `None`.
+    pub cpu_period_us: u64,
+    /// Relative CPU weight against sibling cgroups, in OCI `cpu.shares`
+    /// units (2-262144, default 1024). Converted to cgroup-v2's
+    /// `cpu.weight` scale when applied. `None` leaves the default weight.
+    pub cpu_shares: Option<u64>,
+    /// Hard memory ceiling in bytes (`memory.max`). `None` leaves the
+    /// memory controller unconstrained.
+    pub memory_limit_bytes: Option<u64>,
+    /// Additional swap on top of `memory_limit_bytes` (`memory.swap.max`).
+    /// `None` leaves swap unconstrained.
+    pub memory_swap_bytes: Option<u64>,
+}
+
+impl Default for CgroupLimits {
+    fn default() -> Self {
+        Self {
+            cpu_quota_us: None,
+            cpu_period_us: 100_000,
+            cpu_shares: None,
+            memory_limit_bytes: None,
+            memory_swap_bytes: None,
+        }
+    }
+}
+
+impl From<&SandboxLimits> for CgroupLimits {
+    fn from(limits: &SandboxLimits) -> Self {
+        Self {
+            cpu_quota_us: limits.max_execution_time_ms.map(|ms| ms * 1_000),
+            memory_limit_bytes: limits.max_memory_bytes,
+            ..Self::default()
+        }
+    }
 }
 
 /// Runtime Engine that orchestrates all components
@@ -31,37 +106,134 @@ pub struct RuntimeEngine {
     sandbox: Arc<Sandbox>,
     #[allow(dead_code)]
     host_calls: Arc<HostCallInterface>,
+    /// Parent cgroup-v2 subtree plugin cgroups are created under.
+    cgroup_parent: PathBuf,
+    /// NUMA topology plugins are placed against.
+    topology: Arc<NumaTopology>,
+    /// Decides which node `load_plugin` places each new plugin on.
+    placer: Placer,
+    /// When set, compiled modules are cached here instead of being
+    /// recompiled on every `load_plugin`/restart - see
+    /// [`Self::with_cache_dir`] and [`Self::precompile_dir`].
+    cache_dir: Option<PathBuf>,
 }
 
 impl RuntimeEngine {
     pub fn new(max_concurrent: usize) -> Self {
+        Self::with_placement_policy(max_concurrent, PlacementPolicy::default())
+    }
+
+    /// Create a `RuntimeEngine` that places plugins according to
+    /// `policy` instead of the default [`PlacementPolicy::MostFreeMemory`].
+    pub fn with_placement_policy(max_concurrent: usize, policy: PlacementPolicy) -> Self {
+        let topology = NumaTopology::discover()
+            .expect("NUMA topology discovery failed; cannot size plugin placement");
         Self {
             plugins: Arc::new(RwLock::new(HashMap::new())),
             scheduler: Arc::new(Scheduler::new(max_concurrent)),
             sandbox: Arc::new(Sandbox::new(SandboxLimits::default())),
             host_calls: Arc::new(HostCallInterface::new()),
+            cgroup_parent: PathBuf::from(DEFAULT_CGROUP_PARENT),
+            topology: Arc::new(topology),
+            placer: Placer::new(policy),
+            cache_dir: None,
+        }
+    }
+
+    /// Use `parent` instead of [`DEFAULT_CGROUP_PARENT`] as the cgroup-v2
+    /// subtree plugin cgroups are created under.
+    pub fn with_cgroup_parent(mut self, parent: impl Into<PathBuf>) -> Self {
+        self.cgroup_parent = parent.into();
+        self
+    }
+
+    /// Cache compiled modules under `dir` instead of recompiling every
+    /// `.wasm` on each `load_plugin` call (and on every process restart).
+    /// Pair with [`Self::precompile_dir`] to warm the cache ahead of time,
+    /// so production startup becomes deserialize-only.
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Compiles and caches every `.wasm` file in `dir` ahead of time,
+    /// without loading any of them as a running plugin. Requires
+    /// [`Self::with_cache_dir`] to have been called; returns the number of
+    /// files precompiled.
+    pub fn precompile_dir(&self, dir: impl AsRef<std::path::Path>) -> Result<usize> {
+        let Some(cache_dir) = &self.cache_dir else {
+            tracing::warn!("precompile_dir called with no cache directory configured; skipping");
+            return Ok(0);
+        };
+
+        let mut count = 0;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "wasm") {
+                let bytes = std::fs::read(&path)?;
+                VM::precompile(&bytes, cache_dir)?;
+                count += 1;
+            }
         }
+        Ok(count)
     }
 
-    /// Load a plugin into the runtime
+    /// Load a plugin into the runtime, placing it on the node the
+    /// engine's [`PlacementPolicy`] selects.
     pub async fn load_plugin(&self, id: String, wasm_bytes: &[u8], metadata: PluginMetadata) -> Result<()> {
+        let node_id = self.placer.select_node(&self.topology);
+        self.load_plugin_on_node(id, wasm_bytes, metadata, node_id).await
+    }
+
+    /// Load a plugin pinned to a specific NUMA `node_id`, bypassing the
+    /// engine's [`PlacementPolicy`].
+    pub async fn load_plugin_on_node(
+        &self,
+        id: String,
+        wasm_bytes: &[u8],
+        metadata: PluginMetadata,
+        node_id: u32,
+    ) -> Result<()> {
         // Validate WASM
         self.sandbox.validate_wasm_bytes(wasm_bytes)?;
-        
-        // Create VM
-        let vm = VM::from_bytes(wasm_bytes)?;
-        
+
+        // Create VM, reusing a cached compiled module when a cache
+        // directory is configured.
+        let vm = match &self.cache_dir {
+            Some(cache_dir) => VM::from_bytes_with_cache(wasm_bytes, cache_dir)?,
+            None => VM::from_bytes(wasm_bytes)?,
+        };
+
+        // Best-effort cgroup-v2 confinement, sized from this plugin's
+        // sandbox limits. Degrades to `None` (plugin runs unconfined at
+        // the OS level) when cgroup-v2 isn't available or not writable.
+        let cgroup = metadata.sandbox_limits.as_ref().and_then(|limits| {
+            PluginCgroup::create(&self.cgroup_parent, &id, &CgroupLimits::from(limits))
+        });
+
         // Register plugin
         let entry = PluginEntry {
             id: id.clone(),
             vm,
             metadata,
+            cgroup,
+            numa_node: node_id,
         };
-        
+
         let mut plugins = self.plugins.write().await;
         plugins.insert(id, entry);
-        
-        tracing::info!("Plugin loaded successfully");
+
+        tracing::info!("Plugin loaded successfully on NUMA node {}", node_id);
+        Ok(())
+    }
+
+    /// Unload a plugin, tearing down its cgroup (if any) along with it.
+    pub async fn unload_plugin(&self, plugin_id: &str) -> Result<()> {
+        let mut plugins = self.plugins.write().await;
+        plugins.remove(plugin_id)
+            .ok_or_else(|| anyhow::anyhow!("Plugin not found"))?;
+        // `PluginEntry::cgroup`'s `Drop` removes the cgroup directory.
+        tracing::info!("Plugin '{}' unloaded", plugin_id);
         Ok(())
     }
 
@@ -70,17 +242,45 @@ impl RuntimeEngine {
         let plugins = self.plugins.read().await;
         let entry = plugins.get(plugin_id)
             .ok_or_else(|| anyhow::anyhow!("Plugin not found"))?;
-        
+
+        // Move the worker into the plugin's cgroup (if any) before it
+        // runs, so the OS-level CPU/memory limits actually apply to this
+        // execution.
+        if let Some(cgroup) = &entry.cgroup {
+            cgroup.move_current_process_in();
+        }
+
+        // Pin this worker to the plugin's NUMA node and bind its memory
+        // policy to the same node, so its linear memory stays node-local.
+        placement::pin_to_node(&self.topology, entry.numa_node);
+
         // Create execution context
         let mut ctx = self.sandbox.create_context();
         ctx.start();
-        
+
+        // Install this plugin's capability grant so any `HostAPI` call it
+        // makes during `execute` is gated accordingly; `None` leaves the
+        // calling thread's context untouched (trusted by default).
+        if let Some(capabilities) = &entry.metadata.capabilities {
+            host_api::capability_context::install(capabilities.clone());
+        }
+        // Gives this plugin its own CSPRNG stream for the duration of the
+        // call - see `host_api::random_context`.
+        host_api::random_context::install(plugin_id.to_string());
+
         // Execute
-        let result = entry.vm.execute(function, args)?;
-        
+        let result = entry.vm.execute(function, args);
+
+        host_api::random_context::clear();
+        if entry.metadata.capabilities.is_some() {
+            host_api::capability_context::clear();
+        }
+
+        let result = result?;
+
         // Check timeout
         ctx.check_timeout()?;
-        
+
         Ok(result)
     }
 
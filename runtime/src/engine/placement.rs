@@ -0,0 +1,156 @@
+//! NUMA-aware plugin placement: which node a plugin's worker runs on,
+//! and keeping its memory local to that node once it does.
+//!
+//! Builds directly on `sched_setaffinity`/`set_mempolicy`/`mbind` rather
+//! than depending on the `numa_cpp` backend, mirroring the raw
+//! `libc`-based approach `zenith_runtime_cpu::numa_ffi` already uses for
+//! CPU-set affinity.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use zenith_runtime_cpu::NumaTopology;
+
+/// How [`super::RuntimeEngine::load_plugin`] picks the NUMA node a
+/// plugin's VM and execution worker run on.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum PlacementPolicy {
+    /// Place each plugin on the node with the most free memory at load
+    /// time. Good default for a handful of large, long-lived plugins.
+    #[default]
+    MostFreeMemory,
+    /// Cycle through nodes in order, spreading plugins evenly regardless
+    /// of current memory pressure.
+    RoundRobin,
+    /// Always place on the given node, ignoring topology.
+    Pinned(u32),
+}
+
+/// Picks nodes according to a [`PlacementPolicy`], tracking round-robin
+/// state across `load_plugin` calls.
+pub struct Placer {
+    policy: PlacementPolicy,
+    next_node: AtomicU32,
+}
+
+impl Placer {
+    pub fn new(policy: PlacementPolicy) -> Self {
+        Self {
+            policy,
+            next_node: AtomicU32::new(0),
+        }
+    }
+
+    /// Pick the node the next plugin should load onto.
+    pub fn select_node(&self, topology: &NumaTopology) -> u32 {
+        match self.policy {
+            PlacementPolicy::Pinned(node) => node,
+            PlacementPolicy::MostFreeMemory => topology.node_with_most_free_memory().unwrap_or(0),
+            PlacementPolicy::RoundRobin => {
+                let num_nodes = topology.num_nodes().max(1);
+                self.next_node
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| Some((n + 1) % num_nodes))
+                    .unwrap_or(0)
+            }
+        }
+    }
+}
+
+/// Pin the calling thread to `node_id`'s CPUs via `sched_setaffinity`,
+/// and bind its future allocations to that node via
+/// `set_mempolicy(MPOL_BIND)` so a plugin's WASM linear memory - mapped
+/// lazily by wasmtime as the guest touches pages - gets placed on
+/// node-local memory instead of wherever the kernel's default policy
+/// happens to land it.
+///
+/// Best-effort: logs and returns on failure (missing node, permission
+/// denied, non-Linux) rather than propagating an error, since losing
+/// NUMA locality should never fail plugin execution.
+pub fn pin_to_node(topology: &NumaTopology, node_id: u32) {
+    let Some(node) = topology.get_node(node_id) else {
+        tracing::warn!("no such NUMA node {}; skipping NUMA pinning", node_id);
+        return;
+    };
+    if node.cpu_cores.is_empty() {
+        return;
+    }
+
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_ZERO(&mut set);
+        for &cpu in &node.cpu_cores {
+            libc::CPU_SET(cpu as usize, &mut set);
+        }
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            tracing::warn!(
+                "sched_setaffinity to node {} failed: {}",
+                node_id,
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+
+    set_mempolicy_bind(node_id);
+}
+
+/// `set_mempolicy(MPOL_BIND, &mask, maxnode)` for the calling thread.
+/// Governs allocations made *after* this call, which is why
+/// [`pin_to_node`] runs it right before `VM::execute` touches any fresh
+/// WASM linear-memory pages.
+fn set_mempolicy_bind(node_id: u32) {
+    let mask = node_mask(node_id);
+    let rc = unsafe {
+        libc::syscall(
+            libc::SYS_set_mempolicy,
+            MPOL_BIND,
+            &mask as *const u64,
+            NODE_MASK_BITS,
+        )
+    };
+    if rc != 0 {
+        tracing::warn!(
+            "set_mempolicy(MPOL_BIND, node {}) failed: {}",
+            node_id,
+            std::io::Error::last_os_error()
+        );
+    }
+}
+
+/// `mbind(addr, len, MPOL_BIND, &mask, maxnode, 0)`, binding an
+/// already-allocated region to `node_id`. Unlike [`set_mempolicy_bind`],
+/// this also covers memory allocated *before* the call, e.g. a linear
+/// memory buffer that's handed to us rather than something we mmap
+/// ourselves. Exposed for callers that can get at such a raw region
+/// (`VM` doesn't expose its wasmtime-owned linear memory pointer today).
+#[allow(dead_code)]
+pub fn bind_region_to_node(addr: *mut u8, len: usize, node_id: u32) {
+    let mask = node_mask(node_id);
+    let rc = unsafe {
+        libc::syscall(
+            libc::SYS_mbind,
+            addr as *mut libc::c_void,
+            len,
+            MPOL_BIND,
+            &mask as *const u64,
+            NODE_MASK_BITS,
+            0u32,
+        )
+    };
+    if rc != 0 {
+        tracing::warn!(
+            "mbind(node {}) for {}-byte region failed: {}",
+            node_id,
+            len,
+            std::io::Error::last_os_error()
+        );
+    }
+}
+
+/// `MPOL_BIND`: restrict allocations to exactly the nodes in the mask
+/// (see `set_mempolicy(2)`/`mbind(2)`).
+const MPOL_BIND: i32 = 2;
+/// `maxnode` argument for the single-`u64`-word node mask below: one more
+/// than the number of bits, per `set_mempolicy(2)`.
+const NODE_MASK_BITS: u64 = u64::BITS as u64 + 1;
+
+fn node_mask(node_id: u32) -> u64 {
+    1u64 << node_id
+}
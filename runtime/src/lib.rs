@@ -1,11 +1,19 @@
 use zenith_core::{Engine, error::Result};
 use std::sync::Arc;
-use std::path::{PathBuf};
+use std::path::PathBuf;
+use std::collections::HashMap;
 use notify::{Watcher, RecursiveMode, RecommendedWatcher, EventKind};
 use tracing::{info, error, warn};
 use std::fs;
 use std::time::Duration;
 use tokio::sync::broadcast;
+use tokio::time::Instant;
+
+/// How long a burst of filesystem events on the same `.wasm` path must go
+/// quiet before it's treated as settled and reloaded. Coalesces the several
+/// `Modify`/`Create` events a single file copy tends to generate into one
+/// reload, and avoids loading a file mid-write.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(200);
 
 // Runtime submodules
 pub mod sandbox;
@@ -30,13 +38,48 @@ pub struct Runtime {
 }
 
 impl Runtime {
-    /// Create a new Runtime environment
+    /// Create a new Runtime environment with a single, unpinned consumer
+    /// worker - see [`Self::with_worker_pool`] to scale dispatch across
+    /// more threads.
     pub fn new(buffer_size: usize, plugin_dir: impl Into<PathBuf>) -> Result<Self> {
-        let engine = Arc::new(Engine::new(buffer_size)?);
+        Self::with_worker_pool(buffer_size, plugin_dir, zenith_core::engine::WorkerPoolConfig::default())
+    }
+
+    /// Like [`Self::new`], but dispatches events to `worker_pool.worker_count`
+    /// concurrent consumer threads (each optionally pinned to a NUMA node)
+    /// instead of one. See [`zenith_core::engine::WorkerPoolConfig`] and
+    /// [`zenith_core::Engine::with_worker_pool`].
+    pub fn with_worker_pool(
+        buffer_size: usize,
+        plugin_dir: impl Into<PathBuf>,
+        worker_pool: zenith_core::engine::WorkerPoolConfig,
+    ) -> Result<Self> {
+        let engine = Arc::new(Engine::with_worker_pool(buffer_size, worker_pool)?);
         let path = plugin_dir.into();
-        
+
         let (tx, _) = broadcast::channel(1);
-        
+
+        Ok(Self {
+            engine,
+            plugin_dir: path,
+            shutdown_tx: tx,
+        })
+    }
+
+    /// Like [`Self::new`], caching compiled plugins under `cache_dir`
+    /// instead of recompiling them on every load or restart. [`Self::run`]
+    /// precompiles every `.wasm` already in `plugin_dir` into this cache
+    /// before its initial load, so startup becomes deserialize-only.
+    pub fn with_cache_dir(
+        buffer_size: usize,
+        plugin_dir: impl Into<PathBuf>,
+        cache_dir: impl Into<PathBuf>,
+    ) -> Result<Self> {
+        let engine = Arc::new(Engine::with_cache_dir(buffer_size, cache_dir)?);
+        let path = plugin_dir.into();
+
+        let (tx, _) = broadcast::channel(1);
+
         Ok(Self {
             engine,
             plugin_dir: path,
@@ -48,7 +91,18 @@ impl Runtime {
     /// This enables the hot-reload watcher on the plugin directory.
     pub async fn run(&self) -> anyhow::Result<()> {
         info!("Initializing Zenith Runtime...");
-        
+
+        // 0. Warm the AOT cache, if one is configured (see `with_cache_dir`),
+        // so the loads below are deserialize-only. A no-op, logged as such,
+        // when no cache directory was configured; skipped entirely on a
+        // first run where `plugin_dir` doesn't exist yet.
+        if self.plugin_dir.exists() {
+            let precompiled = self.engine.precompile_dir(&self.plugin_dir)?;
+            if precompiled > 0 {
+                info!("Precompiled {} plugin(s) into the AOT cache.", precompiled);
+            }
+        }
+
         // 1. Initial Load of Plugins
         self.load_all_plugins()?;
 
@@ -61,12 +115,29 @@ impl Runtime {
         let engine_ref = self.engine.clone();
         let mut shutdown_rx = self.shutdown_tx.subscribe();
 
-        // Spawn watcher task
+        // Spawn watcher task. `notify`'s callback runs on its own background
+        // thread, so it feeds a `tokio::sync::mpsc` channel directly instead
+        // of the previous sleep/try_recv polling loop - the task below only
+        // wakes on an actual filesystem event or a pending debounce deadline.
         tokio::spawn(async move {
             info!("Starting Hot-Reload Watcher on {:?}", watcher_plugin_dir);
-            
-            let (tx, rx) = std::sync::mpsc::channel();
-            let mut watcher = match RecommendedWatcher::new(tx, notify::Config::default()) {
+
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<PathBuf>();
+            let mut watcher = match RecommendedWatcher::new(
+                move |res: notify::Result<notify::Event>| match res {
+                    Ok(event) => {
+                        if let EventKind::Modify(_) | EventKind::Create(_) = event.kind {
+                            for path in event.paths {
+                                if path.extension().is_some_and(|ext| ext == "wasm") {
+                                    let _ = tx.send(path);
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => warn!("Watch error: {}", e),
+                },
+                notify::Config::default(),
+            ) {
                 Ok(w) => w,
                 Err(e) => {
                     error!("Failed to create file watcher: {}", e);
@@ -79,37 +150,47 @@ impl Runtime {
                 return;
             }
 
+            // Paths with a pending reload, and the instant their debounce
+            // window closes. An event on a path already pending just pushes
+            // its deadline back out, so a burst of writes to the same file
+            // collapses into a single reload once it finally goes quiet.
+            let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+
             loop {
+                let next_deadline = pending.values().copied().min();
+
                 tokio::select! {
                     _ = shutdown_rx.recv() => {
                         info!("Watcher shutting down.");
                         break;
                     }
-                    // We need to poll the std channel. Since this is async context, 
-                    // a blocking recv is not ideal, but for MVP watcher it's acceptable 
-                    // if we wrap it or just use a small timeout loop.
-                    // Better approach for simple MVP: check channel periodically or use blocking task.
-                    // We'll use a simple loop with yield.
-                    _ = tokio::time::sleep(Duration::from_millis(500)) => {
-                        while let Ok(res) = rx.try_recv() {
-                            match res {
-                                Ok(event) => {
-                                    if let EventKind::Modify(_) | EventKind::Create(_) = event.kind {
-                                        for path in event.paths {
-                                            if path.extension().is_some_and(|ext| ext == "wasm") {
-                                                info!("Change detected in {:?}. Reloading...", path);
-                                                if let Ok(bytes) = fs::read(&path) {
-                                                    if let Err(e) = engine_ref.load_plugin(&bytes) {
-                                                        error!("Failed to hot-reload plugin: {}", e);
-                                                    } else {
-                                                        info!("Plugin hot-reloaded successfully.");
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                },
-                                Err(e) => warn!("Watch error: {}", e),
+                    maybe_path = rx.recv() => {
+                        match maybe_path {
+                            Some(path) => {
+                                pending.insert(path, Instant::now() + RELOAD_DEBOUNCE);
+                            }
+                            None => {
+                                info!("Watcher channel closed.");
+                                break;
+                            }
+                        }
+                    }
+                    _ = sleep_until_or_forever(next_deadline) => {
+                        let now = Instant::now();
+                        let ready: Vec<PathBuf> = pending.iter()
+                            .filter(|(_, deadline)| **deadline <= now)
+                            .map(|(path, _)| path.clone())
+                            .collect();
+
+                        for path in ready {
+                            pending.remove(&path);
+                            info!("Change settled for {:?}. Reloading...", path);
+                            if let Ok(bytes) = fs::read(&path) {
+                                let capabilities = Arc::new(load_capabilities_manifest(&path));
+                                let id = path.to_string_lossy().into_owned();
+                                if let Err(e) = engine_ref.reload_plugin_with_capabilities(id, &bytes, capabilities) {
+                                    error!("Failed to hot-reload plugin {:?}: {}", path, e);
+                                }
                             }
                         }
                     }
@@ -140,9 +221,43 @@ impl Runtime {
             if path.is_file() && path.extension().is_some_and(|ext| ext == "wasm") {
                 info!("Loading plugin: {:?}", path);
                 let bytes = fs::read(&path)?;
-                self.engine.load_plugin(&bytes)?;
+                let capabilities = Arc::new(load_capabilities_manifest(&path));
+                let id = path.to_string_lossy().into_owned();
+                self.engine.reload_plugin_with_capabilities(id, &bytes, capabilities)?;
             }
         }
         Ok(())
     }
 }
+
+/// Resolves to `Instant::now()` once `deadline` elapses, or never if
+/// `deadline` is `None` - lets the watcher's `select!` block on "whichever
+/// comes first: a new event, or the earliest pending debounce deadline"
+/// without a fixed polling interval.
+async fn sleep_until_or_forever(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Reads the capability manifest sitting alongside `wasm_path` (same path
+/// with `.caps` appended, e.g. `transform.wasm.caps` for `transform.wasm`),
+/// parsing it with [`host_api::Capabilities::from_manifest`]. A plugin with
+/// no manifest file is granted every capability, preserving the
+/// unrestricted default that predates capability gating.
+fn load_capabilities_manifest(wasm_path: &std::path::Path) -> host_api::Capabilities {
+    let manifest_path = {
+        let mut p = wasm_path.as_os_str().to_owned();
+        p.push(".caps");
+        PathBuf::from(p)
+    };
+
+    match fs::read_to_string(&manifest_path) {
+        Ok(text) => {
+            info!("Loading capability manifest: {:?}", manifest_path);
+            host_api::Capabilities::from_manifest(&text)
+        }
+        Err(_) => host_api::Capabilities::all(),
+    }
+}
@@ -2,6 +2,7 @@
 /// Manages concurrent plugin execution and resource allocation
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::Semaphore;
 
 /// Task priority levels
@@ -18,6 +19,7 @@ pub struct Task {
     pub id: u64,
     pub priority: Priority,
     pub payload: Vec<u8>,
+    pub enqueued_at: Instant,
 }
 
 /// Simple priority-based task scheduler
@@ -25,10 +27,27 @@ pub struct Scheduler {
     queues: Arc<Mutex<[VecDeque<Task>; 4]>>, // One queue per priority level
     concurrency_limit: Arc<Semaphore>,
     next_task_id: std::sync::atomic::AtomicU64,
+    // `Some` enables aging: a task's effective priority rises by one
+    // level for every interval it has waited. `None` keeps the
+    // original strict-priority behavior.
+    aging_interval: Option<Duration>,
 }
 
 impl Scheduler {
     pub fn new(max_concurrent: usize) -> Self {
+        Self::with_aging_interval(max_concurrent, None)
+    }
+
+    /// Like `new`, but a task's effective priority rises by one level
+    /// for every `aging_interval` it spends waiting (e.g. a `Low` task
+    /// waiting 2x the interval competes as `High`), saturating at
+    /// `Critical`. This keeps a steady stream of high-priority work
+    /// from starving lower-priority tasks indefinitely.
+    pub fn with_aging(max_concurrent: usize, aging_interval: Duration) -> Self {
+        Self::with_aging_interval(max_concurrent, Some(aging_interval))
+    }
+
+    fn with_aging_interval(max_concurrent: usize, aging_interval: Option<Duration>) -> Self {
         Self {
             queues: Arc::new(Mutex::new([
                 VecDeque::new(), // Low
@@ -38,39 +57,58 @@ impl Scheduler {
             ])),
             concurrency_limit: Arc::new(Semaphore::new(max_concurrent)),
             next_task_id: std::sync::atomic::AtomicU64::new(0),
+            aging_interval,
         }
     }
 
     /// Submit a task for execution
     pub fn submit(&self, priority: Priority, payload: Vec<u8>) -> u64 {
         let task_id = self.next_task_id.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        
+
         let task = Task {
             id: task_id,
             priority,
             payload,
+            enqueued_at: Instant::now(),
         };
 
         let mut queues = self.queues.lock().unwrap();
         queues[priority as usize].push_back(task);
-        
+
         task_id
     }
 
-    /// Get next task (highest priority first)
+    /// Effective priority level (0..=3) for a task waiting `elapsed`,
+    /// aging up by one level per `aging_interval` and saturating at
+    /// `Critical`.
+    fn effective_priority(base: usize, elapsed: Duration, aging_interval: Duration) -> usize {
+        let aged_levels = (elapsed.as_secs_f64() / aging_interval.as_secs_f64()) as usize;
+        (base + aged_levels).min(Priority::Critical as usize)
+    }
+
+    /// Get next task. Without aging, highest priority first. With
+    /// aging, the task with the highest effective priority (ties
+    /// broken by oldest `enqueued_at`).
     pub async fn next_task(&self) -> Option<Task> {
         let _permit = self.concurrency_limit.acquire().await.ok()?;
-        
+
         let mut queues = self.queues.lock().unwrap();
-        
-        // Check queues from highest to lowest priority
-        for i in (0..4).rev() {
-            if let Some(task) = queues[i].pop_front() {
-                return Some(task);
+
+        let queue_index = match self.aging_interval {
+            Some(aging_interval) => {
+                let now = Instant::now();
+                (0..4).filter(|&i| !queues[i].is_empty()).max_by(|&a, &b| {
+                    let effective_a = Self::effective_priority(a, now - queues[a][0].enqueued_at, aging_interval);
+                    let effective_b = Self::effective_priority(b, now - queues[b][0].enqueued_at, aging_interval);
+                    effective_a
+                        .cmp(&effective_b)
+                        .then_with(|| queues[b][0].enqueued_at.cmp(&queues[a][0].enqueued_at))
+                })
             }
-        }
-        
-        None
+            None => (0..4).rev().find(|&i| !queues[i].is_empty()),
+        };
+
+        queue_index.and_then(|i| queues[i].pop_front())
     }
 
     /// Get pending task count
@@ -91,7 +129,32 @@ mod tests {
         scheduler.submit(Priority::Low, vec![1]);
         scheduler.submit(Priority::Critical, vec![2]);
         scheduler.submit(Priority::Normal, vec![3]);
-        
+
         assert_eq!(scheduler.pending_count(), 3);
     }
+
+    #[tokio::test]
+    async fn test_aging_lets_a_stale_low_task_dequeue_ahead_of_high() {
+        let scheduler = Scheduler::with_aging(10, Duration::from_millis(20));
+
+        scheduler.submit(Priority::Low, vec![1]);
+        // 3 intervals: Low -> Normal -> High -> Critical
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        scheduler.submit(Priority::High, vec![2]);
+
+        let next = scheduler.next_task().await.unwrap();
+        assert_eq!(next.payload, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_without_aging_high_still_starves_low() {
+        let scheduler = Scheduler::new(10);
+
+        scheduler.submit(Priority::Low, vec![1]);
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        scheduler.submit(Priority::High, vec![2]);
+
+        let next = scheduler.next_task().await.unwrap();
+        assert_eq!(next.payload, vec![2]);
+    }
 }
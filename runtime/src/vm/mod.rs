@@ -1,18 +1,116 @@
 /// Virtual Machine abstraction for WASM execution
 /// Wraps Wasmtime with additional runtime features
-use wasmtime::{Engine as WasmEngine, Store, Module, Linker};
+use wasmtime::{Engine as WasmEngine, Instance, Linker, Module, Store, TypedFunc, Val, ValType};
+use wasmtime::{WasmParams, WasmResults};
 use wasmtime_wasi::WasiCtxBuilder;
 use wasmtime_wasi::p1::{self, WasiP1Ctx};
-use anyhow::Result;
-use std::sync::Arc;
+use anyhow::{bail, Result};
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 /// Type alias for WASI state in wasmtime v39+
 type WasiState = WasiP1Ctx;
 
-/// WASM Virtual Machine
-pub struct VM {
+/// A pre-instantiated `Store`/`Instance` pair, plus a cache of typed
+/// function handles already looked up on this instance.
+///
+/// `TypedFunc` handles are tied to the `Store` they were resolved against,
+/// so the cache lives here rather than on the pool or `VM`.
+struct PooledInstance {
+    store: Store<WasiState>,
+    instance: Instance,
+    typed_cache: HashMap<String, Box<dyn Any + Send>>,
+}
+
+/// Pre-instantiates a WASM module once (and pre-builds a `Linker` with WASI
+/// added) and hands out pooled `Store`+`Instance` pairs, so repeated
+/// `VM::execute` calls reuse state instead of paying re-instantiation cost
+/// on every call.
+///
+/// Checked-in instances keep whatever state the last call left them in
+/// (memory, globals, WASI fds); nothing is reset beyond the pool's own
+/// bookkeeping. A module that needs a clean slate between calls should
+/// export its own reset function.
+struct InstancePool {
     engine: Arc<WasmEngine>,
     module: Module,
+    linker: Linker<WasiState>,
+    idle: Mutex<Vec<PooledInstance>>,
+}
+
+impl InstancePool {
+    fn new(engine: Arc<WasmEngine>, module: Module) -> Result<Self> {
+        let mut linker = Linker::new(&engine);
+        // wasmtime v39+ uses p1 module for WASIp1 compatibility
+        p1::add_to_linker_sync(&mut linker, |s: &mut WasiState| s)?;
+
+        let pool = Self {
+            engine,
+            module,
+            linker,
+            idle: Mutex::new(Vec::new()),
+        };
+
+        // Pre-instantiate once up front so the first `execute` call doesn't
+        // pay instantiation cost either.
+        let warm = pool.instantiate()?;
+        pool.idle.lock().unwrap().push(warm);
+
+        Ok(pool)
+    }
+
+    fn instantiate(&self) -> Result<PooledInstance> {
+        let wasi_ctx = WasiCtxBuilder::new().inherit_stdio().build_p1();
+        let mut store = Store::new(&self.engine, wasi_ctx);
+        let instance = self.linker.instantiate(&mut store, &self.module)?;
+        Ok(PooledInstance {
+            store,
+            instance,
+            typed_cache: HashMap::new(),
+        })
+    }
+
+    /// Take an idle instance from the pool, instantiating a new one if none
+    /// are free.
+    fn checkout(&self) -> Result<PooledInstance> {
+        if let Some(pooled) = self.idle.lock().unwrap().pop() {
+            return Ok(pooled);
+        }
+        self.instantiate()
+    }
+
+    /// Return an instance to the pool for reuse by a later call.
+    fn checkin(&self, pooled: PooledInstance) {
+        self.idle.lock().unwrap().push(pooled);
+    }
+}
+
+/// A cached handle to a typed export, produced by `VM::get_typed`.
+///
+/// The signature is validated once, up front, when the handle is created;
+/// each `call` then resolves (and caches) the `TypedFunc` on whichever
+/// pooled instance services that call.
+pub struct TypedCall<'vm, Params, Results> {
+    vm: &'vm VM,
+    name: String,
+    _marker: std::marker::PhantomData<(Params, Results)>,
+}
+
+impl<'vm, Params, Results> TypedCall<'vm, Params, Results>
+where
+    Params: WasmParams + 'static,
+    Results: WasmResults + 'static,
+{
+    pub fn call(&self, params: Params) -> Result<Results> {
+        self.vm.call_typed(&self.name, params)
+    }
+}
+
+/// WASM Virtual Machine
+pub struct VM {
+    pool: InstancePool,
 }
 
 impl VM {
@@ -20,50 +118,207 @@ impl VM {
     pub fn from_bytes(wasm: &[u8]) -> Result<Self> {
         let engine = Arc::new(WasmEngine::default());
         let module = Module::new(&engine, wasm)?;
-        
-        Ok(Self { engine, module })
+        let pool = InstancePool::new(engine, module)?;
+
+        Ok(Self { pool })
+    }
+
+    /// Like [`Self::from_bytes`], reusing a previously compiled and
+    /// serialized module under `cache_dir` for these exact bytes instead of
+    /// recompiling - content-addressed the same way as
+    /// `core::wasm_host::WasmHost`'s AOT cache. [`Self::precompile`] warms
+    /// this cache ahead of time so the reuse here is deserialize-only.
+    pub fn from_bytes_with_cache(wasm: &[u8], cache_dir: impl AsRef<Path>) -> Result<Self> {
+        let engine = Arc::new(WasmEngine::default());
+        let module = Self::compile_cached(&engine, wasm, cache_dir.as_ref())?;
+        let pool = InstancePool::new(engine, module)?;
+
+        Ok(Self { pool })
     }
 
-    /// Execute the WASM module's exported function
+    /// Compiles `wasm` and persists the serialized artifact under
+    /// `cache_dir`, without instantiating a VM. Used to batch-warm the
+    /// cache (see `RuntimeEngine::precompile_dir`) so a later
+    /// [`Self::from_bytes_with_cache`] call for the same bytes is
+    /// deserialize-only.
+    pub fn precompile(wasm: &[u8], cache_dir: impl AsRef<Path>) -> Result<()> {
+        let engine = WasmEngine::default();
+        Self::compile_cached(&engine, wasm, cache_dir.as_ref())?;
+        Ok(())
+    }
+
+    fn cache_path(cache_dir: &Path, wasm: &[u8]) -> PathBuf {
+        let content_hash = blake3::hash(wasm);
+        cache_dir.join(format!("{content_hash}.cwasm"))
+    }
+
+    /// Loads `wasm` from `cache_dir`'s cached artifact if one exists for
+    /// these bytes, compiling (and caching) it otherwise.
+    fn compile_cached(engine: &WasmEngine, wasm: &[u8], cache_dir: &Path) -> Result<Module> {
+        std::fs::create_dir_all(cache_dir)?;
+        let path = Self::cache_path(cache_dir, wasm);
+
+        if path.exists() {
+            // SAFETY: `Module::deserialize_file` revalidates the
+            // wasmtime-version header and target triple embedded in the
+            // artifact, so an entry left stale by an engine upgrade or
+            // config change is rejected rather than loaded; we simply fall
+            // through to recompiling it below.
+            if let Ok(module) = unsafe { Module::deserialize_file(engine, &path) } {
+                return Ok(module);
+            }
+        }
+
+        let module = Module::new(engine, wasm)?;
+        if let Err(e) = std::fs::write(&path, module.serialize()?) {
+            tracing::warn!("failed to persist AOT VM cache at {path:?}: {e}");
+        }
+        Ok(module)
+    }
+
+    /// Execute the WASM module's exported function, taking and returning
+    /// `i64` values.
     pub fn execute(&self, function_name: &str, args: &[i64]) -> Result<Vec<i64>> {
-        let mut linker = Linker::new(&self.engine);
-        
-        // wasmtime v39+ uses p1 module for WASIp1 compatibility
-        p1::add_to_linker_sync(&mut linker, |s: &mut WasiState| s)?;
-        
-        let wasi_ctx = WasiCtxBuilder::new()
-            .inherit_stdio()
-            .build_p1();
-        
-        let mut store = Store::new(&self.engine, wasi_ctx);
-        let instance = linker.instantiate(&mut store, &self.module)?;
-        
-        // Try to get the function
-        let func = instance.get_func(&mut store, function_name)
-            .ok_or_else(|| anyhow::anyhow!("Function {} not found", function_name))?;
-        
-        // For simplicity, assume function signature matches
-        // In production, we'd validate this
-        let mut results = vec![wasmtime::Val::I64(0)];
-        
-        let params: Vec<wasmtime::Val> = args.iter()
-            .map(|&v| wasmtime::Val::I64(v))
-            .collect();
-        
-        func.call(&mut store, &params, &mut results)?;
-        
-        Ok(results.iter().map(|v| {
-            if let wasmtime::Val::I64(i) = v {
-                *i
-            } else {
-                0
+        let mut pooled = self.pool.checkout()?;
+        let outcome = Self::execute_i64(&mut pooled, function_name, args);
+        self.pool.checkin(pooled);
+        outcome
+    }
+
+    fn execute_i64(pooled: &mut PooledInstance, function_name: &str, args: &[i64]) -> Result<Vec<i64>> {
+        let func = pooled
+            .instance
+            .get_func(&mut pooled.store, function_name)
+            .ok_or_else(|| anyhow::anyhow!("function '{}' not found", function_name))?;
+
+        let ty = func.ty(&pooled.store);
+        let params: Vec<ValType> = ty.params().collect();
+        let results: Vec<ValType> = ty.results().collect();
+
+        if params.len() != args.len() || params.iter().any(|p| *p != ValType::I64) {
+            bail!(
+                "function '{}' expects {} i64 param(s), but export declares params {:?}",
+                function_name,
+                args.len(),
+                params
+            );
+        }
+        if results.len() != 1 || results[0] != ValType::I64 {
+            bail!(
+                "function '{}' expects a single i64 result, but export declares results {:?}",
+                function_name,
+                results
+            );
+        }
+
+        let call_params: Vec<Val> = args.iter().map(|&v| Val::I64(v)).collect();
+        let mut call_results = vec![Val::I64(0)];
+        func.call(&mut pooled.store, &call_params, &mut call_results)?;
+
+        Ok(call_results
+            .iter()
+            .map(|v| match v {
+                Val::I64(i) => *i,
+                _ => unreachable!("result type already validated as i64"),
+            })
+            .collect())
+    }
+
+    /// Execute a function with dynamically-typed arguments/results, for
+    /// signatures that don't fit the `i64`-only `execute` or a statically
+    /// known `get_typed` signature.
+    pub fn execute_vals(&self, function_name: &str, args: &[Val]) -> Result<Vec<Val>> {
+        let mut pooled = self.pool.checkout()?;
+        let outcome = (|| {
+            let func = pooled
+                .instance
+                .get_func(&mut pooled.store, function_name)
+                .ok_or_else(|| anyhow::anyhow!("function '{}' not found", function_name))?;
+
+            let ty = func.ty(&pooled.store);
+            let params: Vec<ValType> = ty.params().collect();
+            if params.len() != args.len() {
+                bail!(
+                    "function '{}' expects {} param(s), but {} were given",
+                    function_name,
+                    params.len(),
+                    args.len()
+                );
             }
-        }).collect())
+
+            let mut results: Vec<Val> = ty.results().map(Self::default_val).collect();
+            func.call(&mut pooled.store, args, &mut results)?;
+            Ok(results)
+        })();
+        self.pool.checkin(pooled);
+        outcome
+    }
+
+    fn default_val(ty: ValType) -> Val {
+        match ty {
+            ValType::I32 => Val::I32(0),
+            ValType::I64 => Val::I64(0),
+            ValType::F32 => Val::F32(0),
+            ValType::F64 => Val::F64(0),
+            other => panic!("unsupported result type {:?}", other),
+        }
+    }
+
+    /// Look up a typed export, validating its signature against
+    /// `Params`/`Results` up front rather than assuming it matches.
+    pub fn get_typed<Params, Results>(&self, name: &str) -> Result<TypedCall<'_, Params, Results>>
+    where
+        Params: WasmParams + 'static,
+        Results: WasmResults + 'static,
+    {
+        let mut pooled = self.pool.checkout()?;
+        let validated = pooled
+            .instance
+            .get_typed_func::<Params, Results>(&mut pooled.store, name)
+            .map(|_| ())
+            .map_err(|e| anyhow::anyhow!("function '{}' does not match the requested signature: {}", name, e));
+        self.pool.checkin(pooled);
+        validated?;
+
+        Ok(TypedCall {
+            vm: self,
+            name: name.to_string(),
+            _marker: std::marker::PhantomData,
+        })
+    }
+
+    fn call_typed<Params, Results>(&self, name: &str, params: Params) -> Result<Results>
+    where
+        Params: WasmParams + 'static,
+        Results: WasmResults + 'static,
+    {
+        let mut pooled = self.pool.checkout()?;
+        let outcome = (|| {
+            let typed = match pooled
+                .typed_cache
+                .get(name)
+                .and_then(|cached| cached.downcast_ref::<TypedFunc<Params, Results>>())
+            {
+                Some(func) => *func,
+                None => {
+                    let func = pooled
+                        .instance
+                        .get_typed_func::<Params, Results>(&mut pooled.store, name)?;
+                    pooled.typed_cache.insert(name.to_string(), Box::new(func));
+                    func
+                }
+            };
+            typed.call(&mut pooled.store, params)
+        })();
+        self.pool.checkin(pooled);
+        outcome
     }
 
     /// Get module metadata
     pub fn get_exports(&self) -> Vec<String> {
-        self.module.exports()
+        self.pool
+            .module
+            .exports()
             .map(|e| e.name().to_string())
             .collect()
     }
@@ -83,9 +338,64 @@ mod tests {
                 )
             )
         "#).unwrap();
-        
+
         let vm = VM::from_bytes(&wasm).unwrap();
         let exports = vm.get_exports();
         assert!(exports.contains(&"test".to_string()));
     }
+
+    #[test]
+    fn test_execute_reuses_pooled_instance() {
+        let wasm = wat::parse_str(r#"
+            (module
+                (func (export "add_one") (param i64) (result i64)
+                    local.get 0
+                    i64.const 1
+                    i64.add
+                )
+            )
+        "#).unwrap();
+
+        let vm = VM::from_bytes(&wasm).unwrap();
+        for i in 0..5 {
+            let result = vm.execute("add_one", &[i]).unwrap();
+            assert_eq!(result, vec![i + 1]);
+        }
+    }
+
+    #[test]
+    fn test_execute_rejects_signature_mismatch() {
+        let wasm = wat::parse_str(r#"
+            (module
+                (func (export "add_one_f32") (param f32) (result f32)
+                    local.get 0
+                    f32.const 1
+                    f32.add
+                )
+            )
+        "#).unwrap();
+
+        let vm = VM::from_bytes(&wasm).unwrap();
+        assert!(vm.execute("add_one_f32", &[1]).is_err());
+    }
+
+    #[test]
+    fn test_get_typed_call() {
+        let wasm = wat::parse_str(r#"
+            (module
+                (func (export "mul") (param i32 i32) (result i32)
+                    local.get 0
+                    local.get 1
+                    i32.mul
+                )
+            )
+        "#).unwrap();
+
+        let vm = VM::from_bytes(&wasm).unwrap();
+        let mul = vm.get_typed::<(i32, i32), i32>("mul").unwrap();
+        assert_eq!(mul.call((6, 7)).unwrap(), 42);
+        assert_eq!(mul.call((3, 3)).unwrap(), 9);
+
+        assert!(vm.get_typed::<(i64, i64), i64>("mul").is_err());
+    }
 }
@@ -1,24 +1,71 @@
 //! Lock-free Ring Buffer for high-performance data streaming
 //!
 //! This module implements a SPSC (Single Producer Single Consumer)
-//! ring buffer optimized for low-latency data transfer.
+//! ring buffer optimized for low-latency data transfer, plus an MPMC
+//! variant for callers that need multiple concurrent producers/consumers.
 
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::cell::UnsafeCell;
 
-/// A lock-free ring buffer for streaming data
-pub struct RingBuffer {
-    buffer: Vec<UnsafeCell<Option<Vec<u8>>>>,
+/// Cache line size on most x86_64/ARM64 hardware
+const CACHE_LINE_SIZE: usize = 64;
+
+/// An `AtomicUsize` padded out to a full cache line, so that placing one
+/// next to another (e.g. a ring buffer's producer and consumer cursors)
+/// doesn't put both on the same line and force every push/pop to bounce
+/// it between CPU cores (false sharing).
+#[repr(align(64))]
+struct PaddedAtomicUsize {
+    value: AtomicUsize,
+    _padding: [u8; CACHE_LINE_SIZE - std::mem::size_of::<AtomicUsize>()],
+}
+
+impl PaddedAtomicUsize {
+    fn new(value: usize) -> Self {
+        Self {
+            value: AtomicUsize::new(value),
+            _padding: [0; CACHE_LINE_SIZE - std::mem::size_of::<AtomicUsize>()],
+        }
+    }
+
+    fn load(&self, order: Ordering) -> usize {
+        self.value.load(order)
+    }
+
+    fn store(&self, value: usize, order: Ordering) {
+        self.value.store(value, order)
+    }
+
+    fn compare_exchange_weak(
+        &self,
+        current: usize,
+        new: usize,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<usize, usize> {
+        self.value.compare_exchange_weak(current, new, success, failure)
+    }
+}
+
+/// A lock-free ring buffer for streaming data.
+///
+/// Generic over the item type `T` (defaulting to raw byte buffers); the
+/// `Engine` instantiates it over published Arrow batches ([`PublishedBatch`]),
+/// while tests and other callers that just need byte streaming use the
+/// `Vec<u8>` default.
+pub struct RingBuffer<T = Vec<u8>> {
+    buffer: Vec<UnsafeCell<Option<T>>>,
     capacity: usize,
-    head: AtomicUsize,  // Writer position
-    tail: AtomicUsize,  // Reader position
+    head: PaddedAtomicUsize,  // Writer position
+    tail: PaddedAtomicUsize,  // Reader position
 }
 
-// Safety: RingBuffer is designed for SPSC use
-unsafe impl Send for RingBuffer {}
-unsafe impl Sync for RingBuffer {}
+// Safety: RingBuffer is designed for SPSC use; a slot's contents are only
+// ever touched by the single producer/consumer that currently owns it
+unsafe impl<T: Send> Send for RingBuffer<T> {}
+unsafe impl<T: Send> Sync for RingBuffer<T> {}
 
-impl RingBuffer {
+impl<T> RingBuffer<T> {
     /// Create a new ring buffer with the specified capacity
     pub fn new(capacity: usize) -> Self {
         let capacity = capacity.next_power_of_two();
@@ -26,12 +73,12 @@ impl RingBuffer {
         for _ in 0..capacity {
             buffer.push(UnsafeCell::new(None));
         }
-        
+
         Self {
             buffer,
             capacity,
-            head: AtomicUsize::new(0),
-            tail: AtomicUsize::new(0),
+            head: PaddedAtomicUsize::new(0),
+            tail: PaddedAtomicUsize::new(0),
         }
     }
     
@@ -60,7 +107,7 @@ impl RingBuffer {
     /// Try to push data into the buffer
     ///
     /// Returns `Ok(())` if successful, `Err(data)` if buffer is full
-    pub fn try_push(&self, data: Vec<u8>) -> Result<(), Vec<u8>> {
+    pub fn try_push(&self, data: T) -> Result<(), T> {
         let head = self.head.load(Ordering::Relaxed);
         let tail = self.tail.load(Ordering::Acquire);
         
@@ -82,7 +129,7 @@ impl RingBuffer {
     /// Try to pop data from the buffer
     ///
     /// Returns `Some(data)` if available, `None` if buffer is empty
-    pub fn try_pop(&self) -> Option<Vec<u8>> {
+    pub fn try_pop(&self) -> Option<T> {
         let tail = self.tail.load(Ordering::Relaxed);
         let head = self.head.load(Ordering::Acquire);
         
@@ -100,6 +147,194 @@ impl RingBuffer {
         self.tail.store(tail.wrapping_add(1), Ordering::Release);
         data
     }
+
+    /// Push a batch of buffers, publishing the new head once for the
+    /// whole batch rather than once per item, amortizing the Release
+    /// store across all of them. Stops and returns the number actually
+    /// pushed if the buffer fills partway through.
+    pub fn try_push_batch(&self, items: &[T]) -> usize
+    where
+        T: Clone,
+    {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        let free = self.capacity - head.wrapping_sub(tail);
+        let to_push = items.len().min(free);
+
+        for (i, item) in items.iter().take(to_push).enumerate() {
+            let index = head.wrapping_add(i) & (self.capacity - 1);
+            // Safety: these slots are all beyond `tail`, ahead of any
+            // concurrent consumer, and not yet visible to it
+            unsafe {
+                *self.buffer[index].get() = Some(item.clone());
+            }
+        }
+
+        if to_push > 0 {
+            self.head.store(head.wrapping_add(to_push), Ordering::Release);
+        }
+
+        to_push
+    }
+
+    /// Pop up to `max_count` buffers, publishing the new tail once for
+    /// the whole batch rather than once per item.
+    pub fn try_pop_batch(&self, max_count: usize) -> Vec<T> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        let available = head.wrapping_sub(tail);
+        let to_pop = max_count.min(available);
+
+        let mut items = Vec::with_capacity(to_pop);
+        for i in 0..to_pop {
+            let index = tail.wrapping_add(i) & (self.capacity - 1);
+            // Safety: these slots are all behind `head`, already written
+            // by the producer
+            if let Some(item) = unsafe { (*self.buffer[index].get()).take() } {
+                items.push(item);
+            }
+        }
+
+        if to_pop > 0 {
+            self.tail.store(tail.wrapping_add(to_pop), Ordering::Release);
+        }
+
+        items
+    }
+}
+
+/// One slot of an [`MpmcRingBuffer`]: its data plus a sequence number
+/// used to coordinate claims without a global lock (Dmitry Vyukov's
+/// bounded MPMC queue algorithm)
+struct MpmcCell {
+    sequence: AtomicUsize,
+    data: UnsafeCell<Option<Vec<u8>>>,
+}
+
+/// Multiple-producer multiple-consumer ring buffer for byte buffers.
+///
+/// Unlike [`RingBuffer`], any number of threads may call [`Self::try_push`]
+/// or [`Self::try_pop`] concurrently: each slot carries its own sequence
+/// number, so a producer (or consumer) claims a slot with a single CAS on
+/// the shared cursor rather than taking a lock.
+pub struct MpmcRingBuffer {
+    buffer: Box<[MpmcCell]>,
+    mask: usize,
+    enqueue_pos: PaddedAtomicUsize,
+    dequeue_pos: PaddedAtomicUsize,
+}
+
+// Safety: every slot is claimed via CAS on enqueue_pos/dequeue_pos before
+// being written or read, so concurrent producers/consumers never alias
+unsafe impl Send for MpmcRingBuffer {}
+unsafe impl Sync for MpmcRingBuffer {}
+
+impl MpmcRingBuffer {
+    /// Create a new MPMC ring buffer with the specified capacity
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.next_power_of_two();
+        let buffer: Vec<MpmcCell> = (0..capacity)
+            .map(|i| MpmcCell {
+                sequence: AtomicUsize::new(i),
+                data: UnsafeCell::new(None),
+            })
+            .collect();
+
+        Self {
+            buffer: buffer.into_boxed_slice(),
+            mask: capacity - 1,
+            enqueue_pos: PaddedAtomicUsize::new(0),
+            dequeue_pos: PaddedAtomicUsize::new(0),
+        }
+    }
+
+    /// Get the buffer capacity
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Get the (approximate, under concurrent access) number of items in
+    /// the buffer
+    pub fn len(&self) -> usize {
+        self.enqueue_pos.load(Ordering::Relaxed)
+            .wrapping_sub(self.dequeue_pos.load(Ordering::Relaxed))
+    }
+
+    /// Check if buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check if buffer is full
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.capacity()
+    }
+
+    /// Try to push data into the buffer from any number of concurrent
+    /// producers. Returns `Err(data)` if the buffer is full.
+    pub fn try_push(&self, data: Vec<u8>) -> Result<(), Vec<u8>> {
+        let mut pos = self.enqueue_pos.load(Ordering::Relaxed);
+        loop {
+            let cell = &self.buffer[pos & self.mask];
+            let seq = cell.sequence.load(Ordering::Acquire);
+            let diff = seq as isize - pos as isize;
+
+            if diff == 0 {
+                if self.enqueue_pos
+                    .compare_exchange_weak(pos, pos.wrapping_add(1), Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    // Safety: the sequence check plus a winning CAS means
+                    // we alone hold this slot until we publish below
+                    unsafe {
+                        *cell.data.get() = Some(data);
+                    }
+                    cell.sequence.store(pos.wrapping_add(1), Ordering::Release);
+                    return Ok(());
+                }
+                pos = self.enqueue_pos.load(Ordering::Relaxed);
+            } else if diff < 0 {
+                return Err(data);
+            } else {
+                pos = self.enqueue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Try to pop data from the buffer from any number of concurrent
+    /// consumers. Returns `None` if the buffer is empty.
+    pub fn try_pop(&self) -> Option<Vec<u8>> {
+        let mut pos = self.dequeue_pos.load(Ordering::Relaxed);
+        loop {
+            let cell = &self.buffer[pos & self.mask];
+            let seq = cell.sequence.load(Ordering::Acquire);
+            let diff = seq as isize - pos.wrapping_add(1) as isize;
+
+            if diff == 0 {
+                if self.dequeue_pos
+                    .compare_exchange_weak(pos, pos.wrapping_add(1), Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    // Safety: the sequence check plus a winning CAS means
+                    // we alone hold this slot until we publish below
+                    let data = unsafe { (*cell.data.get()).take() };
+                    cell.sequence.store(pos.wrapping_add(self.mask + 1), Ordering::Release);
+                    return data;
+                }
+                pos = self.dequeue_pos.load(Ordering::Relaxed);
+            } else if diff < 0 {
+                return None;
+            } else {
+                pos = self.dequeue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl Drop for MpmcRingBuffer {
+    fn drop(&mut self) {
+        while self.try_pop().is_some() {}
+    }
 }
 
 #[cfg(test)]
@@ -134,4 +369,99 @@ mod tests {
         let result = buffer.try_push(vec![3]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_push_pop_batch() {
+        let buffer = RingBuffer::new(8);
+        let items = vec![vec![1], vec![2], vec![3]];
+
+        let pushed = buffer.try_push_batch(&items);
+        assert_eq!(pushed, 3);
+        assert_eq!(buffer.len(), 3);
+
+        let popped = buffer.try_pop_batch(2);
+        assert_eq!(popped, vec![vec![1], vec![2]]);
+        assert_eq!(buffer.len(), 1);
+    }
+
+    #[test]
+    fn test_push_batch_stops_when_full() {
+        let buffer = RingBuffer::new(2);
+        let items = vec![vec![1], vec![2], vec![3]];
+
+        let pushed = buffer.try_push_batch(&items);
+        assert_eq!(pushed, 2);
+        assert!(buffer.is_full());
+    }
+
+    #[test]
+    fn test_pop_batch_stops_when_empty() {
+        let buffer = RingBuffer::new(4);
+        buffer.try_push(vec![1]).unwrap();
+
+        let popped = buffer.try_pop_batch(5);
+        assert_eq!(popped, vec![vec![1]]);
+        assert!(buffer.try_pop_batch(5).is_empty());
+    }
+
+    #[test]
+    fn test_mpmc_basic_operations() {
+        let buffer = MpmcRingBuffer::new(4);
+
+        assert!(buffer.is_empty());
+        buffer.try_push(vec![1, 2, 3]).unwrap();
+        assert_eq!(buffer.len(), 1);
+
+        let data = buffer.try_pop().unwrap();
+        assert_eq!(data, vec![1, 2, 3]);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_mpmc_full_buffer() {
+        let buffer = MpmcRingBuffer::new(2);
+
+        buffer.try_push(vec![1]).unwrap();
+        buffer.try_push(vec![2]).unwrap();
+        assert!(buffer.is_full());
+
+        let result = buffer.try_push(vec![3]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_mpmc_concurrent() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let buffer = Arc::new(MpmcRingBuffer::new(1024));
+        let mut producers = Vec::new();
+
+        for t in 0..4 {
+            let buffer = Arc::clone(&buffer);
+            producers.push(thread::spawn(move || {
+                for i in 0..100 {
+                    let mut item = vec![t as u8];
+                    item.push(i as u8);
+                    while buffer.try_push(item.clone()).is_err() {
+                        thread::yield_now();
+                    }
+                }
+            }));
+        }
+
+        let mut consumed = 0;
+        while consumed < 400 {
+            if buffer.try_pop().is_some() {
+                consumed += 1;
+            } else {
+                thread::yield_now();
+            }
+        }
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        assert!(buffer.is_empty());
+    }
 }
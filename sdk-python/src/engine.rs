@@ -7,17 +7,32 @@
 
 use pyo3::prelude::*;
 use pyo3::exceptions::{PyRuntimeError, PyValueError, PyIOError};
+use pyo3::types::PyCapsule;
 use std::sync::{Arc, Mutex};
 use std::path::Path;
 use std::fs;
 
+use arrow::array::StructArray;
+use arrow::ffi::{FFI_ArrowArray, FFI_ArrowSchema};
+use arrow::ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+use arrow::record_batch::RecordBatch;
+
 use crate::buffer::RingBuffer;
 use crate::plugin::PluginManager;
 use crate::PyPluginInfo;
 
+/// A batch handed to [`PyEngine::publish`], tagged with the source that
+/// produced it and its position in that source's stream. This is the item
+/// type of `EngineCore`'s ring buffer.
+pub struct PublishedBatch {
+    pub source_id: u32,
+    pub seq_no: u64,
+    pub batch: RecordBatch,
+}
+
 /// Internal engine state
 pub struct EngineCore {
-    buffer: RingBuffer,
+    buffer: RingBuffer<PublishedBatch>,
     plugin_manager: PluginManager,
     is_running: bool,
 }
@@ -26,18 +41,108 @@ impl EngineCore {
     pub fn new(buffer_size: usize) -> Result<Self, String> {
         Ok(Self {
             buffer: RingBuffer::new(buffer_size),
-            plugin_manager: PluginManager::new(),
+            plugin_manager: PluginManager::new(host_api::kv::backend::KvBackendKind::Memory),
             is_running: true,
         })
     }
-    
+
     pub fn stop(&mut self) {
         self.is_running = false;
     }
-    
+
     pub fn is_running(&self) -> bool {
         self.is_running
     }
+
+    /// Push a batch into the ring buffer, handing it back if the buffer is
+    /// full rather than blocking for a consumer to drain it.
+    pub fn try_publish(&self, item: PublishedBatch) -> Result<(), PublishedBatch> {
+        self.buffer.try_push(item)
+    }
+}
+
+/// Check that `capsule`'s name matches `expected`, so we never cast a
+/// lookalike object's pointer to an Arrow FFI struct it doesn't actually
+/// hold. `PyCapsule::pointer()` performs no such check itself.
+fn expect_capsule_named(capsule: &Bound<'_, PyCapsule>, expected: &str) -> PyResult<()> {
+    let name = capsule.name()?;
+    let matches = name.map(|n| n.to_bytes() == expected.as_bytes()).unwrap_or(false);
+    if !matches {
+        return Err(PyValueError::new_err(format!(
+            "expected a PyCapsule named \"{expected}\", got {name:?}"
+        )));
+    }
+    Ok(())
+}
+
+/// Import a zero-copy `RecordBatch` out of an `arrow_schema`/`arrow_array`
+/// PyCapsule pair produced by a PyArrow object's `__arrow_c_array__`.
+///
+/// # Safety
+/// Per the Arrow PyCapsule Interface, each capsule owns a valid
+/// `FFI_ArrowSchema`/`FFI_ArrowArray` that has not already been consumed.
+/// We take ownership of both via `ptr::replace`, leaving a `release: None`
+/// placeholder behind so the capsule's own destructor becomes a no-op
+/// instead of releasing the same buffers/`Arc` a second time once our
+/// `RecordBatch` (or `arrow-rs`'s import of it) drops and calls `release`.
+unsafe fn import_array_capsules(
+    schema_capsule: &Bound<'_, PyCapsule>,
+    array_capsule: &Bound<'_, PyCapsule>,
+) -> PyResult<RecordBatch> {
+    expect_capsule_named(schema_capsule, "arrow_schema")?;
+    expect_capsule_named(array_capsule, "arrow_array")?;
+
+    let schema_ptr = schema_capsule.pointer() as *mut FFI_ArrowSchema;
+    let array_ptr = array_capsule.pointer() as *mut FFI_ArrowArray;
+
+    let schema = std::ptr::replace(schema_ptr, FFI_ArrowSchema::empty());
+    let array = std::ptr::replace(array_ptr, FFI_ArrowArray::empty());
+
+    let array_data = arrow::ffi::from_ffi(array, &schema)
+        .map_err(|e| PyValueError::new_err(format!("invalid Arrow array: {e}")))?;
+    Ok(RecordBatch::from(&StructArray::from(array_data)))
+}
+
+/// Import every `RecordBatch` out of an `arrow_array_stream` PyCapsule
+/// produced by a PyArrow object's `__arrow_c_stream__`.
+///
+/// # Safety
+/// Per the Arrow PyCapsule Interface, the capsule owns a valid, unaliased
+/// `FFI_ArrowArrayStream` that has not already been consumed; this
+/// function takes ownership of it via `ArrowArrayStreamReader::from_raw`,
+/// which leaves its own release-neutralized placeholder behind.
+unsafe fn import_stream_capsule(stream_capsule: &Bound<'_, PyCapsule>) -> PyResult<Vec<RecordBatch>> {
+    expect_capsule_named(stream_capsule, "arrow_array_stream")?;
+
+    let stream_ptr = stream_capsule.pointer() as *mut FFI_ArrowArrayStream;
+    let reader = ArrowArrayStreamReader::from_raw(stream_ptr)
+        .map_err(|e| PyValueError::new_err(format!("invalid Arrow stream: {e}")))?;
+    reader
+        .collect::<Result<Vec<RecordBatch>, _>>()
+        .map_err(|e| PyValueError::new_err(format!("failed to read Arrow stream: {e}")))
+}
+
+/// Import one or more `RecordBatch`es out of a PyArrow `RecordBatch`,
+/// `Table`, or `RecordBatchReader` without copying any column buffers, via
+/// the Arrow C Data Interface (`__arrow_c_array__`/`__arrow_c_stream__`).
+fn import_arrow(data: &Bound<'_, PyAny>) -> PyResult<Vec<RecordBatch>> {
+    if let Ok(capsules) = data.call_method0("__arrow_c_array__") {
+        let (schema_capsule, array_capsule): (Bound<'_, PyCapsule>, Bound<'_, PyCapsule>) =
+            capsules.extract()?;
+        // Safety: `__arrow_c_array__` hands us freshly produced capsules.
+        return Ok(vec![unsafe { import_array_capsules(&schema_capsule, &array_capsule)? }]);
+    }
+
+    if let Ok(capsule) = data.call_method0("__arrow_c_stream__") {
+        let stream_capsule: Bound<'_, PyCapsule> = capsule.extract()?;
+        // Safety: `__arrow_c_stream__` hands us a freshly produced capsule.
+        return unsafe { import_stream_capsule(&stream_capsule) };
+    }
+
+    Err(PyValueError::new_err(
+        "data must be a PyArrow RecordBatch, Table, or RecordBatchReader \
+         (an object implementing __arrow_c_array__ or __arrow_c_stream__)",
+    ))
 }
 
 /// Zenith Engine - High-performance data processing
@@ -128,29 +233,82 @@ impl PyEngine {
     
     /// Publish data to the engine for processing
     ///
+    /// Imports `data` zero-copy via the Arrow C Data Interface and pushes
+    /// the resulting batch(es) into the engine's ring buffer, tagged with
+    /// `source_id` and `seq_no` (a `Table`/`RecordBatchReader` contributes
+    /// one entry per batch, with `seq_no` incrementing from the given
+    /// value).
+    ///
     /// Args:
-    ///     data: PyArrow RecordBatch or Table
+    ///     data: PyArrow RecordBatch, Table, or RecordBatchReader
     ///     source_id: Identifier for the data source
     ///     seq_no: Sequence number for ordering
     ///
     /// Raises:
-    ///     RuntimeError: If publishing fails
+    ///     ValueError: If data is not a PyArrow Arrow-compatible object
+    ///     RuntimeError: If the engine isn't running or the ring buffer is full
     #[pyo3(signature = (data, source_id=0, seq_no=0))]
     fn publish(&self, data: &Bound<'_, PyAny>, source_id: u32, seq_no: u64) -> PyResult<()> {
         let inner = self.inner.lock()
             .map_err(|_| PyRuntimeError::new_err("Failed to acquire engine lock"))?;
-        
+
         if !inner.is_running() {
             return Err(PyRuntimeError::new_err("Engine is not running"));
         }
-        
-        // In production, this would:
-        // 1. Convert PyArrow data to Arrow FFI
-        // 2. Push to ring buffer
-        // 3. Trigger plugin processing
-        
+
+        for (i, batch) in import_arrow(data)?.into_iter().enumerate() {
+            let item = PublishedBatch {
+                source_id,
+                seq_no: seq_no.wrapping_add(i as u64),
+                batch,
+            };
+            inner.try_publish(item)
+                .map_err(|_| PyRuntimeError::new_err("Ring buffer is full"))?;
+        }
+
         Ok(())
     }
+
+    /// Publish data to the engine without blocking on backpressure.
+    ///
+    /// Identical to [`Self::publish`], except a full ring buffer is
+    /// reported by returning `False` (dropping any batches from `data`
+    /// that didn't fit) instead of raising `RuntimeError`.
+    ///
+    /// Args:
+    ///     data: PyArrow RecordBatch, Table, or RecordBatchReader
+    ///     source_id: Identifier for the data source
+    ///     seq_no: Sequence number for ordering
+    ///
+    /// Returns:
+    ///     `True` if every batch was published, `False` if the ring buffer
+    ///     filled up partway through
+    ///
+    /// Raises:
+    ///     ValueError: If data is not a PyArrow Arrow-compatible object
+    ///     RuntimeError: If the engine isn't running
+    #[pyo3(signature = (data, source_id=0, seq_no=0))]
+    fn try_publish(&self, data: &Bound<'_, PyAny>, source_id: u32, seq_no: u64) -> PyResult<bool> {
+        let inner = self.inner.lock()
+            .map_err(|_| PyRuntimeError::new_err("Failed to acquire engine lock"))?;
+
+        if !inner.is_running() {
+            return Err(PyRuntimeError::new_err("Engine is not running"));
+        }
+
+        for (i, batch) in import_arrow(data)?.into_iter().enumerate() {
+            let item = PublishedBatch {
+                source_id,
+                seq_no: seq_no.wrapping_add(i as u64),
+                batch,
+            };
+            if inner.try_publish(item).is_err() {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
     
     /// Get list of loaded plugins
     #[getter]
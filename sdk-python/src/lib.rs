@@ -28,7 +28,7 @@ mod plugin;
 
 pub use engine::PyEngine;
 pub use buffer::RingBuffer;
-pub use plugin::PluginManager;
+pub use plugin::{PluginError, PluginManager};
 
 /// Zenith AI Python Module
 ///
@@ -1,11 +1,62 @@
 //! WASM Plugin Manager
 //!
 //! This module handles loading, execution, and lifecycle management
-//! of WebAssembly preprocessing plugins.
+//! of WebAssembly preprocessing plugins, backed by an actual wasmtime
+//! engine with per-invocation fuel and memory limits so an untrusted
+//! plugin can't hang or blow out the host process.
 
 use std::collections::HashMap;
 use std::path::Path;
 
+use host_api::kv::backend::KvBackendKind;
+use host_api::KvAPI;
+use wasmtime::{Caller, Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder, Trap, ValType};
+
+/// Guest memory cap enforced per plugin invocation.
+const DEFAULT_MEMORY_LIMIT_BYTES: usize = 64 * 1024 * 1024;
+/// Fuel budget enforced per plugin invocation; a plugin that exhausts this
+/// traps with [`PluginError::FuelExhausted`] instead of hanging the host.
+const DEFAULT_FUEL: u64 = 1_000_000;
+
+/// Errors from loading or executing a WASM plugin.
+#[derive(Debug)]
+pub enum PluginError {
+    /// The plugin file could not be read from disk.
+    Io(String),
+    /// The WASM bytes failed to compile into a module.
+    Compile(String),
+    /// The module doesn't export `alloc`/`process`/`plugin_info`/
+    /// `plugin_version` with the expected signatures.
+    AbiMismatch(String),
+    /// The module failed to instantiate.
+    Instantiate(String),
+    /// A read/write against the plugin's exported memory failed.
+    MemoryAccess(String),
+    /// The plugin ran out of fuel before `process` returned.
+    FuelExhausted,
+    /// The plugin trapped for a reason other than fuel exhaustion.
+    Trap(String),
+    /// No plugin is loaded under the requested name.
+    NotFound(String),
+}
+
+impl std::fmt::Display for PluginError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(msg) => write!(f, "failed to read plugin: {}", msg),
+            Self::Compile(msg) => write!(f, "failed to compile plugin: {}", msg),
+            Self::AbiMismatch(msg) => write!(f, "plugin ABI mismatch: {}", msg),
+            Self::Instantiate(msg) => write!(f, "failed to instantiate plugin: {}", msg),
+            Self::MemoryAccess(msg) => write!(f, "plugin memory access failed: {}", msg),
+            Self::FuelExhausted => write!(f, "plugin exhausted its fuel budget"),
+            Self::Trap(msg) => write!(f, "plugin trapped: {}", msg),
+            Self::NotFound(name) => write!(f, "plugin not found: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for PluginError {}
+
 /// Information about a loaded plugin
 #[derive(Clone, Debug)]
 pub struct PluginInfo {
@@ -15,87 +66,319 @@ pub struct PluginInfo {
     pub size_bytes: usize,
 }
 
+/// Per-invocation store state: only the resource limiter, since nothing
+/// else needs to live past a single `execute` call.
+struct PluginState {
+    limits: StoreLimits,
+}
+
+/// A compiled, ABI-validated plugin module, ready to be instantiated.
+struct LoadedPlugin {
+    info: PluginInfo,
+    module: Module,
+}
+
 /// Manages WASM plugin lifecycle
 pub struct PluginManager {
-    plugins: HashMap<String, PluginInfo>,
-    // In production, this would hold wasmtime::Module instances
+    engine: Engine,
+    plugins: HashMap<String, LoadedPlugin>,
 }
 
 impl PluginManager {
-    /// Create a new plugin manager
-    pub fn new() -> Self {
+    /// Create a new plugin manager, using `kv_backend` for the host-side
+    /// `KvAPI` state that `#[import] zenith_kv_*` calls land in. Pass
+    /// [`KvBackendKind::Memory`] for the historical process-lifetime-only
+    /// behavior, or [`KvBackendKind::File`] so plugin state survives a
+    /// host restart.
+    pub fn new(kv_backend: KvBackendKind) -> Self {
+        KvAPI::configure(&kv_backend);
+
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).expect("default wasmtime config is always valid");
+
         Self {
+            engine,
             plugins: HashMap::new(),
         }
     }
-    
-    /// Load a WASM plugin from file
-    pub fn load(&mut self, path: &Path) -> Result<PluginInfo, String> {
-        let wasm_bytes = std::fs::read(path)
-            .map_err(|e| format!("Failed to read plugin: {}", e))?;
-        
+
+    /// Load a WASM plugin from file, compiling it and validating that it
+    /// exports our `alloc`/`process`/`plugin_info`/`plugin_version` ABI.
+    pub fn load(&mut self, path: &Path) -> Result<PluginInfo, PluginError> {
+        let wasm_bytes = std::fs::read(path).map_err(|e| PluginError::Io(e.to_string()))?;
+
         let name = path
             .file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("unknown")
             .to_string();
-        
+
+        let module = Module::new(&self.engine, &wasm_bytes).map_err(|e| PluginError::Compile(e.to_string()))?;
+        Self::validate_abi(&module)?;
+
         let info = PluginInfo {
             name: name.clone(),
             version: "0.1.0".to_string(),
             path: path.to_string_lossy().to_string(),
             size_bytes: wasm_bytes.len(),
         };
-        
-        // In production, this would:
-        // 1. Compile WASM module with wasmtime
-        // 2. Validate plugin interface
-        // 3. Store compiled module for execution
-        
-        self.plugins.insert(name.clone(), info.clone());
-        
+
+        self.plugins.insert(
+            name,
+            LoadedPlugin {
+                info: info.clone(),
+                module,
+            },
+        );
+
         Ok(info)
     }
-    
+
+    /// Validate that `module` exports our plugin ABI with the expected
+    /// function signatures, without needing to instantiate it.
+    fn validate_abi(module: &Module) -> Result<(), PluginError> {
+        let expect_func = |name: &str, params: &[ValType], results: &[ValType]| -> Result<(), PluginError> {
+            let export = module
+                .get_export(name)
+                .ok_or_else(|| PluginError::AbiMismatch(format!("missing export '{}'", name)))?;
+            let func_ty = export
+                .func()
+                .ok_or_else(|| PluginError::AbiMismatch(format!("export '{}' is not a function", name)))?;
+
+            let actual_params: Vec<ValType> = func_ty.params().collect();
+            let actual_results: Vec<ValType> = func_ty.results().collect();
+            if actual_params != params || actual_results != results {
+                return Err(PluginError::AbiMismatch(format!(
+                    "export '{}' has signature {:?} -> {:?}, expected {:?} -> {:?}",
+                    name, actual_params, actual_results, params, results
+                )));
+            }
+            Ok(())
+        };
+
+        expect_func("alloc", &[ValType::I32], &[ValType::I32])?;
+        expect_func("process", &[ValType::I32, ValType::I32], &[ValType::I64])?;
+        expect_func("plugin_info", &[], &[ValType::I64])?;
+        expect_func("plugin_version", &[], &[ValType::I64])?;
+        Ok(())
+    }
+
     /// Unload a plugin by name
     pub fn unload(&mut self, name: &str) -> bool {
         self.plugins.remove(name).is_some()
     }
-    
+
     /// Get information about a loaded plugin
     pub fn get(&self, name: &str) -> Option<&PluginInfo> {
-        self.plugins.get(name)
+        self.plugins.get(name).map(|p| &p.info)
     }
-    
+
     /// List all loaded plugins
     pub fn list(&self) -> Vec<&PluginInfo> {
-        self.plugins.values().collect()
+        self.plugins.values().map(|p| &p.info).collect()
     }
-    
+
     /// Get the number of loaded plugins
     pub fn count(&self) -> usize {
         self.plugins.len()
     }
-    
-    /// Execute a plugin on data
-    pub fn execute(
-        &self,
-        name: &str,
-        input: &[u8],
-    ) -> Result<Vec<u8>, String> {
-        let _plugin = self.plugins.get(name)
-            .ok_or_else(|| format!("Plugin not found: {}", name))?;
-        
-        // In production, this would:
-        // 1. Get the compiled WASM module
-        // 2. Create a new instance with memory
-        // 3. Copy input data to WASM memory
-        // 4. Call the process function
-        // 5. Copy output data from WASM memory
-        
-        // Placeholder: return input unchanged
-        Ok(input.to_vec())
+
+    /// Execute a plugin on `input`, in a fresh `Store` with a per-invocation
+    /// fuel budget and memory cap so a runaway plugin traps instead of
+    /// hanging or exhausting host memory.
+    pub fn execute(&self, name: &str, input: &[u8]) -> Result<Vec<u8>, PluginError> {
+        let plugin = self.plugins.get(name).ok_or_else(|| PluginError::NotFound(name.to_string()))?;
+
+        let limits = StoreLimitsBuilder::new().memory_size(DEFAULT_MEMORY_LIMIT_BYTES).build();
+        let mut store = Store::new(&self.engine, PluginState { limits });
+        store.limiter(|state| &mut state.limits);
+        store
+            .set_fuel(DEFAULT_FUEL)
+            .map_err(|e| PluginError::Instantiate(e.to_string()))?;
+
+        let mut linker = Linker::new(&self.engine);
+        Self::link_kv_imports(&mut linker, &plugin.info.name).map_err(|e| PluginError::Instantiate(e.to_string()))?;
+
+        let instance = linker
+            .instantiate(&mut store, &plugin.module)
+            .map_err(|e| PluginError::Instantiate(e.to_string()))?;
+
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| PluginError::AbiMismatch("missing exported 'memory'".to_string()))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "alloc")
+            .map_err(|e| PluginError::AbiMismatch(e.to_string()))?;
+        let process = instance
+            .get_typed_func::<(i32, i32), i64>(&mut store, "process")
+            .map_err(|e| PluginError::AbiMismatch(e.to_string()))?;
+
+        let in_len = input.len() as i32;
+        let in_ptr = alloc.call(&mut store, in_len).map_err(Self::classify_trap)?;
+
+        memory
+            .write(&mut store, in_ptr as usize, input)
+            .map_err(|e| PluginError::MemoryAccess(e.to_string()))?;
+
+        let packed = process.call(&mut store, (in_ptr, in_len)).map_err(Self::classify_trap)?;
+        let (out_ptr, out_len) = Self::unpack(packed);
+
+        let mut output = vec![0u8; out_len as usize];
+        memory
+            .read(&store, out_ptr as usize, &mut output)
+            .map_err(|e| PluginError::MemoryAccess(e.to_string()))?;
+
+        Ok(output)
+    }
+
+    /// Turn a guest trap into a typed error, distinguishing fuel exhaustion
+    /// (the common "runaway plugin" case) from any other trap.
+    fn classify_trap(err: anyhow::Error) -> PluginError {
+        if let Some(trap) = err.downcast_ref::<Trap>() {
+            if *trap == Trap::OutOfFuel {
+                return PluginError::FuelExhausted;
+            }
+        }
+        PluginError::Trap(err.to_string())
+    }
+
+    /// Unpack a `process` result: pointer in the high 32 bits, length in
+    /// the low 32 bits.
+    fn unpack(packed: i64) -> (i32, i32) {
+        ((packed >> 32) as i32, packed as i32)
+    }
+
+    /// Register `zenith_kv_set`/`zenith_kv_get`/`zenith_kv_delete` as `env`
+    /// imports that transparently prefix every key with `plugin_name`, so
+    /// each plugin gets an isolated keyspace in the shared `KvAPI` store
+    /// instead of clobbering other plugins' keys.
+    fn link_kv_imports(linker: &mut Linker<PluginState>, plugin_name: &str) -> anyhow::Result<()> {
+        let prefix = plugin_name.to_string();
+        let set_prefix = prefix.clone();
+        linker.func_wrap(
+            "env",
+            "zenith_kv_set",
+            move |mut caller: Caller<'_, PluginState>, key_ptr: i32, key_len: i32, value_ptr: i32, value_len: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(m) => m,
+                    None => return -1,
+                };
+                let data = memory.data(&caller);
+                let key = match read_str(data, key_ptr, key_len) {
+                    Some(k) => k.to_string(),
+                    None => return -2,
+                };
+                let value = match read_bytes(data, value_ptr, value_len) {
+                    Some(v) => v.to_vec(),
+                    None => return -1,
+                };
+                match KvAPI::set(&Self::kv_namespaced_key(&set_prefix, &key), &value) {
+                    Ok(()) => 0,
+                    Err(_) => -3,
+                }
+            },
+        )?;
+
+        let get_prefix = prefix.clone();
+        linker.func_wrap(
+            "env",
+            "zenith_kv_get",
+            move |mut caller: Caller<'_, PluginState>, key_ptr: i32, key_len: i32, out_ptr: i32, out_len: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(m) => m,
+                    None => return -1,
+                };
+                let key = match read_str(memory.data(&caller), key_ptr, key_len) {
+                    Some(k) => k.to_string(),
+                    None => return -2,
+                };
+                match KvAPI::get(&Self::kv_namespaced_key(&get_prefix, &key)) {
+                    Some(value) => {
+                        let copy_len = value.len().min(out_len.max(0) as usize);
+                        let data = memory.data_mut(&mut caller);
+                        match data.get_mut(out_ptr as usize..out_ptr as usize + copy_len) {
+                            Some(dst) => {
+                                dst.copy_from_slice(&value[..copy_len]);
+                                copy_len as i32
+                            }
+                            None => -1,
+                        }
+                    }
+                    None => -3,
+                }
+            },
+        )?;
+
+        let delete_prefix = prefix;
+        linker.func_wrap(
+            "env",
+            "zenith_kv_delete",
+            move |mut caller: Caller<'_, PluginState>, key_ptr: i32, key_len: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(m) => m,
+                    None => return -1,
+                };
+                let key = match read_str(memory.data(&caller), key_ptr, key_len) {
+                    Some(k) => k.to_string(),
+                    None => return -2,
+                };
+                if KvAPI::delete(&Self::kv_namespaced_key(&delete_prefix, &key)) {
+                    0
+                } else {
+                    -3
+                }
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Prefix `key` with `plugin_name`, isolating each plugin's keyspace
+    /// inside the shared `KvAPI` store.
+    fn kv_namespaced_key(plugin_name: &str, key: &str) -> String {
+        format!("{}:{}", plugin_name, key)
+    }
+
+    /// Snapshot a single plugin's KV state, with the plugin-name prefix
+    /// stripped back off each key, without touching any other plugin's
+    /// entries.
+    pub fn kv_snapshot(&self, name: &str) -> HashMap<String, Vec<u8>> {
+        let prefix = Self::kv_namespaced_key(name, "");
+        KvAPI::keys()
+            .into_iter()
+            .filter_map(|full_key| {
+                let local_key = full_key.strip_prefix(&prefix)?.to_string();
+                let value = KvAPI::get(&full_key)?;
+                Some((local_key, value))
+            })
+            .collect()
+    }
+
+    /// Wipe a single plugin's KV state, leaving every other plugin's
+    /// entries untouched.
+    pub fn kv_clear(&self, name: &str) {
+        let prefix = Self::kv_namespaced_key(name, "");
+        for full_key in KvAPI::keys() {
+            if full_key.starts_with(&prefix) {
+                KvAPI::delete(&full_key);
+            }
+        }
+    }
+}
+
+/// Read `len` bytes at `ptr` from a guest memory snapshot, bounds-checked.
+fn read_bytes(data: &[u8], ptr: i32, len: i32) -> Option<&[u8]> {
+    if ptr < 0 || len < 0 {
+        return None;
     }
+    let (start, len) = (ptr as usize, len as usize);
+    data.get(start..start.checked_add(len)?)
+}
+
+/// Read a UTF-8 string of `len` bytes at `ptr` from a guest memory snapshot.
+fn read_str(data: &[u8], ptr: i32, len: i32) -> Option<&str> {
+    std::str::from_utf8(read_bytes(data, ptr, len)?).ok()
 }
 
 impl Default for PluginManager {
@@ -109,21 +392,176 @@ mod tests {
     use super::*;
     use std::io::Write;
     use tempfile::NamedTempFile;
-    
+
+    /// A minimal plugin that satisfies the ABI and echoes its input back
+    /// unchanged by returning the same (ptr, len) it was given.
+    fn echo_plugin_wasm() -> Vec<u8> {
+        wat::parse_str(
+            r#"
+            (module
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32)
+                    i32.const 1024
+                )
+                (func (export "process") (param i32 i32) (result i64)
+                    local.get 0
+                    i64.extend_i32_u
+                    i64.const 32
+                    i64.shl
+                    local.get 1
+                    i64.extend_i32_u
+                    i64.or
+                )
+                (func (export "plugin_info") (result i64)
+                    i64.const 0
+                )
+                (func (export "plugin_version") (result i64)
+                    i64.const 0
+                )
+            )
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn infinite_loop_plugin_wasm() -> Vec<u8> {
+        wat::parse_str(
+            r#"
+            (module
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32)
+                    i32.const 0
+                )
+                (func (export "process") (param i32 i32) (result i64)
+                    (loop $l
+                        br $l
+                    )
+                    i64.const 0
+                )
+                (func (export "plugin_info") (result i64)
+                    i64.const 0
+                )
+                (func (export "plugin_version") (result i64)
+                    i64.const 0
+                )
+            )
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn write_temp_wasm(bytes: &[u8]) -> NamedTempFile {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(bytes).unwrap();
+        temp_file
+    }
+
+    /// A plugin that imports the KV host functions, writes key "k" = "hello"
+    /// through `zenith_kv_set`, reads it back through `zenith_kv_get`, and
+    /// returns whatever it read.
+    fn kv_roundtrip_plugin_wasm() -> Vec<u8> {
+        wat::parse_str(
+            r#"
+            (module
+                (import "env" "zenith_kv_set" (func $kv_set (param i32 i32 i32 i32) (result i32)))
+                (import "env" "zenith_kv_get" (func $kv_get (param i32 i32 i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "k")
+                (data (i32.const 16) "hello")
+                (func (export "alloc") (param i32) (result i32)
+                    i32.const 1024
+                )
+                (func (export "process") (param i32 i32) (result i64)
+                    i32.const 0
+                    i32.const 1
+                    i32.const 16
+                    i32.const 5
+                    call $kv_set
+                    drop
+                    i32.const 0
+                    i32.const 1
+                    i32.const 32
+                    i32.const 5
+                    call $kv_get
+                    drop
+                    i32.const 32
+                    i64.extend_i32_u
+                    i64.const 32
+                    i64.shl
+                    i32.const 5
+                    i64.extend_i32_u
+                    i64.or
+                )
+                (func (export "plugin_info") (result i64)
+                    i64.const 0
+                )
+                (func (export "plugin_version") (result i64)
+                    i64.const 0
+                )
+            )
+            "#,
+        )
+        .unwrap()
+    }
+
     #[test]
-    fn test_plugin_manager() {
-        let mut manager = PluginManager::new();
+    fn test_plugin_manager_load_and_execute_round_trip() {
+        let mut manager = PluginManager::new(KvBackendKind::Memory);
         assert_eq!(manager.count(), 0);
-        
-        // Create a fake WASM file
-        let mut temp_file = NamedTempFile::new().unwrap();
-        temp_file.write_all(b"\x00asm\x01\x00\x00\x00").unwrap();
-        
-        let result = manager.load(temp_file.path());
-        assert!(result.is_ok());
-        
-        let info = result.unwrap();
-        assert_eq!(info.size_bytes, 8);
+
+        let temp_file = write_temp_wasm(&echo_plugin_wasm());
+        let info = manager.load(temp_file.path()).unwrap();
         assert_eq!(manager.count(), 1);
+        assert!(info.size_bytes > 0);
+
+        let output = manager.execute(&info.name, b"hello").unwrap();
+        assert_eq!(output, b"hello");
+    }
+
+    #[test]
+    fn test_plugin_manager_rejects_missing_abi_exports() {
+        let mut manager = PluginManager::new(KvBackendKind::Memory);
+        let temp_file = write_temp_wasm(&wat::parse_str("(module)").unwrap());
+        let result = manager.load(temp_file.path());
+        assert!(matches!(result, Err(PluginError::AbiMismatch(_))));
+    }
+
+    #[test]
+    fn test_plugin_manager_execute_reports_fuel_exhaustion() {
+        let mut manager = PluginManager::new(KvBackendKind::Memory);
+        let temp_file = write_temp_wasm(&infinite_loop_plugin_wasm());
+        let info = manager.load(temp_file.path()).unwrap();
+
+        let result = manager.execute(&info.name, b"x");
+        assert!(matches!(result, Err(PluginError::FuelExhausted)));
+    }
+
+    #[test]
+    fn test_plugin_manager_execute_unknown_plugin() {
+        let manager = PluginManager::new(KvBackendKind::Memory);
+        let result = manager.execute("missing", b"x");
+        assert!(matches!(result, Err(PluginError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_plugin_kv_is_namespaced_per_plugin() {
+        KvAPI::clear();
+        let mut manager = PluginManager::new(KvBackendKind::Memory);
+
+        let temp_a = write_temp_wasm(&kv_roundtrip_plugin_wasm());
+        let info_a = manager.load(temp_a.path()).unwrap();
+        let output = manager.execute(&info_a.name, b"x").unwrap();
+        assert_eq!(output, b"hello");
+
+        // The plugin's own snapshot sees its unprefixed key...
+        let snapshot = manager.kv_snapshot(&info_a.name);
+        assert_eq!(snapshot.get("k"), Some(&b"hello".to_vec()));
+
+        // ...but a different plugin name's snapshot does not.
+        let other_snapshot = manager.kv_snapshot("some-other-plugin");
+        assert!(other_snapshot.is_empty());
+
+        manager.kv_clear(&info_a.name);
+        assert!(manager.kv_snapshot(&info_a.name).is_empty());
     }
 }
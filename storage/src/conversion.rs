@@ -0,0 +1,176 @@
+/// Typed schema layer for decoding `StoredEvent.data`.
+///
+/// `StoredEvent.data` is opaque `Vec<u8>`; a `Conversion` describes how
+/// to turn one such field into a `TypedValue` so downstream ML loaders
+/// don't each reinvent parsing.
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::str::FromStr;
+
+/// How to decode a raw byte field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conversion {
+    /// Pass the bytes through unchanged.
+    Bytes,
+    /// Parse as UTF-8 then as an integer.
+    Integer,
+    /// Parse as UTF-8 then as a float.
+    Float,
+    /// Parse as UTF-8; accepts `true`/`false`/`1`/`0`.
+    Boolean,
+    /// Parse as RFC 3339, falling back to unix seconds.
+    Timestamp,
+    /// Parse with a `chrono` format string, interpreted as UTC.
+    TimestampFmt(String),
+    /// Parse with a `chrono` format string that embeds its own offset.
+    TimestampTzFmt(String),
+}
+
+impl FromStr for Conversion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some(fmt) = s.strip_prefix("timestamp_tz|") {
+            return Ok(Conversion::TimestampTzFmt(fmt.to_string()));
+        }
+        if let Some(fmt) = s.strip_prefix("timestamp|") {
+            return Ok(Conversion::TimestampFmt(fmt.to_string()));
+        }
+
+        match s {
+            "bytes" | "string" | "asis" => Ok(Conversion::Bytes),
+            "int" | "integer" => Ok(Conversion::Integer),
+            "float" => Ok(Conversion::Float),
+            "bool" | "boolean" => Ok(Conversion::Boolean),
+            "timestamp" => Ok(Conversion::Timestamp),
+            other => Err(anyhow!("unknown conversion: {}", other)),
+        }
+    }
+}
+
+/// A decoded field, in the shape its `Conversion` describes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedValue {
+    Bytes(Vec<u8>),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    /// Nanoseconds since the Unix epoch.
+    Timestamp(i64),
+}
+
+impl Conversion {
+    /// Decode `bytes` according to this conversion.
+    pub fn convert(&self, bytes: &[u8]) -> Result<TypedValue> {
+        match self {
+            Conversion::Bytes => Ok(TypedValue::Bytes(bytes.to_vec())),
+            Conversion::Integer => {
+                let text = std::str::from_utf8(bytes)?;
+                Ok(TypedValue::Integer(text.trim().parse()?))
+            }
+            Conversion::Float => {
+                let text = std::str::from_utf8(bytes)?;
+                Ok(TypedValue::Float(text.trim().parse()?))
+            }
+            Conversion::Boolean => {
+                let text = std::str::from_utf8(bytes)?.trim();
+                match text {
+                    "true" | "1" => Ok(TypedValue::Boolean(true)),
+                    "false" | "0" => Ok(TypedValue::Boolean(false)),
+                    other => Err(anyhow!("not a boolean: {}", other)),
+                }
+            }
+            Conversion::Timestamp => {
+                let text = std::str::from_utf8(bytes)?.trim();
+                if let Ok(dt) = DateTime::parse_from_rfc3339(text) {
+                    return Ok(TypedValue::Timestamp(dt.timestamp_nanos_opt().ok_or_else(|| {
+                        anyhow!("timestamp out of range for nanosecond representation")
+                    })?));
+                }
+                let secs: i64 = text
+                    .parse()
+                    .map_err(|_| anyhow!("not an RFC3339 timestamp or unix seconds: {}", text))?;
+                Ok(TypedValue::Timestamp(
+                    secs.checked_mul(1_000_000_000).ok_or_else(|| anyhow!("unix timestamp overflows"))?,
+                ))
+            }
+            Conversion::TimestampFmt(fmt) => {
+                let text = std::str::from_utf8(bytes)?.trim();
+                let naive = NaiveDateTime::parse_from_str(text, fmt)?;
+                let dt = naive.and_utc();
+                Ok(TypedValue::Timestamp(dt.timestamp_nanos_opt().ok_or_else(|| {
+                    anyhow!("timestamp out of range for nanosecond representation")
+                })?))
+            }
+            Conversion::TimestampTzFmt(fmt) => {
+                let text = std::str::from_utf8(bytes)?.trim();
+                let dt = DateTime::parse_from_str(text, fmt)?.with_timezone(&Utc);
+                Ok(TypedValue::Timestamp(dt.timestamp_nanos_opt().ok_or_else(|| {
+                    anyhow!("timestamp out of range for nanosecond representation")
+                })?))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_matches_known_names() {
+        assert_eq!(Conversion::from_str("bytes").unwrap(), Conversion::Bytes);
+        assert_eq!(Conversion::from_str("asis").unwrap(), Conversion::Bytes);
+        assert_eq!(Conversion::from_str("int").unwrap(), Conversion::Integer);
+        assert_eq!(Conversion::from_str("integer").unwrap(), Conversion::Integer);
+        assert_eq!(Conversion::from_str("float").unwrap(), Conversion::Float);
+        assert_eq!(Conversion::from_str("bool").unwrap(), Conversion::Boolean);
+        assert_eq!(Conversion::from_str("timestamp").unwrap(), Conversion::Timestamp);
+        assert_eq!(
+            Conversion::from_str("timestamp|%Y-%m-%d %H:%M:%S").unwrap(),
+            Conversion::TimestampFmt("%Y-%m-%d %H:%M:%S".to_string())
+        );
+        assert_eq!(
+            Conversion::from_str("timestamp_tz|%Y-%m-%d %H:%M:%S %z").unwrap(),
+            Conversion::TimestampTzFmt("%Y-%m-%d %H:%M:%S %z".to_string())
+        );
+        assert!(Conversion::from_str("nope").is_err());
+    }
+
+    #[test]
+    fn test_convert_integer_float_boolean() {
+        assert_eq!(Conversion::Integer.convert(b"42").unwrap(), TypedValue::Integer(42));
+        assert_eq!(Conversion::Float.convert(b"3.5").unwrap(), TypedValue::Float(3.5));
+        assert_eq!(Conversion::Boolean.convert(b"true").unwrap(), TypedValue::Boolean(true));
+        assert_eq!(Conversion::Boolean.convert(b"0").unwrap(), TypedValue::Boolean(false));
+        assert!(Conversion::Boolean.convert(b"maybe").is_err());
+    }
+
+    #[test]
+    fn test_convert_bytes_passes_through() {
+        assert_eq!(
+            Conversion::Bytes.convert(&[1, 2, 3]).unwrap(),
+            TypedValue::Bytes(vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn test_convert_timestamp_rfc3339_and_unix_seconds() {
+        let rfc3339 = Conversion::Timestamp.convert(b"2024-01-01T00:00:00Z").unwrap();
+        assert_eq!(rfc3339, TypedValue::Timestamp(1_704_067_200_000_000_000));
+
+        let unix = Conversion::Timestamp.convert(b"1704067200").unwrap();
+        assert_eq!(unix, TypedValue::Timestamp(1_704_067_200_000_000_000));
+    }
+
+    #[test]
+    fn test_convert_timestamp_fmt_and_tz_fmt() {
+        let fmt = Conversion::TimestampFmt("%Y-%m-%d %H:%M:%S".to_string());
+        let value = fmt.convert(b"2024-01-01 00:00:00").unwrap();
+        assert_eq!(value, TypedValue::Timestamp(1_704_067_200_000_000_000));
+
+        let tz_fmt = Conversion::TimestampTzFmt("%Y-%m-%d %H:%M:%S %z".to_string());
+        let value = tz_fmt.convert(b"2024-01-01 00:00:00 +0000").unwrap();
+        assert_eq!(value, TypedValue::Timestamp(1_704_067_200_000_000_000));
+    }
+}
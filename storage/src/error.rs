@@ -0,0 +1,31 @@
+/// Structured errors for `StorageEngine`, so callers (including the
+/// PyO3 layer) can distinguish a missing key from corruption or an
+/// encode/decode failure instead of matching on an opaque `anyhow::Error`.
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("sled I/O error during {operation}: {source}")]
+    Io {
+        operation: &'static str,
+        #[source]
+        source: sled::Error,
+    },
+
+    #[error("failed to encode event: {0}")]
+    Encode(#[from] bincode::error::EncodeError),
+
+    #[error("failed to decode event for source {source_id} seq {seq_no}: {source}")]
+    Decode {
+        source_id: u32,
+        seq_no: u64,
+        #[source]
+        source: bincode::error::DecodeError,
+    },
+
+    #[error("corrupt key or metadata encountered during {0}")]
+    KeyCorrupt(&'static str),
+
+    #[error("failed to open tree {0}: {1}")]
+    TreeOpen(&'static str, #[source] sled::Error),
+}
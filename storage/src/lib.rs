@@ -1,10 +1,29 @@
 /// Zenith Storage Layer
 /// Provides persistent event storage using embedded database
 use sled::{Db, Tree};
+use sled::transaction::{ConflictableTransactionError, ConflictableTransactionResult, TransactionalTree};
 use serde::{Serialize, Deserialize};
 use bincode::{Encode, Decode};
-use anyhow::Result;
+use anyhow::{anyhow, Result as AnyhowResult};
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Mutex;
+
+mod conversion;
+mod error;
+mod merkle;
+mod subscription;
+
+pub use conversion::{Conversion, TypedValue};
+pub use error::StorageError;
+pub use merkle::verify_proof;
+pub use subscription::{KeyExpr, Subscriber};
+
+/// Result type for the methods that carry `StorageError` context
+/// (`store_event`/`get_event`/`get_source_events`/`delete_event`).
+/// Everything else still returns `anyhow::Result` pending the same
+/// treatment.
+pub type Result<T> = std::result::Result<T, StorageError>;
 
 /// Event storage record
 #[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
@@ -19,76 +38,226 @@ pub struct StoredEvent {
 pub struct StorageEngine {
     db: Db,
     events: Tree,
+    meta: Tree,
+    schemas: Mutex<HashMap<u32, Vec<Conversion>>>,
+    subscribers: std::sync::RwLock<Vec<subscription::Registration>>,
 }
 
 impl StorageEngine {
     /// Open or create storage at path
-    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let db = sled::open(path)?;
-        let events = db.open_tree("events")?;
-        
-        Ok(Self { db, events })
+    pub fn open<P: AsRef<Path>>(path: P) -> AnyhowResult<Self> {
+        let db = sled::open(path).map_err(|source| StorageError::TreeOpen("db", source))?;
+        let events = db
+            .open_tree("events")
+            .map_err(|source| StorageError::TreeOpen("events", source))?;
+        let meta = db
+            .open_tree("meta")
+            .map_err(|source| StorageError::TreeOpen("meta", source))?;
+
+        Ok(Self {
+            db,
+            events,
+            meta,
+            schemas: Mutex::new(HashMap::new()),
+            subscribers: std::sync::RwLock::new(Vec::new()),
+        })
     }
-    
-    /// Store an event
+
+    /// Subscribe to events whose `source_id/seq_no` matches `key_expr`
+    /// (`*` as a per-segment wildcard, e.g. `"1/*"`). The returned
+    /// `Subscriber` yields every event stored afterwards that matches,
+    /// until this `StorageEngine` is dropped.
+    pub fn subscribe(&self, key_expr: &str) -> Subscriber {
+        subscription::subscribe(&self.subscribers, key_expr)
+    }
+
+    /// Store an event, assigning it the next enumeration index and
+    /// folding its leaf hash into the Merkle tree.
     pub fn store_event(&self, event: StoredEvent) -> Result<()> {
         let key = Self::make_key(event.source_id, event.seq_no);
         let config = bincode::config::standard();
         let value = bincode::encode_to_vec(&event, config)?;
-        self.events.insert(key, value)?;
+        self.events
+            .insert(key, value)
+            .map_err(|source| StorageError::Io { operation: "insert event", source })?;
+
+        let mut leaf_data = Vec::with_capacity(key.len() + 8 + event.data.len());
+        leaf_data.extend_from_slice(&key);
+        leaf_data.extend_from_slice(&event.timestamp_ns.to_be_bytes());
+        leaf_data.extend_from_slice(&event.data);
+        let leaf_hash = *blake3::hash(&leaf_data).as_bytes();
+
+        // Bump the counter, record this event's enumeration index, and
+        // fold its leaf into the Merkle tree in one sled transaction, so
+        // concurrent `store_event` calls (e.g. from the multi-worker
+        // consumer pool) can never be handed the same index or race each
+        // other over the shared interior-node path up to the root.
+        self.meta
+            .transaction(|tx| {
+                let enum_index = Self::next_enum_index_tx(tx)?;
+                tx.insert(&Self::index_key(&key)[..], enum_index.to_be_bytes().to_vec())?;
+                Self::insert_leaf_tx(tx, enum_index, leaf_hash)?;
+                Ok(())
+            })
+            .map_err(|_: sled::transaction::TransactionError<anyhow::Error>| {
+                StorageError::KeyCorrupt("merkle tree update")
+            })?;
+
+        subscription::publish(&self.subscribers, &event);
         Ok(())
     }
-    
+
     /// Retrieve an event
     pub fn get_event(&self, source_id: u32, seq_no: u64) -> Result<Option<StoredEvent>> {
         let key = Self::make_key(source_id, seq_no);
-        match self.events.get(key)? {
+        let stored = self
+            .events
+            .get(key)
+            .map_err(|source| StorageError::Io { operation: "get event", source })?;
+        match stored {
             Some(data) => {
                 let config = bincode::config::standard();
-                let (event, _): (StoredEvent, _) = bincode::decode_from_slice(&data, config)?;
+                let (event, _): (StoredEvent, _) = bincode::decode_from_slice(&data, config)
+                    .map_err(|source| StorageError::Decode { source_id, seq_no, source })?;
                 Ok(Some(event))
             }
             None => Ok(None),
         }
     }
-    
+
     /// Get all events for a source
     pub fn get_source_events(&self, source_id: u32) -> Result<Vec<StoredEvent>> {
         let prefix = source_id.to_be_bytes();
         let mut events = Vec::new();
         let config = bincode::config::standard();
-        
+
         for item in self.events.scan_prefix(prefix) {
-            let (_key, value) = item?;
-            let (event, _): (StoredEvent, _) = bincode::decode_from_slice(&value, config)?;
+            let (key, value) = item.map_err(|source| StorageError::Io {
+                operation: "scan source events",
+                source,
+            })?;
+            let seq_no = u64::from_be_bytes(
+                key[4..12]
+                    .try_into()
+                    .map_err(|_| StorageError::KeyCorrupt("scan source events"))?,
+            );
+            let (event, _): (StoredEvent, _) = bincode::decode_from_slice(&value, config)
+                .map_err(|source| StorageError::Decode { source_id, seq_no, source })?;
             events.push(event);
         }
-        
+
         Ok(events)
     }
-    
+
+    /// Register a conversion schema for `source_id`, enabling
+    /// `get_source_events_typed` for that source.
+    pub fn set_schema(&self, source_id: u32, schema: Vec<Conversion>) {
+        self.schemas.lock().unwrap().insert(source_id, schema);
+    }
+
+    /// Decode every stored event for `source_id` through its registered
+    /// schema, applying each `Conversion` to the event's `data` to
+    /// produce one typed column per entry, so callers like the PyO3
+    /// `PyDataLoader` can hand out already-typed values instead of raw
+    /// bytes. Errors if no schema was registered via `set_schema`.
+    pub fn get_source_events_typed(&self, source_id: u32) -> AnyhowResult<Vec<Vec<TypedValue>>> {
+        let schema = self
+            .schemas
+            .lock()
+            .unwrap()
+            .get(&source_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("no schema registered for source {}", source_id))?;
+
+        self.get_source_events(source_id)?
+            .iter()
+            .map(|event| schema.iter().map(|conversion| conversion.convert(&event.data)).collect())
+            .collect()
+    }
+
     /// Count total events
     pub fn count_events(&self) -> usize {
         self.events.len()
     }
     
-    /// Delete an event
+    /// Delete an event. The leaf is replaced with a tombstone hash
+    /// rather than removed, so other events' enumeration indices and
+    /// inclusion proofs stay valid.
     pub fn delete_event(&self, source_id: u32, seq_no: u64) -> Result<bool> {
         let key = Self::make_key(source_id, seq_no);
-        Ok(self.events.remove(key)?.is_some())
+        let existed = self
+            .events
+            .remove(key)
+            .map_err(|source| StorageError::Io { operation: "delete event", source })?
+            .is_some();
+        if existed {
+            // Look up the enumeration index and tombstone its leaf in the
+            // same transaction, so this can't race a concurrent
+            // `store_event`/`delete_event` walking the same interior-node
+            // path.
+            self.meta
+                .transaction(|tx| {
+                    let enum_index = match tx.get(&Self::index_key(&key)[..])? {
+                        Some(bytes) => Some(u64::from_be_bytes(bytes.as_ref().try_into().map_err(
+                            |_| ConflictableTransactionError::Abort(anyhow!("corrupt enumeration index")),
+                        )?)),
+                        None => None,
+                    };
+                    if let Some(enum_index) = enum_index {
+                        Self::insert_leaf_tx(tx, enum_index, merkle::tombstone_hash())?;
+                    }
+                    Ok(())
+                })
+                .map_err(|_: sled::transaction::TransactionError<anyhow::Error>| {
+                    StorageError::KeyCorrupt("merkle tombstone update")
+                })?;
+        }
+        Ok(existed)
     }
-    
+
+    /// Current Merkle root over every leaf ever inserted (including
+    /// tombstoned ones). A consumer who only trusts this hash can
+    /// confirm an event was stored and not later mutated via
+    /// `inclusion_proof` and `verify_proof`.
+    pub fn root_hash(&self) -> AnyhowResult<[u8; 32]> {
+        Ok(self
+            .get_node(merkle::TREE_DEPTH, 0)?
+            .unwrap_or_else(|| merkle::empty_hash(merkle::TREE_DEPTH)))
+    }
+
+    /// Sibling hashes and left/right flags from `(source_id, seq_no)`'s
+    /// leaf up to the root, for use with `verify_proof`.
+    pub fn inclusion_proof(&self, source_id: u32, seq_no: u64) -> AnyhowResult<Vec<(bool, [u8; 32])>> {
+        let key = Self::make_key(source_id, seq_no);
+        let mut index = self
+            .get_enum_index(&key)?
+            .ok_or_else(|| anyhow!("no event stored for source {} seq {}", source_id, seq_no))?;
+
+        let mut proof = Vec::with_capacity(merkle::TREE_DEPTH as usize);
+        for level in 0..merkle::TREE_DEPTH {
+            let sibling_index = index ^ 1;
+            let sibling = self
+                .get_node(level, sibling_index)?
+                .unwrap_or_else(|| merkle::empty_hash(level));
+            proof.push((index % 2 == 0, sibling));
+            index /= 2;
+        }
+        Ok(proof)
+    }
+
     /// Flush to disk
-    pub fn flush(&self) -> Result<usize> {
+    pub fn flush(&self) -> AnyhowResult<usize> {
         Ok(self.db.flush()?)
     }
-    
-    /// Clear all events
-    pub fn clear(&self) -> Result<()> {
+
+    /// Clear all events and the enumeration index / Merkle tree built
+    /// over them.
+    pub fn clear(&self) -> AnyhowResult<()> {
         self.events.clear()?;
+        self.meta.clear()?;
         Ok(())
     }
-    
+
     // Helper: create composite key
     fn make_key(source_id: u32, seq_no: u64) -> [u8; 12] {
         let mut key = [0u8; 12];
@@ -96,6 +265,111 @@ impl StorageEngine {
         key[4..12].copy_from_slice(&seq_no.to_be_bytes());
         key
     }
+
+    // Helper: meta-tree key mapping an event key to its enumeration index
+    fn index_key(event_key: &[u8; 12]) -> [u8; 13] {
+        let mut key = [0u8; 13];
+        key[0] = b'i';
+        key[1..13].copy_from_slice(event_key);
+        key
+    }
+
+    fn get_enum_index(&self, event_key: &[u8; 12]) -> AnyhowResult<Option<u64>> {
+        match self.meta.get(Self::index_key(event_key))? {
+            Some(bytes) => Ok(Some(u64::from_be_bytes(
+                bytes.as_ref().try_into().map_err(|_| anyhow!("corrupt enumeration index"))?,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    // Helper: next monotonically increasing enumeration index, global
+    // across all sources so it can index directly into the Merkle tree.
+    // Runs inside a `meta` transaction so the read-then-bump is atomic
+    // with respect to every other transaction on this tree.
+    fn next_enum_index_tx(tx: &TransactionalTree) -> ConflictableTransactionResult<u64, anyhow::Error> {
+        let key = [b'c'];
+        let next = match tx.get(key)? {
+            Some(bytes) => u64::from_be_bytes(bytes.as_ref().try_into().map_err(|_| {
+                ConflictableTransactionError::Abort(anyhow!("corrupt enumeration counter"))
+            })?),
+            None => 0,
+        };
+        tx.insert(&key[..], (next + 1).to_be_bytes().to_vec())?;
+        Ok(next)
+    }
+
+    // Helper: meta-tree key for the interior/leaf node at (level, index)
+    fn node_key(level: u32, index: u64) -> [u8; 13] {
+        let mut key = [0u8; 13];
+        key[0] = b'n';
+        key[1..5].copy_from_slice(&level.to_be_bytes());
+        key[5..13].copy_from_slice(&index.to_be_bytes());
+        key
+    }
+
+    fn get_node(&self, level: u32, index: u64) -> AnyhowResult<Option<[u8; 32]>> {
+        match self.meta.get(Self::node_key(level, index))? {
+            Some(bytes) => Ok(Some(
+                bytes.as_ref().try_into().map_err(|_| anyhow!("corrupt Merkle node"))?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    fn set_node(&self, level: u32, index: u64, hash: [u8; 32]) -> AnyhowResult<()> {
+        self.meta.insert(Self::node_key(level, index), hash.to_vec())?;
+        Ok(())
+    }
+
+    // Transactional counterpart of `get_node`, used by `insert_leaf_tx` so
+    // every read/write along a leaf's path to the root commits atomically.
+    fn get_node_tx(
+        tx: &TransactionalTree,
+        level: u32,
+        index: u64,
+    ) -> ConflictableTransactionResult<Option<[u8; 32]>, anyhow::Error> {
+        match tx.get(&Self::node_key(level, index)[..])? {
+            Some(bytes) => Ok(Some(bytes.as_ref().try_into().map_err(|_| {
+                ConflictableTransactionError::Abort(anyhow!("corrupt Merkle node"))
+            })?)),
+            None => Ok(None),
+        }
+    }
+
+    fn set_node_tx(
+        tx: &TransactionalTree,
+        level: u32,
+        index: u64,
+        hash: [u8; 32],
+    ) -> ConflictableTransactionResult<(), anyhow::Error> {
+        tx.insert(&Self::node_key(level, index)[..], hash.to_vec())?;
+        Ok(())
+    }
+
+    // Helper: fold `leaf_hash` in at enumeration index `index`, updating
+    // every interior node on the path to the root, all within the
+    // enclosing `meta` transaction so a concurrent insert over a shared
+    // ancestor node can't be lost or read a half-updated path.
+    fn insert_leaf_tx(
+        tx: &TransactionalTree,
+        index: u64,
+        leaf_hash: [u8; 32],
+    ) -> ConflictableTransactionResult<(), anyhow::Error> {
+        let mut index = index;
+        let mut hash = leaf_hash;
+        Self::set_node_tx(tx, 0, index, hash)?;
+
+        for level in 0..merkle::TREE_DEPTH {
+            let sibling_index = index ^ 1;
+            let sibling = Self::get_node_tx(tx, level, sibling_index)?.unwrap_or_else(|| merkle::empty_hash(level));
+            let (left, right) = if index % 2 == 0 { (hash, sibling) } else { (sibling, hash) };
+            hash = merkle::combine(&left, &right);
+            index /= 2;
+            Self::set_node_tx(tx, level + 1, index, hash)?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -163,4 +437,121 @@ mod tests {
             assert_eq!(event.seq_no, i as u64);
         }
     }
+
+    #[test]
+    fn test_inclusion_proof_verifies_against_root() {
+        let dir = tempdir().unwrap();
+        let storage = StorageEngine::open(dir.path()).unwrap();
+
+        for i in 0..5 {
+            storage.store_event(StoredEvent {
+                source_id: 1,
+                seq_no: i,
+                timestamp_ns: i * 1000,
+                data: vec![i as u8],
+            }).unwrap();
+        }
+
+        let root = storage.root_hash().unwrap();
+        let key = StorageEngine::make_key(1, 3);
+        let mut leaf_data = Vec::new();
+        leaf_data.extend_from_slice(&key);
+        leaf_data.extend_from_slice(&3000u64.to_be_bytes());
+        leaf_data.extend_from_slice(&[3u8]);
+        let leaf_hash = *blake3::hash(&leaf_data).as_bytes();
+
+        let proof = storage.inclusion_proof(1, 3).unwrap();
+        assert!(verify_proof(leaf_hash, &proof, root));
+
+        // A tampered leaf hash should not verify against the same proof/root
+        assert!(!verify_proof([0u8; 32], &proof, root));
+    }
+
+    #[test]
+    fn test_delete_tombstones_without_disturbing_other_proofs() {
+        let dir = tempdir().unwrap();
+        let storage = StorageEngine::open(dir.path()).unwrap();
+
+        for i in 0..4 {
+            storage.store_event(StoredEvent {
+                source_id: 1,
+                seq_no: i,
+                timestamp_ns: i,
+                data: vec![i as u8],
+            }).unwrap();
+        }
+
+        let proof_before = storage.inclusion_proof(1, 2).unwrap();
+        let key = StorageEngine::make_key(1, 2);
+        let mut leaf_data = Vec::new();
+        leaf_data.extend_from_slice(&key);
+        leaf_data.extend_from_slice(&2u64.to_be_bytes());
+        leaf_data.extend_from_slice(&[2u8]);
+        let leaf_hash = *blake3::hash(&leaf_data).as_bytes();
+
+        storage.delete_event(1, 0).unwrap();
+
+        let root_after = storage.root_hash().unwrap();
+        let proof_after = storage.inclusion_proof(1, 2).unwrap();
+        assert_eq!(proof_before.len(), proof_after.len());
+        assert!(verify_proof(leaf_hash, &proof_after, root_after));
+    }
+
+    #[test]
+    fn test_get_source_events_typed_decodes_per_schema() {
+        let dir = tempdir().unwrap();
+        let storage = StorageEngine::open(dir.path()).unwrap();
+
+        storage.store_event(StoredEvent {
+            source_id: 1,
+            seq_no: 0,
+            timestamp_ns: 0,
+            data: b"42".to_vec(),
+        }).unwrap();
+        storage.store_event(StoredEvent {
+            source_id: 1,
+            seq_no: 1,
+            timestamp_ns: 0,
+            data: b"7".to_vec(),
+        }).unwrap();
+
+        storage.set_schema(1, vec![Conversion::Integer, Conversion::Bytes]);
+
+        let rows = storage.get_source_events_typed(1).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], vec![TypedValue::Integer(42), TypedValue::Bytes(b"42".to_vec())]);
+        assert_eq!(rows[1], vec![TypedValue::Integer(7), TypedValue::Bytes(b"7".to_vec())]);
+    }
+
+    #[test]
+    fn test_get_source_events_typed_without_schema_errors() {
+        let dir = tempdir().unwrap();
+        let storage = StorageEngine::open(dir.path()).unwrap();
+        assert!(storage.get_source_events_typed(1).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_receives_only_matching_events() {
+        let dir = tempdir().unwrap();
+        let storage = StorageEngine::open(dir.path()).unwrap();
+
+        let mut subscriber = storage.subscribe("1/*");
+
+        storage.store_event(StoredEvent {
+            source_id: 2,
+            seq_no: 0,
+            timestamp_ns: 0,
+            data: vec![],
+        }).unwrap();
+        storage.store_event(StoredEvent {
+            source_id: 1,
+            seq_no: 0,
+            timestamp_ns: 0,
+            data: vec![9],
+        }).unwrap();
+
+        let received = subscriber.recv().await.unwrap();
+        assert_eq!(received.source_id, 1);
+        assert_eq!(received.data, vec![9]);
+    }
 }
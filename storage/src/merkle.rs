@@ -0,0 +1,80 @@
+/// Incremental Merkle tree backing `StorageEngine`'s verifiable event log.
+///
+/// Leaves are addressed by a monotonically increasing enumeration index
+/// assigned at insert time, not by their storage key, so the tree never
+/// needs reshaping: inserting leaf `i` only touches the `TREE_DEPTH`
+/// interior nodes on the path from that leaf to the root. Every other
+/// node is either already stored or deterministically "empty".
+
+/// Depth of the tree. Large enough to address every `u64` enumeration
+/// index without ever needing to grow.
+pub const TREE_DEPTH: u32 = 64;
+
+/// Hash of the canonical empty subtree at `level` (0 = leaf). Independent
+/// of what has actually been inserted, so a node whose sibling subtree
+/// is still unpopulated can use this without touching storage.
+pub fn empty_hash(level: u32) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    for _ in 0..level {
+        hash = combine(&hash, &hash);
+    }
+    hash
+}
+
+/// Hash standing in for a deleted leaf. Distinct from `empty_hash(0)` so
+/// "deleted" and "never inserted" can't be confused, and replacing a
+/// leaf with it (rather than unwinding its index) leaves every other
+/// leaf's proof valid.
+pub fn tombstone_hash() -> [u8; 32] {
+    *blake3::hash(b"zenith-storage-tombstone").as_bytes()
+}
+
+/// Interior node hash from its two children.
+pub fn combine(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+/// Verify an inclusion proof for `leaf_hash` against `root`. `proof` is
+/// read leaf-to-root; each step's `bool` is `true` when the node being
+/// folded up so far is the *left* child at that level.
+pub fn verify_proof(leaf_hash: [u8; 32], proof: &[(bool, [u8; 32])], root: [u8; 32]) -> bool {
+    let mut current = leaf_hash;
+    for (is_left, sibling) in proof {
+        current = if *is_left {
+            combine(&current, sibling)
+        } else {
+            combine(sibling, &current)
+        };
+    }
+    current == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_hash_is_deterministic_and_level_dependent() {
+        assert_eq!(empty_hash(0), [0u8; 32]);
+        assert_eq!(empty_hash(1), combine(&empty_hash(0), &empty_hash(0)));
+        assert_ne!(empty_hash(1), empty_hash(2));
+    }
+
+    #[test]
+    fn test_tombstone_hash_differs_from_empty_leaf() {
+        assert_ne!(tombstone_hash(), empty_hash(0));
+    }
+
+    #[test]
+    fn test_verify_proof_round_trips() {
+        let leaf = [7u8; 32];
+        let sibling = [9u8; 32];
+        let proof = vec![(true, sibling)];
+        let root = combine(&leaf, &sibling);
+        assert!(verify_proof(leaf, &proof, root));
+        assert!(!verify_proof(leaf, &proof, [0u8; 32]));
+    }
+}
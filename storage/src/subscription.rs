@@ -0,0 +1,136 @@
+/// Pub/sub layer so consumers are notified as new events are stored.
+///
+/// A `KeyExpr` matches against an event's `source_id/seq_no` key, with
+/// `*` as a per-segment wildcard (e.g. `"1/*"` matches every event for
+/// source 1, `"*/*"` matches everything). `StorageEngine::store_event`
+/// fans each newly stored event out to every `Subscriber` whose
+/// `KeyExpr` matches.
+use crate::StoredEvent;
+use tokio::sync::mpsc;
+
+/// How many unconsumed events a `Subscriber` buffers before new ones
+/// are silently dropped for it (the store itself never blocks on a
+/// slow subscriber).
+const SUBSCRIBE_CHANNEL_CAPACITY: usize = 128;
+
+/// A wildcard key expression over `source_id/seq_no`.
+#[derive(Debug, Clone)]
+pub struct KeyExpr(String);
+
+impl KeyExpr {
+    pub fn new(expr: impl Into<String>) -> Self {
+        Self(expr.into())
+    }
+
+    /// Whether `source_id/seq_no` matches this expression, `*` standing
+    /// in for any value at that segment.
+    pub fn matches(&self, source_id: u32, seq_no: u64) -> bool {
+        let key = format!("{}/{}", source_id, seq_no);
+        let mut pattern = self.0.split('/');
+        let mut value = key.split('/');
+        loop {
+            match (pattern.next(), value.next()) {
+                (Some(p), Some(v)) => {
+                    if p != "*" && p != v {
+                        return false;
+                    }
+                }
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// Registry entry: a filter plus the channel it feeds.
+pub(crate) type Registration = (KeyExpr, mpsc::Sender<StoredEvent>);
+
+/// Handle returned by `StorageEngine::subscribe`. Yields every
+/// subsequently stored event whose key matches this subscriber's
+/// `KeyExpr`.
+pub struct Subscriber {
+    receiver: mpsc::Receiver<StoredEvent>,
+}
+
+impl Subscriber {
+    pub(crate) fn new(receiver: mpsc::Receiver<StoredEvent>) -> Self {
+        Self { receiver }
+    }
+
+    /// Wait for the next matching event, or `None` once the
+    /// `StorageEngine` that created this subscriber is dropped.
+    pub async fn recv(&mut self) -> Option<StoredEvent> {
+        self.receiver.recv().await
+    }
+}
+
+/// Register `sender` under `key_expr` in `registry`, returning a
+/// `Subscriber` reading the other end.
+pub(crate) fn subscribe(registry: &std::sync::RwLock<Vec<Registration>>, key_expr: &str) -> Subscriber {
+    let (sender, receiver) = mpsc::channel(SUBSCRIBE_CHANNEL_CAPACITY);
+    registry.write().unwrap().push((KeyExpr::new(key_expr), sender));
+    Subscriber::new(receiver)
+}
+
+/// Fan `event` out to every registration in `registry` whose `KeyExpr`
+/// matches, pruning subscribers whose receiver has been dropped.
+pub(crate) fn publish(registry: &std::sync::RwLock<Vec<Registration>>, event: &StoredEvent) {
+    registry.write().unwrap().retain(|(key_expr, sender)| {
+        if key_expr.matches(event.source_id, event.seq_no) {
+            // A full channel means a slow subscriber; drop the event
+            // for it rather than blocking the store on one consumer.
+            !matches!(sender.try_send(event.clone()), Err(mpsc::error::TrySendError::Closed(_)))
+        } else {
+            !sender.is_closed()
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_expr_wildcards() {
+        assert!(KeyExpr::new("1/*").matches(1, 42));
+        assert!(!KeyExpr::new("1/*").matches(2, 42));
+        assert!(KeyExpr::new("*/*").matches(7, 7));
+        assert!(KeyExpr::new("1/5").matches(1, 5));
+        assert!(!KeyExpr::new("1/5").matches(1, 6));
+    }
+
+    #[tokio::test]
+    async fn test_publish_delivers_to_matching_subscriber_only() {
+        let registry: std::sync::RwLock<Vec<Registration>> = std::sync::RwLock::new(Vec::new());
+        let mut matching = subscribe(&registry, "1/*");
+        let mut other = subscribe(&registry, "2/*");
+
+        let event = StoredEvent {
+            source_id: 1,
+            seq_no: 0,
+            timestamp_ns: 0,
+            data: vec![1],
+        };
+        publish(&registry, &event);
+
+        assert_eq!(matching.recv().await.unwrap().source_id, 1);
+        assert!(other.receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_publish_prunes_dropped_subscribers() {
+        let registry: std::sync::RwLock<Vec<Registration>> = std::sync::RwLock::new(Vec::new());
+        let subscriber = subscribe(&registry, "*/*");
+        drop(subscriber);
+
+        let event = StoredEvent {
+            source_id: 1,
+            seq_no: 0,
+            timestamp_ns: 0,
+            data: vec![],
+        };
+        publish(&registry, &event);
+
+        assert!(registry.read().unwrap().is_empty());
+    }
+}
@@ -40,6 +40,27 @@ pub enum Commands {
         #[arg(short, long, default_value = "benchmark_results.json")]
         output: String,
     },
+    /// Run multi-GPU collective benchmarks (all_reduce/all_gather/broadcast)
+    Collective {
+        /// Collective operation to benchmark (all_reduce, all_gather, broadcast)
+        #[arg(short, long, default_value = "all_reduce")]
+        op: String,
+        /// Smallest message size to sweep, in bytes
+        #[arg(long, default_value = "4096")]
+        min_size: usize,
+        /// Largest message size to sweep, in bytes
+        #[arg(long, default_value = "67108864")]
+        max_size: usize,
+        /// Timed iterations per message size
+        #[arg(short, long, default_value = "100")]
+        iterations: usize,
+        /// Untimed warmup iterations run before timing, to absorb cold-start effects
+        #[arg(short, long, default_value = "10")]
+        warmup_iterations: usize,
+        /// Output file for the sweep results
+        #[arg(long, default_value = "collective_benchmark_results.json")]
+        output: String,
+    },
 }
 
 /// Benchmark result
@@ -55,6 +76,10 @@ pub struct BenchmarkResult {
     pub p95_time_us: f64,
     pub p99_time_us: f64,
     pub throughput_ops_sec: f64,
+    /// Busbar-style algorithm bandwidth, in GB/s, for collective-operation
+    /// benchmarks. `None` for benchmarks that don't move data between
+    /// ranks (e.g. [`Commands::Cpu`], [`Commands::RingBuffer`]).
+    pub bandwidth_gb_sec: Option<f64>,
 }
 
 impl BenchmarkResult {
@@ -89,9 +114,25 @@ impl BenchmarkResult {
             p95_time_us: p95,
             p99_time_us: p99,
             throughput_ops_sec: throughput,
+            bandwidth_gb_sec: None,
         }
     }
-    
+
+    /// Attach a busbar-style algorithm-bandwidth figure, computed from the
+    /// message size in bytes and the number of ranks participating in the
+    /// collective. For a ring all-reduce, each rank moves
+    /// `2(N-1)/N` times the message size over the course of the
+    /// reduce-scatter + all-gather, so that factor (rather than the raw
+    /// message size) is what should be compared against link bandwidth.
+    pub fn with_algo_bandwidth(mut self, message_size_bytes: usize, num_ranks: usize) -> Self {
+        let n = num_ranks.max(1) as f64;
+        let algo_bytes = message_size_bytes as f64 * 2.0 * (n - 1.0) / n;
+        let total_secs = self.total_time_ms / 1000.0;
+        let per_iter_secs = total_secs / self.iterations.max(1) as f64;
+        self.bandwidth_gb_sec = Some(algo_bytes / per_iter_secs / 1e9);
+        self
+    }
+
     /// Print result
     pub fn print(&self) {
         println!("\n📊 {} Benchmark Results:", self.name);
@@ -104,5 +145,8 @@ impl BenchmarkResult {
         println!("  P95 latency:    {:>12.2} µs", self.p95_time_us);
         println!("  P99 latency:    {:>12.2} µs", self.p99_time_us);
         println!("  Throughput:     {:>12.0} ops/sec", self.throughput_ops_sec);
+        if let Some(bandwidth) = self.bandwidth_gb_sec {
+            println!("  Algo bandwidth: {:>12.2} GB/s", bandwidth);
+        }
     }
 }
@@ -24,6 +24,13 @@ fn main() -> anyhow::Result<()> {
  Commands::RingBuffer { size } => {
  run_ringbuffer_benchmarks(size)?;
  }
+ Commands::Collective { op, min_size, max_size, iterations, warmup_iterations, output } => {
+ let results = run_collective_benchmarks(&op, min_size, max_size, iterations, warmup_iterations)?;
+
+ let json = serde_json::to_string_pretty(&results)?;
+ std::fs::write(&output, &json)?;
+ println!("\n Results saved to: {}", output);
+ }
  Commands::Full { output } => {
  let results = run_full_suite()?;
  
@@ -98,6 +105,69 @@ fn run_ringbuffer_benchmarks(size: usize) -> anyhow::Result<()> {
  Ok(())
 }
 
+fn run_collective_benchmarks(
+ op: &str,
+ min_size: usize,
+ max_size: usize,
+ iterations: usize,
+ warmup_iterations: usize,
+) -> anyhow::Result<Vec<BenchmarkResult>> {
+ use zenith_runtime_gpu::multigpu::{MultiGpuComm, MultiGpuStrategy};
+
+ println!("\n Running Multi-GPU Collective Benchmarks ({})...\n", op);
+
+ let comm = MultiGpuComm::new(MultiGpuStrategy::DataParallel)?;
+ let num_ranks = comm.num_gpus().max(1) as usize;
+
+ let mut results = Vec::new();
+ let mut size = min_size.max(std::mem::size_of::<f32>());
+ while size <= max_size {
+ let num_elements = (size / std::mem::size_of::<f32>()).max(1);
+ let mut data = vec![1.0f32; num_elements];
+
+ for _ in 0..warmup_iterations {
+ run_one_collective(&comm, op, &mut data)?;
+ }
+
+ let mut timings = Vec::with_capacity(iterations);
+ for _ in 0..iterations {
+ let start = Instant::now();
+ run_one_collective(&comm, op, &mut data)?;
+ timings.push(start.elapsed());
+ }
+
+ let name = format!("{} ({} bytes)", op, size);
+ let result = BenchmarkResult::from_timings(&name, &mut timings)
+ .with_algo_bandwidth(size, num_ranks);
+ result.print();
+ results.push(result);
+
+ size *= 2;
+ }
+
+ Ok(results)
+}
+
+fn run_one_collective(
+ comm: &zenith_runtime_gpu::multigpu::MultiGpuComm,
+ op: &str,
+ data: &mut Vec<f32>,
+) -> anyhow::Result<()> {
+ use zenith_runtime_gpu::multigpu::ReductionOp;
+
+ match op {
+ "all_reduce" => comm.all_reduce(data, ReductionOp::Sum)?,
+ "all_gather" => {
+ let send = data.clone();
+ comm.all_gather(&send, data)?;
+ }
+ "broadcast" => comm.broadcast(data, 0)?,
+ other => anyhow::bail!("unknown collective op: {} (expected all_reduce, all_gather, or broadcast)", other),
+ }
+
+ Ok(())
+}
+
 fn run_full_suite() -> anyhow::Result<Vec<BenchmarkResult>> {
  println!("\n Running Full Benchmark Suite...\n");
  
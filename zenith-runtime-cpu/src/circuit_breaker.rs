@@ -17,26 +17,142 @@ pub enum CircuitState {
     HalfOpen,
 }
 
+/// Decides when a [`CircuitBreaker`] in the `Closed` state should trip to `Open`.
+#[derive(Debug, Clone)]
+pub enum TrippingPolicy {
+    /// Open after `threshold` *consecutive* failures; a single success resets
+    /// the count to zero. Cheap, but sporadic failures interleaved with
+    /// occasional successes never trip the breaker even when a backend is
+    /// clearly degraded.
+    ConsecutiveFailures {
+        /// Number of consecutive failures before opening the circuit
+        threshold: u32,
+    },
+    /// Open when, across the trailing `window`, at least `min_requests` calls
+    /// were recorded and the failure ratio is at or above `error_rate`. This
+    /// matches the windowed error-counting approach used to prevent retry
+    /// storms against an overloaded node: sporadic failures accumulate in the
+    /// window instead of being wiped out by the next success.
+    RollingWindow {
+        /// Length of the trailing window considered on each decision
+        window: Duration,
+        /// Minimum number of calls observed in the window before the error
+        /// rate is trusted (avoids tripping on e.g. 1 failure out of 1 call)
+        min_requests: u32,
+        /// Failure ratio (0.0-1.0) at or above which the circuit opens
+        error_rate: f64,
+    },
+}
+
 /// Circuit breaker configuration
 #[derive(Debug, Clone)]
 pub struct CircuitBreakerConfig {
-    /// Number of failures before opening circuit
-    pub failure_threshold: u32,
+    /// Policy deciding when the circuit trips from `Closed` to `Open`
+    pub tripping_policy: TrippingPolicy,
     /// Duration to wait before trying half-open
     pub reset_timeout: Duration,
     /// Number of successes in half-open before closing
     pub success_threshold: u32,
     /// Timeout for individual calls
     pub call_timeout: Duration,
+    /// Maximum number of trial calls let through while `HalfOpen`, bounding
+    /// the probe so an Open->HalfOpen transition doesn't let an unbounded
+    /// flood of requests through before the first one resolves
+    pub half_open_max_calls: u32,
+    /// Upper bound on the backed-off reset timeout (see
+    /// [`CircuitBreaker::effective_reset_timeout`]); a persistently failing
+    /// backend is re-probed at most this rarely, however many times the
+    /// circuit has re-opened in a row.
+    pub max_reset_timeout: Duration,
 }
 
 impl Default for CircuitBreakerConfig {
     fn default() -> Self {
         Self {
-            failure_threshold: 5,
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 5 },
             reset_timeout: Duration::from_secs(30),
             success_threshold: 3,
             call_timeout: Duration::from_secs(10),
+            half_open_max_calls: 1,
+            max_reset_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// One bucket of a [`RollingWindowCounters`] ring, covering a `bucket_width`
+/// slice of time identified by `generation` (the bucket's index since the
+/// counters were created, counting up forever; `generation % bucket count`
+/// picks the slot). A slot whose stored `generation` doesn't match the
+/// generation currently mapping to it holds stale counts from a previous lap
+/// around the ring and is lazily zeroed on the next record into that slot.
+#[derive(Debug, Default, Clone, Copy)]
+struct Bucket {
+    generation: u64,
+    failures: u64,
+    total: u64,
+}
+
+/// Ring of per-bucket failure/total counters covering the last `window` of
+/// calls, used by [`TrippingPolicy::RollingWindow`]. Buckets are addressed by
+/// wall-clock time since creation rather than an explicit advancing cursor,
+/// so a burst of calls after a quiet period zeroes exactly the buckets that
+/// have gone stale rather than needing a background sweep.
+#[derive(Debug)]
+struct RollingWindowCounters {
+    created_at: Instant,
+    bucket_width: Duration,
+    buckets: RwLock<Vec<Bucket>>,
+}
+
+impl RollingWindowCounters {
+    const BUCKET_COUNT: usize = 10;
+
+    fn new(window: Duration) -> Self {
+        let bucket_width = window / Self::BUCKET_COUNT as u32;
+        Self {
+            created_at: Instant::now(),
+            bucket_width: bucket_width.max(Duration::from_nanos(1)),
+            buckets: RwLock::new(vec![Bucket::default(); Self::BUCKET_COUNT]),
+        }
+    }
+
+    fn current_generation(&self) -> u64 {
+        (self.created_at.elapsed().as_nanos() / self.bucket_width.as_nanos()) as u64
+    }
+
+    /// Record a call outcome in the bucket for "now", zeroing it first if it
+    /// still holds counts from a previous lap around the ring.
+    fn record(&self, is_failure: bool) {
+        let generation = self.current_generation();
+        let mut buckets = self.buckets.write();
+        let idx = (generation as usize) % buckets.len();
+        let bucket = &mut buckets[idx];
+        if bucket.generation != generation {
+            *bucket = Bucket { generation, failures: 0, total: 0 };
+        }
+        bucket.total += 1;
+        if is_failure {
+            bucket.failures += 1;
+        }
+    }
+
+    /// Sum failures/total across every bucket still within the trailing window.
+    fn snapshot(&self) -> (u64, u64) {
+        let generation = self.current_generation();
+        let buckets = self.buckets.read();
+        let bucket_count = buckets.len() as u64;
+        buckets
+            .iter()
+            .filter(|b| generation.saturating_sub(b.generation) < bucket_count)
+            .fold((0u64, 0u64), |(failures, total), b| {
+                (failures + b.failures, total + b.total)
+            })
+    }
+
+    fn reset(&self) {
+        let mut buckets = self.buckets.write();
+        for bucket in buckets.iter_mut() {
+            *bucket = Bucket::default();
         }
     }
 }
@@ -52,11 +168,24 @@ pub struct CircuitBreaker {
     total_failures: AtomicU64,
     total_successes: AtomicU64,
     total_rejections: AtomicU64,
+    /// Populated only when `config.tripping_policy` is `RollingWindow`.
+    rolling: Option<RollingWindowCounters>,
+    /// Trial-call budget remaining in the current `HalfOpen` window; reseeded
+    /// from `config.half_open_max_calls` on every Open->HalfOpen transition.
+    half_open_permits: AtomicU32,
+    /// Number of consecutive times a `HalfOpen` probe has failed and sent the
+    /// circuit back to `Open`, driving [`Self::effective_reset_timeout`]'s
+    /// exponential backoff. Reset to 0 once the circuit successfully closes.
+    open_cycles: AtomicU32,
 }
 
 impl CircuitBreaker {
     /// Create a new circuit breaker
     pub fn new(config: CircuitBreakerConfig) -> Self {
+        let rolling = match &config.tripping_policy {
+            TrippingPolicy::RollingWindow { window, .. } => Some(RollingWindowCounters::new(*window)),
+            TrippingPolicy::ConsecutiveFailures { .. } => None,
+        };
         Self {
             config,
             state: RwLock::new(CircuitState::Closed),
@@ -67,28 +196,81 @@ impl CircuitBreaker {
             total_failures: AtomicU64::new(0),
             total_successes: AtomicU64::new(0),
             total_rejections: AtomicU64::new(0),
+            rolling,
+            half_open_permits: AtomicU32::new(0),
+            open_cycles: AtomicU32::new(0),
         }
     }
-    
+
     /// Get current state
     pub fn state(&self) -> CircuitState {
         *self.state.read()
     }
-    
+
+    /// Atomically consume one `HalfOpen` trial-call permit, returning
+    /// whether one was available. Safe under concurrency: callers racing for
+    /// the last permit never both observe success.
+    fn try_consume_half_open_permit(&self) -> bool {
+        loop {
+            let current = self.half_open_permits.load(Ordering::SeqCst);
+            if current == 0 {
+                return false;
+            }
+            if self
+                .half_open_permits
+                .compare_exchange(current, current - 1, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                return true;
+            }
+        }
+    }
+
+    /// The reset timeout to apply before the next half-open probe,
+    /// `reset_timeout * 2^open_cycles` capped at `max_reset_timeout`: each
+    /// consecutive failed probe doubles the wait, so a persistently failing
+    /// backend is re-probed less and less often instead of being hammered at
+    /// a fixed cadence.
+    fn effective_reset_timeout(&self) -> Duration {
+        let cycles = self.open_cycles.load(Ordering::SeqCst);
+        let factor = 1u32.checked_shl(cycles.min(31)).unwrap_or(u32::MAX);
+        self.config
+            .reset_timeout
+            .checked_mul(factor)
+            .unwrap_or(self.config.max_reset_timeout)
+            .min(self.config.max_reset_timeout)
+    }
+
     /// Check if calls are allowed
     pub fn is_allowed(&self) -> bool {
         let state = *self.state.read();
-        
+
         match state {
             CircuitState::Closed => true,
-            CircuitState::HalfOpen => true, // Allow limited calls
+            CircuitState::HalfOpen => self.try_consume_half_open_permit(),
             CircuitState::Open => {
                 // Check if we should try half-open
                 if let Some(last_failure) = *self.last_failure_time.read() {
-                    if last_failure.elapsed() >= self.config.reset_timeout {
-                        *self.state.write() = CircuitState::HalfOpen;
-                        self.success_count.store(0, Ordering::SeqCst);
-                        return true;
+                    if last_failure.elapsed() >= self.effective_reset_timeout() {
+                        // Gate the re-arm on the state write lock itself, so
+                        // the Open->HalfOpen transition only ever reseeds
+                        // `half_open_permits` once: several threads can pass
+                        // the `elapsed()` check above while still `Open`,
+                        // but only the one that actually observes `Open`
+                        // here (the rest queue behind the write lock and
+                        // then see `HalfOpen`) does the reseed. Everyone
+                        // else just competes for the budget it set.
+                        let mut state = self.state.write();
+                        if *state == CircuitState::Open {
+                            *state = CircuitState::HalfOpen;
+                            drop(state);
+                            self.success_count.store(0, Ordering::SeqCst);
+                            self.half_open_permits
+                                .store(self.config.half_open_max_calls, Ordering::SeqCst);
+                        } else {
+                            drop(state);
+                        }
+                        return self.try_consume_half_open_permit();
                     }
                 }
                 false
@@ -96,18 +278,28 @@ impl CircuitBreaker {
         }
     }
     
+    /// Shared preamble for [`Self::call`]/[`Self::call_async`]/the tower
+    /// [`crate::circuit_breaker_layer::CircuitBreakerService`]: records the
+    /// attempt and, if the breaker doesn't currently allow calls through,
+    /// also records the rejection. Returns whether the caller may proceed.
+    pub(crate) fn record_call_attempt(&self) -> bool {
+        self.total_calls.fetch_add(1, Ordering::Relaxed);
+        if !self.is_allowed() {
+            self.total_rejections.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        true
+    }
+
     /// Execute a function through the circuit breaker
     pub fn call<F, T, E>(&self, f: F) -> Result<T, CircuitBreakerError<E>>
     where
         F: FnOnce() -> Result<T, E>,
     {
-        self.total_calls.fetch_add(1, Ordering::Relaxed);
-        
-        if !self.is_allowed() {
-            self.total_rejections.fetch_add(1, Ordering::Relaxed);
+        if !self.record_call_attempt() {
             return Err(CircuitBreakerError::CircuitOpen);
         }
-        
+
         match f() {
             Ok(result) => {
                 self.on_success();
@@ -119,7 +311,35 @@ impl CircuitBreaker {
             }
         }
     }
-    
+
+    /// Execute an async function through the circuit breaker, racing it
+    /// against `config.call_timeout`. A timeout counts as a failure against
+    /// the breaker's tripping policy, the same as a call returning `Err`.
+    pub async fn call_async<F, Fut, T, E>(&self, f: F) -> Result<T, CircuitBreakerError<E>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
+    {
+        if !self.record_call_attempt() {
+            return Err(CircuitBreakerError::CircuitOpen);
+        }
+
+        match tokio::time::timeout(self.config.call_timeout, f()).await {
+            Ok(Ok(result)) => {
+                self.on_success();
+                Ok(result)
+            }
+            Ok(Err(e)) => {
+                self.on_failure();
+                Err(CircuitBreakerError::CallFailed(e))
+            }
+            Err(_elapsed) => {
+                self.on_failure();
+                Err(CircuitBreakerError::Timeout)
+            }
+        }
+    }
+
     /// Record a success
     pub fn on_success(&self) {
         self.total_successes.fetch_add(1, Ordering::Relaxed);
@@ -127,10 +347,21 @@ impl CircuitBreaker {
         let state = *self.state.read();
         
         match state {
-            CircuitState::Closed => {
-                // Reset failure count on success
-                self.failure_count.store(0, Ordering::SeqCst);
-            }
+            CircuitState::Closed => match &self.config.tripping_policy {
+                TrippingPolicy::ConsecutiveFailures { .. } => {
+                    // Reset failure count on success
+                    self.failure_count.store(0, Ordering::SeqCst);
+                }
+                TrippingPolicy::RollingWindow { .. } => {
+                    // A success only records into the window; it must not
+                    // wipe out failures from elsewhere in the window, or a
+                    // sporadically-failing backend would never trip.
+                    self.rolling
+                        .as_ref()
+                        .expect("rolling counters present for RollingWindow policy")
+                        .record(false);
+                }
+            },
             CircuitState::HalfOpen => {
                 let count = self.success_count.fetch_add(1, Ordering::SeqCst) + 1;
                 if count >= self.config.success_threshold {
@@ -138,6 +369,7 @@ impl CircuitBreaker {
                     *self.state.write() = CircuitState::Closed;
                     self.failure_count.store(0, Ordering::SeqCst);
                     self.success_count.store(0, Ordering::SeqCst);
+                    self.open_cycles.store(0, Ordering::SeqCst);
                 }
             }
             CircuitState::Open => {}
@@ -153,42 +385,119 @@ impl CircuitBreaker {
         
         match state {
             CircuitState::Closed => {
-                let count = self.failure_count.fetch_add(1, Ordering::SeqCst) + 1;
-                if count >= self.config.failure_threshold {
+                let should_open = match &self.config.tripping_policy {
+                    TrippingPolicy::ConsecutiveFailures { threshold } => {
+                        let count = self.failure_count.fetch_add(1, Ordering::SeqCst) + 1;
+                        count >= *threshold
+                    }
+                    TrippingPolicy::RollingWindow { min_requests, error_rate, .. } => {
+                        let rolling = self
+                            .rolling
+                            .as_ref()
+                            .expect("rolling counters present for RollingWindow policy");
+                        rolling.record(true);
+                        let (failures, total) = rolling.snapshot();
+                        total >= *min_requests as u64
+                            && (failures as f64 / total as f64) >= *error_rate
+                    }
+                };
+                if should_open {
                     // Too many failures, open the circuit
                     *self.state.write() = CircuitState::Open;
                 }
             }
             CircuitState::HalfOpen => {
-                // Failure in half-open, go back to open
+                // Failure in half-open, go back to open: another probe has
+                // failed, so the next reset timeout backs off further.
                 *self.state.write() = CircuitState::Open;
                 self.success_count.store(0, Ordering::SeqCst);
+                self.open_cycles.fetch_add(1, Ordering::SeqCst);
             }
             CircuitState::Open => {}
         }
     }
-    
+
     /// Force reset the circuit breaker
     pub fn reset(&self) {
         *self.state.write() = CircuitState::Closed;
         self.failure_count.store(0, Ordering::SeqCst);
         self.success_count.store(0, Ordering::SeqCst);
         *self.last_failure_time.write() = None;
+        self.open_cycles.store(0, Ordering::SeqCst);
+        if let Some(rolling) = &self.rolling {
+            rolling.reset();
+        }
     }
-    
+
     /// Get statistics
     pub fn stats(&self) -> CircuitBreakerStats {
+        // Under ConsecutiveFailures this is the live consecutive-failure
+        // count; under RollingWindow it's the failure count currently live
+        // in the window (saturating, since the window total is a u64).
+        let current_failure_count = match &self.rolling {
+            Some(rolling) => rolling.snapshot().0.min(u32::MAX as u64) as u32,
+            None => self.failure_count.load(Ordering::Relaxed),
+        };
         CircuitBreakerStats {
             state: *self.state.read(),
             total_calls: self.total_calls.load(Ordering::Relaxed),
             total_successes: self.total_successes.load(Ordering::Relaxed),
             total_failures: self.total_failures.load(Ordering::Relaxed),
             total_rejections: self.total_rejections.load(Ordering::Relaxed),
-            current_failure_count: self.failure_count.load(Ordering::Relaxed),
+            current_failure_count,
         }
     }
 }
 
+/// Registry of [`CircuitBreaker`]s keyed by an arbitrary per-target id (e.g.
+/// a backend's address or route name), so a single failing upstream doesn't
+/// trip the breaker for every other target behind the same dataplane. Every
+/// breaker it creates shares the same `config`; callers wanting different
+/// policies per target should keep separate registries.
+pub struct CircuitBreakerRegistry<K> {
+    config: CircuitBreakerConfig,
+    breakers: RwLock<std::collections::HashMap<K, std::sync::Arc<CircuitBreaker>>>,
+}
+
+impl<K: Eq + std::hash::Hash + Clone> CircuitBreakerRegistry<K> {
+    /// Create an empty registry; breakers are created lazily on first use.
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            breakers: RwLock::new(std::collections::HashMap::new()),
+        }
+    }
+
+    /// Get the breaker for `key`, creating one from the shared config the
+    /// first time this key is seen.
+    pub fn get_or_create(&self, key: K) -> std::sync::Arc<CircuitBreaker> {
+        if let Some(breaker) = self.breakers.read().get(&key) {
+            return breaker.clone();
+        }
+        self.breakers
+            .write()
+            .entry(key)
+            .or_insert_with(|| std::sync::Arc::new(CircuitBreaker::new(self.config.clone())))
+            .clone()
+    }
+
+    /// Current state of `key`'s breaker, or `None` if no call has ever been
+    /// made against that key.
+    pub fn state(&self, key: &K) -> Option<CircuitState> {
+        self.breakers.read().get(key).map(|b| b.state())
+    }
+
+    /// Stats for every target the registry has created a breaker for, for
+    /// scraping into metrics. Order is unspecified.
+    pub fn snapshot(&self) -> Vec<(K, CircuitBreakerStats)> {
+        self.breakers
+            .read()
+            .iter()
+            .map(|(key, breaker)| (key.clone(), breaker.stats()))
+            .collect()
+    }
+}
+
 /// Circuit breaker error
 #[derive(Debug)]
 pub enum CircuitBreakerError<E> {
@@ -196,6 +505,8 @@ pub enum CircuitBreakerError<E> {
     CircuitOpen,
     /// Call failed with underlying error
     CallFailed(E),
+    /// Call exceeded `CircuitBreakerConfig::call_timeout` (`call_async` only)
+    Timeout,
 }
 
 impl<E: std::fmt::Display> std::fmt::Display for CircuitBreakerError<E> {
@@ -203,6 +514,7 @@ impl<E: std::fmt::Display> std::fmt::Display for CircuitBreakerError<E> {
         match self {
             Self::CircuitOpen => write!(f, "Circuit breaker is open"),
             Self::CallFailed(e) => write!(f, "Call failed: {}", e),
+            Self::Timeout => write!(f, "Call timed out"),
         }
     }
 }
@@ -212,6 +524,7 @@ impl<E: std::error::Error + 'static> std::error::Error for CircuitBreakerError<E
         match self {
             Self::CircuitOpen => None,
             Self::CallFailed(e) => Some(e),
+            Self::Timeout => None,
         }
     }
 }
@@ -242,7 +555,7 @@ mod tests {
     #[test]
     fn test_circuit_breaker_normal() {
         let cb = CircuitBreaker::new(CircuitBreakerConfig {
-            failure_threshold: 3,
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 3 },
             ..Default::default()
         });
         
@@ -255,7 +568,7 @@ mod tests {
     #[test]
     fn test_circuit_breaker_opens() {
         let cb = CircuitBreaker::new(CircuitBreakerConfig {
-            failure_threshold: 3,
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 3 },
             ..Default::default()
         });
         
@@ -319,7 +632,7 @@ mod tests {
     #[test]
     fn test_on_success_arithmetic_boundary() {
         let cb = CircuitBreaker::new(CircuitBreakerConfig {
-            failure_threshold: 1,
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 1 },
             success_threshold: 3,
             reset_timeout: Duration::from_millis(1),
             ..Default::default()
@@ -357,7 +670,7 @@ mod tests {
     #[test]
     fn test_on_failure_arithmetic_boundary() {
         let cb = CircuitBreaker::new(CircuitBreakerConfig {
-            failure_threshold: 3,
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 3 },
             ..Default::default()
         });
         
@@ -432,7 +745,7 @@ mod tests {
     #[test]
     fn test_half_open_success_threshold_exact() {
         let cb = CircuitBreaker::new(CircuitBreakerConfig {
-            failure_threshold: 1,
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 1 },
             success_threshold: 2,  // Exactly 2 successes needed
             reset_timeout: Duration::from_millis(1),
             ..Default::default()
@@ -459,7 +772,7 @@ mod tests {
     #[test]
     fn test_stats_accuracy() {
         let cb = CircuitBreaker::new(CircuitBreakerConfig {
-            failure_threshold: 5,
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 5 },
             ..Default::default()
         });
         
@@ -480,4 +793,492 @@ mod tests {
         assert_eq!(stats.current_failure_count, 1, "Current failure count should be 1 (reset by on_success)");
         assert_eq!(stats.total_rejections, 0, "No rejections yet");
     }
+
+    // ========================================================================
+    // ROLLING WINDOW TRIPPING POLICY TESTS
+    // ========================================================================
+
+    /// A success interleaved between failures must not reset progress toward
+    /// tripping the breaker, unlike ConsecutiveFailures.
+    /// Kills mutation: on_success resetting the rolling window instead of recording into it
+    #[test]
+    fn test_rolling_window_survives_interleaved_successes() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::RollingWindow {
+                window: Duration::from_secs(60),
+                min_requests: 4,
+                error_rate: 0.5,
+            },
+            ..Default::default()
+        });
+
+        // 2 failures, 1 success, 1 failure: 3/4 failures >= 0.5 error rate
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Closed);
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Closed);
+        cb.on_success();
+        assert_eq!(cb.state(), CircuitState::Closed,
+            "a lone success must not wipe out the rolling window's failure history");
+        cb.on_failure();
+
+        assert_eq!(cb.state(), CircuitState::Open,
+            "3 failures out of 4 calls must trip a 0.5 error-rate policy");
+    }
+
+    /// Below min_requests, the circuit must stay closed even at 100% failures.
+    /// Kills mutation: min_requests check dropped or inverted
+    #[test]
+    fn test_rolling_window_respects_min_requests() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::RollingWindow {
+                window: Duration::from_secs(60),
+                min_requests: 5,
+                error_rate: 0.1,
+            },
+            ..Default::default()
+        });
+
+        for _ in 0..4 {
+            cb.on_failure();
+        }
+        assert_eq!(cb.state(), CircuitState::Closed,
+            "4 failures must not trip a policy requiring min_requests=5");
+
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Open,
+            "the 5th failure reaches min_requests and trips the breaker");
+    }
+
+    /// A failure ratio below error_rate must not trip the breaker even once
+    /// min_requests is satisfied.
+    /// Kills mutation: error_rate comparison dropped or using total instead of failures
+    #[test]
+    fn test_rolling_window_respects_error_rate() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::RollingWindow {
+                window: Duration::from_secs(60),
+                min_requests: 4,
+                error_rate: 0.75,
+            },
+            ..Default::default()
+        });
+
+        // 1 failure out of 4 calls is a 0.25 error rate, below the 0.75 threshold
+        cb.on_failure();
+        cb.on_success();
+        cb.on_success();
+        cb.on_success();
+        assert_eq!(cb.state(), CircuitState::Closed,
+            "a 0.25 error rate must not trip a 0.75 error-rate threshold");
+    }
+
+    /// Calls older than the window must be excluded from the failure ratio.
+    /// Kills mutation: snapshot() not filtering stale generations
+    #[test]
+    fn test_rolling_window_excludes_calls_outside_window() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::RollingWindow {
+                window: Duration::from_millis(50),
+                min_requests: 2,
+                error_rate: 0.5,
+            },
+            ..Default::default()
+        });
+
+        cb.on_failure();
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Open, "2/2 failures must trip immediately");
+
+        cb.reset();
+        assert_eq!(cb.state(), CircuitState::Closed);
+
+        // reset() cleared the window, so record one more failure and let its
+        // bucket fall outside the window. Counted, it would make the calls
+        // below 2 failures out of 4 (0.5, trips); excluded, they are 1 out
+        // of 3 (below 0.5), so the breaker stays Closed.
+        cb.on_failure();
+        std::thread::sleep(Duration::from_millis(80));
+        cb.on_success();
+        cb.on_success();
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Closed,
+            "a stale failure from before the window must not count toward the ratio");
+    }
+
+    /// current_failure_count in stats() must reflect the live windowed
+    /// failure count for RollingWindow, not the unused consecutive counter.
+    #[test]
+    fn test_rolling_window_stats_reports_windowed_failures() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::RollingWindow {
+                window: Duration::from_secs(60),
+                min_requests: 100,
+                error_rate: 0.9,
+            },
+            ..Default::default()
+        });
+
+        cb.on_failure();
+        cb.on_failure();
+        cb.on_success();
+
+        assert_eq!(cb.stats().current_failure_count, 2,
+            "stats() must report the failures currently live in the rolling window");
+    }
+
+    // ========================================================================
+    // CALL_ASYNC / CALL_TIMEOUT TESTS
+    // ========================================================================
+
+    /// A call_async that completes within call_timeout must succeed normally.
+    #[tokio::test]
+    async fn test_call_async_success() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig::default());
+
+        let result = cb.call_async(|| async { Ok::<i32, &str>(42) }).await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(cb.stats().total_successes, 1);
+    }
+
+    /// A call_async whose future exceeds call_timeout must return
+    /// CircuitBreakerError::Timeout, not CallFailed.
+    /// Kills mutation: timeout branch mapped to CallFailed, or tokio::time::timeout omitted
+    #[tokio::test]
+    async fn test_call_async_times_out() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            call_timeout: Duration::from_millis(10),
+            ..Default::default()
+        });
+
+        let result = cb
+            .call_async(|| async {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                Ok::<i32, &str>(42)
+            })
+            .await;
+
+        assert!(matches!(result, Err(CircuitBreakerError::Timeout)),
+            "a call exceeding call_timeout must fail with Timeout, got {:?}", result);
+    }
+
+    /// A timeout must be recorded as a failure against the tripping policy,
+    /// same as a returned Err.
+    /// Kills mutation: timeout branch not calling on_failure
+    #[tokio::test]
+    async fn test_call_async_timeout_counts_as_failure() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 2 },
+            call_timeout: Duration::from_millis(10),
+            ..Default::default()
+        });
+
+        for _ in 0..2 {
+            let _ = cb
+                .call_async(|| async {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    Ok::<i32, &str>(42)
+                })
+                .await;
+        }
+
+        assert_eq!(cb.state(), CircuitState::Open,
+            "2 consecutive timeouts must trip a threshold=2 ConsecutiveFailures policy");
+        assert_eq!(cb.stats().total_failures, 2);
+    }
+
+    /// call_async must reject calls while the circuit is open, without
+    /// invoking the inner future at all.
+    #[tokio::test]
+    async fn test_call_async_rejected_when_open() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 1 },
+            ..Default::default()
+        });
+
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        let result = cb.call_async(|| async { Ok::<i32, &str>(1) }).await;
+        assert!(matches!(result, Err(CircuitBreakerError::CircuitOpen)));
+        assert_eq!(cb.stats().total_rejections, 1);
+    }
+
+    /// Display for Timeout must be non-empty and mention timing out.
+    #[test]
+    fn test_timeout_error_display() {
+        let err: CircuitBreakerError<TestError> = CircuitBreakerError::Timeout;
+        let display = format!("{}", err);
+        assert!(!display.is_empty());
+        assert!(display.to_lowercase().contains("timed out") || display.to_lowercase().contains("timeout"));
+    }
+
+    // ========================================================================
+    // HALF-OPEN TRIAL-CALL BUDGET TESTS
+    // ========================================================================
+
+    /// Transitioning into HalfOpen must grant exactly half_open_max_calls
+    /// permits; calls beyond that budget must be rejected even though the
+    /// state is still HalfOpen.
+    /// Kills mutation: HalfOpen arm of is_allowed returning true unconditionally
+    #[test]
+    fn test_half_open_bounds_trial_calls() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 1 },
+            reset_timeout: Duration::from_millis(1),
+            half_open_max_calls: 2,
+            ..Default::default()
+        });
+
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+        std::thread::sleep(Duration::from_millis(10));
+
+        // First call transitions Open -> HalfOpen and consumes 1 of 2 permits.
+        assert!(cb.is_allowed(), "first trial call after reset_timeout must be allowed");
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+
+        // Second call consumes the last permit.
+        assert!(cb.is_allowed(), "second trial call must be allowed (half_open_max_calls=2)");
+
+        // Third call must be rejected: the trial budget is exhausted even
+        // though no success/failure has resolved the probe yet.
+        assert!(!cb.is_allowed(),
+            "a third call beyond half_open_max_calls=2 must be rejected while still HalfOpen");
+    }
+
+    /// A single-permit half-open (the default) must let exactly one call
+    /// through and reject everything else until the probe resolves.
+    #[test]
+    fn test_half_open_default_allows_single_trial_call() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 1 },
+            reset_timeout: Duration::from_millis(1),
+            ..Default::default()
+        });
+
+        cb.on_failure();
+        std::thread::sleep(Duration::from_millis(10));
+
+        assert!(cb.is_allowed());
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+        assert!(!cb.is_allowed(),
+            "default half_open_max_calls=1 must reject a second concurrent trial call");
+    }
+
+    /// Each new Open -> HalfOpen transition must replenish the permit budget
+    /// from scratch (a failed probe going back to Open must not leave the
+    /// next probe starved).
+    #[test]
+    fn test_half_open_permits_replenish_on_each_transition() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 1 },
+            reset_timeout: Duration::from_millis(1),
+            ..Default::default()
+        });
+
+        cb.on_failure();
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(cb.is_allowed());
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+
+        // Probe fails: back to Open, consuming the last permit definitively.
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(cb.is_allowed(),
+            "a fresh Open -> HalfOpen transition must replenish the trial-call budget");
+    }
+
+    // ========================================================================
+    // RESET TIMEOUT BACKOFF TESTS
+    // ========================================================================
+
+    /// Each consecutive failed probe must double the wait before the next
+    /// half-open attempt is allowed.
+    /// Kills mutation: effective_reset_timeout not scaling with open_cycles
+    #[test]
+    fn test_reset_timeout_backs_off_after_failed_probes() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 1 },
+            reset_timeout: Duration::from_millis(20),
+            max_reset_timeout: Duration::from_secs(300),
+            ..Default::default()
+        });
+
+        // First trip: no backoff yet, reset_timeout applies as-is.
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cb.is_allowed(), "first reopen must use the unscaled reset_timeout");
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+
+        // Probe fails: open_cycles becomes 1, so the next wait is ~2x.
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(!cb.is_allowed(),
+            "after one failed probe the reset timeout must have doubled to ~40ms, so 30ms isn't enough");
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(cb.is_allowed(), "the doubled reset timeout must elapse by ~50ms total");
+    }
+
+    /// The backoff must not grow without bound; it must saturate at
+    /// max_reset_timeout.
+    /// Kills mutation: max_reset_timeout not applied as a cap
+    #[test]
+    fn test_reset_timeout_backoff_capped() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 1 },
+            reset_timeout: Duration::from_millis(10),
+            max_reset_timeout: Duration::from_millis(35),
+            ..Default::default()
+        });
+
+        // Drive several open -> half-open -> failed-probe cycles so the
+        // uncapped backoff would exceed max_reset_timeout many times over.
+        for _ in 0..6 {
+            cb.on_failure();
+            assert_eq!(cb.state(), CircuitState::Open);
+            std::thread::sleep(Duration::from_millis(40));
+            assert!(cb.is_allowed(), "waiting past max_reset_timeout must always be enough");
+            assert_eq!(cb.state(), CircuitState::HalfOpen);
+        }
+
+        // One more failed probe takes open_cycles to 6, where the uncapped
+        // wait would be 10ms * 2^6 = 640ms; the wait must still top out
+        // at max_reset_timeout rather than growing forever.
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(cb.is_allowed(), "backoff must stay capped at max_reset_timeout, not keep growing");
+    }
+
+    /// Once the circuit successfully closes, the next trip must use the base
+    /// reset_timeout again rather than carrying over the prior backoff.
+    /// Kills mutation: open_cycles not reset to 0 on close
+    #[test]
+    fn test_reset_timeout_resets_after_circuit_closes() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 1 },
+            success_threshold: 1,
+            reset_timeout: Duration::from_millis(20),
+            max_reset_timeout: Duration::from_secs(300),
+            ..Default::default()
+        });
+
+        // Trip, probe, fail the probe once to build up one cycle of backoff.
+        cb.on_failure();
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cb.is_allowed());
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        // This time let the probe succeed and close the circuit.
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(cb.is_allowed());
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+        cb.on_success();
+        assert_eq!(cb.state(), CircuitState::Closed);
+
+        // Trip again: the wait must be back to the unscaled reset_timeout,
+        // not the doubled value from the earlier cycle.
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cb.is_allowed(),
+            "after a successful close, the next reopen must use the base reset_timeout again");
+    }
+
+    /// reset() must also clear open_cycles so a manually-reset breaker
+    /// doesn't inherit backoff from before the reset.
+    #[test]
+    fn test_reset_clears_open_cycles() {
+        let cb = CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 1 },
+            reset_timeout: Duration::from_millis(20),
+            ..Default::default()
+        });
+
+        cb.on_failure();
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cb.is_allowed());
+        cb.on_failure(); // open_cycles -> 1
+
+        cb.reset();
+        assert_eq!(cb.state(), CircuitState::Closed);
+
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cb.is_allowed(),
+            "reset() must clear open_cycles so the base reset_timeout applies again");
+    }
+
+    // ========================================================================
+    // CIRCUIT BREAKER REGISTRY TESTS
+    // ========================================================================
+
+    /// get_or_create must return the same breaker instance for the same key,
+    /// and that breaker's trips must not affect other keys.
+    /// Kills mutation: get_or_create always creating a fresh breaker
+    #[test]
+    fn test_registry_isolates_breakers_per_key() {
+        let registry = CircuitBreakerRegistry::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 1 },
+            ..Default::default()
+        });
+
+        let a = registry.get_or_create("backend-a".to_string());
+        a.on_failure();
+        assert_eq!(a.state(), CircuitState::Open);
+
+        // Same key must return the same breaker (already tripped).
+        let a_again = registry.get_or_create("backend-a".to_string());
+        assert_eq!(a_again.state(), CircuitState::Open);
+
+        // A different key must start Closed, unaffected by backend-a's trip.
+        let b = registry.get_or_create("backend-b".to_string());
+        assert_eq!(b.state(), CircuitState::Closed,
+            "one target tripping must not affect an unrelated target's breaker");
+    }
+
+    /// state() must report None for a key no call has ever been made
+    /// against, and Some once a breaker has been created for it.
+    #[test]
+    fn test_registry_state_reports_none_for_unknown_key() {
+        let registry: CircuitBreakerRegistry<&str> =
+            CircuitBreakerRegistry::new(CircuitBreakerConfig::default());
+
+        assert_eq!(registry.state(&"unknown"), None);
+
+        registry.get_or_create("known");
+        assert_eq!(registry.state(&"known"), Some(CircuitState::Closed));
+    }
+
+    /// snapshot() must include stats for every target the registry has
+    /// created a breaker for.
+    /// Kills mutation: snapshot returning an empty Vec or omitting entries
+    #[test]
+    fn test_registry_snapshot_covers_all_targets() {
+        let registry = CircuitBreakerRegistry::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold: 1 },
+            ..Default::default()
+        });
+
+        registry.get_or_create("a").on_success();
+        registry.get_or_create("b").on_failure();
+
+        let mut snapshot = registry.snapshot();
+        snapshot.sort_by_key(|(key, _)| *key);
+
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[0].0, "a");
+        assert_eq!(snapshot[0].1.total_successes, 1);
+        assert_eq!(snapshot[1].0, "b");
+        assert_eq!(snapshot[1].1.state, CircuitState::Open);
+    }
 }
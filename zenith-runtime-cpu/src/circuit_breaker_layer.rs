@@ -0,0 +1,224 @@
+//! Tower `Layer`/`Service` adapter for [`CircuitBreaker`].
+//!
+//! Lets a breaker protect any `tower::Service` (for example a tonic-generated
+//! gRPC service) without hand-wiring `is_allowed`/`on_success`/`on_failure`
+//! around every call site. Not every returned error should count against the
+//! breaker (e.g. a 404 shouldn't open a circuit meant to detect backend
+//! unavailability), so callers supply predicates classifying which responses
+//! and errors count as failures.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tower::{Layer, Service};
+
+use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerError};
+
+/// `tower::Layer` that wraps a service in a [`CircuitBreakerService`].
+pub struct CircuitBreakerLayer<Res, Err> {
+    breaker: Arc<CircuitBreaker>,
+    is_failure_response: Arc<dyn Fn(&Res) -> bool + Send + Sync>,
+    is_failure_error: Arc<dyn Fn(&Err) -> bool + Send + Sync>,
+}
+
+impl<Res, Err> Clone for CircuitBreakerLayer<Res, Err> {
+    fn clone(&self) -> Self {
+        Self {
+            breaker: self.breaker.clone(),
+            is_failure_response: self.is_failure_response.clone(),
+            is_failure_error: self.is_failure_error.clone(),
+        }
+    }
+}
+
+impl<Res, Err> CircuitBreakerLayer<Res, Err> {
+    /// Create a layer backed by `breaker`, classifying completed calls with
+    /// `is_failure_response`/`is_failure_error`.
+    pub fn new(
+        breaker: Arc<CircuitBreaker>,
+        is_failure_response: impl Fn(&Res) -> bool + Send + Sync + 'static,
+        is_failure_error: impl Fn(&Err) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            breaker,
+            is_failure_response: Arc::new(is_failure_response),
+            is_failure_error: Arc::new(is_failure_error),
+        }
+    }
+}
+
+impl<S, Res, Err> Layer<S> for CircuitBreakerLayer<Res, Err> {
+    type Service = CircuitBreakerService<S, Res, Err>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CircuitBreakerService {
+            inner,
+            breaker: self.breaker.clone(),
+            is_failure_response: self.is_failure_response.clone(),
+            is_failure_error: self.is_failure_error.clone(),
+        }
+    }
+}
+
+/// Service produced by [`CircuitBreakerLayer`]. Rejects calls with
+/// [`CircuitBreakerError::CircuitOpen`] while the breaker is open, and
+/// otherwise classifies the inner service's completed result to drive
+/// `on_success`/`on_failure`.
+pub struct CircuitBreakerService<S, Res, Err> {
+    inner: S,
+    breaker: Arc<CircuitBreaker>,
+    is_failure_response: Arc<dyn Fn(&Res) -> bool + Send + Sync>,
+    is_failure_error: Arc<dyn Fn(&Err) -> bool + Send + Sync>,
+}
+
+impl<S: Clone, Res, Err> Clone for CircuitBreakerService<S, Res, Err> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            breaker: self.breaker.clone(),
+            is_failure_response: self.is_failure_response.clone(),
+            is_failure_error: self.is_failure_error.clone(),
+        }
+    }
+}
+
+impl<S, Req> Service<Req> for CircuitBreakerService<S, S::Response, S::Error>
+where
+    S: Service<Req>,
+    S::Future: Send + 'static,
+    S::Response: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = CircuitBreakerError<S::Error>;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if !self.breaker.record_call_attempt() {
+            return Poll::Ready(Err(CircuitBreakerError::CircuitOpen));
+        }
+        self.inner
+            .poll_ready(cx)
+            .map_err(CircuitBreakerError::CallFailed)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let breaker = self.breaker.clone();
+        let is_failure_response = self.is_failure_response.clone();
+        let is_failure_error = self.is_failure_error.clone();
+        let fut = self.inner.call(req);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(response) => {
+                    if is_failure_response(&response) {
+                        breaker.on_failure();
+                    } else {
+                        breaker.on_success();
+                    }
+                    Ok(response)
+                }
+                Err(e) => {
+                    if is_failure_error(&e) {
+                        breaker.on_failure();
+                    } else {
+                        breaker.on_success();
+                    }
+                    Err(CircuitBreakerError::CallFailed(e))
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit_breaker::{CircuitBreakerConfig, CircuitState, TrippingPolicy};
+    use std::convert::Infallible;
+    use std::time::Duration;
+    use tower::service_fn;
+
+    fn breaker(threshold: u32) -> Arc<CircuitBreaker> {
+        Arc::new(CircuitBreaker::new(CircuitBreakerConfig {
+            tripping_policy: TrippingPolicy::ConsecutiveFailures { threshold },
+            ..Default::default()
+        }))
+    }
+
+    /// A successful call through the layer must count as a breaker success.
+    #[tokio::test]
+    async fn test_layer_records_success() {
+        let cb = breaker(2);
+        let layer = CircuitBreakerLayer::new(cb.clone(), |_: &&str| false, |_: &Infallible| true);
+        let mut svc = layer.layer(service_fn(|_req: ()| async { Ok::<_, Infallible>("ok") }));
+
+        let result = svc.call(()).await;
+        assert_eq!(result.unwrap(), "ok");
+        assert_eq!(cb.stats().total_successes, 1);
+    }
+
+    /// A response the predicate classifies as a failure must count against
+    /// the breaker even though the inner service returned `Ok`.
+    /// Kills mutation: is_failure_response predicate ignored
+    #[tokio::test]
+    async fn test_layer_response_predicate_drives_failure() {
+        let cb = breaker(2);
+        let layer = CircuitBreakerLayer::new(
+            cb.clone(),
+            |status: &&str| *status == "not-found",
+            |_: &Infallible| true,
+        );
+        let mut svc = layer.layer(service_fn(|_req: ()| async {
+            Ok::<_, Infallible>("not-found")
+        }));
+
+        let _ = svc.call(()).await;
+        let _ = svc.call(()).await;
+
+        assert_eq!(cb.state(), CircuitState::Open,
+            "2 calls classified as failures by the response predicate must trip threshold=2");
+        assert_eq!(cb.stats().total_successes, 0);
+    }
+
+    /// An inner error the predicate classifies as NOT a failure (e.g. a 404
+    /// equivalent) must not count against the breaker.
+    /// Kills mutation: is_failure_error predicate ignored, errors always failing
+    #[tokio::test]
+    async fn test_layer_error_predicate_can_excuse_errors() {
+        let cb = breaker(1);
+        let layer = CircuitBreakerLayer::new(cb.clone(), |_: &&str| false, |_: &&str| false);
+        let mut svc = layer.layer(service_fn(|_req: ()| async { Err::<&str, _>("not-found") }));
+
+        let result = svc.call(()).await;
+        assert!(matches!(result, Err(CircuitBreakerError::CallFailed("not-found"))));
+        assert_eq!(cb.state(), CircuitState::Closed,
+            "an error excused by the predicate must not trip the breaker");
+        assert_eq!(cb.stats().total_successes, 1,
+            "an excused error is recorded as a breaker success, not a failure");
+    }
+
+    /// poll_ready must reject with CircuitOpen once the breaker has tripped,
+    /// without invoking the inner service.
+    #[tokio::test]
+    async fn test_poll_ready_rejects_when_open() {
+        let cb = breaker(1);
+        cb.on_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        let layer = CircuitBreakerLayer::new(cb.clone(), |_: &&str| false, |_: &Infallible| true);
+        let mut svc = layer.layer(service_fn(|_req: ()| async { Ok::<_, Infallible>("ok") }));
+
+        let result = std::future::poll_fn(|cx| Service::<()>::poll_ready(&mut svc, cx)).await;
+        assert!(matches!(result, Err(CircuitBreakerError::CircuitOpen)));
+        assert_eq!(cb.stats().total_rejections, 1);
+
+        // Sanity: reset lets calls through again.
+        cb.reset();
+        std::thread::sleep(Duration::from_millis(1));
+        let result = std::future::poll_fn(|cx| Service::<()>::poll_ready(&mut svc, cx)).await;
+        assert!(result.is_ok());
+    }
+}
@@ -1,12 +1,23 @@
 //! High-Performance Data Loader
 //!
 //! Provides fast data loading with prefetching and parallel I/O.
+//!
+//! # Features
+//!
+//! Enable the `object_store` feature to load directly from cloud buckets
+//! (`s3://`, `gs://`, `az://`) or an `http(s)://` endpoint via the
+//! `object_store` crate:
+//! ```toml
+//! zenith-runtime-cpu = { version = "0.3", features = ["object_store"] }
+//! ```
 
 use std::path::Path;
+use std::sync::mpsc;
 use std::sync::Arc;
+use std::thread;
 use parking_lot::RwLock;
 use arrow::array::RecordBatch;
-use arrow::datatypes::Schema;
+use arrow::datatypes::{DataType, Field, Schema};
 
 /// Data loader configuration
 #[derive(Debug, Clone)]
@@ -17,10 +28,50 @@ pub struct LoaderConfig {
     pub prefetch_count: usize,
     /// Number of parallel workers
     pub num_workers: usize,
-    /// Enable memory mapping for large files
+    /// Enable memory mapping for large files. Has no effect on a `.gz`/`.zst`
+    /// source: a compressed file is always read through a sequential decoder
+    /// (see [`open_decoded`]) rather than mapped.
     pub memory_map: bool,
-    /// Buffer size for I/O operations
+    /// Buffer size for I/O operations. Also sized the `BufReader` a
+    /// compressed (`.gz`/`.zst`) source is decoded through.
     pub io_buffer_size: usize,
+    /// When loading a [`DataSource::Directory`], only include files whose
+    /// extension (without the leading dot, matched case-sensitively like
+    /// [`FileFormat::from_extension`]) is in this list. `None` includes
+    /// every file, so a directory mixing formats loads everything it
+    /// recognizes; set this to e.g. `Some(vec!["parquet".to_string()])` to
+    /// select just `*.parquet` out of a mixed directory.
+    pub extension_filter: Option<Vec<String>>,
+    /// Only decode these columns, in this order. `None` decodes every
+    /// column. [`ParquetFormatReader`] pushes this into the row-group
+    /// reader via `ProjectionMask` so unselected columns are never read off
+    /// disk, then reorders the result to match the order given here;
+    /// CSV/Arrow IPC/JSON Lines decode the full row and then drop
+    /// unselected columns (also reordering), since none of those formats
+    /// support column-level pushdown. Either way, the schema a reader
+    /// reports for a source reflects the projection, in the order given.
+    pub projection: Option<Vec<String>>,
+    /// Keep only rows matching this single `column op literal` predicate.
+    /// Applied after decode (and after `projection`) across every format
+    /// uniformly. For Parquet, [`ParquetFormatReader`] additionally uses
+    /// this to skip whole row groups up front: a group's column chunk
+    /// statistics (min/max/null_count) are checked, and the group is
+    /// skipped without being read if its value range can't satisfy the
+    /// predicate, or if it's entirely null and the predicate rejects null
+    /// (see [`should_skip_row_group`]). Missing or untyped statistics fall
+    /// back to reading the group, so pruning is always a safe, lossless
+    /// optimization on top of the row-by-row filter.
+    pub row_filter: Option<RowFilter>,
+    /// Stop after this many rows total, truncating the final batch rather
+    /// than dropping it whole. `None` returns every row.
+    pub limit: Option<usize>,
+    /// Skip the mtime/size check [`DataLoader::load`] otherwise does on
+    /// every call before trusting its cache, so a cached `load()` never
+    /// touches the filesystem again. Only set this for a source you know is
+    /// read-only/immutable for the `DataLoader`'s lifetime: with this set,
+    /// a file changed out from under the loader after the first `load()`
+    /// silently keeps serving the stale cached batches.
+    pub assume_immutable: bool,
 }
 
 impl Default for LoaderConfig {
@@ -31,10 +82,55 @@ impl Default for LoaderConfig {
             num_workers: 4,
             memory_map: true,
             io_buffer_size: 8 * 1024 * 1024, // 8MB
+            extension_filter: None,
+            projection: None,
+            row_filter: None,
+            limit: None,
+            assume_immutable: false,
+        }
+    }
+}
+
+/// A comparison used by [`RowFilter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOp {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl ComparisonOp {
+    fn apply<T: PartialOrd>(self, lhs: T, rhs: T) -> bool {
+        match self {
+            ComparisonOp::Eq => lhs == rhs,
+            ComparisonOp::Lt => lhs < rhs,
+            ComparisonOp::Le => lhs <= rhs,
+            ComparisonOp::Gt => lhs > rhs,
+            ComparisonOp::Ge => lhs >= rhs,
         }
     }
 }
 
+/// The literal side of a [`RowFilter`]'s comparison. Must match the
+/// filtered column's Arrow type (`Int64`/`Float64`/`Utf8`), or
+/// [`DataLoaderError::Config`] is returned instead of a silent false match.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    Int64(i64),
+    Float64(f64),
+    Utf8(String),
+}
+
+/// A single `column op literal` row predicate for [`LoaderConfig::row_filter`].
+#[derive(Debug, Clone)]
+pub struct RowFilter {
+    pub column: String,
+    pub op: ComparisonOp,
+    pub value: FilterValue,
+}
+
 /// Data source types
 #[derive(Debug, Clone)]
 pub enum DataSource {
@@ -44,6 +140,27 @@ pub enum DataSource {
     Directory(String),
     /// In-memory buffer
     Memory(Vec<u8>),
+    /// A cloud object store location (`s3://`, `gs://`, `az://`) or an
+    /// `http(s)://` endpoint, resolved through a `DataLoader`'s
+    /// [`ObjectStoreRegistry`]. A trailing `/` is treated as a prefix and
+    /// every object under it is loaded, the same way [`DataSource::Directory`]
+    /// walks a local directory.
+    ObjectStore {
+        /// Full URL, including scheme and bucket/host
+        url: String,
+    },
+    /// An arbitrary, possibly non-seekable byte stream (a pipe, socket, or
+    /// stdin) holding Arrow IPC Stream format — the only one of this
+    /// crate's formats that doesn't need to seek, which is what makes it
+    /// loadable from something that isn't a file. See [`ReaderSource`] for
+    /// why this variant can still be `Clone`.
+    Reader(ReaderSource),
+    /// A glob pattern (`*` matches within one path segment, `**` matches
+    /// zero or more segments) expanded into a set of files and loaded as a
+    /// single partitioned dataset, the same way [`DataSource::Directory`]
+    /// unifies the files it walks. Unlike `Directory`, the matched files
+    /// don't need to share a parent directory.
+    Glob(String),
 }
 
 impl DataSource {
@@ -56,6 +173,100 @@ impl DataSource {
             DataSource::File(path.to_string_lossy().to_string())
         }
     }
+
+    /// Create from a URL, recognizing `s3://`, `gs://`, `az://`, `http://`
+    /// and `https://` as cloud object store locations; anything else is
+    /// treated as a local path via [`Self::from_path`].
+    pub fn from_url(url: &str) -> Self {
+        const OBJECT_STORE_SCHEMES: [&str; 5] = ["s3://", "gs://", "az://", "http://", "https://"];
+        if OBJECT_STORE_SCHEMES.iter().any(|scheme| url.starts_with(scheme)) {
+            DataSource::ObjectStore { url: url.to_string() }
+        } else {
+            Self::from_path(url)
+        }
+    }
+}
+
+/// A boxed `Read` for [`DataSource::Reader`], wrapped in `Arc<Mutex<Option<_>>>`
+/// so `DataSource` can keep deriving `Clone` even though a trait object
+/// `Read` isn't itself `Clone` — every clone shares the same underlying
+/// handle. [`DataLoader::load`] takes it exactly once via [`Self::take`];
+/// a second load attempt (e.g. a clone, or the same `DataLoader` after a
+/// cache miss) finds it already gone and returns [`DataLoaderError::Config`]
+/// rather than silently reading nothing.
+#[derive(Clone)]
+pub struct ReaderSource(Arc<parking_lot::Mutex<Option<Box<dyn std::io::Read + Send>>>>);
+
+impl ReaderSource {
+    /// Wrap `reader` for one-time consumption by a [`DataLoader`].
+    pub fn new(reader: impl std::io::Read + Send + 'static) -> Self {
+        Self(Arc::new(parking_lot::Mutex::new(Some(Box::new(reader)))))
+    }
+
+    fn take(&self) -> Result<Box<dyn std::io::Read + Send>, DataLoaderError> {
+        self.0
+            .lock()
+            .take()
+            .ok_or_else(|| DataLoaderError::Config("DataSource::Reader has already been consumed".to_string()))
+    }
+}
+
+impl std::fmt::Debug for ReaderSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ReaderSource(..)")
+    }
+}
+
+/// A streaming compression codec [`CsvFormatReader`]/[`JsonLinesFormatReader`]
+/// transparently decode through, detected by [`Self::from_path`] on a
+/// trailing `.gz`/`.zst` filename suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Detect a codec from `path`'s trailing suffix, e.g. `.csv.gz` -> `Gzip`.
+    fn from_path(path: &str) -> Option<Self> {
+        if path.ends_with(".gz") {
+            Some(Compression::Gzip)
+        } else if path.ends_with(".zst") {
+            Some(Compression::Zstd)
+        } else {
+            None
+        }
+    }
+}
+
+/// Strip a trailing `.gz`/`.zst` suffix from `path`, if present, so format
+/// detection ([`FileFormat::from_extension`], [`FormatRegistry::reader_for`])
+/// runs against the inner, uncompressed extension (e.g. `data.csv.gz` ->
+/// `data.csv`).
+fn strip_compression_suffix(path: &str) -> &str {
+    path.strip_suffix(".gz").or_else(|| path.strip_suffix(".zst")).unwrap_or(path)
+}
+
+/// Open `path` for sequential reading, transparently wrapping it in a
+/// gzip/zstd decoder per [`Compression::from_path`] when its name ends in
+/// `.gz`/`.zst`. Buffered at `config.io_buffer_size` either way.
+/// [`LoaderConfig::memory_map`] has no effect here: a compressed source is
+/// always read through a sequential decoder rather than mapped, since the
+/// decoder itself needs a `Read`, not a byte slice.
+fn open_decoded(path: &str, config: &LoaderConfig) -> Result<Box<dyn std::io::Read + Send>, DataLoaderError> {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    let file = File::open(path).map_err(|e| DataLoaderError::Io(e.to_string()))?;
+    let buffered = BufReader::with_capacity(config.io_buffer_size, file);
+
+    match Compression::from_path(path) {
+        Some(Compression::Gzip) => Ok(Box::new(flate2::read::GzDecoder::new(buffered))),
+        Some(Compression::Zstd) => Ok(Box::new(
+            zstd::stream::read::Decoder::new(buffered).map_err(|e| DataLoaderError::DecompressionError(e.to_string()))?,
+        )),
+        None => Ok(Box::new(buffered)),
+    }
 }
 
 /// File format detection
@@ -65,8 +276,11 @@ pub enum FileFormat {
     Parquet,
     /// CSV (Comma-Separated Values)
     Csv,
-    /// Apache Arrow IPC
+    /// Apache Arrow IPC (File format: seekable, footer-based)
     ArrowIpc,
+    /// Apache Arrow IPC Stream format: footer-less, continuation-framed,
+    /// readable from a non-seekable source (see [`DataSource::Reader`]).
+    ArrowStream,
     /// JSON Lines
     JsonLines,
     /// Unknown format
@@ -74,846 +288,3561 @@ pub enum FileFormat {
 }
 
 impl FileFormat {
-    /// Detect format from file extension
+    /// Detect format from file extension, ignoring a trailing `.gz`/`.zst`
+    /// compression suffix (e.g. `data.csv.gz` detects as [`Self::Csv`]).
+    /// `.arrow`/`.feather` detect as [`Self::ArrowIpc`]; `.arrows`/`.ipc`
+    /// detect as [`Self::ArrowStream`]. [`ArrowIpcFormatReader`] itself
+    /// doesn't rely on this distinction for local files (it sniffs magic
+    /// bytes instead, so either extension loads correctly either way) — it
+    /// matters for callers, like object store dispatch, that only have an
+    /// extension to go on.
     pub fn from_extension(path: &str) -> Self {
-        let path = Path::new(path);
+        let path = Path::new(strip_compression_suffix(path));
         match path.extension().and_then(|e| e.to_str()) {
             Some("parquet") | Some("pq") => FileFormat::Parquet,
             Some("csv") | Some("tsv") => FileFormat::Csv,
             Some("arrow") | Some("feather") => FileFormat::ArrowIpc,
+            Some("arrows") | Some("ipc") => FileFormat::ArrowStream,
             Some("jsonl") | Some("ndjson") => FileFormat::JsonLines,
             _ => FileFormat::Unknown,
         }
     }
 }
 
-/// High-performance batch iterator
-pub struct BatchIterator {
+// ============================================================================
+// Pluggable file formats
+// ============================================================================
+
+/// Decodes a file at a given path into Arrow batches. `DataLoader::load_file`
+/// dispatches to a `FileFormatReader` by extension via [`FormatRegistry`]
+/// instead of matching on [`FileFormat`] directly, so a new format (Avro,
+/// ORC, a proprietary layout) can be added by registering an impl rather
+/// than patching this crate.
+pub trait FileFormatReader: Send + Sync {
+    /// Extensions (without the leading dot) this reader claims, e.g.
+    /// `&["parquet", "pq"]`. Checked case-sensitively against the path's
+    /// extension, matching [`FileFormat::from_extension`].
+    fn extensions(&self) -> &[&str];
+
+    /// Infer the schema of the file at `path` without necessarily reading
+    /// every batch. Reflects `config.projection` when set, so the schema
+    /// this returns always matches what [`Self::read`] decodes for the
+    /// same config.
+    fn infer_schema(&self, path: &str, config: &LoaderConfig) -> Result<Arc<Schema>, DataLoaderError>;
+
+    /// Read every batch in the file at `path`.
+    fn read(&self, path: &str, config: &LoaderConfig) -> Result<Vec<RecordBatch>, DataLoaderError>;
+
+    /// Like [`Self::read`], but yields batches lazily instead of collecting
+    /// them all up front, so [`DataLoader::stream`] can overlap decode of
+    /// the next batch with the caller's processing of the current one.
+    ///
+    /// The default falls back to eager [`Self::read`] and replays its
+    /// result, which is correct but gives up the overlap; override it when
+    /// the underlying reader (e.g. an Arrow `RecordBatchReader`) is already
+    /// an iterator, so batches can be handed off as they're decoded.
+    fn read_stream(
+        &self,
+        path: &str,
+        config: &LoaderConfig,
+    ) -> Result<Box<dyn Iterator<Item = Result<RecordBatch, DataLoaderError>> + Send>, DataLoaderError> {
+        Ok(Box::new(self.read(path, config)?.into_iter().map(Ok)))
+    }
+}
+
+/// Resolve `projection`'s column names against `schema` into field indices,
+/// in the order given, for formats that emulate projection by decoding the
+/// full row and dropping unselected columns post-decode (see
+/// [`project_schema_and_batches`]).
+fn projection_indices(schema: &Schema, projection: &[String]) -> Result<Vec<usize>, DataLoaderError> {
+    projection
+        .iter()
+        .map(|name| {
+            schema
+                .index_of(name)
+                .map_err(|_| DataLoaderError::Config(format!("projection references unknown column: {}", name)))
+        })
+        .collect()
+}
+
+/// Apply `config.projection` to an already-decoded `schema`/`batches` pair,
+/// for formats with no native column pushdown (CSV, Arrow IPC, JSON Lines).
+/// A no-op when `config.projection` is `None`.
+fn project_schema_and_batches(
     schema: Arc<Schema>,
     batches: Vec<RecordBatch>,
-    current_index: usize,
-    total_rows: usize,
+    config: &LoaderConfig,
+) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+    let Some(projection) = &config.projection else {
+        return Ok((schema, batches));
+    };
+
+    let indices = projection_indices(&schema, projection)?;
+    let projected_schema = Arc::new(schema.project(&indices).map_err(|e| DataLoaderError::Config(e.to_string()))?);
+    let projected_batches = batches
+        .into_iter()
+        .map(|batch| batch.project(&indices).map_err(|e| DataLoaderError::Parse(e.to_string())))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((projected_schema, projected_batches))
 }
 
-impl BatchIterator {
-    /// Create a new batch iterator
-    pub fn new(schema: Arc<Schema>, batches: Vec<RecordBatch>) -> Self {
-        let total_rows: usize = batches.iter().map(|b| b.num_rows()).sum();
-        Self {
-            schema,
-            batches,
-            current_index: 0,
-            total_rows,
-        }
-    }
-    
-    /// Get the schema
-    pub fn schema(&self) -> Arc<Schema> {
-        self.schema.clone()
+/// Built-in [`FileFormatReader`] for Apache Parquet.
+pub struct ParquetFormatReader;
+
+impl FileFormatReader for ParquetFormatReader {
+    fn extensions(&self) -> &[&str] {
+        &["parquet", "pq"]
     }
-    
-    /// Get total row count
-    pub fn total_rows(&self) -> usize {
-        self.total_rows
+
+    fn infer_schema(&self, path: &str, config: &LoaderConfig) -> Result<Arc<Schema>, DataLoaderError> {
+        use arrow::array::RecordBatchReader;
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use std::fs::File;
+
+        let file = File::open(path).map_err(|e| DataLoaderError::Io(e.to_string()))?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)
+            .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+        let builder = apply_parquet_projection(builder, config)?;
+        // The projected schema is only known once the reader's built, not
+        // from the builder alone, since `with_projection` narrows the
+        // schema the *reader* reports rather than the builder's own.
+        let reader = builder.build().map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+        let schema = reader.schema();
+
+        match &config.projection {
+            Some(projection) => reorder_schema(&schema, projection),
+            None => Ok(schema),
+        }
     }
-    
-    /// Get number of batches
-    pub fn num_batches(&self) -> usize {
-        self.batches.len()
+
+    fn read(&self, path: &str, config: &LoaderConfig) -> Result<Vec<RecordBatch>, DataLoaderError> {
+        use arrow::array::RecordBatchReader;
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use std::fs::File;
+
+        let file = File::open(path).map_err(|e| DataLoaderError::Io(e.to_string()))?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)
+            .map_err(|e| DataLoaderError::Parse(e.to_string()))?
+            .with_batch_size(config.batch_size);
+        let builder = apply_parquet_projection(builder, config)?;
+        let builder = apply_parquet_row_group_pruning(builder, config)?;
+        let reader = builder.build().map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+        let schema = reader.schema();
+        let batches = reader
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        project_schema_and_batches(schema, batches, config).map(|(_, batches)| batches)
     }
-    
-    /// Reset iterator to beginning
-    pub fn reset(&mut self) {
-        self.current_index = 0;
+
+    fn read_stream(
+        &self,
+        path: &str,
+        config: &LoaderConfig,
+    ) -> Result<Box<dyn Iterator<Item = Result<RecordBatch, DataLoaderError>> + Send>, DataLoaderError> {
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use std::fs::File;
+
+        let file = File::open(path).map_err(|e| DataLoaderError::Io(e.to_string()))?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)
+            .map_err(|e| DataLoaderError::Parse(e.to_string()))?
+            .with_batch_size(config.batch_size);
+        let builder = apply_parquet_projection(builder, config)?;
+        let builder = apply_parquet_row_group_pruning(builder, config)?;
+        let reader = builder.build().map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+        Ok(Box::new(reader.map(|r| r.map_err(|e| DataLoaderError::Parse(e.to_string())))))
     }
 }
 
-impl Iterator for BatchIterator {
-    type Item = RecordBatch;
-    
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current_index < self.batches.len() {
-            let batch = self.batches[self.current_index].clone();
-            self.current_index += 1;
-            Some(batch)
-        } else {
-            None
+/// Resolve `config.projection`'s column names into a Parquet `ProjectionMask`
+/// and apply it to `builder`, so unselected columns are never read off disk.
+/// The mask itself reports columns back in the file's original schema
+/// order; [`reorder_schema`]/[`project_schema_and_batches`] fix that up
+/// afterwards to match the order `projection` was given in. A no-op when
+/// `config.projection` is `None`.
+fn apply_parquet_projection<T>(
+    builder: parquet::arrow::arrow_reader::ArrowReaderBuilder<T>,
+    config: &LoaderConfig,
+) -> Result<parquet::arrow::arrow_reader::ArrowReaderBuilder<T>, DataLoaderError> {
+    use parquet::arrow::arrow_reader::ProjectionMask;
+
+    let Some(projection) = &config.projection else {
+        return Ok(builder);
+    };
+
+    let parquet_schema = builder.parquet_schema();
+    for name in projection {
+        if parquet_schema.columns().iter().all(|c| c.name() != name) {
+            return Err(DataLoaderError::Config(format!("projection references unknown column: {}", name)));
         }
     }
+    let mask = ProjectionMask::columns(parquet_schema, projection.iter().map(|s| s.as_str()));
+    Ok(builder.with_projection(mask))
 }
 
-/// High-performance data loader
-pub struct DataLoader {
-    config: LoaderConfig,
-    source: DataSource,
-    schema: Option<Arc<Schema>>,
-    cached_batches: RwLock<Option<Vec<RecordBatch>>>,
+/// Skip row groups that `config.row_filter`'s column statistics prove can't
+/// satisfy the predicate, via [`should_skip_row_group`]. A no-op when
+/// `config.row_filter` is `None`; [`DataLoader::load_file`] still applies
+/// the filter row-by-row afterwards since this only prunes whole groups.
+fn apply_parquet_row_group_pruning<T>(
+    builder: parquet::arrow::arrow_reader::ArrowReaderBuilder<T>,
+    config: &LoaderConfig,
+) -> Result<parquet::arrow::arrow_reader::ArrowReaderBuilder<T>, DataLoaderError> {
+    let Some(filter) = &config.row_filter else {
+        return Ok(builder);
+    };
+
+    let parquet_schema = builder.parquet_schema();
+    let Some(column_index) = parquet_schema.columns().iter().position(|c| c.name() == filter.column) else {
+        return Err(DataLoaderError::Config(format!("row_filter references unknown column: {}", filter.column)));
+    };
+
+    let surviving: Vec<usize> = builder
+        .metadata()
+        .row_groups()
+        .iter()
+        .enumerate()
+        .filter(|(_, row_group)| !should_skip_row_group(row_group, column_index, filter))
+        .map(|(i, _)| i)
+        .collect();
+
+    Ok(builder.with_row_groups(surviving))
 }
 
-impl DataLoader {
-    /// Create a new data loader
-    pub fn new(source: DataSource, config: LoaderConfig) -> Self {
-        Self {
-            config,
-            source,
-            schema: None,
-            cached_batches: RwLock::new(None),
-        }
+/// Whether `row_group` can safely be skipped for `filter`: either every
+/// value in its `column_index`'th column chunk is null (and the predicate
+/// can't match null), or the chunk's min/max statistics put its whole value
+/// range outside what the predicate can satisfy. Missing or untyped
+/// statistics always fall back to `false` (read the group).
+fn should_skip_row_group(
+    row_group: &parquet::file::metadata::RowGroupMetaData,
+    column_index: usize,
+    filter: &RowFilter,
+) -> bool {
+    use parquet::file::statistics::Statistics;
+
+    let Some(column) = row_group.columns().get(column_index) else {
+        return false;
+    };
+    let Some(stats) = column.statistics() else {
+        return false;
+    };
+
+    if stats.null_count_opt() == Some(row_group.num_rows() as u64) {
+        return true;
     }
-    
-    /// Create with default configuration
-    pub fn with_defaults(path: &str) -> Self {
-        Self::new(DataSource::from_path(path), LoaderConfig::default())
+
+    match (stats, &filter.value) {
+        (Statistics::Int64(s), FilterValue::Int64(rhs)) => match (s.min_opt(), s.max_opt()) {
+            (Some(min), Some(max)) => !interval_may_satisfy(*min, *max, filter.op, *rhs),
+            _ => false,
+        },
+        (Statistics::Double(s), FilterValue::Float64(rhs)) => match (s.min_opt(), s.max_opt()) {
+            (Some(min), Some(max)) => !interval_may_satisfy(*min, *max, filter.op, *rhs),
+            _ => false,
+        },
+        (Statistics::ByteArray(s), FilterValue::Utf8(rhs)) => match (s.min_opt(), s.max_opt()) {
+            (Some(min), Some(max)) => match (std::str::from_utf8(min.as_bytes()), std::str::from_utf8(max.as_bytes())) {
+                (Ok(min), Ok(max)) => !interval_may_satisfy(min, max, filter.op, rhs.as_str()),
+                _ => false,
+            },
+            _ => false,
+        },
+        // A type we don't statistics-prune (e.g. Int32/Boolean) or a
+        // column/literal type mismatch: fall back to reading the group,
+        // the exact per-row filter applied after decode still rejects
+        // non-matching rows (or DataLoader::load_file's own type check
+        // already errored, for a mismatch).
+        _ => false,
     }
-    
-    /// Load data and return batch iterator
-    pub fn load(&self) -> Result<BatchIterator, DataLoaderError> {
-        // Check cache first
-        if let Some(batches) = self.cached_batches.read().as_ref() {
-            if let Some(first) = batches.first() {
-                return Ok(BatchIterator::new(first.schema(), batches.clone()));
-            }
-        }
-        
-        // Load from source
-        let (schema, batches) = match &self.source {
-            DataSource::File(path) => self.load_file(path)?,
-            DataSource::Directory(path) => self.load_directory(path)?,
-            DataSource::Memory(data) => self.load_memory(data)?,
-        };
-        
-        // Cache if small enough
-        let total_size: usize = batches.iter()
-            .map(|b| b.get_array_memory_size())
-            .sum();
-        
-        if total_size < 100 * 1024 * 1024 { // Cache if < 100MB
-            *self.cached_batches.write() = Some(batches.clone());
-        }
-        
-        Ok(BatchIterator::new(schema, batches))
+}
+
+/// Whether a row group's `[min, max]` value range could contain a row
+/// satisfying `op rhs`, i.e. whether the group is safe to keep.
+fn interval_may_satisfy<T: PartialOrd>(min: T, max: T, op: ComparisonOp, rhs: T) -> bool {
+    match op {
+        ComparisonOp::Eq => min <= rhs && rhs <= max,
+        ComparisonOp::Lt => min < rhs,
+        ComparisonOp::Le => min <= rhs,
+        ComparisonOp::Gt => max > rhs,
+        ComparisonOp::Ge => max >= rhs,
     }
-    
-    fn load_file(&self, path: &str) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
-        let format = FileFormat::from_extension(path);
-        
-        match format {
-            FileFormat::Parquet => self.load_parquet(path),
-            FileFormat::Csv => self.load_csv(path),
-            FileFormat::ArrowIpc => self.load_arrow_ipc(path),
-            _ => Err(DataLoaderError::UnsupportedFormat(format!("Unknown format for: {}", path))),
-        }
+}
+
+/// Reorder `schema`'s fields to match `projection`'s order (schemas built
+/// from a Parquet `ProjectionMask`, or directly from the full file, both
+/// come back in the file's original column order).
+fn reorder_schema(schema: &Arc<Schema>, projection: &[String]) -> Result<Arc<Schema>, DataLoaderError> {
+    let indices = projection_indices(schema, projection)?;
+    Ok(Arc::new(schema.project(&indices).map_err(|e| DataLoaderError::Config(e.to_string()))?))
+}
+
+/// Built-in [`FileFormatReader`] for CSV/TSV.
+pub struct CsvFormatReader;
+
+impl FileFormatReader for CsvFormatReader {
+    fn extensions(&self) -> &[&str] {
+        &["csv", "tsv"]
     }
-    
-    fn load_parquet(&self, path: &str) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
-        use arrow::array::RecordBatchReader;
-        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
-        use std::fs::File;
-        
-        let file = File::open(path)
-            .map_err(|e| DataLoaderError::Io(e.to_string()))?;
-        
-        let builder = ParquetRecordBatchReaderBuilder::try_new(file)
-            .map_err(|e| DataLoaderError::Parse(e.to_string()))?
-            .with_batch_size(self.config.batch_size);
-        
-        let reader = builder.build()
+
+    fn infer_schema(&self, path: &str, config: &LoaderConfig) -> Result<Arc<Schema>, DataLoaderError> {
+        use arrow::csv::reader::Format;
+
+        let reader = open_decoded(path, config)?;
+        let (schema, _) = Format::default()
+            .with_header(true)
+            .infer_schema(reader, Some(100))
             .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
-        
-        let schema = reader.schema();
-        let batches: Result<Vec<_>, _> = reader.collect();
-        let batches = batches.map_err(|e| DataLoaderError::Parse(e.to_string()))?;
-        
-        Ok((schema, batches))
+        let schema = Arc::new(schema);
+
+        if let Some(projection) = &config.projection {
+            let indices = projection_indices(&schema, projection)?;
+            Ok(Arc::new(schema.project(&indices).map_err(|e| DataLoaderError::Config(e.to_string()))?))
+        } else {
+            Ok(schema)
+        }
     }
-    
-    fn load_csv(&self, path: &str) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+
+    fn read(&self, path: &str, config: &LoaderConfig) -> Result<Vec<RecordBatch>, DataLoaderError> {
         use arrow::csv::reader::Format;
         use arrow::csv::ReaderBuilder;
-        use std::fs::File;
-        
-        let file = File::open(path)
-            .map_err(|e| DataLoaderError::Io(e.to_string()))?;
-        
-        // Infer schema from file
+
         let format = Format::default().with_header(true);
-        let (schema, _) = format.infer_schema(&file, Some(100))
+        let (schema, _) = format
+            .infer_schema(open_decoded(path, config)?, Some(100))
             .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
-        
-        // Reopen file for reading
-        let file = File::open(path)
-            .map_err(|e| DataLoaderError::Io(e.to_string()))?;
-        
-        let reader = ReaderBuilder::new(Arc::new(schema.clone()))
+        let schema = Arc::new(schema);
+
+        // Reopen (and re-decode) the source for reading
+        let reader = open_decoded(path, config)?;
+
+        let reader = ReaderBuilder::new(schema.clone())
             .with_format(format)
-            .with_batch_size(self.config.batch_size)
-            .build(file)
+            .with_batch_size(config.batch_size)
+            .build(reader)
             .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
-        
-        let schema = Arc::new(schema);
-        let batches: Result<Vec<_>, _> = reader.collect();
-        let batches = batches.map_err(|e| DataLoaderError::Parse(e.to_string()))?;
-        
-        Ok((schema, batches))
+
+        let batches = reader
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        project_schema_and_batches(schema, batches, config).map(|(_, batches)| batches)
     }
-    
-    fn load_arrow_ipc(&self, path: &str) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
-        use arrow::ipc::reader::FileReader;
-        use std::fs::File;
-        
-        let file = File::open(path)
-            .map_err(|e| DataLoaderError::Io(e.to_string()))?;
-        
-        let reader = FileReader::try_new(file, None)
+
+    fn read_stream(
+        &self,
+        path: &str,
+        config: &LoaderConfig,
+    ) -> Result<Box<dyn Iterator<Item = Result<RecordBatch, DataLoaderError>> + Send>, DataLoaderError> {
+        use arrow::csv::reader::Format;
+        use arrow::csv::ReaderBuilder;
+
+        let format = Format::default().with_header(true);
+        let (schema, _) = format
+            .infer_schema(open_decoded(path, config)?, Some(100))
             .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
-        
-        let schema = reader.schema();
-        let batches: Result<Vec<_>, _> = reader.collect();
-        let batches = batches.map_err(|e| DataLoaderError::Parse(e.to_string()))?;
-        
-        Ok((schema, batches))
+
+        let reader = open_decoded(path, config)?;
+        let reader = ReaderBuilder::new(Arc::new(schema))
+            .with_format(format)
+            .with_batch_size(config.batch_size)
+            .build(reader)
+            .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        Ok(Box::new(reader.map(|r| r.map_err(|e| DataLoaderError::Parse(e.to_string())))))
     }
-    
-    fn load_directory(&self, path: &str) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
-        use std::fs;
-        
-        let entries: Vec<_> = fs::read_dir(path)
-            .map_err(|e| DataLoaderError::Io(e.to_string()))?
-            .filter_map(|e| e.ok())
-            .filter(|e| e.path().is_file())
-            .collect();
+}
+
+/// Magic bytes at the start (and, again, in the footer) of an Arrow IPC
+/// *File* format payload. Its absence at the start of a payload means
+/// either Arrow IPC *Stream* format framing (see [`ARROW_STREAM_CONTINUATION`])
+/// or a different format entirely.
+const ARROW_FILE_MAGIC: &[u8] = b"ARROW1";
+
+/// Continuation marker that leads every message in Arrow IPC Stream
+/// format's footer-less, continuation-framed encoding.
+const ARROW_STREAM_CONTINUATION: [u8; 4] = [0xFF, 0xFF, 0xFF, 0xFF];
+
+/// Magic bytes at the start (and end) of a Parquet file.
+const PARQUET_MAGIC: &[u8] = b"PAR1";
+
+/// Peek `file`'s leading bytes to tell Arrow IPC File format (seekable,
+/// footer-based) from Stream format (continuation-marker framed), then
+/// rewind so the caller can hand `file` to the matching reader from the
+/// start. `Ok(true)` means File format.
+fn is_arrow_ipc_file_format(file: &mut std::fs::File) -> Result<bool, DataLoaderError> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut header = [0u8; 6];
+    let read = file.read(&mut header).map_err(|e| DataLoaderError::Io(e.to_string()))?;
+    file.seek(SeekFrom::Start(0)).map_err(|e| DataLoaderError::Io(e.to_string()))?;
+    Ok(read >= ARROW_FILE_MAGIC.len() && &header[..ARROW_FILE_MAGIC.len()] == ARROW_FILE_MAGIC)
+}
+
+/// Built-in [`FileFormatReader`] for Apache Arrow IPC
+/// (`.arrow`/`.feather`/`.arrows`/`.ipc`). Dispatches between the seekable,
+/// footer-based File format (`FileReader`) and the footer-less,
+/// continuation-marker-framed Stream format (`StreamReader`) by sniffing the
+/// leading bytes rather than trusting the extension, since a streamed
+/// `.arrows`/socket-captured payload uses Stream format and has no footer
+/// for `FileReader` to find. For a genuinely non-seekable source with no
+/// path at all, see [`DataSource::Reader`] instead.
+pub struct ArrowIpcFormatReader;
+
+impl FileFormatReader for ArrowIpcFormatReader {
+    fn extensions(&self) -> &[&str] {
+        &["arrow", "feather", "arrows", "ipc"]
+    }
+
+    fn infer_schema(&self, path: &str, config: &LoaderConfig) -> Result<Arc<Schema>, DataLoaderError> {
+        use arrow::ipc::reader::{FileReader, StreamReader};
+        use std::fs::File;
+
+        let mut file = File::open(path).map_err(|e| DataLoaderError::Io(e.to_string()))?;
+        let schema = if is_arrow_ipc_file_format(&mut file)? {
+            let reader = FileReader::try_new(file, None).map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+            reader.schema()
+        } else {
+            let reader = StreamReader::try_new(file, None).map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+            reader.schema()
+        };
+
+        if let Some(projection) = &config.projection {
+            let indices = projection_indices(&schema, projection)?;
+            Ok(Arc::new(schema.project(&indices).map_err(|e| DataLoaderError::Config(e.to_string()))?))
+        } else {
+            Ok(schema)
+        }
+    }
+
+    fn read(&self, path: &str, config: &LoaderConfig) -> Result<Vec<RecordBatch>, DataLoaderError> {
+        use arrow::ipc::reader::{FileReader, StreamReader};
+        use std::fs::File;
+
+        let mut file = File::open(path).map_err(|e| DataLoaderError::Io(e.to_string()))?;
+        let (schema, batches) = if is_arrow_ipc_file_format(&mut file)? {
+            let reader = FileReader::try_new(file, None).map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+            let schema = reader.schema();
+            let batches = reader
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+            (schema, batches)
+        } else {
+            let reader = StreamReader::try_new(file, None).map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+            let schema = reader.schema();
+            let batches = reader
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+            (schema, batches)
+        };
+
+        project_schema_and_batches(schema, batches, config).map(|(_, batches)| batches)
+    }
+
+    fn read_stream(
+        &self,
+        path: &str,
+        _config: &LoaderConfig,
+    ) -> Result<Box<dyn Iterator<Item = Result<RecordBatch, DataLoaderError>> + Send>, DataLoaderError> {
+        use arrow::ipc::reader::{FileReader, StreamReader};
+        use std::fs::File;
+
+        let mut file = File::open(path).map_err(|e| DataLoaderError::Io(e.to_string()))?;
+        if is_arrow_ipc_file_format(&mut file)? {
+            let reader = FileReader::try_new(file, None).map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+            Ok(Box::new(reader.map(|r| r.map_err(|e| DataLoaderError::Parse(e.to_string())))))
+        } else {
+            let reader = StreamReader::try_new(file, None).map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+            Ok(Box::new(reader.map(|r| r.map_err(|e| DataLoaderError::Parse(e.to_string())))))
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// JSON Lines: schema inference + tape decoding
+// ----------------------------------------------------------------------------
+
+/// A field's type as seen so far while inferring a JSON Lines schema.
+/// [`Self::widen`] combines two observations the way schema inference
+/// generally does: numeric types widen to the wider of the two, and
+/// anything else incompatible (e.g. a field that's sometimes a bool and
+/// sometimes a string) falls back to `Utf8` rather than erroring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InferredType {
+    Null,
+    Boolean,
+    Int64,
+    Float64,
+    Utf8,
+}
+
+impl InferredType {
+    fn widen(self, other: InferredType) -> InferredType {
+        use InferredType::*;
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Null, other) | (other, Null) => other,
+            (Int64, Float64) | (Float64, Int64) => Float64,
+            _ => Utf8,
+        }
+    }
+
+    fn into_data_type(self) -> DataType {
+        match self {
+            // An all-null field still needs a concrete type; Utf8 is the
+            // least lossy default for a column that turns out to carry
+            // something else later.
+            InferredType::Null => DataType::Utf8,
+            InferredType::Boolean => DataType::Boolean,
+            InferredType::Int64 => DataType::Int64,
+            InferredType::Float64 => DataType::Float64,
+            InferredType::Utf8 => DataType::Utf8,
+        }
+    }
+}
+
+fn infer_json_value_type(value: &serde_json::Value) -> InferredType {
+    match value {
+        serde_json::Value::Null => InferredType::Null,
+        serde_json::Value::Bool(_) => InferredType::Boolean,
+        serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => InferredType::Int64,
+        serde_json::Value::Number(_) => InferredType::Float64,
+        // Strings and nested objects/arrays all flatten to text (see
+        // `json_value_to_tape`), so they infer as Utf8.
+        _ => InferredType::Utf8,
+    }
+}
+
+/// One decoded field in a JSON Lines record's tape: a flat, schema-ordered
+/// array of typed entries tokenized from a row's JSON object, fed straight
+/// into the matching column's array builder without an intermediate
+/// per-row struct.
+#[derive(Debug, Clone)]
+enum TapeValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+/// Tokenize `value`'s fields into `tape`, one entry per `schema` field in
+/// order (`TapeValue::Null` for a field the row omits). `tape` is cleared
+/// and refilled rather than replaced, so its backing allocation carries
+/// over from row to row instead of being reallocated per record.
+fn tokenize_into_tape(value: &serde_json::Value, schema: &Schema, tape: &mut Vec<TapeValue>) {
+    tape.clear();
+    let object = value.as_object();
+    for field in schema.fields() {
+        let entry = object.and_then(|o| o.get(field.name())).map(json_value_to_tape).unwrap_or(TapeValue::Null);
+        tape.push(entry);
+    }
+}
+
+fn json_value_to_tape(value: &serde_json::Value) -> TapeValue {
+    match value {
+        serde_json::Value::Null => TapeValue::Null,
+        serde_json::Value::Bool(b) => TapeValue::Bool(*b),
+        serde_json::Value::Number(n) => TapeValue::Number(n.as_f64().unwrap_or(f64::NAN)),
+        serde_json::Value::String(s) => TapeValue::Text(s.clone()),
+        // Nested objects/arrays are flattened to their compact JSON text;
+        // decoding them into nested Arrow arrays is out of scope here.
+        other => TapeValue::Text(other.to_string()),
+    }
+}
+
+/// Scan up to `sample_size` non-blank lines of the JSON Lines file at
+/// `path`, inferring one widened [`InferredType`] per field across every
+/// record seen (missing fields don't affect a field's inferred type;
+/// only present-but-conflicting types trigger widening).
+fn infer_jsonl_schema(path: &str, config: &LoaderConfig, sample_size: usize) -> Result<Arc<Schema>, DataLoaderError> {
+    use std::io::BufRead;
+
+    let reader = open_decoded(path, config)?;
+
+    let mut field_order: Vec<String> = Vec::new();
+    let mut field_types: std::collections::HashMap<String, InferredType> = std::collections::HashMap::new();
+    let mut sampled = 0usize;
+
+    for line in std::io::BufReader::new(reader).lines() {
+        let line = line.map_err(|e| DataLoaderError::Io(e.to_string()))?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue; // tolerate blank/trailing lines
+        }
+
+        let value: serde_json::Value = serde_json::from_str(line).map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+        let object = value
+            .as_object()
+            .ok_or_else(|| DataLoaderError::Parse(format!("JSON Lines record is not an object: {}", line)))?;
+
+        for (key, val) in object {
+            let inferred = infer_json_value_type(val);
+            match field_types.get_mut(key) {
+                Some(existing) => *existing = existing.widen(inferred),
+                None => {
+                    field_order.push(key.clone());
+                    field_types.insert(key.clone(), inferred);
+                }
+            }
+        }
+
+        sampled += 1;
+        if sampled >= sample_size {
+            break;
+        }
+    }
+
+    if field_order.is_empty() {
+        return Err(DataLoaderError::Empty(format!("No records to infer schema from: {}", path)));
+    }
+
+    let fields: Vec<Field> = field_order
+        .into_iter()
+        .map(|name| {
+            let data_type = field_types[&name].into_data_type();
+            // Nullable: any field not present in a later row decodes as null.
+            Field::new(name, data_type, true)
+        })
+        .collect();
+
+    Ok(Arc::new(Schema::new(fields)))
+}
+
+/// Build one `RecordBatch` from `rows`' tapes, column by column, coercing
+/// each tape entry to `schema`'s (possibly widened) type for that column.
+fn build_jsonl_batch(schema: &Arc<Schema>, rows: &[Vec<TapeValue>]) -> Result<RecordBatch, DataLoaderError> {
+    use arrow::array::{ArrayRef, BooleanBuilder, Float64Builder, Int64Builder, StringBuilder};
+
+    let mut columns: Vec<ArrayRef> = Vec::with_capacity(schema.fields().len());
+
+    for (i, field) in schema.fields().iter().enumerate() {
+        let column: ArrayRef = match field.data_type() {
+            DataType::Boolean => {
+                let mut builder = BooleanBuilder::with_capacity(rows.len());
+                for row in rows {
+                    match &row[i] {
+                        TapeValue::Null => builder.append_null(),
+                        TapeValue::Bool(b) => builder.append_value(*b),
+                        TapeValue::Number(n) => builder.append_value(*n != 0.0),
+                        TapeValue::Text(s) => builder.append_value(s == "true"),
+                    }
+                }
+                Arc::new(builder.finish())
+            }
+            DataType::Int64 => {
+                let mut builder = Int64Builder::with_capacity(rows.len());
+                for row in rows {
+                    match &row[i] {
+                        TapeValue::Null => builder.append_null(),
+                        TapeValue::Bool(b) => builder.append_value(*b as i64),
+                        TapeValue::Number(n) => builder.append_value(*n as i64),
+                        TapeValue::Text(s) => match s.parse() {
+                            Ok(v) => builder.append_value(v),
+                            Err(_) => builder.append_null(),
+                        },
+                    }
+                }
+                Arc::new(builder.finish())
+            }
+            DataType::Float64 => {
+                let mut builder = Float64Builder::with_capacity(rows.len());
+                for row in rows {
+                    match &row[i] {
+                        TapeValue::Null => builder.append_null(),
+                        TapeValue::Bool(b) => builder.append_value(if *b { 1.0 } else { 0.0 }),
+                        TapeValue::Number(n) => builder.append_value(*n),
+                        TapeValue::Text(s) => match s.parse() {
+                            Ok(v) => builder.append_value(v),
+                            Err(_) => builder.append_null(),
+                        },
+                    }
+                }
+                Arc::new(builder.finish())
+            }
+            _ => {
+                let mut builder = StringBuilder::with_capacity(rows.len(), 0);
+                for row in rows {
+                    match &row[i] {
+                        TapeValue::Null => builder.append_null(),
+                        TapeValue::Bool(b) => builder.append_value(b.to_string()),
+                        TapeValue::Number(n) => builder.append_value(n.to_string()),
+                        TapeValue::Text(s) => builder.append_value(s),
+                    }
+                }
+                Arc::new(builder.finish())
+            }
+        };
+        columns.push(column);
+    }
+
+    RecordBatch::try_new(schema.clone(), columns).map_err(|e| DataLoaderError::Parse(e.to_string()))
+}
+
+/// Built-in [`FileFormatReader`] for JSON Lines (`.jsonl`/`.ndjson`).
+///
+/// Schema inference samples up to [`LoaderConfig::batch_size`] records
+/// (see [`infer_jsonl_schema`]); decoding tokenizes each record into a
+/// reusable tape (see [`tokenize_into_tape`]) and materializes one
+/// `RecordBatch` per `batch_size` rows (see [`build_jsonl_batch`]).
+pub struct JsonLinesFormatReader;
+
+impl FileFormatReader for JsonLinesFormatReader {
+    fn extensions(&self) -> &[&str] {
+        &["jsonl", "ndjson"]
+    }
+
+    fn infer_schema(&self, path: &str, config: &LoaderConfig) -> Result<Arc<Schema>, DataLoaderError> {
+        let schema = infer_jsonl_schema(path, config, config.batch_size)?;
+
+        if let Some(projection) = &config.projection {
+            let indices = projection_indices(&schema, projection)?;
+            Ok(Arc::new(schema.project(&indices).map_err(|e| DataLoaderError::Config(e.to_string()))?))
+        } else {
+            Ok(schema)
+        }
+    }
+
+    fn read(&self, path: &str, config: &LoaderConfig) -> Result<Vec<RecordBatch>, DataLoaderError> {
+        use std::io::BufRead;
+
+        let schema = infer_jsonl_schema(path, config, config.batch_size)?;
+        let reader = open_decoded(path, config)?;
+
+        let mut batches = Vec::new();
+        let mut rows: Vec<Vec<TapeValue>> = Vec::with_capacity(config.batch_size);
+        let mut tape: Vec<TapeValue> = Vec::with_capacity(schema.fields().len());
+
+        for line in std::io::BufReader::new(reader).lines() {
+            let line = line.map_err(|e| DataLoaderError::Io(e.to_string()))?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue; // tolerate blank/trailing lines
+            }
+
+            let value: serde_json::Value =
+                serde_json::from_str(line).map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+            tokenize_into_tape(&value, &schema, &mut tape);
+            rows.push(tape.clone());
+
+            if rows.len() >= config.batch_size {
+                batches.push(build_jsonl_batch(&schema, &rows)?);
+                rows.clear();
+            }
+        }
+
+        if !rows.is_empty() {
+            batches.push(build_jsonl_batch(&schema, &rows)?);
+        }
+
+        project_schema_and_batches(schema, batches, config).map(|(_, batches)| batches)
+    }
+}
+
+/// Maps file extensions to [`FileFormatReader`]s, so `DataLoader::load_file`
+/// looks up a reader instead of matching on [`FileFormat`]. Ships with
+/// readers for Parquet/CSV/Arrow IPC/JSON Lines; register additional or
+/// overriding ones via [`Self::register`].
+pub struct FormatRegistry {
+    readers: RwLock<Vec<Arc<dyn FileFormatReader>>>,
+}
+
+impl Default for FormatRegistry {
+    fn default() -> Self {
+        Self {
+            readers: RwLock::new(vec![
+                Arc::new(ParquetFormatReader) as Arc<dyn FileFormatReader>,
+                Arc::new(CsvFormatReader),
+                Arc::new(ArrowIpcFormatReader),
+                Arc::new(JsonLinesFormatReader),
+            ]),
+        }
+    }
+}
+
+impl FormatRegistry {
+    /// Create a registry with the built-in Parquet/CSV/Arrow IPC/JSON
+    /// Lines readers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a reader, checked before any already registered for paths
+    /// whose extension matches, so it can override a built-in format.
+    pub fn register(&self, reader: Arc<dyn FileFormatReader>) {
+        self.readers.write().insert(0, reader);
+    }
+
+    /// Find the first registered reader claiming `path`'s extension, ignoring
+    /// a trailing `.gz`/`.zst` compression suffix (e.g. `data.csv.gz`
+    /// dispatches to the `csv` reader).
+    fn reader_for(&self, path: &str) -> Option<Arc<dyn FileFormatReader>> {
+        let ext = Path::new(strip_compression_suffix(path)).extension().and_then(|e| e.to_str())?;
+        self.readers.read().iter().find(|r| r.extensions().contains(&ext)).cloned()
+    }
+
+    /// Snapshot of currently registered readers. [`DataLoader::stream`]
+    /// hands this to its worker threads instead of sharing this registry's
+    /// lock across the stream's lifetime.
+    fn snapshot(&self) -> Vec<Arc<dyn FileFormatReader>> {
+        self.readers.read().clone()
+    }
+}
+
+/// Find the first reader in `readers` claiming `path`'s extension, the same
+/// lookup [`FormatRegistry::reader_for`] does, but over a plain slice so a
+/// [`StreamingBatchIterator`] worker thread doesn't need a `FormatRegistry`.
+fn reader_for_path(readers: &[Arc<dyn FileFormatReader>], path: &str) -> Option<Arc<dyn FileFormatReader>> {
+    let ext = Path::new(strip_compression_suffix(path)).extension().and_then(|e| e.to_str())?;
+    readers.iter().find(|r| r.extensions().contains(&ext)).cloned()
+}
+
+/// One file's decoded batches within a [`DataSource::Directory`] load,
+/// produced by `DataLoader::load_directory_partitions`. Keeping each file's
+/// batches and schema distinct (rather than flattening straight into one
+/// `Vec<RecordBatch>`) is what lets that method validate every partition's
+/// schema against the first before concatenating. [`DataLoader::stream`]
+/// does not build partitions; its workers read each file directly.
+#[derive(Clone)]
+pub struct DirectoryPartition {
+    /// Path to the source file backing this partition.
+    pub path: String,
+    /// Schema of `batches`.
+    pub schema: Arc<Schema>,
+    /// Batches decoded from `path`.
+    pub batches: Vec<RecordBatch>,
+}
+
+/// Validate that every partition's schema is [`schema_compatible`] with the
+/// first's, then flatten their batches into one dataset. `source_desc`
+/// (e.g. `"directory /data"` or `"glob /data/**/*.parquet"`) is folded into
+/// the error message on a mismatch so it's clear which load triggered it.
+fn unify_partitions(partitions: Vec<DirectoryPartition>, source_desc: &str) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+    let schema = partitions[0].schema.clone();
+    for partition in &partitions[1..] {
+        if !schema_compatible(&schema, &partition.schema) {
+            return Err(DataLoaderError::SchemaMismatch(format!(
+                "schema mismatch in {}: {} does not match {}'s schema",
+                source_desc, partition.path, partitions[0].path
+            )));
+        }
+    }
+
+    let all_batches = partitions.into_iter().flat_map(|p| p.batches).collect();
+    Ok((schema, all_batches))
+}
+
+/// Whether `a` and `b` describe the same logical schema, tolerating the
+/// field-naming differences different Arrow/Parquet writers use for a
+/// [`DataType::Map`]'s inner entry struct (e.g. arrow-rs's `entries`/`keys`/
+/// `values` vs. Spark/Parquet's `key_value`/`key`/`value`) since those are
+/// cosmetic and shouldn't block unifying a dataset written by mixed tools.
+fn schema_compatible(a: &Schema, b: &Schema) -> bool {
+    a.fields().len() == b.fields().len() && a.fields().iter().zip(b.fields().iter()).all(|(fa, fb)| field_compatible(fa, fb))
+}
+
+fn field_compatible(a: &Field, b: &Field) -> bool {
+    a.name() == b.name() && a.is_nullable() == b.is_nullable() && datatype_compatible(a.data_type(), b.data_type())
+}
+
+fn datatype_compatible(a: &DataType, b: &DataType) -> bool {
+    match (a, b) {
+        (DataType::Map(entry_a, sorted_a), DataType::Map(entry_b, sorted_b)) => {
+            sorted_a == sorted_b && map_entry_fields_compatible(entry_a, entry_b)
+        }
+        (DataType::List(a), DataType::List(b)) | (DataType::LargeList(a), DataType::LargeList(b)) => field_compatible(a, b),
+        (DataType::Struct(fields_a), DataType::Struct(fields_b)) => {
+            fields_a.len() == fields_b.len() && fields_a.iter().zip(fields_b.iter()).all(|(fa, fb)| field_compatible(fa, fb))
+        }
+        _ => a == b,
+    }
+}
+
+/// Compare a `Map` type's inner entry struct positionally (index 0 is
+/// always the key field, index 1 the value field) instead of by name, since
+/// Arrow's `Map` layout guarantee is structural, not nominal — the outer
+/// entry field's own name (`"entries"` vs `"key_value"`) is allowed to
+/// differ entirely.
+fn map_entry_fields_compatible(entry_a: &Arc<Field>, entry_b: &Arc<Field>) -> bool {
+    let (DataType::Struct(fields_a), DataType::Struct(fields_b)) = (entry_a.data_type(), entry_b.data_type()) else {
+        return false;
+    };
+    if fields_a.len() != 2 || fields_b.len() != 2 {
+        return false;
+    }
+    let key_compatible =
+        datatype_compatible(fields_a[0].data_type(), fields_b[0].data_type()) && fields_a[0].is_nullable() == fields_b[0].is_nullable();
+    let value_compatible =
+        datatype_compatible(fields_a[1].data_type(), fields_b[1].data_type()) && fields_a[1].is_nullable() == fields_b[1].is_nullable();
+    key_compatible && value_compatible
+}
+
+/// Expand a glob `pattern` (`*` matches within one path segment, `**`
+/// matches zero or more segments) into a sorted list of matching file
+/// paths. No external glob-matching crate is used — [`segment_matches`]
+/// hand-rolls the same kind of per-segment wildcard comparison
+/// `storage::subscription::KeyExpr` already uses for its own key patterns.
+fn expand_glob(pattern: &str) -> Result<Vec<String>, DataLoaderError> {
+    let segments: Vec<&str> = pattern.split('/').collect();
+    let wildcard_start = segments
+        .iter()
+        .position(|s| s.contains('*'))
+        .ok_or_else(|| DataLoaderError::Config(format!("glob pattern has no wildcard segment: {}", pattern)))?;
+
+    let base = match wildcard_start {
+        0 => ".".to_string(),
+        _ => {
+            let base = segments[..wildcard_start].join("/");
+            if base.is_empty() {
+                "/".to_string()
+            } else {
+                base
+            }
+        }
+    };
+
+    let mut matches = Vec::new();
+    walk_glob(Path::new(&base), &segments[wildcard_start..], &mut matches)?;
+    matches.sort();
+    Ok(matches)
+}
+
+/// Recursively match `segments` (the still-unconsumed tail of a glob
+/// pattern) against the filesystem rooted at `dir`, pushing every matching
+/// file's path into `out`. `"**"` matches zero or more directory levels: it
+/// is tried both as "consumed, match the rest of the pattern here" and as
+/// "keep `**` and descend one more level", so `a/**/b` matches `a/b` as well
+/// as `a/x/y/b`.
+fn walk_glob(dir: &Path, segments: &[&str], out: &mut Vec<String>) -> Result<(), DataLoaderError> {
+    let Some((segment, rest)) = segments.split_first() else {
+        return Ok(());
+    };
+
+    if *segment == "**" {
+        walk_glob(dir, rest, out)?;
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Ok(());
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            if entry.path().is_dir() {
+                walk_glob(&entry.path(), segments, out)?;
+            }
+        }
+        return Ok(());
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !segment_matches(&name, segment) {
+            continue;
+        }
+        if rest.is_empty() {
+            if entry.path().is_file() {
+                out.push(entry.path().to_string_lossy().to_string());
+            }
+        } else if entry.path().is_dir() {
+            walk_glob(&entry.path(), rest, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether `name` matches a single path segment `pattern` containing zero or
+/// more `*` wildcards, each standing in for any run of characters (including
+/// none) within that segment.
+fn segment_matches(name: &str, pattern: &str) -> bool {
+    if !pattern.contains('*') {
+        return name == pattern;
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, rest) = parts.split_first().expect("split('*') always yields at least one part");
+    let (last, middle) = rest.split_last().unwrap_or((&"", &[]));
+
+    if !name.starts_with(first) || !name.ends_with(last) || name.len() < first.len() + last.len() {
+        return false;
+    }
+
+    let mut remaining = &name[first.len()..name.len() - last.len()];
+    for part in middle {
+        match remaining.find(part) {
+            Some(i) => remaining = &remaining[i + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// High-performance batch iterator
+pub struct BatchIterator {
+    schema: Arc<Schema>,
+    batches: Vec<RecordBatch>,
+    current_index: usize,
+    total_rows: usize,
+}
+
+impl BatchIterator {
+    /// Create a new batch iterator
+    pub fn new(schema: Arc<Schema>, batches: Vec<RecordBatch>) -> Self {
+        let total_rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        Self {
+            schema,
+            batches,
+            current_index: 0,
+            total_rows,
+        }
+    }
+    
+    /// Get the schema
+    pub fn schema(&self) -> Arc<Schema> {
+        self.schema.clone()
+    }
+    
+    /// Get total row count
+    pub fn total_rows(&self) -> usize {
+        self.total_rows
+    }
+    
+    /// Get number of batches
+    pub fn num_batches(&self) -> usize {
+        self.batches.len()
+    }
+    
+    /// Reset iterator to beginning
+    pub fn reset(&mut self) {
+        self.current_index = 0;
+    }
+}
+
+impl Iterator for BatchIterator {
+    type Item = RecordBatch;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_index < self.batches.len() {
+            let batch = self.batches[self.current_index].clone();
+            self.current_index += 1;
+            Some(batch)
+        } else {
+            None
+        }
+    }
+}
+
+// ============================================================================
+// Streaming/prefetching load
+// ============================================================================
+
+/// Everything [`StreamingBatchIterator`] needs to (re-)spawn its worker
+/// threads, captured by value so [`StreamingBatchIterator::reset`] can
+/// re-open the source without holding a reference back to the `DataLoader`
+/// that created it.
+#[derive(Clone)]
+struct StreamSpec {
+    source: DataSource,
+    config: LoaderConfig,
+    readers: Vec<Arc<dyn FileFormatReader>>,
+}
+
+/// Decode every batch of the file at `path` via `reader`, forwarding each
+/// one to `sender` as it's produced. Returns `Err(())` (the sender's own
+/// error carries no information worth propagating further) as soon as the
+/// receiving end is gone or a reader error was already forwarded, so a
+/// caller looping over several files knows to stop.
+fn stream_file(
+    reader: Arc<dyn FileFormatReader>,
+    path: &str,
+    config: &LoaderConfig,
+    sender: &mpsc::SyncSender<Result<RecordBatch, DataLoaderError>>,
+) -> Result<(), ()> {
+    let batches = match reader.read_stream(path, config) {
+        Ok(batches) => batches,
+        Err(e) => {
+            let _ = sender.send(Err(e));
+            return Err(());
+        }
+    };
+
+    for batch in batches {
+        let is_err = batch.is_err();
+        if sender.send(batch).is_err() {
+            return Err(());
+        }
+        if is_err {
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+/// Streams batches from the underlying reader(s) on a background worker
+/// pool instead of collecting every batch up front, so decode of the next
+/// batch overlaps the caller's processing of the current one. Returned by
+/// [`DataLoader::stream`]; [`BatchIterator`] remains the eager, cached path
+/// for small sources.
+///
+/// A [`DataSource::File`] streams through a single worker, since there's
+/// only one reader to pull from; a [`DataSource::Directory`] or
+/// [`DataSource::Glob`] hands its files out through a shared counter to up
+/// to [`LoaderConfig::num_workers`] workers (each takes the next unclaimed
+/// file when it finishes one), so multiple files decode in
+/// parallel. Every worker's batches land on one channel of depth
+/// [`LoaderConfig::prefetch_count`].
+pub struct StreamingBatchIterator {
+    schema: Arc<Schema>,
+    receiver: mpsc::Receiver<Result<RecordBatch, DataLoaderError>>,
+    spec: StreamSpec,
+}
+
+impl StreamingBatchIterator {
+    fn open(spec: StreamSpec) -> Result<Self, DataLoaderError> {
+        let channel_depth = spec.config.prefetch_count.max(1);
+        let (sender, receiver) = mpsc::sync_channel(channel_depth);
+
+        let schema = match &spec.source {
+            DataSource::File(path) => {
+                let reader = reader_for_path(&spec.readers, path)
+                    .ok_or_else(|| DataLoaderError::UnsupportedFormat(format!("Unknown format for: {}", path)))?;
+                let schema = reader.infer_schema(path, &spec.config)?;
+
+                let path = path.clone();
+                let config = spec.config.clone();
+                thread::spawn(move || {
+                    let _ = stream_file(reader, &path, &config, &sender);
+                });
+
+                schema
+            }
+            DataSource::Directory(dir) => {
+                use std::fs;
+
+                let mut paths: Vec<String> = fs::read_dir(dir)
+                    .map_err(|e| DataLoaderError::Io(e.to_string()))?
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.is_file())
+                    .map(|p| p.to_string_lossy().to_string())
+                    .collect();
+                paths.sort();
+
+                let (first_reader, first_path) = paths
+                    .iter()
+                    .find_map(|p| reader_for_path(&spec.readers, p).map(|r| (r, p.clone())))
+                    .ok_or_else(|| DataLoaderError::Empty(format!("No loadable files in directory: {}", dir)))?;
+                let schema = first_reader.infer_schema(&first_path, &spec.config)?;
+
+                let num_workers = spec.config.num_workers.max(1);
+                let paths = Arc::new(paths);
+                let next_index = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+                for _ in 0..num_workers {
+                    let paths = paths.clone();
+                    let next_index = next_index.clone();
+                    let readers = spec.readers.clone();
+                    let config = spec.config.clone();
+                    let sender = sender.clone();
+
+                    thread::spawn(move || loop {
+                        let i = next_index.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        let Some(path) = paths.get(i) else { break };
+                        match reader_for_path(&readers, path) {
+                            // A file with no matching reader is skipped rather
+                            // than failing the whole stream, mirroring how
+                            // `load_object_store_prefix` skips unrecognized
+                            // extensions.
+                            Some(reader) => {
+                                if stream_file(reader, path, &config, &sender).is_err() {
+                                    break;
+                                }
+                            }
+                            None => continue,
+                        }
+                    });
+                }
+
+                schema
+            }
+            DataSource::Glob(pattern) => {
+                let paths = expand_glob(pattern)?;
+
+                let (first_reader, first_path) = paths
+                    .iter()
+                    .find_map(|p| reader_for_path(&spec.readers, p).map(|r| (r, p.clone())))
+                    .ok_or_else(|| DataLoaderError::Empty(format!("No loadable files for glob: {}", pattern)))?;
+                let schema = first_reader.infer_schema(&first_path, &spec.config)?;
+
+                let num_workers = spec.config.num_workers.max(1);
+                let paths = Arc::new(paths);
+                let next_index = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+                for _ in 0..num_workers {
+                    let paths = paths.clone();
+                    let next_index = next_index.clone();
+                    let readers = spec.readers.clone();
+                    let config = spec.config.clone();
+                    let sender = sender.clone();
+
+                    thread::spawn(move || loop {
+                        let i = next_index.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        let Some(path) = paths.get(i) else { break };
+                        match reader_for_path(&readers, path) {
+                            // A file with no matching reader is skipped rather
+                            // than failing the whole stream, mirroring how
+                            // `load_object_store_prefix` skips unrecognized
+                            // extensions.
+                            Some(reader) => {
+                                if stream_file(reader, path, &config, &sender).is_err() {
+                                    break;
+                                }
+                            }
+                            None => continue,
+                        }
+                    });
+                }
+
+                schema
+            }
+            DataSource::Memory(_) | DataSource::ObjectStore { .. } | DataSource::Reader(_) => {
+                return Err(DataLoaderError::UnsupportedFormat(
+                    "stream() supports File, Directory, and Glob sources only; use load() for Memory/ObjectStore/Reader"
+                        .to_string(),
+                ));
+            }
+        };
+
+        Ok(Self { schema, receiver, spec })
+    }
+
+    /// The schema shared by every batch this iterator yields.
+    pub fn schema(&self) -> Arc<Schema> {
+        self.schema.clone()
+    }
+
+    /// Re-open the source and start streaming from the beginning again,
+    /// replacing any in-flight worker threads and their channel.
+    pub fn reset(&mut self) -> Result<(), DataLoaderError> {
+        let reopened = Self::open(self.spec.clone())?;
+        self.schema = reopened.schema;
+        self.receiver = reopened.receiver;
+        Ok(())
+    }
+}
+
+impl Iterator for StreamingBatchIterator {
+    type Item = Result<RecordBatch, DataLoaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
+
+// ============================================================================
+// Object store registry (cloud DataSource::ObjectStore backing)
+// ============================================================================
+
+/// Maps a `scheme://host` key (e.g. `"s3://my-bucket"`) to a configured
+/// [`object_store::ObjectStore`], so credentials/endpoints are set up once
+/// per bucket/account instead of re-derived from every URL a `DataLoader`
+/// sees. A `scheme://host` not registered via [`Self::register`] is built
+/// lazily from environment-derived defaults the first time it's loaded.
+#[cfg(feature = "object_store")]
+#[derive(Default)]
+pub struct ObjectStoreRegistry {
+    stores: RwLock<std::collections::HashMap<String, Arc<dyn object_store::ObjectStore>>>,
+}
+
+#[cfg(feature = "object_store")]
+impl ObjectStoreRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a pre-configured store under `scheme://host` (e.g.
+    /// `"s3://my-bucket"`), so subsequent loads for that bucket use
+    /// caller-supplied credentials/endpoints instead of environment
+    /// defaults.
+    pub fn register(&self, key: impl Into<String>, store: Arc<dyn object_store::ObjectStore>) {
+        self.stores.write().insert(key.into(), store);
+    }
+
+    fn get_or_build(&self, url: &str) -> Result<Arc<dyn object_store::ObjectStore>, DataLoaderError> {
+        let key = object_store_registry_key(url)?;
+        if let Some(store) = self.stores.read().get(&key) {
+            return Ok(store.clone());
+        }
+        let store = build_default_object_store(url)?;
+        self.stores.write().insert(key, store.clone());
+        Ok(store)
+    }
+}
+
+/// Stub registry used when the `object_store` feature is disabled, so
+/// `DataLoader` doesn't need its own `#[cfg]` to hold one.
+#[cfg(not(feature = "object_store"))]
+#[derive(Default)]
+pub struct ObjectStoreRegistry;
+
+#[cfg(not(feature = "object_store"))]
+impl ObjectStoreRegistry {
+    /// Create an empty registry (a no-op without the `object_store` feature)
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Extract the `scheme://host` portion of `url`, used as an
+/// [`ObjectStoreRegistry`] key so e.g. `s3://my-bucket/a.parquet` and
+/// `s3://my-bucket/b.parquet` share one configured store.
+#[cfg(feature = "object_store")]
+fn object_store_registry_key(url: &str) -> Result<String, DataLoaderError> {
+    let (scheme, rest) = url
+        .split_once("://")
+        .ok_or_else(|| DataLoaderError::ObjectStore(format!("not a URL: {}", url)))?;
+    let host = rest.split(['/', '?']).next().unwrap_or("");
+    Ok(format!("{}://{}", scheme, host))
+}
+
+/// Build a store for `url`'s scheme from environment-derived defaults (the
+/// respective builder's `from_env`), the same credential discovery
+/// [`crate::s3::S3Credentials::Default`] uses for the AWS SDK.
+#[cfg(feature = "object_store")]
+fn build_default_object_store(url: &str) -> Result<Arc<dyn object_store::ObjectStore>, DataLoaderError> {
+    use object_store::aws::AmazonS3Builder;
+    use object_store::azure::MicrosoftAzureBuilder;
+    use object_store::gcp::GoogleCloudStorageBuilder;
+    use object_store::http::HttpBuilder;
+
+    let store: Arc<dyn object_store::ObjectStore> = if url.starts_with("s3://") {
+        Arc::new(
+            AmazonS3Builder::from_env()
+                .with_url(url)
+                .build()
+                .map_err(|e| DataLoaderError::ObjectStore(e.to_string()))?,
+        )
+    } else if url.starts_with("gs://") {
+        Arc::new(
+            GoogleCloudStorageBuilder::from_env()
+                .with_url(url)
+                .build()
+                .map_err(|e| DataLoaderError::ObjectStore(e.to_string()))?,
+        )
+    } else if url.starts_with("az://") {
+        Arc::new(
+            MicrosoftAzureBuilder::from_env()
+                .with_url(url)
+                .build()
+                .map_err(|e| DataLoaderError::ObjectStore(e.to_string()))?,
+        )
+    } else if url.starts_with("http://") || url.starts_with("https://") {
+        Arc::new(
+            HttpBuilder::new()
+                .with_url(url)
+                .build()
+                .map_err(|e| DataLoaderError::ObjectStore(e.to_string()))?,
+        )
+    } else {
+        return Err(DataLoaderError::ObjectStore(format!("unrecognized object store URL: {}", url)));
+    };
+
+    Ok(store)
+}
+
+/// Split `url`'s path portion (everything after `scheme://host`) into an
+/// [`object_store::path::Path`].
+#[cfg(feature = "object_store")]
+fn object_store_path(url: &str) -> Result<object_store::path::Path, DataLoaderError> {
+    let (_, rest) = url
+        .split_once("://")
+        .ok_or_else(|| DataLoaderError::ObjectStore(format!("not a URL: {}", url)))?;
+    let path = rest.splitn(2, '/').nth(1).unwrap_or("");
+    Ok(object_store::path::Path::from(path))
+}
+
+/// Apply `filter` to every batch, keeping only matching rows. A no-op when
+/// `filter` is `None`.
+fn apply_row_filter(
+    schema: &Schema,
+    batches: Vec<RecordBatch>,
+    filter: &Option<RowFilter>,
+) -> Result<Vec<RecordBatch>, DataLoaderError> {
+    let Some(filter) = filter else {
+        return Ok(batches);
+    };
+
+    let column_index = schema
+        .index_of(&filter.column)
+        .map_err(|_| DataLoaderError::Config(format!("row_filter references unknown column: {}", filter.column)))?;
+
+    batches
+        .iter()
+        .map(|batch| {
+            let mask = evaluate_row_filter(batch.column(column_index), filter)?;
+            arrow::compute::filter_record_batch(batch, &mask).map_err(|e| DataLoaderError::Parse(e.to_string()))
+        })
+        .collect()
+}
+
+/// Build the boolean mask selecting rows of `column` that satisfy `filter`.
+/// A null is never a match, regardless of `filter.op`.
+fn evaluate_row_filter(
+    column: &arrow::array::ArrayRef,
+    filter: &RowFilter,
+) -> Result<arrow::array::BooleanArray, DataLoaderError> {
+    use arrow::array::{Float64Array, Int64Array, StringArray};
+
+    let type_mismatch = || {
+        DataLoaderError::Config(format!(
+            "row_filter value for column {} does not match its type {:?}",
+            filter.column,
+            column.data_type()
+        ))
+    };
+
+    let matches: Vec<bool> = match (column.data_type(), &filter.value) {
+        (DataType::Int64, FilterValue::Int64(rhs)) => {
+            let array = column.as_any().downcast_ref::<Int64Array>().ok_or_else(type_mismatch)?;
+            (0..array.len()).map(|i| array.is_valid(i) && filter.op.apply(array.value(i), *rhs)).collect()
+        }
+        (DataType::Float64, FilterValue::Float64(rhs)) => {
+            let array = column.as_any().downcast_ref::<Float64Array>().ok_or_else(type_mismatch)?;
+            (0..array.len()).map(|i| array.is_valid(i) && filter.op.apply(array.value(i), *rhs)).collect()
+        }
+        (DataType::Utf8, FilterValue::Utf8(rhs)) => {
+            let array = column.as_any().downcast_ref::<StringArray>().ok_or_else(type_mismatch)?;
+            (0..array.len()).map(|i| array.is_valid(i) && filter.op.apply(array.value(i), rhs.as_str())).collect()
+        }
+        _ => return Err(type_mismatch()),
+    };
+
+    Ok(arrow::array::BooleanArray::from(matches))
+}
+
+/// Keep only the first `limit` rows across `batches`, truncating the batch
+/// that straddles the boundary rather than dropping it whole. A no-op when
+/// `limit` is `None`.
+fn apply_limit(batches: Vec<RecordBatch>, limit: Option<usize>) -> Vec<RecordBatch> {
+    let Some(mut remaining) = limit else {
+        return batches;
+    };
+
+    let mut result = Vec::new();
+    for batch in batches {
+        if remaining == 0 {
+            break;
+        }
+        if batch.num_rows() <= remaining {
+            remaining -= batch.num_rows();
+            result.push(batch);
+        } else {
+            result.push(batch.slice(0, remaining));
+            remaining = 0;
+        }
+    }
+    result
+}
+
+/// High-performance data loader
+pub struct DataLoader {
+    config: LoaderConfig,
+    source: DataSource,
+    schema: Option<Arc<Schema>>,
+    cached_batches: RwLock<Option<Vec<RecordBatch>>>,
+    /// `(path, mtime_unix_secs, len)` per file backing `cached_batches`, in
+    /// the same order [`Self::source_signature`] always produces them.
+    /// Compared against a freshly-stat'd signature on every [`Self::load`]
+    /// call (unless [`LoaderConfig::assume_immutable`] is set) to decide
+    /// whether the cache is still valid. `None` for sources
+    /// [`Self::source_signature`] can't stat (`Memory`/`ObjectStore`/
+    /// `Reader`), which keep the previous cache-forever behavior.
+    cached_signature: RwLock<Option<Vec<(String, u64, u64)>>>,
+    object_stores: ObjectStoreRegistry,
+    formats: FormatRegistry,
+}
+
+impl DataLoader {
+    /// Create a new data loader
+    pub fn new(source: DataSource, config: LoaderConfig) -> Self {
+        Self {
+            config,
+            source,
+            schema: None,
+            cached_batches: RwLock::new(None),
+            cached_signature: RwLock::new(None),
+            object_stores: ObjectStoreRegistry::new(),
+            formats: FormatRegistry::new(),
+        }
+    }
+
+    /// Registry used to resolve `DataSource::ObjectStore` URLs to configured
+    /// stores. Register per-bucket credentials/endpoints via
+    /// `object_stores().register(...)` before calling [`Self::load`] when
+    /// environment-derived defaults aren't appropriate.
+    pub fn object_stores(&self) -> &ObjectStoreRegistry {
+        &self.object_stores
+    }
+
+    /// Registry mapping file extensions to [`FileFormatReader`]s used by
+    /// [`Self::load`] for `File`/`Directory` sources. Register a custom or
+    /// overriding format via `formats().register(...)`.
+    pub fn formats(&self) -> &FormatRegistry {
+        &self.formats
+    }
+    
+    /// Create with default configuration
+    pub fn with_defaults(path: &str) -> Self {
+        Self::new(DataSource::from_path(path), LoaderConfig::default())
+    }
+    
+    /// Load data and return batch iterator. For a [`DataSource::Reader`],
+    /// this fully drains the underlying stream exactly once: if the result
+    /// fits under the 100MB cache threshold below, it's cached like any
+    /// other source, so a later `load()` call (e.g. after a cache-unaware
+    /// caller retries) still returns it; above that threshold nothing is
+    /// cached and the stream has already been consumed, so the batches
+    /// returned here are a one-shot, non-replayable pass-through — calling
+    /// `load()` again returns [`DataLoaderError::Config`] instead of
+    /// silently yielding nothing.
+    pub fn load(&self) -> Result<BatchIterator, DataLoaderError> {
+        // Check cache first, revalidating against the source's current
+        // mtime/size unless the caller has asserted the source is immutable
+        // or `source_signature` can't stat this kind of source at all (in
+        // which case there's nothing to compare against and the cache is
+        // trusted for the loader's lifetime, as before this check existed).
+        let current_signature = if self.config.assume_immutable { None } else { self.source_signature() };
+        if let Some(batches) = self.cached_batches.read().as_ref() {
+            if let Some(first) = batches.first() {
+                let cache_is_fresh = self.config.assume_immutable
+                    || current_signature.is_none()
+                    || current_signature == *self.cached_signature.read();
+                if cache_is_fresh {
+                    return Ok(BatchIterator::new(first.schema(), batches.clone()));
+                }
+            }
+        }
+
+        let (schema, batches) = self.load_from_source()?;
+
+        // The source may have been replaced between the signature taken
+        // above and the read that just completed; re-stat and, if it
+        // changed mid-flight, retry once against whatever is there now
+        // rather than risk caching (or returning) a torn read.
+        let signature_after_load = if self.config.assume_immutable { None } else { self.source_signature() };
+        let (schema, batches, signature_after_load) = if signature_after_load != current_signature {
+            let (schema, batches) = self.load_from_source()?;
+            let retried_signature = if self.config.assume_immutable { None } else { self.source_signature() };
+            (schema, batches, retried_signature)
+        } else {
+            (schema, batches, signature_after_load)
+        };
+
+        // Cache if small enough
+        let total_size: usize = batches.iter()
+            .map(|b| b.get_array_memory_size())
+            .sum();
+
+        if total_size < 100 * 1024 * 1024 { // Cache if < 100MB
+            *self.cached_batches.write() = Some(batches.clone());
+            *self.cached_signature.write() = signature_after_load;
+        }
+
+        Ok(BatchIterator::new(schema, batches))
+    }
+
+    fn load_from_source(&self) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        match &self.source {
+            DataSource::File(path) => self.load_file(path),
+            DataSource::Directory(path) => self.load_directory(path),
+            DataSource::Memory(data) => self.load_memory(data),
+            DataSource::ObjectStore { url } => self.load_object_store(url),
+            DataSource::Reader(source) => self.load_reader(source),
+            DataSource::Glob(pattern) => self.load_glob(pattern),
+        }
+    }
+
+    /// `(path, mtime_unix_secs, len)` for every local file backing
+    /// `self.source`, sorted by path for a stable comparison — `Some(vec![..])`
+    /// for `File`/`Directory`/`Glob`, `None` for `Memory`/`ObjectStore`/
+    /// `Reader`, which either have no filesystem path to stat or (for
+    /// `Reader`) are already single-use regardless of caching.
+    fn source_signature(&self) -> Option<Vec<(String, u64, u64)>> {
+        let paths: Vec<String> = match &self.source {
+            DataSource::File(path) => vec![path.clone()],
+            DataSource::Directory(dir) => {
+                let mut paths: Vec<String> = std::fs::read_dir(dir)
+                    .ok()?
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.is_file())
+                    .map(|p| p.to_string_lossy().to_string())
+                    .collect();
+                paths.sort();
+                paths
+            }
+            DataSource::Glob(pattern) => expand_glob(pattern).ok()?,
+            DataSource::Memory(_) | DataSource::ObjectStore { .. } | DataSource::Reader(_) => return None,
+        };
+
+        paths
+            .into_iter()
+            .map(|path| {
+                let metadata = std::fs::metadata(&path).ok()?;
+                let mtime = metadata.modified().ok()?.duration_since(std::time::UNIX_EPOCH).ok()?.as_secs();
+                Some((path, mtime, metadata.len()))
+            })
+            .collect()
+    }
+
+    /// Like [`Self::load`], but streams batches lazily from a background
+    /// worker pool instead of collecting them all up front — see
+    /// [`StreamingBatchIterator`] for how workers and prefetch depth are
+    /// chosen. Nothing is cached, and `Memory`/`ObjectStore`/`Reader`
+    /// sources aren't supported; use [`Self::load`] for those.
+    /// `config.projection`, `row_filter`, and `limit` are honored by
+    /// [`Self::load`] but not yet by this method's per-reader `read_stream`
+    /// path.
+    pub fn stream(&self) -> Result<StreamingBatchIterator, DataLoaderError> {
+        let spec = StreamSpec {
+            source: self.source.clone(),
+            config: self.config.clone(),
+            readers: self.formats.snapshot(),
+        };
+        StreamingBatchIterator::open(spec)
+    }
+
+    fn load_file(&self, path: &str) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        let reader = self
+            .formats
+            .reader_for(path)
+            .ok_or_else(|| DataLoaderError::UnsupportedFormat(format!("Unknown format for: {}", path)))?;
+
+        let schema = reader.infer_schema(path, &self.config)?;
+        let batches = reader.read(path, &self.config)?;
+        let batches = apply_row_filter(&schema, batches, &self.config.row_filter)?;
+        let batches = apply_limit(batches, self.config.limit);
+        Ok((schema, batches))
+    }
+
+    fn load_directory(&self, path: &str) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        let partitions = self.load_directory_partitions(path)?;
+        unify_partitions(partitions, &format!("directory {}", path))
+    }
+
+    /// Expand `pattern` into a sorted set of matching files and decode each
+    /// as its own [`DirectoryPartition`] before unifying them the same way
+    /// [`Self::load_directory`] does, except a file whose extension isn't
+    /// recognized by `self.formats` is silently skipped rather than failing
+    /// the whole load, since a glob is far more likely than a plain
+    /// directory listing to also sweep up unrelated files.
+    fn load_glob(&self, pattern: &str) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        let paths = expand_glob(pattern)?;
+        let paths: Vec<String> = paths.into_iter().filter(|p| self.formats.reader_for(p).is_some()).collect();
+
+        if paths.is_empty() {
+            return Err(DataLoaderError::Empty(format!("No matching files for glob: {}", pattern)));
+        }
+
+        let num_workers = self.config.num_workers.max(1).min(paths.len());
+        let slots: Vec<std::sync::Mutex<Option<Result<DirectoryPartition, DataLoaderError>>>> =
+            paths.iter().map(|_| std::sync::Mutex::new(None)).collect();
+
+        thread::scope(|scope| {
+            for worker in 0..num_workers {
+                let paths = &paths;
+                let slots = &slots;
+                scope.spawn(move || {
+                    let mut i = worker;
+                    while i < paths.len() {
+                        let result = self.load_file(&paths[i]).map(|(schema, batches)| DirectoryPartition {
+                            path: paths[i].clone(),
+                            schema,
+                            batches,
+                        });
+                        *slots[i].lock().unwrap() = Some(result);
+                        i += num_workers;
+                    }
+                });
+            }
+        });
+
+        let partitions = slots
+            .into_iter()
+            .map(|slot| slot.into_inner().unwrap().expect("every slot is filled by its assigned worker"))
+            .collect::<Result<Vec<_>, _>>()?;
+        unify_partitions(partitions, &format!("glob {}", pattern))
+    }
+
+    /// Load every file directly under `path` (optionally narrowed by
+    /// [`LoaderConfig::extension_filter`]) as its own [`DirectoryPartition`],
+    /// dispatching across up to [`LoaderConfig::num_workers`] worker
+    /// threads. Partitions are returned sorted by path, so callers get a
+    /// deterministic order regardless of how many workers raced to decode
+    /// them or the filesystem's own directory-listing order.
+    fn load_directory_partitions(&self, path: &str) -> Result<Vec<DirectoryPartition>, DataLoaderError> {
+        use std::fs;
+
+        let mut paths: Vec<String> = fs::read_dir(path)
+            .map_err(|e| DataLoaderError::Io(e.to_string()))?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file())
+            .filter(|p| match &self.config.extension_filter {
+                None => true,
+                Some(extensions) => p
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .map(|ext| extensions.iter().any(|allowed| allowed == ext))
+                    .unwrap_or(false),
+            })
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        paths.sort();
+
+        if paths.is_empty() {
+            return Err(DataLoaderError::Empty(format!("No files in directory: {}", path)));
+        }
+
+        let num_workers = self.config.num_workers.max(1).min(paths.len());
+        let slots: Vec<std::sync::Mutex<Option<Result<DirectoryPartition, DataLoaderError>>>> =
+            paths.iter().map(|_| std::sync::Mutex::new(None)).collect();
+
+        thread::scope(|scope| {
+            for worker in 0..num_workers {
+                let paths = &paths;
+                let slots = &slots;
+                scope.spawn(move || {
+                    let mut i = worker;
+                    while i < paths.len() {
+                        let result = self.load_file(&paths[i]).map(|(schema, batches)| DirectoryPartition {
+                            path: paths[i].clone(),
+                            schema,
+                            batches,
+                        });
+                        *slots[i].lock().unwrap() = Some(result);
+                        i += num_workers;
+                    }
+                });
+            }
+        });
+
+        slots.into_iter().map(|slot| slot.into_inner().unwrap().expect("every slot is filled by its assigned worker")).collect()
+    }
+    
+    /// Sniff `data`'s leading bytes the same way [`is_arrow_ipc_file_format`]
+    /// sniffs a file, since there's no path extension to dispatch on for an
+    /// in-memory buffer: `PAR1` for Parquet, `ARROW1` for Arrow IPC File
+    /// format, or the Stream format continuation marker for Arrow IPC
+    /// Stream format. CSV/JSON Lines have no reliable magic bytes and
+    /// aren't sniffable this way; load them via [`DataSource::File`] instead.
+    fn load_memory(&self, data: &[u8]) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        let bytes = bytes::Bytes::copy_from_slice(data);
+
+        if data.starts_with(PARQUET_MAGIC) {
+            self.decode_parquet_bytes(bytes)
+        } else if data.starts_with(ARROW_FILE_MAGIC) {
+            self.decode_arrow_ipc_bytes(bytes)
+        } else if data.starts_with(&ARROW_STREAM_CONTINUATION) {
+            self.decode_arrow_ipc_stream_bytes(bytes)
+        } else {
+            Err(DataLoaderError::UnsupportedFormat(
+                "could not detect a format from magic bytes (expected Parquet 'PAR1', Arrow IPC File 'ARROW1', \
+                 or an Arrow IPC Stream continuation marker)"
+                    .to_string(),
+            ))
+        }
+    }
+
+    /// Drain a [`DataSource::Reader`] as Arrow IPC Stream format — the only
+    /// one of this crate's formats that doesn't need to seek, which is what
+    /// makes a non-seekable pipe/socket readable at all. See
+    /// [`ReaderSource::take`] for what happens on a second call.
+    fn load_reader(&self, source: &ReaderSource) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        use arrow::ipc::reader::StreamReader;
+
+        let reader = source.take()?;
+        let reader = StreamReader::try_new(reader, None).map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        let schema = reader.schema();
+        let batches = reader
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        Ok((schema, batches))
+    }
+
+    /// Resolve `url` through [`Self::object_stores`] and load it: a single
+    /// object if `url` doesn't end in `/`, or every object under it (like
+    /// [`Self::load_directory`] for local files) if it does.
+    #[cfg(feature = "object_store")]
+    fn load_object_store(&self, url: &str) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        let store = self.object_stores.get_or_build(url)?;
+        let path = object_store_path(url)?;
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| DataLoaderError::ObjectStore(e.to_string()))?;
+
+        if url.ends_with('/') {
+            runtime.block_on(self.load_object_store_prefix(store.as_ref(), &path))
+        } else {
+            runtime.block_on(self.load_object_store_object(store.as_ref(), &path))
+        }
+    }
+
+    #[cfg(not(feature = "object_store"))]
+    fn load_object_store(&self, _url: &str) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        Err(DataLoaderError::ObjectStore(
+            "Enable the 'object_store' feature to load from cloud buckets. \
+             Add `features = [\"object_store\"]` to your Cargo.toml."
+                .to_string(),
+        ))
+    }
+
+    /// Fetch and decode a single object addressed by `path`.
+    #[cfg(feature = "object_store")]
+    async fn load_object_store_object(
+        &self,
+        store: &dyn object_store::ObjectStore,
+        path: &object_store::path::Path,
+    ) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        let bytes = store
+            .get(path)
+            .await
+            .map_err(|e| DataLoaderError::ObjectStore(e.to_string()))?
+            .bytes()
+            .await
+            .map_err(|e| DataLoaderError::ObjectStore(e.to_string()))?;
+
+        let format = FileFormat::from_extension(path.as_ref());
+        match format {
+            FileFormat::Parquet => self.decode_parquet_bytes(bytes),
+            FileFormat::Csv => self.decode_csv_bytes(bytes),
+            FileFormat::ArrowIpc => self.decode_arrow_ipc_bytes(bytes),
+            FileFormat::ArrowStream => self.decode_arrow_ipc_stream_bytes(bytes),
+            _ => Err(DataLoaderError::UnsupportedFormat(format!("Unknown format for: {}", path))),
+        }
+    }
+
+    /// List every object under `prefix` via the object store's list API
+    /// (mirroring how [`Self::load_directory`] walks a local directory) and
+    /// decode/concatenate each one whose extension is recognized.
+    #[cfg(feature = "object_store")]
+    async fn load_object_store_prefix(
+        &self,
+        store: &dyn object_store::ObjectStore,
+        prefix: &object_store::path::Path,
+    ) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        use futures_util::TryStreamExt;
+
+        let mut all_batches = Vec::new();
+        let mut schema: Option<Arc<Schema>> = None;
+
+        let mut listing = store.list(Some(prefix));
+        while let Some(meta) = listing.try_next().await.map_err(|e| DataLoaderError::ObjectStore(e.to_string()))? {
+            let bytes = store
+                .get(&meta.location)
+                .await
+                .map_err(|e| DataLoaderError::ObjectStore(e.to_string()))?
+                .bytes()
+                .await
+                .map_err(|e| DataLoaderError::ObjectStore(e.to_string()))?;
+
+            let (file_schema, batches) = match FileFormat::from_extension(meta.location.as_ref()) {
+                FileFormat::Parquet => self.decode_parquet_bytes(bytes)?,
+                FileFormat::Csv => self.decode_csv_bytes(bytes)?,
+                FileFormat::ArrowIpc => self.decode_arrow_ipc_bytes(bytes)?,
+                FileFormat::ArrowStream => self.decode_arrow_ipc_stream_bytes(bytes)?,
+                _ => continue,
+            };
+
+            if schema.is_none() {
+                schema = Some(file_schema);
+            }
+            all_batches.extend(batches);
+        }
+
+        let schema =
+            schema.ok_or_else(|| DataLoaderError::Empty(format!("No loadable objects under: {}", prefix)))?;
+
+        Ok((schema, all_batches))
+    }
+
+    fn decode_parquet_bytes(&self, bytes: bytes::Bytes) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        use arrow::array::RecordBatchReader;
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(bytes)
+            .map_err(|e| DataLoaderError::Parse(e.to_string()))?
+            .with_batch_size(self.config.batch_size);
+
+        let reader = builder.build().map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        let schema = reader.schema();
+        let batches: Result<Vec<_>, _> = reader.collect();
+        let batches = batches.map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        Ok((schema, batches))
+    }
+
+    fn decode_csv_bytes(&self, bytes: bytes::Bytes) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        use arrow::csv::reader::Format;
+        use arrow::csv::ReaderBuilder;
+        use std::io::Cursor;
+
+        let format = Format::default().with_header(true);
+        let (schema, _) = format
+            .infer_schema(Cursor::new(bytes.clone()), Some(100))
+            .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        let reader = ReaderBuilder::new(Arc::new(schema.clone()))
+            .with_format(format)
+            .with_batch_size(self.config.batch_size)
+            .build(Cursor::new(bytes))
+            .map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        let schema = Arc::new(schema);
+        let batches: Result<Vec<_>, _> = reader.collect();
+        let batches = batches.map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        Ok((schema, batches))
+    }
+
+    fn decode_arrow_ipc_bytes(&self, bytes: bytes::Bytes) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        use arrow::ipc::reader::FileReader;
+        use std::io::Cursor;
+
+        let reader = FileReader::try_new(Cursor::new(bytes), None).map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        let schema = reader.schema();
+        let batches: Result<Vec<_>, _> = reader.collect();
+        let batches = batches.map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        Ok((schema, batches))
+    }
+
+    /// Like [`Self::decode_arrow_ipc_bytes`], but for the footer-less Arrow
+    /// IPC Stream format (see [`ArrowIpcFormatReader`]'s magic-byte
+    /// dispatch) rather than assuming File format.
+    fn decode_arrow_ipc_stream_bytes(&self, bytes: bytes::Bytes) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
+        use arrow::ipc::reader::StreamReader;
+        use std::io::Cursor;
+
+        let reader =
+            StreamReader::try_new(Cursor::new(bytes), None).map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        let schema = reader.schema();
+        let batches: Result<Vec<_>, _> = reader.collect();
+        let batches = batches.map_err(|e| DataLoaderError::Parse(e.to_string()))?;
+
+        Ok((schema, batches))
+    }
+
+    /// Get loader configuration
+    pub fn config(&self) -> &LoaderConfig {
+        &self.config
+    }
+    
+    /// Clear cached data, forcing the next [`Self::load`] to re-read the
+    /// source regardless of whether its mtime/size still matches. This is
+    /// also what [`Self::load`] itself now does automatically whenever a
+    /// stat-based revalidation detects the source changed, so manually
+    /// calling this is only needed to invalidate a source `source_signature`
+    /// can't stat (e.g. `Memory`) or one loaded with
+    /// [`LoaderConfig::assume_immutable`] set.
+    pub fn clear_cache(&self) {
+        *self.cached_batches.write() = None;
+        *self.cached_signature.write() = None;
+    }
+    
+    /// Get the cached schema if available
+    #[allow(dead_code)]
+    pub fn schema(&self) -> Option<Arc<Schema>> {
+        self.schema.clone()
+    }
+}
+
+/// Data loader errors
+#[derive(Debug)]
+pub enum DataLoaderError {
+    /// I/O error
+    Io(String),
+    /// Parse error
+    Parse(String),
+    /// Unsupported format
+    UnsupportedFormat(String),
+    /// Empty source
+    Empty(String),
+    /// Configuration error
+    Config(String),
+    /// Cloud object store error (listing, fetch, or registry lookup failure)
+    ObjectStore(String),
+    /// Gzip/zstd decompression failure (corrupt or truncated stream)
+    DecompressionError(String),
+    /// Two partitions of the same dataset load (`Directory`/`Glob`) have
+    /// structurally incompatible schemas (field count, name, nullability, or
+    /// a leaf type actually differs) rather than merely a cosmetic naming
+    /// difference [`schema_compatible`] already tolerates.
+    SchemaMismatch(String),
+}
+
+impl std::fmt::Display for DataLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(msg) => write!(f, "I/O error: {}", msg),
+            Self::Parse(msg) => write!(f, "Parse error: {}", msg),
+            Self::UnsupportedFormat(msg) => write!(f, "Unsupported format: {}", msg),
+            Self::Empty(msg) => write!(f, "Empty source: {}", msg),
+            Self::Config(msg) => write!(f, "Configuration error: {}", msg),
+            Self::ObjectStore(msg) => write!(f, "Object store error: {}", msg),
+            Self::DecompressionError(msg) => write!(f, "Decompression error: {}", msg),
+            Self::SchemaMismatch(msg) => write!(f, "Schema mismatch: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for DataLoaderError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::{DataType, Field};
+    use arrow::array::Int32Array;
+    
+    // ===================== FileFormat Tests =====================
+    
+    #[test]
+    fn test_file_format_detection() {
+        assert_eq!(FileFormat::from_extension("data.parquet"), FileFormat::Parquet);
+        assert_eq!(FileFormat::from_extension("data.csv"), FileFormat::Csv);
+        assert_eq!(FileFormat::from_extension("data.arrow"), FileFormat::ArrowIpc);
+        assert_eq!(FileFormat::from_extension("data.unknown"), FileFormat::Unknown);
+    }
+    
+    #[test]
+    fn test_file_format_feather() {
+        // 'feather' is an alias for ArrowIpc
+        assert_eq!(FileFormat::from_extension("data.feather"), FileFormat::ArrowIpc);
+    }
+
+    #[test]
+    fn test_file_format_arrow_stream_extensions() {
+        assert_eq!(FileFormat::from_extension("data.arrows"), FileFormat::ArrowStream);
+        assert_eq!(FileFormat::from_extension("data.ipc"), FileFormat::ArrowStream);
+    }
+
+    #[test]
+    fn test_file_format_jsonl() {
+        assert_eq!(FileFormat::from_extension("data.jsonl"), FileFormat::JsonLines);
+        assert_eq!(FileFormat::from_extension("data.ndjson"), FileFormat::JsonLines);
+    }
+    
+    #[test]
+    fn test_file_format_uppercase() {
+        // Extensions are case-sensitive, uppercase should be unknown
+        assert_eq!(FileFormat::from_extension("data.PARQUET"), FileFormat::Unknown);
+    }
+    
+    #[test]
+    fn test_file_format_no_extension() {
+        assert_eq!(FileFormat::from_extension("data"), FileFormat::Unknown);
+    }
+    
+    #[test]
+    fn test_file_format_clone_copy() {
+        let format = FileFormat::Parquet;
+        let cloned = format.clone();
+        let copied = format;
+        assert_eq!(format, cloned);
+        assert_eq!(format, copied);
+    }
+    
+    #[test]
+    fn test_file_format_debug() {
+        let format = FileFormat::Parquet;
+        let debug_str = format!("{:?}", format);
+        assert!(debug_str.contains("Parquet"));
+    }
+    
+    // ===================== LoaderConfig Tests =====================
+    
+    #[test]
+    fn test_loader_config_default() {
+        let config = LoaderConfig::default();
+        assert_eq!(config.batch_size, 1024);
+        assert_eq!(config.num_workers, 4);
+        assert_eq!(config.prefetch_count, 4);
+        assert!(config.memory_map);
+        assert_eq!(config.io_buffer_size, 8 * 1024 * 1024);
+    }
+    
+    #[test]
+    fn test_loader_config_custom() {
+        let config = LoaderConfig {
+            batch_size: 2048,
+            num_workers: 8,
+            prefetch_count: 8,
+            memory_map: false,
+            io_buffer_size: 4 * 1024 * 1024,
+            extension_filter: None,
+            projection: None,
+            row_filter: None,
+            limit: None,
+            assume_immutable: false,
+        };
+        assert_eq!(config.batch_size, 2048);
+        assert_eq!(config.num_workers, 8);
+        assert!(!config.memory_map);
+    }
+    
+    #[test]
+    fn test_loader_config_clone() {
+        let config = LoaderConfig::default();
+        let cloned = config.clone();
+        assert_eq!(config.batch_size, cloned.batch_size);
+        assert_eq!(config.num_workers, cloned.num_workers);
+    }
+    
+    #[test]
+    fn test_loader_config_debug() {
+        let config = LoaderConfig::default();
+        let debug_str = format!("{:?}", config);
+        assert!(debug_str.contains("LoaderConfig"));
+        assert!(debug_str.contains("batch_size"));
+    }
+    
+    // ===================== DataSource Tests =====================
+    
+    #[test]
+    fn test_data_source_from_path_file() {
+        let source = DataSource::from_path("/tmp/test.parquet");
+        match source {
+            DataSource::File(p) => assert!(p.contains("test.parquet")),
+            _ => panic!("Expected File variant"),
+        }
+    }
+    
+    #[test]
+    fn test_data_source_directory() {
+        // DataSource::Directory variant can be created directly
+        let source = DataSource::Directory("/tmp/data".to_string());
+        match source {
+            DataSource::Directory(p) => assert!(p.contains("data")),
+            _ => panic!("Expected Directory variant"),
+        }
+    }
+    
+    #[test]
+    fn test_data_source_memory() {
+        let data = vec![1u8, 2, 3, 4, 5];
+        let source = DataSource::Memory(data.clone());
+        match source {
+            DataSource::Memory(d) => assert_eq!(d.len(), 5),
+            _ => panic!("Expected Memory variant"),
+        }
+    }
+    
+    #[test]
+    fn test_data_source_clone() {
+        let source = DataSource::File("test.parquet".to_string());
+        let cloned = source.clone();
+        match (source, cloned) {
+            (DataSource::File(a), DataSource::File(b)) => assert_eq!(a, b),
+            _ => panic!("Clone mismatch"),
+        }
+    }
+    
+    #[test]
+    fn test_data_source_from_url_recognizes_object_store_schemes() {
+        for url in ["s3://bucket/key.parquet", "gs://bucket/key.parquet", "az://bucket/key.parquet", "http://host/key.csv", "https://host/key.csv"] {
+            match DataSource::from_url(url) {
+                DataSource::ObjectStore { url: got } => assert_eq!(got, url),
+                other => panic!("expected ObjectStore for {}, got {:?}", url, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_data_source_from_url_falls_back_to_path() {
+        let source = DataSource::from_url("/tmp/test.parquet");
+        match source {
+            DataSource::File(p) => assert!(p.contains("test.parquet")),
+            other => panic!("expected File variant, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_data_source_debug() {
+        let source = DataSource::File("test.parquet".to_string());
+        let debug_str = format!("{:?}", source);
+        assert!(debug_str.contains("File"));
+        assert!(debug_str.contains("test.parquet"));
+    }
+    
+    // ===================== DataLoader Tests =====================
+    
+    #[test]
+    fn test_data_loader_creation() {
+        let source = DataSource::File("test.parquet".to_string());
+        let config = LoaderConfig::default();
+        let loader = DataLoader::new(source, config);
+        
+        assert_eq!(loader.config().batch_size, 1024);
+    }
+    
+    #[test]
+    fn test_data_loader_with_defaults() {
+        let loader = DataLoader::with_defaults("/tmp/test.parquet");
+        assert_eq!(loader.config().batch_size, 1024);
+    }
+    
+    #[test]
+    fn test_data_loader_config_access() {
+        let config = LoaderConfig {
+            batch_size: 512,
+            num_workers: 2,
+            prefetch_count: 1,
+            memory_map: false,
+            io_buffer_size: 1024 * 1024,
+            extension_filter: None,
+            projection: None,
+            row_filter: None,
+            limit: None,
+            assume_immutable: false,
+        };
+        let loader = DataLoader::new(DataSource::File("test.csv".to_string()), config);
+        
+        assert_eq!(loader.config().batch_size, 512);
+        assert_eq!(loader.config().num_workers, 2);
+    }
+    
+    #[test]
+    fn test_data_loader_clear_cache() {
+        let loader = DataLoader::with_defaults("/tmp/test.parquet");
+        // Should not panic even when cache is empty
+        loader.clear_cache();
+    }
+    
+    #[test]
+    fn test_data_loader_schema_before_load() {
+        let loader = DataLoader::with_defaults("/tmp/test.parquet");
+        // Schema should be None before loading
+        assert!(loader.schema().is_none());
+    }
+    
+    #[test]
+    fn test_data_loader_load_nonexistent_file() {
+        let loader = DataLoader::with_defaults("/nonexistent/path/data.parquet");
+        let result = loader.load();
+        assert!(result.is_err());
+    }
+    
+    #[test]
+    fn test_data_loader_load_unsupported_format() {
+        let loader = DataLoader::with_defaults("/tmp/data.xyz");
+        let result = loader.load();
+        assert!(result.is_err());
+    }
+    
+    // ===================== BatchIterator Tests =====================
+    
+    fn create_test_schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+        ]))
+    }
+    
+    fn create_test_batch(schema: &Arc<Schema>, values: Vec<i32>) -> RecordBatch {
+        let array = Int32Array::from(values);
+        RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap()
+    }
+    
+    #[test]
+    fn test_batch_iterator_creation() {
+        let schema = create_test_schema();
+        let batches = vec![
+            create_test_batch(&schema, vec![1, 2, 3]),
+            create_test_batch(&schema, vec![4, 5, 6]),
+        ];
+        
+        let iter = BatchIterator::new(schema.clone(), batches);
+        assert_eq!(iter.num_batches(), 2);
+        assert_eq!(iter.total_rows(), 6);
+    }
+    
+    #[test]
+    fn test_batch_iterator_schema() {
+        let schema = create_test_schema();
+        let batches = vec![create_test_batch(&schema, vec![1, 2, 3])];
+        
+        let iter = BatchIterator::new(schema.clone(), batches);
+        let iter_schema = iter.schema();
+        
+        assert_eq!(iter_schema.fields().len(), 1);
+        assert_eq!(iter_schema.field(0).name(), "id");
+    }
+    
+    #[test]
+    fn test_batch_iterator_empty() {
+        let schema = create_test_schema();
+        let iter = BatchIterator::new(schema, vec![]);
+        
+        assert_eq!(iter.num_batches(), 0);
+        assert_eq!(iter.total_rows(), 0);
+    }
+    
+    #[test]
+    fn test_batch_iterator_iteration() {
+        let schema = create_test_schema();
+        let batches = vec![
+            create_test_batch(&schema, vec![1, 2]),
+            create_test_batch(&schema, vec![3, 4]),
+        ];
+        
+        let mut iter = BatchIterator::new(schema, batches);
+        
+        let first = iter.next();
+        assert!(first.is_some());
+        assert_eq!(first.unwrap().num_rows(), 2);
+        
+        let second = iter.next();
+        assert!(second.is_some());
+        assert_eq!(second.unwrap().num_rows(), 2);
         
-        let mut all_batches = Vec::new();
-        let mut schema: Option<Arc<Schema>> = None;
+        let third = iter.next();
+        assert!(third.is_none());
+    }
+    
+    #[test]
+    fn test_batch_iterator_reset() {
+        let schema = create_test_schema();
+        let batches = vec![create_test_batch(&schema, vec![1, 2, 3])];
         
-        for entry in entries {
-            let file_path = entry.path().to_string_lossy().to_string();
-            let (file_schema, batches) = self.load_file(&file_path)?;
-            
-            if schema.is_none() {
-                schema = Some(file_schema);
-            }
-            
-            all_batches.extend(batches);
-        }
+        let mut iter = BatchIterator::new(schema, batches);
         
-        let schema = schema.ok_or_else(|| DataLoaderError::Empty("No files in directory".to_string()))?;
+        // Consume the iterator
+        let _ = iter.next();
+        assert!(iter.next().is_none());
         
-        Ok((schema, all_batches))
+        // Reset and iterate again
+        iter.reset();
+        assert!(iter.next().is_some());
     }
     
-    fn load_memory(&self, _data: &[u8]) -> Result<(Arc<Schema>, Vec<RecordBatch>), DataLoaderError> {
-        // TODO: Implement memory loading
-        Err(DataLoaderError::UnsupportedFormat("Memory loading not yet implemented".to_string()))
+    // ===================== DataLoaderError Tests =====================
+    
+    #[test]
+    fn test_error_io() {
+        let err = DataLoaderError::Io("file not found".to_string());
+        let msg = format!("{}", err);
+        assert!(msg.contains("I/O error"));
+        assert!(msg.contains("file not found"));
     }
     
-    /// Get loader configuration
-    pub fn config(&self) -> &LoaderConfig {
-        &self.config
+    #[test]
+    fn test_error_parse() {
+        let err = DataLoaderError::Parse("invalid format".to_string());
+        let msg = format!("{}", err);
+        assert!(msg.contains("Parse error"));
     }
     
-    /// Clear cached data
-    pub fn clear_cache(&self) {
-        *self.cached_batches.write() = None;
+    #[test]
+    fn test_error_unsupported_format() {
+        let err = DataLoaderError::UnsupportedFormat(".xyz".to_string());
+        let msg = format!("{}", err);
+        assert!(msg.contains("Unsupported format"));
     }
     
-    /// Get the cached schema if available
-    #[allow(dead_code)]
-    pub fn schema(&self) -> Option<Arc<Schema>> {
-        self.schema.clone()
+    #[test]
+    fn test_error_empty() {
+        let err = DataLoaderError::Empty("no data".to_string());
+        let msg = format!("{}", err);
+        assert!(msg.contains("Empty source"));
+    }
+    
+    #[test]
+    fn test_error_config() {
+        let err = DataLoaderError::Config("invalid batch size".to_string());
+        let msg = format!("{}", err);
+        assert!(msg.contains("Configuration error"));
     }
-}
 
-/// Data loader errors
-#[derive(Debug)]
-pub enum DataLoaderError {
-    /// I/O error
-    Io(String),
-    /// Parse error
-    Parse(String),
-    /// Unsupported format
-    UnsupportedFormat(String),
-    /// Empty source
-    Empty(String),
-    /// Configuration error
-    Config(String),
-}
+    #[test]
+    fn test_error_object_store() {
+        let err = DataLoaderError::ObjectStore("bucket not found".to_string());
+        let msg = format!("{}", err);
+        assert!(msg.contains("Object store error"));
+    }
 
-impl std::fmt::Display for DataLoaderError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Io(msg) => write!(f, "I/O error: {}", msg),
-            Self::Parse(msg) => write!(f, "Parse error: {}", msg),
-            Self::UnsupportedFormat(msg) => write!(f, "Unsupported format: {}", msg),
-            Self::Empty(msg) => write!(f, "Empty source: {}", msg),
-            Self::Config(msg) => write!(f, "Configuration error: {}", msg),
+    #[test]
+    #[cfg(not(feature = "object_store"))]
+    fn test_object_store_disabled_returns_error() {
+        let loader = DataLoader::new(
+            DataSource::ObjectStore { url: "s3://bucket/key.parquet".to_string() },
+            LoaderConfig::default(),
+        );
+        let result = loader.load();
+        assert!(matches!(result, Err(DataLoaderError::ObjectStore(_))));
+    }
+    
+    #[test]
+    fn test_error_debug() {
+        let err = DataLoaderError::Io("test".to_string());
+        let debug_str = format!("{:?}", err);
+        assert!(debug_str.contains("Io"));
+    }
+    
+    #[test]
+    fn test_error_is_std_error() {
+        let err: Box<dyn std::error::Error> = Box::new(DataLoaderError::Io("test".to_string()));
+        assert!(err.to_string().contains("I/O error"));
+    }
+    
+    // ========================================================================
+    // MUTATION-KILLING TESTS
+    // ========================================================================
+    
+    /// Test that clear_cache actually clears the cache (not a no-op)
+    /// Kills mutation: replace clear_cache with ()
+    #[test]
+    fn test_clear_cache_actually_clears() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+        
+        // Create a temporary parquet file
+        let schema = create_test_schema();
+        let batch = create_test_batch(&schema, vec![1, 2, 3, 4, 5]);
+        
+        let mut temp_file = NamedTempFile::with_suffix(".parquet").unwrap();
+        {
+            use parquet::arrow::ArrowWriter;
+            let mut writer = ArrowWriter::try_new(temp_file.as_file_mut(), schema.clone(), None).unwrap();
+            writer.write(&batch).unwrap();
+            writer.close().unwrap();
         }
+        
+        let loader = DataLoader::with_defaults(temp_file.path().to_str().unwrap());
+        
+        // Load to populate cache
+        let result = loader.load();
+        assert!(result.is_ok(), "Should load parquet file");
+        
+        // Verify cache is populated
+        {
+            let cache = loader.cached_batches.read();
+            assert!(cache.is_some(), "Cache should be populated after load");
+        }
+        
+        // Clear the cache
+        loader.clear_cache();
+        
+        // Verify cache is now empty
+        {
+            let cache = loader.cached_batches.read();
+            assert!(cache.is_none(), 
+                "clear_cache must actually clear the cache, not be a no-op");
+        }
+    }
+    
+    /// Test loading a real parquet file through load_file match arm
+    /// Kills mutation: delete match arm FileFormat::Parquet
+    #[test]
+    fn test_load_parquet_file() {
+        use tempfile::NamedTempFile;
+        
+        let schema = create_test_schema();
+        let batch = create_test_batch(&schema, vec![10, 20, 30]);
+        
+        let mut temp_file = NamedTempFile::with_suffix(".parquet").unwrap();
+        {
+            use parquet::arrow::ArrowWriter;
+            let mut writer = ArrowWriter::try_new(temp_file.as_file_mut(), schema.clone(), None).unwrap();
+            writer.write(&batch).unwrap();
+            writer.close().unwrap();
+        }
+        
+        let loader = DataLoader::with_defaults(temp_file.path().to_str().unwrap());
+        let result = loader.load();
+        
+        assert!(result.is_ok(), "Must be able to load parquet files");
+        let iter = result.unwrap();
+        assert_eq!(iter.total_rows(), 3, "Should have 3 rows from parquet");
+    }
+    
+    /// Test loading a real CSV file through load_file match arm
+    /// Kills mutation: delete match arm FileFormat::Csv
+    #[test]
+    fn test_load_csv_file() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+        
+        let mut temp_file = NamedTempFile::with_suffix(".csv").unwrap();
+        writeln!(temp_file, "id,value").unwrap();
+        writeln!(temp_file, "1,100").unwrap();
+        writeln!(temp_file, "2,200").unwrap();
+        writeln!(temp_file, "3,300").unwrap();
+        temp_file.flush().unwrap();
+        
+        let loader = DataLoader::with_defaults(temp_file.path().to_str().unwrap());
+        let result = loader.load();
+        
+        assert!(result.is_ok(), "Must be able to load CSV files");
+        let iter = result.unwrap();
+        assert_eq!(iter.total_rows(), 3, "Should have 3 rows from CSV");
+    }
+    
+    /// Test loading Arrow IPC file through load_file match arm  
+    /// Kills mutation: delete match arm FileFormat::ArrowIpc
+    #[test]
+    fn test_load_arrow_ipc_file() {
+        use tempfile::NamedTempFile;
+        use arrow::ipc::writer::FileWriter;
+        
+        let schema = create_test_schema();
+        let batch = create_test_batch(&schema, vec![100, 200]);
+        
+        let temp_file = NamedTempFile::with_suffix(".arrow").unwrap();
+        {
+            let mut writer = FileWriter::try_new(temp_file.as_file(), &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+        
+        let loader = DataLoader::with_defaults(temp_file.path().to_str().unwrap());
+        let result = loader.load();
+        
+        assert!(result.is_ok(), "Must be able to load Arrow IPC files");
+        let iter = result.unwrap();
+        assert_eq!(iter.total_rows(), 2, "Should have 2 rows from Arrow IPC");
+    }
+    
+    /// Test cache is populated and subsequent loads use cache
+    /// Kills mutations: cache size comparisons
+    #[test]
+    fn test_cache_is_populated_on_small_data() {
+        use tempfile::NamedTempFile;
+        
+        let schema = create_test_schema();
+        let batch = create_test_batch(&schema, vec![1, 2, 3]);
+        
+        let mut temp_file = NamedTempFile::with_suffix(".parquet").unwrap();
+        {
+            use parquet::arrow::ArrowWriter;
+            let mut writer = ArrowWriter::try_new(temp_file.as_file_mut(), schema.clone(), None).unwrap();
+            writer.write(&batch).unwrap();
+            writer.close().unwrap();
+        }
+        
+        let loader = DataLoader::with_defaults(temp_file.path().to_str().unwrap());
+        
+        // First load
+        let result1 = loader.load();
+        assert!(result1.is_ok());
+        
+        // Verify cache populated (small data < 100MB)
+        {
+            let cache = loader.cached_batches.read();
+            assert!(cache.is_some(), "Small data should be cached");
+            let batches = cache.as_ref().unwrap();
+            assert!(!batches.is_empty(), "Cached batches should not be empty");
+        }
+        
+        // Second load should use cache (test this indirectly)
+        let result2 = loader.load();
+        assert!(result2.is_ok());
+        assert_eq!(result2.unwrap().total_rows(), 3);
     }
-}
-
-impl std::error::Error for DataLoaderError {}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use arrow::datatypes::{DataType, Field};
-    use arrow::array::Int32Array;
-    
-    // ===================== FileFormat Tests =====================
     
+    /// Test FileFormat detection for all supported formats
+    /// Strengthens match arm testing
     #[test]
-    fn test_file_format_detection() {
+    fn test_file_format_all_variants() {
+        // Parquet variants
         assert_eq!(FileFormat::from_extension("data.parquet"), FileFormat::Parquet);
+        assert_eq!(FileFormat::from_extension("path/to/file.pq"), FileFormat::Parquet);
+        
+        // CSV variants
         assert_eq!(FileFormat::from_extension("data.csv"), FileFormat::Csv);
+        assert_eq!(FileFormat::from_extension("data.tsv"), FileFormat::Csv);
+        
+        // Arrow IPC variants
         assert_eq!(FileFormat::from_extension("data.arrow"), FileFormat::ArrowIpc);
-        assert_eq!(FileFormat::from_extension("data.unknown"), FileFormat::Unknown);
-    }
-    
-    #[test]
-    fn test_file_format_feather() {
-        // 'ipc' is not supported, but 'feather' is an alias for ArrowIpc
         assert_eq!(FileFormat::from_extension("data.feather"), FileFormat::ArrowIpc);
-    }
-    
-    #[test]
-    fn test_file_format_jsonl() {
+        
+        // JSON Lines
         assert_eq!(FileFormat::from_extension("data.jsonl"), FileFormat::JsonLines);
         assert_eq!(FileFormat::from_extension("data.ndjson"), FileFormat::JsonLines);
+        
+        // Unknown
+        assert_eq!(FileFormat::from_extension("data.txt"), FileFormat::Unknown);
+        assert_eq!(FileFormat::from_extension("no_extension"), FileFormat::Unknown);
     }
     
+    /// Test that load returns error for unsupported format
+    /// Verifies the _ match arm in load_file
     #[test]
-    fn test_file_format_uppercase() {
-        // Extensions are case-sensitive, uppercase should be unknown
-        assert_eq!(FileFormat::from_extension("data.PARQUET"), FileFormat::Unknown);
+    fn test_load_file_unsupported_returns_error() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+        
+        // Create a file with unknown extension
+        let mut temp_file = NamedTempFile::with_suffix(".xyz").unwrap();
+        writeln!(temp_file, "some data").unwrap();
+        temp_file.flush().unwrap();
+        
+        let loader = DataLoader::with_defaults(temp_file.path().to_str().unwrap());
+        let result = loader.load();
+        
+        assert!(result.is_err(), "Unknown format should return error");
+        match result {
+            Err(DataLoaderError::UnsupportedFormat(_)) => {}
+            _ => panic!("Expected UnsupportedFormat error"),
+        }
     }
     
-    #[test]
-    fn test_file_format_no_extension() {
-        assert_eq!(FileFormat::from_extension("data"), FileFormat::Unknown);
+    /// Test config() returns the correct configuration
+    #[test] 
+    fn test_config_returns_correct_values() {
+        let config = LoaderConfig {
+            batch_size: 512,
+            prefetch_count: 2,
+            num_workers: 8,
+            memory_map: false,
+            io_buffer_size: 4 * 1024 * 1024,
+            extension_filter: None,
+            projection: None,
+            row_filter: None,
+            limit: None,
+            assume_immutable: false,
+        };
+        let loader = DataLoader::new(DataSource::File("test.parquet".to_string()), config);
+        
+        let c = loader.config();
+        assert_eq!(c.batch_size, 512);
+        assert_eq!(c.prefetch_count, 2);
+        assert_eq!(c.num_workers, 8);
+        assert!(!c.memory_map);
+        assert_eq!(c.io_buffer_size, 4 * 1024 * 1024);
     }
-    
-    #[test]
-    fn test_file_format_clone_copy() {
-        let format = FileFormat::Parquet;
-        let cloned = format.clone();
-        let copied = format;
-        assert_eq!(format, cloned);
-        assert_eq!(format, copied);
+
+    // ===================== FormatRegistry Tests =====================
+
+    struct FixedSchemaFormatReader;
+
+    impl FileFormatReader for FixedSchemaFormatReader {
+        fn extensions(&self) -> &[&str] {
+            &["xyz"]
+        }
+
+        fn infer_schema(&self, _path: &str, _config: &LoaderConfig) -> Result<Arc<Schema>, DataLoaderError> {
+            Ok(create_test_schema())
+        }
+
+        fn read(&self, _path: &str, _config: &LoaderConfig) -> Result<Vec<RecordBatch>, DataLoaderError> {
+            Ok(vec![create_test_batch(&create_test_schema(), vec![1, 2, 3])])
+        }
     }
-    
+
     #[test]
-    fn test_file_format_debug() {
-        let format = FileFormat::Parquet;
-        let debug_str = format!("{:?}", format);
-        assert!(debug_str.contains("Parquet"));
+    fn test_format_registry_built_ins_cover_known_extensions() {
+        let registry = FormatRegistry::new();
+        assert!(registry.reader_for("data.parquet").is_some());
+        assert!(registry.reader_for("data.pq").is_some());
+        assert!(registry.reader_for("data.csv").is_some());
+        assert!(registry.reader_for("data.tsv").is_some());
+        assert!(registry.reader_for("data.arrow").is_some());
+        assert!(registry.reader_for("data.feather").is_some());
+        assert!(registry.reader_for("data.jsonl").is_some());
+        assert!(registry.reader_for("data.unknown").is_none());
     }
-    
-    // ===================== LoaderConfig Tests =====================
-    
+
     #[test]
-    fn test_loader_config_default() {
-        let config = LoaderConfig::default();
-        assert_eq!(config.batch_size, 1024);
-        assert_eq!(config.num_workers, 4);
-        assert_eq!(config.prefetch_count, 4);
-        assert!(config.memory_map);
-        assert_eq!(config.io_buffer_size, 8 * 1024 * 1024);
+    fn test_format_registry_register_custom_reader() {
+        let registry = FormatRegistry::new();
+        assert!(registry.reader_for("data.xyz").is_none());
+
+        registry.register(Arc::new(FixedSchemaFormatReader));
+        assert!(registry.reader_for("data.xyz").is_some());
     }
-    
+
     #[test]
-    fn test_loader_config_custom() {
-        let config = LoaderConfig {
-            batch_size: 2048,
-            num_workers: 8,
-            prefetch_count: 8,
-            memory_map: false,
-            io_buffer_size: 4 * 1024 * 1024,
-        };
-        assert_eq!(config.batch_size, 2048);
-        assert_eq!(config.num_workers, 8);
-        assert!(!config.memory_map);
+    fn test_load_file_dispatches_through_custom_format_reader() {
+        let loader = DataLoader::with_defaults("data.xyz");
+        loader.formats().register(Arc::new(FixedSchemaFormatReader));
+
+        let result = loader.load();
+        assert!(result.is_ok(), "custom registered reader should handle .xyz");
+        assert_eq!(result.unwrap().total_rows(), 3);
     }
-    
+
+    // ===================== JSON Lines Tests =====================
+
+    fn write_jsonl(lines: &[&str]) -> tempfile::NamedTempFile {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".jsonl").unwrap();
+        for line in lines {
+            writeln!(file, "{}", line).unwrap();
+        }
+        file.flush().unwrap();
+        file
+    }
+
     #[test]
-    fn test_loader_config_clone() {
-        let config = LoaderConfig::default();
-        let cloned = config.clone();
-        assert_eq!(config.batch_size, cloned.batch_size);
-        assert_eq!(config.num_workers, cloned.num_workers);
+    fn test_jsonl_infers_schema_and_decodes_rows() {
+        let file = write_jsonl(&[
+            r#"{"id": 1, "name": "a", "score": 1.5}"#,
+            r#"{"id": 2, "name": "b", "score": 2.5}"#,
+            r#"{"id": 3, "name": "c", "score": 3.5}"#,
+        ]);
+
+        let loader = DataLoader::with_defaults(file.path().to_str().unwrap());
+        let result = loader.load();
+        assert!(result.is_ok(), "should decode a well-formed JSON Lines file");
+
+        let iter = result.unwrap();
+        assert_eq!(iter.total_rows(), 3);
+        let schema = iter.schema();
+        assert_eq!(schema.field_with_name("id").unwrap().data_type(), &DataType::Int64);
+        assert_eq!(schema.field_with_name("name").unwrap().data_type(), &DataType::Utf8);
+        assert_eq!(schema.field_with_name("score").unwrap().data_type(), &DataType::Float64);
     }
-    
+
     #[test]
-    fn test_loader_config_debug() {
-        let config = LoaderConfig::default();
-        let debug_str = format!("{:?}", config);
-        assert!(debug_str.contains("LoaderConfig"));
-        assert!(debug_str.contains("batch_size"));
+    fn test_jsonl_widens_conflicting_numeric_types() {
+        let file = write_jsonl(&[r#"{"value": 1}"#, r#"{"value": 2.5}"#]);
+
+        let reader = JsonLinesFormatReader;
+        let schema = reader.infer_schema(file.path().to_str().unwrap(), &LoaderConfig::default()).unwrap();
+        assert_eq!(schema.field_with_name("value").unwrap().data_type(), &DataType::Float64);
     }
-    
-    // ===================== DataSource Tests =====================
-    
+
     #[test]
-    fn test_data_source_from_path_file() {
-        let source = DataSource::from_path("/tmp/test.parquet");
-        match source {
-            DataSource::File(p) => assert!(p.contains("test.parquet")),
-            _ => panic!("Expected File variant"),
-        }
+    fn test_jsonl_falls_back_to_utf8_on_incompatible_types() {
+        let file = write_jsonl(&[r#"{"value": true}"#, r#"{"value": "yes"}"#]);
+
+        let reader = JsonLinesFormatReader;
+        let schema = reader.infer_schema(file.path().to_str().unwrap(), &LoaderConfig::default()).unwrap();
+        assert_eq!(schema.field_with_name("value").unwrap().data_type(), &DataType::Utf8);
     }
-    
+
     #[test]
-    fn test_data_source_directory() {
-        // DataSource::Directory variant can be created directly
-        let source = DataSource::Directory("/tmp/data".to_string());
-        match source {
-            DataSource::Directory(p) => assert!(p.contains("data")),
-            _ => panic!("Expected Directory variant"),
-        }
+    fn test_jsonl_missing_field_decodes_as_null() {
+        let file = write_jsonl(&[r#"{"id": 1, "name": "a"}"#, r#"{"id": 2}"#]);
+
+        let loader = DataLoader::with_defaults(file.path().to_str().unwrap());
+        let iter = loader.load().unwrap();
+        assert_eq!(iter.total_rows(), 2);
     }
-    
+
     #[test]
-    fn test_data_source_memory() {
-        let data = vec![1u8, 2, 3, 4, 5];
-        let source = DataSource::Memory(data.clone());
-        match source {
-            DataSource::Memory(d) => assert_eq!(d.len(), 5),
-            _ => panic!("Expected Memory variant"),
-        }
+    fn test_jsonl_tolerates_blank_and_trailing_lines() {
+        let file = write_jsonl(&[r#"{"id": 1}"#, "", r#"{"id": 2}"#, ""]);
+
+        let loader = DataLoader::with_defaults(file.path().to_str().unwrap());
+        let iter = loader.load().unwrap();
+        assert_eq!(iter.total_rows(), 2);
     }
-    
+
     #[test]
-    fn test_data_source_clone() {
-        let source = DataSource::File("test.parquet".to_string());
-        let cloned = source.clone();
-        match (source, cloned) {
-            (DataSource::File(a), DataSource::File(b)) => assert_eq!(a, b),
-            _ => panic!("Clone mismatch"),
-        }
+    fn test_jsonl_nested_value_flattens_to_json_text() {
+        let file = write_jsonl(&[r#"{"meta": {"a": 1}}"#, r#"{"meta": {"b": 2}}"#]);
+
+        let loader = DataLoader::with_defaults(file.path().to_str().unwrap());
+        let iter = loader.load().unwrap();
+        let schema = iter.schema();
+        assert_eq!(schema.field_with_name("meta").unwrap().data_type(), &DataType::Utf8);
     }
-    
+
+    // ===================== Streaming Tests =====================
+
+    fn write_parquet_batches(batches: &[(Arc<Schema>, RecordBatch)]) -> Vec<tempfile::NamedTempFile> {
+        use parquet::arrow::ArrowWriter;
+
+        batches
+            .iter()
+            .map(|(schema, batch)| {
+                let mut file = tempfile::NamedTempFile::with_suffix(".parquet").unwrap();
+                let mut writer = ArrowWriter::try_new(file.as_file_mut(), schema.clone(), None).unwrap();
+                writer.write(batch).unwrap();
+                writer.close().unwrap();
+                file
+            })
+            .collect()
+    }
+
     #[test]
-    fn test_data_source_debug() {
-        let source = DataSource::File("test.parquet".to_string());
-        let debug_str = format!("{:?}", source);
-        assert!(debug_str.contains("File"));
-        assert!(debug_str.contains("test.parquet"));
+    fn test_stream_file_yields_all_batches_lazily() {
+        let schema = create_test_schema();
+        let batch = create_test_batch(&schema, vec![1, 2, 3, 4, 5]);
+        let files = write_parquet_batches(&[(schema, batch)]);
+
+        let config = LoaderConfig { batch_size: 2, ..LoaderConfig::default() };
+        let loader = DataLoader::new(DataSource::File(files[0].path().to_str().unwrap().to_string()), config);
+
+        let stream = loader.stream().expect("stream() should open a file source");
+        assert_eq!(stream.schema().field(0).name(), "id");
+
+        let rows: usize = stream.map(|b| b.expect("no reader errors expected").num_rows()).sum();
+        assert_eq!(rows, 5, "streaming must yield every row across batches");
     }
-    
-    // ===================== DataLoader Tests =====================
-    
+
     #[test]
-    fn test_data_loader_creation() {
-        let source = DataSource::File("test.parquet".to_string());
-        let config = LoaderConfig::default();
-        let loader = DataLoader::new(source, config);
-        
-        assert_eq!(loader.config().batch_size, 1024);
+    fn test_stream_directory_decodes_every_file() {
+        let schema = create_test_schema();
+        let files = write_parquet_batches(&[
+            (schema.clone(), create_test_batch(&schema, vec![1, 2])),
+            (schema.clone(), create_test_batch(&schema, vec![3, 4, 5])),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        for (i, file) in files.iter().enumerate() {
+            std::fs::copy(file.path(), dir.path().join(format!("part-{}.parquet", i))).unwrap();
+        }
+
+        let loader = DataLoader::new(DataSource::Directory(dir.path().to_str().unwrap().to_string()), LoaderConfig::default());
+        let stream = loader.stream().expect("stream() should open a directory source");
+
+        let rows: usize = stream.map(|b| b.expect("no reader errors expected").num_rows()).sum();
+        assert_eq!(rows, 5, "streaming a directory must decode every file");
     }
-    
+
     #[test]
-    fn test_data_loader_with_defaults() {
-        let loader = DataLoader::with_defaults("/tmp/test.parquet");
-        assert_eq!(loader.config().batch_size, 1024);
+    fn test_stream_reset_replays_from_the_beginning() {
+        let schema = create_test_schema();
+        let batch = create_test_batch(&schema, vec![1, 2, 3]);
+        let files = write_parquet_batches(&[(schema, batch)]);
+
+        let loader = DataLoader::with_defaults(files[0].path().to_str().unwrap());
+        let mut stream = loader.stream().unwrap();
+
+        let first_pass: usize = (&mut stream).map(|b| b.unwrap().num_rows()).sum();
+        assert_eq!(first_pass, 3);
+
+        stream.reset().expect("reset should re-open the source");
+        let second_pass: usize = stream.map(|b| b.unwrap().num_rows()).sum();
+        assert_eq!(second_pass, 3, "reset must let the stream be replayed from the start");
     }
-    
+
     #[test]
-    fn test_data_loader_config_access() {
-        let config = LoaderConfig {
-            batch_size: 512,
-            num_workers: 2,
-            prefetch_count: 1,
-            memory_map: false,
-            io_buffer_size: 1024 * 1024,
-        };
-        let loader = DataLoader::new(DataSource::File("test.csv".to_string()), config);
-        
-        assert_eq!(loader.config().batch_size, 512);
-        assert_eq!(loader.config().num_workers, 2);
+    fn test_stream_propagates_reader_errors_through_next() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".csv").unwrap();
+        writeln!(file, "not,a,valid,header\nthis,isn't,csv,either,extra_column").unwrap();
+        file.flush().unwrap();
+
+        let loader = DataLoader::with_defaults(file.path().to_str().unwrap());
+        let mut stream = loader.stream().unwrap();
+
+        let outcome = stream.find(|b| b.is_err());
+        assert!(outcome.is_some(), "a malformed row should surface as an error from next(), not a panic");
     }
-    
+
     #[test]
-    fn test_data_loader_clear_cache() {
-        let loader = DataLoader::with_defaults("/tmp/test.parquet");
-        // Should not panic even when cache is empty
-        loader.clear_cache();
+    fn test_stream_unsupported_for_memory_and_object_store() {
+        let loader = DataLoader::new(DataSource::Memory(vec![1, 2, 3]), LoaderConfig::default());
+        assert!(matches!(loader.stream(), Err(DataLoaderError::UnsupportedFormat(_))));
+
+        let loader = DataLoader::new(
+            DataSource::ObjectStore { url: "s3://bucket/key.parquet".to_string() },
+            LoaderConfig::default(),
+        );
+        assert!(matches!(loader.stream(), Err(DataLoaderError::UnsupportedFormat(_))));
     }
-    
+
+    // ===================== Directory Partition Tests =====================
+
     #[test]
-    fn test_data_loader_schema_before_load() {
-        let loader = DataLoader::with_defaults("/tmp/test.parquet");
-        // Schema should be None before loading
-        assert!(loader.schema().is_none());
+    fn test_load_directory_concatenates_every_file_in_deterministic_order() {
+        let schema = create_test_schema();
+        let files = write_parquet_batches(&[
+            (schema.clone(), create_test_batch(&schema, vec![1, 2])),
+            (schema.clone(), create_test_batch(&schema, vec![3, 4, 5])),
+            (schema.clone(), create_test_batch(&schema, vec![6])),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        for (i, file) in files.iter().enumerate() {
+            std::fs::copy(file.path(), dir.path().join(format!("part-{}.parquet", i))).unwrap();
+        }
+
+        let loader = DataLoader::new(DataSource::Directory(dir.path().to_str().unwrap().to_string()), LoaderConfig::default());
+        let iter = loader.load().expect("directory of matching-schema parquet files should load");
+        assert_eq!(iter.total_rows(), 6);
+
+        // Deterministic (sorted-by-path) ordering: part-0 before part-1 before part-2.
+        let partitions = loader.load_directory_partitions(dir.path().to_str().unwrap()).unwrap();
+        let row_counts: Vec<usize> = partitions.iter().map(|p| p.batches.iter().map(|b| b.num_rows()).sum()).collect();
+        assert_eq!(row_counts, vec![2, 3, 1]);
     }
-    
+
     #[test]
-    fn test_data_loader_load_nonexistent_file() {
-        let loader = DataLoader::with_defaults("/nonexistent/path/data.parquet");
+    fn test_load_directory_rejects_mismatched_schemas() {
+        let wide_schema = create_test_schema();
+        let narrow_schema = Arc::new(Schema::new(vec![Field::new("other", DataType::Utf8, false)]));
+
+        let files = write_parquet_batches(&[(wide_schema.clone(), create_test_batch(&wide_schema, vec![1, 2]))]);
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::copy(files[0].path(), dir.path().join("a.parquet")).unwrap();
+
+        {
+            use arrow::array::StringArray;
+            use parquet::arrow::ArrowWriter;
+            let mut file = tempfile::NamedTempFile::with_suffix(".parquet").unwrap();
+            let batch =
+                RecordBatch::try_new(narrow_schema.clone(), vec![Arc::new(StringArray::from(vec!["x"]))]).unwrap();
+            let mut writer = ArrowWriter::try_new(file.as_file_mut(), narrow_schema, None).unwrap();
+            writer.write(&batch).unwrap();
+            writer.close().unwrap();
+            std::fs::copy(file.path(), dir.path().join("b.parquet")).unwrap();
+        }
+
+        let loader = DataLoader::new(DataSource::Directory(dir.path().to_str().unwrap().to_string()), LoaderConfig::default());
         let result = loader.load();
-        assert!(result.is_err());
+        assert!(
+            matches!(result, Err(DataLoaderError::SchemaMismatch(_))),
+            "mismatched per-file schemas must be rejected"
+        );
     }
-    
+
     #[test]
-    fn test_data_loader_load_unsupported_format() {
-        let loader = DataLoader::with_defaults("/tmp/data.xyz");
-        let result = loader.load();
-        assert!(result.is_err());
-    }
-    
-    // ===================== BatchIterator Tests =====================
-    
-    fn create_test_schema() -> Arc<Schema> {
-        Arc::new(Schema::new(vec![
-            Field::new("id", DataType::Int32, false),
-        ]))
+    fn test_load_directory_extension_filter_selects_only_matching_files() {
+        let schema = create_test_schema();
+        let files = write_parquet_batches(&[(schema.clone(), create_test_batch(&schema, vec![1, 2, 3]))]);
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::copy(files[0].path(), dir.path().join("data.parquet")).unwrap();
+        std::fs::write(dir.path().join("readme.txt"), b"not a data file").unwrap();
+
+        let config = LoaderConfig { extension_filter: Some(vec!["parquet".to_string()]), ..LoaderConfig::default() };
+        let loader = DataLoader::new(DataSource::Directory(dir.path().to_str().unwrap().to_string()), config);
+
+        let iter = loader.load().expect("extension_filter should select only the .parquet file");
+        assert_eq!(iter.total_rows(), 3);
     }
-    
-    fn create_test_batch(schema: &Arc<Schema>, values: Vec<i32>) -> RecordBatch {
-        let array = Int32Array::from(values);
-        RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap()
+
+    // ===================== Glob / Schema Reconciliation Tests =====================
+
+    #[test]
+    fn test_segment_matches_single_star_wildcard() {
+        assert!(segment_matches("part-0.parquet", "*.parquet"));
+        assert!(segment_matches("part-0.parquet", "part-*.parquet"));
+        assert!(!segment_matches("part-0.csv", "*.parquet"));
+        assert!(segment_matches("anything", "*"));
+        assert!(segment_matches("exact.txt", "exact.txt"));
+        assert!(!segment_matches("a", "a*b"));
     }
-    
+
     #[test]
-    fn test_batch_iterator_creation() {
+    fn test_expand_glob_single_star_matches_one_directory_level() {
         let schema = create_test_schema();
-        let batches = vec![
-            create_test_batch(&schema, vec![1, 2, 3]),
-            create_test_batch(&schema, vec![4, 5, 6]),
-        ];
-        
-        let iter = BatchIterator::new(schema.clone(), batches);
-        assert_eq!(iter.num_batches(), 2);
-        assert_eq!(iter.total_rows(), 6);
+        let files = write_parquet_batches(&[
+            (schema.clone(), create_test_batch(&schema, vec![1])),
+            (schema.clone(), create_test_batch(&schema, vec![2])),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::copy(files[0].path(), dir.path().join("part-0.parquet")).unwrap();
+        std::fs::copy(files[1].path(), dir.path().join("part-1.parquet")).unwrap();
+        std::fs::write(dir.path().join("readme.txt"), b"not data").unwrap();
+
+        let pattern = format!("{}/*.parquet", dir.path().to_str().unwrap());
+        let matches = expand_glob(&pattern).unwrap();
+        assert_eq!(matches.len(), 2);
     }
-    
+
     #[test]
-    fn test_batch_iterator_schema() {
+    fn test_expand_glob_double_star_matches_nested_directories() {
         let schema = create_test_schema();
-        let batches = vec![create_test_batch(&schema, vec![1, 2, 3])];
-        
-        let iter = BatchIterator::new(schema.clone(), batches);
-        let iter_schema = iter.schema();
-        
-        assert_eq!(iter_schema.fields().len(), 1);
-        assert_eq!(iter_schema.field(0).name(), "id");
+        let files = write_parquet_batches(&[
+            (schema.clone(), create_test_batch(&schema, vec![1])),
+            (schema.clone(), create_test_batch(&schema, vec![2, 3])),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::copy(files[0].path(), dir.path().join("top.parquet")).unwrap();
+        let nested = dir.path().join("year=2026").join("month=07");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::copy(files[1].path(), nested.join("nested.parquet")).unwrap();
+
+        let pattern = format!("{}/**/*.parquet", dir.path().to_str().unwrap());
+        let matches = expand_glob(&pattern).unwrap();
+        assert_eq!(matches.len(), 2, "** should match both the top-level and the nested file");
     }
-    
+
     #[test]
-    fn test_batch_iterator_empty() {
+    fn test_load_glob_unifies_matched_files_and_skips_unknown_extensions() {
         let schema = create_test_schema();
-        let iter = BatchIterator::new(schema, vec![]);
-        
-        assert_eq!(iter.num_batches(), 0);
-        assert_eq!(iter.total_rows(), 0);
+        let files = write_parquet_batches(&[
+            (schema.clone(), create_test_batch(&schema, vec![1, 2])),
+            (schema.clone(), create_test_batch(&schema, vec![3, 4, 5])),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::copy(files[0].path(), dir.path().join("a.parquet")).unwrap();
+        std::fs::copy(files[1].path(), dir.path().join("b.parquet")).unwrap();
+        std::fs::write(dir.path().join("notes.md"), b"unrelated file").unwrap();
+
+        let pattern = format!("{}/*", dir.path().to_str().unwrap());
+        let loader = DataLoader::new(DataSource::Glob(pattern), LoaderConfig::default());
+        let iter = loader.load().expect("glob should skip notes.md and load only the parquet files");
+        assert_eq!(iter.total_rows(), 5);
     }
-    
+
     #[test]
-    fn test_batch_iterator_iteration() {
-        let schema = create_test_schema();
-        let batches = vec![
-            create_test_batch(&schema, vec![1, 2]),
-            create_test_batch(&schema, vec![3, 4]),
-        ];
-        
-        let mut iter = BatchIterator::new(schema, batches);
-        
-        let first = iter.next();
-        assert!(first.is_some());
-        assert_eq!(first.unwrap().num_rows(), 2);
-        
-        let second = iter.next();
-        assert!(second.is_some());
-        assert_eq!(second.unwrap().num_rows(), 2);
-        
-        let third = iter.next();
-        assert!(third.is_none());
+    fn test_schema_compatible_tolerates_map_field_naming_differences() {
+        let arrow_native_map = DataType::Map(
+            Arc::new(Field::new(
+                "entries",
+                DataType::Struct(
+                    vec![Field::new("keys", DataType::Utf8, false), Field::new("values", DataType::Int32, true)].into(),
+                ),
+                false,
+            )),
+            false,
+        );
+        let parquet_style_map = DataType::Map(
+            Arc::new(Field::new(
+                "key_value",
+                DataType::Struct(
+                    vec![Field::new("key", DataType::Utf8, false), Field::new("value", DataType::Int32, true)].into(),
+                ),
+                false,
+            )),
+            false,
+        );
+
+        let a = Schema::new(vec![Field::new("m", arrow_native_map, true)]);
+        let b = Schema::new(vec![Field::new("m", parquet_style_map, true)]);
+        assert!(schema_compatible(&a, &b), "differently-named Map entry fields should still be compatible");
     }
-    
+
     #[test]
-    fn test_batch_iterator_reset() {
+    fn test_schema_compatible_rejects_true_type_mismatch() {
+        let a = Schema::new(vec![Field::new("id", DataType::Int32, false)]);
+        let b = Schema::new(vec![Field::new("id", DataType::Utf8, false)]);
+        assert!(!schema_compatible(&a, &b));
+    }
+
+    // ===================== Cache Invalidation Tests =====================
+
+    fn write_parquet_at(path: &std::path::Path, schema: &Arc<Schema>, batch: &RecordBatch) {
+        use parquet::arrow::ArrowWriter;
+        let file = std::fs::File::create(path).unwrap();
+        let mut writer = ArrowWriter::try_new(file, schema.clone(), None).unwrap();
+        writer.write(batch).unwrap();
+        writer.close().unwrap();
+    }
+
+    #[test]
+    fn test_load_revalidates_cache_when_file_size_changes() {
         let schema = create_test_schema();
-        let batches = vec![create_test_batch(&schema, vec![1, 2, 3])];
-        
-        let mut iter = BatchIterator::new(schema, batches);
-        
-        // Consume the iterator
-        let _ = iter.next();
-        assert!(iter.next().is_none());
-        
-        // Reset and iterate again
-        iter.reset();
-        assert!(iter.next().is_some());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.parquet");
+        write_parquet_at(&path, &schema, &create_test_batch(&schema, vec![1, 2]));
+
+        let loader = DataLoader::with_defaults(path.to_str().unwrap());
+        assert_eq!(loader.load().unwrap().total_rows(), 2);
+
+        // Overwrite the same path with a different row count (and thus a
+        // different file length), without touching the cache directly.
+        write_parquet_at(&path, &schema, &create_test_batch(&schema, vec![1, 2, 3, 4, 5]));
+
+        assert_eq!(
+            loader.load().unwrap().total_rows(),
+            5,
+            "a changed file size must invalidate the cache and re-read the new content"
+        );
     }
-    
-    // ===================== DataLoaderError Tests =====================
-    
+
     #[test]
-    fn test_error_io() {
-        let err = DataLoaderError::Io("file not found".to_string());
-        let msg = format!("{}", err);
-        assert!(msg.contains("I/O error"));
-        assert!(msg.contains("file not found"));
+    fn test_assume_immutable_skips_revalidation_and_serves_stale_cache() {
+        let schema = create_test_schema();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.parquet");
+        write_parquet_at(&path, &schema, &create_test_batch(&schema, vec![1, 2]));
+
+        let config = LoaderConfig { assume_immutable: true, ..LoaderConfig::default() };
+        let loader = DataLoader::new(DataSource::File(path.to_str().unwrap().to_string()), config);
+        assert_eq!(loader.load().unwrap().total_rows(), 2);
+
+        write_parquet_at(&path, &schema, &create_test_batch(&schema, vec![1, 2, 3, 4, 5]));
+
+        assert_eq!(
+            loader.load().unwrap().total_rows(),
+            2,
+            "assume_immutable must skip the stat check and keep serving the stale cache"
+        );
     }
-    
+
     #[test]
-    fn test_error_parse() {
-        let err = DataLoaderError::Parse("invalid format".to_string());
-        let msg = format!("{}", err);
-        assert!(msg.contains("Parse error"));
+    fn test_clear_cache_forces_reload_even_with_assume_immutable() {
+        let schema = create_test_schema();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.parquet");
+        write_parquet_at(&path, &schema, &create_test_batch(&schema, vec![1, 2]));
+
+        let config = LoaderConfig { assume_immutable: true, ..LoaderConfig::default() };
+        let loader = DataLoader::new(DataSource::File(path.to_str().unwrap().to_string()), config);
+        assert_eq!(loader.load().unwrap().total_rows(), 2);
+
+        write_parquet_at(&path, &schema, &create_test_batch(&schema, vec![1, 2, 3, 4, 5]));
+        loader.clear_cache();
+
+        assert_eq!(loader.load().unwrap().total_rows(), 5);
     }
-    
+
+    // ===================== Projection / Row Filter / Limit Tests =====================
+
+    fn create_wide_test_schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, false),
+            Field::new("score", DataType::Float64, false),
+        ]))
+    }
+
+    fn write_parquet_wide(schema: &Arc<Schema>, ids: Vec<i64>, names: Vec<&str>, scores: Vec<f64>) -> tempfile::NamedTempFile {
+        use arrow::array::{Float64Array, StringArray};
+        use parquet::arrow::ArrowWriter;
+
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(arrow::array::Int64Array::from(ids)),
+                Arc::new(StringArray::from(names)),
+                Arc::new(Float64Array::from(scores)),
+            ],
+        )
+        .unwrap();
+
+        let mut file = tempfile::NamedTempFile::with_suffix(".parquet").unwrap();
+        let mut writer = ArrowWriter::try_new(file.as_file_mut(), schema.clone(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        file
+    }
+
     #[test]
-    fn test_error_unsupported_format() {
-        let err = DataLoaderError::UnsupportedFormat(".xyz".to_string());
-        let msg = format!("{}", err);
-        assert!(msg.contains("Unsupported format"));
+    fn test_parquet_projection_pushes_down_and_reorders_schema() {
+        let schema = create_wide_test_schema();
+        let file = write_parquet_wide(&schema, vec![1, 2, 3], vec!["a", "b", "c"], vec![1.5, 2.5, 3.5]);
+
+        let config = LoaderConfig { projection: Some(vec!["score".to_string(), "id".to_string()]), ..LoaderConfig::default() };
+        let loader = DataLoader::new(DataSource::File(file.path().to_str().unwrap().to_string()), config);
+
+        let iter = loader.load().expect("projection should select only the requested columns");
+        let schema = iter.schema();
+        assert_eq!(schema.fields().len(), 2);
+        assert_eq!(schema.field(0).name(), "score");
+        assert_eq!(schema.field(1).name(), "id");
     }
-    
+
     #[test]
-    fn test_error_empty() {
-        let err = DataLoaderError::Empty("no data".to_string());
-        let msg = format!("{}", err);
-        assert!(msg.contains("Empty source"));
+    fn test_projection_rejects_unknown_column() {
+        let schema = create_wide_test_schema();
+        let file = write_parquet_wide(&schema, vec![1], vec!["a"], vec![1.0]);
+
+        let config = LoaderConfig { projection: Some(vec!["nope".to_string()]), ..LoaderConfig::default() };
+        let loader = DataLoader::new(DataSource::File(file.path().to_str().unwrap().to_string()), config);
+
+        assert!(matches!(loader.load(), Err(DataLoaderError::Config(_))));
     }
-    
+
     #[test]
-    fn test_error_config() {
-        let err = DataLoaderError::Config("invalid batch size".to_string());
-        let msg = format!("{}", err);
-        assert!(msg.contains("Configuration error"));
+    fn test_csv_projection_emulates_column_drop_and_reorder() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".csv").unwrap();
+        writeln!(file, "id,name,score").unwrap();
+        writeln!(file, "1,a,1.5").unwrap();
+        writeln!(file, "2,b,2.5").unwrap();
+        file.flush().unwrap();
+
+        let config = LoaderConfig { projection: Some(vec!["name".to_string(), "id".to_string()]), ..LoaderConfig::default() };
+        let loader = DataLoader::new(DataSource::File(file.path().to_str().unwrap().to_string()), config);
+
+        let iter = loader.load().expect("CSV should emulate projection post-decode");
+        let schema = iter.schema();
+        assert_eq!(schema.fields().len(), 2);
+        assert_eq!(schema.field(0).name(), "name");
+        assert_eq!(schema.field(1).name(), "id");
     }
-    
+
     #[test]
-    fn test_error_debug() {
-        let err = DataLoaderError::Io("test".to_string());
-        let debug_str = format!("{:?}", err);
-        assert!(debug_str.contains("Io"));
+    fn test_row_filter_keeps_only_matching_rows() {
+        let file = write_jsonl(&[
+            r#"{"id": 1, "name": "a", "score": 1.5}"#,
+            r#"{"id": 2, "name": "b", "score": 2.5}"#,
+            r#"{"id": 3, "name": "c", "score": 3.5}"#,
+        ]);
+
+        let config = LoaderConfig {
+            row_filter: Some(RowFilter { column: "id".to_string(), op: ComparisonOp::Gt, value: FilterValue::Int64(1) }),
+            ..LoaderConfig::default()
+        };
+        let loader = DataLoader::new(DataSource::File(file.path().to_str().unwrap().to_string()), config);
+
+        let iter = loader.load().expect("row_filter should keep only matching rows");
+        assert_eq!(iter.total_rows(), 2);
     }
-    
+
     #[test]
-    fn test_error_is_std_error() {
-        let err: Box<dyn std::error::Error> = Box::new(DataLoaderError::Io("test".to_string()));
-        assert!(err.to_string().contains("I/O error"));
+    fn test_row_filter_type_mismatch_is_a_config_error() {
+        let file = write_jsonl(&[r#"{"id": 1, "name": "a", "score": 1.5}"#]);
+
+        let config = LoaderConfig {
+            row_filter: Some(RowFilter {
+                column: "id".to_string(),
+                op: ComparisonOp::Eq,
+                value: FilterValue::Utf8("1".to_string()),
+            }),
+            ..LoaderConfig::default()
+        };
+        let loader = DataLoader::new(DataSource::File(file.path().to_str().unwrap().to_string()), config);
+
+        assert!(matches!(loader.load(), Err(DataLoaderError::Config(_))));
     }
-    
-    // ========================================================================
-    // MUTATION-KILLING TESTS
-    // ========================================================================
-    
-    /// Test that clear_cache actually clears the cache (not a no-op)
-    /// Kills mutation: replace clear_cache with ()
+
     #[test]
-    fn test_clear_cache_actually_clears() {
-        use std::io::Write;
-        use tempfile::NamedTempFile;
-        
-        // Create a temporary parquet file
+    fn test_limit_truncates_the_straddling_batch() {
         let schema = create_test_schema();
         let batch = create_test_batch(&schema, vec![1, 2, 3, 4, 5]);
-        
-        let mut temp_file = NamedTempFile::with_suffix(".parquet").unwrap();
-        {
-            use parquet::arrow::ArrowWriter;
-            let mut writer = ArrowWriter::try_new(temp_file.as_file_mut(), schema.clone(), None).unwrap();
+        let files = write_parquet_batches(&[(schema, batch)]);
+
+        let config = LoaderConfig { batch_size: 2, limit: Some(3), ..LoaderConfig::default() };
+        let loader = DataLoader::new(DataSource::File(files[0].path().to_str().unwrap().to_string()), config);
+
+        let iter = loader.load().expect("limit should truncate rather than error");
+        assert_eq!(iter.total_rows(), 3, "limit must truncate the batch straddling the boundary, not drop it whole");
+    }
+
+    // ===================== Row-Group Pruning Tests =====================
+
+    fn write_parquet_wide_row_groups(schema: &Arc<Schema>, groups: &[(Vec<i64>, Vec<&str>, Vec<f64>)]) -> tempfile::NamedTempFile {
+        use arrow::array::{Float64Array, StringArray};
+        use parquet::arrow::ArrowWriter;
+
+        let mut file = tempfile::NamedTempFile::with_suffix(".parquet").unwrap();
+        let mut writer = ArrowWriter::try_new(file.as_file_mut(), schema.clone(), None).unwrap();
+        for (ids, names, scores) in groups {
+            let batch = RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(arrow::array::Int64Array::from(ids.clone())),
+                    Arc::new(StringArray::from(names.clone())),
+                    Arc::new(Float64Array::from(scores.clone())),
+                ],
+            )
+            .unwrap();
             writer.write(&batch).unwrap();
-            writer.close().unwrap();
+            writer.flush().unwrap(); // force a row-group boundary between calls
         }
-        
-        let loader = DataLoader::with_defaults(temp_file.path().to_str().unwrap());
-        
-        // Load to populate cache
-        let result = loader.load();
-        assert!(result.is_ok(), "Should load parquet file");
-        
-        // Verify cache is populated
+        writer.close().unwrap();
+        file
+    }
+
+    #[test]
+    fn test_row_group_pruning_skips_groups_outside_predicate_range() {
+        let schema = create_wide_test_schema();
+        let file = write_parquet_wide_row_groups(
+            &schema,
+            &[
+                (vec![1, 2], vec!["a", "b"], vec![1.0, 2.0]),
+                (vec![100, 200], vec!["x", "y"], vec![100.0, 200.0]),
+            ],
+        );
+
+        let config = LoaderConfig {
+            row_filter: Some(RowFilter { column: "id".to_string(), op: ComparisonOp::Ge, value: FilterValue::Int64(100) }),
+            ..LoaderConfig::default()
+        };
+        let loader = DataLoader::new(DataSource::File(file.path().to_str().unwrap().to_string()), config);
+
+        let iter = loader.load().expect("row-group pruning should not change correctness");
+        assert_eq!(iter.total_rows(), 2, "only the second row group's rows satisfy id >= 100");
+    }
+
+    #[test]
+    fn test_row_filter_without_statistics_falls_back_to_reading_the_group() {
+        use parquet::arrow::ArrowWriter;
+        use parquet::file::properties::{EnabledStatistics, WriterProperties};
+
+        let schema = create_wide_test_schema();
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(arrow::array::Int64Array::from(vec![1, 2, 3])),
+                Arc::new(arrow::array::StringArray::from(vec!["a", "b", "c"])),
+                Arc::new(arrow::array::Float64Array::from(vec![1.0, 2.0, 3.0])),
+            ],
+        )
+        .unwrap();
+
+        let mut file = tempfile::NamedTempFile::with_suffix(".parquet").unwrap();
+        let props = WriterProperties::builder().set_statistics_enabled(EnabledStatistics::None).build();
+        let mut writer = ArrowWriter::try_new(file.as_file_mut(), schema.clone(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let config = LoaderConfig {
+            row_filter: Some(RowFilter { column: "id".to_string(), op: ComparisonOp::Gt, value: FilterValue::Int64(1) }),
+            ..LoaderConfig::default()
+        };
+        let loader = DataLoader::new(DataSource::File(file.path().to_str().unwrap().to_string()), config);
+
+        let iter = loader.load().expect("missing statistics must fall back to reading the group, not erroring");
+        assert_eq!(iter.total_rows(), 2, "the row filter must still apply correctly once the unpruned group is read");
+    }
+
+    // ===================== Arrow IPC Stream / Memory Tests =====================
+
+    fn write_arrow_ipc_stream_bytes(schema: &Arc<Schema>, batch: &RecordBatch) -> Vec<u8> {
+        use arrow::ipc::writer::StreamWriter;
+
+        let mut buf = Vec::new();
         {
-            let cache = loader.cached_batches.read();
-            assert!(cache.is_some(), "Cache should be populated after load");
+            let mut writer = StreamWriter::try_new(&mut buf, schema).unwrap();
+            writer.write(batch).unwrap();
+            writer.finish().unwrap();
         }
-        
-        // Clear the cache
-        loader.clear_cache();
-        
-        // Verify cache is now empty
+        buf
+    }
+
+    fn write_arrow_ipc_file_bytes(schema: &Arc<Schema>, batch: &RecordBatch) -> Vec<u8> {
+        use arrow::ipc::writer::FileWriter;
+
+        let mut buf = Vec::new();
         {
-            let cache = loader.cached_batches.read();
-            assert!(cache.is_none(), 
-                "clear_cache must actually clear the cache, not be a no-op");
+            let mut writer = FileWriter::try_new(&mut buf, schema).unwrap();
+            writer.write(batch).unwrap();
+            writer.finish().unwrap();
         }
+        buf
     }
-    
-    /// Test loading a real parquet file through load_file match arm
-    /// Kills mutation: delete match arm FileFormat::Parquet
+
     #[test]
-    fn test_load_parquet_file() {
+    fn test_load_file_detects_arrow_ipc_stream_format_by_magic_bytes() {
+        use std::io::Write;
         use tempfile::NamedTempFile;
-        
+
         let schema = create_test_schema();
-        let batch = create_test_batch(&schema, vec![10, 20, 30]);
-        
-        let mut temp_file = NamedTempFile::with_suffix(".parquet").unwrap();
-        {
-            use parquet::arrow::ArrowWriter;
-            let mut writer = ArrowWriter::try_new(temp_file.as_file_mut(), schema.clone(), None).unwrap();
-            writer.write(&batch).unwrap();
-            writer.close().unwrap();
-        }
-        
-        let loader = DataLoader::with_defaults(temp_file.path().to_str().unwrap());
-        let result = loader.load();
-        
-        assert!(result.is_ok(), "Must be able to load parquet files");
-        let iter = result.unwrap();
-        assert_eq!(iter.total_rows(), 3, "Should have 3 rows from parquet");
+        let batch = create_test_batch(&schema, vec![1, 2, 3]);
+        let bytes = write_arrow_ipc_stream_bytes(&schema, &batch);
+
+        // Stream-format bytes saved under the File-format extension: a
+        // streamed `.arrows` payload has no footer for `FileReader` to find.
+        let mut file = NamedTempFile::with_suffix(".arrow").unwrap();
+        file.write_all(&bytes).unwrap();
+        file.flush().unwrap();
+
+        let loader = DataLoader::with_defaults(file.path().to_str().unwrap());
+        let iter = loader.load().expect("Stream-format IPC payload should be detected and decoded");
+        assert_eq!(iter.total_rows(), 3);
     }
-    
-    /// Test loading a real CSV file through load_file match arm
-    /// Kills mutation: delete match arm FileFormat::Csv
+
     #[test]
-    fn test_load_csv_file() {
+    fn test_load_file_still_reads_arrow_ipc_file_format() {
         use std::io::Write;
         use tempfile::NamedTempFile;
-        
-        let mut temp_file = NamedTempFile::with_suffix(".csv").unwrap();
-        writeln!(temp_file, "id,value").unwrap();
-        writeln!(temp_file, "1,100").unwrap();
-        writeln!(temp_file, "2,200").unwrap();
-        writeln!(temp_file, "3,300").unwrap();
-        temp_file.flush().unwrap();
-        
-        let loader = DataLoader::with_defaults(temp_file.path().to_str().unwrap());
-        let result = loader.load();
-        
-        assert!(result.is_ok(), "Must be able to load CSV files");
-        let iter = result.unwrap();
-        assert_eq!(iter.total_rows(), 3, "Should have 3 rows from CSV");
+
+        let schema = create_test_schema();
+        let batch = create_test_batch(&schema, vec![1, 2]);
+        let bytes = write_arrow_ipc_file_bytes(&schema, &batch);
+
+        let mut file = NamedTempFile::with_suffix(".arrow").unwrap();
+        file.write_all(&bytes).unwrap();
+        file.flush().unwrap();
+
+        let loader = DataLoader::with_defaults(file.path().to_str().unwrap());
+        let iter = loader.load().expect("File-format IPC payload should still load");
+        assert_eq!(iter.total_rows(), 2);
     }
-    
-    /// Test loading Arrow IPC file through load_file match arm  
-    /// Kills mutation: delete match arm FileFormat::ArrowIpc
+
     #[test]
-    fn test_load_arrow_ipc_file() {
-        use tempfile::NamedTempFile;
-        use arrow::ipc::writer::FileWriter;
-        
+    fn test_load_memory_sniffs_parquet_magic_bytes() {
+        use parquet::arrow::ArrowWriter;
+
         let schema = create_test_schema();
-        let batch = create_test_batch(&schema, vec![100, 200]);
-        
-        let temp_file = NamedTempFile::with_suffix(".arrow").unwrap();
+        let batch = create_test_batch(&schema, vec![1, 2, 3, 4]);
+        let mut buf = Vec::new();
         {
-            let mut writer = FileWriter::try_new(temp_file.as_file(), &schema).unwrap();
+            let mut writer = ArrowWriter::try_new(&mut buf, schema, None).unwrap();
             writer.write(&batch).unwrap();
-            writer.finish().unwrap();
+            writer.close().unwrap();
         }
-        
-        let loader = DataLoader::with_defaults(temp_file.path().to_str().unwrap());
-        let result = loader.load();
-        
-        assert!(result.is_ok(), "Must be able to load Arrow IPC files");
-        let iter = result.unwrap();
-        assert_eq!(iter.total_rows(), 2, "Should have 2 rows from Arrow IPC");
+
+        let loader = DataLoader::new(DataSource::Memory(buf), LoaderConfig::default());
+        let iter = loader.load().expect("Parquet bytes should be detected via the 'PAR1' magic");
+        assert_eq!(iter.total_rows(), 4);
     }
-    
-    /// Test cache is populated and subsequent loads use cache
-    /// Kills mutations: cache size comparisons
+
     #[test]
-    fn test_cache_is_populated_on_small_data() {
-        use tempfile::NamedTempFile;
-        
+    fn test_load_memory_sniffs_arrow_ipc_file_and_stream_magic_bytes() {
         let schema = create_test_schema();
-        let batch = create_test_batch(&schema, vec![1, 2, 3]);
-        
-        let mut temp_file = NamedTempFile::with_suffix(".parquet").unwrap();
-        {
-            use parquet::arrow::ArrowWriter;
-            let mut writer = ArrowWriter::try_new(temp_file.as_file_mut(), schema.clone(), None).unwrap();
-            writer.write(&batch).unwrap();
-            writer.close().unwrap();
-        }
-        
-        let loader = DataLoader::with_defaults(temp_file.path().to_str().unwrap());
-        
-        // First load
-        let result1 = loader.load();
-        assert!(result1.is_ok());
-        
-        // Verify cache populated (small data < 100MB)
-        {
-            let cache = loader.cached_batches.read();
-            assert!(cache.is_some(), "Small data should be cached");
-            let batches = cache.as_ref().unwrap();
-            assert!(!batches.is_empty(), "Cached batches should not be empty");
-        }
-        
-        // Second load should use cache (test this indirectly)
-        let result2 = loader.load();
-        assert!(result2.is_ok());
-        assert_eq!(result2.unwrap().total_rows(), 3);
+        let batch = create_test_batch(&schema, vec![1, 2]);
+
+        let file_bytes = write_arrow_ipc_file_bytes(&schema, &batch);
+        let loader = DataLoader::new(DataSource::Memory(file_bytes), LoaderConfig::default());
+        assert_eq!(loader.load().unwrap().total_rows(), 2);
+
+        let stream_bytes = write_arrow_ipc_stream_bytes(&schema, &batch);
+        let loader = DataLoader::new(DataSource::Memory(stream_bytes), LoaderConfig::default());
+        assert_eq!(loader.load().unwrap().total_rows(), 2);
     }
-    
-    /// Test FileFormat detection for all supported formats
-    /// Strengthens match arm testing
+
     #[test]
-    fn test_file_format_all_variants() {
-        // Parquet variants
+    fn test_load_memory_rejects_unrecognized_magic_bytes() {
+        let loader = DataLoader::new(DataSource::Memory(b"not a known format".to_vec()), LoaderConfig::default());
+        assert!(matches!(loader.load(), Err(DataLoaderError::UnsupportedFormat(_))));
+    }
+
+    #[test]
+    fn test_jsonl_respects_batch_size_for_batching() {
+        let file = write_jsonl(&[r#"{"id": 1}"#, r#"{"id": 2}"#, r#"{"id": 3}"#, r#"{"id": 4}"#, r#"{"id": 5}"#]);
+
+        let config = LoaderConfig { batch_size: 2, ..LoaderConfig::default() };
+        let loader = DataLoader::new(DataSource::File(file.path().to_str().unwrap().to_string()), config);
+
+        let iter = loader.load().unwrap();
+        assert_eq!(iter.total_rows(), 5);
+        assert_eq!(iter.num_batches(), 3); // 2 + 2 + 1
+    }
+
+    // ===================== Compression Tests =====================
+
+    fn write_gzip_file(suffix: &str, contents: &[u8]) -> tempfile::NamedTempFile {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(contents).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut file = NamedTempFile::with_suffix(suffix).unwrap();
+        file.write_all(&compressed).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    fn write_zstd_file(suffix: &str, contents: &[u8]) -> tempfile::NamedTempFile {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let compressed = zstd::encode_all(contents, 0).unwrap();
+
+        let mut file = NamedTempFile::with_suffix(suffix).unwrap();
+        file.write_all(&compressed).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    #[test]
+    fn test_file_format_detection_ignores_compression_suffix() {
+        assert_eq!(FileFormat::from_extension("data.csv.gz"), FileFormat::Csv);
+        assert_eq!(FileFormat::from_extension("data.jsonl.zst"), FileFormat::JsonLines);
         assert_eq!(FileFormat::from_extension("data.parquet"), FileFormat::Parquet);
-        assert_eq!(FileFormat::from_extension("path/to/file.pq"), FileFormat::Parquet);
-        
-        // CSV variants
-        assert_eq!(FileFormat::from_extension("data.csv"), FileFormat::Csv);
-        assert_eq!(FileFormat::from_extension("data.tsv"), FileFormat::Csv);
-        
-        // Arrow IPC variants
-        assert_eq!(FileFormat::from_extension("data.arrow"), FileFormat::ArrowIpc);
-        assert_eq!(FileFormat::from_extension("data.feather"), FileFormat::ArrowIpc);
-        
-        // JSON Lines
-        assert_eq!(FileFormat::from_extension("data.jsonl"), FileFormat::JsonLines);
-        assert_eq!(FileFormat::from_extension("data.ndjson"), FileFormat::JsonLines);
-        
-        // Unknown
-        assert_eq!(FileFormat::from_extension("data.txt"), FileFormat::Unknown);
-        assert_eq!(FileFormat::from_extension("no_extension"), FileFormat::Unknown);
     }
-    
-    /// Test that load returns error for unsupported format
-    /// Verifies the _ match arm in load_file
+
     #[test]
-    fn test_load_file_unsupported_returns_error() {
+    fn test_csv_gz_round_trips_through_load() {
+        let file = write_gzip_file(".csv.gz", b"id,name\n1,a\n2,b\n3,c\n");
+        let loader = DataLoader::with_defaults(file.path().to_str().unwrap());
+
+        let iter = loader.load().expect("gzip-compressed CSV should decode transparently");
+        assert_eq!(iter.total_rows(), 3);
+    }
+
+    #[test]
+    fn test_jsonl_gz_round_trips_through_load() {
+        let contents = b"{\"id\": 1}\n{\"id\": 2}\n";
+        let file = write_gzip_file(".jsonl.gz", contents);
+        let loader = DataLoader::with_defaults(file.path().to_str().unwrap());
+
+        let iter = loader.load().expect("gzip-compressed JSON Lines should decode transparently");
+        assert_eq!(iter.total_rows(), 2);
+    }
+
+    #[test]
+    fn test_ndjson_zst_round_trips_through_load() {
+        let contents = b"{\"id\": 1}\n{\"id\": 2}\n{\"id\": 3}\n";
+        let file = write_zstd_file(".ndjson.zst", contents);
+        let loader = DataLoader::with_defaults(file.path().to_str().unwrap());
+
+        let iter = loader.load().expect("zstd-compressed JSON Lines should decode transparently");
+        assert_eq!(iter.total_rows(), 3);
+    }
+
+    #[test]
+    fn test_corrupt_gzip_stream_yields_decompression_error() {
         use std::io::Write;
         use tempfile::NamedTempFile;
-        
-        // Create a file with unknown extension
-        let mut temp_file = NamedTempFile::with_suffix(".xyz").unwrap();
-        writeln!(temp_file, "some data").unwrap();
-        temp_file.flush().unwrap();
-        
-        let loader = DataLoader::with_defaults(temp_file.path().to_str().unwrap());
-        let result = loader.load();
-        
-        assert!(result.is_err(), "Unknown format should return error");
-        match result {
-            Err(DataLoaderError::UnsupportedFormat(_)) => {}
-            _ => panic!("Expected UnsupportedFormat error"),
-        }
+
+        let mut file = NamedTempFile::with_suffix(".csv.gz").unwrap();
+        file.write_all(b"not actually gzip data").unwrap();
+        file.flush().unwrap();
+
+        let loader = DataLoader::with_defaults(file.path().to_str().unwrap());
+        let err = loader.load().expect_err("garbage bytes under a .gz suffix must not decode as gzip");
+        assert!(matches!(err, DataLoaderError::Io(_) | DataLoaderError::Parse(_) | DataLoaderError::DecompressionError(_)));
     }
-    
-    /// Test config() returns the correct configuration
-    #[test] 
-    fn test_config_returns_correct_values() {
-        let config = LoaderConfig {
-            batch_size: 512,
-            prefetch_count: 2,
-            num_workers: 8,
-            memory_map: false,
-            io_buffer_size: 4 * 1024 * 1024,
-        };
-        let loader = DataLoader::new(DataSource::File("test.parquet".to_string()), config);
-        
-        let c = loader.config();
-        assert_eq!(c.batch_size, 512);
-        assert_eq!(c.prefetch_count, 2);
-        assert_eq!(c.num_workers, 8);
-        assert!(!c.memory_map);
-        assert_eq!(c.io_buffer_size, 4 * 1024 * 1024);
+
+    #[test]
+    fn test_corrupt_zstd_stream_yields_decompression_error() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".jsonl.zst").unwrap();
+        file.write_all(b"not actually zstd data").unwrap();
+        file.flush().unwrap();
+
+        let loader = DataLoader::with_defaults(file.path().to_str().unwrap());
+        let err = loader.load().expect_err("garbage bytes under a .zst suffix must not decode as zstd");
+        assert!(matches!(err, DataLoaderError::DecompressionError(_)));
+    }
+
+    // ===================== DataSource::Reader Tests =====================
+
+    #[test]
+    fn test_reader_source_loads_arrow_ipc_stream_from_a_non_seekable_source() {
+        let schema = create_test_schema();
+        let batch = create_test_batch(&schema, vec![1, 2, 3]);
+        let bytes = write_arrow_ipc_stream_bytes(&schema, &batch);
+
+        // `Cursor` over an owned `Vec<u8>` isn't `Seek`-free, but it's a
+        // plain `Read`, which is all `ReaderSource`/`StreamReader` require —
+        // standing in for a pipe or socket that genuinely can't seek.
+        let source = ReaderSource::new(std::io::Cursor::new(bytes));
+        let loader = DataLoader::new(DataSource::Reader(source), LoaderConfig::default());
+
+        let iter = loader.load().expect("Arrow IPC Stream bytes should decode from an arbitrary Read");
+        assert_eq!(iter.total_rows(), 3);
+    }
+
+    #[test]
+    fn test_reader_source_errors_on_a_second_load() {
+        let schema = create_test_schema();
+        let batch = create_test_batch(&schema, vec![1, 2]);
+        let bytes = write_arrow_ipc_stream_bytes(&schema, &batch);
+
+        let source = ReaderSource::new(std::io::Cursor::new(bytes));
+        let loader = DataLoader::new(DataSource::Reader(source.clone()), LoaderConfig::default());
+
+        loader.load().expect("first load should succeed");
+        // Large enough to not be cached would also hit this path, but even a
+        // cached small result re-reads from the cache, not the source; only
+        // a clone of the same `ReaderSource` (sharing the same consumed
+        // handle) exercises the "already taken" branch directly.
+        let err = source.take().expect_err("the reader was already consumed by the first load");
+        assert!(matches!(err, DataLoaderError::Config(_)));
+    }
+
+    #[test]
+    fn test_stream_rejects_reader_source() {
+        let source = ReaderSource::new(std::io::Cursor::new(Vec::<u8>::new()));
+        let loader = DataLoader::new(DataSource::Reader(source), LoaderConfig::default());
+
+        assert!(matches!(loader.stream(), Err(DataLoaderError::UnsupportedFormat(_))));
     }
 }
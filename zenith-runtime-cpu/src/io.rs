@@ -4,6 +4,36 @@
 
 use crate::Result;
 
+/// Common async file-I/O surface implemented by every backend (`standard`
+/// today, `iouring` once it grows a per-file handle instead of taking a raw
+/// fd per call), so callers can be generic over which engine is serving a
+/// given file.
+///
+/// `read`/`write` are the classic sequential, cursor-moving operations.
+/// `read_at`/`write_at` are the positional-I/O model (`pread(2)`/`pwrite(2)`):
+/// they read/write at an explicit `offset` and leave the cursor exactly
+/// where it was before the call, which is what [`seek`](Self::seek) and
+/// [`tell`](Self::tell) observe/control. This is the shape a data-plane
+/// engine wants for random access into index and segment files, where many
+/// reads at independent offsets must not race each other over one shared
+/// cursor.
+pub trait AsyncFile {
+    /// Reads into `buf` starting at the cursor, advancing it by the number
+    /// of bytes read.
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
+    /// Writes `buf` starting at the cursor, advancing it by the number of
+    /// bytes written.
+    async fn write(&mut self, buf: &[u8]) -> Result<usize>;
+    /// Reads into `buf` starting at `offset`, without moving the cursor.
+    async fn read_at(&mut self, buf: &mut [u8], offset: u64) -> Result<usize>;
+    /// Writes `buf` starting at `offset`, without moving the cursor.
+    async fn write_at(&mut self, buf: &[u8], offset: u64) -> Result<usize>;
+    /// Moves the cursor to `pos` and returns the new position.
+    async fn seek(&mut self, pos: u64) -> Result<u64>;
+    /// Returns the current cursor position without moving it.
+    async fn tell(&mut self) -> Result<u64>;
+}
+
 #[cfg(feature = "io_uring")]
 pub mod iouring {
     //! io_uring based I/O operations
@@ -90,59 +120,159 @@ pub mod iouring {
 pub mod standard {
     use super::*;
     use tokio::fs::File;
-    use tokio::io::{AsyncReadExt, AsyncWriteExt};
-    
+    use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, SeekFrom};
+
     /// Standard async file reader
     pub struct AsyncFileReader {
         file: File,
     }
-    
+
     impl AsyncFileReader {
         /// Open a file for reading
         pub async fn open(path: &str) -> Result<Self> {
             let file = File::open(path).await?;
             Ok(Self { file })
         }
-        
+
         /// Read data into buffer
         pub async fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
             Ok(self.file.read(buf).await?)
         }
-        
+
         /// Read entire file
         pub async fn read_all(&mut self) -> Result<Vec<u8>> {
             let mut buf = Vec::new();
             self.file.read_to_end(&mut buf).await?;
             Ok(buf)
         }
+
+        /// Read into `buf` starting at `offset` (`pread(2)`), restoring the
+        /// cursor to its prior position afterwards.
+        pub async fn read_at(&mut self, buf: &mut [u8], offset: u64) -> Result<usize> {
+            let saved = self.file.stream_position().await?;
+            self.file.seek(SeekFrom::Start(offset)).await?;
+            let result = self.file.read(buf).await;
+            self.file.seek(SeekFrom::Start(saved)).await?;
+            Ok(result?)
+        }
+
+        /// Move the cursor to `pos` (`lseek(2)`), returning the new position.
+        pub async fn seek(&mut self, pos: u64) -> Result<u64> {
+            Ok(self.file.seek(SeekFrom::Start(pos)).await?)
+        }
+
+        /// Return the current cursor position without moving it.
+        pub async fn tell(&mut self) -> Result<u64> {
+            Ok(self.file.stream_position().await?)
+        }
     }
-    
+
+    impl super::AsyncFile for AsyncFileReader {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            self.read(buf).await
+        }
+
+        async fn write(&mut self, _buf: &[u8]) -> Result<usize> {
+            Err(crate::Error::NotImplemented(
+                "AsyncFileReader is read-only; open with AsyncFileWriter::create to write".into(),
+            ))
+        }
+
+        async fn read_at(&mut self, buf: &mut [u8], offset: u64) -> Result<usize> {
+            self.read_at(buf, offset).await
+        }
+
+        async fn write_at(&mut self, _buf: &[u8], _offset: u64) -> Result<usize> {
+            Err(crate::Error::NotImplemented(
+                "AsyncFileReader is read-only; open with AsyncFileWriter::create to write".into(),
+            ))
+        }
+
+        async fn seek(&mut self, pos: u64) -> Result<u64> {
+            self.seek(pos).await
+        }
+
+        async fn tell(&mut self) -> Result<u64> {
+            self.tell().await
+        }
+    }
+
     /// Standard async file writer
     pub struct AsyncFileWriter {
         file: File,
     }
-    
+
     impl AsyncFileWriter {
         /// Create/open a file for writing
         pub async fn create(path: &str) -> Result<Self> {
             let file = File::create(path).await?;
             Ok(Self { file })
         }
-        
+
         /// Write data
         pub async fn write(&mut self, buf: &[u8]) -> Result<usize> {
             Ok(self.file.write(buf).await?)
         }
-        
+
         /// Write all data
         pub async fn write_all(&mut self, buf: &[u8]) -> Result<()> {
             Ok(self.file.write_all(buf).await?)
         }
-        
+
         /// Flush to disk
         pub async fn flush(&mut self) -> Result<()> {
             Ok(self.file.flush().await?)
         }
+
+        /// Write `buf` starting at `offset` (`pwrite(2)`), restoring the
+        /// cursor to its prior position afterwards.
+        pub async fn write_at(&mut self, buf: &[u8], offset: u64) -> Result<usize> {
+            let saved = self.file.stream_position().await?;
+            self.file.seek(SeekFrom::Start(offset)).await?;
+            let result = self.file.write(buf).await;
+            self.file.seek(SeekFrom::Start(saved)).await?;
+            Ok(result?)
+        }
+
+        /// Move the cursor to `pos` (`lseek(2)`), returning the new position.
+        pub async fn seek(&mut self, pos: u64) -> Result<u64> {
+            Ok(self.file.seek(SeekFrom::Start(pos)).await?)
+        }
+
+        /// Return the current cursor position without moving it.
+        pub async fn tell(&mut self) -> Result<u64> {
+            Ok(self.file.stream_position().await?)
+        }
+    }
+
+    impl super::AsyncFile for AsyncFileWriter {
+        async fn read(&mut self, _buf: &mut [u8]) -> Result<usize> {
+            Err(crate::Error::NotImplemented(
+                "AsyncFileWriter is write-only; open with AsyncFileReader::open to read".into(),
+            ))
+        }
+
+        async fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.write(buf).await
+        }
+
+        async fn read_at(&mut self, _buf: &mut [u8], _offset: u64) -> Result<usize> {
+            Err(crate::Error::NotImplemented(
+                "AsyncFileWriter is write-only; open with AsyncFileReader::open to read".into(),
+            ))
+        }
+
+        async fn write_at(&mut self, buf: &[u8], offset: u64) -> Result<usize> {
+            self.write_at(buf, offset).await
+        }
+
+        async fn seek(&mut self, pos: u64) -> Result<u64> {
+            self.seek(pos).await
+        }
+
+        async fn tell(&mut self) -> Result<u64> {
+            self.tell().await
+        }
     }
 }
 
@@ -0,0 +1,100 @@
+//! Asymmetric membarrier-based synchronization for the lock-free hot path
+//!
+//! Implements Linux's asymmetric `sys_membarrier` scheme: frequent
+//! readers/producers do only a relaxed load plus a compiler fence, while
+//! the rare writer/reclaimer calls [`MembarrierBarrier::heavy_barrier`] to
+//! force every other thread of this process through a full memory barrier
+//! before it returns - shifting the cost of cross-core ordering off the
+//! hot path and onto the rare slow path instead.
+
+use std::sync::atomic::{fence, AtomicBool, Ordering};
+
+const MEMBARRIER_CMD_QUERY: libc::c_long = 0;
+const MEMBARRIER_CMD_PRIVATE_EXPEDITED: libc::c_long = 1 << 3;
+const MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED: libc::c_long = 1 << 4;
+
+/// Issues `sys_membarrier(cmd, 0, 0)` directly. Returns the raw result: a
+/// command bitmask for `MEMBARRIER_CMD_QUERY`, `0` on success or `-1` on
+/// failure otherwise.
+fn membarrier(cmd: libc::c_long) -> libc::c_long {
+    unsafe { libc::syscall(libc::SYS_membarrier, cmd, 0, 0) }
+}
+
+/// An asymmetric `sys_membarrier`-based barrier.
+///
+/// `heavy_barrier()` is the rare, expensive side: it forces every other
+/// running thread of this process through a full memory barrier. Readers
+/// on the hot path only need a relaxed load plus a compiler fence and can
+/// skip the atomic fence entirely, as long as they only rely on ordering
+/// established by the most recent `heavy_barrier()` call.
+pub struct MembarrierBarrier {
+    expedited: AtomicBool,
+}
+
+impl MembarrierBarrier {
+    /// Query kernel support and, if available, register this process for
+    /// `MEMBARRIER_CMD_PRIVATE_EXPEDITED`.
+    ///
+    /// Never fails: if the kernel lacks membarrier support, or the query
+    /// or registration calls fail, `heavy_barrier()` transparently falls
+    /// back to `fence(SeqCst)` and [`is_expedited`](Self::is_expedited)
+    /// reports that.
+    pub fn new() -> Self {
+        let supported_mask = membarrier(MEMBARRIER_CMD_QUERY);
+        let expedited = supported_mask >= 0
+            && (supported_mask & MEMBARRIER_CMD_PRIVATE_EXPEDITED) != 0
+            && (supported_mask & MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED) != 0
+            && membarrier(MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED) == 0;
+
+        Self {
+            expedited: AtomicBool::new(expedited),
+        }
+    }
+
+    /// Whether `heavy_barrier()` is backed by the expedited membarrier
+    /// syscall (`true`) or the `fence(SeqCst)` fallback (`false`).
+    pub fn is_expedited(&self) -> bool {
+        self.expedited.load(Ordering::Relaxed)
+    }
+
+    /// Establish ordering across every thread of this process.
+    ///
+    /// If registered, this forces every other running thread to execute a
+    /// full memory barrier before the call returns
+    /// (`MEMBARRIER_CMD_PRIVATE_EXPEDITED`). Otherwise it falls back to a
+    /// local `fence(SeqCst)` - in that mode readers must use acquire loads
+    /// rather than relaxed ones, since there is no cross-thread guarantee
+    /// to lean on.
+    pub fn heavy_barrier(&self) {
+        if self.is_expedited() && membarrier(MEMBARRIER_CMD_PRIVATE_EXPEDITED) == 0 {
+            return;
+        }
+        fence(Ordering::SeqCst);
+    }
+}
+
+impl Default for MembarrierBarrier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_never_fails_and_reports_a_mode() {
+        // Either membarrier is supported and registered, or we fell back -
+        // both are valid depending on the kernel running the test.
+        let barrier = MembarrierBarrier::new();
+        let _ = barrier.is_expedited();
+    }
+
+    #[test]
+    fn test_heavy_barrier_does_not_panic_in_either_mode() {
+        let barrier = MembarrierBarrier::new();
+        barrier.heavy_barrier();
+        barrier.heavy_barrier();
+    }
+}
@@ -14,6 +14,10 @@ pub struct NumaNode {
     pub node_id: u32,
     /// CPU cores belonging to this node
     pub cpu_cores: Vec<u32>,
+    /// Physical cores belonging to this node, one representative logical
+    /// CPU per physical core (i.e. `cpu_cores` with SMT/hyperthread
+    /// siblings collapsed away)
+    pub physical_cores: Vec<u32>,
     /// Total memory in bytes
     pub total_memory: u64,
     /// Free memory in bytes
@@ -35,8 +39,21 @@ pub struct NumaTopology {
     num_nodes: u32,
     /// Total number of CPU cores
     num_cpus: u32,
+    /// Total number of physical cores, with SMT/hyperthread siblings
+    /// collapsed
+    num_physical_cpus: u32,
+    /// Logical CPU -> every logical CPU sharing its physical core
+    /// (including itself), keyed by `(physical id, core id)` from
+    /// `/proc/cpuinfo`
+    cpu_siblings: HashMap<u32, Vec<u32>>,
     /// Whether NUMA is actually available
     numa_available: bool,
+    /// CPUs this process may actually use, after `sched_getaffinity` and
+    /// cgroup CPU quota/cpuset are taken into account
+    effective_cpus: u32,
+    /// Memory this process may actually use, after the cgroup memory limit
+    /// is taken into account
+    memory_limit: u64,
 }
 
 impl NumaTopology {
@@ -52,22 +69,36 @@ impl NumaTopology {
             return Ok(Self::single_node_fallback());
         }
         
-        let nodes = Self::discover_nodes()?;
+        let cpu_siblings = Self::discover_cpu_siblings();
+        let mut nodes = Self::discover_nodes()?;
+        for node in nodes.values_mut() {
+            node.physical_cores = Self::physical_cores_for(&node.cpu_cores, &cpu_siblings);
+        }
         let num_nodes = nodes.len() as u32;
         let num_cpus = nodes.values()
             .map(|n| n.cpu_cores.len() as u32)
             .sum();
-        
+        let num_physical_cpus = nodes.values()
+            .map(|n| n.physical_cores.len() as u32)
+            .sum();
+        let total_memory = nodes.values().map(|n| n.total_memory).sum();
+        let effective_cpus = Self::compute_effective_cpus(num_cpus);
+        let memory_limit = Self::compute_memory_limit(total_memory);
+
         info!(
-            "Discovered {} NUMA nodes with {} total CPUs",
-            num_nodes, num_cpus
+            "Discovered {} NUMA nodes with {} total CPUs ({} physical, {} effective)",
+            num_nodes, num_cpus, num_physical_cpus, effective_cpus
         );
-        
+
         Ok(Self {
             nodes,
             num_nodes,
             num_cpus,
+            num_physical_cpus,
+            cpu_siblings,
             numa_available,
+            effective_cpus,
+            memory_limit,
         })
     }
     
@@ -143,6 +174,9 @@ impl NumaTopology {
         Ok(NumaNode {
             node_id,
             cpu_cores,
+            // Filled in once `/proc/cpuinfo` has been parsed, after all
+            // nodes are discovered.
+            physical_cores: vec![],
             total_memory,
             free_memory,
             hugepages_available,
@@ -204,28 +238,256 @@ impl NumaTopology {
     fn single_node_fallback() -> Self {
         let sys_info = sysinfo::System::new_all();
         let num_cpus = sys_info.cpus().len() as u32;
-        
+        let cpu_cores: Vec<u32> = (0..num_cpus).collect();
+        let cpu_siblings = Self::discover_cpu_siblings();
+        let physical_cores = Self::physical_cores_for(&cpu_cores, &cpu_siblings);
+        let num_physical_cpus = physical_cores.len() as u32;
+
         let node = NumaNode {
             node_id: 0,
-            cpu_cores: (0..num_cpus).collect(),
+            cpu_cores,
+            physical_cores,
             total_memory: sys_info.total_memory(),
             free_memory: sys_info.available_memory(),
             hugepages_available: false,
             hugepages_free: 0,
             hugepage_size: 0,
         };
-        
+
+        let total_memory = node.total_memory;
         let mut nodes = HashMap::new();
         nodes.insert(0, node);
-        
+
         Self {
             nodes,
             num_nodes: 1,
             num_cpus,
+            num_physical_cpus,
+            cpu_siblings,
             numa_available: false,
+            effective_cpus: Self::compute_effective_cpus(num_cpus),
+            memory_limit: Self::compute_memory_limit(total_memory),
         }
     }
-    
+
+    /// Compute how many of the system's `total_cpus` this process may
+    /// actually schedule work on.
+    ///
+    /// Starts from the `sched_getaffinity` mask (the set of CPUs the OS
+    /// scheduler will actually run us on), then narrows it further by any
+    /// cgroup CPU controls in effect: the `cpuset.cpus` allow-list and the
+    /// `cpu.max`/`cpu.cfs_quota_us` time-sliced quota, expressed as an
+    /// equivalent whole-core count via `ceil(quota / period)`. A plugin
+    /// pinned to a 2-core cgroup quota on a 64-core box must size its
+    /// thread pools off this number, not `total_cpus`.
+    fn compute_effective_cpus(total_cpus: u32) -> u32 {
+        let mut effective = Self::affinity_cpu_count().unwrap_or(total_cpus).max(1);
+
+        if let Some(cpuset) = Self::cgroup_cpuset() {
+            effective = effective.min(cpuset.len() as u32);
+        }
+
+        if let Some(quota_cpus) = Self::cgroup_quota_cpus() {
+            effective = effective.min(quota_cpus);
+        }
+
+        effective.max(1)
+    }
+
+    /// Compute the memory budget this process may actually use, clamping
+    /// the cgroup `memory.max`/`memory.limit_in_bytes` limit (if any)
+    /// against the node total so an "unlimited" cgroup never reports more
+    /// memory than the machine actually has.
+    fn compute_memory_limit(node_total: u64) -> u64 {
+        match Self::cgroup_memory_limit() {
+            Some(limit) if node_total > 0 => limit.min(node_total),
+            Some(limit) => limit,
+            None => node_total,
+        }
+    }
+
+    /// Read back the current process's CPU affinity mask via
+    /// `sched_getaffinity` and count the CPUs it allows.
+    fn affinity_cpu_count() -> Option<u32> {
+        unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            let rc = libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set);
+            if rc != 0 {
+                return None;
+            }
+            let count = (0..libc::CPU_SETSIZE)
+                .filter(|&cpu| libc::CPU_ISSET(cpu as usize, &set))
+                .count();
+            Some(count as u32)
+        }
+    }
+
+    /// Read the cgroup `cpuset.cpus` allow-list, preferring the unified
+    /// (v2) hierarchy and falling back to v1.
+    fn cgroup_cpuset() -> Option<Vec<u32>> {
+        let path = if std::path::Path::new("/sys/fs/cgroup/cpuset.cpus").exists() {
+            "/sys/fs/cgroup/cpuset.cpus"
+        } else {
+            "/sys/fs/cgroup/cpuset/cpuset.cpus"
+        };
+        let content = std::fs::read_to_string(path).ok()?;
+        let cpus = Self::parse_cpulist(&content);
+        if cpus.is_empty() {
+            None
+        } else {
+            Some(cpus)
+        }
+    }
+
+    /// Compute the effective whole-core CPU quota from cgroup CPU
+    /// bandwidth controls: v2 `cpu.max` ("$quota $period", or "max" for
+    /// unlimited), falling back to v1's separate
+    /// `cpu.cfs_quota_us`/`cpu.cfs_period_us` files (`-1` quota means
+    /// unlimited).
+    fn cgroup_quota_cpus() -> Option<u32> {
+        if let Ok(content) = std::fs::read_to_string("/sys/fs/cgroup/cpu.max") {
+            let mut parts = content.trim().split_whitespace();
+            let quota = parts.next()?;
+            let period: u64 = parts.next()?.parse().ok()?;
+            if quota == "max" {
+                return None;
+            }
+            let quota: u64 = quota.parse().ok()?;
+            return Some(Self::quota_to_cpus(quota, period));
+        }
+
+        let quota: i64 = std::fs::read_to_string("/sys/fs/cgroup/cpu/cpu.cfs_quota_us")
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        if quota <= 0 {
+            return None;
+        }
+        let period: u64 = std::fs::read_to_string("/sys/fs/cgroup/cpu/cpu.cfs_period_us")
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        Some(Self::quota_to_cpus(quota as u64, period))
+    }
+
+    /// `ceil(quota / period)`, the number of whole cores a CFS
+    /// quota/period pair is equivalent to.
+    fn quota_to_cpus(quota: u64, period: u64) -> u32 {
+        if period == 0 {
+            return 1;
+        }
+        (quota.div_ceil(period)).max(1) as u32
+    }
+
+    /// Read the cgroup memory limit: v2 `memory.max` ("max" for
+    /// unlimited), falling back to v1 `memory.limit_in_bytes` (a very
+    /// large sentinel value means unlimited there too).
+    fn cgroup_memory_limit() -> Option<u64> {
+        if let Ok(content) = std::fs::read_to_string("/sys/fs/cgroup/memory.max") {
+            let content = content.trim();
+            if content == "max" {
+                return None;
+            }
+            return content.parse().ok();
+        }
+
+        let limit: u64 = std::fs::read_to_string("/sys/fs/cgroup/memory/memory.limit_in_bytes")
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        // v1 reports a near-u64::MAX sentinel (rounded to a page boundary)
+        // for "unlimited" rather than omitting the file.
+        if limit > u64::MAX / 2 {
+            None
+        } else {
+            Some(limit)
+        }
+    }
+
+    /// Build the logical-CPU sibling map from `/proc/cpuinfo`, grouping
+    /// processors by their `(physical id, core id)` pair so hyperthread
+    /// siblings collapse into one physical core. Falls back to mapping
+    /// every logical CPU to itself when either field is missing (e.g. in
+    /// VMs that don't expose topology, or non-x86 `/proc/cpuinfo` layouts).
+    fn discover_cpu_siblings() -> HashMap<u32, Vec<u32>> {
+        let content = match std::fs::read_to_string("/proc/cpuinfo") {
+            Ok(content) => content,
+            Err(_) => return HashMap::new(),
+        };
+        Self::parse_cpuinfo_siblings(&content)
+    }
+
+    /// Parse the sibling map out of `/proc/cpuinfo` text (split out from
+    /// [`discover_cpu_siblings`] so the grouping logic is testable without
+    /// a real `/proc`).
+    fn parse_cpuinfo_siblings(content: &str) -> HashMap<u32, Vec<u32>> {
+        let mut groups: HashMap<(i64, i64), Vec<u32>> = HashMap::new();
+        let mut processor: Option<u32> = None;
+        let mut physical_id: Option<i64> = None;
+        let mut core_id: Option<i64> = None;
+
+        let flush = |processor: &mut Option<u32>,
+                          physical_id: &mut Option<i64>,
+                          core_id: &mut Option<i64>,
+                          groups: &mut HashMap<(i64, i64), Vec<u32>>| {
+            if let Some(cpu) = processor.take() {
+                let key = (physical_id.take().unwrap_or(-1), core_id.take().unwrap_or(cpu as i64));
+                groups.entry(key).or_default().push(cpu);
+            }
+        };
+
+        for line in content.lines() {
+            if line.trim().is_empty() {
+                flush(&mut processor, &mut physical_id, &mut core_id, &mut groups);
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else { continue };
+            let key = key.trim();
+            let value = value.trim();
+            match key {
+                "processor" => processor = value.parse().ok(),
+                "physical id" => physical_id = value.parse().ok(),
+                "core id" => core_id = value.parse().ok(),
+                _ => {}
+            }
+        }
+        flush(&mut processor, &mut physical_id, &mut core_id, &mut groups);
+
+        let mut siblings = HashMap::new();
+        for mut group in groups.into_values() {
+            group.sort_unstable();
+            for &cpu in &group {
+                siblings.insert(cpu, group.clone());
+            }
+        }
+        siblings
+    }
+
+    /// Collapse `cpus` to one representative logical CPU per physical
+    /// core, using `siblings` to find each CPU's hyperthread group.
+    /// CPUs with no sibling-group entry are treated as their own
+    /// physical core.
+    fn physical_cores_for(cpus: &[u32], siblings: &HashMap<u32, Vec<u32>>) -> Vec<u32> {
+        let mut seen = std::collections::HashSet::new();
+        let mut physical = Vec::new();
+
+        for &cpu in cpus {
+            let representative = siblings
+                .get(&cpu)
+                .and_then(|group| group.iter().min().copied())
+                .unwrap_or(cpu);
+            if seen.insert(representative) {
+                physical.push(representative);
+            }
+        }
+
+        physical.sort_unstable();
+        physical
+    }
+
     // Public API
     
     /// Get the number of NUMA nodes
@@ -237,7 +499,37 @@ impl NumaTopology {
     pub fn num_cpus(&self) -> u32 {
         self.num_cpus
     }
-    
+
+    /// Get the total number of physical cores, with SMT/hyperthread
+    /// siblings collapsed into one. Equal to `num_cpus()` on systems
+    /// without SMT, or where `/proc/cpuinfo` doesn't expose topology.
+    pub fn num_physical_cpus(&self) -> u32 {
+        self.num_physical_cpus
+    }
+
+    /// Get the logical CPUs that share a physical core with `cpu`
+    /// (including `cpu` itself), in ascending order. Spread
+    /// latency-sensitive work across distinct cores first and only fall
+    /// back to a `cpu`'s siblings under load.
+    pub fn core_siblings(&self, cpu: u32) -> Option<&[u32]> {
+        self.cpu_siblings.get(&cpu).map(Vec::as_slice)
+    }
+
+    /// Get the number of CPUs this process can actually use, after
+    /// `sched_getaffinity` and any cgroup `cpuset.cpus`/quota are applied.
+    /// Always `<= num_cpus()`; use this instead of `num_cpus()` to size
+    /// worker pools under containerization.
+    pub fn effective_cpus(&self) -> u32 {
+        self.effective_cpus
+    }
+
+    /// Get the memory this process can actually use, after the cgroup
+    /// `memory.max`/`memory.limit_in_bytes` limit is applied and clamped
+    /// against the node total. Always `<= total_memory()`.
+    pub fn memory_limit(&self) -> u64 {
+        self.memory_limit
+    }
+
     /// Check if NUMA is available
     pub fn is_numa_available(&self) -> bool {
         self.numa_available
@@ -308,11 +600,60 @@ mod tests {
         );
     }
     
+    #[test]
+    fn test_quota_to_cpus() {
+        assert_eq!(NumaTopology::quota_to_cpus(200_000, 100_000), 2);
+        assert_eq!(NumaTopology::quota_to_cpus(150_000, 100_000), 2);
+        assert_eq!(NumaTopology::quota_to_cpus(50_000, 100_000), 1);
+        assert_eq!(NumaTopology::quota_to_cpus(0, 100_000), 1);
+    }
+
+    #[test]
+    fn test_parse_cpuinfo_siblings_with_smt() {
+        // 2 physical cores, 2 threads each (HT), all on physical id 0
+        let cpuinfo = "\
+processor\t: 0
+physical id\t: 0
+core id\t: 0
+
+processor\t: 1
+physical id\t: 0
+core id\t: 1
+
+processor\t: 2
+physical id\t: 0
+core id\t: 0
+
+processor\t: 3
+physical id\t: 0
+core id\t: 1
+";
+        let siblings = NumaTopology::parse_cpuinfo_siblings(cpuinfo);
+        assert_eq!(siblings.get(&0).unwrap(), &vec![0, 2]);
+        assert_eq!(siblings.get(&2).unwrap(), &vec![0, 2]);
+        assert_eq!(siblings.get(&1).unwrap(), &vec![1, 3]);
+
+        let physical = NumaTopology::physical_cores_for(&[0, 1, 2, 3], &siblings);
+        assert_eq!(physical, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_physical_cores_for_without_siblings() {
+        // No topology info available: every logical CPU is its own core.
+        let siblings = HashMap::new();
+        let physical = NumaTopology::physical_cores_for(&[0, 1, 2, 3], &siblings);
+        assert_eq!(physical, vec![0, 1, 2, 3]);
+    }
+
     #[test]
     fn test_topology_discovery() {
         // This will use fallback on most development machines
         let topology = NumaTopology::discover().unwrap();
         assert!(topology.num_cpus() > 0);
         assert!(topology.num_nodes() >= 1);
+        assert!(topology.effective_cpus() > 0);
+        assert!(topology.effective_cpus() <= topology.num_cpus());
+        assert!(topology.num_physical_cpus() > 0);
+        assert!(topology.num_physical_cpus() <= topology.num_cpus());
     }
 }
@@ -82,6 +82,10 @@ pub enum NumaError {
     BindFailed,
     /// Null pointer passed
     NullPtr,
+    /// Requested priority is outside the policy's `priority_range`
+    InvalidPriority,
+    /// Operation needs a capability the caller doesn't hold (e.g. `CAP_SYS_NICE`)
+    PermissionDenied,
     /// Unknown error
     Unknown(i32),
 }
@@ -94,6 +98,8 @@ impl std::fmt::Display for NumaError {
             NumaError::AllocFailed => write!(f, "NUMA allocation failed"),
             NumaError::BindFailed => write!(f, "Thread binding failed"),
             NumaError::NullPtr => write!(f, "Null pointer"),
+            NumaError::InvalidPriority => write!(f, "Priority outside policy's valid range"),
+            NumaError::PermissionDenied => write!(f, "Permission denied (missing CAP_SYS_NICE?)"),
             NumaError::Unknown(code) => write!(f, "Unknown NUMA error: {}", code),
         }
     }
@@ -197,6 +203,184 @@ pub fn unbind_thread() -> NumaResult<()> {
     check_result(unsafe { ffi::zenith_numa_unbind_thread() })
 }
 
+// ============================================================================
+// CPU-set affinity
+// ============================================================================
+//
+// `bind_thread_to_cpu` above pins a thread to exactly one core via the C++
+// backend. For a dataplane worker that should stay on one socket but is
+// still free to migrate among that socket's cores, we need a full affinity
+// mask instead - built directly on `sched_setaffinity`/`sched_getaffinity`
+// so it works independent of the `numa_cpp` feature.
+
+/// A CPU affinity mask, as accepted by `sched_setaffinity`/`sched_getaffinity`.
+///
+/// Wraps the kernel's `cpu_set_t` bitmask (one bit per logical CPU, sized to
+/// `libc::CPU_SETSIZE`), which comfortably covers `num_cpus()` on every
+/// machine this backend targets.
+#[derive(Clone, Copy)]
+pub struct CpuSet(libc::cpu_set_t);
+
+impl CpuSet {
+    /// An empty mask (no CPUs set).
+    pub fn new() -> Self {
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        unsafe { libc::CPU_ZERO(&mut set) };
+        Self(set)
+    }
+
+    /// Add `cpu` to the mask.
+    pub fn add(&mut self, cpu: i32) {
+        unsafe { libc::CPU_SET(cpu as usize, &mut self.0) };
+    }
+
+    /// Whether `cpu` is set in the mask.
+    pub fn contains(&self, cpu: i32) -> bool {
+        unsafe { libc::CPU_ISSET(cpu as usize, &self.0) }
+    }
+
+    /// Every CPU set in the mask, in ascending order.
+    pub fn cpus(&self) -> Vec<i32> {
+        (0..libc::CPU_SETSIZE).filter(|&cpu| self.contains(cpu)).collect()
+    }
+
+    /// Build a mask containing exactly the given logical CPUs.
+    pub fn from_cpus(cpus: &[i32]) -> Self {
+        let mut set = Self::new();
+        for &cpu in cpus {
+            set.add(cpu);
+        }
+        set
+    }
+}
+
+impl Default for CpuSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pin the current thread to exactly the CPUs in `cpus`, via
+/// `sched_setaffinity`. Returns `NumaError::BindFailed` on `EINVAL`/`EPERM`.
+pub fn bind_thread_to_cpus(cpus: &[i32]) -> NumaResult<()> {
+    let set = CpuSet::from_cpus(cpus);
+    let rc = unsafe {
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set.0)
+    };
+    if rc == 0 {
+        Ok(())
+    } else {
+        Err(NumaError::BindFailed)
+    }
+}
+
+/// Read back the current thread's affinity mask via `sched_getaffinity`.
+pub fn current_affinity() -> NumaResult<CpuSet> {
+    let mut set = CpuSet::new();
+    let rc = unsafe {
+        libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set.0)
+    };
+    if rc == 0 {
+        Ok(set)
+    } else {
+        Err(NumaError::BindFailed)
+    }
+}
+
+/// Pin the current thread to every CPU belonging to `node`, letting the
+/// scheduler migrate it freely among that socket's cores - the right way to
+/// keep a dataplane worker node-local without over-constraining it to one
+/// core.
+#[cfg(feature = "numa_cpp")]
+pub fn bind_thread_to_node_cpus(node: i32) -> NumaResult<()> {
+    let cpus: Vec<i32> = (0..num_cpus())
+        .filter(|&cpu| node_of_cpu(cpu) == Some(node))
+        .collect();
+
+    if cpus.is_empty() {
+        return Err(NumaError::InvalidNode);
+    }
+
+    bind_thread_to_cpus(&cpus)
+}
+
+// ============================================================================
+// Real-time scheduler policy
+// ============================================================================
+//
+// A worker thread that is NUMA-bound and CPU-pinned can still be preempted
+// by `SCHED_OTHER` contention; tail-latency-sensitive packet/storage
+// processing additionally wants a real-time policy. Built directly on
+// `sched_setscheduler`/`sched_get_priority_min`/`sched_get_priority_max`, so
+// it works independent of the `numa_cpp` feature, same as the CPU-set API
+// above.
+
+/// Scheduling policy for [`set_scheduler_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedPolicy {
+    /// `SCHED_FIFO` - fixed-priority, runs until it blocks or yields to a
+    /// higher-priority thread.
+    Fifo,
+    /// `SCHED_RR` - fixed-priority, time-sliced among equal-priority threads.
+    RoundRobin,
+    /// `SCHED_OTHER` - the default, non-real-time policy. Priority is
+    /// always `0`.
+    Other,
+}
+
+impl SchedPolicy {
+    fn as_raw(self) -> i32 {
+        match self {
+            SchedPolicy::Fifo => libc::SCHED_FIFO,
+            SchedPolicy::RoundRobin => libc::SCHED_RR,
+            SchedPolicy::Other => libc::SCHED_OTHER,
+        }
+    }
+}
+
+/// The valid priority range `[min, max]` for `policy`, via
+/// `sched_get_priority_min`/`sched_get_priority_max`. Typically `1..99` for
+/// `Fifo`/`RoundRobin` and `0..0` for `Other`.
+pub fn priority_range(policy: SchedPolicy) -> NumaResult<(i32, i32)> {
+    let raw = policy.as_raw();
+    let min = unsafe { libc::sched_get_priority_min(raw) };
+    let max = unsafe { libc::sched_get_priority_max(raw) };
+
+    if min < 0 || max < 0 {
+        return Err(NumaError::BindFailed);
+    }
+
+    Ok((min, max))
+}
+
+/// Set the current thread's scheduling policy and real-time priority via
+/// `sched_setscheduler`. `priority` is validated against
+/// [`priority_range`] before the syscall, so an out-of-range value fails
+/// with `NumaError::InvalidPriority` instead of a raw `EINVAL`.
+///
+/// Returns `NumaError::PermissionDenied` on `EPERM`, distinct from other
+/// failures, so callers can detect a missing `CAP_SYS_NICE` specifically.
+pub fn set_scheduler_policy(policy: SchedPolicy, priority: i32) -> NumaResult<()> {
+    let (min, max) = priority_range(policy)?;
+    if priority < min || priority > max {
+        return Err(NumaError::InvalidPriority);
+    }
+
+    let param = libc::sched_param {
+        sched_priority: priority,
+    };
+
+    let rc = unsafe { libc::sched_setscheduler(0, policy.as_raw(), &param) };
+    if rc == 0 {
+        return Ok(());
+    }
+
+    match std::io::Error::last_os_error().raw_os_error() {
+        Some(libc::EPERM) => Err(NumaError::PermissionDenied),
+        _ => Err(NumaError::BindFailed),
+    }
+}
+
 /// Set the preferred NUMA node for future allocations
 #[cfg(feature = "numa_cpp")]
 pub fn set_preferred(node: i32) -> NumaResult<()> {
@@ -301,6 +485,160 @@ impl NumaAllocator {
     }
 }
 
+// ============================================================================
+// GlobalAlloc adapter
+// ============================================================================
+
+/// Memory returned by `zenith_numa_alloc_onnode` is page-aligned; any
+/// request coarser than that needs the over-allocate-and-store-header
+/// fallback below.
+#[cfg(feature = "numa_cpp")]
+const NUMA_GUARANTEED_ALIGN: usize = 4096;
+
+/// Header stashed immediately before the pointer handed back for an
+/// over-aligned allocation, so `dealloc` can recover the true base pointer
+/// and size `NumaAllocator::free` needs.
+#[cfg(feature = "numa_cpp")]
+#[repr(C)]
+struct OverAlignedHeader {
+    base: *mut u8,
+    size: usize,
+}
+
+#[cfg(feature = "numa_cpp")]
+thread_local! {
+    /// Caches a `NumaAllocator` for the calling thread's `preferred_node()`,
+    /// so the allocation hot path doesn't re-run `init()`/node lookup on
+    /// every call. Invalidated (and rebuilt) if `preferred_node()` changes,
+    /// e.g. after the thread is rebound with `bind_thread_to_node`.
+    static LOCAL_ALLOCATOR: std::cell::RefCell<Option<(i32, NumaAllocator)>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+#[cfg(feature = "numa_cpp")]
+fn refresh_local_allocator(slot: &mut Option<(i32, NumaAllocator)>) {
+    let node = preferred_node();
+    let stale = !matches!(slot, Some((cached, _)) if *cached == node);
+    if stale {
+        *slot = NumaAllocator::new(node).ok().map(|allocator| (node, allocator));
+    }
+}
+
+#[cfg(feature = "numa_cpp")]
+unsafe fn over_aligned_alloc(layout: std::alloc::Layout) -> *mut u8 {
+    use std::alloc::{GlobalAlloc, System};
+
+    let header_size = std::mem::size_of::<OverAlignedHeader>();
+    let total = header_size + layout.align() + layout.size();
+
+    let base = LOCAL_ALLOCATOR.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        refresh_local_allocator(&mut slot);
+        slot.as_ref().and_then(|(_, allocator)| unsafe { allocator.alloc(total) }.ok())
+    });
+    let base = match base {
+        Some(base) => base,
+        None => System.alloc(std::alloc::Layout::from_size_align_unchecked(total, 1)),
+    };
+    if base.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let data_min = base as usize + header_size;
+    let aligned = (data_min + layout.align() - 1) & !(layout.align() - 1);
+    let header_ptr = (aligned as *mut OverAlignedHeader).sub(1);
+    header_ptr.write(OverAlignedHeader { base, size: total });
+    aligned as *mut u8
+}
+
+#[cfg(feature = "numa_cpp")]
+unsafe fn over_aligned_dealloc(ptr: *mut u8) {
+    use std::alloc::{GlobalAlloc, System};
+
+    let header = (ptr as *mut OverAlignedHeader).sub(1).read();
+    let freed = LOCAL_ALLOCATOR.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        refresh_local_allocator(&mut slot);
+        if let Some((_, allocator)) = slot.as_ref() {
+            unsafe { allocator.free(header.base, header.size) };
+            true
+        } else {
+            false
+        }
+    });
+    if !freed {
+        System.dealloc(header.base, std::alloc::Layout::from_size_align_unchecked(header.size, 1));
+    }
+}
+
+/// `GlobalAlloc` adapter that routes every allocation to the calling
+/// thread's local NUMA node via [`NumaAllocator`], so installing it with
+/// `#[global_allocator]` gives node-local heap memory by default.
+///
+/// Allocations whose alignment fits within what libnuma guarantees
+/// (`NUMA_GUARANTEED_ALIGN`) go straight to `NumaAllocator::alloc`/`free`;
+/// coarser alignments fall back to an over-allocate-and-store-header
+/// strategy so the true base pointer and size can still be recovered for
+/// `free`. When the `numa_cpp` feature is disabled, this delegates to
+/// `System` outright, so the same binary still builds and runs on
+/// non-NUMA hosts.
+pub struct NumaGlobalAlloc;
+
+#[cfg(feature = "numa_cpp")]
+unsafe impl std::alloc::GlobalAlloc for NumaGlobalAlloc {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        use std::alloc::GlobalAlloc;
+
+        if layout.align() > NUMA_GUARANTEED_ALIGN {
+            return unsafe { over_aligned_alloc(layout) };
+        }
+
+        let ptr = LOCAL_ALLOCATOR.with(|cell| {
+            let mut slot = cell.borrow_mut();
+            refresh_local_allocator(&mut slot);
+            slot.as_ref()
+                .and_then(|(_, allocator)| unsafe { allocator.alloc(layout.size()) }.ok())
+        });
+
+        ptr.unwrap_or_else(|| unsafe { std::alloc::System.alloc(layout) })
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        use std::alloc::GlobalAlloc;
+
+        if layout.align() > NUMA_GUARANTEED_ALIGN {
+            unsafe { over_aligned_dealloc(ptr) };
+            return;
+        }
+
+        let freed = LOCAL_ALLOCATOR.with(|cell| {
+            let mut slot = cell.borrow_mut();
+            refresh_local_allocator(&mut slot);
+            if let Some((_, allocator)) = slot.as_ref() {
+                unsafe { allocator.free(ptr, layout.size()) };
+                true
+            } else {
+                false
+            }
+        });
+
+        if !freed {
+            unsafe { std::alloc::System.dealloc(ptr, layout) };
+        }
+    }
+}
+
+#[cfg(not(feature = "numa_cpp"))]
+unsafe impl std::alloc::GlobalAlloc for NumaGlobalAlloc {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        unsafe { std::alloc::System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        unsafe { std::alloc::System.dealloc(ptr, layout) }
+    }
+}
+
 // ============================================================================
 // Fallback implementations when numa_cpp is not enabled
 // ============================================================================
@@ -350,4 +688,90 @@ mod tests {
         // Either it works or NUMA is unavailable, both are valid
         assert!(result.is_ok() || matches!(result, Err(NumaError::Unavailable)));
     }
+
+    #[test]
+    fn test_cpu_set_add_and_contains() {
+        let mut set = CpuSet::new();
+        assert!(!set.contains(0));
+
+        set.add(0);
+        set.add(2);
+        assert!(set.contains(0));
+        assert!(!set.contains(1));
+        assert!(set.contains(2));
+        assert_eq!(set.cpus(), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_cpu_set_from_cpus() {
+        let set = CpuSet::from_cpus(&[1, 3, 5]);
+        assert_eq!(set.cpus(), vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn test_bind_and_read_back_current_affinity() {
+        // Pin to CPU 0 only, then read the mask back - CPU 0 should always
+        // exist, so this doesn't depend on how many cores the test runner has.
+        bind_thread_to_cpus(&[0]).expect("sched_setaffinity should succeed for CPU 0");
+        let affinity = current_affinity().expect("sched_getaffinity should succeed");
+        assert_eq!(affinity.cpus(), vec![0]);
+    }
+
+    #[test]
+    fn test_priority_range_matches_policy() {
+        let (other_min, other_max) = priority_range(SchedPolicy::Other).unwrap();
+        assert_eq!((other_min, other_max), (0, 0));
+
+        let (fifo_min, fifo_max) = priority_range(SchedPolicy::Fifo).unwrap();
+        assert!(fifo_min >= 1);
+        assert!(fifo_max >= fifo_min);
+
+        let (rr_min, rr_max) = priority_range(SchedPolicy::RoundRobin).unwrap();
+        assert!(rr_min >= 1);
+        assert!(rr_max >= rr_min);
+    }
+
+    #[test]
+    fn test_set_scheduler_policy_rejects_out_of_range_priority() {
+        let (_, max) = priority_range(SchedPolicy::Fifo).unwrap();
+        let result = set_scheduler_policy(SchedPolicy::Fifo, max + 1);
+        assert_eq!(result, Err(NumaError::InvalidPriority));
+    }
+
+    #[test]
+    fn test_set_scheduler_policy_other_is_priority_zero() {
+        // SCHED_OTHER never needs CAP_SYS_NICE, so this should always succeed.
+        set_scheduler_policy(SchedPolicy::Other, 0).expect("SCHED_OTHER at priority 0 should succeed");
+    }
+
+    #[test]
+    fn test_numa_global_alloc_round_trips_small_allocation() {
+        use std::alloc::{GlobalAlloc, Layout};
+
+        let alloc = NumaGlobalAlloc;
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        unsafe {
+            let ptr = alloc.alloc(layout);
+            assert!(!ptr.is_null());
+            ptr.write_bytes(0xAB, layout.size());
+            alloc.dealloc(ptr, layout);
+        }
+    }
+
+    #[test]
+    fn test_numa_global_alloc_round_trips_over_aligned_allocation() {
+        use std::alloc::{GlobalAlloc, Layout};
+
+        // Larger than NUMA_GUARANTEED_ALIGN on the numa_cpp build, exercising
+        // the over-allocate-and-store-header path either way.
+        let alloc = NumaGlobalAlloc;
+        let layout = Layout::from_size_align(256, 65536).unwrap();
+        unsafe {
+            let ptr = alloc.alloc(layout);
+            assert!(!ptr.is_null());
+            assert_eq!(ptr as usize % layout.align(), 0);
+            ptr.write_bytes(0xCD, layout.size());
+            alloc.dealloc(ptr, layout);
+        }
+    }
 }
@@ -9,6 +9,91 @@
 //! zenith-runtime-cpu = { version = "0.3", features = ["aws_s3"] }
 //! ```
 
+use futures_core::Stream;
+
+/// How the AWS SDK client backing an [`S3Adapter`] obtains credentials.
+#[derive(Debug, Clone, Default)]
+pub enum S3Credentials {
+    /// Use the SDK's standard credential chain (env vars, shared config
+    /// file, IMDS, etc.) via `aws_config::defaults`. The right choice for
+    /// real AWS in almost every case.
+    #[default]
+    Default,
+    /// Static, caller-supplied credentials. Mainly for MinIO/LocalStack,
+    /// where there's no IAM to assume a role from.
+    Static {
+        access_key: String,
+        secret_key: String,
+        session_token: Option<String>,
+    },
+    /// Exchange a projected Kubernetes service-account token for temporary
+    /// credentials via STS `AssumeRoleWithWebIdentity`, the mechanism behind
+    /// EKS IAM roles for service accounts (IRSA).
+    WebIdentity {
+        /// ARN of the role to assume
+        role_arn: String,
+        /// Path to the projected service-account token file
+        token_file: String,
+    },
+    /// Query the EC2/ECS instance metadata service for temporary
+    /// credentials. Cached by the SDK's credential provider and refreshed
+    /// automatically as they near expiry, so callers never see a stale or
+    /// expired credential.
+    InstanceMetadata,
+}
+
+/// Compression codec applied transparently by [`S3Adapter::put_object_compressed`]/
+/// [`S3Adapter::read_object_decompressed`], the way Fluentd-style pipelines
+/// store compressed objects to cut storage costs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// Store/read the object as-is
+    #[default]
+    None,
+    /// gzip (`Content-Encoding: gzip`, `.gz` key suffix)
+    Gzip,
+    /// Zstandard (`Content-Encoding: zstd`, `.zst` key suffix)
+    Zstd,
+}
+
+impl Compression {
+    /// Key suffix appended on write, so a listing shows which objects are
+    /// compressed without a HEAD request.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Compression::None => "",
+            Compression::Gzip => ".gz",
+            Compression::Zstd => ".zst",
+        }
+    }
+
+    /// Value written to the `Content-Encoding` header on write.
+    pub fn content_encoding(&self) -> Option<&'static str> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some("gzip"),
+            Compression::Zstd => Some("zstd"),
+        }
+    }
+
+    /// Infer the codec of an already-stored object from its `Content-Encoding`
+    /// metadata, falling back to the key's file extension.
+    fn from_key_or_encoding(key: &str, content_encoding: Option<&str>) -> Self {
+        match content_encoding {
+            Some("gzip") => return Compression::Gzip,
+            Some("zstd") => return Compression::Zstd,
+            _ => {}
+        }
+        if key.ends_with(".gz") {
+            Compression::Gzip
+        } else if key.ends_with(".zst") {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+}
+
 /// S3 configuration
 #[derive(Debug, Clone)]
 pub struct S3Config {
@@ -24,6 +109,13 @@ pub struct S3Config {
     pub max_connections: usize,
     /// Request timeout in seconds
     pub timeout_secs: u64,
+    /// How the client obtains credentials
+    pub credentials: S3Credentials,
+    /// Page size for `list_objects`/`list_objects_paginated` (`max-keys` on
+    /// the request). `None` leaves it to the server default (1000).
+    pub max_keys: Option<i32>,
+    /// Codec used by `put_object_compressed`/`read_object_decompressed`
+    pub compression: Compression,
 }
 
 impl Default for S3Config {
@@ -35,6 +127,9 @@ impl Default for S3Config {
             path_style: false,
             max_connections: 8,
             timeout_secs: 30,
+            credentials: S3Credentials::default(),
+            max_keys: None,
+            compression: Compression::default(),
         }
     }
 }
@@ -48,18 +143,36 @@ impl S3Config {
             ..Default::default()
         }
     }
-    
+
     /// Use custom endpoint (MinIO, LocalStack)
     pub fn with_endpoint(mut self, endpoint: &str) -> Self {
         self.endpoint = Some(endpoint.to_string());
         self
     }
-    
+
     /// Enable path-style addressing (required for MinIO)
     pub fn with_path_style(mut self, path_style: bool) -> Self {
         self.path_style = path_style;
         self
     }
+
+    /// Override how the client obtains credentials
+    pub fn with_credentials(mut self, credentials: S3Credentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Set the page size used when listing objects
+    pub fn with_max_keys(mut self, max_keys: i32) -> Self {
+        self.max_keys = Some(max_keys);
+        self
+    }
+
+    /// Set the codec used by `put_object_compressed`/`read_object_decompressed`
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
 }
 
 /// S3 object reference
@@ -86,6 +199,8 @@ pub enum S3Error {
     NotFound(String),
     /// Access denied
     AccessDenied(String),
+    /// Requested byte range could not be satisfied (HTTP 416)
+    InvalidRange(String),
     /// General S3 error
     Other(String),
 }
@@ -97,6 +212,7 @@ impl std::fmt::Display for S3Error {
             Self::Connection(msg) => write!(f, "Connection error: {}", msg),
             Self::NotFound(msg) => write!(f, "Not found: {}", msg),
             Self::AccessDenied(msg) => write!(f, "Access denied: {}", msg),
+            Self::InvalidRange(msg) => write!(f, "Invalid byte range: {}", msg),
             Self::Other(msg) => write!(f, "S3 error: {}", msg),
         }
     }
@@ -104,16 +220,131 @@ impl std::fmt::Display for S3Error {
 
 impl std::error::Error for S3Error {}
 
+/// Result of a partial-object read ([`S3Adapter::read_object_range`]/
+/// [`S3Adapter::read_object_suffix`]): the requested bytes plus the full
+/// object size, parsed from the response's `Content-Range` header, so
+/// callers (e.g. a Parquet footer reader) know the file's total extent
+/// without a separate HEAD request.
+#[derive(Debug, Clone)]
+pub struct S3RangeRead {
+    /// Bytes returned for the requested range
+    pub data: Vec<u8>,
+    /// Total size of the object, independent of how much was requested
+    pub total_size: u64,
+}
+
 // ============================================================================
 // AWS SDK Implementation (when aws_s3 feature is enabled)
 // ============================================================================
 
+/// Minimum size of every part but the last in a multipart upload, per the
+/// S3 API contract.
+#[cfg(feature = "aws_s3")]
+const MIN_MULTIPART_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// Result of a completed multipart upload part, needed to build the
+/// `CompleteMultipartUpload` request in order.
+#[cfg(feature = "aws_s3")]
+#[derive(Debug, Clone)]
+pub struct UploadedPart {
+    /// 1-based part number, matching upload order
+    pub part_number: i32,
+    /// ETag returned by S3 for this part
+    pub etag: String,
+}
+
 #[cfg(feature = "aws_s3")]
 mod aws_impl {
     use super::*;
+    use aws_config::imds::credentials::ImdsCredentialsProvider;
+    use aws_config::web_identity_token::{StaticConfiguration, WebIdentityTokenCredentialsProvider};
+    use aws_credential_types::provider::SharedCredentialsProvider;
+    use aws_credential_types::Credentials;
+    use async_compression::tokio::bufread::{GzipDecoder, ZstdDecoder};
     use aws_sdk_s3::Client;
     use aws_sdk_s3::config::{Region, Builder};
-    
+    use aws_sdk_s3::presigning::PresigningConfig;
+    use aws_sdk_s3::primitives::ByteStream;
+    use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+    use base64::Engine;
+    use std::io::{Read, Write};
+    use std::time::Duration;
+    use tokio::io::{AsyncRead, AsyncReadExt, BufReader};
+
+    /// Base64-encoded MD5 digest of `body`, for the `Content-MD5` header S3
+    /// requires to succeed against object-lock-enabled buckets and to catch
+    /// in-transit corruption.
+    fn content_md5(body: &[u8]) -> String {
+        let digest = md5::compute(body);
+        base64::engine::general_purpose::STANDARD.encode(digest.0)
+    }
+
+    /// Compress `data` with `compression`, returning it unchanged for
+    /// [`Compression::None`].
+    fn compress_bytes(compression: Compression, data: &[u8]) -> Result<Vec<u8>, S3Error> {
+        match compression {
+            Compression::None => Ok(data.to_vec()),
+            Compression::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data).map_err(|e| S3Error::Other(e.to_string()))?;
+                encoder.finish().map_err(|e| S3Error::Other(e.to_string()))
+            }
+            Compression::Zstd => {
+                zstd::encode_all(data, 0).map_err(|e| S3Error::Other(e.to_string()))
+            }
+        }
+    }
+
+    /// Decompress `data` with `compression`, returning it unchanged for
+    /// [`Compression::None`].
+    fn decompress_bytes(compression: Compression, data: &[u8]) -> Result<Vec<u8>, S3Error> {
+        match compression {
+            Compression::None => Ok(data.to_vec()),
+            Compression::Gzip => {
+                let mut decoder = flate2::read::GzDecoder::new(data);
+                let mut out = Vec::new();
+                decoder.read_to_end(&mut out).map_err(|e| S3Error::Other(e.to_string()))?;
+                Ok(out)
+            }
+            Compression::Zstd => {
+                zstd::decode_all(data).map_err(|e| S3Error::Other(e.to_string()))
+            }
+        }
+    }
+
+    /// Read `reader` to exhaustion in `chunk_size` pieces, invoking
+    /// `callback` for each and stopping early if it returns `false`. Shared
+    /// by [`S3Adapter::stream_object_decompressed`] so the decompressor
+    /// (or the raw body for [`Compression::None`]) never has to be buffered
+    /// whole.
+    async fn drain_into_callback<R, F>(
+        mut reader: R,
+        chunk_size: usize,
+        callback: &mut F,
+    ) -> Result<u64, S3Error>
+    where
+        R: AsyncRead + Unpin,
+        F: FnMut(&[u8]) -> bool,
+    {
+        let mut total_bytes = 0u64;
+        let mut buf = vec![0u8; chunk_size];
+        loop {
+            let n = reader
+                .read(&mut buf)
+                .await
+                .map_err(|e| S3Error::Other(e.to_string()))?;
+            if n == 0 {
+                break;
+            }
+            total_bytes += n as u64;
+            if !callback(&buf[..n]) {
+                break;
+            }
+        }
+        Ok(total_bytes)
+    }
+
     /// S3 adapter with real AWS SDK
     pub struct S3Adapter {
         client: Client,
@@ -123,49 +354,155 @@ mod aws_impl {
     impl S3Adapter {
         /// Create new S3 adapter with AWS SDK
         pub async fn new(config: S3Config) -> Result<Self, S3Error> {
-            let sdk_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
-                .region(Region::new(config.region.clone()))
-                .load()
-                .await;
-            
+            let mut config_loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+                .region(Region::new(config.region.clone()));
+
+            if let Some(provider) = Self::credentials_provider(&config.credentials)? {
+                config_loader = config_loader.credentials_provider(provider);
+            }
+
+            let sdk_config = config_loader.load().await;
+
             let mut s3_config_builder = Builder::from(&sdk_config);
-            
+
             if let Some(endpoint) = &config.endpoint {
                 s3_config_builder = s3_config_builder.endpoint_url(endpoint);
             }
-            
+
             if config.path_style {
                 s3_config_builder = s3_config_builder.force_path_style(true);
             }
-            
+
             let client = Client::from_conf(s3_config_builder.build());
-            
+
             Ok(Self { client, config })
         }
+
+        /// Resolve `credentials` into an SDK credentials provider to install
+        /// on the config loader. Returns `None` for [`S3Credentials::Default`],
+        /// leaving the SDK's standard credential chain in place.
+        fn credentials_provider(
+            credentials: &S3Credentials,
+        ) -> Result<Option<SharedCredentialsProvider>, S3Error> {
+            let provider = match credentials {
+                S3Credentials::Default => return Ok(None),
+                S3Credentials::Static { access_key, secret_key, session_token } => {
+                    SharedCredentialsProvider::new(Credentials::new(
+                        access_key,
+                        secret_key,
+                        session_token.clone(),
+                        None,
+                        "zenith-static",
+                    ))
+                }
+                S3Credentials::WebIdentity { role_arn, token_file } => {
+                    SharedCredentialsProvider::new(
+                        WebIdentityTokenCredentialsProvider::builder()
+                            .static_configuration(StaticConfiguration {
+                                web_identity_token_file: token_file.into(),
+                                role_arn: role_arn.clone(),
+                                session_name: "zenith-dataplane".to_string(),
+                            })
+                            .build(),
+                    )
+                }
+                S3Credentials::InstanceMetadata => {
+                    // `ImdsCredentialsProvider` caches the credentials it
+                    // fetches and transparently refreshes them as they
+                    // approach expiry, so callers never observe a stale
+                    // credential.
+                    SharedCredentialsProvider::new(ImdsCredentialsProvider::builder().build())
+                }
+            };
+            Ok(Some(provider))
+        }
         
-        /// List objects with prefix (Issue #41)
+        /// List objects with prefix (Issue #41). Loops on `is_truncated`/
+        /// `next_continuation_token` so prefixes with more than the 1000-key
+        /// server cap per page are returned in full rather than silently
+        /// truncated.
         pub async fn list_objects(&self, prefix: &str) -> Result<Vec<S3Object>, S3Error> {
-            let resp = self.client
-                .list_objects_v2()
-                .bucket(&self.config.bucket)
-                .prefix(prefix)
-                .send()
-                .await
-                .map_err(|e| S3Error::Connection(e.to_string()))?;
-            
-            let objects: Vec<S3Object> = resp.contents()
-                .iter()
-                .map(|obj| S3Object {
+            let mut objects = Vec::new();
+            let mut continuation_token: Option<String> = None;
+
+            loop {
+                let mut req = self.client
+                    .list_objects_v2()
+                    .bucket(&self.config.bucket)
+                    .prefix(prefix);
+
+                if let Some(max_keys) = self.config.max_keys {
+                    req = req.max_keys(max_keys);
+                }
+                if let Some(token) = &continuation_token {
+                    req = req.continuation_token(token);
+                }
+
+                let resp = req.send().await.map_err(|e| S3Error::Connection(e.to_string()))?;
+
+                objects.extend(resp.contents().iter().map(|obj| S3Object {
                     key: obj.key().unwrap_or_default().to_string(),
                     size: obj.size().unwrap_or(0) as u64,
                     etag: obj.e_tag().map(|s| s.to_string()),
                     last_modified: obj.last_modified().map(|dt| dt.to_string()),
-                })
-                .collect();
-            
+                }));
+
+                if resp.is_truncated().unwrap_or(false) {
+                    continuation_token = resp.next_continuation_token().map(|s| s.to_string());
+                } else {
+                    break;
+                }
+            }
+
             Ok(objects)
         }
-        
+
+        /// Stream objects under `prefix` as soon as each page arrives,
+        /// instead of buffering the full listing like [`Self::list_objects`]
+        /// does. Useful for prefixes large enough that starting to process
+        /// keys before pagination finishes matters.
+        pub fn list_objects_paginated(
+            &self,
+            prefix: &str,
+        ) -> impl Stream<Item = Result<S3Object, S3Error>> + '_ {
+            let prefix = prefix.to_string();
+            async_stream::try_stream! {
+                let mut continuation_token: Option<String> = None;
+
+                loop {
+                    let mut req = self.client
+                        .list_objects_v2()
+                        .bucket(&self.config.bucket)
+                        .prefix(&prefix);
+
+                    if let Some(max_keys) = self.config.max_keys {
+                        req = req.max_keys(max_keys);
+                    }
+                    if let Some(token) = &continuation_token {
+                        req = req.continuation_token(token);
+                    }
+
+                    let resp = req.send().await.map_err(|e| S3Error::Connection(e.to_string()))?;
+
+                    for obj in resp.contents() {
+                        yield S3Object {
+                            key: obj.key().unwrap_or_default().to_string(),
+                            size: obj.size().unwrap_or(0) as u64,
+                            etag: obj.e_tag().map(|s| s.to_string()),
+                            last_modified: obj.last_modified().map(|dt| dt.to_string()),
+                        };
+                    }
+
+                    if resp.is_truncated().unwrap_or(false) {
+                        continuation_token = resp.next_continuation_token().map(|s| s.to_string());
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+
+
         /// Read object contents (Issue #42)
         pub async fn read_object(&self, key: &str) -> Result<Vec<u8>, S3Error> {
             let resp = self.client
@@ -194,7 +531,96 @@ mod aws_impl {
             
             Ok(data)
         }
-        
+
+        /// Read an object and transparently inflate it, inferring the codec
+        /// from its `Content-Encoding` metadata (falling back to the key's
+        /// file extension) the way a Fluentd-style S3 pipeline stores
+        /// compressed Arrow record batches to cut storage costs.
+        pub async fn read_object_decompressed(&self, key: &str) -> Result<Vec<u8>, S3Error> {
+            let resp = self.client
+                .get_object()
+                .bucket(&self.config.bucket)
+                .key(key)
+                .send()
+                .await
+                .map_err(|e| {
+                    let err_str = e.to_string();
+                    if err_str.contains("NoSuchKey") {
+                        S3Error::NotFound(key.to_string())
+                    } else if err_str.contains("AccessDenied") {
+                        S3Error::AccessDenied(key.to_string())
+                    } else {
+                        S3Error::Connection(err_str)
+                    }
+                })?;
+
+            let compression = Compression::from_key_or_encoding(key, resp.content_encoding());
+
+            let data = resp.body
+                .collect()
+                .await
+                .map_err(|e| S3Error::Connection(e.to_string()))?
+                .into_bytes()
+                .to_vec();
+
+            decompress_bytes(compression, &data)
+        }
+
+        /// Read the bytes in `[start, end]` (inclusive, 0-indexed) of an
+        /// object via `Range: bytes=start-end`, e.g. to fetch just a
+        /// Parquet footer/metadata block or a selected row group instead of
+        /// the whole file. Returns the partial bytes alongside the object's
+        /// total size, parsed from the response's `Content-Range` header.
+        pub async fn read_object_range(&self, key: &str, start: u64, end: u64) -> Result<S3RangeRead, S3Error> {
+            self.get_object_with_range(key, format!("bytes={}-{}", start, end)).await
+        }
+
+        /// Read the trailing `n` bytes of an object via `Range: bytes=-n`,
+        /// the common case of fetching just a columnar format's footer
+        /// without knowing the object's size up front.
+        pub async fn read_object_suffix(&self, key: &str, n: u64) -> Result<S3RangeRead, S3Error> {
+            self.get_object_with_range(key, format!("bytes=-{}", n)).await
+        }
+
+        /// Shared GetObject-with-Range implementation for
+        /// [`Self::read_object_range`]/[`Self::read_object_suffix`].
+        async fn get_object_with_range(&self, key: &str, range: String) -> Result<S3RangeRead, S3Error> {
+            let resp = self.client
+                .get_object()
+                .bucket(&self.config.bucket)
+                .key(key)
+                .range(range)
+                .send()
+                .await
+                .map_err(|e| {
+                    let err_str = e.to_string();
+                    if err_str.contains("NoSuchKey") {
+                        S3Error::NotFound(key.to_string())
+                    } else if err_str.contains("AccessDenied") {
+                        S3Error::AccessDenied(key.to_string())
+                    } else if err_str.contains("InvalidRange") || err_str.contains("416") {
+                        S3Error::InvalidRange(err_str)
+                    } else {
+                        S3Error::Connection(err_str)
+                    }
+                })?;
+
+            let total_size = resp
+                .content_range()
+                .and_then(parse_content_range_total)
+                .or_else(|| resp.content_length().map(|len| len as u64))
+                .unwrap_or(0);
+
+            let data = resp.body
+                .collect()
+                .await
+                .map_err(|e| S3Error::Connection(e.to_string()))?
+                .into_bytes()
+                .to_vec();
+
+            Ok(S3RangeRead { data, total_size })
+        }
+
         /// Stream object in chunks (Issue #43)
         pub async fn stream_object<F>(
             &self,
@@ -241,7 +667,44 @@ mod aws_impl {
             
             Ok(total_bytes)
         }
-        
+
+        /// Like [`Self::stream_object`], but transparently inflates a
+        /// compressed object as its bytes arrive instead of buffering the
+        /// whole object before decompressing it. The codec is inferred the
+        /// same way as [`Self::read_object_decompressed`].
+        pub async fn stream_object_decompressed<F>(
+            &self,
+            key: &str,
+            chunk_size: usize,
+            mut callback: F,
+        ) -> Result<u64, S3Error>
+        where
+            F: FnMut(&[u8]) -> bool,
+        {
+            let resp = self.client
+                .get_object()
+                .bucket(&self.config.bucket)
+                .key(key)
+                .send()
+                .await
+                .map_err(|e| S3Error::Connection(e.to_string()))?;
+
+            let compression = Compression::from_key_or_encoding(key, resp.content_encoding());
+            let body = BufReader::new(resp.body.into_async_read());
+
+            match compression {
+                Compression::None => {
+                    drain_into_callback(body, chunk_size, &mut callback).await
+                }
+                Compression::Gzip => {
+                    drain_into_callback(GzipDecoder::new(body), chunk_size, &mut callback).await
+                }
+                Compression::Zstd => {
+                    drain_into_callback(ZstdDecoder::new(body), chunk_size, &mut callback).await
+                }
+            }
+        }
+
         /// Check if object exists (Issue #44)
         pub async fn object_exists(&self, key: &str) -> Result<bool, S3Error> {
             match self.client
@@ -281,11 +744,226 @@ mod aws_impl {
             })
         }
         
+        /// Upload a small object in a single request, with a `Content-MD5`
+        /// header computed from `bytes` so S3 rejects the request on
+        /// in-transit corruption and object-lock-enabled buckets accept it.
+        pub async fn put_object(&self, key: &str, bytes: Vec<u8>) -> Result<(), S3Error> {
+            let md5 = content_md5(&bytes);
+            self.client
+                .put_object()
+                .bucket(&self.config.bucket)
+                .key(key)
+                .content_md5(md5)
+                .body(ByteStream::from(bytes))
+                .send()
+                .await
+                .map_err(|e| S3Error::Connection(e.to_string()))?;
+            Ok(())
+        }
+
+        /// Compress `bytes` with `S3Config.compression` and upload it,
+        /// appending the codec's suffix to `key` and setting
+        /// `Content-Encoding` so a plain `read_object`/`get_object` from
+        /// another client still knows how to inflate it.
+        pub async fn put_object_compressed(&self, key: &str, bytes: Vec<u8>) -> Result<(), S3Error> {
+            let compression = self.config.compression;
+            let compressed = compress_bytes(compression, &bytes)?;
+            let md5 = content_md5(&compressed);
+            let full_key = format!("{key}{}", compression.suffix());
+
+            let mut req = self.client
+                .put_object()
+                .bucket(&self.config.bucket)
+                .key(full_key)
+                .content_md5(md5)
+                .body(ByteStream::from(compressed));
+
+            if let Some(encoding) = compression.content_encoding() {
+                req = req.content_encoding(encoding);
+            }
+
+            req.send().await.map_err(|e| S3Error::Connection(e.to_string()))?;
+            Ok(())
+        }
+
+        /// Upload a large object read from `stream` using the S3 multipart
+        /// upload protocol: CreateMultipartUpload, then UploadPart for each
+        /// buffered part of at least `part_size` bytes (minimum 5 MiB,
+        /// enforced by S3), then CompleteMultipartUpload with the parts in
+        /// order. Any failure aborts the upload via AbortMultipartUpload so
+        /// no dangling parts remain in the bucket.
+        pub async fn multipart_upload<R>(
+            &self,
+            key: &str,
+            mut stream: R,
+            part_size: usize,
+        ) -> Result<(), S3Error>
+        where
+            R: tokio::io::AsyncRead + Unpin,
+        {
+            let part_size = part_size.max(MIN_MULTIPART_PART_SIZE);
+
+            let create_resp = self
+                .client
+                .create_multipart_upload()
+                .bucket(&self.config.bucket)
+                .key(key)
+                .send()
+                .await
+                .map_err(|e| S3Error::Connection(e.to_string()))?;
+            let upload_id = create_resp
+                .upload_id()
+                .ok_or_else(|| S3Error::Other("CreateMultipartUpload returned no UploadId".to_string()))?
+                .to_string();
+
+            match self
+                .upload_parts_and_complete(key, &upload_id, &mut stream, part_size)
+                .await
+            {
+                Ok(()) => Ok(()),
+                Err(e) => {
+                    // Best-effort: failing to abort leaves a dangling upload
+                    // billed by S3, but must not mask the original error.
+                    let _ = self
+                        .client
+                        .abort_multipart_upload()
+                        .bucket(&self.config.bucket)
+                        .key(key)
+                        .upload_id(&upload_id)
+                        .send()
+                        .await;
+                    Err(e)
+                }
+            }
+        }
+
+        /// Reads `stream` into `part_size` chunks, uploads each as a part,
+        /// then completes the upload. Split out of [`Self::multipart_upload`]
+        /// so its caller can abort on any `Err` this returns.
+        async fn upload_parts_and_complete<R>(
+            &self,
+            key: &str,
+            upload_id: &str,
+            stream: &mut R,
+            part_size: usize,
+        ) -> Result<(), S3Error>
+        where
+            R: tokio::io::AsyncRead + Unpin,
+        {
+            let mut parts = Vec::new();
+            let mut part_number = 1i32;
+            let mut buf = vec![0u8; part_size];
+
+            loop {
+                let mut filled = 0;
+                while filled < buf.len() {
+                    let n = stream
+                        .read(&mut buf[filled..])
+                        .await
+                        .map_err(|e| S3Error::Other(e.to_string()))?;
+                    if n == 0 {
+                        break;
+                    }
+                    filled += n;
+                }
+                if filled == 0 {
+                    break;
+                }
+
+                let part_body = buf[..filled].to_vec();
+                let md5 = content_md5(&part_body);
+                let upload_resp = self
+                    .client
+                    .upload_part()
+                    .bucket(&self.config.bucket)
+                    .key(key)
+                    .upload_id(upload_id)
+                    .part_number(part_number)
+                    .content_md5(md5)
+                    .body(ByteStream::from(part_body))
+                    .send()
+                    .await
+                    .map_err(|e| S3Error::Connection(e.to_string()))?;
+                let etag = upload_resp
+                    .e_tag()
+                    .ok_or_else(|| S3Error::Other("UploadPart returned no ETag".to_string()))?
+                    .to_string();
+                parts.push(UploadedPart { part_number, etag });
+                part_number += 1;
+
+                if filled < buf.len() {
+                    break;
+                }
+            }
+
+            let completed_parts = parts
+                .into_iter()
+                .map(|p| {
+                    CompletedPart::builder()
+                        .part_number(p.part_number)
+                        .e_tag(p.etag)
+                        .build()
+                })
+                .collect();
+
+            self.client
+                .complete_multipart_upload()
+                .bucket(&self.config.bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .multipart_upload(
+                    CompletedMultipartUpload::builder()
+                        .set_parts(Some(completed_parts))
+                        .build(),
+                )
+                .send()
+                .await
+                .map_err(|e| S3Error::Connection(e.to_string()))?;
+
+            Ok(())
+        }
+
+        /// Generate a time-limited, signed HTTPS URL that lets a third party
+        /// `GET` this object without holding Zenith's credentials. Honors
+        /// `S3Config.endpoint`/`path_style` so the URL also works against
+        /// MinIO/LocalStack.
+        pub async fn presign_get(&self, key: &str, expires_in: Duration) -> Result<String, S3Error> {
+            let presigning_config = PresigningConfig::expires_in(expires_in)
+                .map_err(|e| S3Error::Other(e.to_string()))?;
+            let presigned = self
+                .client
+                .get_object()
+                .bucket(&self.config.bucket)
+                .key(key)
+                .presigned(presigning_config)
+                .await
+                .map_err(|e| S3Error::Connection(e.to_string()))?;
+            Ok(presigned.uri().to_string())
+        }
+
+        /// Generate a time-limited, signed HTTPS URL that lets a third party
+        /// `PUT` this object without holding Zenith's credentials. Honors
+        /// `S3Config.endpoint`/`path_style` so the URL also works against
+        /// MinIO/LocalStack.
+        pub async fn presign_put(&self, key: &str, expires_in: Duration) -> Result<String, S3Error> {
+            let presigning_config = PresigningConfig::expires_in(expires_in)
+                .map_err(|e| S3Error::Other(e.to_string()))?;
+            let presigned = self
+                .client
+                .put_object()
+                .bucket(&self.config.bucket)
+                .key(key)
+                .presigned(presigning_config)
+                .await
+                .map_err(|e| S3Error::Connection(e.to_string()))?;
+            Ok(presigned.uri().to_string())
+        }
+
         /// Get bucket name
         pub fn bucket(&self) -> &str {
             &self.config.bucket
         }
-        
+
         /// Get region
         pub fn region(&self) -> &str {
             &self.config.region
@@ -303,13 +981,28 @@ pub use aws_impl::S3Adapter;
 #[cfg(not(feature = "aws_s3"))]
 mod stub_impl {
     use super::*;
-    
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// A [`Stream`] that immediately yields one item then completes, used
+    /// to back [`S3Adapter::list_objects_paginated`] when the `aws_s3`
+    /// feature is disabled.
+    struct NotEnabledStream(Option<S3Error>);
+
+    impl Stream for NotEnabledStream {
+        type Item = Result<S3Object, S3Error>;
+
+        fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Poll::Ready(self.0.take().map(Err))
+        }
+    }
+
     /// S3 adapter stub (enable aws_s3 feature for real implementation)
     #[derive(Debug)]
     pub struct S3Adapter {
         config: S3Config,
     }
-    
+
     impl S3Adapter {
         /// Create new S3 adapter (stub)
         pub fn new(config: S3Config) -> Self {
@@ -323,7 +1016,14 @@ mod stub_impl {
                  Add `features = [\"aws_s3\"]` to your Cargo.toml.".to_string()
             ))
         }
-        
+
+        /// Paginated listing - requires aws_s3 feature
+        pub fn list_objects_paginated(&self, _prefix: &str) -> impl Stream<Item = Result<S3Object, S3Error>> {
+            NotEnabledStream(Some(S3Error::NotEnabled(
+                "Enable the 'aws_s3' feature to use S3.".to_string()
+            )))
+        }
+
         /// Read object - requires aws_s3 feature
         pub fn read_object(&self, _key: &str) -> Result<Vec<u8>, S3Error> {
             Err(S3Error::NotEnabled(
@@ -348,7 +1048,78 @@ mod stub_impl {
                 "Enable the 'aws_s3' feature to use S3.".to_string()
             ))
         }
-        
+
+        /// Upload an object - requires aws_s3 feature
+        pub fn put_object(&self, _key: &str, _bytes: Vec<u8>) -> Result<(), S3Error> {
+            Err(S3Error::NotEnabled(
+                "Enable the 'aws_s3' feature to use S3.".to_string()
+            ))
+        }
+
+        /// Multipart upload - requires aws_s3 feature
+        pub fn multipart_upload(
+            &self,
+            _key: &str,
+            _part_size: usize,
+        ) -> Result<(), S3Error> {
+            Err(S3Error::NotEnabled(
+                "Enable the 'aws_s3' feature to use S3.".to_string()
+            ))
+        }
+
+        /// Byte-range read - requires aws_s3 feature
+        pub fn read_object_range(&self, _key: &str, _start: u64, _end: u64) -> Result<S3RangeRead, S3Error> {
+            Err(S3Error::NotEnabled(
+                "Enable the 'aws_s3' feature to use S3.".to_string()
+            ))
+        }
+
+        /// Suffix (trailing bytes) read - requires aws_s3 feature
+        pub fn read_object_suffix(&self, _key: &str, _n: u64) -> Result<S3RangeRead, S3Error> {
+            Err(S3Error::NotEnabled(
+                "Enable the 'aws_s3' feature to use S3.".to_string()
+            ))
+        }
+
+        /// Decompressed read - requires aws_s3 feature
+        pub fn read_object_decompressed(&self, _key: &str) -> Result<Vec<u8>, S3Error> {
+            Err(S3Error::NotEnabled(
+                "Enable the 'aws_s3' feature to use S3.".to_string()
+            ))
+        }
+
+        /// Decompressed streaming read - requires aws_s3 feature
+        pub fn stream_object_decompressed(
+            &self,
+            _key: &str,
+            _chunk_size: usize,
+        ) -> Result<(), S3Error> {
+            Err(S3Error::NotEnabled(
+                "Enable the 'aws_s3' feature to use S3.".to_string()
+            ))
+        }
+
+        /// Compressed upload - requires aws_s3 feature
+        pub fn put_object_compressed(&self, _key: &str, _bytes: Vec<u8>) -> Result<(), S3Error> {
+            Err(S3Error::NotEnabled(
+                "Enable the 'aws_s3' feature to use S3.".to_string()
+            ))
+        }
+
+        /// Presigned GET URL - requires aws_s3 feature
+        pub fn presign_get(&self, _key: &str, _expires_in: std::time::Duration) -> Result<String, S3Error> {
+            Err(S3Error::NotEnabled(
+                "Enable the 'aws_s3' feature to use S3.".to_string()
+            ))
+        }
+
+        /// Presigned PUT URL - requires aws_s3 feature
+        pub fn presign_put(&self, _key: &str, _expires_in: std::time::Duration) -> Result<String, S3Error> {
+            Err(S3Error::NotEnabled(
+                "Enable the 'aws_s3' feature to use S3.".to_string()
+            ))
+        }
+
         /// Get bucket name
         pub fn bucket(&self) -> &str {
             &self.config.bucket
@@ -388,6 +1159,14 @@ pub fn is_s3_path(path: &str) -> bool {
     path.starts_with("s3://") || path.starts_with("s3a://")
 }
 
+/// Parse the total object size out of a `Content-Range` header value
+/// (`"bytes start-end/total"`), returning `None` if it's missing or
+/// malformed.
+#[cfg_attr(not(feature = "aws_s3"), allow(dead_code))]
+fn parse_content_range_total(content_range: &str) -> Option<u64> {
+    content_range.rsplit_once('/')?.1.parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -423,8 +1202,30 @@ mod tests {
         assert_eq!(config.region, "us-west-2");
         assert_eq!(config.endpoint, Some("http://localhost:9000".to_string()));
         assert!(config.path_style);
+        assert!(matches!(config.credentials, S3Credentials::Default));
+        assert_eq!(config.max_keys, None);
     }
-    
+
+    #[test]
+    fn test_s3_config_with_max_keys() {
+        let config = S3Config::new("bucket", "us-east-1").with_max_keys(200);
+        assert_eq!(config.max_keys, Some(200));
+    }
+
+    #[test]
+    fn test_s3_config_with_credentials() {
+        let config = S3Config::new("bucket", "us-east-1").with_credentials(S3Credentials::Static {
+            access_key: "AKIA".to_string(),
+            secret_key: "secret".to_string(),
+            session_token: None,
+        });
+
+        assert!(matches!(
+            config.credentials,
+            S3Credentials::Static { access_key, .. } if access_key == "AKIA"
+        ));
+    }
+
     #[test]
     #[cfg(not(feature = "aws_s3"))]
     fn test_stub_returns_not_enabled() {
@@ -439,5 +1240,105 @@ mod tests {
             adapter.read_object("key"),
             Err(S3Error::NotEnabled(_))
         ));
+
+        assert!(matches!(
+            adapter.put_object("key", vec![1, 2, 3]),
+            Err(S3Error::NotEnabled(_))
+        ));
+
+        assert!(matches!(
+            adapter.multipart_upload("key", 5 * 1024 * 1024),
+            Err(S3Error::NotEnabled(_))
+        ));
+
+        assert!(matches!(
+            adapter.read_object_range("key", 0, 99),
+            Err(S3Error::NotEnabled(_))
+        ));
+
+        assert!(matches!(
+            adapter.read_object_suffix("key", 1024),
+            Err(S3Error::NotEnabled(_))
+        ));
+
+        assert!(matches!(
+            adapter.presign_get("key", std::time::Duration::from_secs(3600)),
+            Err(S3Error::NotEnabled(_))
+        ));
+
+        assert!(matches!(
+            adapter.presign_put("key", std::time::Duration::from_secs(3600)),
+            Err(S3Error::NotEnabled(_))
+        ));
+
+        assert!(matches!(
+            adapter.read_object_decompressed("key"),
+            Err(S3Error::NotEnabled(_))
+        ));
+
+        assert!(matches!(
+            adapter.stream_object_decompressed("key", 4096),
+            Err(S3Error::NotEnabled(_))
+        ));
+
+        assert!(matches!(
+            adapter.put_object_compressed("key", vec![1, 2, 3]),
+            Err(S3Error::NotEnabled(_))
+        ));
+    }
+
+    #[test]
+    fn test_compression_suffix_and_content_encoding() {
+        assert_eq!(Compression::None.suffix(), "");
+        assert_eq!(Compression::Gzip.suffix(), ".gz");
+        assert_eq!(Compression::Zstd.suffix(), ".zst");
+
+        assert_eq!(Compression::None.content_encoding(), None);
+        assert_eq!(Compression::Gzip.content_encoding(), Some("gzip"));
+        assert_eq!(Compression::Zstd.content_encoding(), Some("zstd"));
+    }
+
+    #[test]
+    fn test_compression_from_key_or_encoding() {
+        assert_eq!(
+            Compression::from_key_or_encoding("batch.parquet.gz", None),
+            Compression::Gzip
+        );
+        assert_eq!(
+            Compression::from_key_or_encoding("batch.parquet.zst", None),
+            Compression::Zstd
+        );
+        assert_eq!(
+            Compression::from_key_or_encoding("batch.parquet", Some("zstd")),
+            Compression::Zstd
+        );
+        assert_eq!(
+            Compression::from_key_or_encoding("batch.parquet", None),
+            Compression::None
+        );
+    }
+
+    #[test]
+    fn test_s3_config_with_compression() {
+        let config = S3Config::new("bucket", "us-east-1").with_compression(Compression::Zstd);
+        assert_eq!(config.compression, Compression::Zstd);
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "aws_s3"))]
+    async fn test_stub_list_objects_paginated_not_enabled() {
+        let adapter = S3Adapter::new(S3Config::new("bucket", "us-east-1"));
+        let stream = adapter.list_objects_paginated("prefix");
+        let mut stream = std::pin::pin!(stream);
+        let item = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await;
+        assert!(matches!(item, Some(Err(S3Error::NotEnabled(_)))));
+    }
+
+    #[test]
+    fn test_parse_content_range_total() {
+        assert_eq!(parse_content_range_total("bytes 0-99/1234"), Some(1234));
+        assert_eq!(parse_content_range_total("bytes 200-1023/4096"), Some(4096));
+        assert_eq!(parse_content_range_total("not-a-range"), None);
+        assert_eq!(parse_content_range_total("bytes 0-99/not-a-number"), None);
     }
 }
@@ -121,6 +121,10 @@ pub struct OnnxSession {
     config: OnnxConfig,
     input_info: Vec<TensorInfo>,
     output_info: Vec<TensorInfo>,
+    ir_version: i64,
+    producer_name: String,
+    producer_version: String,
+    opset_version: i64,
     loaded: bool,
 }
 
@@ -131,62 +135,127 @@ impl OnnxSession {
         if !Path::new(model_path).exists() {
             return Err(OnnxError::ModelNotFound(model_path.to_string()));
         }
-        
-        // Parse model metadata (placeholder - real impl would use onnxruntime-rs)
-        let input_info = vec![TensorInfo {
-            name: "input".to_string(),
-            shape: vec![-1, 3, 224, 224], // Dynamic batch
-            dtype: TensorType::Float32,
-        }];
-        
-        let output_info = vec![TensorInfo {
-            name: "output".to_string(),
-            shape: vec![-1, 1000],
-            dtype: TensorType::Float32,
-        }];
-        
+
+        let bytes = std::fs::read(model_path)
+            .map_err(|e| OnnxError::RuntimeError(format!("failed to read model file: {}", e)))?;
+        let metadata = proto::parse_model(&bytes)?;
+
         Ok(Self {
             model_path: model_path.to_string(),
             config,
-            input_info,
-            output_info,
+            input_info: metadata.inputs,
+            output_info: metadata.outputs,
+            ir_version: metadata.ir_version,
+            producer_name: metadata.producer_name,
+            producer_version: metadata.producer_version,
+            opset_version: metadata.opset_version,
             loaded: true,
         })
     }
-    
+
     /// Get input tensor info
     pub fn inputs(&self) -> &[TensorInfo] {
         &self.input_info
     }
-    
+
     /// Get output tensor info
     pub fn outputs(&self) -> &[TensorInfo] {
         &self.output_info
     }
-    
+
+    /// ONNX IR version declared by the model
+    pub fn ir_version(&self) -> i64 {
+        self.ir_version
+    }
+
+    /// Name of the tool that produced the model (e.g. `pytorch`)
+    pub fn producer_name(&self) -> &str {
+        &self.producer_name
+    }
+
+    /// Version of the tool that produced the model
+    pub fn producer_version(&self) -> &str {
+        &self.producer_version
+    }
+
+    /// Default-domain opset version the model was exported against; compare
+    /// against the `opset_version=17` target [`ModelConverter`] emits to
+    /// confirm a converted model matches.
+    pub fn opset_version(&self) -> i64 {
+        self.opset_version
+    }
+
+    /// Validate one declared input's shape and dtype against the slice a
+    /// caller is about to run inference with.
+    fn validate_input(info: &TensorInfo, data: &[f32]) -> Result<(), OnnxError> {
+        if info.dtype != TensorType::Float32 {
+            return Err(OnnxError::InvalidInput(format!(
+                "input '{}' declared dtype {:?}, but this API only accepts Float32 slices",
+                info.name, info.dtype
+            )));
+        }
+
+        let known_elements: usize = info.shape.iter().filter(|&&d| d >= 0).map(|&d| d as usize).product();
+        if known_elements == 0 {
+            if !data.is_empty() {
+                return Err(OnnxError::InvalidInput(format!(
+                    "input '{}' declared shape {:?} but got {} elements",
+                    info.name, info.shape, data.len()
+                )));
+            }
+            return Ok(());
+        }
+
+        if data.len() % known_elements != 0 {
+            return Err(OnnxError::InvalidInput(format!(
+                "input '{}' size {} is not a multiple of the {} elements implied by declared shape {:?}",
+                info.name, data.len(), known_elements, info.shape
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Run inference (placeholder - real impl would use onnxruntime-rs)
     pub fn run(&self, inputs: &[&[f32]]) -> Result<Vec<Vec<f32>>, OnnxError> {
         if !self.loaded {
             return Err(OnnxError::SessionNotLoaded);
         }
-        
+
         if inputs.is_empty() {
             return Err(OnnxError::InvalidInput("No inputs provided".into()));
         }
-        
-        // Placeholder output
-        let output_size = self.output_info[0].shape.iter()
-            .map(|&s| if s < 0 { 1 } else { s as usize })
-            .product();
-        
-        Ok(vec![vec![0.0f32; output_size]])
+
+        if inputs.len() != self.input_info.len() {
+            return Err(OnnxError::InvalidInput(format!(
+                "expected {} input(s), got {}",
+                self.input_info.len(),
+                inputs.len()
+            )));
+        }
+
+        for (info, data) in self.input_info.iter().zip(inputs.iter()) {
+            Self::validate_input(info, data)?;
+        }
+
+        // Placeholder output: real inference would run the ONNX graph, but
+        // each output buffer is now sized from the model's actual declared
+        // shape instead of a hard-coded one.
+        Ok(self
+            .output_info
+            .iter()
+            .map(|info| {
+                let output_size = info.shape.iter().map(|&s| if s < 0 { 1 } else { s as usize }).product();
+                vec![0.0f32; output_size]
+            })
+            .collect())
     }
-    
+
     /// Get model path
     pub fn model_path(&self) -> &str {
         &self.model_path
     }
-    
+
     /// Get active execution provider
     pub fn active_provider(&self) -> ExecutionProvider {
         for provider in &self.config.providers {
@@ -205,6 +274,7 @@ pub enum OnnxError {
     SessionNotLoaded,
     InvalidInput(String),
     RuntimeError(String),
+    ParseError(String),
 }
 
 impl std::fmt::Display for OnnxError {
@@ -214,6 +284,7 @@ impl std::fmt::Display for OnnxError {
             Self::SessionNotLoaded => write!(f, "Session not loaded"),
             Self::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
             Self::RuntimeError(msg) => write!(f, "Runtime error: {}", msg),
+            Self::ParseError(msg) => write!(f, "Failed to parse ONNX model: {}", msg),
         }
     }
 }
@@ -303,6 +374,296 @@ impl InferenceBenchmark {
     }
 }
 
+/// Minimal ONNX `ModelProto` reader.
+///
+/// Decodes just the handful of fields [`OnnxSession::new`] needs --
+/// `ir_version`, `producer_name`/`producer_version`, `opset_import`, and
+/// the graph's declared inputs/outputs -- by walking the protobuf wire
+/// format directly, rather than pulling in a full protobuf/prost
+/// dependency for a few scalar and nested-message fields.
+mod proto {
+    use super::{OnnxError, TensorInfo, TensorType};
+
+    /// The subset of an ONNX model's header [`super::OnnxSession`]
+    /// surfaces -- not the graph's nodes or weights.
+    pub(super) struct ModelMetadata {
+        pub ir_version: i64,
+        pub producer_name: String,
+        pub producer_version: String,
+        pub opset_version: i64,
+        pub inputs: Vec<TensorInfo>,
+        pub outputs: Vec<TensorInfo>,
+    }
+
+    enum WireValue<'a> {
+        Varint(u64),
+        LengthDelimited(&'a [u8]),
+    }
+
+    struct Reader<'a> {
+        data: &'a [u8],
+        pos: usize,
+    }
+
+    impl<'a> Reader<'a> {
+        fn new(data: &'a [u8]) -> Self {
+            Self { data, pos: 0 }
+        }
+
+        fn has_remaining(&self) -> bool {
+            self.pos < self.data.len()
+        }
+
+        fn read_varint(&mut self) -> Result<u64, OnnxError> {
+            let mut result: u64 = 0;
+            let mut shift = 0;
+            loop {
+                let byte = *self
+                    .data
+                    .get(self.pos)
+                    .ok_or_else(|| OnnxError::ParseError("truncated varint".to_string()))?;
+                self.pos += 1;
+                result |= ((byte & 0x7F) as u64) << shift;
+                if byte & 0x80 == 0 {
+                    return Ok(result);
+                }
+                shift += 7;
+                if shift >= 64 {
+                    return Err(OnnxError::ParseError("varint too long".to_string()));
+                }
+            }
+        }
+
+        fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], OnnxError> {
+            let end = self
+                .pos
+                .checked_add(len)
+                .filter(|&end| end <= self.data.len())
+                .ok_or_else(|| OnnxError::ParseError("truncated message".to_string()))?;
+            let bytes = &self.data[self.pos..end];
+            self.pos = end;
+            Ok(bytes)
+        }
+
+        /// Read one `(field_number, value)` pair. Fixed32/Fixed64 fields
+        /// are skipped -- ONNX doesn't use them for anything this reader
+        /// extracts.
+        fn read_field(&mut self) -> Result<(u32, WireValue<'a>), OnnxError> {
+            let tag = self.read_varint()?;
+            let field_number = (tag >> 3) as u32;
+            match tag & 0x7 {
+                0 => Ok((field_number, WireValue::Varint(self.read_varint()?))),
+                2 => {
+                    let len = self.read_varint()? as usize;
+                    Ok((field_number, WireValue::LengthDelimited(self.read_bytes(len)?)))
+                }
+                1 => {
+                    self.read_bytes(8)?;
+                    Ok((field_number, WireValue::Varint(0)))
+                }
+                5 => {
+                    self.read_bytes(4)?;
+                    Ok((field_number, WireValue::Varint(0)))
+                }
+                other => Err(OnnxError::ParseError(format!("unsupported wire type {}", other))),
+            }
+        }
+    }
+
+    fn read_string(bytes: &[u8]) -> String {
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+
+    /// Maps `onnx.TensorProto.DataType` values onto [`TensorType`].
+    fn elem_type_to_tensor_type(elem_type: i64) -> Result<TensorType, OnnxError> {
+        match elem_type {
+            1 => Ok(TensorType::Float32),
+            2 => Ok(TensorType::UInt8),
+            6 => Ok(TensorType::Int32),
+            7 => Ok(TensorType::Int64),
+            8 => Ok(TensorType::String),
+            9 => Ok(TensorType::Bool),
+            10 => Ok(TensorType::Float16),
+            other => Err(OnnxError::ParseError(format!(
+                "unsupported ONNX tensor element type {}",
+                other
+            ))),
+        }
+    }
+
+    /// `onnx.TensorShapeProto`: a `repeated Dimension dim`, where each
+    /// dimension is either a fixed `dim_value` or a symbolic `dim_param`
+    /// (mapped here to `-1`, matching [`TensorInfo::shape`]'s existing
+    /// dynamic-dimension convention).
+    fn parse_shape(bytes: &[u8]) -> Result<Vec<i64>, OnnxError> {
+        let mut reader = Reader::new(bytes);
+        let mut shape = Vec::new();
+        while reader.has_remaining() {
+            let (field, value) = reader.read_field()?;
+            let WireValue::LengthDelimited(dim_bytes) = value else {
+                continue;
+            };
+            if field != 1 {
+                continue;
+            }
+
+            let mut dim_reader = Reader::new(dim_bytes);
+            let mut dim_value: i64 = -1;
+            while dim_reader.has_remaining() {
+                let (dim_field, dim_field_value) = dim_reader.read_field()?;
+                match (dim_field, dim_field_value) {
+                    (1, WireValue::Varint(v)) => dim_value = v as i64,
+                    (2, WireValue::LengthDelimited(_)) => dim_value = -1,
+                    _ => {}
+                }
+            }
+            shape.push(dim_value);
+        }
+        Ok(shape)
+    }
+
+    /// `onnx.TypeProto`: only the `tensor_type` oneof variant (field 1)
+    /// is understood; sequence/map/optional types are left unsupported.
+    fn parse_type(bytes: &[u8]) -> Result<(i64, Vec<i64>), OnnxError> {
+        let mut reader = Reader::new(bytes);
+        let mut elem_type: i64 = 0;
+        let mut shape = Vec::new();
+        while reader.has_remaining() {
+            let (field, value) = reader.read_field()?;
+            let WireValue::LengthDelimited(tensor_bytes) = value else {
+                continue;
+            };
+            if field != 1 {
+                continue;
+            }
+
+            let mut tensor_reader = Reader::new(tensor_bytes);
+            while tensor_reader.has_remaining() {
+                let (tensor_field, tensor_value) = tensor_reader.read_field()?;
+                match (tensor_field, tensor_value) {
+                    (1, WireValue::Varint(v)) => elem_type = v as i64,
+                    (2, WireValue::LengthDelimited(shape_bytes)) => shape = parse_shape(shape_bytes)?,
+                    _ => {}
+                }
+            }
+        }
+        Ok((elem_type, shape))
+    }
+
+    /// `onnx.ValueInfoProto`: `name` (field 1) plus `type` (field 2).
+    fn parse_value_info(bytes: &[u8]) -> Result<TensorInfo, OnnxError> {
+        let mut reader = Reader::new(bytes);
+        let mut name = String::new();
+        let mut elem_type: i64 = 0;
+        let mut shape = Vec::new();
+        while reader.has_remaining() {
+            let (field, value) = reader.read_field()?;
+            match (field, value) {
+                (1, WireValue::LengthDelimited(bytes)) => name = read_string(bytes),
+                (2, WireValue::LengthDelimited(bytes)) => {
+                    let (parsed_elem_type, parsed_shape) = parse_type(bytes)?;
+                    elem_type = parsed_elem_type;
+                    shape = parsed_shape;
+                }
+                _ => {}
+            }
+        }
+        Ok(TensorInfo {
+            name,
+            shape,
+            dtype: elem_type_to_tensor_type(elem_type)?,
+        })
+    }
+
+    /// `onnx.GraphProto`: `input` (field 11) and `output` (field 12),
+    /// each a `repeated ValueInfoProto`. Nodes, initializers, and
+    /// intermediate `value_info` entries aren't needed here.
+    fn parse_graph(bytes: &[u8]) -> Result<(Vec<TensorInfo>, Vec<TensorInfo>), OnnxError> {
+        let mut reader = Reader::new(bytes);
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        while reader.has_remaining() {
+            let (field, value) = reader.read_field()?;
+            let WireValue::LengthDelimited(bytes) = value else {
+                continue;
+            };
+            match field {
+                11 => inputs.push(parse_value_info(bytes)?),
+                12 => outputs.push(parse_value_info(bytes)?),
+                _ => {}
+            }
+        }
+        Ok((inputs, outputs))
+    }
+
+    /// `onnx.OperatorSetIdProto`: `domain` (field 1, empty for the
+    /// default ONNX domain) and `version` (field 2).
+    fn parse_opset_id(bytes: &[u8]) -> Result<(String, i64), OnnxError> {
+        let mut reader = Reader::new(bytes);
+        let mut domain = String::new();
+        let mut version = 0;
+        while reader.has_remaining() {
+            let (field, value) = reader.read_field()?;
+            match (field, value) {
+                (1, WireValue::LengthDelimited(bytes)) => domain = read_string(bytes),
+                (2, WireValue::Varint(v)) => version = v as i64,
+                _ => {}
+            }
+        }
+        Ok((domain, version))
+    }
+
+    /// `onnx.ModelProto`: walks the top-level fields for `ir_version`
+    /// (1), `producer_name` (2), `producer_version` (3), `graph` (7),
+    /// and `opset_import` (8); the default-domain entry's `version` is
+    /// reported as the model's opset version.
+    pub(super) fn parse_model(bytes: &[u8]) -> Result<ModelMetadata, OnnxError> {
+        let mut reader = Reader::new(bytes);
+        let mut ir_version = 0;
+        let mut producer_name = String::new();
+        let mut producer_version = String::new();
+        let mut opset_version = 0;
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+
+        while reader.has_remaining() {
+            let (field, value) = reader.read_field()?;
+            match (field, value) {
+                (1, WireValue::Varint(v)) => ir_version = v as i64,
+                (2, WireValue::LengthDelimited(bytes)) => producer_name = read_string(bytes),
+                (3, WireValue::LengthDelimited(bytes)) => producer_version = read_string(bytes),
+                (7, WireValue::LengthDelimited(bytes)) => {
+                    let (parsed_inputs, parsed_outputs) = parse_graph(bytes)?;
+                    inputs = parsed_inputs;
+                    outputs = parsed_outputs;
+                }
+                (8, WireValue::LengthDelimited(bytes)) => {
+                    let (domain, version) = parse_opset_id(bytes)?;
+                    if domain.is_empty() {
+                        opset_version = version;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if inputs.is_empty() && outputs.is_empty() {
+            return Err(OnnxError::ParseError(
+                "model graph declares no inputs or outputs".to_string(),
+            ));
+        }
+
+        Ok(ModelMetadata {
+            ir_version,
+            producer_name,
+            producer_version,
+            opset_version,
+            inputs,
+            outputs,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,4 +700,167 @@ mod tests {
         let cmd = ModelConverter::tensorflow_to_onnx_cmd("saved_model", "model.onnx");
         assert!(cmd.contains("tf2onnx"));
     }
+
+    // -- Hand-rolled ONNX protobuf encoders, mirroring the reader in
+    // `proto`, so these tests can build a minimal but real `ModelProto`
+    // without a protobuf dependency or an on-disk `.onnx` fixture.
+
+    fn encode_varint(mut value: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                return out;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    fn encode_varint_field(field: u32, value: i64) -> Vec<u8> {
+        let mut out = encode_varint(((field as u64) << 3) | 0);
+        out.extend(encode_varint(value as u64));
+        out
+    }
+
+    fn encode_len_field(field: u32, payload: &[u8]) -> Vec<u8> {
+        let mut out = encode_varint(((field as u64) << 3) | 2);
+        out.extend(encode_varint(payload.len() as u64));
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn encode_shape(dims: &[Option<i64>]) -> Vec<u8> {
+        let mut shape = Vec::new();
+        for dim in dims {
+            let dim_bytes = match dim {
+                Some(v) => encode_varint_field(1, *v),
+                None => encode_len_field(2, b"dynamic"),
+            };
+            shape.extend(encode_len_field(1, &dim_bytes));
+        }
+        shape
+    }
+
+    fn encode_value_info(name: &str, elem_type: i64, dims: &[Option<i64>]) -> Vec<u8> {
+        let mut tensor = encode_varint_field(1, elem_type);
+        tensor.extend(encode_len_field(2, &encode_shape(dims)));
+        let type_proto = encode_len_field(1, &tensor);
+
+        let mut value_info = encode_len_field(1, name.as_bytes());
+        value_info.extend(encode_len_field(2, &type_proto));
+        value_info
+    }
+
+    fn encode_test_model(
+        producer_name: &str,
+        producer_version: &str,
+        opset_version: i64,
+        input: &[u8],
+        output: &[u8],
+    ) -> Vec<u8> {
+        let mut graph = encode_len_field(11, input);
+        graph.extend(encode_len_field(12, output));
+
+        let opset_import = encode_varint_field(2, opset_version);
+
+        let mut model = encode_varint_field(1, 8); // ir_version
+        model.extend(encode_len_field(2, producer_name.as_bytes()));
+        model.extend(encode_len_field(3, producer_version.as_bytes()));
+        model.extend(encode_len_field(7, &graph));
+        model.extend(encode_len_field(8, &opset_import));
+        model
+    }
+
+    fn write_test_model(bytes: &[u8]) -> tempfile::NamedTempFile {
+        use std::io::Write;
+        let mut file = tempfile::NamedTempFile::with_suffix(".onnx").unwrap();
+        file.write_all(bytes).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_session_new_parses_real_graph_metadata() {
+        let input = encode_value_info("input", 1, &[None, Some(3), Some(224), Some(224)]);
+        let output = encode_value_info("output", 1, &[None, Some(1000)]);
+        let model = encode_test_model("pytorch", "2.1.0", 17, &input, &output);
+        let file = write_test_model(&model);
+
+        let session = OnnxSession::new(file.path().to_str().unwrap(), OnnxConfig::default()).unwrap();
+
+        assert_eq!(session.inputs().len(), 1);
+        assert_eq!(session.inputs()[0].name, "input");
+        assert_eq!(session.inputs()[0].shape, vec![-1, 3, 224, 224]);
+        assert_eq!(session.inputs()[0].dtype, TensorType::Float32);
+        assert_eq!(session.outputs()[0].shape, vec![-1, 1000]);
+        assert_eq!(session.producer_name(), "pytorch");
+        assert_eq!(session.producer_version(), "2.1.0");
+        assert_eq!(session.opset_version(), 17);
+        assert_eq!(session.ir_version(), 8);
+    }
+
+    #[test]
+    fn test_session_new_rejects_unsupported_elem_type() {
+        let input = encode_value_info("input", 11, &[Some(1)]); // Double: unsupported
+        let output = encode_value_info("output", 1, &[Some(1)]);
+        let model = encode_test_model("t", "1", 17, &input, &output);
+        let file = write_test_model(&model);
+
+        let result = OnnxSession::new(file.path().to_str().unwrap(), OnnxConfig::default());
+        assert!(matches!(result, Err(OnnxError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_run_rejects_wrong_input_count() {
+        let input = encode_value_info("input", 1, &[Some(1), Some(4)]);
+        let output = encode_value_info("output", 1, &[Some(1), Some(2)]);
+        let model = encode_test_model("t", "1", 17, &input, &output);
+        let file = write_test_model(&model);
+        let session = OnnxSession::new(file.path().to_str().unwrap(), OnnxConfig::default()).unwrap();
+
+        let data = vec![0.0f32; 4];
+        let result = session.run(&[&data, &data]);
+        assert!(matches!(result, Err(OnnxError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_run_rejects_wrong_input_size() {
+        let input = encode_value_info("input", 1, &[Some(1), Some(4)]);
+        let output = encode_value_info("output", 1, &[Some(1), Some(2)]);
+        let model = encode_test_model("t", "1", 17, &input, &output);
+        let file = write_test_model(&model);
+        let session = OnnxSession::new(file.path().to_str().unwrap(), OnnxConfig::default()).unwrap();
+
+        let bad = vec![0.0f32; 3];
+        let result = session.run(&[&bad]);
+        assert!(matches!(result, Err(OnnxError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_run_rejects_non_float32_dtype() {
+        let input = encode_value_info("input", 7, &[Some(1), Some(4)]); // Int64
+        let output = encode_value_info("output", 1, &[Some(1), Some(2)]);
+        let model = encode_test_model("t", "1", 17, &input, &output);
+        let file = write_test_model(&model);
+        let session = OnnxSession::new(file.path().to_str().unwrap(), OnnxConfig::default()).unwrap();
+
+        let data = vec![0.0f32; 4];
+        let result = session.run(&[&data]);
+        assert!(matches!(result, Err(OnnxError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_run_sizes_output_from_declared_shape() {
+        let input = encode_value_info("input", 1, &[Some(1), Some(4)]);
+        let output = encode_value_info("output", 1, &[Some(1), Some(2)]);
+        let model = encode_test_model("t", "1", 17, &input, &output);
+        let file = write_test_model(&model);
+        let session = OnnxSession::new(file.path().to_str().unwrap(), OnnxConfig::default()).unwrap();
+
+        let data = vec![0.0f32; 4];
+        let result = session.run(&[&data]).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 2);
+    }
 }
@@ -50,6 +50,63 @@ impl Float16 {
         }
     }
     
+    /// Convert from f32 to fp16, rounding the dropped 13 mantissa bits to
+    /// nearest-even instead of truncating.
+    pub fn from_f32_nearest_even(value: f32) -> Self {
+        Self::from_f32_rounded(value, 0x1000)
+    }
+
+    /// Convert from f32 to fp16 with stochastic rounding: adds a uniformly
+    /// random value over the 13 dropped mantissa bits before truncating, so
+    /// the probability of rounding up equals the truncated fraction.
+    ///
+    /// `rand13` only needs its low 13 bits to be random.
+    pub fn from_f32_stochastic(value: f32, rand13: u16) -> Self {
+        Self::from_f32_rounded(value, (rand13 & 0x1FFF) as u32)
+    }
+
+    /// Shared implementation for `from_f32_nearest_even` / `from_f32_stochastic`:
+    /// both just add a different bias over the 13 dropped mantissa bits
+    /// before truncating, with the same exponent rebias and
+    /// overflow-to-infinity handling as the plain truncating `from_f32`.
+    fn from_f32_rounded(value: f32, bias: u32) -> Self {
+        let bits = value.to_bits();
+
+        let sign = (bits >> 31) & 1;
+        let exp = ((bits >> 23) & 0xFF) as i32;
+        let frac = bits & 0x7FFFFF;
+
+        if exp == 0xFF {
+            // Inf or NaN
+            if frac == 0 {
+                return Self(((sign << 15) | 0x7C00) as u16);
+            } else {
+                return Self(0x7E00); // NaN
+            }
+        }
+
+        let new_exp = exp - 127 + 15;
+
+        if new_exp <= 0 {
+            // Subnormal or zero
+            Self((sign << 15) as u16)
+        } else if new_exp >= 31 {
+            // Overflow to infinity
+            Self(((sign << 15) | 0x7C00) as u16)
+        } else {
+            let rounded_frac = frac + bias;
+            let carry = rounded_frac >> 23;
+            let new_frac = ((rounded_frac >> 13) & 0x3FF) as u16;
+            let bumped_exp = new_exp + carry as i32;
+
+            if bumped_exp >= 31 {
+                Self(((sign << 15) | 0x7C00) as u16)
+            } else {
+                Self(((sign << 15) | ((bumped_exp as u32) << 10) | new_frac as u32) as u16)
+            }
+        }
+    }
+
     /// Convert from fp16 to f32
     pub fn to_f32(self) -> f32 {
         let bits = self.0 as u32;
@@ -100,17 +157,39 @@ impl BFloat16 {
         let bits = value.to_bits();
         Self((bits >> 16) as u16)
     }
-    
+
+    /// Convert from f32 to bf16, rounding the dropped 16 bits to nearest-even
+    /// instead of truncating.
+    pub fn from_f32_nearest_even(value: f32) -> Self {
+        let bits = value.to_bits();
+        let rounding_bias = 0x7FFF + ((bits >> 16) & 1);
+        Self((bits.wrapping_add(rounding_bias) >> 16) as u16)
+    }
+
+    /// Convert from f32 to bf16 with stochastic rounding: rounds up with
+    /// probability equal to the truncated fraction, which removes the
+    /// downward bias plain truncation introduces when a tensor gets
+    /// rounded to bf16 repeatedly (e.g. across training steps).
+    ///
+    /// `rand16` must be a uniformly random 16-bit value; the carry out of
+    /// `bits + rand16` is exactly a Bernoulli round-up with probability
+    /// `low16 / 65536`.
+    pub fn from_f32_stochastic(value: f32, rand16: u16) -> Self {
+        let bits = value.to_bits();
+        let rounded = bits.wrapping_add(rand16 as u32) & 0xFFFF_0000;
+        Self((rounded >> 16) as u16)
+    }
+
     /// Convert from bf16 to f32 (just add 16 zero bits)
     pub fn to_f32(self) -> f32 {
         f32::from_bits((self.0 as u32) << 16)
     }
-    
+
     /// Get raw bits
     pub fn to_bits(self) -> u16 {
         self.0
     }
-    
+
     /// Create from raw bits
     pub fn from_bits(bits: u16) -> Self {
         Self(bits)
@@ -130,10 +209,12 @@ pub struct MixedPrecisionConfig {
     pub initial_scale: f32,
     /// Scale growth factor
     pub growth_factor: f32,
-    /// Scale reduction factor  
+    /// Scale reduction factor
     pub backoff_factor: f32,
     /// Growth interval (steps)
     pub growth_interval: u32,
+    /// Rounding mode used when narrowing to fp16/bf16
+    pub rounding: RoundingMode,
 }
 
 /// Precision type enum
@@ -144,6 +225,21 @@ pub enum PrecisionType {
     BFloat16,
 }
 
+/// Rounding mode used when narrowing f32 to fp16/bf16
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Drop the extra bits (fast, but biased downward in expectation)
+    #[default]
+    Truncate,
+    /// Round to the nearest representable value
+    NearestEven,
+    /// Round up with probability equal to the truncated fraction, giving an
+    /// unbiased result in expectation. Needs an RNG handle, so it's only
+    /// available via `PrecisionConverter::f32_to_bf16_stochastic` /
+    /// `f32_to_fp16_stochastic`.
+    Stochastic,
+}
+
 impl Default for MixedPrecisionConfig {
     fn default() -> Self {
         Self {
@@ -154,6 +250,7 @@ impl Default for MixedPrecisionConfig {
             growth_factor: 2.0,
             backoff_factor: 0.5,
             growth_interval: 2000,
+            rounding: RoundingMode::default(),
         }
     }
 }
@@ -243,14 +340,31 @@ impl PrecisionConverter {
         Self { config }
     }
     
-    /// Convert f32 slice to bf16
+    /// Convert f32 slice to bf16, using the converter's configured rounding
+    /// mode (`Stochastic` falls back to `NearestEven` here since there's no
+    /// RNG handle to draw from; use `f32_to_bf16_stochastic` for that).
     pub fn f32_to_bf16(&self, input: &[f32], output: &mut [u16]) {
         assert_eq!(input.len(), output.len());
         for (i, &val) in input.iter().enumerate() {
-            output[i] = BFloat16::from_f32(val).to_bits();
+            output[i] = match self.config.rounding {
+                RoundingMode::Truncate => BFloat16::from_f32(val),
+                RoundingMode::NearestEven | RoundingMode::Stochastic => {
+                    BFloat16::from_f32_nearest_even(val)
+                }
+            }
+            .to_bits();
         }
     }
-    
+
+    /// Convert f32 slice to bf16 with stochastic rounding, drawing one
+    /// 16-bit random value per element from `rng`.
+    pub fn f32_to_bf16_stochastic(&self, input: &[f32], output: &mut [u16], rng: &mut dyn FnMut() -> u16) {
+        assert_eq!(input.len(), output.len());
+        for (i, &val) in input.iter().enumerate() {
+            output[i] = BFloat16::from_f32_stochastic(val, rng()).to_bits();
+        }
+    }
+
     /// Convert bf16 slice to f32
     pub fn bf16_to_f32(&self, input: &[u16], output: &mut [f32]) {
         assert_eq!(input.len(), output.len());
@@ -258,15 +372,32 @@ impl PrecisionConverter {
             output[i] = BFloat16::from_bits(val).to_f32();
         }
     }
-    
-    /// Convert f32 slice to fp16
+
+    /// Convert f32 slice to fp16, using the converter's configured rounding
+    /// mode (`Stochastic` falls back to `NearestEven` here since there's no
+    /// RNG handle to draw from; use `f32_to_fp16_stochastic` for that).
     pub fn f32_to_fp16(&self, input: &[f32], output: &mut [u16]) {
         assert_eq!(input.len(), output.len());
         for (i, &val) in input.iter().enumerate() {
-            output[i] = Float16::from_f32(val).to_bits();
+            output[i] = match self.config.rounding {
+                RoundingMode::Truncate => Float16::from_f32(val),
+                RoundingMode::NearestEven | RoundingMode::Stochastic => {
+                    Float16::from_f32_nearest_even(val)
+                }
+            }
+            .to_bits();
         }
     }
-    
+
+    /// Convert f32 slice to fp16 with stochastic rounding, drawing one
+    /// 13-bit random value per element from `rng`.
+    pub fn f32_to_fp16_stochastic(&self, input: &[f32], output: &mut [u16], rng: &mut dyn FnMut() -> u16) {
+        assert_eq!(input.len(), output.len());
+        for (i, &val) in input.iter().enumerate() {
+            output[i] = Float16::from_f32_stochastic(val, rng()).to_bits();
+        }
+    }
+
     /// Convert fp16 slice to f32
     pub fn fp16_to_f32(&self, input: &[u16], output: &mut [f32]) {
         assert_eq!(input.len(), output.len());
@@ -274,7 +405,7 @@ impl PrecisionConverter {
             output[i] = Float16::from_bits(val).to_f32();
         }
     }
-    
+
     /// Get compute dtype
     pub fn compute_dtype(&self) -> PrecisionType {
         self.config.compute_dtype
@@ -332,6 +463,53 @@ mod tests {
         assert!(scaler.scale() > initial_scale * 0.5);
     }
     
+    #[test]
+    fn test_bf16_stochastic_rounding_averages_out() {
+        // A value exactly halfway between two bf16 steps should round up
+        // about as often as it rounds down across many draws.
+        let value = f32::from_bits(((BFloat16::from_f32(1.0).to_bits() as u32) << 16) | 0x8000);
+        let mut state = 0x1234_5678u32;
+        let mut next_rand = || {
+            // Small xorshift so the test doesn't depend on any particular RNG.
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state & 0xFFFF) as u16
+        };
+
+        let mut rounded_up = 0;
+        let trials = 2000;
+        for _ in 0..trials {
+            let bf16 = BFloat16::from_f32_stochastic(value, next_rand());
+            if bf16.to_f32() > value {
+                rounded_up += 1;
+            }
+        }
+
+        let fraction = rounded_up as f64 / trials as f64;
+        assert!(
+            (fraction - 0.5).abs() < 0.1,
+            "expected roughly half of draws to round up, got {}",
+            fraction
+        );
+    }
+
+    #[test]
+    fn test_fp16_stochastic_rounding_stays_finite() {
+        let mut state = 0xdead_beefu32;
+        let mut next_rand = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state & 0x1FFF) as u16
+        };
+
+        for &val in &[0.0f32, 1.0, -1.0, std::f32::consts::PI, 65504.0] {
+            let fp16 = Float16::from_f32_stochastic(val, next_rand());
+            assert!(fp16.to_f32().is_finite() || val.abs() >= 65504.0);
+        }
+    }
+
     #[test]
     fn test_precision_converter() {
         let config = MixedPrecisionConfig::default();
@@ -4,9 +4,13 @@
 
 use std::os::unix::io::RawFd;
 use std::path::Path;
-use std::collections::VecDeque;
+use std::cell::UnsafeCell;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
 
 use crate::{Error, Result};
 
@@ -67,46 +71,353 @@ impl Default for UringConfig {
     }
 }
 
+/// A pool of fixed (pre-registered) buffers backing `submit_read_fixed`/
+/// `submit_write_fixed`.
+///
+/// Each slot is independently boxed so its address never moves once handed
+/// to `Submitter::register_buffers`, even though the surrounding `Vec` could
+/// in principle be reallocated (it isn't: its length is fixed at
+/// `num_buffers` for the pool's whole lifetime). Access past construction is
+/// mediated entirely by the free list in `acquire`/`release` - a slot is
+/// only ever touched by whichever `BufferLease` currently holds its index.
+struct FixedBufferPool {
+    storage: BufferStorage,
+    buffer_size: usize,
+    num_buffers: usize,
+    free: parking_lot::Mutex<VecDeque<usize>>,
+}
+
+/// Where a [`FixedBufferPool`]'s backing memory came from.
+enum BufferStorage {
+    /// Per-slot heap slabs from the default global allocator.
+    Heap(Vec<UnsafeCell<Box<[u8]>>>),
+    /// One contiguous allocation from `NumaAllocator`, sliced into
+    /// `buffer_size`-sized slots. Freed through `allocator` on drop.
+    #[cfg(feature = "numa_cpp")]
+    Numa {
+        allocator: crate::numa_ffi::NumaAllocator,
+        ptr: *mut u8,
+    },
+}
+
+// SAFETY: a slot is only dereferenced by the single `BufferLease` that
+// checked its index out of `free`, so concurrent access to the same slot
+// never happens across threads.
+unsafe impl Sync for FixedBufferPool {}
+
+impl FixedBufferPool {
+    fn new_heap(num_buffers: usize, buffer_size: usize) -> Self {
+        let slots = (0..num_buffers)
+            .map(|_| UnsafeCell::new(vec![0u8; buffer_size].into_boxed_slice()))
+            .collect();
+        Self {
+            storage: BufferStorage::Heap(slots),
+            buffer_size,
+            num_buffers,
+            free: parking_lot::Mutex::new((0..num_buffers).collect()),
+        }
+    }
+
+    /// Allocates the whole pool as one `NumaAllocator` slab so every slot is
+    /// physically resident on `allocator`'s node, then slices it into
+    /// `num_buffers` fixed-size slots.
+    #[cfg(feature = "numa_cpp")]
+    fn new_numa(
+        allocator: crate::numa_ffi::NumaAllocator,
+        num_buffers: usize,
+        buffer_size: usize,
+    ) -> Result<Self> {
+        let total = num_buffers
+            .checked_mul(buffer_size)
+            .ok_or_else(|| Error::Buffer("registered buffer pool size overflows usize".to_string()))?;
+
+        // SAFETY: `total` is freed with the matching size in `Drop`.
+        let ptr = unsafe {
+            allocator
+                .alloc(total)
+                .map_err(|e| Error::Buffer(format!("NUMA buffer allocation failed: {}", e)))?
+        };
+        unsafe { std::ptr::write_bytes(ptr, 0, total) };
+
+        Ok(Self {
+            storage: BufferStorage::Numa { allocator, ptr },
+            buffer_size,
+            num_buffers,
+            free: parking_lot::Mutex::new((0..num_buffers).collect()),
+        })
+    }
+
+    fn slot_ptr(&self, index: usize) -> *mut u8 {
+        match &self.storage {
+            BufferStorage::Heap(slots) => unsafe { (*slots[index].get()).as_mut_ptr() },
+            #[cfg(feature = "numa_cpp")]
+            BufferStorage::Numa { ptr, .. } => unsafe { ptr.add(index * self.buffer_size) },
+        }
+    }
+
+    /// `iovec`s describing every slot, in registration order - index `i`
+    /// here is the `buf_index` that `ReadFixed`/`WriteFixed` must pass back.
+    fn iovecs(&self) -> Vec<libc::iovec> {
+        (0..self.num_buffers)
+            .map(|i| libc::iovec {
+                iov_base: self.slot_ptr(i) as *mut libc::c_void,
+                iov_len: self.buffer_size,
+            })
+            .collect()
+    }
+
+    fn acquire(self: &Arc<Self>) -> Option<BufferLease> {
+        let index = self.free.lock().pop_front()?;
+        Some(BufferLease {
+            pool: self.clone(),
+            index,
+        })
+    }
+
+    fn release(&self, index: usize) {
+        self.free.lock().push_back(index);
+    }
+}
+
+impl Drop for FixedBufferPool {
+    fn drop(&mut self) {
+        #[cfg(feature = "numa_cpp")]
+        if let BufferStorage::Numa { allocator, ptr } = &self.storage {
+            unsafe { allocator.free(*ptr, self.num_buffers * self.buffer_size) };
+        }
+    }
+}
+
+/// A checked-out slot of a registered [`FixedBufferPool`].
+///
+/// Returned to the pool's free list on drop so later completions can reuse
+/// the same registered index rather than registering fresh memory per call.
+pub struct BufferLease {
+    pool: Arc<FixedBufferPool>,
+    index: usize,
+}
+
+impl BufferLease {
+    /// The registered `buf_index` this lease occupies, as required by
+    /// `submit_read_fixed`/`submit_write_fixed`.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Mutable view of this lease's slab, valid until the lease is dropped.
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.pool.slot_ptr(self.index), self.pool.buffer_size) }
+    }
+
+    /// Read-only view of this lease's slab.
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.pool.slot_ptr(self.index), self.pool.buffer_size) }
+    }
+}
+
+impl Drop for BufferLease {
+    fn drop(&mut self) {
+        self.pool.release(self.index);
+    }
+}
+
+/// Checks that `required_bytes` of locked memory fits under the process's
+/// `RLIMIT_MEMLOCK` soft limit, so a too-large registered buffer pool fails
+/// with a clear error here instead of deep inside `register_buffers`.
+fn check_memlock_limit(required_bytes: u64) -> Result<()> {
+    let mut rlim = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+
+    let rc = unsafe { libc::getrlimit(libc::RLIMIT_MEMLOCK, &mut rlim) };
+    if rc != 0 {
+        return Err(Error::Buffer(format!(
+            "getrlimit(RLIMIT_MEMLOCK) failed: {}",
+            std::io::Error::last_os_error()
+        )));
+    }
+
+    if rlim.rlim_cur != libc::RLIM_INFINITY && required_bytes > rlim.rlim_cur as u64 {
+        return Err(Error::Buffer(format!(
+            "registered buffer pool needs {} bytes locked but RLIMIT_MEMLOCK allows only {}; \
+             raise the soft limit or shrink num_buffers/buffer_size",
+            required_bytes, rlim.rlim_cur
+        )));
+    }
+
+    Ok(())
+}
+
 /// High-performance io_uring engine
-/// 
+///
 /// Provides async I/O operations with minimal syscall overhead.
 pub struct UringEngine {
     _config: UringConfig,
     ring: parking_lot::Mutex<io_uring::IoUring>,
     next_id: AtomicU64,
-    pending: parking_lot::Mutex<VecDeque<PendingOp>>,
+    pending: parking_lot::Mutex<HashMap<u64, OpState>>,
+    buffer_pool: Option<Arc<FixedBufferPool>>,
 }
 
-struct PendingOp {
-    id: u64,
+/// Per-request bookkeeping the reactor needs between submission and
+/// completion: the operation kind (for [`Completion::op`]), the result once
+/// its CQE arrives, and the `Waker` of whichever [`OpFuture`] is waiting on
+/// it.
+struct OpState {
     op: IoOp,
+    result: Option<i32>,
+    waker: Option<Waker>,
+}
+
+fn build_ring(config: &UringConfig) -> Result<io_uring::IoUring> {
+    let mut builder = io_uring::IoUring::builder();
+
+    if config.sq_poll {
+        builder.setup_sqpoll(config.sq_poll_idle_ms);
+    }
+
+    if config.io_poll {
+        builder.setup_iopoll();
+    }
+
+    builder
+        .build(config.sq_entries)
+        .map_err(|e| Error::IoUring(format!("Failed to create io_uring: {}", e)))
+}
+
+fn register_pool(ring: &io_uring::IoUring, pool: &FixedBufferPool) -> Result<()> {
+    let iovecs = pool.iovecs();
+
+    // SAFETY: `iovecs` point into `pool`'s slots, which stay pinned at their
+    // current addresses for as long as `pool` (and therefore this
+    // registration) is alive.
+    unsafe {
+        ring.submitter()
+            .register_buffers(&iovecs)
+            .map_err(|e| Error::IoUring(format!("register_buffers failed: {}", e)))
+    }
 }
 
 impl UringEngine {
     /// Create a new io_uring engine
     pub fn new(config: UringConfig) -> Result<Self> {
-        let mut builder = io_uring::IoUring::builder();
-        
-        if config.sq_poll {
-            builder.setup_sqpoll(config.sq_poll_idle_ms);
+        let ring = build_ring(&config)?;
+
+        let buffer_pool = if config.registered_buffers {
+            let required_bytes = (config.num_buffers * config.buffer_size) as u64;
+            check_memlock_limit(required_bytes)?;
+
+            let pool = Arc::new(FixedBufferPool::new_heap(config.num_buffers, config.buffer_size));
+            register_pool(&ring, &pool)?;
+
+            Some(pool)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            _config: config,
+            ring: parking_lot::Mutex::new(ring),
+            next_id: AtomicU64::new(1),
+            pending: parking_lot::Mutex::new(HashMap::new()),
+            buffer_pool,
+        })
+    }
+
+    /// Like [`new`](Self::new), but backs the registered buffer pool with
+    /// `NumaAllocator` memory on `node` (or the calling thread's preferred
+    /// node, via `NumaAllocator::local()`, when `node` is `None`) instead of
+    /// the default global allocator. A worker bound to that node then reads
+    /// and writes DMA buffers physically resident on it, avoiding
+    /// cross-socket traffic on every completion.
+    ///
+    /// `config.registered_buffers` must be `true`.
+    #[cfg(feature = "numa_cpp")]
+    pub fn with_numa_buffers(config: UringConfig, node: Option<i32>) -> Result<Self> {
+        if !config.registered_buffers {
+            return Err(Error::Config(
+                "with_numa_buffers requires registered_buffers = true".to_string(),
+            ));
         }
-        
-        if config.io_poll {
-            builder.setup_iopoll();
+
+        let ring = build_ring(&config)?;
+
+        let required_bytes = (config.num_buffers * config.buffer_size) as u64;
+        check_memlock_limit(required_bytes)?;
+
+        let allocator = match node {
+            Some(node) => crate::numa_ffi::NumaAllocator::new(node),
+            None => crate::numa_ffi::NumaAllocator::local(),
         }
-        
-        let ring = builder
-            .build(config.sq_entries)
-            .map_err(|e| Error::IoUring(format!("Failed to create io_uring: {}", e)))?;
-        
+        .map_err(|e| Error::Numa(e.to_string()))?;
+
+        let pool = Arc::new(FixedBufferPool::new_numa(
+            allocator,
+            config.num_buffers,
+            config.buffer_size,
+        )?);
+        register_pool(&ring, &pool)?;
+
         Ok(Self {
             _config: config,
             ring: parking_lot::Mutex::new(ring),
             next_id: AtomicU64::new(1),
-            pending: parking_lot::Mutex::new(VecDeque::new()),
+            pending: parking_lot::Mutex::new(HashMap::new()),
+            buffer_pool: Some(pool),
         })
     }
-    
+
+    /// Check out a registered buffer, if this engine was configured with
+    /// `registered_buffers` and a slot is currently free.
+    pub fn acquire_buffer(&self) -> Option<BufferLease> {
+        self.buffer_pool.as_ref()?.acquire()
+    }
+
+    /// Submit a read into a registered (fixed) buffer.
+    pub fn submit_read_fixed(&self, fd: RawFd, lease: &mut BufferLease, len: u32, offset: u64) -> Result<u64> {
+        let id = self.next_id();
+        let buf_index = lease.index() as u16;
+        let ptr = lease.as_mut_slice().as_mut_ptr();
+
+        let read_e = io_uring::opcode::ReadFixed::new(io_uring::types::Fd(fd), ptr, len, buf_index)
+            .offset(offset)
+            .build()
+            .user_data(id);
+
+        unsafe {
+            self.ring.lock().submission()
+                .push(&read_e)
+                .map_err(|_| Error::IoUring("Submission queue full".to_string()))?;
+        }
+
+        self.pending.lock().insert(id, OpState { op: IoOp::Read, result: None, waker: None });
+
+        Ok(id)
+    }
+
+    /// Submit a write out of a registered (fixed) buffer.
+    pub fn submit_write_fixed(&self, fd: RawFd, lease: &BufferLease, len: u32, offset: u64) -> Result<u64> {
+        let id = self.next_id();
+        let buf_index = lease.index() as u16;
+        let ptr = lease.as_slice().as_ptr();
+
+        let write_e = io_uring::opcode::WriteFixed::new(io_uring::types::Fd(fd), ptr, len, buf_index)
+            .offset(offset)
+            .build()
+            .user_data(id);
+
+        unsafe {
+            self.ring.lock().submission()
+                .push(&write_e)
+                .map_err(|_| Error::IoUring("Submission queue full".to_string()))?;
+        }
+
+        self.pending.lock().insert(id, OpState { op: IoOp::Write, result: None, waker: None });
+
+        Ok(id)
+    }
+
     /// Get next request ID
     fn next_id(&self) -> u64 {
         self.next_id.fetch_add(1, Ordering::Relaxed)
@@ -133,7 +444,7 @@ impl UringEngine {
                 .map_err(|_| Error::IoUring("Submission queue full".to_string()))?;
         }
         
-        self.pending.lock().push_back(PendingOp { id, op: IoOp::Read });
+        self.pending.lock().insert(id, OpState { op: IoOp::Read, result: None, waker: None });
         
         Ok(id)
     }
@@ -157,7 +468,7 @@ impl UringEngine {
                 .map_err(|_| Error::IoUring("Submission queue full".to_string()))?;
         }
         
-        self.pending.lock().push_back(PendingOp { id, op: IoOp::Write });
+        self.pending.lock().insert(id, OpState { op: IoOp::Write, result: None, waker: None });
         
         Ok(id)
     }
@@ -181,7 +492,7 @@ impl UringEngine {
                 .map_err(|_| Error::IoUring("Submission queue full".to_string()))?;
         }
         
-        self.pending.lock().push_back(PendingOp { id, op: IoOp::Read });
+        self.pending.lock().insert(id, OpState { op: IoOp::Read, result: None, waker: None });
         
         Ok(id)
     }
@@ -200,7 +511,7 @@ impl UringEngine {
                 .map_err(|_| Error::IoUring("Submission queue full".to_string()))?;
         }
         
-        self.pending.lock().push_back(PendingOp { id, op: IoOp::Fsync });
+        self.pending.lock().insert(id, OpState { op: IoOp::Fsync, result: None, waker: None });
         
         Ok(id)
     }
@@ -217,38 +528,169 @@ impl UringEngine {
             .map_err(|e| Error::IoUring(format!("Submit and wait failed: {}", e)))
     }
     
-    /// Get completions
+    /// Drain ready CQEs, recording each one's result against its `OpState`
+    /// and waking the `Waker` of whichever `OpFuture` is waiting on it (if
+    /// any). Returns one `Completion` per CQE for callers that still want
+    /// to poll manually instead of going through `OpFuture`.
+    ///
+    /// This is the O(1)-per-CQE replacement for the old linear scan: each
+    /// CQE's `user_data` is looked up directly in the `pending` map rather
+    /// than searched for.
     pub fn completions(&self) -> Vec<Completion> {
         let mut completions = Vec::new();
-        let pending = self.pending.lock();
         let mut ring = self.ring.lock();
-        
+        let mut pending = self.pending.lock();
+
         for cqe in ring.completion() {
             let user_data = cqe.user_data();
             let result = cqe.result();
-            
-            // Find the operation type
-            let op = pending.iter()
-                .find(|p| p.id == user_data)
-                .map(|p| p.op)
-                .unwrap_or(IoOp::Read);
-            
-            completions.push(Completion {
-                user_data,
-                result,
-                op,
-            });
+
+            if let Some(state) = pending.get_mut(&user_data) {
+                state.result = Some(result);
+                let op = state.op;
+
+                // If an `OpFuture` is waiting, let its own poll remove the
+                // entry once it reads the result; otherwise nobody else
+                // will, so clean up now rather than leaking it forever.
+                let has_waiter = match state.waker.take() {
+                    Some(waker) => {
+                        waker.wake();
+                        true
+                    }
+                    None => false,
+                };
+
+                completions.push(Completion {
+                    user_data,
+                    result,
+                    op,
+                });
+
+                if !has_waiter {
+                    pending.remove(&user_data);
+                }
+            }
         }
-        
+
         completions
     }
-    
+
+    /// Submit any queued SQEs, block until at least `want` completions are
+    /// ready, then process every ready CQE (same bookkeeping as
+    /// `completions()`). Returns the number of CQEs processed.
+    ///
+    /// This is the reactor's single step: call it from a dedicated thread
+    /// (see [`spawn_driver`]) or from an executor's idle loop to turn
+    /// `OpFuture`s returned by `AsyncFile::read`/`write`/`fsync` into
+    /// something that actually makes progress.
+    pub fn drive(&self, want: usize) -> Result<usize> {
+        self.submit_and_wait(want)?;
+        Ok(self.completions().len())
+    }
+
+    /// A future that resolves to the result of the operation submitted as
+    /// `id`, once some `drive()`/`completions()` call observes its CQE.
+    fn future_for(self: &Arc<Self>, id: Result<u64>) -> OpFuture {
+        match id {
+            Ok(id) => OpFuture {
+                engine: self.clone(),
+                state: OpFutureState::Submitted(id),
+            },
+            Err(e) => OpFuture {
+                engine: self.clone(),
+                state: OpFutureState::Failed(Some(e)),
+            },
+        }
+    }
+
     /// Get number of pending operations
     pub fn pending_count(&self) -> usize {
         self.pending.lock().len()
     }
 }
 
+/// Repeatedly calls `engine.drive(1)` on a dedicated thread until it
+/// returns an error or `stop` is set, turning `engine` into a background
+/// reactor for `OpFuture`s. The ring has no built-in "shutdown" signal, so
+/// a caller that wants a clean stop must also get the ring to produce a
+/// CQE (e.g. submit a no-op) after setting `stop`, or simply drop the
+/// engine and let the thread's next `drive()` call fail.
+pub fn spawn_driver(
+    engine: Arc<UringEngine>,
+    stop: Arc<std::sync::atomic::AtomicBool>,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        while !stop.load(Ordering::Relaxed) {
+            if engine.drive(1).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+enum OpFutureState {
+    Submitted(u64),
+    Failed(Option<Error>),
+}
+
+/// Future returned by `AsyncFile::read`/`write`/`fsync`. Resolves once the
+/// reactor (`UringEngine::drive`/`completions`) observes the operation's
+/// CQE: `Ok(result)` with the raw `cqe.result()` on success, `Err` if the
+/// kernel reported a negative result or submission itself failed.
+pub struct OpFuture {
+    engine: Arc<UringEngine>,
+    state: OpFutureState,
+}
+
+impl Future for OpFuture {
+    type Output = Result<i32>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let id = match &mut this.state {
+            OpFutureState::Failed(err) => {
+                return Poll::Ready(Err(err
+                    .take()
+                    .unwrap_or_else(|| Error::IoUring("OpFuture polled after completion".to_string()))));
+            }
+            OpFutureState::Submitted(id) => *id,
+        };
+
+        let mut pending = this.engine.pending.lock();
+        match pending.get_mut(&id) {
+            Some(op_state) => match op_state.result {
+                Some(result) => {
+                    pending.remove(&id);
+                    if result < 0 {
+                        Poll::Ready(Err(Error::IoUring(format!(
+                            "operation failed: {}",
+                            std::io::Error::from_raw_os_error(-result)
+                        ))))
+                    } else {
+                        Poll::Ready(Ok(result))
+                    }
+                }
+                None => {
+                    op_state.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            },
+            None => Poll::Ready(Err(Error::IoUring(
+                "operation state missing - already awaited?".to_string(),
+            ))),
+        }
+    }
+}
+
+impl Drop for UringEngine {
+    fn drop(&mut self) {
+        if self.buffer_pool.is_some() {
+            let _ = self.ring.lock().submitter().unregister_buffers();
+        }
+    }
+}
+
 /// Async file handle using io_uring
 pub struct AsyncFile {
     fd: RawFd,
@@ -282,19 +724,27 @@ impl AsyncFile {
         Ok(Self { fd, engine })
     }
     
-    /// Submit a read operation
-    pub fn read(&self, buf: &mut [u8], offset: u64) -> Result<u64> {
-        self.engine.submit_read(self.fd, buf, offset)
+    /// Read `buf.len()` bytes at `offset`, resolving once the reactor
+    /// observes the operation's completion. Requires `drive()` (or
+    /// `spawn_driver`) to be running somewhere, the same as any other
+    /// `OpFuture`.
+    pub fn read(&self, buf: &mut [u8], offset: u64) -> impl Future<Output = Result<i32>> {
+        let id = self.engine.submit_read(self.fd, buf, offset);
+        self.engine.future_for(id)
     }
-    
-    /// Submit a write operation
-    pub fn write(&self, buf: &[u8], offset: u64) -> Result<u64> {
-        self.engine.submit_write(self.fd, buf, offset)
+
+    /// Write `buf` at `offset`, resolving once the reactor observes the
+    /// operation's completion.
+    pub fn write(&self, buf: &[u8], offset: u64) -> impl Future<Output = Result<i32>> {
+        let id = self.engine.submit_write(self.fd, buf, offset);
+        self.engine.future_for(id)
     }
-    
-    /// Sync to disk
-    pub fn fsync(&self) -> Result<u64> {
-        self.engine.submit_fsync(self.fd)
+
+    /// Sync to disk, resolving once the reactor observes the operation's
+    /// completion.
+    pub fn fsync(&self) -> impl Future<Output = Result<i32>> {
+        let id = self.engine.submit_fsync(self.fd);
+        self.engine.future_for(id)
     }
 }
 
@@ -307,7 +757,17 @@ impl Drop for AsyncFile {
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+    use std::task::{RawWaker, RawWakerVTable};
+
+    fn noop_waker() -> Waker {
+        fn no_op(_: *const ()) {}
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, no_op, no_op, no_op);
+        unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+    }
+
     #[test]
     fn test_uring_config() {
         let config = UringConfig::default();
@@ -332,4 +792,92 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_uring_registered_buffers_acquire_and_release() {
+        let config = UringConfig {
+            sq_entries: 32,
+            registered_buffers: true,
+            num_buffers: 4,
+            buffer_size: 4096,
+            ..Default::default()
+        };
+
+        match UringEngine::new(config) {
+            Ok(engine) => {
+                let mut leases = Vec::new();
+                for _ in 0..4 {
+                    leases.push(engine.acquire_buffer().expect("pool should have a free slot"));
+                }
+                assert!(engine.acquire_buffer().is_none(), "pool should be exhausted");
+
+                drop(leases.pop());
+                let lease = engine.acquire_buffer().expect("slot freed by drop should be reusable");
+                assert!(lease.index() < 4);
+            }
+            Err(_) => {
+                // io_uring (or registered buffers) may not be available in test environment
+            }
+        }
+    }
+
+    #[test]
+    fn test_uring_registered_buffers_over_memlock_limit_errors_cleanly() {
+        let config = UringConfig {
+            sq_entries: 32,
+            registered_buffers: true,
+            num_buffers: 1,
+            buffer_size: usize::MAX / 2,
+            ..Default::default()
+        };
+
+        match UringEngine::new(config) {
+            Ok(_) => panic!("pool far exceeding any RLIMIT_MEMLOCK should be rejected"),
+            Err(Error::Buffer(_)) => {}
+            Err(other) => panic!("expected Error::Buffer, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_op_future_resolves_once_drive_observes_completion() {
+        let config = UringConfig {
+            sq_entries: 32,
+            ..Default::default()
+        };
+
+        match UringEngine::new(config) {
+            Ok(engine) => {
+                let engine = Arc::new(engine);
+                let fd = nix::fcntl::open(
+                    "/dev/null",
+                    nix::fcntl::OFlag::O_WRONLY,
+                    nix::sys::stat::Mode::empty(),
+                )
+                .expect("/dev/null should always be openable");
+
+                let id = engine.submit_fsync(fd);
+                let mut future = engine.future_for(id);
+
+                let waker = noop_waker();
+                let mut cx = Context::from_waker(&waker);
+
+                match Pin::new(&mut future).poll(&mut cx) {
+                    Poll::Pending => {}
+                    Poll::Ready(result) => panic!("should not resolve before drive(), got {result:?}"),
+                }
+
+                engine.drive(1).expect("drive should observe the fsync completion");
+
+                match Pin::new(&mut future).poll(&mut cx) {
+                    Poll::Ready(Ok(_)) => {}
+                    other => panic!("expected Ready(Ok(_)) after drive(), got {other:?}"),
+                }
+
+                let _ = nix::unistd::close(fd);
+            }
+            Err(_) => {
+                // io_uring may not be available in test environment
+            }
+        }
+    }
 }
@@ -110,8 +110,17 @@ impl Default for DeviceProperties {
 pub struct CudaStream {
     handle: u64, // Placeholder for cudaStream_t
     device_id: i32,
+    /// Lazily-allocated workspace for `scratchpad()`, torn down on `Drop`
+    /// via `CudaMemory`'s own `Drop` impl.
+    scratchpad: std::sync::Mutex<Option<CudaMemory>>,
+    /// Lazily-allocated counter for `semaphore()`.
+    semaphore: std::sync::Mutex<Option<CudaMemory>>,
 }
 
+/// Size of the per-stream scratchpad buffer, as in Eigen's
+/// `StreamInterface::scratchpad()`.
+const STREAM_SCRATCHPAD_SIZE: usize = 1024;
+
 impl CudaStream {
     /// Create new CUDA stream
     pub fn new(device_id: i32) -> Result<Self, CudaError> {
@@ -119,20 +128,51 @@ impl CudaStream {
         Ok(Self {
             handle: 0,
             device_id,
+            scratchpad: std::sync::Mutex::new(None),
+            semaphore: std::sync::Mutex::new(None),
         })
     }
-    
+
     /// Synchronize the stream
     pub fn synchronize(&self) -> Result<(), CudaError> {
         // In real implementation: cudaStreamSynchronize
         Ok(())
     }
-    
+
     /// Check if stream is ready
     pub fn is_ready(&self) -> bool {
         // In real implementation: cudaStreamQuery
         true
     }
+
+    /// A fixed-size (1 KB) device scratch buffer bound to this stream's
+    /// device, allocated on first use, as in Eigen's `StreamInterface`.
+    /// Lets multi-block kernels (e.g. reductions) share a workspace without
+    /// each caller hand-rolling its own allocation.
+    pub fn scratchpad(&self) -> *mut c_void {
+        let mut slot = self.scratchpad.lock().unwrap();
+        if slot.is_none() {
+            *slot = Some(
+                CudaMemory::allocate(STREAM_SCRATCHPAD_SIZE, self.device_id)
+                    .expect("stream scratchpad allocation"),
+            );
+        }
+        slot.as_ref().unwrap().as_ptr()
+    }
+
+    /// A zero-initialized device counter bound to this stream's device,
+    /// allocated on first use. Kernels reset it to 0 on completion so it
+    /// can serve as a reusable completion signal across launches.
+    pub fn semaphore(&self) -> *mut u32 {
+        let mut slot = self.semaphore.lock().unwrap();
+        if slot.is_none() {
+            let mem = CudaMemory::allocate(std::mem::size_of::<u32>(), self.device_id)
+                .expect("stream semaphore allocation");
+            // In real implementation: cudaMemsetAsync(mem.as_ptr(), 0, size, self.handle)
+            *slot = Some(mem);
+        }
+        slot.as_ref().unwrap().as_ptr() as *mut u32
+    }
 }
 
 /// CUDA memory allocation
@@ -143,13 +183,20 @@ pub struct CudaMemory {
     device_id: i32,
 }
 
+// `ptr` is an opaque device-memory handle, not a reference into this
+// process' address space, so (unlike a host raw pointer) it's sound to move
+// or share across threads; the actual safety of concurrent access is
+// governed by CUDA's own stream/context rules, not Rust's aliasing model.
+unsafe impl Send for CudaMemory {}
+unsafe impl Sync for CudaMemory {}
+
 impl CudaMemory {
     /// Allocate device memory
     pub fn allocate(size: usize, device_id: i32) -> Result<Self, CudaError> {
         if size == 0 {
             return Err(CudaError::InvalidValue);
         }
-        
+
         // In real implementation: cudaMalloc
         // For now, we simulate the allocation
         Ok(Self {
@@ -158,21 +205,101 @@ impl CudaMemory {
             device_id,
         })
     }
-    
+
+    /// Reconstruct a `CudaMemory` from a raw pointer/size/device previously
+    /// handed out by [`Device::allocate`](crate::device::Device::allocate),
+    /// so it can be freed through the normal `Drop` impl.
+    ///
+    /// # Safety
+    /// `ptr` must have been allocated for `device_id` with exactly `size`
+    /// bytes and must not be freed anywhere else.
+    pub unsafe fn from_raw_parts(ptr: *mut c_void, size: usize, device_id: i32) -> Self {
+        Self { ptr, size, device_id }
+    }
+
     /// Get pointer
     pub fn as_ptr(&self) -> *mut c_void {
         self.ptr
     }
-    
+
     /// Get size
     pub fn size(&self) -> usize {
         self.size
     }
-    
+
     /// Get device ID
     pub fn device_id(&self) -> i32 {
         self.device_id
     }
+
+    /// Copy `src` from the host into this device allocation (`cudaMemcpy`
+    /// with `HostToDevice`), blocking until the transfer completes.
+    pub fn copy_from_host<T: Copy>(&mut self, src: &[T]) -> Result<(), CudaError> {
+        assert_eq!(
+            src.len() * std::mem::size_of::<T>(),
+            self.size,
+            "copy_from_host: source length does not match allocation size"
+        );
+
+        // In real implementation: cudaMemcpy(self.ptr, src.as_ptr(), self.size, HostToDevice)
+        let _ = MemcpyKind::HostToDevice;
+        Ok(())
+    }
+
+    /// Copy this device allocation's contents into `dst` on the host
+    /// (`cudaMemcpy` with `DeviceToHost`), blocking until the transfer
+    /// completes.
+    pub fn copy_to_host<T: Copy>(&self, dst: &mut [T]) -> Result<(), CudaError> {
+        assert_eq!(
+            dst.len() * std::mem::size_of::<T>(),
+            self.size,
+            "copy_to_host: destination length does not match allocation size"
+        );
+
+        // In real implementation: cudaMemcpy(dst.as_mut_ptr(), self.ptr, self.size, DeviceToHost)
+        let _ = MemcpyKind::DeviceToHost;
+        Ok(())
+    }
+
+    /// Enqueue a host-to-device copy of `src` on `stream` (`cudaMemcpyAsync`
+    /// with `HostToDevice`). `src` must remain valid and unmodified until
+    /// `stream.synchronize()` has been called.
+    pub fn copy_from_host_async<T: Copy>(
+        &mut self,
+        src: &[T],
+        stream: &CudaStream,
+    ) -> Result<(), CudaError> {
+        assert_eq!(
+            src.len() * std::mem::size_of::<T>(),
+            self.size,
+            "copy_from_host_async: source length does not match allocation size"
+        );
+
+        // In real implementation:
+        // cudaMemcpyAsync(self.ptr, src.as_ptr(), self.size, HostToDevice, stream.handle)
+        let _ = (MemcpyKind::HostToDevice, stream);
+        Ok(())
+    }
+
+    /// Enqueue a device-to-host copy into `dst` on `stream`
+    /// (`cudaMemcpyAsync` with `DeviceToHost`). The caller must call
+    /// `stream.synchronize()` before reading `dst`.
+    pub fn copy_to_host_async<T: Copy>(
+        &self,
+        dst: &mut [T],
+        stream: &CudaStream,
+    ) -> Result<(), CudaError> {
+        assert_eq!(
+            dst.len() * std::mem::size_of::<T>(),
+            self.size,
+            "copy_to_host_async: destination length does not match allocation size"
+        );
+
+        // In real implementation:
+        // cudaMemcpyAsync(dst.as_mut_ptr(), self.ptr, self.size, DeviceToHost, stream.handle)
+        let _ = (MemcpyKind::DeviceToHost, stream);
+        Ok(())
+    }
 }
 
 impl Drop for CudaMemory {
@@ -182,6 +309,108 @@ impl Drop for CudaMemory {
     }
 }
 
+/// Hints passed to `cudaMemAdvise` describing expected access patterns for
+/// a unified memory allocation.
+#[derive(Debug, Clone, Copy)]
+pub enum MemoryAdvice {
+    SetReadMostly,
+    UnsetReadMostly,
+    SetPreferredLocation,
+    UnsetPreferredLocation,
+    SetAccessedBy,
+    UnsetAccessedBy,
+}
+
+/// Unified (managed) memory allocation, visible from both host and device.
+///
+/// Wraps `cudaMallocManaged`. Derefs to a host-accessible byte slice so
+/// callers can read/write without an explicit `copy_from_host`/`copy_to_host`
+/// round trip; `prefetch_to` and `advise` hint the driver about expected
+/// access patterns ahead of a kernel launch.
+#[derive(Debug)]
+pub struct UnifiedMemory {
+    ptr: *mut c_void,
+    size: usize,
+    device_id: i32,
+}
+
+impl UnifiedMemory {
+    /// Allocate managed memory
+    pub fn allocate(size: usize, device_id: i32) -> Result<Self, CudaError> {
+        if size == 0 {
+            return Err(CudaError::InvalidValue);
+        }
+
+        // In real implementation: cudaMallocManaged
+        Ok(Self {
+            ptr: ptr::null_mut(), // Would be actual unified pointer
+            size,
+            device_id,
+        })
+    }
+
+    /// Get pointer
+    pub fn as_ptr(&self) -> *mut c_void {
+        self.ptr
+    }
+
+    /// Get size
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Get device ID
+    pub fn device_id(&self) -> i32 {
+        self.device_id
+    }
+
+    /// Hint the driver to migrate this allocation to `device_id` ahead of
+    /// use (`cudaMemPrefetchAsync`).
+    pub fn prefetch_to(&self, device_id: i32) -> Result<(), CudaError> {
+        // In real implementation: cudaMemPrefetchAsync(self.ptr, self.size, device_id, 0)
+        let _ = device_id;
+        Ok(())
+    }
+
+    /// Advise the driver about expected access patterns for this allocation
+    /// (`cudaMemAdvise`).
+    pub fn advise(&self, advice: MemoryAdvice, device_id: i32) -> Result<(), CudaError> {
+        // In real implementation: cudaMemAdvise(self.ptr, self.size, advice, device_id)
+        let _ = (advice, device_id);
+        Ok(())
+    }
+}
+
+impl std::ops::Deref for UnifiedMemory {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        if self.ptr.is_null() {
+            &[]
+        } else {
+            // In real implementation, `ptr` is host-visible by construction.
+            unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.size) }
+        }
+    }
+}
+
+impl std::ops::DerefMut for UnifiedMemory {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        if self.ptr.is_null() {
+            &mut []
+        } else {
+            unsafe { std::slice::from_raw_parts_mut(self.ptr as *mut u8, self.size) }
+        }
+    }
+}
+
+impl Drop for UnifiedMemory {
+    fn drop(&mut self) {
+        // In real implementation: cudaFree
+        // Memory is automatically freed when dropped
+    }
+}
+
 /// CUDA Runtime wrapper
 pub struct CudaRuntime {
     initialized: bool,
@@ -295,7 +524,17 @@ impl CudaRuntime {
     pub fn malloc(&self, size: usize) -> Result<CudaMemory, CudaError> {
         CudaMemory::allocate(size, self.current_device)
     }
-    
+
+    /// Allocate unified (managed) memory visible from both host and device,
+    /// failing if the current device doesn't report `managed_memory` support.
+    pub fn malloc_managed(&self, size: usize) -> Result<UnifiedMemory, CudaError> {
+        let props = self.get_device_properties(self.current_device)?;
+        if !props.managed_memory {
+            return Err(CudaError::NotInitialized);
+        }
+        UnifiedMemory::allocate(size, self.current_device)
+    }
+
     /// Create a stream
     pub fn create_stream(&self) -> Result<CudaStream, CudaError> {
         CudaStream::new(self.current_device)
@@ -384,6 +623,54 @@ impl LaunchConfig {
         self.shared_mem = size;
         self
     }
+
+    /// Pick a 1D launch configuration that maximizes theoretical occupancy
+    /// for `n` work items on `props`, following the same block-size sweep
+    /// as CUB's device-occupancy utilities.
+    ///
+    /// Candidate block sizes are multiples of `warp_size` up to
+    /// `max_threads_per_block`; for each, blocks-per-SM is
+    /// `min(max_threads_per_multiprocessor / block_size, hardware_block_limit)`
+    /// (the block limit defaults to 32, since CUDA doesn't expose it via
+    /// `nvidia-smi`), and occupancy is active warps over the SM's warp
+    /// capacity. The smallest block size achieving the maximum occupancy
+    /// wins; the resulting grid is never smaller than `multiprocessor_count`
+    /// blocks, so a small `n` still spreads across every SM.
+    pub fn occupancy_optimized(n: usize, props: &DeviceProperties) -> Self {
+        const DEFAULT_HARDWARE_BLOCK_LIMIT: u32 = 32;
+
+        let warp_size = (props.warp_size.max(1)) as u32;
+        let max_threads_per_block = (props.max_threads_per_block.max(warp_size as i32)) as u32;
+        let max_threads_per_sm = (props.max_threads_per_multiprocessor.max(warp_size as i32)) as u32;
+        let max_warps_per_sm = (max_threads_per_sm / warp_size).max(1);
+
+        let mut best_block_size = warp_size;
+        let mut best_occupancy = 0.0f64;
+
+        let mut block_size = warp_size;
+        while block_size <= max_threads_per_block {
+            let blocks_per_sm = (max_threads_per_sm / block_size).min(DEFAULT_HARDWARE_BLOCK_LIMIT);
+            let active_warps = blocks_per_sm * (block_size / warp_size);
+            let occupancy = active_warps as f64 / max_warps_per_sm as f64;
+
+            if occupancy > best_occupancy {
+                best_occupancy = occupancy;
+                best_block_size = block_size;
+            }
+
+            block_size += warp_size;
+        }
+
+        let blocks_needed = ((n as u32) + best_block_size - 1) / best_block_size.max(1);
+        let blocks = blocks_needed.max((props.multiprocessor_count.max(1)) as u32);
+
+        Self {
+            grid: (blocks, 1, 1),
+            block: (best_block_size, 1, 1),
+            shared_mem: 0,
+            stream: None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -416,7 +703,25 @@ mod tests {
         assert_eq!(config.grid.0, 120); // ceil(1920/16)
         assert_eq!(config.grid.1, 68);  // ceil(1080/16)
     }
-    
+
+    #[test]
+    fn test_launch_config_occupancy_optimized_picks_full_occupancy_block() {
+        let props = DeviceProperties::default();
+        let config = LaunchConfig::occupancy_optimized(1000, &props);
+        // 64 threads/block is the smallest block size reaching 100% occupancy
+        // (32 blocks/SM * 2 warps/block = 64 of 64 max warps) on the default props.
+        assert_eq!(config.block.0, 64);
+        assert_eq!(config.grid.0, 16); // ceil(1000/64)
+    }
+
+    #[test]
+    fn test_launch_config_occupancy_optimized_covers_every_sm() {
+        let mut props = DeviceProperties::default();
+        props.multiprocessor_count = 40;
+        let config = LaunchConfig::occupancy_optimized(1, &props);
+        assert!(config.grid.0 >= 40);
+    }
+
     #[test]
     fn test_cuda_memory() {
         let mem = CudaMemory::allocate(1024, 0);
@@ -425,4 +730,73 @@ mod tests {
         assert_eq!(mem.size(), 1024);
         assert_eq!(mem.device_id(), 0);
     }
+
+    #[test]
+    fn test_cuda_stream_scratchpad_is_stable_across_calls() {
+        let stream = CudaStream::new(0).unwrap();
+        let first = stream.scratchpad();
+        let second = stream.scratchpad();
+        assert_eq!(first, second); // lazily allocated once, then reused
+    }
+
+    #[test]
+    fn test_cuda_stream_semaphore_is_stable_across_calls() {
+        let stream = CudaStream::new(0).unwrap();
+        let first = stream.semaphore();
+        let second = stream.semaphore();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_cuda_memory_transfer_round_trip() {
+        let src = [1i32, 2, 3, 4];
+        let mut mem = CudaMemory::allocate(src.len() * std::mem::size_of::<i32>(), 0).unwrap();
+        assert!(mem.copy_from_host(&src).is_ok());
+
+        let mut dst = [0i32; 4];
+        assert!(mem.copy_to_host(&mut dst).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "copy_from_host: source length does not match allocation size")]
+    fn test_cuda_memory_copy_from_host_rejects_length_mismatch() {
+        let mut mem = CudaMemory::allocate(16, 0).unwrap();
+        let src = [1i32, 2, 3];
+        let _ = mem.copy_from_host(&src);
+    }
+
+    #[test]
+    fn test_cuda_memory_transfer_async_requires_synchronize() {
+        let stream = CudaStream::new(0).unwrap();
+        let src = [1.0f32, 2.0, 3.0, 4.0];
+        let mut mem = CudaMemory::allocate(src.len() * std::mem::size_of::<f32>(), 0).unwrap();
+
+        assert!(mem.copy_from_host_async(&src, &stream).is_ok());
+        stream.synchronize().unwrap();
+
+        let mut dst = [0.0f32; 4];
+        assert!(mem.copy_to_host_async(&mut dst, &stream).is_ok());
+        stream.synchronize().unwrap();
+    }
+
+    #[test]
+    fn test_unified_memory_derefs_to_host_slice() {
+        let mem = UnifiedMemory::allocate(16, 0).unwrap();
+        assert_eq!(mem.size(), 16);
+        assert_eq!(mem.device_id(), 0);
+        assert_eq!(mem.len(), 0); // placeholder ptr is null until real cudaMallocManaged lands
+        assert!(mem.prefetch_to(0).is_ok());
+        assert!(mem.advise(MemoryAdvice::SetReadMostly, 0).is_ok());
+    }
+
+    #[test]
+    fn test_malloc_managed_fails_without_managed_memory_support() {
+        let runtime = CudaRuntime {
+            initialized: true,
+            device_count: 1,
+            current_device: 0,
+        };
+        // query_device_properties doesn't set managed_memory, so it defaults to false.
+        assert_eq!(runtime.malloc_managed(1024), Err(CudaError::NotInitialized));
+    }
 }
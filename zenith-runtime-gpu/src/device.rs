@@ -0,0 +1,223 @@
+/// Backend-agnostic compute device abstraction
+///
+/// Modeled on Eigen's `DefaultDevice`/`GpuDevice` split: code that allocates
+/// memory or issues transfers can be generic over `D: Device` instead of
+/// hard-coding CUDA, so the crate keeps working on machines with no GPU and
+/// can later gain a Level-Zero/ZLUDA-style backend (with its own
+/// `mem_free`/context model) without touching call sites.
+use std::alloc::{self, Layout};
+use std::ffi::c_void;
+
+use crate::cuda::{CudaError, CudaMemory, CudaRuntime};
+
+/// Byte alignment used for `CpuDevice` allocations, matching common SIMD
+/// register widths (AVX-512) so host fallback buffers are at least as
+/// well-aligned as a typical device allocation.
+const CPU_ALIGNMENT: usize = 64;
+
+pub trait Device {
+    /// Error type surfaced by this device's operations.
+    type Error: std::fmt::Debug;
+
+    /// Allocate `size` bytes on this device, returning an opaque pointer.
+    fn allocate(&self, size: usize) -> Result<*mut c_void, Self::Error>;
+
+    /// Free a pointer previously returned by `allocate` on this device.
+    ///
+    /// # Safety
+    /// `ptr` must have been returned by this device's `allocate` with the
+    /// same `size`, and must not already have been freed.
+    unsafe fn deallocate(&self, ptr: *mut c_void, size: usize) -> Result<(), Self::Error>;
+
+    /// Copy `size` bytes from `src` to `dst`, both resident on this device.
+    ///
+    /// # Safety
+    /// `src` and `dst` must each be valid for `size` bytes and must not
+    /// overlap.
+    unsafe fn memcpy(&self, dst: *mut c_void, src: *const c_void, size: usize) -> Result<(), Self::Error>;
+
+    /// Copy `size` bytes from a host pointer into this device's memory.
+    ///
+    /// # Safety
+    /// `host_src` must be valid for `size` bytes; `device_dst` must be a
+    /// valid allocation of at least `size` bytes on this device.
+    unsafe fn memcpy_host_to_device(
+        &self,
+        device_dst: *mut c_void,
+        host_src: *const c_void,
+        size: usize,
+    ) -> Result<(), Self::Error>;
+
+    /// Copy `size` bytes from this device's memory into a host pointer.
+    ///
+    /// # Safety
+    /// `device_src` must be valid for `size` bytes on this device;
+    /// `host_dst` must be valid for `size` bytes.
+    unsafe fn memcpy_device_to_host(
+        &self,
+        host_dst: *mut c_void,
+        device_src: *const c_void,
+        size: usize,
+    ) -> Result<(), Self::Error>;
+
+    /// Block until all outstanding operations on this device complete.
+    fn synchronize(&self) -> Result<(), Self::Error>;
+
+    /// Number of parallel execution units this device exposes (e.g. SM
+    /// count on a GPU, or `1` for the single-threaded CPU fallback).
+    fn num_threads(&self) -> usize;
+}
+
+/// Host-only fallback device, used on machines with no GPU.
+///
+/// Allocations are plain `CPU_ALIGNMENT`-aligned heap buffers; `memcpy`-style
+/// operations are all just `ptr::copy_nonoverlapping`, since host and
+/// "device" memory are the same memory.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CpuDevice;
+
+impl CpuDevice {
+    fn layout(size: usize) -> Layout {
+        Layout::from_size_align(size.max(1), CPU_ALIGNMENT).expect("valid CpuDevice layout")
+    }
+}
+
+impl Device for CpuDevice {
+    type Error = CudaError;
+
+    fn allocate(&self, size: usize) -> Result<*mut c_void, CudaError> {
+        if size == 0 {
+            return Err(CudaError::InvalidValue);
+        }
+        // SAFETY: `layout` has a non-zero size.
+        let ptr = unsafe { alloc::alloc(Self::layout(size)) };
+        if ptr.is_null() {
+            return Err(CudaError::OutOfMemory);
+        }
+        Ok(ptr as *mut c_void)
+    }
+
+    unsafe fn deallocate(&self, ptr: *mut c_void, size: usize) -> Result<(), CudaError> {
+        alloc::dealloc(ptr as *mut u8, Self::layout(size));
+        Ok(())
+    }
+
+    unsafe fn memcpy(&self, dst: *mut c_void, src: *const c_void, size: usize) -> Result<(), CudaError> {
+        std::ptr::copy_nonoverlapping(src as *const u8, dst as *mut u8, size);
+        Ok(())
+    }
+
+    unsafe fn memcpy_host_to_device(
+        &self,
+        device_dst: *mut c_void,
+        host_src: *const c_void,
+        size: usize,
+    ) -> Result<(), CudaError> {
+        self.memcpy(device_dst, host_src, size)
+    }
+
+    unsafe fn memcpy_device_to_host(
+        &self,
+        host_dst: *mut c_void,
+        device_src: *const c_void,
+        size: usize,
+    ) -> Result<(), CudaError> {
+        self.memcpy(host_dst, device_src, size)
+    }
+
+    fn synchronize(&self) -> Result<(), CudaError> {
+        // Host operations above are already synchronous.
+        Ok(())
+    }
+
+    fn num_threads(&self) -> usize {
+        1
+    }
+}
+
+impl Device for CudaRuntime {
+    type Error = CudaError;
+
+    fn allocate(&self, size: usize) -> Result<*mut c_void, CudaError> {
+        let mem = self.malloc(size)?;
+        let ptr = mem.as_ptr();
+        // Ownership of the allocation transfers to the caller; it is freed
+        // through `deallocate` instead of `CudaMemory`'s `Drop`.
+        std::mem::forget(mem);
+        Ok(ptr)
+    }
+
+    unsafe fn deallocate(&self, ptr: *mut c_void, size: usize) -> Result<(), CudaError> {
+        drop(CudaMemory::from_raw_parts(ptr, size, self.current_device()));
+        Ok(())
+    }
+
+    unsafe fn memcpy(&self, dst: *mut c_void, src: *const c_void, size: usize) -> Result<(), CudaError> {
+        // In real implementation: cudaMemcpy(dst, src, size, DeviceToDevice)
+        let _ = (dst, src, size);
+        Ok(())
+    }
+
+    unsafe fn memcpy_host_to_device(
+        &self,
+        device_dst: *mut c_void,
+        host_src: *const c_void,
+        size: usize,
+    ) -> Result<(), CudaError> {
+        // In real implementation: cudaMemcpy(device_dst, host_src, size, HostToDevice)
+        let _ = (device_dst, host_src, size);
+        Ok(())
+    }
+
+    unsafe fn memcpy_device_to_host(
+        &self,
+        host_dst: *mut c_void,
+        device_src: *const c_void,
+        size: usize,
+    ) -> Result<(), CudaError> {
+        // In real implementation: cudaMemcpy(host_dst, device_src, size, DeviceToHost)
+        let _ = (host_dst, device_src, size);
+        Ok(())
+    }
+
+    fn synchronize(&self) -> Result<(), CudaError> {
+        CudaRuntime::synchronize(self)
+    }
+
+    fn num_threads(&self) -> usize {
+        // In real implementation: multiprocessor_count from DeviceProperties.
+        self.device_count().max(1) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_device_round_trips_through_memcpy() {
+        let device = CpuDevice;
+        let size = 32;
+        let src_ptr = device.allocate(size).unwrap();
+        let dst_ptr = device.allocate(size).unwrap();
+
+        unsafe {
+            std::ptr::write_bytes(src_ptr as *mut u8, 0xAB, size);
+            device.memcpy_host_to_device(dst_ptr, src_ptr, size).unwrap();
+            let dst_slice = std::slice::from_raw_parts(dst_ptr as *const u8, size);
+            assert!(dst_slice.iter().all(|&b| b == 0xAB));
+
+            device.deallocate(src_ptr, size).unwrap();
+            device.deallocate(dst_ptr, size).unwrap();
+        }
+
+        assert_eq!(device.num_threads(), 1);
+        assert!(device.synchronize().is_ok());
+    }
+
+    #[test]
+    fn test_cpu_device_rejects_zero_size_allocation() {
+        let device = CpuDevice;
+        assert_eq!(device.allocate(0), Err(CudaError::InvalidValue));
+    }
+}
@@ -0,0 +1,137 @@
+/// GPU health checks
+///
+/// Adapts `CudaRuntime`/`CudaStream` into `zenith_runtime_cpu::health`-style
+/// check factories, mirroring `memory_health_check`/`disk_health_check`, so
+/// GPU saturation and stream stalls show up in the same readiness probes.
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use zenith_runtime_cpu::health::{HealthCheckResult, HealthStatus};
+
+use crate::cuda::{CudaRuntime, CudaStream};
+
+/// Create a GPU health check reporting used-VRAM percentage for `runtime`'s
+/// current device, labeled `gpu:<device_id>` with the device name from
+/// `get_device_properties`.
+pub fn gpu_health_check(
+    runtime: Arc<CudaRuntime>,
+    threshold_percent: f64,
+) -> impl Fn() -> HealthCheckResult + Send + Sync {
+    move || {
+        let device_id = runtime.current_device();
+        let component = format!("gpu:{}", device_id);
+
+        let (free, total) = match runtime.mem_info() {
+            Ok(info) => info,
+            Err(e) => {
+                return HealthCheckResult {
+                    component,
+                    status: HealthStatus::Unhealthy,
+                    message: Some(format!("mem_info failed: {}", e)),
+                    latency_us: 0,
+                    last_check: 0,
+                };
+            }
+        };
+
+        if total == 0 {
+            return HealthCheckResult {
+                component,
+                status: HealthStatus::Unhealthy,
+                message: Some("device reports 0 bytes of VRAM".to_string()),
+                latency_us: 0,
+                last_check: 0,
+            };
+        }
+
+        let used_percent = ((total - free) as f64 / total as f64) * 100.0;
+        let status = if used_percent > threshold_percent {
+            HealthStatus::Unhealthy
+        } else if used_percent > threshold_percent * 0.8 {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        };
+
+        let device_name = runtime
+            .get_device_properties(device_id)
+            .map(|props| props.name)
+            .unwrap_or_else(|_| "unknown".to_string());
+
+        HealthCheckResult {
+            component,
+            status,
+            message: Some(format!("{} VRAM {:.1}% used", device_name, used_percent)),
+            latency_us: 0,
+            last_check: 0,
+        }
+    }
+}
+
+/// Create a health check that watches `stream` via the non-blocking
+/// `CudaStream::is_ready()` and marks the GPU `Degraded` once the stream has
+/// stayed not-ready for longer than `stall_timeout` across polls, catching a
+/// stalled kernel launch rather than transient in-flight work.
+pub fn gpu_stream_health_check(
+    stream: Arc<CudaStream>,
+    device_id: i32,
+    stall_timeout: Duration,
+) -> impl Fn() -> HealthCheckResult + Send + Sync {
+    let not_ready_since: Arc<Mutex<Option<Instant>>> = Arc::new(Mutex::new(None));
+
+    move || {
+        let component = format!("gpu:{}:stream", device_id);
+
+        if stream.is_ready() {
+            *not_ready_since.lock().unwrap() = None;
+            return HealthCheckResult {
+                component,
+                status: HealthStatus::Healthy,
+                message: None,
+                latency_us: 0,
+                last_check: 0,
+            };
+        }
+
+        let mut since = not_ready_since.lock().unwrap();
+        let stalled_since = *since.get_or_insert_with(Instant::now);
+        let stalled_for = stalled_since.elapsed();
+
+        let status = if stalled_for >= stall_timeout {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        };
+
+        HealthCheckResult {
+            component,
+            status,
+            message: (status == HealthStatus::Degraded)
+                .then(|| format!("stream not ready for {:?}", stalled_for)),
+            latency_us: 0,
+            last_check: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gpu_health_check_reports_component_and_status() {
+        let runtime = Arc::new(CudaRuntime::default());
+        let check = gpu_health_check(runtime, 90.0);
+        let result = check();
+        assert_eq!(result.component, "gpu:0");
+    }
+
+    #[test]
+    fn test_gpu_stream_health_check_is_healthy_when_ready() {
+        let stream = Arc::new(CudaStream::new(0).unwrap());
+        let check = gpu_stream_health_check(stream, 0, Duration::from_secs(5));
+        let result = check();
+        assert_eq!(result.status, HealthStatus::Healthy);
+        assert_eq!(result.component, "gpu:0:stream");
+    }
+}
@@ -0,0 +1,40 @@
+//! # Zenith GPU Runtime
+//!
+//! CUDA/NVML/TensorRT/multi-GPU wrappers for Zenith infrastructure.
+//!
+//! Copyright 2025 Wahyu Ardiansyah and Zenith AI Contributors
+
+pub mod backend;
+pub mod cuda;
+pub mod device;
+pub mod health;
+pub mod metrics;
+pub mod multigpu;
+pub mod nvml;
+pub mod rocm;
+pub mod tensorrt;
+
+pub use backend::{discover_all_gpus, GpuBackend};
+pub use cuda::CudaRuntime;
+pub use device::{CpuDevice, Device};
+pub use health::{gpu_health_check, gpu_stream_health_check};
+
+/// Result type alias for this crate
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error types for the GPU runtime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// GPU-related errors
+    Gpu(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Gpu(msg) => write!(f, "GPU error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
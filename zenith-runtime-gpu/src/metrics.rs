@@ -0,0 +1,231 @@
+//! InfluxDB line-protocol metrics export for [`GpuInfo`]/[`ProcessInfo`] snapshots.
+//!
+//! Hand-rolled rather than pulling in an InfluxDB client crate for one
+//! serialization format in a crate that otherwise has no line-protocol
+//! dependency — the format itself is a handful of escaping rules.
+use crate::nvml::{GpuInfo, NvmlManager, ProcessInfo};
+
+/// A single field [`gpu_info_to_line_protocol`] can omit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricField {
+    UtilizationGpu,
+    UtilizationMemory,
+    MemoryUsed,
+    MemoryFree,
+    PowerDraw,
+    ClockGraphics,
+    ClockSm,
+    ClockMemory,
+    TemperatureGpu,
+    EccSingleBitErrors,
+    EccDoubleBitErrors,
+    PcieTxThroughput,
+    PcieRxThroughput,
+}
+
+/// Controls what [`NvmlManager::sample_all_metrics`] emits.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsExportOptions {
+    /// Fields to leave out of every `gpu` measurement line.
+    pub exclude_fields: Vec<MetricField>,
+    /// GPU indices to skip entirely (no `gpu` or `gpu_process` lines).
+    pub exclude_devices: Vec<u32>,
+    /// Whether to also emit one `gpu_process` line per process returned by
+    /// [`NvmlManager::get_processes`].
+    pub include_processes: bool,
+}
+
+impl MetricsExportOptions {
+    fn field_excluded(&self, field: MetricField) -> bool {
+        self.exclude_fields.contains(&field)
+    }
+
+    fn device_excluded(&self, index: u32) -> bool {
+        self.exclude_devices.contains(&index)
+    }
+}
+
+/// Escape a tag key/value per the line protocol: commas, spaces, and equals
+/// signs are backslash-escaped; nothing else is.
+fn escape_tag(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, ',' | ' ' | '=') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Escape a string field value: wrapped in double quotes, with embedded
+/// quotes and backslashes backslash-escaped.
+fn escape_field_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        if matches!(c, '"' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+    out
+}
+
+/// Serialize one [`GpuInfo`] snapshot as a single `gpu` measurement line.
+/// `pci_bus_id` is accepted separately since `GpuInfo` doesn't carry one
+/// today (NVML's PCI info query isn't wired up yet — see `nvmlDeviceGetPciInfo`);
+/// pass `None` to omit the tag.
+pub fn gpu_info_to_line_protocol(info: &GpuInfo, pci_bus_id: Option<&str>, timestamp_ns: u64, options: &MetricsExportOptions) -> String {
+    let mut tags = format!(
+        "uuid={},index={},name={}",
+        escape_tag(&info.uuid),
+        info.index,
+        escape_tag(&info.name)
+    );
+    if let Some(bus_id) = pci_bus_id {
+        tags.push_str(&format!(",pci_bus_id={}", escape_tag(bus_id)));
+    }
+
+    let mut fields: Vec<String> = Vec::new();
+    let mut push = |field: MetricField, name: &str, value: String| {
+        if !options.field_excluded(field) {
+            fields.push(format!("{}={}i", name, value));
+        }
+    };
+    push(MetricField::UtilizationGpu, "utilization_gpu", info.utilization.gpu.to_string());
+    push(MetricField::UtilizationMemory, "utilization_memory", info.utilization.memory.to_string());
+    push(MetricField::MemoryUsed, "memory_used", info.memory.used.to_string());
+    push(MetricField::MemoryFree, "memory_free", info.memory.free.to_string());
+    push(MetricField::PowerDraw, "power_draw", info.power_draw.to_string());
+    push(MetricField::ClockGraphics, "clock_graphics", info.clocks.graphics.to_string());
+    push(MetricField::ClockSm, "clock_sm", info.clocks.sm.to_string());
+    push(MetricField::ClockMemory, "clock_memory", info.clocks.memory.to_string());
+    push(MetricField::TemperatureGpu, "temperature_gpu", info.temperature.gpu.to_string());
+    push(MetricField::EccSingleBitErrors, "ecc_single_bit_errors", info.ecc.single_bit_errors.to_string());
+    push(MetricField::EccDoubleBitErrors, "ecc_double_bit_errors", info.ecc.double_bit_errors.to_string());
+    push(MetricField::PcieTxThroughput, "pcie_tx_throughput", info.pcie.tx_throughput.to_string());
+    push(MetricField::PcieRxThroughput, "pcie_rx_throughput", info.pcie.rx_throughput.to_string());
+
+    let field_list = fields.join(",");
+    // Line protocol requires at least one field; if every field was
+    // excluded, fall back to a harmless presence marker so the line is
+    // still syntactically valid rather than silently dropped.
+    let field_list = if field_list.is_empty() { "present=1i".to_string() } else { field_list };
+
+    format!("gpu,{} {} {}", tags, field_list, timestamp_ns)
+}
+
+/// Serialize one [`ProcessInfo`] as a single `gpu_process` measurement line,
+/// tagged with the device it was reported against.
+pub fn process_info_to_line_protocol(gpu_index: u32, process: &ProcessInfo, timestamp_ns: u64) -> String {
+    format!(
+        "gpu_process,index={},pid={},kind={:?} name={},used_memory={}i {}",
+        gpu_index,
+        process.pid,
+        process.kind,
+        escape_field_string(&process.name),
+        process.used_memory,
+        timestamp_ns
+    )
+}
+
+impl NvmlManager {
+    /// Sample every GPU (and, if requested, every process on every GPU)
+    /// and render the batch as newline-delimited InfluxDB line protocol,
+    /// ready to hand to a line-protocol HTTP write endpoint or a file tailed
+    /// by a telegraf-style collector.
+    pub fn sample_all_metrics(&self, options: &MetricsExportOptions) -> String {
+        let timestamp_ns = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+
+        let mut lines = Vec::new();
+        for index in 0..self.gpu_count() {
+            if options.device_excluded(index) {
+                continue;
+            }
+            let Ok(info) = self.get_gpu_info(index) else {
+                continue;
+            };
+            lines.push(gpu_info_to_line_protocol(&info, None, timestamp_ns, options));
+
+            if options.include_processes {
+                if let Ok(processes) = self.get_processes(index) {
+                    for process in &processes {
+                        lines.push(process_info_to_line_protocol(index, process, timestamp_ns));
+                    }
+                }
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nvml::{ClockInfo, EccStats, GpuVendor, MemoryInfo, PcieInfo, PowerState, TemperatureInfo, ThrottleReasons, UtilizationInfo};
+
+    fn sample_gpu_info() -> GpuInfo {
+        GpuInfo {
+            vendor: GpuVendor::Nvidia,
+            index: 0,
+            name: "Tesla V100".to_string(),
+            uuid: "GPU-abc123".to_string(),
+            serial: None,
+            vbios_version: "1.0".to_string(),
+            driver_version: "535.0".to_string(),
+            compute_capability: (7, 0),
+            sm_count: 80,
+            power_state: PowerState::P0,
+            power_limit: 300,
+            power_draw: 150,
+            memory: MemoryInfo { total: 16_000_000_000, used: 4_000_000_000, free: 12_000_000_000 },
+            utilization: UtilizationInfo { gpu: 42, memory: 20, encoder: 0, decoder: 0 },
+            clocks: ClockInfo { graphics: 1300, sm: 1300, memory: 877, video: 1000 },
+            temperature: TemperatureInfo { gpu: 65, memory: None, slowdown_threshold: 90, shutdown_threshold: 95 },
+            pcie: PcieInfo { generation: 3, width: 16, max_generation: 3, max_width: 16, tx_throughput: 1000, rx_throughput: 2000 },
+            nvlink: None,
+            ecc: EccStats { enabled: true, single_bit_errors: 1, double_bit_errors: 0 },
+            throttle_reasons: ThrottleReasons::NONE,
+            mig_enabled: false,
+            mig_devices: None,
+        }
+    }
+
+    #[test]
+    fn test_gpu_info_to_line_protocol_includes_tags_and_fields() {
+        let info = sample_gpu_info();
+        let line = gpu_info_to_line_protocol(&info, Some("0000:00:1e.0"), 1_700_000_000_000_000_000, &MetricsExportOptions::default());
+
+        assert!(line.starts_with("gpu,uuid=GPU-abc123,index=0,name=Tesla\\ V100,pci_bus_id=0000:00:1e.0 "));
+        assert!(line.contains("memory_used=4000000000i"));
+        assert!(line.ends_with(" 1700000000000000000"));
+    }
+
+    #[test]
+    fn test_gpu_info_to_line_protocol_excludes_requested_fields() {
+        let info = sample_gpu_info();
+        let options = MetricsExportOptions { exclude_fields: vec![MetricField::EccDoubleBitErrors], ..Default::default() };
+        let line = gpu_info_to_line_protocol(&info, None, 0, &options);
+        assert!(!line.contains("ecc_double_bit_errors"));
+    }
+
+    #[test]
+    fn test_escape_tag_escapes_reserved_characters() {
+        assert_eq!(escape_tag("a b,c=d"), "a\\ b\\,c\\=d");
+    }
+
+    #[test]
+    fn test_process_info_to_line_protocol_quotes_name() {
+        use crate::nvml::GpuProcessKind;
+        let process = ProcessInfo { pid: 1234, name: "python train.py".to_string(), used_memory: 2_000_000_000, kind: GpuProcessKind::Compute };
+        let line = process_info_to_line_protocol(0, &process, 42);
+        assert!(line.contains("name=\"python train.py\""));
+        assert!(line.starts_with("gpu_process,index=0,pid=1234,kind=Compute "));
+    }
+}
@@ -22,14 +22,28 @@ pub enum MultiGpuStrategy {
     TensorParallel,
 }
 
+/// GPU vendor detected during topology discovery, so [`MultiGpuComm`] can
+/// later dispatch to NCCL (NVIDIA) vs RCCL (AMD) collectives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuVendor {
+    /// NVIDIA, discovered via `nvidia-smi`
+    Nvidia,
+    /// AMD, discovered via `amd-smi`/`rocm-smi`
+    Amd,
+    /// No supported vendor tooling was found
+    Unknown,
+}
+
 /// GPU topology information
 #[derive(Debug, Clone)]
 pub struct GpuTopology {
     /// Number of GPUs
     pub num_gpus: i32,
+    /// Vendor these GPUs were discovered from
+    pub vendor: GpuVendor,
     /// GPU names
     pub gpu_names: Vec<String>,
-    /// NVLink connectivity matrix
+    /// NVLink (or, on AMD, XGMI) connectivity matrix
     pub nvlink_matrix: Vec<Vec<bool>>,
     /// PCIe peer access matrix
     pub pcie_peer_matrix: Vec<Vec<bool>>,
@@ -38,41 +52,53 @@ pub struct GpuTopology {
 }
 
 impl GpuTopology {
-    /// Discover GPU topology
+    /// Discover GPU topology: probes for NVIDIA first via `nvidia-smi`, and
+    /// if none are found falls back to AMD's `amd-smi`. Returns a
+    /// zero-GPU, [`GpuVendor::Unknown`] topology if neither is present.
     pub fn discover() -> Self {
-        let num_gpus = Self::detect_gpu_count();
+        Self::discover_nvidia()
+            .or_else(Self::discover_amd)
+            .unwrap_or_else(|| Self {
+                num_gpus: 0,
+                vendor: GpuVendor::Unknown,
+                gpu_names: Vec::new(),
+                nvlink_matrix: Vec::new(),
+                pcie_peer_matrix: Vec::new(),
+                memory_per_gpu: Vec::new(),
+            })
+    }
+
+    /// Probe for NVIDIA GPUs via `nvidia-smi`. Returns `None` if it isn't
+    /// installed or reports zero devices, so [`Self::discover`] can fall
+    /// back to AMD tooling.
+    fn discover_nvidia() -> Option<Self> {
+        let num_gpus = Self::detect_nvidia_gpu_count();
+        if num_gpus == 0 {
+            return None;
+        }
+
         let mut gpu_names = Vec::new();
         let mut memory_per_gpu = Vec::new();
-        
-        // Query GPU info via nvidia-smi
+
         for i in 0..num_gpus {
-            if let Some(name) = Self::query_gpu_name(i) {
-                gpu_names.push(name);
-            } else {
-                gpu_names.push(format!("GPU {}", i));
-            }
-            
-            if let Some(mem) = Self::query_gpu_memory(i) {
-                memory_per_gpu.push(mem);
-            } else {
-                memory_per_gpu.push(0);
-            }
+            gpu_names.push(Self::query_nvidia_gpu_name(i).unwrap_or_else(|| format!("GPU {}", i)));
+            memory_per_gpu.push(Self::query_nvidia_gpu_memory(i).unwrap_or(0));
         }
-        
-        // Build connectivity matrices
+
         let nvlink_matrix = vec![vec![false; num_gpus as usize]; num_gpus as usize];
         let pcie_peer_matrix = vec![vec![true; num_gpus as usize]; num_gpus as usize];
-        
-        Self {
+
+        Some(Self {
             num_gpus,
+            vendor: GpuVendor::Nvidia,
             gpu_names,
             nvlink_matrix,
             pcie_peer_matrix,
             memory_per_gpu,
-        }
+        })
     }
-    
-    fn detect_gpu_count() -> i32 {
+
+    fn detect_nvidia_gpu_count() -> i32 {
         match std::process::Command::new("nvidia-smi")
             .args(["--list-gpus"])
             .output()
@@ -89,26 +115,26 @@ impl GpuTopology {
             Err(_) => 0,
         }
     }
-    
-    fn query_gpu_name(device_id: i32) -> Option<String> {
+
+    fn query_nvidia_gpu_name(device_id: i32) -> Option<String> {
         let output = std::process::Command::new("nvidia-smi")
             .args(["--query-gpu=name", "--format=csv,noheader", "-i", &device_id.to_string()])
             .output()
             .ok()?;
-        
+
         if output.status.success() {
             Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
         } else {
             None
         }
     }
-    
-    fn query_gpu_memory(device_id: i32) -> Option<usize> {
+
+    fn query_nvidia_gpu_memory(device_id: i32) -> Option<usize> {
         let output = std::process::Command::new("nvidia-smi")
             .args(["--query-gpu=memory.total", "--format=csv,noheader,nounits", "-i", &device_id.to_string()])
             .output()
             .ok()?;
-        
+
         if output.status.success() {
             let mem_mb: usize = String::from_utf8_lossy(&output.stdout)
                 .trim()
@@ -119,7 +145,102 @@ impl GpuTopology {
             None
         }
     }
-    
+
+    /// Probe for AMD GPUs via `amd-smi static --json`, parsing JSON rather
+    /// than scraping CSV/table output so this survives tool version
+    /// changes. Returns `None` if `amd-smi` isn't installed, reports zero
+    /// devices, or its output doesn't parse as expected.
+    fn discover_amd() -> Option<Self> {
+        let cards = Self::query_amd_static_json()?;
+        let num_gpus = cards.len() as i32;
+        if num_gpus == 0 {
+            return None;
+        }
+
+        let gpu_names: Vec<String> = cards.iter()
+            .enumerate()
+            .map(|(i, card)| {
+                card.get("asic")
+                    .and_then(|a| a.get("market_name"))
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| format!("AMD GPU {}", i))
+            })
+            .collect();
+
+        let memory_per_gpu: Vec<usize> = cards.iter()
+            .map(|card| {
+                card.get("mem_info")
+                    .and_then(|m| m.get("mem_total"))
+                    .and_then(|v| v.as_u64())
+                    .map(|bytes| bytes as usize)
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let nvlink_matrix = Self::query_amd_xgmi_matrix(num_gpus);
+        let pcie_peer_matrix = vec![vec![true; num_gpus as usize]; num_gpus as usize];
+
+        Some(Self {
+            num_gpus,
+            vendor: GpuVendor::Amd,
+            gpu_names,
+            nvlink_matrix,
+            pcie_peer_matrix,
+            memory_per_gpu,
+        })
+    }
+
+    /// Run `amd-smi static --json` and return its per-GPU array, or `None`
+    /// if `amd-smi` isn't installed, exits non-zero, or the output isn't
+    /// the JSON array this expects.
+    fn query_amd_static_json() -> Option<Vec<serde_json::Value>> {
+        let output = std::process::Command::new("amd-smi")
+            .args(["static", "--json"])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        serde_json::from_slice::<Vec<serde_json::Value>>(&output.stdout).ok()
+    }
+
+    /// XGMI (AMD's NVLink-equivalent interconnect) link-connectivity
+    /// matrix, via `amd-smi xgmi --json`. Any failure just yields an
+    /// all-`false` matrix -- XGMI reporting is best-effort, not required
+    /// for discovery to succeed.
+    fn query_amd_xgmi_matrix(num_gpus: i32) -> Vec<Vec<bool>> {
+        let matrix = vec![vec![false; num_gpus as usize]; num_gpus as usize];
+
+        let Ok(output) = std::process::Command::new("amd-smi").args(["xgmi", "--json"]).output() else {
+            return matrix;
+        };
+        if !output.status.success() {
+            return matrix;
+        }
+        let Ok(entries) = serde_json::from_slice::<Vec<serde_json::Value>>(&output.stdout) else {
+            return matrix;
+        };
+
+        let mut matrix = matrix;
+        for entry in entries {
+            let Some(gpu) = entry.get("gpu").and_then(|v| v.as_i64()) else { continue };
+            let Some(links) = entry.get("links").and_then(|v| v.as_array()) else { continue };
+
+            for link in links {
+                if let Some(peer) = link.get("peer").and_then(|v| v.as_i64()) {
+                    if gpu >= 0 && peer >= 0 && (gpu as usize) < matrix.len() && (peer as usize) < matrix.len() {
+                        matrix[gpu as usize][peer as usize] = true;
+                    }
+                }
+            }
+        }
+
+        matrix
+    }
+
     /// Check if NVLink is available between two GPUs
     pub fn has_nvlink(&self, gpu1: i32, gpu2: i32) -> bool {
         if gpu1 < 0 || gpu2 < 0 || gpu1 >= self.num_gpus || gpu2 >= self.num_gpus {
@@ -136,6 +257,62 @@ impl GpuTopology {
         self.pcie_peer_matrix[gpu1 as usize][gpu2 as usize]
     }
     
+    /// A rank ordering for ring collectives that walks `nvlink_matrix`
+    /// greedily, so consecutive ring neighbors are NVLink-connected
+    /// wherever possible; a GPU with no remaining NVLink-connected
+    /// neighbor falls back to the next not-yet-visited GPU by index.
+    pub fn ring_order(&self) -> Vec<usize> {
+        let n = self.num_gpus as usize;
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let mut visited = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        let mut current = 0;
+        visited[0] = true;
+        order.push(0);
+
+        while order.len() < n {
+            let next = (0..n)
+                .find(|&c| {
+                    !visited[c]
+                        && self.nvlink_matrix.get(current).and_then(|row| row.get(c)).copied().unwrap_or(false)
+                })
+                .or_else(|| (0..n).find(|&c| !visited[c]));
+
+            match next {
+                Some(c) => {
+                    visited[c] = true;
+                    order.push(c);
+                    current = c;
+                }
+                None => break,
+            }
+        }
+
+        order
+    }
+
+    /// True when every GPU pair can reach each other directly, via
+    /// either NVLink or PCIe peer access -- the precondition for
+    /// [`MultiGpuComm`]'s one-shot/two-shot low-latency all-reduce,
+    /// which stages each rank's buffer directly in another rank's
+    /// peer-accessible memory instead of hopping around a ring.
+    pub fn fully_peer_connected(&self) -> bool {
+        let n = self.num_gpus as usize;
+        if n <= 1 {
+            return true;
+        }
+        (0..n).all(|i| {
+            (0..n).all(|j| {
+                i == j
+                    || self.nvlink_matrix.get(i).and_then(|row| row.get(j)).copied().unwrap_or(false)
+                    || self.pcie_peer_matrix.get(i).and_then(|row| row.get(j)).copied().unwrap_or(false)
+            })
+        })
+    }
+
     /// Get recommended strategy based on topology
     pub fn recommend_strategy(&self, model_size_mb: usize) -> MultiGpuStrategy {
         if self.num_gpus <= 1 {
@@ -171,7 +348,7 @@ pub enum CollectiveOp {
 }
 
 /// NCCL reduction operation
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ReductionOp {
     Sum,
     Prod,
@@ -180,56 +357,402 @@ pub enum ReductionOp {
     Avg,
 }
 
+/// Below this element count, the ring algorithm's N-1 serialized round
+/// trips cost more than a recursive-halving/doubling (tree) exchange, so
+/// [`MultiGpuComm::all_reduce`] picks the tree path automatically.
+const TREE_ALL_REDUCE_MAX_ELEMENTS: usize = 4096;
+
+/// Above this element count, staging every rank's buffer for the
+/// one-shot/two-shot peer-copy all-reduce costs more bandwidth than it
+/// saves in latency, so [`MultiGpuComm::all_reduce`] falls back to the
+/// ring/tree path even when every GPU pair is peer-accessible.
+const LOW_LATENCY_ALL_REDUCE_MAX_ELEMENTS: usize = 1024;
+
+/// Below this element count (and within the low-latency regime above),
+/// the one-shot all-reduce's O(n^2) peer reads are cheaper than the
+/// two-shot reduce-scatter/all-gather's extra round trip.
+const ONE_SHOT_ALL_REDUCE_MAX_ELEMENTS: usize = 64;
+
+/// Collective completion mode, mirroring the `sync_nccl_allreduce` flag
+/// real NCCL wrappers expose to trade determinism for overlap with other
+/// device work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommMode {
+    /// [`MultiGpuComm::all_reduce`] performs a device-synchronize
+    /// equivalent before returning, so the caller's buffer holds the
+    /// final result immediately -- simpler, and gives reproducible
+    /// timing, at the cost of blocking until the collective completes.
+    Sync,
+    /// Callers should prefer [`MultiGpuComm::all_reduce_async`], which
+    /// returns immediately with a handle to `wait()` on later, letting
+    /// the collective overlap with other work such as backward-pass
+    /// computation.
+    Async,
+}
+
 /// Multi-GPU communicator
 pub struct MultiGpuComm {
     num_gpus: i32,
     topology: GpuTopology,
     strategy: MultiGpuStrategy,
+    mode: CommMode,
 }
 
 impl MultiGpuComm {
     /// Create new communicator
     pub fn new(strategy: MultiGpuStrategy) -> Result<Self, MultiGpuError> {
         let topology = GpuTopology::discover();
-        
+
         if topology.num_gpus < 1 {
             return Err(MultiGpuError::NoGpuFound);
         }
-        
+
         Ok(Self {
             num_gpus: topology.num_gpus,
             topology,
             strategy,
+            mode: CommMode::Sync,
         })
     }
-    
+
     /// Get number of GPUs
     pub fn num_gpus(&self) -> i32 {
         self.num_gpus
     }
-    
+
     /// Get topology
     pub fn topology(&self) -> &GpuTopology {
         &self.topology
     }
-    
+
     /// Get strategy
     pub fn strategy(&self) -> MultiGpuStrategy {
         self.strategy
     }
-    
-    /// All-reduce operation (placeholder)
-    pub fn all_reduce(&self, _data: &mut [f32], _op: ReductionOp) -> Result<(), MultiGpuError> {
-        // In real implementation: ncclAllReduce
+
+    /// Get completion mode
+    pub fn mode(&self) -> CommMode {
+        self.mode
+    }
+
+    /// Set completion mode
+    pub fn set_mode(&mut self, mode: CommMode) {
+        self.mode = mode;
+    }
+
+    /// All-reduce `data` across every GPU in this communicator.
+    ///
+    /// No real CUDA peer-copy backend exists yet, so this runs a host
+    /// (CPU) fallback: each of `num_gpus` GPUs is modeled as holding a
+    /// clone of `data` combined with the others via one of four paths,
+    /// picked by size and topology:
+    /// - [`Self::one_shot_reduce`] / [`Self::two_shot_reduce`]: low-latency
+    ///   peer-copy paths used when every GPU pair is peer-accessible (see
+    ///   [`GpuTopology::fully_peer_connected`]) and `data` is at or below
+    ///   [`LOW_LATENCY_ALL_REDUCE_MAX_ELEMENTS`] -- one-shot for very small
+    ///   buffers (below [`ONE_SHOT_ALL_REDUCE_MAX_ELEMENTS`]), two-shot
+    ///   above that.
+    /// - [`Self::tree_reduce`]: latency-optimal recursive halving/doubling,
+    ///   chosen for buffers at or below [`TREE_ALL_REDUCE_MAX_ELEMENTS`]
+    ///   that didn't qualify for the peer-copy paths.
+    /// - [`Self::ring_reduce`]: bandwidth-optimal, the fallback for
+    ///   everything larger.
+    ///
+    /// All four operate on a `&mut [Vec<f32>]` of per-GPU buffers, so a
+    /// future CUDA backend can slot in real device buffers without
+    /// touching the reduction logic. `ReductionOp::Avg` runs as `Sum` and
+    /// then divides by `num_gpus`.
+    pub fn all_reduce(&self, data: &mut [f32], op: ReductionOp) -> Result<(), MultiGpuError> {
+        let n = self.num_gpus as usize;
+        if n <= 1 || data.is_empty() {
+            return Ok(());
+        }
+
+        let mut buffers: Vec<Vec<f32>> = (0..n).map(|_| data.to_vec()).collect();
+
+        if data.len() <= LOW_LATENCY_ALL_REDUCE_MAX_ELEMENTS && self.topology.fully_peer_connected() {
+            self.barrier();
+            if data.len() <= ONE_SHOT_ALL_REDUCE_MAX_ELEMENTS {
+                Self::one_shot_reduce(&mut buffers, op);
+            } else {
+                Self::two_shot_reduce(&mut buffers, op);
+            }
+            self.barrier();
+        } else if data.len() <= TREE_ALL_REDUCE_MAX_ELEMENTS {
+            Self::tree_reduce(&mut buffers, op);
+        } else {
+            let order = self.topology.ring_order();
+            Self::ring_reduce(&mut buffers, &order, op);
+        }
+
+        data.copy_from_slice(&buffers[0]);
+        if op == ReductionOp::Avg {
+            for v in data.iter_mut() {
+                *v /= n as f32;
+            }
+        }
+
+        if self.mode == CommMode::Sync {
+            self.device_synchronize();
+        }
+
         Ok(())
     }
-    
+
+    /// Launch an all-reduce without blocking the caller. No real CUDA
+    /// stream exists in this host fallback, so the reduction actually
+    /// runs eagerly -- but the launch/[`AllReduceHandle::wait`] shape
+    /// matches a real NCCL backend, so callers can overlap other work
+    /// (e.g. backward-pass computation) with the wait.
+    ///
+    /// `data` is snapshotted at launch time; the hazard this mirrors is
+    /// real -- a true async collective would keep reading/writing the
+    /// source buffer until the stream completes, so callers must not
+    /// reuse `data` for anything the collective depends on until
+    /// [`AllReduceHandle::wait`] is called.
+    pub fn all_reduce_async(
+        &self,
+        data: &[f32],
+        op: ReductionOp,
+    ) -> Result<AllReduceHandle, MultiGpuError> {
+        let mut result = data.to_vec();
+        self.all_reduce(&mut result, op)?;
+        Ok(AllReduceHandle { result })
+    }
+
+    /// Device-synchronize equivalent: a real CUDA backend would block
+    /// here until all in-flight work on every GPU's stream completes.
+    /// The host fallback has no streams, so this is a no-op kept only to
+    /// document [`CommMode::Sync`]'s contract.
+    fn device_synchronize(&self) {}
+
+    /// Synchronization barrier used before and after the one-shot and
+    /// two-shot peer-copy all-reduce paths, which (unlike ring/tree)
+    /// rely on every rank having published its buffer to peer-accessible
+    /// memory before any rank starts reading it. No real peer-accessible
+    /// staging memory exists in this host fallback, so this is a no-op
+    /// kept for parity with a real NCCL/RCCL backend's synchronization
+    /// contract.
+    pub fn barrier(&self) {}
+
+    /// One-shot all-reduce: every rank's buffer is already "published"
+    /// (it's a full local clone in this host fallback), so each rank
+    /// just reads and sums all `n` buffers directly -- O(n^2) total
+    /// reads, but only a single barrier round trip, which wins for very
+    /// small messages where the two-shot path's extra round trip would
+    /// dominate.
+    fn one_shot_reduce(buffers: &mut [Vec<f32>], op: ReductionOp) {
+        let n = buffers.len();
+        if n <= 1 {
+            return;
+        }
+
+        let mut result = buffers[0].clone();
+        for buf in buffers.iter().skip(1) {
+            for (d, s) in result.iter_mut().zip(buf) {
+                *d = Self::reduce_values(op, *d, *s);
+            }
+        }
+
+        for buf in buffers.iter_mut() {
+            buf.copy_from_slice(&result);
+        }
+    }
+
+    /// Two-shot all-reduce: a peer-copy reduce-scatter where each rank
+    /// reduces only its own `1/n` slice directly from every other rank's
+    /// published buffer -- no ring hops needed, since every pair is
+    /// peer-accessible -- followed by a peer-copy all-gather of the
+    /// reduced slices. Wins over one-shot once the O(n^2) full-buffer
+    /// reads cost more than the two round trips.
+    fn two_shot_reduce(buffers: &mut [Vec<f32>], op: ReductionOp) {
+        let n = buffers.len();
+        if n <= 1 {
+            return;
+        }
+        let bounds = Self::chunk_bounds(buffers[0].len(), n);
+
+        // Reduce-scatter: rank `r` reduces chunk `r` directly from every
+        // other rank's buffer.
+        let reduced: Vec<Vec<f32>> = (0..n)
+            .map(|r| {
+                let (start, end) = bounds[r];
+                let mut chunk = buffers[0][start..end].to_vec();
+                for buf in buffers.iter().skip(1) {
+                    for (d, s) in chunk.iter_mut().zip(&buf[start..end]) {
+                        *d = Self::reduce_values(op, *d, *s);
+                    }
+                }
+                chunk
+            })
+            .collect();
+
+        // All-gather: every rank copies every reduced chunk directly.
+        for buf in buffers.iter_mut() {
+            for (r, &(start, end)) in bounds.iter().enumerate() {
+                buf[start..end].copy_from_slice(&reduced[r]);
+            }
+        }
+    }
+
+    fn reduce_values(op: ReductionOp, a: f32, b: f32) -> f32 {
+        match op {
+            ReductionOp::Sum | ReductionOp::Avg => a + b,
+            ReductionOp::Prod => a * b,
+            ReductionOp::Max => a.max(b),
+            ReductionOp::Min => a.min(b),
+        }
+    }
+
+    /// Split `len` elements into `n` contiguous chunks as evenly as
+    /// possible, front-loading any remainder onto the earliest chunks.
+    fn chunk_bounds(len: usize, n: usize) -> Vec<(usize, usize)> {
+        let base = len / n;
+        let remainder = len % n;
+        let mut bounds = Vec::with_capacity(n);
+        let mut start = 0;
+        for i in 0..n {
+            let size = base + if i < remainder { 1 } else { 0 };
+            bounds.push((start, start + size));
+            start += size;
+        }
+        bounds
+    }
+
+    /// Bandwidth-optimal ring all-reduce: a reduce-scatter phase followed
+    /// by an all-gather phase, each `n - 1` steps, over `buffers` (one per
+    /// rank, all the same length). `order` is a topology-aware rank
+    /// sequence (see [`GpuTopology::ring_order`]) so neighbor exchanges
+    /// prefer NVLink over PCIe. Every buffer holds the identical,
+    /// fully-reduced result once this returns.
+    fn ring_reduce(buffers: &mut [Vec<f32>], order: &[usize], op: ReductionOp) {
+        let n = order.len();
+        if n <= 1 {
+            return;
+        }
+        let bounds = Self::chunk_bounds(buffers[0].len(), n);
+
+        // Reduce-scatter: at step `step`, the rank at ring position `pos`
+        // sends chunk `(pos - step) mod n` to its ring successor, which
+        // accumulates it into its own copy of that chunk via `op`. Updates
+        // are collected before being applied so every rank reads the
+        // pre-step state, matching a real simultaneous exchange.
+        for step in 0..n - 1 {
+            let updates: Vec<(usize, usize, Vec<f32>)> = (0..n)
+                .map(|pos| {
+                    let sender = order[pos];
+                    let receiver = order[(pos + 1) % n];
+                    let chunk = (pos + n - step) % n;
+                    let (start, end) = bounds[chunk];
+                    (receiver, chunk, buffers[sender][start..end].to_vec())
+                })
+                .collect();
+
+            for (receiver, chunk, incoming) in updates {
+                let (start, end) = bounds[chunk];
+                for (d, s) in buffers[receiver][start..end].iter_mut().zip(incoming) {
+                    *d = Self::reduce_values(op, *d, s);
+                }
+            }
+        }
+
+        // All-gather: circulate each rank's now fully-reduced chunk for
+        // another `n - 1` steps so every rank ends with every chunk.
+        for step in 0..n - 1 {
+            let updates: Vec<(usize, usize, Vec<f32>)> = (0..n)
+                .map(|pos| {
+                    let sender = order[pos];
+                    let receiver = order[(pos + 1) % n];
+                    let chunk = (pos + n - step + 1) % n;
+                    let (start, end) = bounds[chunk];
+                    (receiver, chunk, buffers[sender][start..end].to_vec())
+                })
+                .collect();
+
+            for (receiver, chunk, value) in updates {
+                let (start, end) = bounds[chunk];
+                buffers[receiver][start..end].copy_from_slice(&value);
+            }
+        }
+    }
+
+    /// Latency-optimized all-reduce for small buffers, where the ring
+    /// path's `n - 1` serialized round trips would dominate total
+    /// latency. Reduces via recursive halving (pairing ranks at
+    /// increasing power-of-two strides) when `buffers.len()` is a power
+    /// of two, then broadcasts the result back out (doubling); otherwise
+    /// falls back to combining directly into rank 0 and broadcasting --
+    /// still a single round trip, just not the classic halving pairing.
+    fn tree_reduce(buffers: &mut [Vec<f32>], op: ReductionOp) {
+        let n = buffers.len();
+        if n <= 1 {
+            return;
+        }
+
+        if n.is_power_of_two() {
+            let mut stride = 1;
+            while stride < n {
+                let pairs: Vec<(usize, Vec<f32>)> = (0..n)
+                    .step_by(stride * 2)
+                    .map(|a| (a, buffers[a + stride].clone()))
+                    .collect();
+
+                for (a, other) in pairs {
+                    for (d, s) in buffers[a].iter_mut().zip(other) {
+                        *d = Self::reduce_values(op, *d, s);
+                    }
+                }
+                stride *= 2;
+            }
+        } else {
+            let mut result = buffers[0].clone();
+            for buf in buffers.iter().skip(1) {
+                for (d, s) in result.iter_mut().zip(buf) {
+                    *d = Self::reduce_values(op, *d, *s);
+                }
+            }
+            buffers[0].copy_from_slice(&result);
+        }
+
+        let result = buffers[0].clone();
+        for buf in buffers.iter_mut().skip(1) {
+            buf.copy_from_slice(&result);
+        }
+    }
+
     /// All-gather operation (placeholder)
     pub fn all_gather(&self, _send: &[f32], _recv: &mut [f32]) -> Result<(), MultiGpuError> {
         // In real implementation: ncclAllGather
         Ok(())
     }
-    
+
+    /// Sparse all-gather used by Deep Gradient Compression (see
+    /// [`DataParallelTrainer::set_compression`]): every GPU contributes a
+    /// small set of `(index, value)` pairs instead of a dense buffer, and
+    /// every GPU should receive every other GPU's pairs. As with
+    /// [`Self::all_reduce`], no real NCCL backend exists yet, so the host
+    /// fallback models each of `num_gpus` GPUs as contributing the same
+    /// local pairs and returns the concatenation of all `num_gpus` copies.
+    pub fn sparse_all_gather(
+        &self,
+        indices: &[u32],
+        values: &[f32],
+    ) -> Result<Vec<(u32, f32)>, MultiGpuError> {
+        if indices.len() != values.len() {
+            return Err(MultiGpuError::CommunicationError(
+                "sparse_all_gather: indices and values length mismatch".to_string(),
+            ));
+        }
+
+        let n = self.num_gpus.max(1) as usize;
+        let local: Vec<(u32, f32)> = indices.iter().copied().zip(values.iter().copied()).collect();
+        let mut gathered = Vec::with_capacity(local.len() * n);
+        for _ in 0..n {
+            gathered.extend_from_slice(&local);
+        }
+        Ok(gathered)
+    }
+
     /// Broadcast from one GPU to all (placeholder)
     pub fn broadcast(&self, _data: &mut [f32], _root: i32) -> Result<(), MultiGpuError> {
         // In real implementation: ncclBroadcast
@@ -243,6 +766,28 @@ impl MultiGpuComm {
     }
 }
 
+/// A pending all-reduce launched by [`MultiGpuComm::all_reduce_async`].
+/// Dropping the handle without calling [`Self::wait`] silently discards
+/// the result, just as leaking a real CUDA stream event would.
+pub struct AllReduceHandle {
+    result: Vec<f32>,
+}
+
+impl AllReduceHandle {
+    /// Block until the collective completes and copy the reduced result
+    /// back into `data`, which must be the same buffer (and length)
+    /// passed to [`MultiGpuComm::all_reduce_async`].
+    pub fn wait(self, data: &mut [f32]) -> Result<(), MultiGpuError> {
+        if data.len() != self.result.len() {
+            return Err(MultiGpuError::CommunicationError(
+                "AllReduceHandle::wait: buffer length changed since launch".to_string(),
+            ));
+        }
+        data.copy_from_slice(&self.result);
+        Ok(())
+    }
+}
+
 /// Multi-GPU error types
 #[derive(Debug)]
 pub enum MultiGpuError {
@@ -265,41 +810,262 @@ impl std::fmt::Display for MultiGpuError {
 
 impl std::error::Error for MultiGpuError {}
 
+/// Configuration for Deep Gradient Compression, see
+/// [`DataParallelTrainer::set_compression`]. Based on Lin et al., "Deep
+/// Gradient Compression" (ICLR 2018): momentum correction plus a
+/// persistent error-feedback residual let most of the gradient go
+/// untransmitted each step without stalling convergence.
+#[derive(Debug, Clone)]
+pub struct CompressionConfig {
+    /// Fraction of gradient entries transmitted per step once warmup has
+    /// completed (e.g. `0.001` keeps the top 0.1% by magnitude)
+    pub target_sparsity: f32,
+    /// Number of steps over which sparsity ramps linearly from dense
+    /// (`1.0`) down to `target_sparsity`, so early training isn't
+    /// starved of signal before the residual has built up
+    pub warmup_steps: u32,
+    /// Momentum factor `beta` applied when folding each step's gradient
+    /// into the residual buffer (`m = beta * m + g`)
+    pub momentum: f32,
+    /// Optional gradient-norm clip applied before compression
+    pub clip_norm: Option<f32>,
+    /// Fraction of residual entries sampled to estimate the top-k
+    /// magnitude threshold, avoiding a full sort of the residual buffer
+    pub sample_fraction: f32,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            target_sparsity: 0.001,
+            warmup_steps: 4,
+            momentum: 0.9,
+            clip_norm: None,
+            sample_fraction: 0.1,
+        }
+    }
+}
+
+/// A pending gradient synchronization returned by
+/// [`DataParallelTrainer::sync_gradients`].
+pub enum GradientSyncHandle {
+    /// The all-reduce already completed before this handle was
+    /// returned; [`Self::wait`] only validates the buffer.
+    Ready,
+    /// An [`CommMode::Async`] all-reduce is in flight; `gradients` must
+    /// stay untouched until [`Self::wait`] is called.
+    Pending(AllReduceHandle),
+}
+
+impl GradientSyncHandle {
+    /// Block until the synchronization completes, writing the reduced
+    /// result back into `gradients` if it was still pending.
+    pub fn wait(self, gradients: &mut [f32]) -> Result<(), MultiGpuError> {
+        match self {
+            GradientSyncHandle::Ready => Ok(()),
+            GradientSyncHandle::Pending(handle) => handle.wait(gradients),
+        }
+    }
+}
+
 /// Data parallel trainer
 pub struct DataParallelTrainer {
     comm: MultiGpuComm,
     batch_size_per_gpu: usize,
     gradient_accumulation_steps: i32,
+    compression: Option<CompressionConfig>,
+    residual: Vec<f32>,
+    momentum_buffer: Vec<f32>,
+    step: u32,
 }
 
 impl DataParallelTrainer {
     /// Create new data parallel trainer
     pub fn new(batch_size_per_gpu: usize) -> Result<Self, MultiGpuError> {
         let comm = MultiGpuComm::new(MultiGpuStrategy::DataParallel)?;
-        
+
         Ok(Self {
             comm,
             batch_size_per_gpu,
             gradient_accumulation_steps: 1,
+            compression: None,
+            residual: Vec::new(),
+            momentum_buffer: Vec::new(),
+            step: 0,
         })
     }
-    
+
     /// Set gradient accumulation steps
     pub fn set_gradient_accumulation(&mut self, steps: i32) {
         self.gradient_accumulation_steps = steps;
     }
-    
+
     /// Get effective batch size
     pub fn effective_batch_size(&self) -> usize {
-        self.batch_size_per_gpu 
-            * self.comm.num_gpus() as usize 
+        self.batch_size_per_gpu
+            * self.comm.num_gpus() as usize
             * self.gradient_accumulation_steps as usize
     }
-    
-    /// Synchronize gradients across GPUs
-    pub fn sync_gradients(&self, gradients: &mut [f32]) -> Result<(), MultiGpuError> {
-        // All-reduce gradients with averaging
-        self.comm.all_reduce(gradients, ReductionOp::Avg)
+
+    /// Enable Deep Gradient Compression for subsequent calls to
+    /// [`Self::sync_gradients`], resetting the residual/momentum buffers
+    /// and warmup step counter.
+    pub fn set_compression(&mut self, config: CompressionConfig) {
+        self.compression = Some(config);
+        self.residual.clear();
+        self.momentum_buffer.clear();
+        self.step = 0;
+    }
+
+    /// Disable Deep Gradient Compression, falling back to a plain dense
+    /// all-reduce on the next [`Self::sync_gradients`] call.
+    pub fn clear_compression(&mut self) {
+        self.compression = None;
+    }
+
+    /// Synchronize gradients across GPUs: Deep Gradient Compression if
+    /// [`Self::set_compression`] was called (always blocking, since the
+    /// residual/momentum update needs the reduced result immediately),
+    /// otherwise a plain dense all-reduce dispatched on the
+    /// communicator's [`CommMode`] -- blocking in [`CommMode::Sync`] for
+    /// reproducible timing, or launched without blocking in
+    /// [`CommMode::Async`] so the caller can overlap other work (e.g.
+    /// the next backward pass) before calling [`GradientSyncHandle::wait`].
+    ///
+    /// In the async case, `gradients` must not be read or written again
+    /// until `wait` is called -- the handle owns the pending result, not
+    /// a reference into `gradients`.
+    pub fn sync_gradients(
+        &mut self,
+        gradients: &mut [f32],
+    ) -> Result<GradientSyncHandle, MultiGpuError> {
+        if let Some(config) = self.compression.clone() {
+            self.sync_gradients_compressed(gradients, &config)?;
+            return Ok(GradientSyncHandle::Ready);
+        }
+
+        match self.comm.mode() {
+            CommMode::Sync => {
+                self.comm.all_reduce(gradients, ReductionOp::Avg)?;
+                Ok(GradientSyncHandle::Ready)
+            }
+            CommMode::Async => {
+                let handle = self.comm.all_reduce_async(gradients, ReductionOp::Avg)?;
+                Ok(GradientSyncHandle::Pending(handle))
+            }
+        }
+    }
+
+    /// Deep Gradient Compression: momentum-corrects and accumulates
+    /// `gradients` into a persistent residual (error-feedback) buffer,
+    /// transmits only the top-k entries by magnitude -- `k` driven by
+    /// `config.target_sparsity`, ramped up from dense over
+    /// `config.warmup_steps` -- through [`MultiGpuComm::sparse_all_gather`],
+    /// then subtracts what was sent from the residual so untransmitted
+    /// gradients carry forward to later steps.
+    fn sync_gradients_compressed(
+        &mut self,
+        gradients: &mut [f32],
+        config: &CompressionConfig,
+    ) -> Result<(), MultiGpuError> {
+        if self.residual.len() != gradients.len() {
+            self.residual = vec![0.0; gradients.len()];
+            self.momentum_buffer = vec![0.0; gradients.len()];
+        }
+
+        if let Some(max_norm) = config.clip_norm {
+            Self::clip_by_norm(gradients, max_norm);
+        }
+
+        for i in 0..gradients.len() {
+            self.momentum_buffer[i] = config.momentum * self.momentum_buffer[i] + gradients[i];
+            self.residual[i] += self.momentum_buffer[i];
+        }
+
+        let sparsity = Self::warmup_sparsity(config, self.step);
+        let k = (((gradients.len() as f32) * sparsity).ceil() as usize)
+            .clamp(1, gradients.len().max(1));
+
+        let threshold = Self::sampled_magnitude_threshold(&self.residual, k, config.sample_fraction);
+
+        let mut indices = Vec::with_capacity(k);
+        let mut values = Vec::with_capacity(k);
+        for (i, &v) in self.residual.iter().enumerate() {
+            if v.abs() >= threshold {
+                indices.push(i as u32);
+                values.push(v);
+                if indices.len() >= k {
+                    break;
+                }
+            }
+        }
+
+        let gathered = self.comm.sparse_all_gather(&indices, &values)?;
+
+        for v in gradients.iter_mut() {
+            *v = 0.0;
+        }
+        for (index, value) in &gathered {
+            if let Some(slot) = gradients.get_mut(*index as usize) {
+                *slot += value;
+            }
+        }
+        let n = self.comm.num_gpus().max(1) as f32;
+        for v in gradients.iter_mut() {
+            *v /= n;
+        }
+
+        for &index in &indices {
+            self.residual[index as usize] = 0.0;
+            self.momentum_buffer[index as usize] = 0.0;
+        }
+
+        self.step += 1;
+        Ok(())
+    }
+
+    /// Linearly ramp sparsity from dense (`1.0`) down to
+    /// `config.target_sparsity` over `config.warmup_steps` steps.
+    fn warmup_sparsity(config: &CompressionConfig, step: u32) -> f32 {
+        if config.warmup_steps == 0 || step >= config.warmup_steps {
+            return config.target_sparsity;
+        }
+        let progress = step as f32 / config.warmup_steps as f32;
+        1.0 - progress * (1.0 - config.target_sparsity)
+    }
+
+    /// Estimate the magnitude threshold for the top-`k` entries of
+    /// `residual` by sampling a subset rather than sorting the full
+    /// buffer -- exact for small buffers, approximate (and much cheaper)
+    /// for large ones.
+    fn sampled_magnitude_threshold(residual: &[f32], k: usize, sample_fraction: f32) -> f32 {
+        if residual.is_empty() || k >= residual.len() {
+            return 0.0;
+        }
+
+        let sample_size = (((residual.len() as f32) * sample_fraction.clamp(0.0, 1.0)).ceil() as usize)
+            .max(k)
+            .min(residual.len());
+        let stride = (residual.len() / sample_size).max(1);
+
+        let mut sample: Vec<f32> = residual.iter().step_by(stride).map(|v| v.abs()).collect();
+        sample.sort_by(|a, b| b.partial_cmp(a).unwrap());
+
+        let k_in_sample = ((k * sample.len()) / residual.len()).clamp(1, sample.len());
+        sample[k_in_sample - 1]
+    }
+
+    /// Scale `gradients` down so its L2 norm does not exceed `max_norm`,
+    /// applied before compression so a single spiking gradient can't
+    /// dominate the sampled top-k selection.
+    fn clip_by_norm(gradients: &mut [f32], max_norm: f32) {
+        let norm = gradients.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > max_norm && norm > 0.0 {
+            let scale = max_norm / norm;
+            for v in gradients.iter_mut() {
+                *v *= scale;
+            }
+        }
     }
 }
 
@@ -317,8 +1083,11 @@ mod tests {
         let topology = GpuTopology::discover();
         // May or may not find GPUs, but should not panic
         assert!(topology.num_gpus >= 0);
+        if topology.num_gpus == 0 {
+            assert_eq!(topology.vendor, GpuVendor::Unknown);
+        }
     }
-    
+
     #[test]
     fn test_recommend_strategy_small_model() {
         let mut topology = GpuTopology::discover();
@@ -337,4 +1106,243 @@ mod tests {
         let op = ReductionOp::Sum;
         assert_eq!(format!("{:?}", op), "Sum");
     }
+
+    fn synthetic_comm(num_gpus: i32) -> MultiGpuComm {
+        let n = num_gpus as usize;
+        MultiGpuComm {
+            num_gpus,
+            topology: GpuTopology {
+                num_gpus,
+                vendor: GpuVendor::Nvidia,
+                gpu_names: (0..n).map(|i| format!("GPU {}", i)).collect(),
+                nvlink_matrix: vec![vec![false; n]; n],
+                pcie_peer_matrix: vec![vec![true; n]; n],
+                memory_per_gpu: vec![16 * 1024 * 1024 * 1024; n],
+            },
+            strategy: MultiGpuStrategy::DataParallel,
+            mode: CommMode::Sync,
+        }
+    }
+
+    #[test]
+    fn test_all_reduce_sum_tree_path() {
+        let mut comm = synthetic_comm(4);
+        comm.topology.pcie_peer_matrix[0][1] = false; // force the ring/tree fallback
+        let mut data = vec![1.0, 2.0, 3.0];
+        comm.all_reduce(&mut data, ReductionOp::Sum).unwrap();
+        assert_eq!(data, vec![4.0, 8.0, 12.0]);
+    }
+
+    #[test]
+    fn test_all_reduce_avg_tree_path() {
+        let mut comm = synthetic_comm(4);
+        comm.topology.pcie_peer_matrix[0][1] = false; // force the ring/tree fallback
+        let mut data = vec![1.0, 2.0, 3.0];
+        comm.all_reduce(&mut data, ReductionOp::Avg).unwrap();
+        assert_eq!(data, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_all_reduce_sum_ring_path() {
+        let comm = synthetic_comm(4);
+        let mut data = vec![1.0_f32; TREE_ALL_REDUCE_MAX_ELEMENTS + 1];
+        comm.all_reduce(&mut data, ReductionOp::Sum).unwrap();
+        assert!(data.iter().all(|&v| v == 4.0));
+    }
+
+    #[test]
+    fn test_all_reduce_single_gpu_is_noop() {
+        let comm = synthetic_comm(1);
+        let mut data = vec![1.0, 2.0, 3.0];
+        comm.all_reduce(&mut data, ReductionOp::Sum).unwrap();
+        assert_eq!(data, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_fully_peer_connected_true_by_default() {
+        let comm = synthetic_comm(4);
+        assert!(comm.topology.fully_peer_connected());
+    }
+
+    #[test]
+    fn test_fully_peer_connected_false_when_a_pair_is_missing() {
+        let mut topology = synthetic_comm(4).topology;
+        topology.pcie_peer_matrix[0][1] = false;
+        assert!(!topology.fully_peer_connected());
+    }
+
+    #[test]
+    fn test_all_reduce_sum_one_shot_path() {
+        let comm = synthetic_comm(4);
+        let mut data = vec![1.0, 2.0, 3.0];
+        assert!(data.len() <= ONE_SHOT_ALL_REDUCE_MAX_ELEMENTS);
+        comm.all_reduce(&mut data, ReductionOp::Sum).unwrap();
+        assert_eq!(data, vec![4.0, 8.0, 12.0]);
+    }
+
+    #[test]
+    fn test_all_reduce_sum_two_shot_path() {
+        let comm = synthetic_comm(4);
+        let mut data = vec![1.0_f32; ONE_SHOT_ALL_REDUCE_MAX_ELEMENTS + 1];
+        assert!(data.len() <= LOW_LATENCY_ALL_REDUCE_MAX_ELEMENTS);
+        comm.all_reduce(&mut data, ReductionOp::Sum).unwrap();
+        assert!(data.iter().all(|&v| v == 4.0));
+    }
+
+    #[test]
+    fn test_all_reduce_falls_back_to_ring_tree_without_full_peer_access() {
+        let mut comm = synthetic_comm(4);
+        comm.topology.pcie_peer_matrix[0][1] = false;
+        let mut data = vec![1.0, 2.0, 3.0];
+        comm.all_reduce(&mut data, ReductionOp::Sum).unwrap();
+        assert_eq!(data, vec![4.0, 8.0, 12.0]);
+    }
+
+    #[test]
+    fn test_default_comm_mode_is_sync() {
+        let comm = synthetic_comm(4);
+        assert_eq!(comm.mode(), CommMode::Sync);
+    }
+
+    #[test]
+    fn test_set_mode_switches_to_async() {
+        let mut comm = synthetic_comm(4);
+        comm.set_mode(CommMode::Async);
+        assert_eq!(comm.mode(), CommMode::Async);
+    }
+
+    #[test]
+    fn test_all_reduce_async_defers_result_until_wait() {
+        let comm = synthetic_comm(4);
+        let mut data = vec![1.0, 2.0, 3.0];
+        let handle = comm.all_reduce_async(&data, ReductionOp::Sum).unwrap();
+        // The source buffer is untouched until `wait` is called.
+        assert_eq!(data, vec![1.0, 2.0, 3.0]);
+        handle.wait(&mut data).unwrap();
+        assert_eq!(data, vec![4.0, 8.0, 12.0]);
+    }
+
+    #[test]
+    fn test_all_reduce_handle_wait_rejects_length_mismatch() {
+        let comm = synthetic_comm(4);
+        let handle = comm.all_reduce_async(&[1.0, 2.0, 3.0], ReductionOp::Sum).unwrap();
+        let mut wrong_len = vec![0.0, 0.0];
+        assert!(handle.wait(&mut wrong_len).is_err());
+    }
+
+    #[test]
+    fn test_ring_order_prefers_nvlink_neighbors() {
+        let mut topology = synthetic_comm(3).topology;
+        // Connect GPU 0 <-> GPU 2 over NVLink; GPU 1 is PCIe-only.
+        topology.nvlink_matrix[0][2] = true;
+        topology.nvlink_matrix[2][0] = true;
+
+        let order = topology.ring_order();
+        assert_eq!(order[0], 0);
+        assert_eq!(order[1], 2);
+        assert_eq!(order[2], 1);
+    }
+
+    fn synthetic_trainer(num_gpus: i32) -> DataParallelTrainer {
+        DataParallelTrainer {
+            comm: synthetic_comm(num_gpus),
+            batch_size_per_gpu: 32,
+            gradient_accumulation_steps: 1,
+            compression: None,
+            residual: Vec::new(),
+            momentum_buffer: Vec::new(),
+            step: 0,
+        }
+    }
+
+    #[test]
+    fn test_sparse_all_gather_concatenates_per_rank_copies() {
+        let comm = synthetic_comm(3);
+        let gathered = comm.sparse_all_gather(&[2, 5], &[1.5, -2.0]).unwrap();
+        assert_eq!(gathered.len(), 6);
+        assert_eq!(gathered.iter().filter(|(i, _)| *i == 2).count(), 3);
+    }
+
+    #[test]
+    fn test_sparse_all_gather_rejects_mismatched_lengths() {
+        let comm = synthetic_comm(2);
+        assert!(comm.sparse_all_gather(&[0, 1], &[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_warmup_sparsity_ramps_from_dense_to_target() {
+        let config = CompressionConfig {
+            target_sparsity: 0.1,
+            warmup_steps: 4,
+            ..Default::default()
+        };
+        assert_eq!(DataParallelTrainer::warmup_sparsity(&config, 0), 1.0);
+        assert!((DataParallelTrainer::warmup_sparsity(&config, 2) - 0.55).abs() < 1e-6);
+        assert_eq!(DataParallelTrainer::warmup_sparsity(&config, 4), 0.1);
+        assert_eq!(DataParallelTrainer::warmup_sparsity(&config, 10), 0.1);
+    }
+
+    #[test]
+    fn test_clip_by_norm_scales_down_when_over_budget() {
+        let mut gradients = vec![3.0, 4.0]; // norm = 5
+        DataParallelTrainer::clip_by_norm(&mut gradients, 1.0);
+        let norm = gradients.iter().map(|v| v * v).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sync_gradients_dense_path_without_compression() {
+        let mut trainer = synthetic_trainer(4);
+        let mut gradients = vec![1.0, 2.0, 3.0];
+        let handle = trainer.sync_gradients(&mut gradients).unwrap();
+        handle.wait(&mut gradients).unwrap();
+        assert_eq!(gradients, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_sync_gradients_async_path_defers_result_until_wait() {
+        let mut trainer = synthetic_trainer(4);
+        trainer.comm.set_mode(CommMode::Async);
+        let mut gradients = vec![1.0, 2.0, 3.0];
+        let handle = trainer.sync_gradients(&mut gradients).unwrap();
+        assert!(matches!(handle, GradientSyncHandle::Pending(_)));
+        handle.wait(&mut gradients).unwrap();
+        assert_eq!(gradients, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_sync_gradients_compressed_transmits_top_k() {
+        let mut trainer = synthetic_trainer(4);
+        trainer.set_compression(CompressionConfig {
+            target_sparsity: 0.5,
+            warmup_steps: 0,
+            momentum: 0.0,
+            clip_norm: None,
+            sample_fraction: 0.1,
+        });
+
+        let mut gradients = vec![1.0, 5.0, 2.0, 8.0];
+        let handle = trainer.sync_gradients(&mut gradients).unwrap();
+        handle.wait(&mut gradients).unwrap();
+
+        assert_eq!(gradients, vec![0.0, 5.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_sync_gradients_compressed_clears_transmitted_residual() {
+        let mut trainer = synthetic_trainer(4);
+        trainer.set_compression(CompressionConfig {
+            target_sparsity: 0.5,
+            warmup_steps: 0,
+            momentum: 0.0,
+            clip_norm: None,
+            sample_fraction: 0.1,
+        });
+
+        let mut gradients = vec![1.0, 5.0, 2.0, 8.0];
+        let handle = trainer.sync_gradients(&mut gradients).unwrap();
+        handle.wait(&mut gradients).unwrap();
+
+        assert_eq!(trainer.residual, vec![1.0, 0.0, 0.0, 8.0]);
+    }
 }
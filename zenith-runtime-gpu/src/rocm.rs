@@ -0,0 +1,411 @@
+//! ROCm SMI GPU Management Interface
+//!
+//! AMD counterpart to [`crate::nvml`]: an [`RocmManager`] backend that
+//! prefers native ROCm SMI (loaded via `libloading`) and falls back to
+//! shelling out to the `rocm-smi` CLI when `librocm_smi64.so` isn't
+//! present. Surfaced through the vendor-agnostic [`crate::backend::GpuBackend`]
+//! trait alongside [`crate::nvml::NvmlManager`].
+use std::os::raw::{c_char, c_int};
+
+use libloading::Library;
+use serde::Deserialize;
+
+use crate::nvml::{ClockInfo, EccStats, GpuInfo, GpuVendor, MemoryInfo, PcieInfo, PowerState, TemperatureInfo, ThrottleReasons, UtilizationInfo};
+use crate::{Error, Result};
+
+/// Raw ROCm SMI C API surface: constants and the function pointer types
+/// [`NativeRocm`] resolves out of `librocm_smi64.so` via `libloading`. Kept
+/// private — everything a caller needs goes through [`RocmManager`] instead.
+mod ffi {
+    use std::os::raw::{c_char, c_int};
+
+    pub const RSMI_STATUS_SUCCESS: c_int = 0;
+
+    // rsmi_memory_type_t
+    pub const RSMI_MEM_TYPE_VRAM: c_int = 0;
+
+    // rsmi_temperature_metric_t
+    pub const RSMI_TEMP_CURRENT: c_int = 0;
+    pub const RSMI_TEMP_MAX: c_int = 1;
+
+    // rsmi_temperature_type_t
+    pub const RSMI_TEMP_TYPE_EDGE: c_int = 0;
+
+    pub type RsmiInit = unsafe extern "C" fn(u64) -> c_int;
+    pub type RsmiShutDown = unsafe extern "C" fn() -> c_int;
+    pub type RsmiNumMonitorDevices = unsafe extern "C" fn(*mut u32) -> c_int;
+    pub type RsmiDevNameGet = unsafe extern "C" fn(u32, *mut c_char, usize) -> c_int;
+    pub type RsmiDevSerialNumberGet = unsafe extern "C" fn(u32, *mut c_char, u32) -> c_int;
+    pub type RsmiDevVbiosVersionGet = unsafe extern "C" fn(u32, *mut c_char, u32) -> c_int;
+    pub type RsmiDevUniqueIdGet = unsafe extern "C" fn(u32, *mut u64) -> c_int;
+    pub type RsmiDevMemoryTotalGet = unsafe extern "C" fn(u32, c_int, *mut u64) -> c_int;
+    pub type RsmiDevMemoryUsageGet = unsafe extern "C" fn(u32, c_int, *mut u64) -> c_int;
+    pub type RsmiDevBusyPercentGet = unsafe extern "C" fn(u32, *mut u32) -> c_int;
+    pub type RsmiDevPowerAveGet = unsafe extern "C" fn(u32, u32, *mut u64) -> c_int;
+    pub type RsmiDevPowerCapGet = unsafe extern "C" fn(u32, u32, *mut u64) -> c_int;
+    pub type RsmiDevPowerCapSet = unsafe extern "C" fn(u32, u32, u64) -> c_int;
+    pub type RsmiDevTempMetricGet = unsafe extern "C" fn(u32, c_int, c_int, *mut i64) -> c_int;
+    pub type RsmiDevGpuClkFreqGet = unsafe extern "C" fn(u32, c_int, *mut u32) -> c_int;
+    pub type RsmiDevPciThroughputGet = unsafe extern "C" fn(u32, *mut u64, *mut u64, *mut u64) -> c_int;
+    pub type RsmiDevEccCountGet = unsafe extern "C" fn(u32, c_int, *mut u64, *mut u64) -> c_int;
+}
+
+/// `.so` names tried in order, matching the naming ROCm's own package
+/// layout uses across releases.
+const ROCM_SMI_LIBRARY_NAMES: &[&str] = &["librocm_smi64.so.1", "librocm_smi64.so"];
+
+fn check(call: &'static str, code: c_int) -> Result<()> {
+    if code == ffi::RSMI_STATUS_SUCCESS {
+        Ok(())
+    } else {
+        Err(Error::Gpu(format!("{} failed with ROCm SMI status code {}", call, code)))
+    }
+}
+
+fn read_rocm_string(buffer: &[c_char]) -> String {
+    let bytes: Vec<u8> = buffer.iter().take_while(|&&c| c != 0).map(|&c| c as u8).collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Native backend: ROCm SMI resolved via `libloading` instead of shelling
+/// out to `rocm-smi`. Holding on to `_library` keeps `librocm_smi64.so`
+/// mapped for as long as the function pointers below remain callable.
+struct NativeRocm {
+    _library: Library,
+    shutdown: ffi::RsmiShutDown,
+    num_monitor_devices: ffi::RsmiNumMonitorDevices,
+    dev_name_get: ffi::RsmiDevNameGet,
+    dev_serial_number_get: ffi::RsmiDevSerialNumberGet,
+    dev_vbios_version_get: ffi::RsmiDevVbiosVersionGet,
+    dev_unique_id_get: ffi::RsmiDevUniqueIdGet,
+    dev_memory_total_get: ffi::RsmiDevMemoryTotalGet,
+    dev_memory_usage_get: ffi::RsmiDevMemoryUsageGet,
+    dev_busy_percent_get: ffi::RsmiDevBusyPercentGet,
+    dev_power_ave_get: ffi::RsmiDevPowerAveGet,
+    dev_power_cap_get: ffi::RsmiDevPowerCapGet,
+    dev_power_cap_set: ffi::RsmiDevPowerCapSet,
+    dev_temp_metric_get: ffi::RsmiDevTempMetricGet,
+    dev_gpu_clk_freq_get: ffi::RsmiDevGpuClkFreqGet,
+    dev_pci_throughput_get: ffi::RsmiDevPciThroughputGet,
+    dev_ecc_count_get: ffi::RsmiDevEccCountGet,
+}
+
+impl NativeRocm {
+    /// Try to `dlopen` `librocm_smi64.so` and call `rsmi_init`. `Ok(None)`
+    /// means no ROCm SMI library was found or it failed to initialize (no
+    /// driver loaded, permissions, etc.) — both are ordinary conditions a
+    /// caller falls back to the `rocm-smi` CLI backend for, not hard errors.
+    fn load() -> Result<Option<Self>> {
+        let Some(library) = ROCM_SMI_LIBRARY_NAMES.iter().find_map(|name| unsafe { Library::new(name).ok() }) else {
+            return Ok(None);
+        };
+
+        // Safety: every symbol below is resolved by its documented ROCm SMI
+        // name and cast to the matching `ffi` function pointer type.
+        let result: Result<Self> = unsafe {
+            let init: ffi::RsmiInit = *library
+                .get(b"rsmi_init\0")
+                .map_err(|e| Error::Gpu(format!("failed to resolve rsmi_init: {}", e)))?;
+            if init(0) != ffi::RSMI_STATUS_SUCCESS {
+                return Ok(None);
+            }
+
+            macro_rules! sym {
+                ($name:literal) => {
+                    *library
+                        .get($name)
+                        .map_err(|e| Error::Gpu(format!("failed to resolve {}: {}", stringify!($name), e)))?
+                };
+            }
+
+            Ok(Self {
+                shutdown: sym!(b"rsmi_shut_down\0"),
+                num_monitor_devices: sym!(b"rsmi_num_monitor_devices\0"),
+                dev_name_get: sym!(b"rsmi_dev_name_get\0"),
+                dev_serial_number_get: sym!(b"rsmi_dev_serial_number_get\0"),
+                dev_vbios_version_get: sym!(b"rsmi_dev_vbios_version_get\0"),
+                dev_unique_id_get: sym!(b"rsmi_dev_unique_id_get\0"),
+                dev_memory_total_get: sym!(b"rsmi_dev_memory_total_get\0"),
+                dev_memory_usage_get: sym!(b"rsmi_dev_memory_usage_get\0"),
+                dev_busy_percent_get: sym!(b"rsmi_dev_busy_percent_get\0"),
+                dev_power_ave_get: sym!(b"rsmi_dev_power_ave_get\0"),
+                dev_power_cap_get: sym!(b"rsmi_dev_power_cap_get\0"),
+                dev_power_cap_set: sym!(b"rsmi_dev_power_cap_set\0"),
+                dev_temp_metric_get: sym!(b"rsmi_dev_temp_metric_get\0"),
+                dev_gpu_clk_freq_get: sym!(b"rsmi_dev_gpu_clk_freq_get\0"),
+                dev_pci_throughput_get: sym!(b"rsmi_dev_pci_throughput_get\0"),
+                dev_ecc_count_get: sym!(b"rsmi_dev_ecc_count_get\0"),
+                _library: library,
+            })
+        };
+
+        result.map(Some)
+    }
+
+    fn device_count(&self) -> Result<u32> {
+        let mut count: u32 = 0;
+        check("rsmi_num_monitor_devices", unsafe { (self.num_monitor_devices)(&mut count) })?;
+        Ok(count)
+    }
+
+    fn read_string(&self, call: unsafe extern "C" fn(u32, *mut c_char, u32) -> c_int, index: u32) -> Option<String> {
+        const BUFFER_SIZE: usize = 96;
+        let mut buffer = [0 as c_char; BUFFER_SIZE];
+        let code = unsafe { call(index, buffer.as_mut_ptr(), BUFFER_SIZE as u32) };
+        (code == ffi::RSMI_STATUS_SUCCESS).then(|| read_rocm_string(&buffer))
+    }
+
+    /// Query everything ROCm SMI exposes for one device and assemble it
+    /// into the same [`GpuInfo`] shape the NVML backend produces. Fields
+    /// ROCm SMI has no equivalent for (`compute_capability`, `sm_count`,
+    /// encoder/decoder utilization, PCIe generation/width, NVLink) are left
+    /// at their zero/empty/`None` defaults — a documented gap, not a silent
+    /// one, the same tradeoff `NvmlManager::get_gpu_info_via_smi` makes.
+    fn get_gpu_info(&self, index: u32) -> Result<GpuInfo> {
+        const BUFFER_SIZE: usize = 96;
+        let mut name_buf = [0 as c_char; BUFFER_SIZE];
+        check("rsmi_dev_name_get", unsafe { (self.dev_name_get)(index, name_buf.as_mut_ptr(), BUFFER_SIZE) })?;
+
+        let mut unique_id: u64 = 0;
+        let uuid = if unsafe { (self.dev_unique_id_get)(index, &mut unique_id) } == ffi::RSMI_STATUS_SUCCESS {
+            format!("{:016x}", unique_id)
+        } else {
+            String::new()
+        };
+
+        let mut mem_total: u64 = 0;
+        let mut mem_used: u64 = 0;
+        unsafe {
+            (self.dev_memory_total_get)(index, ffi::RSMI_MEM_TYPE_VRAM, &mut mem_total);
+            (self.dev_memory_usage_get)(index, ffi::RSMI_MEM_TYPE_VRAM, &mut mem_used);
+        }
+
+        let mut busy_percent: u32 = 0;
+        unsafe { (self.dev_busy_percent_get)(index, &mut busy_percent) };
+
+        let mut power_draw_uw: u64 = 0;
+        let mut power_cap_uw: u64 = 0;
+        unsafe {
+            (self.dev_power_ave_get)(index, 0, &mut power_draw_uw);
+            (self.dev_power_cap_get)(index, 0, &mut power_cap_uw);
+        }
+
+        let mut temp_current: i64 = 0;
+        let mut temp_max: i64 = 0;
+        unsafe {
+            (self.dev_temp_metric_get)(index, ffi::RSMI_TEMP_TYPE_EDGE, ffi::RSMI_TEMP_CURRENT, &mut temp_current);
+            (self.dev_temp_metric_get)(index, ffi::RSMI_TEMP_TYPE_EDGE, ffi::RSMI_TEMP_MAX, &mut temp_max);
+        }
+
+        let mut graphics_clock_mhz: u32 = 0;
+        unsafe { (self.dev_gpu_clk_freq_get)(index, 0, &mut graphics_clock_mhz) };
+
+        let mut pci_sent: u64 = 0;
+        let mut pci_received: u64 = 0;
+        let mut pci_max_pkt_size: u64 = 0;
+        unsafe { (self.dev_pci_throughput_get)(index, &mut pci_sent, &mut pci_received, &mut pci_max_pkt_size) };
+
+        let mut ecc_correctable: u64 = 0;
+        let mut ecc_uncorrectable: u64 = 0;
+        let ecc_supported = unsafe { (self.dev_ecc_count_get)(index, 0, &mut ecc_correctable, &mut ecc_uncorrectable) } == ffi::RSMI_STATUS_SUCCESS;
+
+        Ok(GpuInfo {
+            vendor: GpuVendor::Amd,
+            index,
+            name: read_rocm_string(&name_buf),
+            uuid,
+            serial: self.read_string(self.dev_serial_number_get, index),
+            vbios_version: self.read_string(self.dev_vbios_version_get, index).unwrap_or_default(),
+            driver_version: "unknown".to_string(),
+            compute_capability: (0, 0), // No CUDA-style compute capability on ROCm devices.
+            sm_count: 0, // Not exposed by ROCm SMI; see rsmi_dev_* docs.
+            power_state: PowerState::Unknown, // ROCm SMI has no P-state equivalent.
+            power_limit: (power_cap_uw / 1_000_000) as u32,
+            power_draw: (power_draw_uw / 1_000_000) as u32,
+            memory: MemoryInfo { total: mem_total, used: mem_used, free: mem_total.saturating_sub(mem_used) },
+            utilization: UtilizationInfo { gpu: busy_percent, memory: 0, encoder: 0, decoder: 0 },
+            clocks: ClockInfo { graphics: graphics_clock_mhz, sm: graphics_clock_mhz, memory: 0, video: 0 },
+            temperature: TemperatureInfo {
+                gpu: (temp_current / 1000) as i32,
+                memory: None,
+                slowdown_threshold: (temp_max / 1000) as i32,
+                shutdown_threshold: (temp_max / 1000) as i32,
+            },
+            pcie: PcieInfo {
+                generation: 0, // Not queried: rsmi_dev_pci_bandwidth_get reports link speed, not generation.
+                width: 0,
+                max_generation: 0,
+                max_width: 0,
+                tx_throughput: pci_sent,
+                rx_throughput: pci_received,
+            },
+            nvlink: None, // AMD's equivalent (Infinity Fabric) isn't modeled here.
+            ecc: EccStats { enabled: ecc_supported, single_bit_errors: ecc_correctable, double_bit_errors: ecc_uncorrectable },
+            throttle_reasons: ThrottleReasons::NONE, // rocm-smi doesn't expose a throttle-reason bitmask.
+            mig_enabled: false, // MIG is an NVIDIA-specific feature.
+            mig_devices: None,
+        })
+    }
+
+    fn set_power_limit(&self, index: u32, watts: u32) -> Result<()> {
+        check("rsmi_dev_power_cap_set", unsafe { (self.dev_power_cap_set)(index, 0, watts as u64 * 1_000_000) })
+    }
+}
+
+impl Drop for NativeRocm {
+    fn drop(&mut self) {
+        unsafe { (self.shutdown)() };
+    }
+}
+
+/// ROCm-SMI-backed GPU management interface, mirroring [`crate::nvml::NvmlManager`]:
+/// prefers a native [`NativeRocm`] backend (ROCm SMI loaded directly via
+/// `libloading`) when `librocm_smi64.so` is present and initializes
+/// successfully, otherwise falls back to shelling out to `rocm-smi`.
+pub struct RocmManager {
+    initialized: bool,
+    gpu_count: u32,
+    native: Option<NativeRocm>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RocmSmiCard {
+    #[serde(rename = "Card series")]
+    card_series: Option<String>,
+    #[serde(rename = "Card model")]
+    card_model: Option<String>,
+}
+
+impl RocmManager {
+    /// Initialize ROCm SMI: try the native backend first, falling back to
+    /// `rocm-smi` if `librocm_smi64.so` isn't present or fails to
+    /// initialize (e.g. no driver loaded).
+    pub fn new() -> Result<Self> {
+        if let Some(native) = NativeRocm::load()? {
+            let gpu_count = native.device_count()?;
+            return Ok(Self { initialized: true, gpu_count, native: Some(native) });
+        }
+
+        let gpu_count = Self::detect_gpu_count_via_cli();
+        Ok(Self { initialized: gpu_count > 0, gpu_count, native: None })
+    }
+
+    /// Whether a usable backend (native ROCm SMI or the `rocm-smi` CLI
+    /// fallback) was found at construction time.
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    /// Whether [`Self::new`] loaded the native ROCm SMI backend rather than
+    /// falling back to shelling out to `rocm-smi`.
+    pub fn is_native(&self) -> bool {
+        self.native.is_some()
+    }
+
+    fn detect_gpu_count_via_cli() -> u32 {
+        match std::process::Command::new("rocm-smi").args(["--showid", "--json"]).output() {
+            Ok(output) if output.status.success() => {
+                serde_json::from_slice::<std::collections::HashMap<String, RocmSmiCard>>(&output.stdout)
+                    .map(|cards| cards.len() as u32)
+                    .unwrap_or(0)
+            }
+            _ => 0,
+        }
+    }
+
+    pub fn gpu_count(&self) -> u32 {
+        self.gpu_count
+    }
+
+    pub fn get_gpu_info(&self, index: u32) -> Result<GpuInfo> {
+        if index >= self.gpu_count {
+            return Err(Error::Gpu(format!("Invalid GPU index: {}", index)));
+        }
+
+        match &self.native {
+            Some(native) => native.get_gpu_info(index),
+            None => Err(Error::Gpu(
+                "GPU info requires the native ROCm SMI library; the rocm-smi CLI fallback only reports device count".to_string(),
+            )),
+        }
+    }
+
+    pub fn get_all_gpus(&self) -> Vec<GpuInfo> {
+        (0..self.gpu_count).filter_map(|i| self.get_gpu_info(i).ok()).collect()
+    }
+
+    pub fn set_power_limit(&self, index: u32, watts: u32) -> Result<()> {
+        if index >= self.gpu_count {
+            return Err(Error::Gpu(format!("Invalid GPU index: {}", index)));
+        }
+
+        match &self.native {
+            Some(native) => native.set_power_limit(index, watts),
+            None => {
+                let status = std::process::Command::new("rocm-smi")
+                    .args(["-d", &index.to_string(), "--setpoweroverdrive", &watts.to_string()])
+                    .status()
+                    .map_err(|e| Error::Gpu(format!("Failed to set power limit: {}", e)))?;
+
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err(Error::Gpu("Failed to set power limit".to_string()))
+                }
+            }
+        }
+    }
+
+    pub fn reset_gpu(&self, index: u32) -> Result<()> {
+        if index >= self.gpu_count {
+            return Err(Error::Gpu(format!("Invalid GPU index: {}", index)));
+        }
+
+        let status = std::process::Command::new("rocm-smi")
+            .args(["-d", &index.to_string(), "--gpureset"])
+            .status()
+            .map_err(|e| Error::Gpu(format!("Failed to reset GPU: {}", e)))?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::Gpu("Failed to reset GPU".to_string()))
+        }
+    }
+}
+
+impl Default for RocmManager {
+    fn default() -> Self {
+        Self::new().unwrap_or(Self { initialized: false, gpu_count: 0, native: None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rocm_manager() {
+        let manager = RocmManager::default();
+        // GPU count depends on hardware
+        println!("Detected {} AMD GPUs ({})", manager.gpu_count(), if manager.is_native() { "native ROCm SMI" } else { "rocm-smi fallback" });
+    }
+
+    #[test]
+    fn test_native_rocm_load_without_driver_falls_back_cleanly() {
+        // On a machine with no AMD driver (the common case in CI),
+        // `NativeRocm::load` must return `Ok(None)` rather than erroring,
+        // so `RocmManager::new` falls back to the rocm-smi CLI path.
+        let result = NativeRocm::load();
+        assert!(result.is_ok(), "missing librocm_smi64.so must not be treated as an error");
+    }
+
+    #[test]
+    fn test_read_rocm_string_stops_at_first_nul() {
+        let mut buffer = [0 as c_char; 16];
+        for (i, b) in b"Instinct MI250X".iter().enumerate() {
+            buffer[i] = *b as c_char;
+        }
+        assert_eq!(read_rocm_string(&buffer), "Instinct MI250X");
+    }
+}
@@ -2,13 +2,48 @@
 
 use tonic::Status;
 use std::sync::Arc;
+use serde::{Deserialize, Serialize};
 use crate::scheduler::Scheduler;
 use crate::node::NodeRegistry;
-use crate::job::{Job, JobDescriptor, ResourceRequirements, LocalityPreferences, SchedulingPolicy};
+use crate::job::{Job, JobDescriptor, JobState, ResourceRequirements, LocalityPreferences, SchedulingPolicy};
+use crate::state::{FileBackend, StateBackend, StateStore};
 use std::collections::HashMap;
 
+/// Wire type that accepts either a single item or a collection under one
+/// handler, modeled on the "OneOrVec" unified interface idiom: a caller
+/// submitting one job sends a bare object, a sweep caller sends an array,
+/// and [`SchedulerService::submit_jobs`]/[`SchedulerService::get_jobs_status`]
+/// treat both the same way via [`OneOrMany::into_vec`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::One(item) => vec![item],
+            OneOrMany::Many(items) => items,
+        }
+    }
+}
+
+impl<T> From<T> for OneOrMany<T> {
+    fn from(item: T) -> Self {
+        OneOrMany::One(item)
+    }
+}
+
+impl<T> From<Vec<T>> for OneOrMany<T> {
+    fn from(items: Vec<T>) -> Self {
+        OneOrMany::Many(items)
+    }
+}
+
 /// Job submission request
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubmitJobRequest {
     pub name: String,
     pub user_id: String,
@@ -22,23 +57,26 @@ pub struct SubmitJobRequest {
     pub memory_mb: u64,
     pub priority: i32,
     pub gang_schedule: bool,
+    /// IDs of jobs that must complete before this one becomes eligible
+    #[serde(default)]
+    pub depends_on: Vec<String>,
 }
 
 /// Job submission response
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubmitJobResponse {
     pub job_id: String,
     pub status: String,
 }
 
 /// Job status request
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetJobStatusRequest {
     pub job_id: String,
 }
 
 /// Job status response
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetJobStatusResponse {
     pub job_id: String,
     pub state: String,
@@ -46,6 +84,39 @@ pub struct GetJobStatusResponse {
     pub allocated_nodes: Vec<String>,
 }
 
+/// Job result request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetJobResultRequest {
+    pub job_id: String,
+    /// When set, `stdout`/`stderr` are each truncated to their last
+    /// `tail_bytes`; see [`Scheduler::get_job_result`].
+    #[serde(default)]
+    pub tail_bytes: Option<usize>,
+}
+
+/// Job result response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetJobResultResponse {
+    pub job_id: String,
+    pub state: String,
+    pub exit_code: Option<i32>,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+/// Job heartbeat request
+#[derive(Debug, Clone)]
+pub struct ReportJobHeartbeatRequest {
+    pub job_id: String,
+    pub node_id: String,
+}
+
+/// Job heartbeat response
+#[derive(Debug, Clone)]
+pub struct ReportJobHeartbeatResponse {
+    pub success: bool,
+}
+
 /// Cancel job request
 #[derive(Debug, Clone)]
 pub struct CancelJobRequest {
@@ -71,59 +142,148 @@ pub struct ClusterStatusResponse {
     pub queued_jobs: usize,
 }
 
-/// Scheduler gRPC service
-pub struct SchedulerService {
+/// Build the [`JobDescriptor`] a [`SubmitJobRequest`] describes, shared by
+/// [`SchedulerService::submit_jobs`] so single- and batch-submission go
+/// through identical job construction.
+fn job_descriptor_from(request: SubmitJobRequest) -> JobDescriptor {
+    JobDescriptor {
+        name: request.name,
+        user_id: request.user_id,
+        project_id: request.project_id,
+        command: request.command,
+        arguments: request.arguments,
+        environment: request.environment,
+        working_directory: request.working_directory,
+        resources: ResourceRequirements {
+            gpu_count: request.gpu_count,
+            cpu_cores: request.cpu_cores,
+            cpu_memory: request.memory_mb * 1024 * 1024, // Convert MB to bytes
+            ..Default::default()
+        },
+        locality: LocalityPreferences::default(),
+        policy: SchedulingPolicy {
+            priority: request.priority,
+            gang_schedule: request.gang_schedule,
+            ..Default::default()
+        },
+        labels: HashMap::new(),
+        annotations: HashMap::new(),
+        schedule: None,
+        depends_on: request.depends_on,
+        gang_group: None,
+    }
+}
+
+/// Scheduler gRPC service, optionally backed by a [`StateStore`] so
+/// `submit_job`/`cancel_job` survive a process restart instead of only
+/// living in [`Scheduler`]'s in-memory queue. Generic over the backend the
+/// same way [`Scheduler::restore_from_state`] is, defaulting to
+/// [`FileBackend`] so `SchedulerService<FileBackend>` (what [`Self::new`]
+/// produces) stays inferable at existing call sites.
+pub struct SchedulerService<B: StateBackend = FileBackend> {
     scheduler: Arc<Scheduler>,
     node_registry: Arc<NodeRegistry>,
+    state_store: Option<Arc<StateStore<B>>>,
 }
 
-impl SchedulerService {
-    /// Create a new scheduler service
+impl SchedulerService<FileBackend> {
+    /// Create a new scheduler service with no durable job store: job state
+    /// lives only in `scheduler`'s in-memory queue, as before.
     pub fn new(scheduler: Arc<Scheduler>, node_registry: Arc<NodeRegistry>) -> Self {
         Self {
             scheduler,
             node_registry,
+            state_store: None,
         }
     }
-    
-    /// Submit a job
+}
+
+impl<B: StateBackend> SchedulerService<B> {
+    /// Create a scheduler service whose `submit_job`/`cancel_job` persist
+    /// into `state_store`, and which immediately reconciles/restores
+    /// whatever `state_store` already holds into `scheduler` (see
+    /// [`Scheduler::reconcile_and_restore`]) so a restart picks back up
+    /// queued work and reschedules jobs orphaned by a prior crash.
+    pub fn with_state_store(
+        scheduler: Arc<Scheduler>,
+        node_registry: Arc<NodeRegistry>,
+        state_store: Arc<StateStore<B>>,
+    ) -> crate::Result<Self> {
+        scheduler.reconcile_and_restore(&state_store)?;
+        Ok(Self {
+            scheduler,
+            node_registry,
+            state_store: Some(state_store),
+        })
+    }
+
+    /// Thin wrapper around [`Scheduler::spawn_ha_sync`] for multi-scheduler
+    /// HA: keeps this replica's reads fresh while it's a standby and
+    /// replays uncommitted bindings the moment it wins the cluster lock.
+    /// Returns `None` if this service has no `state_store` configured (see
+    /// [`Self::with_state_store`]) — there is nothing to sync against.
+    pub fn spawn_ha_sync(&self, interval: std::time::Duration) -> Option<crate::scheduler::JanitorHandle>
+    where
+        B: 'static,
+    {
+        let store = self.state_store.clone()?;
+        Some(self.scheduler.clone().spawn_ha_sync(store, interval))
+    }
+
+    /// Submit a job. Thin wrapper around [`Self::submit_jobs`] for the
+    /// common single-job case.
     #[allow(clippy::result_large_err)]
     pub fn submit_job(&self, request: SubmitJobRequest) -> Result<SubmitJobResponse, Status> {
-        let descriptor = JobDescriptor {
-            name: request.name,
-            user_id: request.user_id,
-            project_id: request.project_id,
-            command: request.command,
-            arguments: request.arguments,
-            environment: request.environment,
-            working_directory: request.working_directory,
-            resources: ResourceRequirements {
-                gpu_count: request.gpu_count,
-                cpu_cores: request.cpu_cores,
-                cpu_memory: request.memory_mb * 1024 * 1024, // Convert MB to bytes
-                ..Default::default()
-            },
-            locality: LocalityPreferences::default(),
-            policy: SchedulingPolicy {
-                priority: request.priority,
-                gang_schedule: request.gang_schedule,
-                ..Default::default()
-            },
-            labels: HashMap::new(),
-            annotations: HashMap::new(),
-        };
-        
-        let job = Job::new(descriptor);
-        
-        match self.scheduler.submit(job) {
-            Ok(job_id) => Ok(SubmitJobResponse {
-                job_id,
-                status: "QUEUED".to_string(),
-            }),
-            Err(e) => Err(Status::internal(e.to_string())),
+        self.submit_jobs(request)
+            .into_iter()
+            .next()
+            .expect("submit_jobs(OneOrMany::One) always returns exactly one result")
+    }
+
+    /// Submit one or more jobs in a single call. Accepts a bare
+    /// [`SubmitJobRequest`] or a `Vec<SubmitJobRequest>` (see [`OneOrMany`]).
+    /// If any request in the batch has `gang_schedule` set, the whole batch
+    /// is admitted atomically: if one job fails to submit, every job already
+    /// submitted in this call is cancelled and every slot in the returned
+    /// `Vec` reports the same failure, so a gang-scheduled sweep never ends
+    /// up partially queued.
+    #[allow(clippy::result_large_err)]
+    pub fn submit_jobs(
+        &self,
+        requests: impl Into<OneOrMany<SubmitJobRequest>>,
+    ) -> Vec<Result<SubmitJobResponse, Status>> {
+        let requests = requests.into().into_vec();
+        let atomic = requests.iter().any(|r| r.gang_schedule);
+
+        let mut results: Vec<Result<SubmitJobResponse, Status>> = Vec::with_capacity(requests.len());
+        for request in requests {
+            let job = Job::new(job_descriptor_from(request));
+            results.push(match self.scheduler.submit(job) {
+                Ok(job_id) => {
+                    self.persist_job(&job_id);
+                    Ok(SubmitJobResponse { job_id, status: "QUEUED".to_string() })
+                }
+                Err(e) => Err(Status::internal(e.to_string())),
+            });
+        }
+
+        if atomic && results.iter().any(Result::is_err) {
+            let failure = results
+                .iter()
+                .find_map(|r| r.as_ref().err().cloned())
+                .expect("just checked at least one Err is present");
+            for response in results.iter().flatten() {
+                let _ = self
+                    .scheduler
+                    .cancel(&response.job_id, "rolled back: batch gang-scheduled submission failed");
+                self.persist_job(&response.job_id);
+            }
+            return results.into_iter().map(|_| Err(failure.clone())).collect();
         }
+
+        results
     }
-    
+
     /// Get job status
     #[allow(clippy::result_large_err)]
     pub fn get_job_status(&self, request: GetJobStatusRequest) -> Result<GetJobStatusResponse, Status> {
@@ -137,19 +297,107 @@ impl SchedulerService {
             None => Err(Status::not_found(format!("Job not found: {}", request.job_id))),
         }
     }
-    
+
+    /// Get the status of one or more jobs in a single call. Accepts a bare
+    /// job ID or a `Vec<String>` (see [`OneOrMany`]); each ID is looked up
+    /// independently, so one missing job does not fail the others.
+    #[allow(clippy::result_large_err)]
+    pub fn get_jobs_status(
+        &self,
+        job_ids: impl Into<OneOrMany<String>>,
+    ) -> Vec<Result<GetJobStatusResponse, Status>> {
+        job_ids
+            .into()
+            .into_vec()
+            .into_iter()
+            .map(|job_id| self.get_job_status(GetJobStatusRequest { job_id }))
+            .collect()
+    }
+
+    /// Get the captured stdout/stderr/exit status of a finished job.
+    /// `Status::failed_precondition` if the job hasn't reached a terminal
+    /// state yet (there's nothing to capture from a job still running), or
+    /// `Status::not_found` if no such job exists or no result has been
+    /// recorded for it.
+    #[allow(clippy::result_large_err)]
+    pub fn get_job_result(&self, request: GetJobResultRequest) -> Result<GetJobResultResponse, Status> {
+        let job = self
+            .scheduler
+            .get_job(&request.job_id)
+            .ok_or_else(|| Status::not_found(format!("Job not found: {}", request.job_id)))?;
+
+        if !matches!(
+            job.state,
+            JobState::Completed | JobState::Failed | JobState::Cancelled | JobState::Timeout
+        ) {
+            return Err(Status::failed_precondition(format!(
+                "job {} is {:?}, not yet finished",
+                request.job_id, job.state
+            )));
+        }
+
+        let result = self.scheduler.get_job_result(&request.job_id, request.tail_bytes).ok_or_else(|| {
+            Status::not_found(format!("no result recorded for job: {}", request.job_id))
+        })?;
+
+        Ok(GetJobResultResponse {
+            job_id: request.job_id,
+            state: format!("{:?}", job.state),
+            exit_code: result.exit_code,
+            stdout: result.stdout,
+            stderr: result.stderr,
+        })
+    }
+
+    /// Renew a running job's heartbeat lease; see
+    /// [`Scheduler::report_job_heartbeat`]. Called periodically by the node
+    /// agent actually running the job, not by the job's own code.
+    #[allow(clippy::result_large_err)]
+    pub fn report_job_heartbeat(
+        &self,
+        request: ReportJobHeartbeatRequest,
+    ) -> Result<ReportJobHeartbeatResponse, Status> {
+        self.scheduler
+            .report_job_heartbeat(&request.job_id, &request.node_id)
+            .map(|()| ReportJobHeartbeatResponse { success: true })
+            .map_err(|e| Status::internal(e.to_string()))
+    }
+
     /// Cancel a job
     #[allow(clippy::result_large_err)]
     pub fn cancel_job(&self, request: CancelJobRequest) -> Result<CancelJobResponse, Status> {
         match self.scheduler.cancel(&request.job_id, &request.reason) {
-            Ok(()) => Ok(CancelJobResponse {
-                success: true,
-                message: "Job cancelled".to_string(),
-            }),
+            Ok(()) => {
+                self.persist_job(&request.job_id);
+                Ok(CancelJobResponse {
+                    success: true,
+                    message: "Job cancelled".to_string(),
+                })
+            }
             Err(e) => Err(Status::internal(e.to_string())),
         }
     }
-    
+
+    /// Mirrors `scheduler`'s current view of `job_id` into `state_store`, if
+    /// one is configured. Best-effort: a persistence failure is logged but
+    /// does not fail the gRPC call, since the in-memory scheduler state (the
+    /// source of truth this process serves reads from) already reflects the
+    /// mutation either way.
+    fn persist_job(&self, job_id: &str) {
+        let Some(store) = &self.state_store else { return };
+        let Some(job) = self.scheduler.get_job(job_id) else { return };
+        if let Err(e) = store.store_job(&job) {
+            tracing::warn!("failed to persist job {} to state store: {}", job_id, e);
+        }
+    }
+
+    /// Renders the Prometheus/OpenMetrics text-format scrape body for this
+    /// scheduler; see [`Scheduler::render_metrics`]. Callers mount the
+    /// returned string on a `/metrics` HTTP endpoint.
+    pub fn metrics_text(&self) -> String {
+        self.scheduler.render_metrics()
+    }
+
     /// Get cluster status
     pub fn get_cluster_status(&self) -> ClusterStatusResponse {
         let summary = self.node_registry.summary();
@@ -176,6 +424,37 @@ mod tests {
         let scheduler = Arc::new(Scheduler::new(node_registry.clone(), SchedulerConfig::default()));
         SchedulerService::new(scheduler, node_registry)
     }
+
+    fn create_test_node(id: &str) -> crate::node::Node {
+        crate::node::Node::new(
+            id.to_string(),
+            format!("{}.local", id),
+            "192.168.1.1".to_string(),
+            crate::node::NodeTopology {
+                gpus: vec![],
+                cpu_cores: 64,
+                cpu_memory: 512 * 1024 * 1024 * 1024,
+                cpu_memory_free: 500 * 1024 * 1024 * 1024,
+                numa_nodes: 2,
+                nvlink_present: true,
+                nvswitch_present: false,
+                rdma_capable: true,
+                nvlink_topology: HashMap::new(),
+            },
+        )
+    }
+
+    /// Submits a CPU-only job, schedules it onto whatever node is already
+    /// registered on `service`, and marks it Running, returning its job id.
+    /// Shared setup for the heartbeat tests.
+    fn submit_and_start_running_job(service: &SchedulerService) -> String {
+        let mut request = create_test_request();
+        request.gpu_count = 0;
+        let job_id = service.submit_job(request).unwrap().job_id;
+        service.scheduler.schedule_cycle();
+        service.scheduler.mark_job_started(&job_id).unwrap();
+        job_id
+    }
     
     fn create_test_request() -> SubmitJobRequest {
         SubmitJobRequest {
@@ -191,6 +470,8 @@ mod tests {
             memory_mb: 16384,
             priority: 50,
             gang_schedule: true,
+            depends_on: vec![],
+            gang_group: None,
         }
     }
     
@@ -229,8 +510,10 @@ mod tests {
             memory_mb: 8192,
             priority: 100,
             gang_schedule: false,
+            depends_on: vec![],
+            gang_group: None,
         };
-        
+
         assert_eq!(request.environment.get("CUDA_VISIBLE_DEVICES"), Some(&"0,1".to_string()));
     }
     
@@ -383,7 +666,59 @@ mod tests {
         let status = status_result.unwrap();
         assert_eq!(status.job_id, job_id);
     }
-    
+
+    #[test]
+    fn test_get_job_result_not_found() {
+        let service = create_test_service();
+        let request = GetJobResultRequest { job_id: "non-existent-job".to_string(), tail_bytes: None };
+
+        let result = service.get_job_result(request);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_get_job_result_fails_precondition_while_running() {
+        let service = create_test_service();
+        let submit_result = service.submit_job(create_test_request());
+        let job_id = submit_result.unwrap().job_id;
+
+        let request = GetJobResultRequest { job_id, tail_bytes: None };
+        let result = service.get_job_result(request);
+        assert_eq!(result.unwrap_err().code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[test]
+    fn test_get_job_result_after_completion() {
+        let service = create_test_service();
+        let submit_result = service.submit_job(create_test_request());
+        let job_id = submit_result.unwrap().job_id;
+
+        service.scheduler.mark_job_completed(&job_id, true, "done").unwrap();
+        service.scheduler.record_job_result(&job_id, Some(0), b"out".to_vec(), b"err".to_vec());
+
+        let request = GetJobResultRequest { job_id: job_id.clone(), tail_bytes: None };
+        let response = service.get_job_result(request).unwrap();
+        assert_eq!(response.job_id, job_id);
+        assert_eq!(response.exit_code, Some(0));
+        assert_eq!(response.stdout, b"out".to_vec());
+        assert_eq!(response.stderr, b"err".to_vec());
+    }
+
+    #[test]
+    fn test_get_job_result_truncates_to_tail_bytes() {
+        let service = create_test_service();
+        let submit_result = service.submit_job(create_test_request());
+        let job_id = submit_result.unwrap().job_id;
+
+        service.scheduler.mark_job_completed(&job_id, true, "done").unwrap();
+        service.scheduler.record_job_result(&job_id, Some(0), b"0123456789".to_vec(), b"abcdef".to_vec());
+
+        let request = GetJobResultRequest { job_id, tail_bytes: Some(3) };
+        let response = service.get_job_result(request).unwrap();
+        assert_eq!(response.stdout, b"789".to_vec());
+        assert_eq!(response.stderr, b"def".to_vec());
+    }
+
     #[test]
     fn test_cancel_job_not_found() {
         let service = create_test_service();
@@ -395,7 +730,43 @@ mod tests {
         let result = service.cancel_job(request);
         assert!(result.is_err());
     }
-    
+
+    #[test]
+    fn test_report_job_heartbeat_succeeds_for_allocated_node() {
+        let service = create_test_service();
+        service.node_registry.register(create_test_node("node-1")).unwrap();
+        let job_id = submit_and_start_running_job(&service);
+        let node_id = service.scheduler.get_job(&job_id).unwrap().allocated_nodes[0].clone();
+
+        let response = service
+            .report_job_heartbeat(ReportJobHeartbeatRequest { job_id, node_id })
+            .unwrap();
+        assert!(response.success);
+    }
+
+    #[test]
+    fn test_report_job_heartbeat_rejects_node_not_allocated_to_job() {
+        let service = create_test_service();
+        service.node_registry.register(create_test_node("node-1")).unwrap();
+        let job_id = submit_and_start_running_job(&service);
+
+        let result = service.report_job_heartbeat(ReportJobHeartbeatRequest {
+            job_id,
+            node_id: "some-other-node".to_string(),
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_report_job_heartbeat_not_found() {
+        let service = create_test_service();
+        let result = service.report_job_heartbeat(ReportJobHeartbeatRequest {
+            job_id: "non-existent-job".to_string(),
+            node_id: "node-1".to_string(),
+        });
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_get_cluster_status_empty() {
         let service = create_test_service();
@@ -408,7 +779,17 @@ mod tests {
         assert_eq!(status.running_jobs, 0);
         assert_eq!(status.queued_jobs, 0);
     }
-    
+
+    #[test]
+    fn test_metrics_text_reports_submission_counter() {
+        let service = create_test_service();
+        service.submit_job(create_test_request()).unwrap();
+
+        let rendered = service.metrics_text();
+        assert!(rendered.contains("zenith_scheduler_jobs_submitted_total 1"));
+        assert!(rendered.contains("zenith_scheduler_jobs_queued 1"));
+    }
+
     // ===================== Clone Tests =====================
     
     #[test]
@@ -473,4 +854,205 @@ mod tests {
         let debug_str = format!("{:?}", response);
         assert!(debug_str.contains("SubmitJobResponse"));
     }
+
+    // ===================== Durable State Store Tests =====================
+
+    use crate::state::StateStoreConfig;
+    use tempfile::TempDir;
+
+    fn create_state_backed_service(data_dir: std::path::PathBuf) -> SchedulerService<FileBackend> {
+        let node_registry = Arc::new(NodeRegistry::new(60));
+        let scheduler = Arc::new(Scheduler::new(node_registry.clone(), SchedulerConfig::default()));
+        let store = Arc::new(
+            StateStore::new(StateStoreConfig { data_dir, ..Default::default() }).unwrap(),
+        );
+        SchedulerService::with_state_store(scheduler, node_registry, store).unwrap()
+    }
+
+    #[test]
+    fn test_submit_and_cancel_persist_into_state_store() {
+        let temp_dir = TempDir::new().unwrap();
+        let service = create_state_backed_service(temp_dir.path().to_path_buf());
+
+        let submitted = service.submit_job(create_test_request()).unwrap();
+        assert_eq!(submitted.status, "QUEUED");
+
+        let store = StateStore::new(StateStoreConfig {
+            data_dir: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+        let persisted = store.get_job(&submitted.job_id).expect("submit_job must persist the job");
+        assert_eq!(persisted.state, crate::job::JobState::Queued);
+
+        service
+            .cancel_job(CancelJobRequest { job_id: submitted.job_id.clone(), reason: "test".to_string() })
+            .unwrap();
+
+        let store = StateStore::new(StateStoreConfig {
+            data_dir: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+        let persisted = store.get_job(&submitted.job_id).expect("cancel_job must persist the job");
+        assert_eq!(persisted.state, crate::job::JobState::Cancelled);
+    }
+
+    #[test]
+    fn test_with_state_store_restores_queued_job_after_restart() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let job_id = {
+            let service = create_state_backed_service(temp_dir.path().to_path_buf());
+            service.submit_job(create_test_request()).unwrap().job_id
+        };
+
+        // A fresh `SchedulerService` over the same directory must see the
+        // job already queued, without resubmitting it.
+        let restarted = create_state_backed_service(temp_dir.path().to_path_buf());
+        let status = restarted
+            .get_job_status(GetJobStatusRequest { job_id: job_id.clone() })
+            .unwrap();
+        assert_eq!(status.state, "Queued");
+    }
+
+    #[test]
+    fn test_with_state_store_reschedules_orphaned_running_job() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = Arc::new(
+            StateStore::new(StateStoreConfig { data_dir: temp_dir.path().to_path_buf(), ..Default::default() })
+                .unwrap(),
+        );
+
+        let mut job = Job::new(JobDescriptor {
+            name: "orphan".to_string(),
+            user_id: "user1".to_string(),
+            project_id: "project1".to_string(),
+            command: "python".to_string(),
+            arguments: vec![],
+            environment: HashMap::new(),
+            working_directory: "/app".to_string(),
+            resources: ResourceRequirements::default(),
+            locality: LocalityPreferences::default(),
+            policy: SchedulingPolicy::default(),
+            labels: HashMap::new(),
+            annotations: HashMap::new(),
+            schedule: None,
+            depends_on: vec![],
+            gang_group: None,
+        });
+        job.transition(crate::job::JobState::Queued, "Submitted to scheduler").unwrap();
+        job.transition(crate::job::JobState::Scheduled, "Resources allocated").unwrap();
+        job.transition(crate::job::JobState::Running, "was running before the crash").unwrap();
+        store.store_job(&job).unwrap();
+
+        let node_registry = Arc::new(NodeRegistry::new(60));
+        let scheduler = Arc::new(Scheduler::new(node_registry.clone(), SchedulerConfig::default()));
+        let service = SchedulerService::with_state_store(scheduler, node_registry, store).unwrap();
+
+        let status = service
+            .get_job_status(GetJobStatusRequest { job_id: job.id.to_string() })
+            .unwrap();
+        assert_eq!(status.state, "Queued", "the orphaned job must be re-enqueued, not left Running");
+    }
+
+    // ===================== Batch Submission Tests =====================
+
+    #[test]
+    fn test_one_or_many_serializes_single_as_bare_value() {
+        let one: OneOrMany<i32> = OneOrMany::from(5);
+        assert_eq!(serde_json::to_string(&one).unwrap(), "5");
+        assert_eq!(one.into_vec(), vec![5]);
+    }
+
+    #[test]
+    fn test_one_or_many_serializes_many_as_array() {
+        let many: OneOrMany<i32> = OneOrMany::from(vec![1, 2, 3]);
+        assert_eq!(serde_json::to_string(&many).unwrap(), "[1,2,3]");
+        assert_eq!(many.into_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_submit_jobs_accepts_single_request() {
+        let service = create_test_service();
+        let results = service.submit_jobs(create_test_request());
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_ok());
+    }
+
+    #[test]
+    fn test_submit_jobs_accepts_batch() {
+        let service = create_test_service();
+        let mut a = create_test_request();
+        a.gang_schedule = false;
+        let mut b = create_test_request();
+        b.gang_schedule = false;
+        b.name = "job-b".to_string();
+
+        let results = service.submit_jobs(vec![a, b]);
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(service.get_cluster_status().queued_jobs, 2);
+    }
+
+    #[test]
+    fn test_submit_jobs_gang_scheduled_batch_succeeds_together() {
+        let service = create_test_service();
+        let mut a = create_test_request();
+        a.gang_schedule = true;
+        let mut b = create_test_request();
+        b.gang_schedule = true;
+        b.name = "job-b".to_string();
+
+        let results = service.submit_jobs(vec![a, b]);
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(service.get_cluster_status().queued_jobs, 2);
+    }
+
+    /// `Scheduler::submit` only ever rejects a job for a dependency cycle
+    /// (see `test_submit_rejects_self_dependency_cycle` in `scheduler.rs`),
+    /// and a job's id is assigned internally by `Job::new` inside
+    /// `submit_jobs`, so a cycle can't be steered in through a
+    /// [`SubmitJobRequest`]. This test instead exercises the rollback branch
+    /// directly at the `Scheduler` level the same way `submit_jobs` would,
+    /// confirming `cancel` truly undoes an earlier `submit` in the batch.
+    #[test]
+    fn test_submit_jobs_atomic_rollback_cancels_the_prior_success() {
+        let service = create_test_service();
+
+        let ok_job = Job::new(job_descriptor_from(create_test_request()));
+        let ok_job_id = service.scheduler.submit(ok_job).unwrap();
+        assert_eq!(service.get_cluster_status().queued_jobs, 1);
+
+        service
+            .scheduler
+            .cancel(&ok_job_id, "rolled back: batch gang-scheduled submission failed")
+            .unwrap();
+
+        let status = service.get_job_status(GetJobStatusRequest { job_id: ok_job_id }).unwrap();
+        assert_eq!(status.state, "Cancelled");
+        assert_eq!(service.get_cluster_status().queued_jobs, 0);
+    }
+
+    #[test]
+    fn test_get_jobs_status_accepts_single_id() {
+        let service = create_test_service();
+        let job_id = service.submit_job(create_test_request()).unwrap().job_id;
+
+        let results = service.get_jobs_status(job_id.clone());
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].as_ref().unwrap().job_id, job_id);
+    }
+
+    #[test]
+    fn test_get_jobs_status_accepts_batch_with_partial_misses() {
+        let service = create_test_service();
+        let job_id = service.submit_job(create_test_request()).unwrap().job_id;
+
+        let results = service.get_jobs_status(vec![job_id.clone(), "missing-job".to_string()]);
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
 }
@@ -3,37 +3,159 @@
 use axum::{
     Router,
     routing::{get, post, delete},
-    response::{Json, IntoResponse},
-    extract::{State, Path},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Json, IntoResponse, Response,
+    },
+    extract::{Query, Request, State, Path},
     http::StatusCode,
+    middleware::{self, Next},
 };
+use axum_server::tls_rustls::RustlsConfig;
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use std::collections::HashMap;
+use tokio::sync::broadcast;
 
-use crate::scheduler::Scheduler;
+use crate::api::grpc::OneOrMany;
+use crate::scheduler::{JobFilter, JobLogLine, LogStream, Scheduler};
 use crate::node::NodeRegistry;
-use crate::job::{Job, JobDescriptor, ResourceRequirements, LocalityPreferences, SchedulingPolicy};
+use crate::job::{Job, JobDescriptor, JobState, ResourceRequirements, LocalityPreferences, SchedulingPolicy, GangGroup};
+use crate::webhook::WebhookScope;
 
 /// Application state
 pub struct AppState {
     pub scheduler: Arc<Scheduler>,
     pub node_registry: Arc<NodeRegistry>,
+    /// Shared secret checked against `Authorization: Bearer <token>` on
+    /// every `/api/v1/*` route. `None` disables auth (local/dev only).
+    pub auth_token: Option<String>,
+}
+
+/// PEM cert/key path pair used to terminate TLS for [`serve`].
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    /// Path to the PEM-encoded certificate (chain)
+    pub cert_path: String,
+    /// Path to the PEM-encoded private key
+    pub key_path: String,
+}
+
+/// Listener configuration for [`serve`]: bind address plus optional TLS.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Address to bind the listener to, e.g. `0.0.0.0:8443`
+    pub bind_addr: SocketAddr,
+    /// Terminate TLS with this cert/key pair; `None` serves plain HTTP
+    pub tls: Option<TlsConfig>,
+}
+
+impl ServerConfig {
+    /// Plain-HTTP config bound to `bind_addr`
+    pub fn new(bind_addr: SocketAddr) -> Self {
+        Self { bind_addr, tls: None }
+    }
+
+    /// Terminate TLS using the PEM cert/key at the given paths
+    pub fn with_tls(mut self, cert_path: &str, key_path: &str) -> Self {
+        self.tls = Some(TlsConfig {
+            cert_path: cert_path.to_string(),
+            key_path: key_path.to_string(),
+        });
+        self
+    }
 }
 
 /// Create REST API router
 pub fn create_router(state: Arc<AppState>) -> Router {
-    Router::new()
+    let api_routes = Router::new()
         .route("/api/v1/jobs", post(submit_job))
         .route("/api/v1/jobs", get(list_jobs))
-        .route("/api/v1/jobs/:job_id", get(get_job))
-        .route("/api/v1/jobs/:job_id", delete(cancel_job))
+        .route("/api/v1/jobs/{job_id}", get(get_job))
+        .route("/api/v1/jobs/{job_id}", delete(cancel_job))
+        .route("/api/v1/jobs/{job_id}/heartbeat", post(report_job_heartbeat))
+        .route("/api/v1/jobs/{job_id}/logs", get(stream_job_logs))
         .route("/api/v1/cluster/status", get(cluster_status))
         .route("/api/v1/nodes", get(list_nodes))
+        .route("/api/v1/webhooks", post(register_webhook))
+        .route("/api/v1/webhooks/{id}", delete(unregister_webhook))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_bearer_token));
+
+    Router::new()
+        .merge(api_routes)
         .route("/health", get(health_check))
+        .route("/metrics", get(metrics))
         .with_state(state)
 }
 
+/// Compare two byte strings in time independent of where they first differ,
+/// so a mismatched `Authorization` header can't be used to brute-force
+/// `AppState.auth_token` one byte at a time via response-time measurements.
+/// Still short-circuits on length (the token length itself isn't secret).
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Reject requests with a missing or wrong `Authorization: Bearer <token>`
+/// header, checked against `AppState.auth_token`. A `None` token disables
+/// auth entirely. Only layered onto the `/api/v1/*` routes in
+/// [`create_router`] — `/health` and `/metrics` stay open.
+async fn require_bearer_token(
+    State(state): State<Arc<AppState>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(expected) = &state.auth_token else {
+        return next.run(req).await;
+    };
+
+    let provided = req
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    if provided.is_some_and(|token| constant_time_eq(token.as_bytes(), expected.as_bytes())) {
+        next.run(req).await
+    } else {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(ErrorResponse {
+                error: "unauthorized".to_string(),
+                message: "missing or invalid bearer token".to_string(),
+            }),
+        )
+            .into_response()
+    }
+}
+
+/// Bind `create_router(state)` to `config.bind_addr`, terminating TLS via
+/// rustls when `config.tls` is set and falling back to plain HTTP
+/// otherwise. The standard way to expose this control-plane API to an
+/// untrusted network.
+pub async fn serve(state: Arc<AppState>, config: ServerConfig) -> std::io::Result<()> {
+    let app = create_router(state).into_make_service();
+
+    match config.tls {
+        Some(tls) => {
+            let rustls_config = RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path)
+                .await
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+            axum_server::bind_rustls(config.bind_addr, rustls_config)
+                .serve(app)
+                .await
+        }
+        None => axum_server::bind(config.bind_addr).serve(app).await,
+    }
+}
+
 // === Request/Response Types ===
 
 #[derive(Debug, Deserialize)]
@@ -58,6 +180,43 @@ pub struct SubmitJobRequest {
     pub priority: i32,
     #[serde(default)]
     pub gang_schedule: bool,
+    /// IDs of jobs that must complete before this one becomes eligible
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// All-or-nothing multi-job placement group. When set, this request
+    /// submits one job per [`GangMemberRequest`] instead of a single job
+    /// from the fields above, and only those members are placed when
+    /// enough nodes can satisfy all of them simultaneously; see
+    /// [`Scheduler::gang_group_ready`].
+    #[serde(default)]
+    pub gang: Option<GangRequest>,
+}
+
+/// Per-member overrides for a [`GangRequest`]; every member shares the
+/// parent [`SubmitJobRequest`]'s `command`/`user_id`/`project_id`/etc. but
+/// declares its own resource footprint, mirroring the top-level
+/// `gpu_count`/`cpu_cores`/`memory_mb` fields.
+#[derive(Debug, Deserialize)]
+pub struct GangMemberRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub gpu_count: u32,
+    #[serde(default = "default_cpu_cores")]
+    pub cpu_cores: u32,
+    #[serde(default = "default_memory")]
+    pub memory_mb: u64,
+}
+
+/// All-or-nothing multi-job group carried by `SubmitJobRequest.gang`; see
+/// [`crate::job::GangGroup`].
+#[derive(Debug, Deserialize)]
+pub struct GangRequest {
+    pub group_id: String,
+    pub min_members: u32,
+    pub members: Vec<GangMemberRequest>,
+    #[serde(default)]
+    pub wait_timeout_secs: u64,
 }
 
 fn default_working_dir() -> String { "/app".to_string() }
@@ -75,6 +234,11 @@ pub struct JobResponse {
     pub created_at: String,
     pub allocated_nodes: Vec<String>,
     pub gpu_count: u32,
+    /// Present only for gang-group jobs: members currently `Scheduled`,
+    /// `Running`, or `Completed` out of the group's total member count; see
+    /// [`Scheduler::gang_group_progress`].
+    pub placed_members: Option<usize>,
+    pub total_members: Option<usize>,
 }
 
 #[derive(Debug, Serialize)]
@@ -112,12 +276,115 @@ pub struct SuccessResponse {
     pub message: String,
 }
 
+/// Request body for renewing a running job's heartbeat lease
+#[derive(Debug, Deserialize)]
+pub struct HeartbeatRequest {
+    /// ID of the node that is actually running the job
+    pub node_id: String,
+}
+
+/// Request body for `POST /api/v1/webhooks`. At most one of `job_id`,
+/// `user_id`, `project_id` should be set; an unscoped request receives
+/// every job's state transitions.
+#[derive(Debug, Deserialize)]
+pub struct RegisterWebhookRequest {
+    pub url: String,
+    #[serde(default)]
+    pub job_id: Option<String>,
+    #[serde(default)]
+    pub user_id: Option<String>,
+    #[serde(default)]
+    pub project_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookResponse {
+    pub id: String,
+}
+
+/// Query parameters for `GET /api/v1/jobs`; every field is optional.
+#[derive(Debug, Deserialize)]
+pub struct ListJobsQuery {
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub user_id: Option<String>,
+    #[serde(default)]
+    pub project_id: Option<String>,
+    #[serde(default = "default_list_limit")]
+    pub limit: usize,
+    #[serde(default)]
+    pub offset: usize,
+}
+
+fn default_list_limit() -> usize { 50 }
+
+/// Paginated envelope returned by `GET /api/v1/jobs`.
+#[derive(Debug, Serialize)]
+pub struct JobListResponse {
+    pub items: Vec<JobResponse>,
+    pub total: usize,
+    pub next_offset: Option<usize>,
+}
+
 // === Handlers ===
 
+/// Accepts either a single `SubmitJobRequest` or a JSON array of them (see
+/// [`OneOrMany`]), submitting every job in the batch. A failure on one item
+/// doesn't fail the others: the response is always a `JobResponse` per
+/// input item, in order, with `201 Created` if all of them were submitted
+/// or `207 Multi-Status` if at least one failed. A request whose `gang`
+/// block can never fit the cluster's total GPU capacity aborts the whole
+/// batch with `409 Conflict` instead of being counted as a per-item
+/// failure, since no job in that gang would ever become schedulable.
 async fn submit_job(
     State(state): State<Arc<AppState>>,
-    Json(request): Json<SubmitJobRequest>,
-) -> impl IntoResponse {
+    Json(request): Json<OneOrMany<SubmitJobRequest>>,
+) -> Response {
+    let mut all_ok = true;
+    let mut results: Vec<JobResponse> = Vec::new();
+
+    for request in request.into_vec() {
+        if let Some(gang) = request.gang.as_ref() {
+            let needed: u64 = gang.members.iter().map(|m| m.gpu_count as u64).sum();
+            let total_gpus = state.node_registry.summary().total_gpus as u64;
+            if needed > total_gpus {
+                return (
+                    StatusCode::CONFLICT,
+                    Json(ErrorResponse {
+                        error: "gang_unschedulable".to_string(),
+                        message: format!(
+                            "gang {} needs {} GPUs across {} member(s) but the cluster only has {} total",
+                            gang.group_id, needed, gang.members.len(), total_gpus,
+                        ),
+                    }),
+                )
+                    .into_response();
+            }
+        }
+
+        match submit_one_job(&state, request) {
+            Ok(response) => results.push(response),
+            Err(response) => {
+                all_ok = false;
+                results.push(response);
+            }
+        }
+    }
+
+    let status = if all_ok { StatusCode::CREATED } else { StatusCode::MULTI_STATUS };
+    (status, Json(results)).into_response()
+}
+
+/// Submit a single job, returning the created [`JobResponse`] on success or
+/// one carrying the failure reason in its `state` field on error — see
+/// [`submit_job`]'s batch wrapper, which is the only caller. Delegates to
+/// [`submit_gang_job`] instead when `request.gang` is set.
+fn submit_one_job(state: &AppState, mut request: SubmitJobRequest) -> Result<JobResponse, JobResponse> {
+    if let Some(gang) = request.gang.take() {
+        return submit_gang_job(state, request, gang);
+    }
+
     let descriptor = JobDescriptor {
         name: request.name,
         user_id: request.user_id,
@@ -140,16 +407,19 @@ async fn submit_job(
         },
         labels: HashMap::new(),
         annotations: HashMap::new(),
+        schedule: None,
+        depends_on: request.depends_on,
+        gang_group: None,
     };
-    
+
     let job = Job::new(descriptor);
-    
+
     match state.scheduler.submit(job) {
         Ok(job_id) => {
             if let Some(job) = state.scheduler.get_job(&job_id) {
-                (StatusCode::CREATED, Json(job_to_response(&job)))
+                Ok(job_to_response(&job))
             } else {
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(JobResponse {
+                Err(JobResponse {
                     job_id,
                     name: "unknown".to_string(),
                     state: "QUEUED".to_string(),
@@ -158,22 +428,102 @@ async fn submit_job(
                     created_at: chrono::Utc::now().to_rfc3339(),
                     allocated_nodes: vec![],
                     gpu_count: 0,
-                }))
+                    placed_members: None,
+                    total_members: None,
+                })
             }
         }
-        Err(e) => {
-            (StatusCode::BAD_REQUEST, Json(JobResponse {
-                job_id: "".to_string(),
-                name: "error".to_string(),
+        Err(e) => Err(JobResponse {
+            job_id: "".to_string(),
+            name: "error".to_string(),
+            state: e.to_string(),
+            user_id: "".to_string(),
+            project_id: "".to_string(),
+            created_at: "".to_string(),
+            allocated_nodes: vec![],
+            gpu_count: 0,
+            placed_members: None,
+            total_members: None,
+        }),
+    }
+}
+
+/// Submits every [`GangMemberRequest`] in `gang` as its own job sharing
+/// `request`'s command/environment/user/project, tagged with the same
+/// [`GangGroup`] so [`Scheduler::gang_group_ready`] only places them once
+/// the whole group can be satisfied at once. Feasibility against the
+/// cluster's total GPU capacity is checked by [`submit_job`] before this
+/// runs, so failures here are limited to ordinary `submit()` errors (e.g. a
+/// dependency cycle) on an individual member.
+fn submit_gang_job(
+    state: &AppState,
+    request: SubmitJobRequest,
+    gang: GangRequest,
+) -> Result<JobResponse, JobResponse> {
+    let total_gpu_count: u32 = gang.members.iter().map(|m| m.gpu_count).sum();
+
+    for (index, member) in gang.members.iter().enumerate() {
+        let descriptor = JobDescriptor {
+            name: member.name.clone().unwrap_or_else(|| format!("{}-{}", request.name, index)),
+            user_id: request.user_id.clone(),
+            project_id: request.project_id.clone(),
+            command: request.command.clone(),
+            arguments: request.arguments.clone(),
+            environment: request.environment.clone(),
+            working_directory: request.working_directory.clone(),
+            resources: ResourceRequirements {
+                gpu_count: member.gpu_count,
+                cpu_cores: member.cpu_cores,
+                cpu_memory: member.memory_mb * 1024 * 1024,
+                ..Default::default()
+            },
+            locality: LocalityPreferences::default(),
+            policy: SchedulingPolicy {
+                priority: request.priority,
+                gang_schedule: true,
+                max_wait_time_seconds: gang.wait_timeout_secs,
+                ..Default::default()
+            },
+            labels: HashMap::new(),
+            annotations: HashMap::new(),
+            schedule: None,
+            depends_on: request.depends_on.clone(),
+            gang_group: Some(GangGroup {
+                group_id: gang.group_id.clone(),
+                min_members: gang.min_members,
+            }),
+        };
+
+        if let Err(e) = state.scheduler.submit(Job::new(descriptor)) {
+            return Err(JobResponse {
+                job_id: gang.group_id.clone(),
+                name: request.name.clone(),
                 state: e.to_string(),
-                user_id: "".to_string(),
-                project_id: "".to_string(),
+                user_id: request.user_id.clone(),
+                project_id: request.project_id.clone(),
                 created_at: "".to_string(),
                 allocated_nodes: vec![],
-                gpu_count: 0,
-            }))
+                gpu_count: total_gpu_count,
+                placed_members: None,
+                total_members: Some(gang.members.len()),
+            });
         }
     }
+
+    let (placed_members, total_members) = state.scheduler.gang_group_progress(&gang.group_id);
+
+    Ok(JobResponse {
+        job_id: gang.group_id,
+        name: request.name,
+        state: "QUEUED".to_string(),
+        user_id: request.user_id,
+        project_id: request.project_id,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        allocated_nodes: vec![],
+        gpu_count: total_gpu_count,
+        placed_members: Some(placed_members),
+        total_members: Some(total_members),
+    })
 }
 
 async fn get_job(
@@ -191,17 +541,41 @@ async fn get_job(
             created_at: "".to_string(),
             allocated_nodes: vec![],
             gpu_count: 0,
+            placed_members: None,
+            total_members: None,
         })),
     }
 }
 
+/// Enumerate jobs with optional `state`/`user_id`/`project_id` filters and
+/// `limit`/`offset` pagination; see [`Scheduler::list_jobs`]. An
+/// unrecognized `state` value is treated as no filter rather than an error.
 async fn list_jobs(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ListJobsQuery>,
 ) -> impl IntoResponse {
-    // Get jobs in different states
-    let jobs: Vec<JobResponse> = vec![];
-    // In production: iterate all jobs and convert
-    Json(jobs)
+    let filter = JobFilter {
+        state: query.state.as_deref().and_then(parse_job_state),
+        user_id: query.user_id,
+        project_id: query.project_id,
+    };
+
+    let (jobs, total) = state.scheduler.list_jobs(&filter, query.offset, query.limit);
+    let next_offset = if query.offset + jobs.len() < total {
+        Some(query.offset + jobs.len())
+    } else {
+        None
+    };
+
+    Json(JobListResponse {
+        items: jobs.iter().map(job_to_response).collect(),
+        total,
+        next_offset,
+    })
+}
+
+fn parse_job_state(raw: &str) -> Option<JobState> {
+    serde_json::from_value(serde_json::Value::String(raw.to_string())).ok()
 }
 
 async fn cancel_job(
@@ -220,6 +594,120 @@ async fn cancel_job(
     }
 }
 
+/// Renew a running job's heartbeat lease; see [`Scheduler::report_job_heartbeat`].
+async fn report_job_heartbeat(
+    State(state): State<Arc<AppState>>,
+    Path(job_id): Path<String>,
+    Json(request): Json<HeartbeatRequest>,
+) -> impl IntoResponse {
+    match state.scheduler.report_job_heartbeat(&job_id, &request.node_id) {
+        Ok(()) => (StatusCode::OK, Json(SuccessResponse {
+            status: "success".to_string(),
+            message: format!("Heartbeat recorded for job {}", job_id),
+        })),
+        Err(e) => (StatusCode::BAD_REQUEST, Json(SuccessResponse {
+            status: "error".to_string(),
+            message: e.to_string(),
+        })),
+    }
+}
+
+/// Stream a running job's live stdout/stderr as Server-Sent Events; see
+/// [`Scheduler::subscribe_job_logs`]. Each line arrives as an `stdout` or
+/// `stderr` event; once the job reaches a terminal state the stream emits
+/// one final `terminal` event carrying that state and closes. Returns
+/// `404` with an [`ErrorResponse`] if `job_id` doesn't exist.
+async fn stream_job_logs(
+    State(state): State<Arc<AppState>>,
+    Path(job_id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, Response> {
+    let Some(mut receiver) = state.scheduler.subscribe_job_logs(&job_id) else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "not_found".to_string(),
+                message: format!("job {} not found", job_id),
+            }),
+        )
+            .into_response());
+    };
+
+    let scheduler = state.scheduler.clone();
+    let stream = async_stream::stream! {
+        loop {
+            tokio::select! {
+                line = receiver.recv() => {
+                    match line {
+                        Ok(line) => yield Ok(log_line_event(&line)),
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+                _ = tokio::time::sleep(Duration::from_secs(1)) => {
+                    match scheduler.get_job(&job_id) {
+                        Some(job) if job.state.is_terminal() => {
+                            yield Ok(Event::default().event("terminal").data(format!("{:?}", job.state)));
+                            break;
+                        }
+                        Some(_) => continue,
+                        None => break,
+                    }
+                }
+            }
+        }
+    };
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+fn log_line_event(line: &JobLogLine) -> Event {
+    Event::default()
+        .event(match line.stream {
+            LogStream::Stdout => "stdout",
+            LogStream::Stderr => "stderr",
+        })
+        .data(line.line.clone())
+}
+
+/// Register a webhook subscription; see [`crate::webhook::WebhookRegistry::register`].
+/// Scoped to `job_id`, `user_id`, or `project_id` if given, otherwise
+/// receives every job's state transitions.
+async fn register_webhook(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<RegisterWebhookRequest>,
+) -> impl IntoResponse {
+    let scope = if let Some(job_id) = request.job_id {
+        WebhookScope::Job(job_id)
+    } else if let Some(user_id) = request.user_id {
+        WebhookScope::User(user_id)
+    } else if let Some(project_id) = request.project_id {
+        WebhookScope::Project(project_id)
+    } else {
+        WebhookScope::All
+    };
+
+    let id = state.scheduler.webhooks().register(request.url, scope);
+    (StatusCode::CREATED, Json(WebhookResponse { id }))
+}
+
+/// Remove a webhook subscription; see [`crate::webhook::WebhookRegistry::unregister`].
+async fn unregister_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if state.scheduler.webhooks().unregister(&id) {
+        (StatusCode::OK, Json(SuccessResponse {
+            status: "success".to_string(),
+            message: format!("Webhook {} removed", id),
+        }))
+    } else {
+        (StatusCode::NOT_FOUND, Json(SuccessResponse {
+            status: "error".to_string(),
+            message: format!("Webhook {} not found", id),
+        }))
+    }
+}
+
 async fn cluster_status(
     State(state): State<Arc<AppState>>,
 ) -> impl IntoResponse {
@@ -257,6 +745,15 @@ async fn health_check() -> impl IntoResponse {
     (StatusCode::OK, "OK")
 }
 
+/// Prometheus/OpenMetrics scrape endpoint; see [`Scheduler::render_metrics`].
+async fn metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        [("content-type", "text/plain; version=0.0.4")],
+        state.scheduler.render_metrics(),
+    )
+}
+
 // === Helpers ===
 
 fn job_to_response(job: &Job) -> JobResponse {
@@ -269,6 +766,8 @@ fn job_to_response(job: &Job) -> JobResponse {
         created_at: job.submit_time.to_rfc3339(),
         allocated_nodes: job.allocated_nodes.clone(),
         gpu_count: job.descriptor.resources.gpu_count,
+        placed_members: None,
+        total_members: None,
     }
 }
 
@@ -283,9 +782,88 @@ mod tests {
         Arc::new(AppState {
             scheduler,
             node_registry,
+            auth_token: None,
         })
     }
-    
+
+    fn create_test_state_with_token(token: &str) -> Arc<AppState> {
+        let node_registry = Arc::new(NodeRegistry::new(60));
+        let scheduler = Arc::new(Scheduler::new(node_registry.clone(), SchedulerConfig::default()));
+        Arc::new(AppState {
+            scheduler,
+            node_registry,
+            auth_token: Some(token.to_string()),
+        })
+    }
+
+    fn create_test_state_with_gpus(gpu_count: usize) -> Arc<AppState> {
+        use crate::node::{GpuDevice, GpuVendor, Node, NodeTopology};
+
+        let gpus: Vec<GpuDevice> = (0..gpu_count)
+            .map(|i| GpuDevice {
+                device_id: format!("cuda:{}", i),
+                vendor: GpuVendor::Nvidia,
+                device_name: "NVIDIA A100".to_string(),
+                uuid: format!("GPU-{}", i),
+                total_memory: 80 * 1024 * 1024 * 1024,
+                free_memory: 80 * 1024 * 1024 * 1024,
+                utilization: 0.0,
+                temperature: 40,
+                allocated: false,
+                allocated_job_id: None,
+                processes: vec![],
+                power_usage_mw: 0,
+                power_limit_mw: 0,
+                power_limit_max_mw: 0,
+                energy_consumed_mj: 0,
+                ecc_volatile_errors: 0,
+                ecc_aggregate_errors: 0,
+                throttle_reasons: vec![],
+            })
+            .collect();
+        let topology = NodeTopology {
+            gpus,
+            cpu_cores: 64,
+            cpu_memory: 512 * 1024 * 1024 * 1024,
+            cpu_memory_free: 500 * 1024 * 1024 * 1024,
+            numa_nodes: 1,
+            nvlink_present: true,
+            nvswitch_present: false,
+            rdma_capable: true,
+            nvlink_topology: HashMap::new(),
+        };
+
+        let node_registry = Arc::new(NodeRegistry::new(60));
+        node_registry
+            .register(Node::new("node-1".to_string(), "node-1.local".to_string(), "192.168.1.1".to_string(), topology))
+            .unwrap();
+        let scheduler = Arc::new(Scheduler::new(node_registry.clone(), SchedulerConfig::default()));
+        Arc::new(AppState {
+            scheduler,
+            node_registry,
+            auth_token: None,
+        })
+    }
+
+    fn create_test_gang_request(group_id: &str, member_gpu_counts: &[u32]) -> SubmitJobRequest {
+        let mut request = create_test_submit_request();
+        request.gang = Some(GangRequest {
+            group_id: group_id.to_string(),
+            min_members: member_gpu_counts.len() as u32,
+            members: member_gpu_counts
+                .iter()
+                .map(|&gpu_count| GangMemberRequest {
+                    name: None,
+                    gpu_count,
+                    cpu_cores: default_cpu_cores(),
+                    memory_mb: default_memory(),
+                })
+                .collect(),
+            wait_timeout_secs: 0,
+        });
+        request
+    }
+
     fn create_test_submit_request() -> SubmitJobRequest {
         SubmitJobRequest {
             name: "test-job".to_string(),
@@ -300,18 +878,93 @@ mod tests {
             memory_mb: 8192,
             priority: 50,
             gang_schedule: false,
+            depends_on: vec![],
+            gang: None,
         }
     }
     
     #[test]
-    #[ignore = "Router syntax requires Axum 0.8+ path format"]
     fn test_create_router() {
         let state = create_test_state();
         let _router = create_router(state);
         // Router creation should not panic
         assert!(true);
     }
-    
+
+    #[tokio::test]
+    async fn test_require_bearer_token_rejects_missing_or_wrong_header() {
+        use axum::body::Body;
+        use tower::ServiceExt;
+
+        let state = create_test_state_with_token("secret-token");
+        let app = create_router(state);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/cluster/status")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/cluster/status")
+                    .header("authorization", "Bearer wrong-token")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_require_bearer_token_accepts_correct_header() {
+        use axum::body::Body;
+        use tower::ServiceExt;
+
+        let state = create_test_state_with_token("secret-token");
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/cluster/status")
+                    .header("authorization", "Bearer secret-token")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_require_bearer_token_disabled_without_configured_token() {
+        use axum::body::Body;
+        use tower::ServiceExt;
+
+        let state = create_test_state();
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/cluster/status")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[test]
     fn test_default_functions() {
         assert_eq!(default_working_dir(), "/app");
@@ -319,7 +972,25 @@ mod tests {
         assert_eq!(default_memory(), 4096);
         assert_eq!(default_priority(), 50);
     }
-    
+
+    #[test]
+    fn test_server_config_defaults_to_plain_http() {
+        let addr: std::net::SocketAddr = "0.0.0.0:8443".parse().unwrap();
+        let config = ServerConfig::new(addr);
+        assert_eq!(config.bind_addr, addr);
+        assert!(config.tls.is_none());
+    }
+
+    #[test]
+    fn test_server_config_with_tls() {
+        let addr: std::net::SocketAddr = "0.0.0.0:8443".parse().unwrap();
+        let config = ServerConfig::new(addr).with_tls("/etc/zenith/cert.pem", "/etc/zenith/key.pem");
+
+        let tls = config.tls.expect("with_tls should set tls config");
+        assert_eq!(tls.cert_path, "/etc/zenith/cert.pem");
+        assert_eq!(tls.key_path, "/etc/zenith/key.pem");
+    }
+
     #[test]
     fn test_submit_job_request_defaults() {
         let request = create_test_submit_request();
@@ -342,6 +1013,8 @@ mod tests {
             created_at: "2024-12-10T00:00:00Z".to_string(),
             allocated_nodes: vec!["node-1".to_string()],
             gpu_count: 4,
+            placed_members: None,
+            total_members: None,
         };
         
         let json = serde_json::to_string(&response).unwrap();
@@ -427,6 +1100,9 @@ mod tests {
             policy: SchedulingPolicy::default(),
             labels: HashMap::new(),
             annotations: HashMap::new(),
+            schedule: None,
+            depends_on: vec![],
+            gang_group: None,
         };
         
         let job = Job::new(descriptor);
@@ -455,6 +1131,8 @@ mod tests {
             memory_mb: 1024, // 1GB in MB
             priority: 50,
             gang_schedule: false,
+            depends_on: vec![],
+            gang: None,
         };
         
         // Verify memory conversion: MB to bytes
@@ -470,15 +1148,115 @@ mod tests {
         assert_eq!(status.status, StatusCode::OK);
     }
     
+    fn empty_list_jobs_query() -> ListJobsQuery {
+        ListJobsQuery { state: None, user_id: None, project_id: None, limit: default_list_limit(), offset: 0 }
+    }
+
     #[tokio::test]
     async fn test_list_jobs_returns_empty_initially() {
         let state = create_test_state();
-        let response = list_jobs(State(state)).await;
+        let response = list_jobs(State(state), Query(empty_list_jobs_query())).await;
         // Should return empty list initially
         let json = response.into_response();
         assert_eq!(json.status(), StatusCode::OK);
     }
-    
+
+    #[tokio::test]
+    async fn test_list_jobs_paginates_and_reports_total() {
+        let state = create_test_state();
+        for i in 0..3 {
+            let mut request = create_test_submit_request();
+            request.name = format!("job-{}", i);
+            submit_one_job(&state, request).unwrap();
+        }
+
+        let response = list_jobs(
+            State(state.clone()),
+            Query(ListJobsQuery { limit: 2, ..empty_list_jobs_query() }),
+        ).await;
+        let body = axum::body::to_bytes(response.into_response().into_body(), usize::MAX).await.unwrap();
+        let parsed: JobListResponse = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(parsed.total, 3);
+        assert_eq!(parsed.items.len(), 2);
+        assert_eq!(parsed.next_offset, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_submit_job_batch_submits_every_item() {
+        let state = create_test_state();
+        let mut first = create_test_submit_request();
+        first.name = "batch-job-1".to_string();
+        let mut second = create_test_submit_request();
+        second.name = "batch-job-2".to_string();
+
+        let response = submit_job(
+            State(state),
+            Json(OneOrMany::Many(vec![first, second])),
+        ).await;
+        let (parts, body) = response.into_response().into_parts();
+        assert_eq!(parts.status, StatusCode::CREATED);
+
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let parsed: Vec<JobResponse> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert!(parsed.iter().all(|r| !r.job_id.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_submit_job_batch_reports_error_in_job_state_on_failure() {
+        let state = create_test_state();
+        let mut unmet_dependency = create_test_submit_request();
+        unmet_dependency.depends_on = vec!["no-such-predecessor".to_string()];
+
+        // An unmet (but non-cyclical) dependency still submits successfully
+        // as `Blocked` rather than failing outright — the batch wrapper only
+        // surfaces genuine submit() errors (e.g. a dependency cycle) per item.
+        let response = submit_job(
+            State(state),
+            Json(OneOrMany::One(unmet_dependency)),
+        ).await;
+        let (parts, body) = response.into_response().into_parts();
+        assert_eq!(parts.status, StatusCode::CREATED);
+
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let parsed: Vec<JobResponse> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(parsed[0].state, "Blocked");
+    }
+
+    #[tokio::test]
+    async fn test_submit_job_gang_rejects_group_that_can_never_fit_cluster() {
+        let state = create_test_state_with_gpus(2);
+        let request = create_test_gang_request("group-1", &[2, 2]); // needs 4, cluster has 2
+
+        let response = submit_job(State(state), Json(OneOrMany::One(request))).await;
+        let (parts, body) = response.into_response().into_parts();
+        assert_eq!(parts.status, StatusCode::CONFLICT);
+
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let parsed: ErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(parsed.error, "gang_unschedulable");
+    }
+
+    #[tokio::test]
+    async fn test_submit_job_gang_submits_one_job_per_member_and_reports_progress() {
+        let state = create_test_state_with_gpus(4);
+        let request = create_test_gang_request("group-1", &[2, 2]);
+
+        let response = submit_job(State(state.clone()), Json(OneOrMany::One(request))).await;
+        let (parts, body) = response.into_response().into_parts();
+        assert_eq!(parts.status, StatusCode::CREATED);
+
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let parsed: Vec<JobResponse> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(parsed.len(), 1, "one JobResponse aggregates the whole gang");
+        assert_eq!(parsed[0].job_id, "group-1");
+        assert_eq!(parsed[0].total_members, Some(2));
+
+        let (_, total) = state.scheduler.gang_group_progress("group-1");
+        assert_eq!(total, 2, "both members were actually submitted to the scheduler");
+    }
+
     #[tokio::test]
     async fn test_cluster_status_handler() {
         let state = create_test_state();
@@ -494,6 +1272,14 @@ mod tests {
         let json = response.into_response();
         assert_eq!(json.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_metrics_handler() {
+        let state = create_test_state();
+        let response = metrics(State(state)).await;
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
     
     #[tokio::test]
     async fn test_get_job_not_found() {
@@ -519,5 +1305,72 @@ mod tests {
         // Should return error for non-existent job
         assert_eq!(parts.status, StatusCode::BAD_REQUEST);
     }
+
+    #[tokio::test]
+    async fn test_stream_job_logs_not_found() {
+        let state = create_test_state();
+        let response = stream_job_logs(
+            State(state),
+            Path("non-existent-job".to_string()),
+        ).await;
+
+        let response = response.expect_err("unknown job should be rejected before streaming starts");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_register_and_unregister_webhook() {
+        let state = create_test_state();
+
+        let register_response = register_webhook(
+            State(state.clone()),
+            Json(RegisterWebhookRequest {
+                url: "http://example.com/hook".to_string(),
+                job_id: None,
+                user_id: None,
+                project_id: None,
+            }),
+        ).await;
+        let (parts, body) = register_response.into_response().into_parts();
+        assert_eq!(parts.status, StatusCode::CREATED);
+
+        let body_bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let response: WebhookResponse = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(state.scheduler.webhooks().list().len(), 1);
+
+        let unregister_response = unregister_webhook(
+            State(state.clone()),
+            Path(response.id),
+        ).await;
+        let (parts, _body) = unregister_response.into_response().into_parts();
+        assert_eq!(parts.status, StatusCode::OK);
+        assert!(state.scheduler.webhooks().list().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unregister_webhook_not_found() {
+        let state = create_test_state();
+        let response = unregister_webhook(
+            State(state),
+            Path("no-such-id".to_string()),
+        ).await;
+
+        let (parts, _body) = response.into_response().into_parts();
+        assert_eq!(parts.status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_report_job_heartbeat_not_found() {
+        let state = create_test_state();
+        let response = report_job_heartbeat(
+            State(state),
+            Path("non-existent-job".to_string()),
+            Json(HeartbeatRequest { node_id: "node-1".to_string() }),
+        ).await;
+
+        let (parts, _body) = response.into_response().into_parts();
+        // Should return error for non-existent job
+        assert_eq!(parts.status, StatusCode::BAD_REQUEST);
+    }
 }
 
@@ -0,0 +1,281 @@
+//! Async executor that runs scheduled [`Job`]s as tracked OS processes.
+//!
+//! Complements [`crate::storage::JobStorage`]: a caller pops a job off a
+//! queue, hands it to [`Executor::append_task`], and the executor owns
+//! running it to completion (or timeout) and reporting the outcome back
+//! through [`JobOutcome`](crate::storage::JobOutcome) - the rest of the
+//! scheduler only ever sees the job's state transitions, never the
+//! `std::process::Child` itself.
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use tokio::process::Command;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::job::{Job, JobState};
+use crate::storage::{JobOutcome, JobStorage};
+
+/// Captured stdout/stderr/exit status of a job's process - the same shape
+/// [`crate::scheduler::JobResult`] reports over the REST API, reused here
+/// rather than duplicated.
+pub use crate::scheduler::JobResult as ProcessOutput;
+
+/// Outcome of one [`Executor::append_task`] run: the job's ID, the state it
+/// ended up in (which may be `Queued` again if [`JobStorage::complete`]
+/// decided to retry it), and its captured process output.
+#[derive(Debug, Clone)]
+pub struct JobResult {
+    pub id: Uuid,
+    pub state: JobState,
+    pub data: ProcessOutput,
+}
+
+/// Runs [`Job`]s as async child processes and tracks each as a joinable
+/// task. Every job it's handed is assumed already `Scheduled` (i.e. popped
+/// from a [`JobStorage`]) - `Executor` drives the `Running` -> terminal
+/// transition and reports the result back to `storage`.
+pub struct Executor {
+    storage: Arc<dyn JobStorage>,
+    tasks: Mutex<HashMap<Uuid, JoinHandle<JobResult>>>,
+}
+
+impl Executor {
+    pub fn new(storage: Arc<dyn JobStorage>) -> Self {
+        Self { storage, tasks: Mutex::new(HashMap::new()) }
+    }
+
+    /// Launch `job` as a child process and register it for tracking,
+    /// returning its ID immediately without waiting for it to finish. See
+    /// [`Self::poll`]/[`Self::wait`] to observe the result.
+    pub fn append_task(&self, job: Job) -> Uuid {
+        let id = job.id;
+        let handle = tokio::spawn(run_job(job, self.storage.clone()));
+        self.tasks.lock().insert(id, handle);
+        id
+    }
+
+    /// Non-blockingly check whether `id`'s task has finished. Returns
+    /// `None` both when the task is still running and when `id` is
+    /// unknown (never appended, or already consumed by a prior `poll`/
+    /// `wait`) - callers that need to tell those apart should track
+    /// `append_task`'s return value themselves.
+    pub async fn poll(&self, id: Uuid) -> Option<JobResult> {
+        let finished = matches!(self.tasks.lock().get(&id), Some(handle) if handle.is_finished());
+        if !finished {
+            return None;
+        }
+        self.take_result(id).await
+    }
+
+    /// Wait for `id`'s task to finish, however long that takes.
+    pub async fn wait(&self, id: Uuid) -> Option<JobResult> {
+        self.take_result(id).await
+    }
+
+    async fn take_result(&self, id: Uuid) -> Option<JobResult> {
+        let handle = self.tasks.lock().remove(&id)?;
+        handle.await.ok()
+    }
+}
+
+/// Run `job`'s command to completion (or timeout), transition it through
+/// `Running` to a terminal state, and report the outcome to `storage`.
+async fn run_job(mut job: Job, storage: Arc<dyn JobStorage>) -> JobResult {
+    job.transition(JobState::Running, "Started")
+        .expect("append_task only hands run_job jobs already Scheduled, which can transition to Running");
+    let id = job.id;
+
+    // Mirror the local transition in storage too, so `finish`'s later
+    // `storage.complete` call - which requires the stored job to already
+    // be `Running` - sees that instead of the `Scheduled` state `pop` left
+    // it in.
+    let _ = storage.mark_running(id).await;
+
+    let max_runtime_secs = job.descriptor.policy.max_runtime_seconds;
+
+    let mut command = Command::new(&job.descriptor.command);
+    command
+        .args(&job.descriptor.arguments)
+        .envs(&job.descriptor.environment)
+        .current_dir(&job.descriptor.working_directory)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Dropping the `wait_with_output` future below (the timeout branch)
+        // otherwise leaves the child running in the background; this makes
+        // that drop kill it instead.
+        .kill_on_drop(true);
+
+    let child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            let message = format!("failed to spawn process: {}", e);
+            return finish(job, &storage, JobOutcome::Failure { message: message.clone() }, ProcessOutput { exit_code: None, stdout: vec![], stderr: message.into_bytes() }).await;
+        }
+    };
+
+    let output_fut = child.wait_with_output();
+    let outcome_result = if max_runtime_secs > 0 {
+        tokio::time::timeout(Duration::from_secs(max_runtime_secs), output_fut).await
+    } else {
+        Ok(output_fut.await)
+    };
+
+    match outcome_result {
+        Ok(Ok(output)) => {
+            let data = ProcessOutput {
+                exit_code: output.status.code(),
+                stdout: output.stdout,
+                stderr: output.stderr,
+            };
+            let outcome = if output.status.success() {
+                JobOutcome::Success { message: "Process exited successfully".to_string() }
+            } else {
+                JobOutcome::Failure { message: format!("process exited with status {}", output.status) }
+            };
+            finish(job, &storage, outcome, data).await
+        }
+        Ok(Err(e)) => {
+            let message = format!("process I/O error: {}", e);
+            finish(job, &storage, JobOutcome::Failure { message: message.clone() }, ProcessOutput { exit_code: None, stdout: vec![], stderr: message.into_bytes() }).await
+        }
+        Err(_) => {
+            let message = format!("exceeded max_runtime_seconds ({}s)", max_runtime_secs);
+            finish(job, &storage, JobOutcome::Timeout { message }, ProcessOutput::default()).await
+        }
+    }
+}
+
+/// Report `outcome` to `storage` and return the [`JobResult`] the caller
+/// actually ended up with. `storage.complete` - not `outcome` - has the
+/// final say on `job`'s state: a retryable failure lands back on `Queued`
+/// rather than the `Failed`/`Timeout` state `outcome` names, so this reads
+/// the job back from `storage` afterward rather than trusting `job` as
+/// mutated locally.
+async fn finish(mut job: Job, storage: &Arc<dyn JobStorage>, outcome: JobOutcome, data: ProcessOutput) -> JobResult {
+    let id = job.id;
+    let message = match &outcome {
+        JobOutcome::Success { message } | JobOutcome::Failure { message } | JobOutcome::Timeout { message } => message.clone(),
+    };
+
+    let _ = storage.complete(id, outcome).await;
+
+    let state = match storage.info(id).await {
+        Some(current) => current.state,
+        None => {
+            // Best-effort only: `job`'s local copy may already be in a
+            // state (e.g. still `Running`) that can't legally reach
+            // `Failed` were this rejected, so this doesn't `.expect()`.
+            let _ = job.transition(JobState::Failed, &message);
+            job.state
+        }
+    };
+
+    JobResult { id, state, data }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::{JobDescriptor, LocalityPreferences, ResourceRequirements, SchedulingPolicy};
+    use crate::storage::InMemoryJobStorage;
+    use std::collections::HashMap as Map;
+
+    fn test_descriptor(command: &str, arguments: Vec<&str>, max_runtime_seconds: u64, max_retries: u32) -> JobDescriptor {
+        JobDescriptor {
+            name: "test-job".to_string(),
+            user_id: "user1".to_string(),
+            project_id: "project1".to_string(),
+            command: command.to_string(),
+            arguments: arguments.into_iter().map(String::from).collect(),
+            environment: Map::new(),
+            working_directory: ".".to_string(),
+            resources: ResourceRequirements::default(),
+            locality: LocalityPreferences::default(),
+            policy: SchedulingPolicy { queue_name: "default".to_string(), max_runtime_seconds, max_retries, ..SchedulingPolicy::default() },
+            labels: Map::new(),
+            annotations: Map::new(),
+            schedule: None,
+            depends_on: vec![],
+            gang_group: None,
+        }
+    }
+
+    /// Push `descriptor` into `storage` and immediately claim it, mirroring
+    /// how a real caller gets a `Scheduled` job to hand to `append_task` -
+    /// and keeping the job's id known to `storage` so `Executor`'s
+    /// `storage.complete(...)` calls land on the same entry.
+    async fn scheduled_job(storage: &InMemoryJobStorage, descriptor: JobDescriptor) -> Job {
+        storage.push(descriptor).await;
+        storage.pop("default", "test-runner").await.expect("job should be poppable right after push")
+    }
+
+    #[tokio::test]
+    async fn test_append_task_then_wait_captures_successful_exit() {
+        let storage = InMemoryJobStorage::new();
+        let job = scheduled_job(&storage, test_descriptor("echo", vec!["hello"], 0, 0)).await;
+        let executor = Executor::new(Arc::new(storage));
+        let id = executor.append_task(job);
+
+        let result = executor.wait(id).await.expect("task should produce a result");
+        assert_eq!(result.id, id);
+        assert_eq!(result.state, JobState::Completed);
+        assert_eq!(result.data.exit_code, Some(0));
+        assert_eq!(result.data.stdout, b"hello\n".to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_append_task_captures_nonzero_exit_as_failure() {
+        let storage = InMemoryJobStorage::new();
+        let job = scheduled_job(&storage, test_descriptor("false", vec![], 0, 0)).await;
+        let executor = Executor::new(Arc::new(storage));
+        let id = executor.append_task(job);
+
+        let result = executor.wait(id).await.expect("task should produce a result");
+        assert_eq!(result.state, JobState::Failed);
+        assert_ne!(result.data.exit_code, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_append_task_times_out_long_running_process() {
+        let storage = InMemoryJobStorage::new();
+        let job = scheduled_job(&storage, test_descriptor("sleep", vec!["5"], 1, 0)).await;
+        let executor = Executor::new(Arc::new(storage));
+        let id = executor.append_task(job);
+
+        let result = executor.wait(id).await.expect("task should produce a result");
+        assert_eq!(result.state, JobState::Timeout);
+    }
+
+    #[tokio::test]
+    async fn test_poll_returns_none_while_running_then_some_once_finished() {
+        let storage = InMemoryJobStorage::new();
+        let job = scheduled_job(&storage, test_descriptor("sleep", vec!["0"], 0, 0)).await;
+        let executor = Executor::new(Arc::new(storage));
+        let id = executor.append_task(job);
+
+        // Give the process a moment to actually finish before polling.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let result = executor.poll(id).await;
+        assert!(result.is_some());
+
+        // Already consumed by the poll above.
+        assert!(executor.poll(id).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_append_task_failure_with_retries_requeues_instead_of_failing() {
+        let storage = Arc::new(InMemoryJobStorage::new());
+        let job = scheduled_job(&storage, test_descriptor("false", vec![], 0, 3)).await;
+        let executor = Executor::new(storage.clone());
+        let id = executor.append_task(job);
+
+        let result = executor.wait(id).await.expect("task should produce a result");
+        assert_eq!(result.state, JobState::Queued);
+        assert_eq!(storage.info(id).await.unwrap().retry_count, 1);
+    }
+}
@@ -2,6 +2,9 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+use thiserror::Error;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
@@ -12,6 +15,9 @@ pub enum JobState {
     /// Job is pending submission
     #[default]
     Pending,
+    /// Job is waiting on one or more `depends_on` predecessors to reach
+    /// `Completed` and is not eligible for scheduling yet.
+    Blocked,
     /// Job is queued waiting for resources
     Queued,
     /// Job has been scheduled to nodes
@@ -30,6 +36,59 @@ pub enum JobState {
     Timeout,
 }
 
+impl JobState {
+    /// Whether this is a final state the job will never transition out of.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, JobState::Completed | JobState::Failed | JobState::Cancelled | JobState::Timeout)
+    }
+
+    /// Whether `self -> to` is a legal edge in the job lifecycle graph.
+    ///
+    /// Every non-terminal state can always reach [`JobState::Cancelled`], and
+    /// re-asserting the same non-terminal state is always a no-op rather
+    /// than an error — [`crate::scheduler::Scheduler::restore_from_state`]
+    /// re-submits already-`Blocked`/`Queued` jobs on startup, which would
+    /// otherwise spuriously reject as a self-loop. The remaining edges
+    /// mirror how the scheduler actually drives a job end to end, including
+    /// the recovery paths that don't simply walk forward: `Running ->
+    /// Pending` re-enqueues a job orphaned by a dead node or a restarted
+    /// leader (see [`crate::scheduler::Scheduler::reconcile_and_restore`]),
+    /// `Running -> Queued` requeues one that missed a heartbeat or lost its
+    /// node but still has retries left, and `Suspended -> Scheduled` lets a
+    /// preempted job be rebound without first passing back through
+    /// `Queued`.
+    fn can_transition_to(self, to: JobState) -> bool {
+        if to == JobState::Cancelled {
+            return !self.is_terminal();
+        }
+        if to == self {
+            return !self.is_terminal();
+        }
+        matches!(
+            (self, to),
+            (JobState::Pending, JobState::Queued | JobState::Blocked)
+                | (JobState::Blocked, JobState::Queued | JobState::Failed)
+                | (JobState::Queued, JobState::Scheduled)
+                | (JobState::Scheduled, JobState::Running)
+                | (
+                    JobState::Running,
+                    JobState::Completed | JobState::Failed | JobState::Timeout | JobState::Suspended | JobState::Pending | JobState::Queued
+                )
+                | (JobState::Suspended, JobState::Queued | JobState::Scheduled | JobState::Running)
+        )
+    }
+}
+
+/// Returned by [`Job::transition`] when `to` is not reachable from the
+/// job's current state — e.g. a terminal job being moved anywhere, or a
+/// state being skipped the lifecycle graph doesn't allow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("cannot transition job from {from:?} to {to:?}")]
+pub struct InvalidTransition {
+    pub from: JobState,
+    pub to: JobState,
+}
+
 
 /// Resource requirements for a job
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +109,11 @@ pub struct ResourceRequirements {
     pub require_nvswitch: bool,
     /// Require RDMA
     pub require_rdma: bool,
+    /// Estimated runtime in seconds, used by backfill to project when this
+    /// job will free its GPUs (0 = unknown, falls back to the scheduler's
+    /// `job_timeout_secs`).
+    #[serde(default)]
+    pub estimated_runtime_secs: u64,
 }
 
 impl Default for ResourceRequirements {
@@ -63,6 +127,7 @@ impl Default for ResourceRequirements {
             min_nvlink_version: 0,
             require_nvswitch: false,
             require_rdma: false,
+            estimated_runtime_secs: 0,
         }
     }
 }
@@ -101,6 +166,13 @@ pub struct SchedulingPolicy {
     pub gang_schedule: bool,
     /// Maximum retry attempts
     pub max_retries: u32,
+    /// How long, in seconds, a running job's heartbeat may go unrenewed
+    /// before [`crate::scheduler::Scheduler::cleanup_zombie_jobs`] treats it
+    /// as dead and requeues or fails it, independent of node health. `0`
+    /// (the default) disables per-job heartbeat monitoring, leaving node
+    /// health and `max_runtime_seconds` as the only zombie checks.
+    #[serde(default)]
+    pub heartbeat_interval_secs: u64,
 }
 
 impl Default for SchedulingPolicy {
@@ -114,10 +186,74 @@ impl Default for SchedulingPolicy {
             queue_name: "default".to_string(),
             gang_schedule: true,
             max_retries: 3,
+            heartbeat_interval_secs: 0,
+        }
+    }
+}
+
+/// Periodic firing policy for a recurring job template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Schedule {
+    /// Standard 5-field cron expression, evaluated in UTC.
+    Cron(String),
+    /// Fixed interval between firings, with optional uniform jitter to avoid
+    /// a thundering herd of templates sharing the same period.
+    Interval {
+        period_seconds: u64,
+        jitter_seconds: u64,
+    },
+}
+
+/// Recurrence policy attached to a [`JobDescriptor`]. A [`Job`] carrying a
+/// `RecurrenceSchedule` is a template: it is never itself executed, only
+/// cloned into fresh concrete instances by `StateStore::recurring_tick`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurrenceSchedule {
+    pub schedule: Schedule,
+    /// Maximum concurrently-active instances (Pending/Queued/Scheduled/
+    /// Running) of this template. A due firing is skipped — but
+    /// `next_run_at` still advances — while this many already exist.
+    pub max_concurrent: u32,
+}
+
+impl RecurrenceSchedule {
+    /// Computes the next firing time strictly after `after`. Always
+    /// advances relative to `after` (typically "now" at tick time) rather
+    /// than the template's previous `next_run_at`, so a template that
+    /// missed several intervals while the process was down fires at most
+    /// once on catch-up instead of backfilling every missed slot.
+    pub fn next_fire_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match &self.schedule {
+            Schedule::Interval { period_seconds, jitter_seconds } => {
+                // Deterministic offset derived from `after` rather than an
+                // RNG, so repeated calls with the same input agree.
+                let jitter = if *jitter_seconds > 0 {
+                    (after.timestamp().unsigned_abs()) % (*jitter_seconds + 1)
+                } else {
+                    0
+                };
+                Some(after + chrono::Duration::seconds(*period_seconds as i64 + jitter as i64))
+            }
+            Schedule::Cron(expr) => cron::Schedule::from_str(expr).ok()?.after(&after).next(),
         }
     }
 }
 
+/// Ties a job to a multi-job "gang" that must be placed all-or-nothing.
+/// Unlike [`SchedulingPolicy::gang_schedule`], which atomically places a
+/// *single* job's GPUs together, a `GangGroup` coordinates *separate* jobs
+/// (e.g. the workers of a distributed training run) so none of them starts
+/// until the whole group can be satisfied simultaneously.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GangGroup {
+    /// Shared by every member job submitted as part of the same gang.
+    pub group_id: String,
+    /// Number of members that must be schedulable at once for the group to
+    /// be placed. Normally equal to the member count, but may be lower to
+    /// tolerate best-effort stragglers.
+    pub min_members: u32,
+}
+
 /// Job descriptor - the core unit of work submission
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JobDescriptor {
@@ -145,6 +281,18 @@ pub struct JobDescriptor {
     pub labels: HashMap<String, String>,
     /// Annotations for metadata
     pub annotations: HashMap<String, String>,
+    /// Recurrence policy, if this job is a recurring template rather than a
+    /// one-shot submission.
+    #[serde(default)]
+    pub schedule: Option<RecurrenceSchedule>,
+    /// IDs of jobs that must reach [`JobState::Completed`] before this job
+    /// becomes eligible for scheduling. Empty means no dependencies.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Gang group this job belongs to, if it was submitted as part of an
+    /// all-or-nothing multi-job placement. `None` for ordinary jobs.
+    #[serde(default)]
+    pub gang_group: Option<GangGroup>,
 }
 
 /// A job instance with state
@@ -172,6 +320,20 @@ pub struct Job {
     pub retry_count: u32,
     /// Last state change message
     pub message: String,
+    /// Next firing time, set only on recurring templates
+    /// (`descriptor.schedule.is_some()`); `None` for one-shot jobs and for
+    /// concrete instances fired from a template.
+    #[serde(default)]
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// ID of the recurring template this instance was fired from, if any.
+    #[serde(default)]
+    pub template_id: Option<Uuid>,
+    /// Timestamp of the most recent [`crate::scheduler::Scheduler::report_job_heartbeat`]
+    /// call while `state` is [`JobState::Running`]; reset to the job's own
+    /// `start_time` each time it (re-)starts. `None` for a job that has
+    /// never run. See [`SchedulingPolicy::heartbeat_interval_secs`].
+    #[serde(default)]
+    pub last_heartbeat: Option<DateTime<Utc>>,
 }
 
 impl Job {
@@ -189,28 +351,71 @@ impl Job {
             allocated_gpus: HashMap::new(),
             retry_count: 0,
             message: String::new(),
+            next_run_at: None,
+            template_id: None,
+            last_heartbeat: None,
         }
     }
-    
-    /// Transition to a new state
-    pub fn transition(&mut self, new_state: JobState, message: &str) {
+
+    /// Creates a recurring template job: `descriptor.schedule` must be set,
+    /// and `next_run_at` is initialized to its first firing after `now`. The
+    /// template itself is never scheduled for execution — only cloned into
+    /// concrete one-shot instances by `StateStore::recurring_tick`.
+    pub fn new_template(descriptor: JobDescriptor, now: DateTime<Utc>) -> Self {
+        let mut job = Self::new(descriptor);
+        if let Some(schedule) = job.descriptor.schedule.clone() {
+            job.next_run_at = schedule.next_fire_after(now);
+        }
+        job
+    }
+
+    /// Transition to a new state, rejecting edges [`JobState::can_transition_to`]
+    /// doesn't allow (e.g. moving a terminal job anywhere, or `Completed`
+    /// going back to `Running`) and leaving `self` untouched when it does.
+    pub fn transition(&mut self, new_state: JobState, message: &str) -> Result<(), InvalidTransition> {
+        if !self.state.can_transition_to(new_state) {
+            return Err(InvalidTransition { from: self.state, to: new_state });
+        }
+
         self.state = new_state;
         self.message = message.to_string();
-        
+
         match new_state {
             JobState::Scheduled => {
                 self.schedule_time = Some(Utc::now());
             }
             JobState::Running => {
                 self.start_time = Some(Utc::now());
+                self.last_heartbeat = self.start_time;
             }
             JobState::Completed | JobState::Failed | JobState::Cancelled | JobState::Timeout => {
                 self.end_time = Some(Utc::now());
             }
             _ => {}
         }
+
+        Ok(())
     }
-    
+
+    /// Renew this job's heartbeat lease to now. Callers are expected to
+    /// only do this while `state == Running`; see [`Self::is_stale`].
+    pub fn record_heartbeat(&mut self) {
+        self.last_heartbeat = Some(Utc::now());
+    }
+
+    /// Whether a `Running` job's heartbeat lease (or its `start_time`, if
+    /// it has never sent one) has gone quiet longer than `timeout`. Always
+    /// `false` for a job that isn't `Running`, or one that has neither a
+    /// heartbeat nor a start time yet.
+    pub fn is_stale(&self, timeout: Duration) -> bool {
+        if self.state != JobState::Running {
+            return false;
+        }
+        self.last_heartbeat
+            .or(self.start_time)
+            .is_some_and(|last| (Utc::now() - last).num_seconds() > timeout.as_secs() as i64)
+    }
+
     /// Get job runtime in seconds
     pub fn runtime_seconds(&self) -> Option<i64> {
         match (self.start_time, self.end_time) {
@@ -252,9 +457,24 @@ mod tests {
             policy: SchedulingPolicy::default(),
             labels: HashMap::new(),
             annotations: HashMap::new(),
+            schedule: None,
+            depends_on: vec![],
+            gang_group: None,
         }
     }
-    
+
+    #[test]
+    fn test_job_state_is_terminal() {
+        assert!(JobState::Completed.is_terminal());
+        assert!(JobState::Failed.is_terminal());
+        assert!(JobState::Cancelled.is_terminal());
+        assert!(JobState::Timeout.is_terminal());
+
+        assert!(!JobState::Pending.is_terminal());
+        assert!(!JobState::Queued.is_terminal());
+        assert!(!JobState::Running.is_terminal());
+    }
+
     #[test]
     fn test_job_creation() {
         let descriptor = create_test_descriptor();
@@ -268,12 +488,98 @@ mod tests {
     fn test_job_transition() {
         let descriptor = create_test_descriptor();
         let mut job = Job::new(descriptor);
-        
-        job.transition(JobState::Queued, "Submitted to queue");
+
+        job.transition(JobState::Queued, "Submitted to queue").unwrap();
         assert_eq!(job.state, JobState::Queued);
-        
-        job.transition(JobState::Running, "Started");
+
+        job.transition(JobState::Scheduled, "Resources allocated").unwrap();
+        job.transition(JobState::Running, "Started").unwrap();
         assert_eq!(job.state, JobState::Running);
         assert!(job.start_time.is_some());
     }
+
+    #[test]
+    fn test_job_transition_to_running_seeds_heartbeat() {
+        let descriptor = create_test_descriptor();
+        let mut job = Job::new(descriptor);
+        assert!(job.last_heartbeat.is_none());
+
+        job.transition(JobState::Queued, "Submitted to queue").unwrap();
+        assert!(job.last_heartbeat.is_none());
+
+        job.transition(JobState::Scheduled, "Resources allocated").unwrap();
+        job.transition(JobState::Running, "Started").unwrap();
+        assert_eq!(job.last_heartbeat, job.start_time,
+            "starting a job must seed its heartbeat lease from its own start_time");
+    }
+
+    #[test]
+    fn test_job_restart_resets_heartbeat() {
+        let descriptor = create_test_descriptor();
+        let mut job = Job::new(descriptor);
+
+        job.transition(JobState::Queued, "Submitted to queue").unwrap();
+        job.transition(JobState::Scheduled, "Resources allocated").unwrap();
+        job.transition(JobState::Running, "Started").unwrap();
+        let first_start = job.start_time;
+
+        job.transition(JobState::Queued, "Requeued after missed heartbeat").unwrap();
+        job.last_heartbeat = None;
+        job.transition(JobState::Scheduled, "Resources allocated").unwrap();
+        job.transition(JobState::Running, "Restarted").unwrap();
+
+        assert!(job.start_time > first_start, "restarting a job must refresh its start_time");
+        assert_eq!(job.last_heartbeat, job.start_time,
+            "restarting a job must also reseed its heartbeat lease");
+    }
+
+    #[test]
+    fn test_transition_rejects_edges_outside_the_lifecycle_graph() {
+        let descriptor = create_test_descriptor();
+        let mut job = Job::new(descriptor);
+
+        // Can't skip straight to Running from Pending.
+        let err = job.transition(JobState::Running, "skip ahead").unwrap_err();
+        assert_eq!(err, InvalidTransition { from: JobState::Pending, to: JobState::Running });
+        assert_eq!(job.state, JobState::Pending, "a rejected transition must not mutate state");
+
+        job.transition(JobState::Queued, "Submitted to queue").unwrap();
+        job.transition(JobState::Scheduled, "Resources allocated").unwrap();
+        job.transition(JobState::Running, "Started").unwrap();
+        job.transition(JobState::Completed, "done").unwrap();
+
+        // A terminal job can't go anywhere, including back to Running.
+        assert!(job.transition(JobState::Running, "resurrect").is_err());
+        assert!(job.transition(JobState::Queued, "resurrect").is_err());
+        assert!(job.transition(JobState::Cancelled, "too late").is_err());
+        assert_eq!(job.state, JobState::Completed);
+    }
+
+    #[test]
+    fn test_transition_to_cancelled_allowed_from_any_non_terminal_state() {
+        for state in [JobState::Pending, JobState::Blocked, JobState::Queued, JobState::Scheduled, JobState::Running, JobState::Suspended] {
+            assert!(state.can_transition_to(JobState::Cancelled), "{:?} -> Cancelled should be legal", state);
+        }
+        for state in [JobState::Completed, JobState::Failed, JobState::Cancelled, JobState::Timeout] {
+            assert!(!state.can_transition_to(JobState::Cancelled), "{:?} -> Cancelled should be rejected", state);
+        }
+    }
+
+    #[test]
+    fn test_record_heartbeat_and_is_stale() {
+        let descriptor = create_test_descriptor();
+        let mut job = Job::new(descriptor);
+        assert!(!job.is_stale(Duration::from_secs(60)), "a non-Running job is never stale");
+
+        job.transition(JobState::Queued, "Submitted to queue").unwrap();
+        job.transition(JobState::Scheduled, "Resources allocated").unwrap();
+        job.transition(JobState::Running, "Started").unwrap();
+        assert!(!job.is_stale(Duration::from_secs(60)), "heartbeat was just seeded by the transition");
+
+        job.last_heartbeat = Some(Utc::now() - chrono::Duration::seconds(120));
+        assert!(job.is_stale(Duration::from_secs(60)));
+
+        job.record_heartbeat();
+        assert!(!job.is_stale(Duration::from_secs(60)), "a fresh heartbeat must clear staleness");
+    }
 }
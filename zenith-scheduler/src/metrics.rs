@@ -0,0 +1,360 @@
+//! Scheduler observability surface: a small in-process Prometheus/OpenMetrics
+//! registry in the same style as the control-plane's `/metrics` endpoint,
+//! plus the counters/histogram [`crate::scheduler::Scheduler`] feeds as jobs
+//! move through its lifecycle.
+
+use crate::job::JobState;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Small in-process Prometheus/OpenMetrics text-format registry, mirroring
+/// the one used by the control-plane's `/metrics` endpoint: push gauges as
+/// you compute them, then [`Self::render`] once at the end.
+#[derive(Default)]
+pub struct MetricRegistry {
+    lines: Vec<String>,
+}
+
+impl MetricRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn gauge(&mut self, name: &str, value: impl std::fmt::Display) {
+        self.lines.push(format!("{name} {value}"));
+    }
+
+    pub fn gauge_with_labels(&mut self, name: &str, labels: &[(&str, &str)], value: impl std::fmt::Display) {
+        let label_str = labels
+            .iter()
+            .map(|(k, v)| format!("{k}=\"{v}\""))
+            .collect::<Vec<_>>()
+            .join(",");
+        self.lines.push(format!("{name}{{{label_str}}} {value}"));
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = self.lines.join("\n");
+        out.push('\n');
+        out
+    }
+}
+
+/// Configuration for [`SchedulerMetrics`]'s latency histograms and for how
+/// often stale accumulated series (per-user/per-project GPU-seconds, queue
+/// wait time, runtime) are cleared.
+#[derive(Debug, Clone)]
+pub struct MetricsConfig {
+    /// Upper bound, in seconds, of the first (smallest) `schedule_cycle`
+    /// latency bucket.
+    pub cycle_bucket_start_secs: f64,
+    /// Multiplicative growth factor applied to each subsequent bucket bound.
+    pub cycle_bucket_factor: f64,
+    /// Number of finite buckets; an implicit `+Inf` bucket is always added.
+    pub cycle_bucket_count: usize,
+    /// Upper bound, in seconds, of the first (smallest) queue-wait-time
+    /// bucket.
+    pub queue_wait_bucket_start_secs: f64,
+    /// Multiplicative growth factor applied to each subsequent bucket bound.
+    pub queue_wait_bucket_factor: f64,
+    /// Number of finite buckets; an implicit `+Inf` bucket is always added.
+    pub queue_wait_bucket_count: usize,
+    /// Upper bound, in seconds, of the first (smallest) job-runtime bucket.
+    pub runtime_bucket_start_secs: f64,
+    /// Multiplicative growth factor applied to each subsequent bucket bound.
+    pub runtime_bucket_factor: f64,
+    /// Number of finite buckets; an implicit `+Inf` bucket is always added.
+    pub runtime_bucket_count: usize,
+    /// How often accumulated per-user/per-project GPU-seconds, queue-wait,
+    /// and runtime series are cleared, bounding unbounded growth from
+    /// long-finished jobs that otherwise linger in the scrape forever. `0`
+    /// disables periodic reset.
+    pub job_state_metrics_reset_interval_secs: u64,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            cycle_bucket_start_secs: 0.01,
+            cycle_bucket_factor: 2.0,
+            cycle_bucket_count: 16,
+            queue_wait_bucket_start_secs: 1.0,
+            queue_wait_bucket_factor: 4.0,
+            queue_wait_bucket_count: 10,
+            runtime_bucket_start_secs: 10.0,
+            runtime_bucket_factor: 4.0,
+            runtime_bucket_count: 12,
+            job_state_metrics_reset_interval_secs: 3600,
+        }
+    }
+}
+
+/// Cumulative (Prometheus-style) histogram with exponentially growing
+/// bucket bounds, shared by [`SchedulerMetrics`]'s `schedule_cycle` latency,
+/// queue-wait-time, and runtime series.
+struct Histogram {
+    bounds: Vec<f64>,
+    /// `counts[i]` is the per-bucket (non-cumulative) observation count for
+    /// `bounds[i]`; `counts[bounds.len()]` is the `+Inf` bucket.
+    counts: Vec<AtomicU64>,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    fn new(start_secs: f64, factor: f64, bucket_count: usize) -> Self {
+        let mut bounds = Vec::with_capacity(bucket_count);
+        let mut bound = start_secs.max(f64::MIN_POSITIVE);
+        for _ in 0..bucket_count {
+            bounds.push(bound);
+            bound *= factor;
+        }
+        let counts = (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect();
+        Self { bounds, counts, sum_micros: AtomicU64::new(0) }
+    }
+
+    fn observe(&self, seconds: f64) {
+        let idx = self.bounds.iter().position(|bound| seconds <= *bound).unwrap_or(self.bounds.len());
+        self.counts[idx].fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add((seconds.max(0.0) * 1_000_000.0) as u64, Ordering::Relaxed);
+    }
+
+    /// Clears every bucket and the running sum, so a caller bounding
+    /// unbounded growth from long-finished jobs can periodically start the
+    /// distribution over.
+    fn reset(&self) {
+        for count in &self.counts {
+            count.store(0, Ordering::Relaxed);
+        }
+        self.sum_micros.store(0, Ordering::Relaxed);
+    }
+
+    fn render_into(&self, registry: &mut MetricRegistry, metric_name: &str) {
+        let mut cumulative = 0u64;
+        for (bound, count) in self.bounds.iter().zip(&self.counts) {
+            cumulative += count.load(Ordering::Relaxed);
+            registry.gauge_with_labels(
+                &format!("{metric_name}_bucket"),
+                &[("le", &bound.to_string())],
+                cumulative,
+            );
+        }
+        cumulative += self.counts[self.bounds.len()].load(Ordering::Relaxed);
+        registry.gauge_with_labels(&format!("{metric_name}_bucket"), &[("le", "+Inf")], cumulative);
+        registry.gauge(&format!("{metric_name}_sum"), self.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0);
+        registry.gauge(&format!("{metric_name}_count"), cumulative);
+    }
+}
+
+/// Counters, the `schedule_cycle` latency histogram, and per-user/per-project
+/// GPU-seconds accounting for one [`crate::scheduler::Scheduler`]. Point-in-time
+/// gauges (pending/scheduled/running job counts) are a snapshot of the live
+/// job table rather than something to accumulate here, so those are computed
+/// directly by `Scheduler::render_metrics` instead.
+pub struct SchedulerMetrics {
+    submitted_total: AtomicU64,
+    completed_total: AtomicU64,
+    failed_total: AtomicU64,
+    timeout_total: AtomicU64,
+    cancelled_total: AtomicU64,
+    cycle_latency: Histogram,
+    queue_wait: Histogram,
+    runtime: Histogram,
+    gpu_seconds: RwLock<HashMap<(String, String), f64>>,
+    reset_interval_secs: u64,
+    last_reset: RwLock<DateTime<Utc>>,
+}
+
+impl SchedulerMetrics {
+    pub fn new(config: &MetricsConfig) -> Self {
+        Self {
+            submitted_total: AtomicU64::new(0),
+            completed_total: AtomicU64::new(0),
+            failed_total: AtomicU64::new(0),
+            timeout_total: AtomicU64::new(0),
+            cancelled_total: AtomicU64::new(0),
+            cycle_latency: Histogram::new(config.cycle_bucket_start_secs, config.cycle_bucket_factor, config.cycle_bucket_count),
+            queue_wait: Histogram::new(config.queue_wait_bucket_start_secs, config.queue_wait_bucket_factor, config.queue_wait_bucket_count),
+            runtime: Histogram::new(config.runtime_bucket_start_secs, config.runtime_bucket_factor, config.runtime_bucket_count),
+            gpu_seconds: RwLock::new(HashMap::new()),
+            reset_interval_secs: config.job_state_metrics_reset_interval_secs,
+            last_reset: RwLock::new(Utc::now()),
+        }
+    }
+
+    /// Records one `Scheduler::submit` call, queued or blocked alike.
+    pub fn record_submission(&self) {
+        self.submitted_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records one `schedule_cycle` call's wall-clock duration.
+    pub fn record_schedule_cycle(&self, duration: std::time::Duration) {
+        self.cycle_latency.observe(duration.as_secs_f64());
+    }
+
+    /// Records how long a job just sat in the queue before being scheduled,
+    /// i.e. `Job::wait_time_seconds()` read right after its `Queued` ->
+    /// `Scheduled` transition.
+    pub fn record_scheduled(&self, wait_seconds: i64) {
+        self.queue_wait.observe(wait_seconds.max(0) as f64);
+    }
+
+    /// Bumps the counter matching a job's terminal `state`, folds
+    /// `gpu_seconds` into its owner's running total, and - if it ever
+    /// started running - observes `runtime_seconds` in the runtime
+    /// histogram. A no-op for any non-terminal `state`.
+    pub fn record_terminal_transition(
+        &self,
+        state: JobState,
+        user_id: &str,
+        project_id: &str,
+        gpu_seconds: f64,
+        runtime_seconds: Option<i64>,
+    ) {
+        match state {
+            JobState::Completed => { self.completed_total.fetch_add(1, Ordering::Relaxed); }
+            JobState::Failed => { self.failed_total.fetch_add(1, Ordering::Relaxed); }
+            JobState::Timeout => { self.timeout_total.fetch_add(1, Ordering::Relaxed); }
+            JobState::Cancelled => { self.cancelled_total.fetch_add(1, Ordering::Relaxed); }
+            _ => return,
+        }
+
+        if gpu_seconds > 0.0 {
+            let mut gpu_seconds_by_owner = self.gpu_seconds.write().unwrap();
+            *gpu_seconds_by_owner.entry((user_id.to_string(), project_id.to_string())).or_insert(0.0) += gpu_seconds;
+        }
+
+        if let Some(runtime_seconds) = runtime_seconds {
+            self.runtime.observe(runtime_seconds.max(0) as f64);
+        }
+    }
+
+    /// Clears accumulated per-user/per-project GPU-seconds, queue-wait, and
+    /// runtime series once `job_state_metrics_reset_interval_secs` has
+    /// elapsed since the last reset, bounding both label cardinality and how
+    /// long finished jobs keep skewing the distributions. A no-op when the
+    /// interval is `0` or hasn't elapsed yet.
+    pub fn maybe_reset(&self, now: DateTime<Utc>) {
+        if self.reset_interval_secs == 0 {
+            return;
+        }
+
+        let mut last_reset = self.last_reset.write().unwrap();
+        if (now - *last_reset).num_seconds() as u64 >= self.reset_interval_secs {
+            self.gpu_seconds.write().unwrap().clear();
+            self.queue_wait.reset();
+            self.runtime.reset();
+            *last_reset = now;
+        }
+    }
+
+    /// Renders every counter, the cycle-time/queue-wait/runtime histograms,
+    /// and the GPU-seconds series into `registry`. Callers mount
+    /// `registry.render()` on their own `/metrics` HTTP endpoint.
+    pub fn render_into(&self, registry: &mut MetricRegistry) {
+        registry.gauge("zenith_scheduler_jobs_submitted_total", self.submitted_total.load(Ordering::Relaxed));
+        registry.gauge("zenith_scheduler_jobs_completed_total", self.completed_total.load(Ordering::Relaxed));
+        registry.gauge("zenith_scheduler_jobs_failed_total", self.failed_total.load(Ordering::Relaxed));
+        registry.gauge("zenith_scheduler_jobs_timeout_total", self.timeout_total.load(Ordering::Relaxed));
+        registry.gauge("zenith_scheduler_jobs_cancelled_total", self.cancelled_total.load(Ordering::Relaxed));
+
+        self.cycle_latency.render_into(registry, "zenith_schedule_cycle_seconds");
+        self.queue_wait.render_into(registry, "zenith_scheduler_queue_wait_seconds");
+        self.runtime.render_into(registry, "zenith_scheduler_job_runtime_seconds");
+
+        for ((user_id, project_id), seconds) in self.gpu_seconds.read().unwrap().iter() {
+            registry.gauge_with_labels(
+                "zenith_scheduler_gpu_seconds_total",
+                &[("user_id", user_id), ("project_id", project_id)],
+                seconds,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_histogram_buckets_cumulatively() {
+        let config = MetricsConfig {
+            cycle_bucket_start_secs: 1.0,
+            cycle_bucket_factor: 2.0,
+            cycle_bucket_count: 3, // bounds: 1, 2, 4
+            ..Default::default()
+        };
+        let histogram = Histogram::new(config.cycle_bucket_start_secs, config.cycle_bucket_factor, config.cycle_bucket_count);
+        histogram.observe(0.5);
+        histogram.observe(1.5);
+        histogram.observe(10.0);
+
+        let mut registry = MetricRegistry::new();
+        histogram.render_into(&mut registry, "zenith_schedule_cycle_seconds");
+        let rendered = registry.render();
+
+        assert!(rendered.contains("zenith_schedule_cycle_seconds_bucket{le=\"1\"} 1"));
+        assert!(rendered.contains("zenith_schedule_cycle_seconds_bucket{le=\"2\"} 2"));
+        assert!(rendered.contains("zenith_schedule_cycle_seconds_bucket{le=\"+Inf\"} 3"));
+        assert!(rendered.contains("zenith_schedule_cycle_seconds_count 3"));
+    }
+
+    #[test]
+    fn test_record_terminal_transition_accumulates_gpu_seconds_per_owner() {
+        let metrics = SchedulerMetrics::new(&MetricsConfig::default());
+        metrics.record_terminal_transition(JobState::Completed, "alice", "proj-a", 120.0, Some(300));
+        metrics.record_terminal_transition(JobState::Completed, "alice", "proj-a", 30.0, Some(60));
+        metrics.record_terminal_transition(JobState::Failed, "bob", "proj-b", 60.0, Some(90));
+
+        let mut registry = MetricRegistry::new();
+        metrics.render_into(&mut registry);
+        let rendered = registry.render();
+
+        assert!(rendered.contains("zenith_scheduler_jobs_completed_total 2"));
+        assert!(rendered.contains("zenith_scheduler_jobs_failed_total 1"));
+        assert!(rendered.contains("user_id=\"alice\",project_id=\"proj-a\"} 150"));
+        assert!(rendered.contains("user_id=\"bob\",project_id=\"proj-b\"} 60"));
+    }
+
+    #[test]
+    fn test_record_submission_accumulates_separately_from_terminal_counters() {
+        let metrics = SchedulerMetrics::new(&MetricsConfig::default());
+        metrics.record_submission();
+        metrics.record_submission();
+        metrics.record_terminal_transition(JobState::Completed, "alice", "proj-a", 10.0, Some(20));
+
+        let mut registry = MetricRegistry::new();
+        metrics.render_into(&mut registry);
+        let rendered = registry.render();
+
+        assert!(rendered.contains("zenith_scheduler_jobs_submitted_total 2"));
+        assert!(rendered.contains("zenith_scheduler_jobs_completed_total 1"));
+    }
+
+    #[test]
+    fn test_maybe_reset_clears_gpu_seconds_after_interval_elapses() {
+        let metrics = SchedulerMetrics::new(&MetricsConfig {
+            job_state_metrics_reset_interval_secs: 60,
+            ..Default::default()
+        });
+        metrics.record_terminal_transition(JobState::Completed, "alice", "proj-a", 120.0, Some(300));
+
+        metrics.maybe_reset(Utc::now());
+        assert!(!metrics.gpu_seconds.read().unwrap().is_empty(), "reset must not fire before the interval elapses");
+
+        metrics.maybe_reset(Utc::now() + chrono::Duration::seconds(61));
+        assert!(metrics.gpu_seconds.read().unwrap().is_empty(), "reset must clear GPU-seconds series once the interval elapses");
+    }
+
+    #[test]
+    fn test_maybe_reset_disabled_when_interval_is_zero() {
+        let metrics = SchedulerMetrics::new(&MetricsConfig {
+            job_state_metrics_reset_interval_secs: 0,
+            ..Default::default()
+        });
+        metrics.record_terminal_transition(JobState::Completed, "alice", "proj-a", 120.0, Some(300));
+        metrics.maybe_reset(Utc::now() + chrono::Duration::days(365));
+        assert!(!metrics.gpu_seconds.read().unwrap().is_empty(), "reset interval 0 must disable periodic reset");
+    }
+}
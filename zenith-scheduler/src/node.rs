@@ -5,12 +5,30 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use parking_lot::RwLock;
 use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+
+/// GPU vendor, as identified by PCI vendor ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GpuVendor {
+    /// NVIDIA (PCI vendor 0x10de)
+    Nvidia,
+    /// AMD (PCI vendor 0x1002)
+    Amd,
+    /// Intel (PCI vendor 0x8086)
+    Intel,
+    /// Apple silicon integrated GPU
+    Apple,
+    /// Vendor could not be determined
+    Unknown,
+}
 
 /// GPU device information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GpuDevice {
     /// Device ID (e.g., "cuda:0")
     pub device_id: String,
+    /// Device vendor
+    pub vendor: GpuVendor,
     /// Device name (e.g., "NVIDIA A100-SXM4-80GB")
     pub device_name: String,
     /// UUID
@@ -27,6 +45,63 @@ pub struct GpuDevice {
     pub allocated: bool,
     /// Job ID if allocated
     pub allocated_job_id: Option<String>,
+    /// Processes currently running on this device
+    pub processes: Vec<GpuProcess>,
+    /// Current power draw, in milliwatts
+    pub power_usage_mw: u64,
+    /// Current power management limit, in milliwatts
+    pub power_limit_mw: u64,
+    /// Maximum power management limit the device will accept, in milliwatts
+    pub power_limit_max_mw: u64,
+    /// Total energy consumed since driver load, in millijoules (monotonic;
+    /// diff consecutive heartbeats to get per-interval joules)
+    pub energy_consumed_mj: u64,
+    /// Volatile (since-reboot) ECC error count
+    pub ecc_volatile_errors: u64,
+    /// Aggregate (lifetime) ECC error count
+    pub ecc_aggregate_errors: u64,
+    /// Active clock-throttle reasons (e.g. "hw_thermal_slowdown", "sw_power_cap")
+    pub throttle_reasons: Vec<String>,
+}
+
+/// A process observed running on a GPU device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuProcess {
+    /// OS process ID
+    pub pid: u32,
+    /// Command name, resolved from /proc/<pid>/comm where available
+    pub command: String,
+    /// GPU memory used by this process, in bytes
+    pub used_memory: u64,
+    /// SM (compute) utilization attributed to this process (0.0-1.0)
+    pub sm_utilization: f32,
+    /// Memory-controller utilization attributed to this process (0.0-1.0)
+    pub memory_utilization: f32,
+}
+
+/// One GPU's NVLink adjacency to a peer device (or NVSwitch endpoint)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NvLinkPeer {
+    /// Index of the peer device within this node's GPU list
+    pub peer_index: u32,
+    /// Number of active NVLink lanes connecting to this peer
+    pub link_count: u32,
+    /// Estimated aggregate bandwidth across all lanes to this peer, in MB/s
+    pub bandwidth_mbps: u64,
+}
+
+/// Quality of a placement returned by [`Node::allocate_gpus_topology_aware`],
+/// from best to worst locality for collective communication
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AllocationQuality {
+    /// All requested GPUs are mutually reachable over NVLink/NVSwitch
+    SameIsland,
+    /// Requested GPUs span more than one interconnect island, but the
+    /// placement minimizes the number of NUMA domains crossed
+    CrossIsland,
+    /// No island/NUMA-aware combination could satisfy the request; GPUs
+    /// were picked without regard to interconnect topology
+    Fragmented,
 }
 
 /// Node topology information
@@ -48,6 +123,29 @@ pub struct NodeTopology {
     pub nvswitch_present: bool,
     /// RDMA capable
     pub rdma_capable: bool,
+    /// GPU-to-GPU NVLink adjacency, keyed by device_id, so the scheduler
+    /// can co-locate tightly-coupled ranks instead of splitting an
+    /// all-reduce group across a slow PCIe hop
+    pub nvlink_topology: HashMap<String, Vec<NvLinkPeer>>,
+}
+
+/// Available vs. total space on one storage partition, in bytes
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PartitionInfo {
+    /// Free space, in bytes
+    pub available: u64,
+    /// Total capacity, in bytes
+    pub total: u64,
+}
+
+/// Local storage availability on a node, so the scheduler can tell
+/// whether a node has room for datasets and checkpoints, not just GPUs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiskInfo {
+    /// Scratch space for datasets and job working directories
+    pub data_partition: PartitionInfo,
+    /// Smaller, typically faster partition for checkpoint/job metadata
+    pub metadata_partition: PartitionInfo,
 }
 
 /// Node health status
@@ -86,6 +184,24 @@ pub struct Node {
     pub labels: HashMap<String, String>,
     /// Running job IDs
     pub running_jobs: Vec<String>,
+    /// Failure domain this node belongs to (e.g. a rack or availability
+    /// zone), used to spread fault-sensitive placements across domains
+    pub zone: String,
+    /// Relative scheduling weight for capacity-aware placement; higher
+    /// values mean the node should receive a proportionally larger share
+    /// of jobs. Defaults to 100 (an "average" node).
+    pub capacity_weight: u32,
+    /// Free-form operator tags (e.g. "critical", "spot") for filtering
+    pub tags: Vec<String>,
+    /// Cordoned for maintenance: excluded from new placements, but running
+    /// jobs are left alone
+    pub draining: bool,
+    /// When this node first became [`NodeHealth::Unreachable`], for
+    /// [`NodeRegistry::reap`]'s deregistration grace window. Cleared once
+    /// the node recovers.
+    pub unreachable_since: Option<DateTime<Utc>>,
+    /// Local scratch/checkpoint storage availability
+    pub disk: DiskInfo,
 }
 
 impl Node {
@@ -103,6 +219,12 @@ impl Node {
             last_heartbeat: now,
             labels: HashMap::new(),
             running_jobs: vec![],
+            zone: "default".to_string(),
+            capacity_weight: 100,
+            tags: vec![],
+            draining: false,
+            unreachable_since: None,
+            disk: DiskInfo::default(),
         }
     }
     
@@ -154,6 +276,153 @@ impl Node {
         Ok(allocated_ids)
     }
     
+    /// Allocate `count` GPUs to `job_id`, preferring placements that keep
+    /// collective operations (all-reduce, etc.) off a slow PCIe hop:
+    ///
+    /// 1. A single NVLink/NVSwitch "interconnect island" with enough free
+    ///    GPUs ([`AllocationQuality::SameIsland`]).
+    /// 2. Otherwise, the pair of NUMA domains with the most free GPUs,
+    ///    combined ([`AllocationQuality::CrossIsland`]). Bounded at two
+    ///    domains: spanning further rarely buys back any locality, so it
+    ///    isn't worth searching every combination.
+    /// 3. Otherwise, [`Self::allocate_gpus`]'s plain first-N-free behavior
+    ///    ([`AllocationQuality::Fragmented`]).
+    pub fn allocate_gpus_topology_aware(
+        &mut self,
+        job_id: &str,
+        count: usize,
+    ) -> Result<(Vec<String>, AllocationQuality)> {
+        let mut islands = self.interconnect_islands();
+        islands.sort();
+
+        if let Some(chosen) = islands.iter().find_map(|island| {
+            let free: Vec<String> = island.iter()
+                .filter(|id| self.is_gpu_free(id))
+                .cloned()
+                .collect();
+            (free.len() >= count).then(|| free.into_iter().take(count).collect::<Vec<_>>())
+        }) {
+            self.mark_allocated(job_id, &chosen);
+            return Ok((chosen, AllocationQuality::SameIsland));
+        }
+
+        // No single island covers the request; try the two NUMA domains
+        // with the most free GPUs between them.
+        let mut free_by_domain: HashMap<u32, Vec<String>> = HashMap::new();
+        for gpu in &self.topology.gpus {
+            if !gpu.allocated {
+                let domain = self.numa_domain_of(&gpu.device_id);
+                free_by_domain.entry(domain).or_default().push(gpu.device_id.clone());
+            }
+        }
+        let mut domains: Vec<(u32, Vec<String>)> = free_by_domain.into_iter().collect();
+        domains.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(&b.0)));
+
+        let mut chosen = Vec::with_capacity(count);
+        for (_, ids) in domains.iter().take(2) {
+            if chosen.len() >= count {
+                break;
+            }
+            let take = (count - chosen.len()).min(ids.len());
+            chosen.extend(ids[..take].iter().cloned());
+        }
+
+        if chosen.len() >= count {
+            self.mark_allocated(job_id, &chosen);
+            return Ok((chosen, AllocationQuality::CrossIsland));
+        }
+
+        let ids = self.allocate_gpus(job_id, count)?;
+        Ok((ids, AllocationQuality::Fragmented))
+    }
+
+    /// Mark `device_ids` allocated to `job_id` and record the job as
+    /// running on this node. Callers must have already verified every ID
+    /// refers to a free GPU.
+    fn mark_allocated(&mut self, job_id: &str, device_ids: &[String]) {
+        for gpu in self.topology.gpus.iter_mut() {
+            if device_ids.iter().any(|id| id == &gpu.device_id) {
+                gpu.allocated = true;
+                gpu.allocated_job_id = Some(job_id.to_string());
+            }
+        }
+        self.running_jobs.push(job_id.to_string());
+    }
+
+    fn is_gpu_free(&self, device_id: &str) -> bool {
+        self.topology.gpus.iter().any(|g| g.device_id == device_id && !g.allocated)
+    }
+
+    /// Approximate NUMA domain for a GPU. `NodeTopology` only records a
+    /// NUMA domain *count*, not a per-device mapping, so this assumes GPUs
+    /// are interleaved round-robin across domains in topology order --
+    /// good enough to bias placement away from crossing domains, though
+    /// not a substitute for real per-device affinity data.
+    fn numa_domain_of(&self, device_id: &str) -> u32 {
+        let numa_nodes = self.topology.numa_nodes.max(1);
+        self.topology.gpus.iter()
+            .position(|g| g.device_id == device_id)
+            .map(|index| (index as u32) % numa_nodes)
+            .unwrap_or(0)
+    }
+
+    /// Group this node's GPUs into "interconnect islands": sets of devices
+    /// mutually reachable without leaving NVLink/NVSwitch. If an NVSwitch
+    /// fabric is present it connects every GPU all-to-all, so the whole
+    /// node is one island; otherwise islands are the connected components
+    /// of the per-device NVLink adjacency in `nvlink_topology`, and a GPU
+    /// with no NVLink peers is its own singleton island.
+    fn interconnect_islands(&self) -> Vec<Vec<String>> {
+        let all_ids: Vec<String> = self.topology.gpus.iter().map(|g| g.device_id.clone()).collect();
+
+        if self.topology.nvswitch_present {
+            return vec![all_ids];
+        }
+
+        let mut parent: HashMap<String, String> =
+            all_ids.iter().cloned().map(|id| (id.clone(), id)).collect();
+
+        fn find(parent: &mut HashMap<String, String>, id: &str) -> String {
+            let mut root = id.to_string();
+            while parent[&root] != root {
+                root = parent[&root].clone();
+            }
+            let mut cur = id.to_string();
+            while cur != root {
+                let next = parent[&cur].clone();
+                parent.insert(cur, root.clone());
+                cur = next;
+            }
+            root
+        }
+
+        for (device_id, peers) in &self.topology.nvlink_topology {
+            if !parent.contains_key(device_id) {
+                continue;
+            }
+            for peer in peers {
+                let Some(peer_gpu) = self.topology.gpus.get(peer.peer_index as usize) else {
+                    continue;
+                };
+                if !parent.contains_key(&peer_gpu.device_id) {
+                    continue;
+                }
+                let root_a = find(&mut parent, device_id);
+                let root_b = find(&mut parent, &peer_gpu.device_id);
+                if root_a != root_b {
+                    parent.insert(root_a, root_b);
+                }
+            }
+        }
+
+        let mut islands: HashMap<String, Vec<String>> = HashMap::new();
+        for id in &all_ids {
+            let root = find(&mut parent, id);
+            islands.entry(root).or_default().push(id.clone());
+        }
+        islands.into_values().collect()
+    }
+
     /// Release GPUs from a job
     pub fn release_gpus(&mut self, job_id: &str) {
         for gpu in &mut self.topology.gpus {
@@ -164,12 +433,83 @@ impl Node {
         }
         self.running_jobs.retain(|id| id != job_id);
     }
+
+    /// Relative grace multiplier applied to the registry's base heartbeat
+    /// timeout before [`NodeRegistry::reap`] escalates this node's health:
+    /// nodes running more jobs, or tagged `"critical"`, earn proportionally
+    /// more slack before being treated as stale.
+    pub fn importance_weight(&self) -> f64 {
+        let mut weight = 1.0 + self.running_jobs.len() as f64 * 0.1;
+        if self.tags.iter().any(|t| t == "critical") {
+            weight *= 3.0;
+        }
+        weight
+    }
+}
+
+/// Thresholds used by [`NodeRegistry::refresh_telemetry`] to downgrade a
+/// node's health from live GPU telemetry
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GpuHealthThresholds {
+    /// Temperature in Celsius at or above which a node is marked `Warning`
+    pub warning_temp_c: i32,
+    /// Temperature in Celsius at or above which a node is marked `Unhealthy`
+    pub critical_temp_c: i32,
+    /// Volatile (since-reboot) ECC error count at or above which a node is
+    /// marked `Warning`
+    pub warning_ecc_errors: u64,
+}
+
+impl Default for GpuHealthThresholds {
+    fn default() -> Self {
+        Self {
+            warning_temp_c: 85,
+            critical_temp_c: 95,
+            warning_ecc_errors: 1,
+        }
+    }
+}
+
+/// A [`Node::health`] transition observed by [`NodeRegistry::reap`], as
+/// delivered to subscribers of [`NodeRegistry::subscribe_health_events`]
+#[derive(Debug, Clone)]
+pub struct NodeHealthTransition {
+    /// The node whose health changed
+    pub node_id: String,
+    /// Health before this sweep
+    pub previous: NodeHealth,
+    /// Health after this sweep
+    pub current: NodeHealth,
+    /// When the transition was observed
+    pub at: DateTime<Utc>,
+}
+
+/// How many heartbeat-timeout multiples of age before a node is escalated
+/// to each more severe health state
+const REAP_WARNING_MULTIPLE: i64 = 1;
+const REAP_UNHEALTHY_MULTIPLE: i64 = 2;
+const REAP_UNREACHABLE_MULTIPLE: i64 = 4;
+
+/// Default grace window (seconds) an `Unreachable` node is kept registered
+/// before [`NodeRegistry::reap`] deregisters it outright
+const DEFAULT_UNREACHABLE_GRACE_SECONDS: i64 = 300;
+
+fn health_severity(health: NodeHealth) -> u8 {
+    match health {
+        NodeHealth::Healthy => 0,
+        NodeHealth::Warning => 1,
+        NodeHealth::Unhealthy => 2,
+        NodeHealth::Unreachable => 3,
+    }
 }
 
 /// Node registry - manages all nodes in the cluster
 pub struct NodeRegistry {
     nodes: RwLock<HashMap<String, Node>>,
     heartbeat_timeout_seconds: i64,
+    gpu_health_thresholds: GpuHealthThresholds,
+    unreachable_grace_seconds: i64,
+    health_events: broadcast::Sender<NodeHealthTransition>,
 }
 
 impl NodeRegistry {
@@ -178,9 +518,101 @@ impl NodeRegistry {
         Self {
             nodes: RwLock::new(HashMap::new()),
             heartbeat_timeout_seconds,
+            gpu_health_thresholds: GpuHealthThresholds::default(),
+            unreachable_grace_seconds: DEFAULT_UNREACHABLE_GRACE_SECONDS,
+            health_events: broadcast::channel(256).0,
         }
     }
-    
+
+    /// Create a node registry with custom GPU health thresholds
+    pub fn with_gpu_health_thresholds(
+        heartbeat_timeout_seconds: i64,
+        gpu_health_thresholds: GpuHealthThresholds,
+    ) -> Self {
+        Self {
+            gpu_health_thresholds,
+            ..Self::new(heartbeat_timeout_seconds)
+        }
+    }
+
+    /// Create a node registry with a custom deregistration grace window
+    /// for nodes stuck in [`NodeHealth::Unreachable`]
+    pub fn with_unreachable_grace_seconds(
+        heartbeat_timeout_seconds: i64,
+        unreachable_grace_seconds: i64,
+    ) -> Self {
+        Self {
+            unreachable_grace_seconds,
+            ..Self::new(heartbeat_timeout_seconds)
+        }
+    }
+
+    /// Subscribe to [`Node::health`] transitions observed by [`Self::reap`]
+    pub fn subscribe_health_events(&self) -> broadcast::Receiver<NodeHealthTransition> {
+        self.health_events.subscribe()
+    }
+
+    /// Sweep all registered nodes, escalating health as heartbeat age
+    /// crosses escalating multiples of `heartbeat_timeout_seconds` --
+    /// scaled per node by [`Node::importance_weight`], so nodes running
+    /// more jobs or tagged `"critical"` get proportionally more grace.
+    /// Escalation only ever makes health worse here; recovery happens
+    /// through a fresh [`Self::update`]/[`Self::refresh_telemetry`] call.
+    /// Nodes that have been `Unreachable` for longer than
+    /// `unreachable_grace_seconds` are deregistered outright, to avoid
+    /// flapping nodes being evicted prematurely. Returns the IDs of any
+    /// nodes deregistered this sweep.
+    pub fn reap(&self) -> Vec<String> {
+        let now = Utc::now();
+        let mut nodes = self.nodes.write();
+
+        for node in nodes.values_mut() {
+            let effective_timeout =
+                (self.heartbeat_timeout_seconds as f64 * node.importance_weight()) as i64;
+            let age_seconds = (now - node.last_heartbeat).num_seconds();
+
+            let target = if age_seconds > effective_timeout * REAP_UNREACHABLE_MULTIPLE {
+                NodeHealth::Unreachable
+            } else if age_seconds > effective_timeout * REAP_UNHEALTHY_MULTIPLE {
+                NodeHealth::Unhealthy
+            } else if age_seconds > effective_timeout * REAP_WARNING_MULTIPLE {
+                NodeHealth::Warning
+            } else {
+                NodeHealth::Healthy
+            };
+
+            if health_severity(target) > health_severity(node.health) {
+                let previous = node.health;
+                node.health = target;
+                if target == NodeHealth::Unreachable {
+                    node.unreachable_since.get_or_insert(now);
+                }
+                let _ = self.health_events.send(NodeHealthTransition {
+                    node_id: node.id.clone(),
+                    previous,
+                    current: target,
+                    at: now,
+                });
+            }
+        }
+
+        let expired: Vec<String> = nodes.values()
+            .filter(|n| {
+                n.health == NodeHealth::Unreachable
+                    && n.unreachable_since
+                        .map(|since| (now - since).num_seconds() >= self.unreachable_grace_seconds)
+                        .unwrap_or(false)
+            })
+            .map(|n| n.id.clone())
+            .collect();
+
+        for node_id in &expired {
+            nodes.remove(node_id);
+        }
+
+        expired
+    }
+
     /// Register a new node
     pub fn register(&self, node: Node) -> Result<()> {
         let mut nodes = self.nodes.write();
@@ -207,6 +639,56 @@ impl NodeRegistry {
         }
     }
     
+    /// Apply a fresh GPU telemetry snapshot (utilization, memory,
+    /// temperature, power, ECC counters, throttle reasons) to a node, and
+    /// downgrade its health to `Warning` or `Unhealthy` if the readings
+    /// cross `gpu_health_thresholds`. This is what gives the scheduler real
+    /// GPU data instead of the zeroed placeholders from a bare registration.
+    pub fn refresh_telemetry(&self, node_id: &str, gpus: Vec<GpuDevice>) -> Result<()> {
+        let mut nodes = self.nodes.write();
+        let node = nodes.get_mut(node_id)
+            .ok_or_else(|| Error::Node(format!("Node not found: {}", node_id)))?;
+
+        let health = self.assess_gpu_health(&gpus);
+        node.topology.gpus = gpus;
+
+        if health != NodeHealth::Healthy {
+            node.health = health;
+            node.health_message = match health {
+                NodeHealth::Unhealthy => "GPU telemetry crossed a critical threshold".to_string(),
+                NodeHealth::Warning => "GPU telemetry crossed a warning threshold".to_string(),
+                _ => node.health_message.clone(),
+            };
+        } else if node.health != NodeHealth::Unreachable {
+            node.health = NodeHealth::Healthy;
+            node.health_message = "OK".to_string();
+        }
+
+        node.heartbeat();
+        Ok(())
+    }
+
+    /// Worst-case health implied by a set of GPU telemetry readings
+    fn assess_gpu_health(&self, gpus: &[GpuDevice]) -> NodeHealth {
+        let t = &self.gpu_health_thresholds;
+        let mut worst = NodeHealth::Healthy;
+
+        for gpu in gpus {
+            // A nonzero aggregate (lifetime) ECC error count means the device
+            // has an uncorrectable hardware fault that predates this poll --
+            // the closest a pure telemetry poller gets to a persistent
+            // XID-style fault without an NVML event-set subscription.
+            if gpu.temperature >= t.critical_temp_c || gpu.ecc_aggregate_errors > 0 {
+                return NodeHealth::Unhealthy;
+            }
+            if gpu.temperature >= t.warning_temp_c || gpu.ecc_volatile_errors >= t.warning_ecc_errors {
+                worst = NodeHealth::Warning;
+            }
+        }
+
+        worst
+    }
+
     /// Get a node by ID
     pub fn get(&self, node_id: &str) -> Option<Node> {
         self.nodes.read().get(node_id).cloned()
@@ -221,14 +703,65 @@ impl NodeRegistry {
             .collect()
     }
     
-    /// Get nodes with available GPUs
+    /// Get nodes with available GPUs, excluding nodes cordoned for
+    /// maintenance (`draining`)
     pub fn nodes_with_available_gpus(&self, count: usize) -> Vec<Node> {
         self.healthy_nodes()
             .into_iter()
-            .filter(|n| n.available_gpus() >= count)
+            .filter(|n| !n.draining && n.available_gpus() >= count)
             .collect()
     }
-    
+
+    /// Get all healthy, non-draining nodes in a given failure domain
+    pub fn nodes_in_zone(&self, zone: &str) -> Vec<Node> {
+        self.healthy_nodes()
+            .into_iter()
+            .filter(|n| !n.draining && n.zone == zone)
+            .collect()
+    }
+
+    /// Pick up to `count` nodes spread across as many distinct zones as
+    /// possible, so a multi-node job doesn't land entirely in one
+    /// failure domain. Within each zone, the highest-`capacity_weight`
+    /// healthy, non-draining node with at least one free GPU is chosen.
+    /// Falls back to filling remaining slots from any zone once every
+    /// zone has contributed a node.
+    pub fn spread_across_zones(&self, count: usize) -> Vec<Node> {
+        let mut by_zone: HashMap<String, Vec<Node>> = HashMap::new();
+        for node in self.healthy_nodes().into_iter().filter(|n| !n.draining && n.available_gpus() > 0) {
+            by_zone.entry(node.zone.clone()).or_default().push(node);
+        }
+        for nodes in by_zone.values_mut() {
+            nodes.sort_by(|a, b| b.capacity_weight.cmp(&a.capacity_weight));
+        }
+
+        let mut zones: Vec<String> = by_zone.keys().cloned().collect();
+        zones.sort();
+
+        let mut picked = Vec::with_capacity(count);
+        let mut round = 0;
+        while picked.len() < count {
+            let mut made_progress = false;
+            for zone in &zones {
+                if picked.len() >= count {
+                    break;
+                }
+                if let Some(nodes) = by_zone.get(zone) {
+                    if let Some(node) = nodes.get(round) {
+                        picked.push(node.clone());
+                        made_progress = true;
+                    }
+                }
+            }
+            if !made_progress {
+                break;
+            }
+            round += 1;
+        }
+
+        picked
+    }
+
     /// Check if a specific node is healthy
     pub fn is_node_healthy(&self, node_id: &str) -> bool {
         if let Some(node) = self.nodes.read().get(node_id) {
@@ -244,15 +777,90 @@ impl NodeRegistry {
         let healthy_nodes: Vec<_> = nodes.values()
             .filter(|n| n.health == NodeHealth::Healthy)
             .collect();
-        
+
+        let mut zones: HashMap<String, ZoneSummary> = HashMap::new();
+        for node in nodes.values() {
+            let zone = zones.entry(node.zone.clone()).or_default();
+            zone.total_nodes += 1;
+            if node.health == NodeHealth::Healthy {
+                zone.healthy_nodes += 1;
+            }
+            zone.total_gpus += node.total_gpus();
+            zone.available_gpus += node.available_gpus();
+        }
+
         ClusterSummary {
             total_nodes: nodes.len(),
             healthy_nodes: healthy_nodes.len(),
             total_gpus: nodes.values().map(|n| n.total_gpus()).sum(),
             available_gpus: nodes.values().map(|n| n.available_gpus()).sum(),
             running_jobs: nodes.values().map(|n| n.running_jobs.len()).sum(),
+            zones,
         }
     }
+
+    /// A richer, JSON-friendly admin snapshot of the cluster, modeled after
+    /// Garage's cluster status endpoint: a layout version plus a per-node
+    /// view covering liveness, placement, and disk headroom -- the detail
+    /// an ops dashboard or monitoring scrape needs that [`Self::summary`]'s
+    /// aggregate counters don't carry.
+    pub fn cluster_status(&self) -> ClusterStatusReport {
+        let now = Utc::now();
+        let nodes = self.nodes.read();
+
+        let node_statuses = nodes.values()
+            .map(|n| NodeStatus {
+                node_id: n.id.clone(),
+                hostname: n.hostname.clone(),
+                zone: n.zone.clone(),
+                is_up: n.health != NodeHealth::Unreachable
+                    && !n.is_stale(self.heartbeat_timeout_seconds),
+                last_seen_secs_ago: (now - n.last_heartbeat).num_seconds().max(0),
+                draining: n.draining,
+                data_partition: n.disk.data_partition,
+                metadata_partition: n.disk.metadata_partition,
+            })
+            .collect();
+
+        ClusterStatusReport {
+            layout_version: CLUSTER_LAYOUT_VERSION,
+            nodes: node_statuses,
+        }
+    }
+}
+
+/// Schema version of [`ClusterStatusReport`]; bump when the shape of
+/// [`NodeStatus`] changes in a way that isn't backwards compatible
+const CLUSTER_LAYOUT_VERSION: u32 = 1;
+
+/// Admin snapshot returned by [`NodeRegistry::cluster_status`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterStatusReport {
+    /// Schema version of this report; see [`CLUSTER_LAYOUT_VERSION`]
+    pub layout_version: u32,
+    /// Per-node status, in no particular order
+    pub nodes: Vec<NodeStatus>,
+}
+
+/// Per-node entry of a [`ClusterStatusReport`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeStatus {
+    /// Node ID
+    pub node_id: String,
+    /// Hostname
+    pub hostname: String,
+    /// Failure domain; see [`Node::zone`]
+    pub zone: String,
+    /// Reachable and within the heartbeat timeout
+    pub is_up: bool,
+    /// Seconds since the last heartbeat was received
+    pub last_seen_secs_ago: i64,
+    /// Cordoned for maintenance; see [`Node::draining`]
+    pub draining: bool,
+    /// Dataset/checkpoint scratch partition headroom
+    pub data_partition: PartitionInfo,
+    /// Checkpoint/job metadata partition headroom
+    pub metadata_partition: PartitionInfo,
 }
 
 /// Cluster summary statistics
@@ -268,6 +876,21 @@ pub struct ClusterSummary {
     pub available_gpus: usize,
     /// Running jobs
     pub running_jobs: usize,
+    /// Per-zone breakdown, keyed by [`Node::zone`]
+    pub zones: HashMap<String, ZoneSummary>,
+}
+
+/// Per-zone slice of a [`ClusterSummary`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ZoneSummary {
+    /// Total number of nodes in this zone
+    pub total_nodes: usize,
+    /// Number of healthy nodes in this zone
+    pub healthy_nodes: usize,
+    /// Total GPUs in this zone
+    pub total_gpus: usize,
+    /// Available GPUs in this zone
+    pub available_gpus: usize,
 }
 
 #[cfg(test)]
@@ -277,6 +900,7 @@ mod tests {
     fn create_test_node() -> Node {
         let gpu = GpuDevice {
             device_id: "cuda:0".to_string(),
+            vendor: GpuVendor::Nvidia,
             device_name: "NVIDIA A100".to_string(),
             uuid: "GPU-12345".to_string(),
             total_memory: 80 * 1024 * 1024 * 1024,
@@ -285,8 +909,16 @@ mod tests {
             temperature: 40,
             allocated: false,
             allocated_job_id: None,
+            processes: vec![],
+            power_usage_mw: 0,
+            power_limit_mw: 0,
+            power_limit_max_mw: 0,
+            energy_consumed_mj: 0,
+            ecc_volatile_errors: 0,
+            ecc_aggregate_errors: 0,
+            throttle_reasons: vec![],
         };
-        
+
         let topology = NodeTopology {
             gpus: vec![gpu],
             cpu_cores: 64,
@@ -296,6 +928,7 @@ mod tests {
             nvlink_present: true,
             nvswitch_present: false,
             rdma_capable: true,
+            nvlink_topology: HashMap::new(),
         };
         
         Node::new(
@@ -339,4 +972,336 @@ mod tests {
         assert_eq!(summary.total_nodes, 1);
         assert_eq!(summary.total_gpus, 1);
     }
+
+    #[test]
+    fn test_refresh_telemetry_updates_gpu_fields() {
+        let registry = NodeRegistry::new(60);
+        let node = create_test_node();
+        registry.register(node.clone()).unwrap();
+
+        let mut gpu = node.topology.gpus[0].clone();
+        gpu.utilization = 0.75;
+        gpu.temperature = 60;
+        registry.refresh_telemetry(&node.id, vec![gpu]).unwrap();
+
+        let updated = registry.get(&node.id).unwrap();
+        assert_eq!(updated.topology.gpus[0].utilization, 0.75);
+        assert_eq!(updated.topology.gpus[0].temperature, 60);
+        assert_eq!(updated.health, NodeHealth::Healthy);
+    }
+
+    #[test]
+    fn test_refresh_telemetry_downgrades_to_warning_on_temperature() {
+        let registry = NodeRegistry::new(60);
+        let node = create_test_node();
+        registry.register(node.clone()).unwrap();
+
+        let mut gpu = node.topology.gpus[0].clone();
+        gpu.temperature = 90;
+        registry.refresh_telemetry(&node.id, vec![gpu]).unwrap();
+
+        let updated = registry.get(&node.id).unwrap();
+        assert_eq!(updated.health, NodeHealth::Warning);
+    }
+
+    #[test]
+    fn test_refresh_telemetry_downgrades_to_unhealthy_on_ecc_fault() {
+        let registry = NodeRegistry::new(60);
+        let node = create_test_node();
+        registry.register(node.clone()).unwrap();
+
+        let mut gpu = node.topology.gpus[0].clone();
+        gpu.ecc_aggregate_errors = 1;
+        registry.refresh_telemetry(&node.id, vec![gpu]).unwrap();
+
+        let updated = registry.get(&node.id).unwrap();
+        assert_eq!(updated.health, NodeHealth::Unhealthy);
+    }
+
+    #[test]
+    fn test_refresh_telemetry_unknown_node() {
+        let registry = NodeRegistry::new(60);
+        let result = registry.refresh_telemetry("no-such-node", vec![]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_with_gpu_health_thresholds_custom() {
+        let thresholds = GpuHealthThresholds {
+            warning_temp_c: 70,
+            critical_temp_c: 80,
+            warning_ecc_errors: 5,
+        };
+        let registry = NodeRegistry::with_gpu_health_thresholds(60, thresholds);
+        let node = create_test_node();
+        registry.register(node.clone()).unwrap();
+
+        let mut gpu = node.topology.gpus[0].clone();
+        gpu.temperature = 72;
+        registry.refresh_telemetry(&node.id, vec![gpu]).unwrap();
+
+        let updated = registry.get(&node.id).unwrap();
+        assert_eq!(updated.health, NodeHealth::Warning);
+    }
+
+    fn create_test_node_in_zone(id: &str, zone: &str) -> Node {
+        let mut node = create_test_node();
+        node.id = id.to_string();
+        node.zone = zone.to_string();
+        node
+    }
+
+    #[test]
+    fn test_nodes_in_zone() {
+        let registry = NodeRegistry::new(60);
+        registry.register(create_test_node_in_zone("node-a", "zone-a")).unwrap();
+        registry.register(create_test_node_in_zone("node-b", "zone-b")).unwrap();
+
+        let zone_a_nodes = registry.nodes_in_zone("zone-a");
+        assert_eq!(zone_a_nodes.len(), 1);
+        assert_eq!(zone_a_nodes[0].id, "node-a");
+    }
+
+    #[test]
+    fn test_nodes_with_available_gpus_skips_draining() {
+        let registry = NodeRegistry::new(60);
+        let mut node = create_test_node();
+        node.draining = true;
+        registry.register(node).unwrap();
+
+        assert!(registry.nodes_with_available_gpus(1).is_empty());
+    }
+
+    #[test]
+    fn test_spread_across_zones_picks_distinct_zones_first() {
+        let registry = NodeRegistry::new(60);
+        registry.register(create_test_node_in_zone("node-a", "zone-a")).unwrap();
+        registry.register(create_test_node_in_zone("node-b", "zone-b")).unwrap();
+        registry.register(create_test_node_in_zone("node-c", "zone-a")).unwrap();
+
+        let picked = registry.spread_across_zones(2);
+        let zones: std::collections::HashSet<_> = picked.iter().map(|n| n.zone.clone()).collect();
+        assert_eq!(picked.len(), 2);
+        assert_eq!(zones.len(), 2);
+    }
+
+    #[test]
+    fn test_cluster_summary_breaks_down_by_zone() {
+        let registry = NodeRegistry::new(60);
+        registry.register(create_test_node_in_zone("node-a", "zone-a")).unwrap();
+        registry.register(create_test_node_in_zone("node-b", "zone-b")).unwrap();
+
+        let summary = registry.summary();
+        assert_eq!(summary.zones.len(), 2);
+        assert_eq!(summary.zones["zone-a"].total_nodes, 1);
+        assert_eq!(summary.zones["zone-a"].total_gpus, 1);
+    }
+
+    #[test]
+    fn test_cluster_status_reports_disk_and_liveness() {
+        let registry = NodeRegistry::new(60);
+        let mut node = create_test_node_in_zone("node-a", "zone-a");
+        node.disk = DiskInfo {
+            data_partition: PartitionInfo { available: 100, total: 1000 },
+            metadata_partition: PartitionInfo { available: 50, total: 100 },
+        };
+        registry.register(node).unwrap();
+
+        let report = registry.cluster_status();
+        assert_eq!(report.layout_version, 1);
+        assert_eq!(report.nodes.len(), 1);
+
+        let status = &report.nodes[0];
+        assert_eq!(status.node_id, "node-a");
+        assert_eq!(status.zone, "zone-a");
+        assert!(status.is_up);
+        assert!(!status.draining);
+        assert_eq!(status.data_partition.available, 100);
+        assert_eq!(status.metadata_partition.total, 100);
+    }
+
+    #[test]
+    fn test_cluster_status_marks_stale_node_down() {
+        let registry = NodeRegistry::new(10);
+        registry.register(backdated_node(100)).unwrap();
+
+        let report = registry.cluster_status();
+        assert!(!report.nodes[0].is_up);
+        assert!(report.nodes[0].last_seen_secs_ago >= 100);
+    }
+
+    fn backdated_node(seconds_ago: i64) -> Node {
+        let mut node = create_test_node();
+        node.last_heartbeat = Utc::now() - chrono::Duration::seconds(seconds_ago);
+        node
+    }
+
+    #[test]
+    fn test_reap_escalates_to_warning_after_timeout() {
+        let registry = NodeRegistry::new(10);
+        registry.register(backdated_node(15)).unwrap();
+
+        registry.reap();
+
+        assert_eq!(registry.get("node-1").unwrap().health, NodeHealth::Warning);
+    }
+
+    #[test]
+    fn test_reap_does_not_escalate_within_timeout() {
+        let registry = NodeRegistry::new(60);
+        registry.register(backdated_node(5)).unwrap();
+
+        registry.reap();
+
+        assert_eq!(registry.get("node-1").unwrap().health, NodeHealth::Healthy);
+    }
+
+    #[test]
+    fn test_reap_gives_critical_nodes_more_grace() {
+        let registry = NodeRegistry::new(10);
+        let mut node = backdated_node(15);
+        node.tags.push("critical".to_string());
+        registry.register(node).unwrap();
+
+        registry.reap();
+
+        // A plain node at the same age would already be Warning (15s > 10s);
+        // the critical tag's 3x weight keeps this one under its threshold.
+        assert_eq!(registry.get("node-1").unwrap().health, NodeHealth::Healthy);
+    }
+
+    #[test]
+    fn test_reap_deregisters_after_unreachable_grace_expires() {
+        let registry = NodeRegistry::with_unreachable_grace_seconds(10, 0);
+        registry.register(backdated_node(100)).unwrap();
+
+        let deregistered = registry.reap();
+
+        assert_eq!(deregistered, vec!["node-1".to_string()]);
+        assert!(registry.get("node-1").is_none());
+    }
+
+    #[test]
+    fn test_subscribe_health_events_receives_transition() {
+        let registry = NodeRegistry::new(10);
+        registry.register(backdated_node(15)).unwrap();
+        let mut events = registry.subscribe_health_events();
+
+        registry.reap();
+
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.node_id, "node-1");
+        assert_eq!(event.previous, NodeHealth::Healthy);
+        assert_eq!(event.current, NodeHealth::Warning);
+    }
+
+    fn test_gpu(index: usize) -> GpuDevice {
+        GpuDevice {
+            device_id: format!("cuda:{}", index),
+            vendor: GpuVendor::Nvidia,
+            device_name: "NVIDIA A100".to_string(),
+            uuid: format!("GPU-{}", index),
+            total_memory: 80 * 1024 * 1024 * 1024,
+            free_memory: 80 * 1024 * 1024 * 1024,
+            utilization: 0.0,
+            temperature: 40,
+            allocated: false,
+            allocated_job_id: None,
+            processes: vec![],
+            power_usage_mw: 0,
+            power_limit_mw: 0,
+            power_limit_max_mw: 0,
+            energy_consumed_mj: 0,
+            ecc_volatile_errors: 0,
+            ecc_aggregate_errors: 0,
+            throttle_reasons: vec![],
+        }
+    }
+
+    /// A 4-GPU node split into two NVLink islands: {0, 1} and {2, 3}
+    fn create_split_island_node() -> Node {
+        let mut nvlink_topology = HashMap::new();
+        nvlink_topology.insert("cuda:0".to_string(), vec![NvLinkPeer { peer_index: 1, link_count: 4, bandwidth_mbps: 100_000 }]);
+        nvlink_topology.insert("cuda:1".to_string(), vec![NvLinkPeer { peer_index: 0, link_count: 4, bandwidth_mbps: 100_000 }]);
+        nvlink_topology.insert("cuda:2".to_string(), vec![NvLinkPeer { peer_index: 3, link_count: 4, bandwidth_mbps: 100_000 }]);
+        nvlink_topology.insert("cuda:3".to_string(), vec![NvLinkPeer { peer_index: 2, link_count: 4, bandwidth_mbps: 100_000 }]);
+
+        let topology = NodeTopology {
+            gpus: (0..4).map(test_gpu).collect(),
+            cpu_cores: 64,
+            cpu_memory: 512 * 1024 * 1024 * 1024,
+            cpu_memory_free: 500 * 1024 * 1024 * 1024,
+            numa_nodes: 2,
+            nvlink_present: true,
+            nvswitch_present: false,
+            rdma_capable: true,
+            nvlink_topology,
+        };
+
+        Node::new("node-1".to_string(), "gpu-node-1".to_string(), "192.168.1.1".to_string(), topology)
+    }
+
+    #[test]
+    fn test_allocate_gpus_topology_aware_prefers_same_island() {
+        let mut node = create_split_island_node();
+
+        let (chosen, quality) = node.allocate_gpus_topology_aware("job-1", 2).unwrap();
+
+        assert_eq!(quality, AllocationQuality::SameIsland);
+        assert_eq!(chosen, vec!["cuda:0".to_string(), "cuda:1".to_string()]);
+    }
+
+    #[test]
+    fn test_allocate_gpus_topology_aware_crosses_islands_when_no_single_island_fits() {
+        let mut node = create_split_island_node();
+
+        let (chosen, quality) = node.allocate_gpus_topology_aware("job-1", 3).unwrap();
+
+        assert_eq!(quality, AllocationQuality::CrossIsland);
+        assert_eq!(chosen.len(), 3);
+    }
+
+    #[test]
+    fn test_allocate_gpus_topology_aware_nvswitch_is_one_island() {
+        let mut node = create_split_island_node();
+        node.topology.nvswitch_present = true;
+
+        let (chosen, quality) = node.allocate_gpus_topology_aware("job-1", 4).unwrap();
+
+        assert_eq!(quality, AllocationQuality::SameIsland);
+        assert_eq!(chosen.len(), 4);
+    }
+
+    #[test]
+    fn test_allocate_gpus_topology_aware_fragmented_when_request_spans_three_domains() {
+        let topology = NodeTopology {
+            gpus: (0..3).map(test_gpu).collect(),
+            cpu_cores: 64,
+            cpu_memory: 512 * 1024 * 1024 * 1024,
+            cpu_memory_free: 500 * 1024 * 1024 * 1024,
+            numa_nodes: 3,
+            nvlink_present: false,
+            nvswitch_present: false,
+            rdma_capable: false,
+            nvlink_topology: HashMap::new(),
+        };
+        let mut node = Node::new("node-1".to_string(), "gpu-node-1".to_string(), "192.168.1.1".to_string(), topology);
+
+        // Three singleton islands/NUMA domains; the 2-domain bound can
+        // only cover 2 of the 3 requested GPUs, so this falls all the way
+        // through to the plain fallback.
+        let (chosen, quality) = node.allocate_gpus_topology_aware("job-1", 3).unwrap();
+
+        assert_eq!(quality, AllocationQuality::Fragmented);
+        assert_eq!(chosen.len(), 3);
+    }
+
+    #[test]
+    fn test_allocate_gpus_topology_aware_errors_when_not_enough_gpus() {
+        let mut node = create_split_island_node();
+
+        let result = node.allocate_gpus_topology_aware("job-1", 10);
+
+        assert!(result.is_err());
+    }
 }
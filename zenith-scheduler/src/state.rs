@@ -4,14 +4,151 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::Write;
 use std::path::PathBuf;
-use std::fs;
+use std::fs::{self, File};
 use parking_lot::RwLock;
 
 use crate::job::{Job, JobState};
 use crate::{Error, Result};
+use chrono::{DateTime, Utc};
 
-/// State store configuration
+/// Storage backend for [`StateStore`]. The `RwLock` caches in `StateStore`
+/// are a read-through layer in front of whichever `StateBackend` is plugged
+/// in, so swapping backends changes durability/distribution characteristics
+/// without touching the query API. [`FileBackend`] (JSON snapshot + WAL) is
+/// the default; [`SledBackend`] stores each job/node under its own key for
+/// per-record writes; [`EtcdBackend`] shares state (and a cluster-wide lock)
+/// across multiple scheduler replicas for active/active HA.
+pub trait StateBackend: Send + Sync {
+    /// Loads the full job/node state on startup.
+    fn load_all(&self) -> Result<(HashMap<String, Job>, HashMap<String, NodeState>)>;
+    /// Persists an upsert of a single job.
+    fn persist_job(&self, job: &Job) -> Result<()>;
+    /// Persists the deletion of a single job.
+    fn delete_job(&self, id: &str) -> Result<()>;
+    /// Persists an upsert of a single node.
+    fn persist_node(&self, node: &NodeState) -> Result<()>;
+    /// Flushes accumulated mutations into a durable, compacted form (e.g. a
+    /// fresh snapshot with the WAL truncated, or a plain `flush` for a
+    /// backend where every mutation is already durable).
+    fn checkpoint(&self, jobs: &HashMap<String, Job>, nodes: &HashMap<String, NodeState>) -> Result<()>;
+
+    /// Persists a batch of mutations. The default applies each independently;
+    /// backends that can batch writes (a single WAL flush, a single KV
+    /// transaction) should override this to avoid one round-trip per record.
+    fn persist_batch(&self, ops: &[BatchOp]) -> Result<()> {
+        for op in ops {
+            match op {
+                BatchOp::PutJob(job) => self.persist_job(job)?,
+                BatchOp::DeleteJob(id) => self.delete_job(id)?,
+                BatchOp::PutNode(node) => self.persist_node(node)?,
+                BatchOp::DeleteNode(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Attempts to acquire a cluster-wide advisory lock on `key`, held by
+    /// `holder` for up to `ttl_secs` before it is considered abandoned.
+    /// Returns `Ok(true)` once `holder` holds the lock, `Ok(false)` if a
+    /// fail-fast implementation found it already held by someone else.
+    /// [`FileBackend`]/[`SledBackend`] fail fast against an in-process
+    /// table (sufficient for a single scheduler instance); [`EtcdBackend`]
+    /// instead blocks on etcd's native queued lock, so it only ever returns
+    /// `Ok(true)` or an error.
+    fn try_lock(&self, key: &str, holder: &str, ttl_secs: u64) -> Result<bool>;
+
+    /// Releases a lock previously acquired by `holder` via `try_lock`. A
+    /// no-op if `holder` does not currently hold `key`.
+    fn unlock(&self, key: &str, holder: &str) -> Result<()>;
+}
+
+/// One mutation appended to [`FileBackend`]'s write-ahead log. Replaying the
+/// WAL in order on top of the last checkpoint reconstructs the in-memory
+/// state without requiring a full snapshot rewrite on every mutating call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum WalRecord {
+    UpsertJob(Job),
+    DeleteJob(String),
+    UpsertNode(NodeState),
+}
+
+/// A single mutation within a [`StateStore::batch_apply`] call.
+#[derive(Debug, Clone)]
+pub enum BatchOp {
+    PutJob(Job),
+    DeleteJob(String),
+    PutNode(NodeState),
+    DeleteNode(String),
+}
+
+/// Outcome of one [`BatchOp`] within a batch.
+#[derive(Debug, Clone)]
+pub struct BatchOpResult {
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+impl BatchOpResult {
+    fn ok() -> Self {
+        Self { success: true, error: None }
+    }
+}
+
+/// Result of a [`StateStore::batch_apply`] call: one [`BatchOpResult`] per
+/// input op (in order), plus an overall persistence error if the backend
+/// flush itself failed after the in-memory mutations applied.
+#[derive(Debug, Clone)]
+pub struct BatchReport {
+    pub results: Vec<BatchOpResult>,
+    pub error: Option<String>,
+}
+
+/// Filter for [`StateStore::batch_read`]. All set fields are ANDed together;
+/// `None`/empty fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct ReadQuery {
+    pub state: Option<JobState>,
+    pub user_id: Option<String>,
+    pub project_id: Option<String>,
+    pub labels: HashMap<String, String>,
+}
+
+/// Single-process lock table shared by the bundled [`FileBackend`]/
+/// [`SledBackend`] implementations of `try_lock`/`unlock`: fine for a lone
+/// scheduler instance, but it does not coordinate across processes — that
+/// requires a network backend like [`EtcdBackend`].
+struct LocalLockTable {
+    holders: RwLock<HashMap<String, (String, DateTime<Utc>)>>,
+}
+
+impl LocalLockTable {
+    fn new() -> Self {
+        Self { holders: RwLock::new(HashMap::new()) }
+    }
+
+    fn try_lock(&self, key: &str, holder: &str, ttl_secs: u64) -> bool {
+        let now = Utc::now();
+        let mut holders = self.holders.write();
+        if let Some((current_holder, expires_at)) = holders.get(key) {
+            if current_holder != holder && *expires_at > now {
+                return false;
+            }
+        }
+        holders.insert(key.to_string(), (holder.to_string(), now + chrono::Duration::seconds(ttl_secs as i64)));
+        true
+    }
+
+    fn unlock(&self, key: &str, holder: &str) {
+        let mut holders = self.holders.write();
+        if holders.get(key).map(|(h, _)| h == holder).unwrap_or(false) {
+            holders.remove(key);
+        }
+    }
+}
+
+/// [`FileBackend`] configuration.
 #[derive(Debug, Clone)]
 pub struct StateStoreConfig {
     /// Data directory
@@ -35,140 +172,550 @@ impl Default for StateStoreConfig {
     }
 }
 
-/// Persistent state store
-pub struct StateStore {
-    config: StateStoreConfig,
-    jobs: RwLock<HashMap<String, Job>>,
-    nodes: RwLock<HashMap<String, NodeState>>,
+impl StateStoreConfig {
+    fn wal_path(&self) -> PathBuf {
+        self.data_dir.join("wal.log")
+    }
 }
 
-/// Persisted node state
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct NodeState {
-    /// Node ID
-    pub id: String,
-    /// Last heartbeat timestamp
-    pub last_heartbeat: i64,
-    /// Registration time
-    pub registered_at: i64,
-    /// Allocated jobs
-    pub allocated_jobs: Vec<String>,
+/// Default [`StateBackend`]: JSON snapshots (`jobs.json`/`nodes.json`) under
+/// `data_dir`, with a length-prefixed WAL (`wal.log`) absorbing per-record
+/// mutations between checkpoints. When `enable_wal` is false, mutations are
+/// not persisted incrementally at all; only an explicit `checkpoint()` (or
+/// `StateStore::checkpoint`) writes them to disk.
+pub struct FileBackend {
+    config: StateStoreConfig,
+    wal: Option<RwLock<File>>,
+    lock_table: LocalLockTable,
 }
 
-impl StateStore {
-    /// Create a new state store
+impl FileBackend {
     pub fn new(config: StateStoreConfig) -> Result<Self> {
-        // Create data directory if needed
         if !config.data_dir.exists() {
-            fs::create_dir_all(&config.data_dir)
-                .map_err(Error::Io)?;
+            fs::create_dir_all(&config.data_dir).map_err(Error::Io)?;
         }
-        
-        let store = Self {
-            config,
-            jobs: RwLock::new(HashMap::new()),
-            nodes: RwLock::new(HashMap::new()),
+
+        let wal = if config.enable_wal {
+            let file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(config.wal_path())
+                .map_err(Error::Io)?;
+            Some(RwLock::new(file))
+        } else {
+            None
         };
-        
-        // Load existing state
-        store.load()?;
-        
-        Ok(store)
-    }
-    
-    /// Load state from disk
-    fn load(&self) -> Result<()> {
+
+        Ok(Self { config, wal, lock_table: LocalLockTable::new() })
+    }
+
+    fn load_snapshot(&self) -> Result<(HashMap<String, Job>, HashMap<String, NodeState>)> {
+        let mut jobs = HashMap::new();
+        let mut nodes = HashMap::new();
+
         let jobs_path = self.config.data_dir.join("jobs.json");
         let nodes_path = self.config.data_dir.join("nodes.json");
-        
-        // Load jobs
+
         if jobs_path.exists() {
-            let data = fs::read_to_string(&jobs_path)
-                .map_err(Error::Io)?;
-            let jobs: HashMap<String, Job> = serde_json::from_str(&data)
-                .map_err(|e| Error::Serialization(e.to_string()))?;
-            *self.jobs.write() = jobs;
+            let data = fs::read_to_string(&jobs_path).map_err(Error::Io)?;
+            jobs = serde_json::from_str(&data).map_err(|e| Error::Serialization(e.to_string()))?;
         }
-        
-        // Load nodes
+
         if nodes_path.exists() {
-            let data = fs::read_to_string(&nodes_path)
-                .map_err(Error::Io)?;
-            let nodes: HashMap<String, NodeState> = serde_json::from_str(&data)
-                .map_err(|e| Error::Serialization(e.to_string()))?;
-            *self.nodes.write() = nodes;
+            let data = fs::read_to_string(&nodes_path).map_err(Error::Io)?;
+            nodes = serde_json::from_str(&data).map_err(|e| Error::Serialization(e.to_string()))?;
+        }
+
+        Ok((jobs, nodes))
+    }
+
+    /// Replays `wal.log` on top of `jobs`/`nodes` loaded from the last
+    /// checkpoint. A truncated or corrupt trailing record (e.g. a crash
+    /// mid-append) is tolerated: replay stops at the first record that
+    /// doesn't fully parse rather than failing the whole recovery.
+    fn replay_wal(&self, jobs: &mut HashMap<String, Job>, nodes: &mut HashMap<String, NodeState>) -> Result<()> {
+        let path = self.config.wal_path();
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let bytes = fs::read(&path).map_err(Error::Io)?;
+        let mut offset = 0usize;
+
+        while offset + 4 <= bytes.len() {
+            let len = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+            let record_start = offset + 4;
+            if record_start + len > bytes.len() {
+                break; // short trailing record from a crash mid-write
+            }
+
+            let record: WalRecord = match serde_json::from_slice(&bytes[record_start..record_start + len]) {
+                Ok(r) => r,
+                Err(_) => break, // corrupt trailing record
+            };
+
+            match record {
+                WalRecord::UpsertJob(job) => {
+                    jobs.insert(job.id.to_string(), job);
+                }
+                WalRecord::DeleteJob(id) => {
+                    jobs.remove(&id);
+                }
+                WalRecord::UpsertNode(node) => {
+                    nodes.insert(node.id.clone(), node);
+                }
+            }
+            offset = record_start + len;
         }
-        
+
         Ok(())
     }
-    
-    /// Save state to disk
-    pub fn save(&self) -> Result<()> {
+
+    /// Appends `record` to the WAL, fsyncing when `sync_writes` is set. A
+    /// no-op when WAL is disabled.
+    fn append_wal(&self, record: &WalRecord) -> Result<()> {
+        let Some(wal) = &self.wal else { return Ok(()) };
+
+        let payload = serde_json::to_vec(record).map_err(|e| Error::Serialization(e.to_string()))?;
+        let len = (payload.len() as u32).to_le_bytes();
+
+        let mut file = wal.write();
+        file.write_all(&len).map_err(Error::Io)?;
+        file.write_all(&payload).map_err(Error::Io)?;
+        if self.config.sync_writes {
+            file.sync_data().map_err(Error::Io)?;
+        }
+        Ok(())
+    }
+
+    /// Appends every record in `records` to the WAL under a single lock
+    /// acquisition, fsyncing once at the end rather than once per record.
+    fn append_wal_batch(&self, records: &[WalRecord]) -> Result<()> {
+        let Some(wal) = &self.wal else { return Ok(()) };
+
+        let mut file = wal.write();
+        for record in records {
+            let payload = serde_json::to_vec(record).map_err(|e| Error::Serialization(e.to_string()))?;
+            let len = (payload.len() as u32).to_le_bytes();
+            file.write_all(&len).map_err(Error::Io)?;
+            file.write_all(&payload).map_err(Error::Io)?;
+        }
+        if self.config.sync_writes {
+            file.sync_data().map_err(Error::Io)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a fresh full snapshot of `jobs`/`nodes` to a temp file and
+    /// renames it into place, so a crash mid-write leaves either the old or
+    /// the new snapshot intact, never a half-written one.
+    fn write_snapshot(&self, jobs: &HashMap<String, Job>, nodes: &HashMap<String, NodeState>) -> Result<()> {
         let jobs_path = self.config.data_dir.join("jobs.json");
         let nodes_path = self.config.data_dir.join("nodes.json");
-        
-        // Save jobs
-        let jobs = self.jobs.read();
-        let jobs_data = serde_json::to_string_pretty(&*jobs)
-            .map_err(|e| Error::Serialization(e.to_string()))?;
-        fs::write(&jobs_path, jobs_data)
-            .map_err(Error::Io)?;
-        
-        // Save nodes
-        let nodes = self.nodes.read();
-        let nodes_data = serde_json::to_string_pretty(&*nodes)
-            .map_err(|e| Error::Serialization(e.to_string()))?;
-        fs::write(&nodes_path, nodes_data)
-            .map_err(Error::Io)?;
-        
+
+        let jobs_data = serde_json::to_string_pretty(jobs).map_err(|e| Error::Serialization(e.to_string()))?;
+        Self::write_atomic(&jobs_path, jobs_data.as_bytes())?;
+
+        let nodes_data = serde_json::to_string_pretty(nodes).map_err(|e| Error::Serialization(e.to_string()))?;
+        Self::write_atomic(&nodes_path, nodes_data.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn write_atomic(path: &std::path::Path, data: &[u8]) -> Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, data).map_err(Error::Io)?;
+        fs::rename(&tmp_path, path).map_err(Error::Io)?;
+        Ok(())
+    }
+}
+
+impl StateBackend for FileBackend {
+    fn load_all(&self) -> Result<(HashMap<String, Job>, HashMap<String, NodeState>)> {
+        let (mut jobs, mut nodes) = self.load_snapshot()?;
+        if self.config.enable_wal {
+            self.replay_wal(&mut jobs, &mut nodes)?;
+        }
+        Ok((jobs, nodes))
+    }
+
+    fn persist_job(&self, job: &Job) -> Result<()> {
+        self.append_wal(&WalRecord::UpsertJob(job.clone()))
+    }
+
+    fn delete_job(&self, id: &str) -> Result<()> {
+        self.append_wal(&WalRecord::DeleteJob(id.to_string()))
+    }
+
+    fn persist_node(&self, node: &NodeState) -> Result<()> {
+        self.append_wal(&WalRecord::UpsertNode(node.clone()))
+    }
+
+    fn checkpoint(&self, jobs: &HashMap<String, Job>, nodes: &HashMap<String, NodeState>) -> Result<()> {
+        self.write_snapshot(jobs, nodes)?;
+
+        if let Some(wal) = &self.wal {
+            let mut file = wal.write();
+            file.set_len(0).map_err(Error::Io)?;
+            use std::io::Seek;
+            file.seek(std::io::SeekFrom::Start(0)).map_err(Error::Io)?;
+        }
+
+        Ok(())
+    }
+
+    fn persist_batch(&self, ops: &[BatchOp]) -> Result<()> {
+        if !self.config.enable_wal {
+            return Ok(());
+        }
+        let records: Vec<WalRecord> = ops
+            .iter()
+            .filter_map(|op| match op {
+                BatchOp::PutJob(job) => Some(WalRecord::UpsertJob(job.clone())),
+                BatchOp::DeleteJob(id) => Some(WalRecord::DeleteJob(id.clone())),
+                BatchOp::PutNode(node) => Some(WalRecord::UpsertNode(node.clone())),
+                BatchOp::DeleteNode(_) => None,
+            })
+            .collect();
+        self.append_wal_batch(&records)
+    }
+
+    fn try_lock(&self, key: &str, holder: &str, ttl_secs: u64) -> Result<bool> {
+        Ok(self.lock_table.try_lock(key, holder, ttl_secs))
+    }
+
+    fn unlock(&self, key: &str, holder: &str) -> Result<()> {
+        self.lock_table.unlock(key, holder);
+        Ok(())
+    }
+}
+
+/// Embedded key-value [`StateBackend`] backed by `sled`. Each job/node is
+/// stored under its own key (`job:<id>`/`node:<id>`), so a mutation is a
+/// single keyed write instead of a whole-file rewrite, and `checkpoint` is
+/// just a `flush` rather than a separate snapshot format.
+pub struct SledBackend {
+    db: sled::Db,
+    lock_table: LocalLockTable,
+}
+
+impl SledBackend {
+    pub fn open(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let db = sled::open(path).map_err(sled_err)?;
+        Ok(Self { db, lock_table: LocalLockTable::new() })
+    }
+
+    fn job_key(id: &str) -> String {
+        format!("job:{id}")
+    }
+
+    fn node_key(id: &str) -> String {
+        format!("node:{id}")
+    }
+}
+
+fn sled_err(e: sled::Error) -> Error {
+    Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
+}
+
+impl StateBackend for SledBackend {
+    fn load_all(&self) -> Result<(HashMap<String, Job>, HashMap<String, NodeState>)> {
+        let mut jobs = HashMap::new();
+        for item in self.db.scan_prefix(b"job:") {
+            let (_key, value) = item.map_err(sled_err)?;
+            let job: Job = serde_json::from_slice(&value).map_err(|e| Error::Serialization(e.to_string()))?;
+            jobs.insert(job.id.to_string(), job);
+        }
+
+        let mut nodes = HashMap::new();
+        for item in self.db.scan_prefix(b"node:") {
+            let (_key, value) = item.map_err(sled_err)?;
+            let node: NodeState = serde_json::from_slice(&value).map_err(|e| Error::Serialization(e.to_string()))?;
+            nodes.insert(node.id.clone(), node);
+        }
+
+        Ok((jobs, nodes))
+    }
+
+    fn persist_job(&self, job: &Job) -> Result<()> {
+        let value = serde_json::to_vec(job).map_err(|e| Error::Serialization(e.to_string()))?;
+        self.db.insert(Self::job_key(&job.id.to_string()), value).map_err(sled_err)?;
+        Ok(())
+    }
+
+    fn delete_job(&self, id: &str) -> Result<()> {
+        self.db.remove(Self::job_key(id)).map_err(sled_err)?;
+        Ok(())
+    }
+
+    fn persist_node(&self, node: &NodeState) -> Result<()> {
+        let value = serde_json::to_vec(node).map_err(|e| Error::Serialization(e.to_string()))?;
+        self.db.insert(Self::node_key(&node.id), value).map_err(sled_err)?;
+        Ok(())
+    }
+
+    fn checkpoint(&self, _jobs: &HashMap<String, Job>, _nodes: &HashMap<String, NodeState>) -> Result<()> {
+        // Every mutation is already a durable keyed write; checkpointing
+        // here just flushes sled's write buffer rather than rewriting a
+        // snapshot.
+        self.db.flush().map_err(sled_err)?;
+        Ok(())
+    }
+
+    fn persist_batch(&self, ops: &[BatchOp]) -> Result<()> {
+        let mut batch = sled::Batch::default();
+        for op in ops {
+            match op {
+                BatchOp::PutJob(job) => {
+                    let value = serde_json::to_vec(job).map_err(|e| Error::Serialization(e.to_string()))?;
+                    batch.insert(Self::job_key(&job.id.to_string()).into_bytes(), value);
+                }
+                BatchOp::DeleteJob(id) => batch.remove(Self::job_key(id).into_bytes()),
+                BatchOp::PutNode(node) => {
+                    let value = serde_json::to_vec(node).map_err(|e| Error::Serialization(e.to_string()))?;
+                    batch.insert(Self::node_key(&node.id).into_bytes(), value);
+                }
+                BatchOp::DeleteNode(id) => batch.remove(Self::node_key(id).into_bytes()),
+            }
+        }
+        self.db.apply_batch(batch).map_err(sled_err)
+    }
+
+    fn try_lock(&self, key: &str, holder: &str, ttl_secs: u64) -> Result<bool> {
+        Ok(self.lock_table.try_lock(key, holder, ttl_secs))
+    }
+
+    fn unlock(&self, key: &str, holder: &str) -> Result<()> {
+        self.lock_table.unlock(key, holder);
+        Ok(())
+    }
+}
+
+/// Network-distributed [`StateBackend`] over etcd: multiple scheduler
+/// replicas pointed at the same etcd cluster share one source of truth and
+/// a real cluster-wide lock (etcd's lease-backed `LockClient`), so they can
+/// run active/active without double-allocating a GPU. `etcd-client`'s API
+/// is async; every call here bridges onto a dedicated Tokio runtime so
+/// `EtcdBackend` keeps the same synchronous contract as [`FileBackend`]/
+/// [`SledBackend`].
+pub struct EtcdBackend {
+    client: etcd_client::Client,
+    runtime: tokio::runtime::Runtime,
+    /// etcd identifies a held lock by the key its `lock()` call handed back,
+    /// not by the name that was requested, so `unlock` needs this to find
+    /// what to release.
+    held_locks: RwLock<HashMap<String, Vec<u8>>>,
+}
+
+impl EtcdBackend {
+    /// Connects to an etcd cluster at `endpoints` (e.g. `["127.0.0.1:2379"]`).
+    pub fn connect(endpoints: &[impl AsRef<str>]) -> Result<Self> {
+        let runtime = tokio::runtime::Runtime::new().map_err(Error::Io)?;
+        let client = runtime
+            .block_on(etcd_client::Client::connect(endpoints, None))
+            .map_err(etcd_err)?;
+        Ok(Self { client, runtime, held_locks: RwLock::new(HashMap::new()) })
+    }
+
+    fn job_key(id: &str) -> String {
+        format!("zenith/scheduler/job/{id}")
+    }
+
+    fn node_key(id: &str) -> String {
+        format!("zenith/scheduler/node/{id}")
+    }
+
+    fn lock_name(key: &str) -> String {
+        format!("zenith/scheduler/lock/{key}")
+    }
+}
+
+fn etcd_err(e: etcd_client::Error) -> Error {
+    Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
+}
+
+impl StateBackend for EtcdBackend {
+    fn load_all(&self) -> Result<(HashMap<String, Job>, HashMap<String, NodeState>)> {
+        let mut kv = self.client.kv_client();
+
+        let job_prefix = etcd_client::GetOptions::new().with_prefix();
+        let job_resp = self.runtime.block_on(kv.get("zenith/scheduler/job/", Some(job_prefix))).map_err(etcd_err)?;
+        let mut jobs = HashMap::new();
+        for kv_pair in job_resp.kvs() {
+            let job: Job =
+                serde_json::from_slice(kv_pair.value()).map_err(|e| Error::Serialization(e.to_string()))?;
+            jobs.insert(job.id.to_string(), job);
+        }
+
+        let node_prefix = etcd_client::GetOptions::new().with_prefix();
+        let node_resp =
+            self.runtime.block_on(kv.get("zenith/scheduler/node/", Some(node_prefix))).map_err(etcd_err)?;
+        let mut nodes = HashMap::new();
+        for kv_pair in node_resp.kvs() {
+            let node: NodeState =
+                serde_json::from_slice(kv_pair.value()).map_err(|e| Error::Serialization(e.to_string()))?;
+            nodes.insert(node.id.clone(), node);
+        }
+
+        Ok((jobs, nodes))
+    }
+
+    fn persist_job(&self, job: &Job) -> Result<()> {
+        let value = serde_json::to_vec(job).map_err(|e| Error::Serialization(e.to_string()))?;
+        let mut kv = self.client.kv_client();
+        self.runtime.block_on(kv.put(Self::job_key(&job.id.to_string()), value, None)).map_err(etcd_err)?;
+        Ok(())
+    }
+
+    fn delete_job(&self, id: &str) -> Result<()> {
+        let mut kv = self.client.kv_client();
+        self.runtime.block_on(kv.delete(Self::job_key(id), None)).map_err(etcd_err)?;
+        Ok(())
+    }
+
+    fn persist_node(&self, node: &NodeState) -> Result<()> {
+        let value = serde_json::to_vec(node).map_err(|e| Error::Serialization(e.to_string()))?;
+        let mut kv = self.client.kv_client();
+        self.runtime.block_on(kv.put(Self::node_key(&node.id), value, None)).map_err(etcd_err)?;
+        Ok(())
+    }
+
+    fn checkpoint(&self, _jobs: &HashMap<String, Job>, _nodes: &HashMap<String, NodeState>) -> Result<()> {
+        // Every mutation is already durably committed to etcd; nothing to compact.
+        Ok(())
+    }
+
+    fn try_lock(&self, key: &str, holder: &str, ttl_secs: u64) -> Result<bool> {
+        let mut lease = self.client.lease_client();
+        let granted = self.runtime.block_on(lease.grant(ttl_secs.max(1) as i64, None)).map_err(etcd_err)?;
+
+        let mut lock_client = self.client.lock_client();
+        let options = etcd_client::LockOptions::new().with_lease(granted.id());
+        // etcd's lock client queues rather than failing fast, so this
+        // blocks until `holder` is next in line rather than returning
+        // `Ok(false)` the way the in-memory backends do.
+        let resp = self
+            .runtime
+            .block_on(lock_client.lock(Self::lock_name(key).into_bytes(), Some(options)))
+            .map_err(etcd_err)?;
+
+        self.held_locks.write().insert(format!("{key}:{holder}"), resp.key().to_vec());
+        Ok(true)
+    }
+
+    fn unlock(&self, key: &str, holder: &str) -> Result<()> {
+        let Some(lock_key) = self.held_locks.write().remove(&format!("{key}:{holder}")) else {
+            return Ok(());
+        };
+        let mut lock_client = self.client.lock_client();
+        self.runtime.block_on(lock_client.unlock(lock_key)).map_err(etcd_err)?;
         Ok(())
     }
-    
+}
+
+/// Liveness state of a node, driven by heartbeats and the `reap_nodes` sweep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NodeLiveness {
+    /// Heartbeats are recent; the node is assumed healthy.
+    Active,
+    /// No heartbeat for longer than `suspect_after_secs`; not yet reaped.
+    Suspected,
+    /// No heartbeat for longer than `dead_after_secs`; its jobs are drained.
+    Dead,
+}
+
+/// Persisted node state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeState {
+    /// Node ID
+    pub id: String,
+    /// Last heartbeat timestamp
+    pub last_heartbeat: i64,
+    /// Registration time
+    pub registered_at: i64,
+    /// Allocated jobs
+    pub allocated_jobs: Vec<String>,
+    /// Current liveness, defaulted to `Active` for nodes persisted before
+    /// this field existed.
+    #[serde(default = "NodeState::default_liveness")]
+    pub liveness: NodeLiveness,
+}
+
+impl NodeState {
+    fn default_liveness() -> NodeLiveness {
+        NodeLiveness::Active
+    }
+}
+
+/// Persistent state store. Generic over its [`StateBackend`] (defaulting to
+/// [`FileBackend`]); the `jobs`/`nodes` `RwLock` maps are an in-memory
+/// read-through cache in front of whatever backend is plugged in, so reads
+/// never hit the backend at all.
+pub struct StateStore<B: StateBackend = FileBackend> {
+    backend: B,
+    jobs: RwLock<HashMap<String, Job>>,
+    nodes: RwLock<HashMap<String, NodeState>>,
+    checkpoint_interval_secs: u64,
+}
+
+impl StateStore<FileBackend> {
+    /// Creates a state store backed by [`FileBackend`] (JSON + WAL).
+    pub fn new(config: StateStoreConfig) -> Result<Self> {
+        let checkpoint_interval_secs = config.checkpoint_interval_secs;
+        let backend = FileBackend::new(config)?;
+        Self::with_backend(backend, checkpoint_interval_secs)
+    }
+}
+
+impl<B: StateBackend> StateStore<B> {
+    /// Creates a state store over an arbitrary [`StateBackend`], loading its
+    /// full state immediately.
+    pub fn with_backend(backend: B, checkpoint_interval_secs: u64) -> Result<Self> {
+        let (jobs, nodes) = backend.load_all()?;
+        Ok(Self {
+            backend,
+            jobs: RwLock::new(jobs),
+            nodes: RwLock::new(nodes),
+            checkpoint_interval_secs,
+        })
+    }
+
+    /// Flushes the backend to a durable, compacted form.
+    pub fn checkpoint(&self) -> Result<()> {
+        self.backend.checkpoint(&self.jobs.read(), &self.nodes.read())
+    }
+
     /// Store a job
     pub fn store_job(&self, job: &Job) -> Result<()> {
         self.jobs.write().insert(job.id.to_string(), job.clone());
-        
-        if self.config.sync_writes {
-            self.save()?;
-        }
-        
-        Ok(())
+        self.backend.persist_job(job)
     }
-    
+
     /// Get a job
     pub fn get_job(&self, job_id: &str) -> Option<Job> {
         self.jobs.read().get(job_id).cloned()
     }
-    
+
     /// Update job state
     pub fn update_job_state(&self, job_id: &str, state: JobState, message: &str) -> Result<()> {
         let mut jobs = self.jobs.write();
-        
+
         if let Some(job) = jobs.get_mut(job_id) {
-            job.transition(state, message);
-            
-            if self.config.sync_writes {
-                drop(jobs);
-                self.save()?;
-            }
-            
-            Ok(())
+            job.transition(state, message).map_err(|e| Error::Job(e.to_string()))?;
+            let updated = job.clone();
+            drop(jobs);
+            self.backend.persist_job(&updated)
         } else {
             Err(Error::Job(format!("Job not found: {}", job_id)))
         }
     }
-    
+
     /// Delete a job
     pub fn delete_job(&self, job_id: &str) -> Result<()> {
         self.jobs.write().remove(job_id);
-        
-        if self.config.sync_writes {
-            self.save()?;
-        }
-        
-        Ok(())
+        self.backend.delete_job(job_id)
     }
-    
+
     /// List jobs by state
     pub fn list_jobs_by_state(&self, state: JobState) -> Vec<Job> {
         self.jobs.read()
@@ -177,50 +724,237 @@ impl StateStore {
             .cloned()
             .collect()
     }
-    
+
     /// List all jobs
     pub fn list_all_jobs(&self) -> Vec<Job> {
         self.jobs.read().values().cloned().collect()
     }
-    
+
     /// Store node state
     pub fn store_node(&self, node_state: NodeState) -> Result<()> {
-        self.nodes.write().insert(node_state.id.clone(), node_state);
-        
-        if self.config.sync_writes {
-            self.save()?;
-        }
-        
-        Ok(())
+        self.nodes.write().insert(node_state.id.clone(), node_state.clone());
+        self.backend.persist_node(&node_state)
     }
-    
+
     /// Get node state
     pub fn get_node(&self, node_id: &str) -> Option<NodeState> {
         self.nodes.read().get(node_id).cloned()
     }
-    
+
     /// List all nodes
     pub fn list_nodes(&self) -> Vec<NodeState> {
         self.nodes.read().values().cloned().collect()
     }
-    
+
+    /// Refreshes `last_heartbeat` for `node_id` and resets its liveness back
+    /// to `Active`, reversing any `Suspected` state a missed heartbeat window
+    /// may have caused.
+    pub fn heartbeat(&self, node_id: &str, ts: i64) -> Result<()> {
+        let node = {
+            let mut nodes = self.nodes.write();
+            let node = nodes
+                .get_mut(node_id)
+                .ok_or_else(|| Error::Job(format!("Node not found: {}", node_id)))?;
+            node.last_heartbeat = ts;
+            node.liveness = NodeLiveness::Active;
+            node.clone()
+        };
+        self.backend.persist_node(&node)
+    }
+
+    /// Sweeps all nodes, transitioning `Active` -> `Suspected` after
+    /// `suspect_after_secs` without a heartbeat, and `Suspected` -> `Dead`
+    /// after `dead_after_secs`. When a node is newly marked `Dead`, any
+    /// `Running` job in its `allocated_jobs` is transitioned back to
+    /// `Pending` so the scheduler can reassign it elsewhere. Returns the IDs
+    /// of nodes newly marked `Dead` this sweep.
+    pub fn reap_nodes(&self, suspect_after_secs: i64, dead_after_secs: i64) -> Result<Vec<String>> {
+        let now = chrono::Utc::now().timestamp();
+        let mut newly_dead = Vec::new();
+
+        let transitions: Vec<(NodeState, NodeLiveness)> = {
+            let nodes = self.nodes.read();
+            nodes
+                .values()
+                .filter_map(|node| {
+                    let age = now - node.last_heartbeat;
+                    let next = if age > dead_after_secs {
+                        NodeLiveness::Dead
+                    } else if age > suspect_after_secs {
+                        NodeLiveness::Suspected
+                    } else {
+                        NodeLiveness::Active
+                    };
+                    (next != node.liveness).then(|| (node.clone(), next))
+                })
+                .collect()
+        };
+
+        for (mut node, next) in transitions {
+            let was_dead = node.liveness == NodeLiveness::Dead;
+            node.liveness = next;
+            let became_dead = !was_dead && next == NodeLiveness::Dead;
+            let allocated_jobs = node.allocated_jobs.clone();
+
+            self.nodes.write().insert(node.id.clone(), node.clone());
+            self.backend.persist_node(&node)?;
+
+            if became_dead {
+                for job_id in &allocated_jobs {
+                    let is_running = self
+                        .jobs
+                        .read()
+                        .get(job_id)
+                        .map(|j| j.state == JobState::Running)
+                        .unwrap_or(false);
+                    if is_running {
+                        self.update_job_state(
+                            job_id,
+                            JobState::Pending,
+                            &format!("node {} declared dead, rescheduling", node.id),
+                        )?;
+                    }
+                }
+                eprintln!("[zenith-scheduler] node {} declared dead, drained {} jobs", node.id, allocated_jobs.len());
+                newly_dead.push(node.id.clone());
+            }
+        }
+
+        Ok(newly_dead)
+    }
+
+    /// Applies every op in `ops` to the in-memory caches, then flushes them
+    /// to the backend with a single [`StateBackend::persist_batch`] call
+    /// instead of one round-trip per mutation. Returns a per-op
+    /// success/failure report so a partial batch can be diagnosed.
+    pub fn batch_apply(&self, ops: Vec<BatchOp>) -> Result<BatchReport> {
+        let mut results = Vec::with_capacity(ops.len());
+
+        for op in &ops {
+            match op {
+                BatchOp::PutJob(job) => {
+                    self.jobs.write().insert(job.id.to_string(), job.clone());
+                }
+                BatchOp::DeleteJob(id) => {
+                    self.jobs.write().remove(id);
+                }
+                BatchOp::PutNode(node) => {
+                    self.nodes.write().insert(node.id.clone(), node.clone());
+                }
+                BatchOp::DeleteNode(id) => {
+                    self.nodes.write().remove(id);
+                }
+            }
+            results.push(BatchOpResult::ok());
+        }
+
+        match self.backend.persist_batch(&ops) {
+            Ok(()) => Ok(BatchReport { results, error: None }),
+            Err(e) => Ok(BatchReport { results, error: Some(e.to_string()) }),
+        }
+    }
+
+    /// Reads jobs matching every set filter in `query` (state, label
+    /// selector, `user_id`, `project_id`). An unset filter matches everything.
+    pub fn batch_read(&self, query: ReadQuery) -> Vec<Job> {
+        self.jobs
+            .read()
+            .values()
+            .filter(|job| query.state.map_or(true, |s| job.state == s))
+            .filter(|job| {
+                query
+                    .user_id
+                    .as_ref()
+                    .map_or(true, |u| &job.descriptor.user_id == u)
+            })
+            .filter(|job| {
+                query
+                    .project_id
+                    .as_ref()
+                    .map_or(true, |p| &job.descriptor.project_id == p)
+            })
+            .filter(|job| {
+                query.labels.iter().all(|(k, v)| {
+                    job.descriptor.labels.get(k).map_or(false, |actual| actual == v)
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Get job counts by state
     pub fn job_counts(&self) -> HashMap<JobState, usize> {
         let jobs = self.jobs.read();
         let mut counts = HashMap::new();
-        
+
         for job in jobs.values() {
             *counts.entry(job.state).or_insert(0) += 1;
         }
-        
+
         counts
     }
-    
+
+    /// Returns recurring job templates (`descriptor.schedule.is_some()`)
+    /// whose `next_run_at` has arrived.
+    pub fn due_jobs(&self, now: DateTime<Utc>) -> Vec<Job> {
+        self.jobs
+            .read()
+            .values()
+            .filter(|j| j.descriptor.schedule.is_some())
+            .filter(|j| j.next_run_at.is_some_and(|t| t <= now))
+            .cloned()
+            .collect()
+    }
+
+    /// Scheduler tick for recurring job templates: for each template due at
+    /// `now`, clones a fresh concrete `Job` into `Pending` and advances the
+    /// template's `next_run_at` to the next boundary after `now`. Firing is
+    /// skipped (though `next_run_at` still advances) once `max_concurrent`
+    /// instances of the template are already Pending/Queued/Scheduled/
+    /// Running. Because `next_run_at` always advances relative to `now`
+    /// rather than the missed boundary, a template that was due many times
+    /// while the process was down fires at most once per tick. Returns the
+    /// newly fired instances.
+    pub fn recurring_tick(&self, now: DateTime<Utc>) -> Result<Vec<Job>> {
+        let mut fired = Vec::new();
+
+        for mut template in self.due_jobs(now) {
+            let Some(schedule) = template.descriptor.schedule.clone() else { continue };
+            let template_id = template.id;
+
+            let active_count = self
+                .jobs
+                .read()
+                .values()
+                .filter(|j| j.template_id == Some(template_id))
+                .filter(|j| {
+                    matches!(
+                        j.state,
+                        JobState::Pending | JobState::Queued | JobState::Scheduled | JobState::Running
+                    )
+                })
+                .count() as u32;
+
+            if active_count < schedule.max_concurrent {
+                let mut instance = Job::new(template.descriptor.clone());
+                instance.descriptor.schedule = None; // instances are one-shot, not templates
+                instance.template_id = Some(template_id);
+                self.store_job(&instance)?;
+                fired.push(instance);
+            }
+
+            template.next_run_at = schedule.next_fire_after(now);
+            self.store_job(&template)?;
+        }
+
+        Ok(fired)
+    }
+
     /// Cleanup completed/failed jobs older than given seconds
     pub fn cleanup_old_jobs(&self, max_age_secs: i64) -> Result<usize> {
         let now = chrono::Utc::now().timestamp();
         let mut jobs = self.jobs.write();
-        
+
         let to_remove: Vec<String> = jobs.iter()
             .filter(|(_, job)| {
                 matches!(job.state, JobState::Completed | JobState::Failed | JobState::Cancelled) &&
@@ -228,28 +962,44 @@ impl StateStore {
             })
             .map(|(id, _)| id.clone())
             .collect();
-        
+
         let count = to_remove.len();
-        for id in to_remove {
-            jobs.remove(&id);
+        for id in &to_remove {
+            jobs.remove(id);
         }
-        
+
         drop(jobs);
-        
-        if count > 0 && self.config.sync_writes {
-            self.save()?;
+
+        if count > 0 {
+            let ops: Vec<BatchOp> = to_remove.iter().map(|id| BatchOp::DeleteJob(id.clone())).collect();
+            self.backend.persist_batch(&ops)?;
         }
-        
+
         Ok(count)
     }
 }
 
+impl<B: StateBackend + 'static> StateStore<B> {
+    /// Spawns a background thread that calls `checkpoint()` on
+    /// `checkpoint_interval_secs`, keeping the backend from accumulating
+    /// unbounded uncompacted mutations between restarts.
+    pub fn spawn_checkpoint_timer(self: std::sync::Arc<Self>) {
+        let interval = std::time::Duration::from_secs(self.checkpoint_interval_secs.max(1));
+        std::thread::spawn(move || loop {
+            std::thread::sleep(interval);
+            if let Err(e) = self.checkpoint() {
+                eprintln!("[zenith-scheduler] checkpoint failed: {}", e);
+            }
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::job::{JobDescriptor, ResourceRequirements, LocalityPreferences, SchedulingPolicy};
     use tempfile::TempDir;
-    
+
     fn create_test_job() -> Job {
         let descriptor = JobDescriptor {
             name: "test-job".to_string(),
@@ -264,63 +1014,235 @@ mod tests {
             policy: SchedulingPolicy::default(),
             labels: HashMap::new(),
             annotations: HashMap::new(),
+            schedule: None,
+            depends_on: vec![],
+            gang_group: None,
         };
-        
+
         Job::new(descriptor)
     }
-    
+
     #[test]
     fn test_state_store() {
         let temp_dir = TempDir::new().unwrap();
-        
+
         let config = StateStoreConfig {
             data_dir: temp_dir.path().to_path_buf(),
             enable_wal: false,
             sync_writes: true,
             checkpoint_interval_secs: 60,
         };
-        
+
         let store = StateStore::new(config).unwrap();
-        
+
         // Store a job
         let job = create_test_job();
         let job_id = job.id.to_string();
         store.store_job(&job).unwrap();
-        
+
         // Retrieve job
         let retrieved = store.get_job(&job_id).unwrap();
         assert_eq!(retrieved.descriptor.name, "test-job");
-        
+
         // Update state
+        store.update_job_state(&job_id, JobState::Queued, "Submitted to queue").unwrap();
+        store.update_job_state(&job_id, JobState::Scheduled, "Resources allocated").unwrap();
         store.update_job_state(&job_id, JobState::Running, "Started").unwrap();
-        
+
         let updated = store.get_job(&job_id).unwrap();
         assert_eq!(updated.state, JobState::Running);
     }
-    
+
+    #[test]
+    fn test_wal_replay_recovers_state_without_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = StateStoreConfig {
+            data_dir: temp_dir.path().to_path_buf(),
+            enable_wal: true,
+            sync_writes: true,
+            checkpoint_interval_secs: 60,
+        };
+
+        let job = create_test_job();
+        let job_id = job.id.to_string();
+
+        {
+            let store = StateStore::new(config.clone()).unwrap();
+            store.store_job(&job).unwrap();
+            store.update_job_state(&job_id, JobState::Queued, "Submitted to queue").unwrap();
+        store.update_job_state(&job_id, JobState::Scheduled, "Resources allocated").unwrap();
+        store.update_job_state(&job_id, JobState::Running, "Started").unwrap();
+            // No checkpoint() call: jobs.json is never rewritten, only the WAL.
+        }
+
+        // Reopening the store must replay the WAL to recover state.
+        let reopened = StateStore::new(config).unwrap();
+        let recovered = reopened.get_job(&job_id).unwrap();
+        assert_eq!(recovered.state, JobState::Running);
+    }
+
+    #[test]
+    fn test_wal_tolerates_truncated_trailing_record() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = StateStoreConfig {
+            data_dir: temp_dir.path().to_path_buf(),
+            enable_wal: true,
+            sync_writes: true,
+            checkpoint_interval_secs: 60,
+        };
+
+        let job = create_test_job();
+        let job_id = job.id.to_string();
+
+        {
+            let store = StateStore::new(config.clone()).unwrap();
+            store.store_job(&job).unwrap();
+        }
+
+        // Simulate a crash mid-append: truncate the WAL partway through a
+        // second record.
+        {
+            let wal_path = config.wal_path();
+            let mut bytes = fs::read(&wal_path).unwrap();
+            bytes.extend_from_slice(&[0xFF; 3]); // bogus short trailing record
+            fs::write(&wal_path, bytes).unwrap();
+        }
+
+        let reopened = StateStore::new(config).unwrap();
+        assert!(reopened.get_job(&job_id).is_some(), "replay should recover the valid leading records");
+    }
+
+    #[test]
+    fn test_batch_apply_and_read() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = StateStoreConfig {
+            data_dir: temp_dir.path().to_path_buf(),
+            sync_writes: false,
+            ..Default::default()
+        };
+        let store = StateStore::new(config).unwrap();
+
+        let mut job1 = create_test_job();
+        job1.descriptor.user_id = "alice".to_string();
+        let mut job2 = create_test_job();
+        job2.descriptor.user_id = "bob".to_string();
+
+        let report = store
+            .batch_apply(vec![BatchOp::PutJob(job1.clone()), BatchOp::PutJob(job2.clone())])
+            .unwrap();
+        assert!(report.error.is_none());
+        assert!(report.results.iter().all(|r| r.success));
+
+        let alice_jobs = store.batch_read(ReadQuery {
+            user_id: Some("alice".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(alice_jobs.len(), 1);
+        assert_eq!(alice_jobs[0].descriptor.user_id, "alice");
+    }
+
     #[test]
     fn test_job_counts() {
         let temp_dir = TempDir::new().unwrap();
-        
+
         let config = StateStoreConfig {
             data_dir: temp_dir.path().to_path_buf(),
             sync_writes: false,
             ..Default::default()
         };
-        
+
         let store = StateStore::new(config).unwrap();
-        
+
         let job1 = create_test_job();
         let job2 = create_test_job();
         let mut job3 = create_test_job();
         job3.state = JobState::Running;
-        
+
         store.store_job(&job1).unwrap();
         store.store_job(&job2).unwrap();
         store.store_job(&job3).unwrap();
-        
+
         let counts = store.job_counts();
         assert_eq!(counts.get(&JobState::Pending), Some(&2));
         assert_eq!(counts.get(&JobState::Running), Some(&1));
     }
+
+    #[test]
+    fn test_recurring_tick_fires_due_template_and_respects_max_concurrent() {
+        use crate::job::{RecurrenceSchedule, Schedule};
+
+        let temp_dir = TempDir::new().unwrap();
+        let config = StateStoreConfig {
+            data_dir: temp_dir.path().to_path_buf(),
+            sync_writes: false,
+            ..Default::default()
+        };
+        let store = StateStore::new(config).unwrap();
+
+        let now = Utc::now();
+        let mut descriptor = create_test_job().descriptor;
+        descriptor.schedule = Some(RecurrenceSchedule {
+            schedule: Schedule::Interval { period_seconds: 300, jitter_seconds: 0 },
+            max_concurrent: 1,
+        });
+        let template = crate::job::Job::new_template(descriptor, now - chrono::Duration::seconds(1));
+        let template_id = template.id;
+        store.store_job(&template).unwrap();
+
+        // Due: next_run_at was set in the past relative to `now`.
+        let fired = store.recurring_tick(now).unwrap();
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].template_id, Some(template_id));
+        assert_eq!(fired[0].state, JobState::Pending);
+        assert!(fired[0].descriptor.schedule.is_none(), "instances must not themselves be templates");
+
+        // next_run_at advanced past `now`, so it's no longer due.
+        let reloaded_template = store.get_job(&template_id.to_string()).unwrap();
+        assert!(reloaded_template.next_run_at.unwrap() > now);
+        assert_eq!(store.due_jobs(now).len(), 0);
+
+        // max_concurrent: the instance fired above is still Pending, so
+        // forcing the template due again must skip firing a second one.
+        let mut forced_due = reloaded_template.clone();
+        forced_due.next_run_at = Some(now);
+        store.store_job(&forced_due).unwrap();
+
+        let second_tick = store.recurring_tick(now).unwrap();
+        assert_eq!(second_tick.len(), 0, "max_concurrent must suppress a second concurrent firing");
+    }
+
+    #[test]
+    fn test_file_backend_lock_rejects_other_holder_until_unlocked() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = StateStoreConfig { data_dir: temp_dir.path().to_path_buf(), ..Default::default() };
+        let backend = FileBackend::new(config).unwrap();
+
+        assert!(backend.try_lock("job-queue", "scheduler-a", 30).unwrap());
+        assert!(!backend.try_lock("job-queue", "scheduler-b", 30).unwrap(), "already held by scheduler-a");
+        // Same holder re-acquiring (e.g. a lease renewal) is not contention.
+        assert!(backend.try_lock("job-queue", "scheduler-a", 30).unwrap());
+
+        backend.unlock("job-queue", "scheduler-a").unwrap();
+        assert!(backend.try_lock("job-queue", "scheduler-b", 30).unwrap(), "lock released, scheduler-b may take it");
+    }
+
+    #[test]
+    fn test_sled_backend_persists_and_reloads() {
+        let temp_dir = TempDir::new().unwrap();
+        let job = create_test_job();
+        let job_id = job.id.to_string();
+
+        {
+            let backend = SledBackend::open(temp_dir.path()).unwrap();
+            let store = StateStore::with_backend(backend, 60).unwrap();
+            store.store_job(&job).unwrap();
+        }
+
+        // Reopening over the same sled directory must see the prior write:
+        // SledBackend persists per-record, with no separate snapshot/WAL step.
+        let backend = SledBackend::open(temp_dir.path()).unwrap();
+        let reopened = StateStore::with_backend(backend, 60).unwrap();
+        let recovered = reopened.get_job(&job_id).unwrap();
+        assert_eq!(recovered.state, JobState::Pending);
+    }
 }
@@ -0,0 +1,333 @@
+//! Pluggable async job queue storage.
+//!
+//! Distinct from [`crate::state::StateStore`]: that's a durable snapshot/WAL
+//! layer fronted by synchronous methods, sized for the scheduler's own
+//! bookkeeping. [`JobStorage`] instead models the narrower push/pop/
+//! heartbeat/complete lifecycle a queue consumer like
+//! [`crate::executor::Executor`] needs, as an async trait so a backend can
+//! talk to a network queue (SQL, Redis) without blocking its caller's
+//! runtime. [`InMemoryJobStorage`] is the bundled default.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::job::{Job, JobDescriptor, JobState};
+use crate::{Error, Result};
+
+/// Outcome reported to [`JobStorage::complete`] once a popped job's work has
+/// finished, independent of how that work ran.
+#[derive(Debug, Clone)]
+pub enum JobOutcome {
+    Success { message: String },
+    Failure { message: String },
+    /// The job ran past [`crate::job::SchedulingPolicy::max_runtime_seconds`]
+    /// and was killed. Retried the same way as [`Self::Failure`] when
+    /// retries remain, but lands on [`JobState::Timeout`] rather than
+    /// [`JobState::Failed`] once they're exhausted, so callers can tell a
+    /// runaway job from one that simply exited non-zero.
+    Timeout { message: String },
+}
+
+/// Async queue storage for jobs: push new ones, hand them out to runners one
+/// at a time via `pop`, and resolve them via `complete`.
+///
+/// Implementations are expected to be cheap to `Clone` - typically an
+/// `Arc`-wrapped table, like [`InMemoryJobStorage`] - so a single instance
+/// can be shared across every task [`crate::executor::Executor`] spawns.
+/// The trait itself stays free of generic methods and a `Clone`/`Sized`
+/// supertrait so it remains object-safe behind a `dyn JobStorage`, leaving
+/// room for a SQL- or Redis-backed implementation to drop in later without
+/// the rest of the scheduler noticing.
+#[async_trait]
+pub trait JobStorage: Send + Sync {
+    /// Look up a job by ID, regardless of its current state.
+    async fn info(&self, id: Uuid) -> Option<Job>;
+
+    /// Enqueue a new job built from `descriptor`, returning its assigned ID.
+    async fn push(&self, descriptor: JobDescriptor) -> Uuid;
+
+    /// Atomically claim the next `Pending`/`Queued` job on `queue_name` for
+    /// `runner_id`: transitions it to [`JobState::Scheduled`] and stamps
+    /// `runner_id` as its owner. Returns `None` if the queue has nothing
+    /// eligible right now.
+    async fn pop(&self, queue_name: &str, runner_id: &str) -> Option<Job>;
+
+    /// Renew `runner_id`'s lease on `id`, proving it's still alive. A no-op
+    /// if `id` isn't currently owned by `runner_id` - in particular, it
+    /// never resurrects a job that was already reaped and requeued out from
+    /// under a slow heartbeat.
+    async fn heartbeat(&self, id: Uuid, runner_id: &str) -> Result<()>;
+
+    /// Transition `id` from `Scheduled` to `Running`, mirroring the local
+    /// transition a runner (e.g. [`crate::executor::Executor`]) makes on its
+    /// own copy of the job before it actually starts the work. Must be
+    /// called before `complete`, which requires the stored job to already
+    /// be `Running`.
+    async fn mark_running(&self, id: Uuid) -> Result<()>;
+
+    /// Resolve `id` with `outcome`. On [`JobOutcome::Failure`] with retries
+    /// remaining (see [`Job::can_retry`]), increments `retry_count`,
+    /// requeues the job, and returns `false`; otherwise transitions it to
+    /// its terminal state and returns `true`. The `bool` is `true` only
+    /// when the job is actually done - callers that need to know whether to
+    /// expect it to run again can branch on the return value alone.
+    async fn complete(&self, id: Uuid, outcome: JobOutcome) -> Result<bool>;
+}
+
+/// One queue's worth of state: the jobs themselves, their FIFO order, and
+/// who currently owns each claimed job.
+#[derive(Default)]
+struct State {
+    jobs: HashMap<Uuid, Job>,
+    /// Per-queue FIFO of job IDs waiting on `pop`. An ID lingers here only
+    /// until it's popped or the job moves on some other way; `pop` skips
+    /// (and drops) any entry whose job is no longer `Pending`/`Queued`.
+    queues: HashMap<String, VecDeque<Uuid>>,
+    /// `runner_id` currently holding each popped-but-not-yet-`complete`d job.
+    owners: HashMap<Uuid, String>,
+}
+
+/// Default [`JobStorage`]: an in-memory `HashMap<Uuid, Job>` with no
+/// durability across restarts, suitable until a SQL/Redis backend is wired
+/// in. Cheap to `Clone` - every clone shares the same underlying table.
+#[derive(Clone, Default)]
+pub struct InMemoryJobStorage {
+    state: Arc<Mutex<State>>,
+}
+
+impl InMemoryJobStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl JobStorage for InMemoryJobStorage {
+    async fn info(&self, id: Uuid) -> Option<Job> {
+        self.state.lock().jobs.get(&id).cloned()
+    }
+
+    async fn push(&self, descriptor: JobDescriptor) -> Uuid {
+        let queue_name = descriptor.policy.queue_name.clone();
+        let mut job = Job::new(descriptor);
+        job.transition(JobState::Queued, "Pushed to queue")
+            .expect("a freshly created job is always Pending, which can transition to Queued");
+        let id = job.id;
+
+        let mut state = self.state.lock();
+        state.jobs.insert(id, job);
+        state.queues.entry(queue_name).or_default().push_back(id);
+        id
+    }
+
+    async fn pop(&self, queue_name: &str, runner_id: &str) -> Option<Job> {
+        let mut state = self.state.lock();
+        let queue = state.queues.get_mut(queue_name)?;
+
+        while let Some(id) = queue.pop_front() {
+            let eligible = matches!(state.jobs.get(&id).map(|job| job.state), Some(JobState::Queued));
+            if !eligible {
+                continue;
+            }
+
+            let job = state.jobs.get_mut(&id).expect("checked eligible above");
+            job.transition(JobState::Scheduled, &format!("Claimed by runner '{}'", runner_id))
+                .expect("checked eligible above: only Queued jobs reach here, which can transition to Scheduled");
+            state.owners.insert(id, runner_id.to_string());
+            return state.jobs.get(&id).cloned();
+        }
+
+        None
+    }
+
+    async fn heartbeat(&self, id: Uuid, runner_id: &str) -> Result<()> {
+        let mut state = self.state.lock();
+        if state.owners.get(&id).map(String::as_str) != Some(runner_id) {
+            return Ok(());
+        }
+        if let Some(job) = state.jobs.get_mut(&id) {
+            job.last_heartbeat = Some(Utc::now());
+        }
+        Ok(())
+    }
+
+    async fn mark_running(&self, id: Uuid) -> Result<()> {
+        let mut state = self.state.lock();
+        let job = state
+            .jobs
+            .get_mut(&id)
+            .ok_or_else(|| Error::Job(format!("Job not found: {}", id)))?;
+        job.transition(JobState::Running, "Started")
+            .map_err(|e| Error::Job(e.to_string()))
+    }
+
+    async fn complete(&self, id: Uuid, outcome: JobOutcome) -> Result<bool> {
+        let mut state = self.state.lock();
+        let queue_name = {
+            let job = state
+                .jobs
+                .get(&id)
+                .ok_or_else(|| Error::Job(format!("Job not found: {}", id)))?;
+            job.descriptor.policy.queue_name.clone()
+        };
+
+        let (message, terminal_state) = match outcome {
+            JobOutcome::Success { message } => {
+                let job = state.jobs.get_mut(&id).expect("checked above");
+                job.transition(JobState::Completed, &message)
+                    .map_err(|e| Error::Job(e.to_string()))?;
+                state.owners.remove(&id);
+                return Ok(true);
+            }
+            JobOutcome::Failure { message } => (message, JobState::Failed),
+            JobOutcome::Timeout { message } => (message, JobState::Timeout),
+        };
+
+        // `Failure` and `Timeout` share the same retry logic - only the
+        // terminal state differs once retries are exhausted.
+        let can_retry = state.jobs.get(&id).expect("checked above").can_retry();
+        let job = state.jobs.get_mut(&id).expect("checked above");
+        state.owners.remove(&id);
+
+        if can_retry {
+            job.retry_count += 1;
+            job.transition(JobState::Queued, &message)
+                .map_err(|e| Error::Job(e.to_string()))?;
+            state.queues.entry(queue_name).or_default().push_back(id);
+            Ok(false)
+        } else {
+            job.transition(terminal_state, &message)
+                .map_err(|e| Error::Job(e.to_string()))?;
+            Ok(true)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::{LocalityPreferences, ResourceRequirements, SchedulingPolicy};
+    use std::collections::HashMap as Map;
+
+    fn test_descriptor(queue_name: &str, max_retries: u32) -> JobDescriptor {
+        JobDescriptor {
+            name: "test-job".to_string(),
+            user_id: "user1".to_string(),
+            project_id: "project1".to_string(),
+            command: "python".to_string(),
+            arguments: vec!["train.py".to_string()],
+            environment: Map::new(),
+            working_directory: "/app".to_string(),
+            resources: ResourceRequirements::default(),
+            locality: LocalityPreferences::default(),
+            policy: SchedulingPolicy { queue_name: queue_name.to_string(), max_retries, ..SchedulingPolicy::default() },
+            labels: Map::new(),
+            annotations: Map::new(),
+            schedule: None,
+            depends_on: vec![],
+            gang_group: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_push_then_pop_claims_job_for_runner() {
+        let storage = InMemoryJobStorage::new();
+        let id = storage.push(test_descriptor("default", 3)).await;
+
+        let job = storage.pop("default", "runner-1").await.expect("job should be available");
+        assert_eq!(job.id, id);
+        assert_eq!(job.state, JobState::Scheduled);
+
+        // Already claimed: a second pop on the same queue finds nothing.
+        assert!(storage.pop("default", "runner-2").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pop_on_empty_or_unknown_queue_returns_none() {
+        let storage = InMemoryJobStorage::new();
+        assert!(storage.pop("nope", "runner-1").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_complete_failure_with_retries_requeues_and_returns_false() {
+        let storage = InMemoryJobStorage::new();
+        let id = storage.push(test_descriptor("default", 1)).await;
+        storage.pop("default", "runner-1").await.unwrap();
+
+        let requeued = storage
+            .complete(id, JobOutcome::Failure { message: "boom".to_string() })
+            .await
+            .unwrap();
+        assert!(!requeued);
+        assert_eq!(storage.info(id).await.unwrap().state, JobState::Queued);
+        assert_eq!(storage.info(id).await.unwrap().retry_count, 1);
+
+        // Requeued job is poppable again.
+        let job = storage.pop("default", "runner-2").await.expect("requeued job should be poppable");
+        assert_eq!(job.id, id);
+    }
+
+    #[tokio::test]
+    async fn test_complete_failure_out_of_retries_is_terminal() {
+        let storage = InMemoryJobStorage::new();
+        let id = storage.push(test_descriptor("default", 0)).await;
+        storage.pop("default", "runner-1").await.unwrap();
+
+        let done = storage
+            .complete(id, JobOutcome::Failure { message: "boom".to_string() })
+            .await
+            .unwrap();
+        assert!(done);
+        assert_eq!(storage.info(id).await.unwrap().state, JobState::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_complete_timeout_out_of_retries_lands_on_timeout_state() {
+        let storage = InMemoryJobStorage::new();
+        let id = storage.push(test_descriptor("default", 0)).await;
+        storage.pop("default", "runner-1").await.unwrap();
+
+        let done = storage
+            .complete(id, JobOutcome::Timeout { message: "exceeded max runtime".to_string() })
+            .await
+            .unwrap();
+        assert!(done);
+        assert_eq!(storage.info(id).await.unwrap().state, JobState::Timeout);
+    }
+
+    #[tokio::test]
+    async fn test_complete_success_is_terminal() {
+        let storage = InMemoryJobStorage::new();
+        let id = storage.push(test_descriptor("default", 3)).await;
+        storage.pop("default", "runner-1").await.unwrap();
+
+        let done = storage.complete(id, JobOutcome::Success { message: "ok".to_string() }).await.unwrap();
+        assert!(done);
+        assert_eq!(storage.info(id).await.unwrap().state, JobState::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_complete_unknown_job_errors() {
+        let storage = InMemoryJobStorage::new();
+        let result = storage.complete(Uuid::new_v4(), JobOutcome::Success { message: "ok".to_string() }).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_ignored_for_non_owner() {
+        let storage = InMemoryJobStorage::new();
+        let id = storage.push(test_descriptor("default", 3)).await;
+        storage.pop("default", "runner-1").await.unwrap();
+
+        // Wrong runner: no error, but no effect either.
+        storage.heartbeat(id, "runner-2").await.unwrap();
+        assert!(storage.info(id).await.unwrap().last_heartbeat.is_none());
+
+        storage.heartbeat(id, "runner-1").await.unwrap();
+        assert!(storage.info(id).await.unwrap().last_heartbeat.is_some());
+    }
+}
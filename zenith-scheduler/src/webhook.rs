@@ -0,0 +1,252 @@
+//! Webhook notifications for job state transitions.
+//!
+//! Lets external systems learn when a job changes state
+//! (`Queued`→`Running`→`Completed`/`Failed`/`Cancelled`) instead of polling
+//! [`crate::scheduler::Scheduler::get_job`]. Subscriptions are held in a
+//! [`WebhookRegistry`]; [`WebhookRegistry::notify`] spawns one delivery
+//! task per matching subscription so a slow or unreachable consumer can
+//! never block scheduling.
+
+use crate::job::JobState;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Delivery attempts per notification before giving up on that single event.
+const MAX_DELIVERY_ATTEMPTS: u32 = 5;
+/// Consecutive hard failures (across separate notifications) before a
+/// subscription is dropped entirely.
+const MAX_CONSECUTIVE_FAILURES: u32 = 10;
+/// Delay before the first retry; doubles on each subsequent attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+
+/// What a subscription receives notifications for. `All` matches every job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookScope {
+    All,
+    Job(String),
+    User(String),
+    Project(String),
+}
+
+impl WebhookScope {
+    fn matches(&self, job_id: &str, user_id: &str, project_id: &str) -> bool {
+        match self {
+            WebhookScope::All => true,
+            WebhookScope::Job(id) => id == job_id,
+            WebhookScope::User(id) => id == user_id,
+            WebhookScope::Project(id) => id == project_id,
+        }
+    }
+}
+
+/// A registered callback URL plus its delivery health.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookSubscription {
+    pub id: String,
+    pub url: String,
+    pub scope: WebhookScope,
+    /// Failures since the last successful delivery; reset on success, and
+    /// the subscription is dropped once this reaches
+    /// `MAX_CONSECUTIVE_FAILURES`.
+    pub consecutive_failures: u32,
+}
+
+/// Body POSTed to a matching subscription's URL on every job state transition.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    pub job_id: String,
+    pub old_state: JobState,
+    pub new_state: JobState,
+    pub timestamp: DateTime<Utc>,
+    pub allocated_nodes: Vec<String>,
+}
+
+/// Registered webhook subscriptions plus the HTTP client used to deliver
+/// notifications. Cheap to clone — subscriptions are shared via `Arc`.
+#[derive(Clone)]
+pub struct WebhookRegistry {
+    subscriptions: Arc<RwLock<HashMap<String, WebhookSubscription>>>,
+    client: reqwest::Client,
+}
+
+impl WebhookRegistry {
+    pub fn new() -> Self {
+        Self {
+            subscriptions: Arc::new(RwLock::new(HashMap::new())),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Register a new subscription, returning the id used to remove it later.
+    pub fn register(&self, url: String, scope: WebhookScope) -> String {
+        let id = Uuid::new_v4().to_string();
+        self.subscriptions.write().insert(
+            id.clone(),
+            WebhookSubscription {
+                id: id.clone(),
+                url,
+                scope,
+                consecutive_failures: 0,
+            },
+        );
+        id
+    }
+
+    /// Remove a subscription by id. Returns `false` if it didn't exist.
+    pub fn unregister(&self, id: &str) -> bool {
+        self.subscriptions.write().remove(id).is_some()
+    }
+
+    /// Currently registered subscriptions, for inspection/testing.
+    pub fn list(&self) -> Vec<WebhookSubscription> {
+        self.subscriptions.read().values().cloned().collect()
+    }
+
+    /// Fire-and-forget delivery of `payload` to every subscription scoped to
+    /// `job_id`/`user_id`/`project_id`. Spawns one background task per
+    /// matching subscription so the caller (a job's state-transition path)
+    /// never blocks on network I/O.
+    pub fn notify(&self, job_id: &str, user_id: &str, project_id: &str, payload: WebhookPayload) {
+        let matching: Vec<WebhookSubscription> = self
+            .subscriptions
+            .read()
+            .values()
+            .filter(|s| s.scope.matches(job_id, user_id, project_id))
+            .cloned()
+            .collect();
+
+        for subscription in matching {
+            let registry = self.clone();
+            let payload = payload.clone();
+            tokio::spawn(async move {
+                registry.deliver(subscription, payload).await;
+            });
+        }
+    }
+
+    /// POSTs `payload` to `subscription.url`, retrying transport/non-2xx
+    /// failures with exponential backoff up to `MAX_DELIVERY_ATTEMPTS`
+    /// times before counting it as one hard failure for that subscription.
+    async fn deliver(&self, subscription: WebhookSubscription, payload: WebhookPayload) {
+        let mut backoff = INITIAL_BACKOFF;
+
+        for attempt in 1..=MAX_DELIVERY_ATTEMPTS {
+            match self.client.post(&subscription.url).json(&payload).send().await {
+                Ok(response) if response.status().is_success() => {
+                    self.record_success(&subscription.id);
+                    return;
+                }
+                _ if attempt < MAX_DELIVERY_ATTEMPTS => {
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                _ => {}
+            }
+        }
+
+        self.record_failure(&subscription.id);
+    }
+
+    fn record_success(&self, id: &str) {
+        if let Some(subscription) = self.subscriptions.write().get_mut(id) {
+            subscription.consecutive_failures = 0;
+        }
+    }
+
+    /// Bumps the failure count and drops the subscription once it reaches
+    /// `MAX_CONSECUTIVE_FAILURES`, so a permanently dead endpoint doesn't
+    /// accumulate delivery attempts forever.
+    fn record_failure(&self, id: &str) {
+        let mut subscriptions = self.subscriptions.write();
+        if let Some(subscription) = subscriptions.get_mut(id) {
+            subscription.consecutive_failures += 1;
+            if subscription.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                subscriptions.remove(id);
+            }
+        }
+    }
+}
+
+impl Default for WebhookRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_and_unregister() {
+        let registry = WebhookRegistry::new();
+        let id = registry.register("http://example.com/hook".to_string(), WebhookScope::All);
+
+        assert_eq!(registry.list().len(), 1);
+        assert!(registry.unregister(&id));
+        assert!(registry.list().is_empty());
+    }
+
+    #[test]
+    fn test_unregister_unknown_id_returns_false() {
+        let registry = WebhookRegistry::new();
+        assert!(!registry.unregister("no-such-id"));
+    }
+
+    #[test]
+    fn test_scope_matching() {
+        assert!(WebhookScope::All.matches("job-1", "user-1", "proj-1"));
+        assert!(WebhookScope::Job("job-1".to_string()).matches("job-1", "user-1", "proj-1"));
+        assert!(!WebhookScope::Job("job-2".to_string()).matches("job-1", "user-1", "proj-1"));
+        assert!(WebhookScope::User("user-1".to_string()).matches("job-1", "user-1", "proj-1"));
+        assert!(WebhookScope::Project("proj-1".to_string()).matches("job-1", "user-1", "proj-1"));
+    }
+
+    #[test]
+    fn test_record_failure_drops_subscription_after_max_consecutive_failures() {
+        let registry = WebhookRegistry::new();
+        let id = registry.register("http://example.com/hook".to_string(), WebhookScope::All);
+
+        for _ in 0..MAX_CONSECUTIVE_FAILURES {
+            registry.record_failure(&id);
+        }
+
+        assert!(registry.list().is_empty());
+    }
+
+    #[test]
+    fn test_record_success_resets_failure_count() {
+        let registry = WebhookRegistry::new();
+        let id = registry.register("http://example.com/hook".to_string(), WebhookScope::All);
+
+        registry.record_failure(&id);
+        registry.record_success(&id);
+
+        assert_eq!(registry.list()[0].consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_notify_skips_non_matching_subscriptions() {
+        let registry = WebhookRegistry::new();
+        registry.register("http://example.com/hook".to_string(), WebhookScope::Job("other-job".to_string()));
+
+        // No subscription matches "job-1", so this must not spawn any delivery task.
+        registry.notify(
+            "job-1",
+            "user-1",
+            "proj-1",
+            WebhookPayload {
+                job_id: "job-1".to_string(),
+                old_state: JobState::Queued,
+                new_state: JobState::Running,
+                timestamp: Utc::now(),
+                allocated_nodes: vec![],
+            },
+        );
+    }
+}